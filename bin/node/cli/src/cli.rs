@@ -28,6 +28,13 @@ pub struct Cli {
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Index the events emitted by contracts in finalized blocks.
+	///
+	/// The index is kept in the database and serves the `contracts_getEvents` RPC.
+	/// Only blocks finalized after the index was first enabled are covered.
+	#[structopt(long)]
+	pub contracts_event_index: bool,
 }
 
 /// Possible subcommands of the main binary.
@@ -73,12 +80,27 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export a snapshot of the state of a given block.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Import a snapshot of the state and start the chain from its block.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
+	/// Export the signing history of the local authorities.
+	ExportSlashingProtection(sc_cli::ExportSlashingProtectionCmd),
+
+	/// Import the signing history of the authorities of another node.
+	ImportSlashingProtection(sc_cli::ImportSlashingProtectionCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
+
+	/// Database utilities.
+	Db(sc_cli::DbSubcommand),
 }
@@ -22,7 +22,7 @@
 
 use std::sync::Arc;
 use sc_consensus_babe;
-use node_primitives::Block;
+use node_primitives::{AccountId, Balance, Block, Hash};
 use node_runtime::RuntimeApi;
 use sc_service::{
 	config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager,
@@ -71,13 +71,20 @@ pub fn new_partial(
 		})
 		.transpose()?;
 
-	let (client, backend, keystore_container, task_manager) =
+	let (client, backend, mut keystore_container, task_manager) =
 		sc_service::new_full_parts::<Block, RuntimeApi, Executor>(
 			&config,
 			telemetry.as_ref().map(|(_, telemetry)| telemetry.handle()),
 		)?;
 	let client = Arc::new(client);
 
+	if let Some(uri) = &config.keystore_remote {
+		let keystore = sc_keystore::RemoteKeystore::open(uri).map_err(|e| ServiceError::Other(
+			format!("Error hooking up remote keystore for {}: {}", uri, e)
+		))?;
+		keystore_container.set_remote_keystore(Arc::new(keystore));
+	}
+
 	let telemetry = telemetry
 		.map(|(worker, telemetry)| {
 			task_manager.spawn_handle().spawn("telemetry", worker.run());
@@ -232,14 +239,12 @@ pub fn new_full_base(
 	config.network.extra_sets.push(grandpa::grandpa_peers_set_config());
 
 	#[cfg(feature = "cli")]
-	config.network.request_response_protocols.push(
-		sc_finality_grandpa_warp_sync::request_response_config_for_chain(
-			&config,
-			task_manager.spawn_handle(),
-			backend.clone(),
-			import_setup.1.shared_authority_set().clone(),
-		)
-	);
+	let warp_sync = Some(Arc::new(sc_finality_grandpa_warp_sync::NetworkProvider::new(
+		backend.clone(),
+		import_setup.1.shared_authority_set().clone(),
+	)) as Arc<dyn sc_network::warp_request_handler::WarpSyncProvider<Block>>);
+	#[cfg(not(feature = "cli"))]
+	let warp_sync = None;
 
 	let (network, system_rpc_tx, network_starter) =
 		sc_service::build_network(sc_service::BuildNetworkParams {
@@ -250,6 +255,7 @@ pub fn new_full_base(
 			import_queue,
 			on_demand: None,
 			block_announce_validator_builder: None,
+			warp_sync,
 		})?;
 
 	if config.offchain_worker.enabled {
@@ -419,10 +425,22 @@ pub fn new_full_base(
 }
 
 /// Builds a new service for a full client.
+///
+/// With `contracts_event_index` set the client additionally maintains an index of the events
+/// emitted by contracts.
 pub fn new_full(
 	config: Configuration,
+	contracts_event_index: bool,
 ) -> Result<TaskManager, ServiceError> {
-	new_full_base(config, |_, _| ()).map(|NewFullBase { task_manager, .. }| {
+	new_full_base(config, |_, _| ()).map(|NewFullBase { task_manager, client, .. }| {
+		if contracts_event_index {
+			task_manager.spawn_handle().spawn(
+				"contracts-event-index",
+				pallet_contracts_rpc::index_contract_events::<
+					_, _, AccountId, Balance, Hash,
+				>(client),
+			);
+		}
 		task_manager
 	})
 }
@@ -526,6 +544,7 @@ pub fn new_light_base(
 			import_queue,
 			on_demand: Some(on_demand.clone()),
 			block_announce_validator_builder: None,
+			warp_sync: None,
 		})?;
 
 	let enable_grandpa = !config.disable_grandpa;
@@ -19,7 +19,7 @@
 //! Structs to easily compose inspect sub-command for CLI.
 
 use std::fmt::Debug;
-use sc_cli::{ImportParams, SharedParams};
+use sc_cli::{BlockNumberOrHash, ImportParams, SharedParams};
 use structopt::StructOpt;
 
 /// The `inspect` command used to print decoded chain data.
@@ -61,4 +61,12 @@ pub enum InspectSubCmd {
 		#[structopt(value_name = "BLOCK:INDEX or BYTES")]
 		input: String,
 	},
+	/// Print the number of keys and bytes used by each storage item, including child tries.
+	StorageUsage {
+		/// Block hash or number of the inspected state.
+		///
+		/// Default is the best block.
+		#[structopt(value_name = "HASH or NUMBER")]
+		input: Option<BlockNumberOrHash>,
+	},
 }
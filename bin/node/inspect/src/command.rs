@@ -21,9 +21,12 @@
 use crate::cli::{InspectCmd, InspectSubCmd};
 use crate::Inspector;
 use sc_cli::{CliConfiguration, ImportParams, Result, SharedParams};
-use sc_service::{new_full_client, Configuration, NativeExecutionDispatch};
-use sp_runtime::traits::Block;
-use std::str::FromStr;
+use sc_rpc::state::StorageUsage;
+use sc_service::{new_full_client, Configuration, NativeExecutionDispatch, TFullClient};
+use sp_api::{ConstructRuntimeApi, Metadata};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block, Header};
+use std::{fmt::Debug, str::FromStr};
 
 impl InspectCmd {
 	/// Run the inspect command, passing the inspector.
@@ -31,29 +34,61 @@ impl InspectCmd {
 	where
 		B: Block,
 		B::Hash: FromStr,
-		RA: Send + Sync + 'static,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as Header>::Number as FromStr>::Err: Debug,
+		RA: ConstructRuntimeApi<B, TFullClient<B, RA, EX>> + Send + Sync + 'static,
+		RA::RuntimeApi: Metadata<B>,
 		EX: NativeExecutionDispatch + 'static,
 	{
 		let client = new_full_client::<B, RA, EX>(&config, None)?;
-		let inspect = Inspector::<B>::new(client);
 
 		match &self.command {
 			InspectSubCmd::Block { input } => {
 				let input = input.parse()?;
-				let res = inspect.block(input).map_err(|e| format!("{}", e))?;
+				let res = Inspector::<B>::new(client).block(input).map_err(|e| format!("{}", e))?;
 				println!("{}", res);
 				Ok(())
 			}
 			InspectSubCmd::Extrinsic { input } => {
 				let input = input.parse()?;
-				let res = inspect.extrinsic(input).map_err(|e| format!("{}", e))?;
+				let res = Inspector::<B>::new(client).extrinsic(input).map_err(|e| format!("{}", e))?;
 				println!("{}", res);
 				Ok(())
 			}
+			InspectSubCmd::StorageUsage { input } => {
+				let block = match input {
+					Some(input) => client.expect_block_hash_from_id(&input.parse()?)?,
+					None => client.info().best_hash,
+				};
+				let usage = sc_rpc::state::storage_usage(&client, block)?;
+				print_storage_usage(&usage);
+				Ok(())
+			}
 		}
 	}
 }
 
+fn print_storage_usage<Hash: Debug>(usage: &StorageUsage<Hash>) {
+	println!("Storage usage at block {:?}:", usage.at);
+	println!("{:<24} {:<32} {:>12} {:>16}", "PALLET", "ITEM", "KEYS", "BYTES");
+	for item in &usage.items {
+		println!(
+			"{:<24} {:<32} {:>12} {:>16}",
+			item.pallet.as_deref().unwrap_or("-"),
+			item.item.as_deref().unwrap_or("-"),
+			item.usage.keys,
+			item.usage.bytes,
+		);
+	}
+	println!(
+		"{:<57} {:>12} {:>16}",
+		format!("{} child tries", usage.child_tries),
+		usage.child_tries_usage.keys,
+		usage.child_tries_usage.bytes,
+	);
+	println!("{:<57} {:>12} {:>16}", "Total", usage.total.keys, usage.total.bytes);
+}
+
 impl CliConfiguration for InspectCmd {
 	fn shared_params(&self) -> &SharedParams {
 		&self.shared_params
@@ -0,0 +1,83 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signer serving the requests of remote keystores with the keys of a local keystore.
+//!
+//! A node uses it when started with
+//! `--keystore-uri <address>?secret-file=<path>`, the secret file containing the same secret.
+
+use std::{fs, path::PathBuf, sync::Arc};
+
+use sc_keystore::{
+	remote::{RemoteSigner, RemoteSignerAddress, RemoteSignerSecret},
+	LocalKeystore,
+};
+use sp_core::crypto::SecretString;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+	name = "remote-signer",
+	author = "Parity Team <admin@parity.io>",
+	about = "Signer serving the requests of remote keystores with the keys of a local keystore",
+)]
+struct Opt {
+	/// Address to listen on, either `unix://<path>` or `tcp://<host>:<port>`.
+	#[structopt(long, value_name = "ADDRESS")]
+	listen: RemoteSignerAddress,
+
+	/// File containing the hex encoded secret shared with the nodes, 32 to 64 bytes long.
+	///
+	/// It can be generated with `openssl rand -hex 32`.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	secret_file: PathBuf,
+
+	/// Path of the keystore holding the keys.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	keystore_path: PathBuf,
+
+	/// File that contains the password used by the keystore.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	password_filename: Option<PathBuf>,
+}
+
+fn main() -> Result<(), String> {
+	env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+	let opt = Opt::from_args();
+	let listen = opt.listen;
+	let secret = RemoteSignerSecret::from_file(&opt.secret_file).map_err(|e| e.to_string())?;
+	let password = opt.password_filename
+		.map(|file| fs::read_to_string(file).map(SecretString::new))
+		.transpose()
+		.map_err(|e| e.to_string())?;
+	let keystore = LocalKeystore::open(opt.keystore_path, password).map_err(|e| e.to_string())?;
+
+	// A socket left behind by a previous signer prevents listening on the same path.
+	#[cfg(unix)]
+	if let RemoteSignerAddress::Unix(path) = &listen {
+		if path.exists() && std::os::unix::net::UnixStream::connect(path).is_err() {
+			fs::remove_file(path).map_err(|e| e.to_string())?;
+		}
+	}
+
+	log::info!("Listening on {}", listen);
+	RemoteSigner::new(Arc::new(keystore), secret)
+		.serve(&listen)
+		.map_err(|e| format!("Failed to listen on {}: {}", listen, e))
+}
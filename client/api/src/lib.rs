@@ -40,7 +40,7 @@ pub use notifications::*;
 pub use proof_provider::*;
 pub use sp_blockchain::HeaderBackend;
 
-pub use sp_state_machine::{StorageProof, ExecutionStrategy};
+pub use sp_state_machine::{StorageProof, KeyValueStates, KeyValueStorageLevel, ExecutionStrategy};
 
 /// Usage Information Provider interface
 ///
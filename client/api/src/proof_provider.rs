@@ -23,6 +23,7 @@ use sp_runtime::{
 };
 use crate::{StorageProof, ChangesProof};
 use sp_storage::{ChildInfo, StorageKey, PrefixedStorageKey};
+use sp_state_machine::KeyValueStates;
 
 /// Interface for providing block proving utilities.
 pub trait ProofProvider<Block: BlockT> {
@@ -70,4 +71,36 @@ pub trait ProofProvider<Block: BlockT> {
 		storage_key: Option<&PrefixedStorageKey>,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<ChangesProof<Block::Header>>;
+
+	/// Given a `BlockId` iterate over all storage values starting at `start_key` exclusively,
+	/// including the content of default child tries, building a proof until the size limit
+	/// is reached. Returns the proof and the number of collected key-value pairs.
+	///
+	/// `start_key` is either empty, the last top trie key already read, or the prefixed key
+	/// of a default child trie followed by the last key already read from it.
+	fn read_proof_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[Vec<u8>],
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)>;
+
+	/// Given a `BlockId` iterate over all storage values starting at `start_key` exclusively,
+	/// including the content of default child tries, until the size limit is reached.
+	/// Returns the collected keys and values.
+	fn storage_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[Vec<u8>],
+		size_limit: usize,
+	) -> sp_blockchain::Result<KeyValueStates>;
+
+	/// Verify a proof built by `read_proof_collection` against the given state root.
+	/// Returns the proven keys and values.
+	fn verify_range_proof(
+		&self,
+		root: Block::Hash,
+		proof: StorageProof,
+		start_key: &[Vec<u8>],
+	) -> sp_blockchain::Result<KeyValueStates>;
 }
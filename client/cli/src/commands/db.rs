@@ -0,0 +1,59 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database related CLI utilities
+
+use crate::{error, params::{PruningParams, SharedParams}, CliConfiguration};
+use sc_service::{DatabaseConfig, PruningMode};
+use sp_runtime::traits::Block as BlockT;
+use structopt::StructOpt;
+
+use super::migrate_db_cmd::MigrateDbCmd;
+
+/// Database utilities for the cli.
+#[derive(Debug, StructOpt)]
+pub enum DbSubcommand {
+	/// Migrate the RocksDb database of the node to ParityDb.
+	Migrate(MigrateDbCmd),
+}
+
+impl DbSubcommand {
+	/// run the db subcommands
+	pub fn run<B: BlockT>(
+		&self,
+		database_config: DatabaseConfig,
+		state_pruning: PruningMode,
+	) -> error::Result<()> {
+		match self {
+			DbSubcommand::Migrate(cmd) => cmd.run::<B>(database_config, state_pruning),
+		}
+	}
+}
+
+impl CliConfiguration for DbSubcommand {
+	fn shared_params(&self) -> &SharedParams {
+		match self {
+			DbSubcommand::Migrate(cmd) => cmd.shared_params(),
+		}
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		match self {
+			DbSubcommand::Migrate(cmd) => cmd.pruning_params(),
+		}
+	}
+}
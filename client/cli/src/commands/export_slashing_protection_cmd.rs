@@ -0,0 +1,72 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `export-slashing-protection` subcommand
+use crate::{
+	CliConfiguration, error, params::{DatabaseParams, SharedParams},
+};
+use sc_client_api::backend::AuxStore;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{fs, io, path::PathBuf, sync::Arc};
+use structopt::StructOpt;
+
+/// The `export-slashing-protection` command used to export the signing history of the local
+/// authorities, to move it to another node.
+#[derive(Debug, StructOpt, Clone)]
+pub struct ExportSlashingProtectionCmd {
+	/// Output file name or stdout if unspecified.
+	#[structopt(parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportSlashingProtectionCmd {
+	/// Run the `export-slashing-protection` command
+	pub async fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		C: AuxStore + HeaderBackend<B>,
+	{
+		let genesis_hash = client.info().genesis_hash;
+		let history = sc_slashing_protection::export(&*client, genesis_hash.as_ref())?;
+
+		let file: Box<dyn io::Write> = match &self.output {
+			Some(filename) => Box::new(io::BufWriter::new(fs::File::create(filename)?)),
+			None => Box::new(io::stdout()),
+		};
+		serde_json::to_writer_pretty(file, &history)
+			.map_err(|e| sc_slashing_protection::Error::from(e).into())
+	}
+}
+
+impl CliConfiguration for ExportSlashingProtectionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
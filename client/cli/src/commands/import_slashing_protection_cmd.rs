@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `import-slashing-protection` subcommand
+use crate::{
+	CliConfiguration, error, params::{DatabaseParams, SharedParams},
+};
+use sc_client_api::backend::AuxStore;
+use sc_slashing_protection::ExportedHistory;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{fs, io, path::PathBuf, sync::Arc};
+use structopt::StructOpt;
+
+/// The `import-slashing-protection` command used to import the signing history exported from
+/// another node.
+///
+/// The imported history is merged with the history of the node, so that the node refuses to sign
+/// anything conflicting with what either node signed.
+#[derive(Debug, StructOpt, Clone)]
+pub struct ImportSlashingProtectionCmd {
+	/// Input file or stdin if unspecified.
+	#[structopt(parse(from_os_str))]
+	pub input: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ImportSlashingProtectionCmd {
+	/// Run the `import-slashing-protection` command
+	pub async fn run<B, C>(&self, client: Arc<C>) -> error::Result<()>
+	where
+		B: BlockT,
+		C: AuxStore + HeaderBackend<B>,
+	{
+		let file: Box<dyn io::Read> = match &self.input {
+			Some(filename) => Box::new(io::BufReader::new(fs::File::open(filename)?)),
+			None => Box::new(io::stdin()),
+		};
+		let history: ExportedHistory = serde_json::from_reader(file)
+			.map_err(sc_slashing_protection::Error::from)?;
+
+		let genesis_hash = client.info().genesis_hash;
+		sc_slashing_protection::import(&*client, history, genesis_hash.as_ref()).map_err(Into::into)
+	}
+}
+
+impl CliConfiguration for ImportSlashingProtectionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -22,6 +22,7 @@ use structopt::StructOpt;
 
 use super::{
 	insert_key::InsertKeyCmd,
+	migrate_keystore_cmd::MigrateKeystoreCmd,
 	inspect_key::InspectKeyCmd,
 	generate::GenerateCmd,
 	inspect_node_key::InspectNodeKeyCmd,
@@ -46,6 +47,9 @@ pub enum KeySubcommand {
 
 	/// Insert a key to the keystore of a node.
 	Insert(InsertKeyCmd),
+
+	/// Encrypt the keys of the keystore of a node stored in plain text.
+	MigrateKeystore(MigrateKeystoreCmd),
 }
 
 impl KeySubcommand {
@@ -57,6 +61,7 @@ impl KeySubcommand {
 			KeySubcommand::Inspect(cmd) => cmd.run(),
 			KeySubcommand::Insert(cmd) => cmd.run(cli),
 			KeySubcommand::InspectNodeKey(cmd) => cmd.run(),
+			KeySubcommand::MigrateKeystore(cmd) => cmd.run(cli),
 		}
 	}
 }
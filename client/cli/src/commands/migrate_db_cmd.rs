@@ -0,0 +1,88 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `db migrate` subcommand
+use crate::error;
+use crate::params::{PruningParams, SharedParams};
+use crate::CliConfiguration;
+use sc_service::{chain_ops::migrate_database, DatabaseConfig, PruningMode};
+use sp_runtime::traits::Block as BlockT;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Name of the ParityDb database directory, next to the RocksDb one.
+const PARITY_DB_DIR: &str = "paritydb";
+
+/// The `db migrate` command used to copy the RocksDb database of the node to ParityDb.
+///
+/// The node must be stopped during the migration. The RocksDb database is left untouched, and
+/// an interrupted migration is resumed when running the command again. The pruning mode must be
+/// the one the node runs with.
+#[derive(Debug, StructOpt, Clone)]
+pub struct MigrateDbCmd {
+	/// Path of the ParityDb database to create.
+	///
+	/// Defaults to the path used by the node when started with `--database paritydb`.
+	#[structopt(long, value_name = "PATH", parse(from_os_str))]
+	pub target: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+}
+
+impl MigrateDbCmd {
+	/// Run the migrate command
+	pub fn run<B: BlockT>(
+		&self,
+		database_config: DatabaseConfig,
+		state_pruning: PruningMode,
+	) -> error::Result<()> {
+		let target = match (&self.target, &database_config) {
+			(Some(path), _) => path.clone(),
+			(None, DatabaseConfig::RocksDb { path, .. }) => path
+				.parent()
+				.map(|parent| parent.join(PARITY_DB_DIR))
+				.ok_or_else(|| error::Error::Input("Invalid database path".into()))?,
+			(None, _) => return Err(error::Error::Input(
+				"Only RocksDb databases can be migrated".into()
+			)),
+		};
+
+		migrate_database::<B>(
+			&database_config,
+			&DatabaseConfig::ParityDb { path: target },
+			state_pruning,
+		)?;
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for MigrateDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+}
@@ -0,0 +1,65 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `migrate-keystore` subcommand
+
+use crate::{Error, KeystoreParams, SharedParams, SubstrateCli};
+use structopt::StructOpt;
+use sc_keystore::LocalKeystore;
+use sc_service::config::{KeystoreConfig, BasePath};
+
+/// The `migrate-keystore` command
+#[derive(Debug, StructOpt, Clone)]
+#[structopt(
+	name = "migrate-keystore",
+	about = "Encrypt the keys of the keystore of a node stored in plain text."
+)]
+pub struct MigrateKeystoreCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl MigrateKeystoreCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let base_path = self.shared_params
+			.base_path()
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		let (path, password) = match self.keystore_params.keystore_config(&config_dir)? {
+			(_, KeystoreConfig::Path { path, password }) => (path, password),
+			_ => unreachable!("keystore_config always returns path and password; qed")
+		};
+		if password.is_none() {
+			return Err(Error::Input("A password is required to encrypt the keystore".into()))
+		}
+
+		let encrypted = LocalKeystore::open(&path, password)?.encrypt_plain_keys()?;
+		println!("Encrypted {} key(s) in {}", encrypted, path.display());
+
+		Ok(())
+	}
+}
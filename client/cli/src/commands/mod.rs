@@ -17,9 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 mod build_spec_cmd;
 mod check_block_cmd;
+mod db;
 mod export_blocks_cmd;
+mod export_slashing_protection_cmd;
+mod export_snapshot_cmd;
 mod export_state_cmd;
 mod import_blocks_cmd;
+mod import_slashing_protection_cmd;
+mod import_snapshot_cmd;
 mod purge_chain_cmd;
 mod sign;
 mod verify;
@@ -32,14 +37,21 @@ mod insert_key;
 mod inspect_node_key;
 mod inspect_key;
 mod key;
+mod migrate_db_cmd;
+mod migrate_keystore_cmd;
 pub mod utils;
 
 pub use self::{
 	build_spec_cmd::BuildSpecCmd,
 	check_block_cmd::CheckBlockCmd,
+	db::DbSubcommand,
 	export_blocks_cmd::ExportBlocksCmd,
+	export_slashing_protection_cmd::ExportSlashingProtectionCmd,
+	export_snapshot_cmd::ExportSnapshotCmd,
 	export_state_cmd::ExportStateCmd,
 	import_blocks_cmd::ImportBlocksCmd,
+	import_slashing_protection_cmd::ImportSlashingProtectionCmd,
+	import_snapshot_cmd::ImportSnapshotCmd,
 	purge_chain_cmd::PurgeChainCmd,
 	sign::SignCmd,
 	generate::GenerateCmd,
@@ -48,6 +60,8 @@ pub use self::{
 	generate_node_key::GenerateNodeKeyCmd,
 	inspect_node_key::InspectNodeKeyCmd,
 	key::KeySubcommand,
+	migrate_db_cmd::MigrateDbCmd,
+	migrate_keystore_cmd::MigrateKeystoreCmd,
 	vanity::VanityCmd,
 	verify::VerifyCmd,
 	revert_cmd::RevertCmd,
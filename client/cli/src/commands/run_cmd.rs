@@ -27,11 +27,12 @@ use crate::params::TransactionPoolParams;
 use crate::CliConfiguration;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{BasePath, PrometheusConfig, TransactionPoolOptions, TransactionPoolJournalOptions},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 /// The `run` command used to run a node.
@@ -431,6 +432,13 @@ impl CliConfiguration for RunCmd {
 		Ok(self.pool_config.transaction_pool())
 	}
 
+	fn transaction_pool_journal(
+		&self,
+		config_dir: &PathBuf,
+	) -> Result<Option<TransactionPoolJournalOptions>> {
+		Ok(self.pool_config.transaction_pool_journal(config_dir))
+	}
+
 	fn max_runtime_instances(&self) -> Result<Option<usize>> {
 		Ok(self.max_runtime_instances.map(|x| x.min(256)))
 	}
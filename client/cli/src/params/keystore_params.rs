@@ -30,6 +30,10 @@ const DEFAULT_KEYSTORE_CONFIG_PATH: &'static str = "keystore";
 #[derive(Debug, StructOpt, Clone)]
 pub struct KeystoreParams {
 	/// Specify custom URIs to connect to for keystore-services
+	///
+	/// A remote signer is specified as `unix://<path>?secret-file=<path>` or
+	/// `tcp://<host>:<port>?secret-file=<path>`, the file containing the hex encoded secret
+	/// shared with the signer.
 	#[structopt(long = "keystore-uri")]
 	pub keystore_uri: Option<String>,
 
@@ -40,7 +44,7 @@ pub struct KeystoreParams {
 	/// Use interactive shell for entering the password used by the keystore.
 	#[structopt(
 		long = "password-interactive",
-		conflicts_with_all = &[ "password", "password-filename" ]
+		conflicts_with_all = &[ "password", "password-filename", "password-env" ]
 	)]
 	pub password_interactive: bool,
 
@@ -48,7 +52,7 @@ pub struct KeystoreParams {
 	#[structopt(
 		long = "password",
 		parse(try_from_str = secret_string_from_str),
-		conflicts_with_all = &[ "password-interactive", "password-filename" ]
+		conflicts_with_all = &[ "password-interactive", "password-filename", "password-env" ]
 	)]
 	pub password: Option<SecretString>,
 
@@ -57,9 +61,17 @@ pub struct KeystoreParams {
 		long = "password-filename",
 		value_name = "PATH",
 		parse(from_os_str),
-		conflicts_with_all = &[ "password-interactive", "password" ]
+		conflicts_with_all = &[ "password-interactive", "password", "password-env" ]
 	)]
 	pub password_filename: Option<PathBuf>,
+
+	/// Environment variable that contains the password used by the keystore.
+	#[structopt(
+		long = "password-env",
+		value_name = "VAR",
+		conflicts_with_all = &[ "password-interactive", "password", "password-filename" ]
+	)]
+	pub password_env: Option<String>,
 }
 
 /// Parse a sercret string, returning a displayable error.
@@ -84,6 +96,10 @@ impl KeystoreParams {
 			let password = fs::read_to_string(file)
 				.map_err(|e| format!("{}", e))?;
 			Some(SecretString::new(password))
+		} else if let Some(ref var) = self.password_env {
+			let password = std::env::var(var)
+				.map_err(|e| format!("Could not read keystore password from {}: {}", var, e))?;
+			Some(SecretString::new(password))
 		} else {
 			self.password.clone()
 		};
@@ -116,3 +132,33 @@ fn input_keystore_password() -> Result<String> {
 	rpassword::read_password_from_tty(Some("Keystore password: "))
 		.map_err(|e| format!("{:?}", e).into())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::crypto::ExposeSecret;
+
+	fn password(args: &[&str]) -> Option<String> {
+		let params = KeystoreParams::from_iter(std::iter::once("test").chain(args.iter().copied()));
+		match params.keystore_config(Path::new("/tmp")).unwrap() {
+			(_, KeystoreConfig::Path { password, .. }) => password.map(|p| p.expose_secret().clone()),
+			_ => unreachable!(),
+		}
+	}
+
+	#[test]
+	fn password_is_read_from_env() {
+		std::env::set_var("KEYSTORE_PARAMS_TEST_PASSWORD", "secret");
+		assert_eq!(
+			password(&["--password-env", "KEYSTORE_PARAMS_TEST_PASSWORD"]).as_deref(),
+			Some("secret"),
+		);
+		assert_eq!(password(&[]), None);
+		assert!(KeystoreParams::from_iter_safe(
+			&["test", "--password-env", "KEYSTORE_PARAMS_TEST_PASSWORD", "--password", "secret"],
+		).is_err());
+
+		let params = KeystoreParams::from_iter(&["test", "--password-env", "KEYSTORE_PARAMS_UNSET"]);
+		assert!(params.keystore_config(Path::new("/tmp")).is_err());
+	}
+}
@@ -28,11 +28,20 @@ pub struct PruningParams {
 	/// Default is to keep all block states if the node is running as a
 	/// validator (i.e. 'archive'), otherwise state is only kept for the last
 	/// 256 blocks.
-	#[structopt(long = "pruning", value_name = "PRUNING_MODE")]
+	#[structopt(long = "pruning", alias = "state-pruning", value_name = "PRUNING_MODE")]
 	pub pruning: Option<String>,
-	/// Specify the number of finalized blocks to keep in the database.
+	/// Specify the block pruning mode, independently from the state pruning mode.
+	///
+	/// This is either 'archive' to keep all blocks, 'archive-canonical' to keep all finalized
+	/// blocks, or a number of finalized blocks to keep. Block bodies and justifications of the
+	/// other blocks are removed from the database, headers are always kept.
 	///
 	/// Default is to keep all blocks.
+	#[structopt(long, value_name = "PRUNING_MODE", conflicts_with = "keep-blocks")]
+	pub blocks_pruning: Option<String>,
+	/// Specify the number of finalized blocks to keep in the database.
+	///
+	/// Same as `--blocks-pruning COUNT`. Default is to keep all blocks.
 	#[structopt(long, value_name = "COUNT")]
 	pub keep_blocks: Option<u32>,
 }
@@ -66,9 +75,45 @@ impl PruningParams {
 
 	/// Get the block pruning value from the parameters
 	pub fn keep_blocks(&self) -> error::Result<KeepBlocks> {
-		Ok(match self.keep_blocks {
-			Some(n) => KeepBlocks::Some(n),
-			None => KeepBlocks::All,
+		Ok(match (&self.blocks_pruning, self.keep_blocks) {
+			(Some(ref s), _) if s == "archive" => KeepBlocks::All,
+			(Some(ref s), _) if s == "archive-canonical" => KeepBlocks::Finalized,
+			(Some(s), _) => KeepBlocks::Some(s.parse().map_err(|_| {
+				error::Error::Input("Invalid blocks pruning mode specified".to_string())
+			})?),
+			(None, Some(n)) => KeepBlocks::Some(n),
+			(None, None) => KeepBlocks::All,
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn keep_blocks(args: &[&str]) -> error::Result<KeepBlocks> {
+		PruningParams::from_iter_safe(std::iter::once("test").chain(args.iter().copied()))
+			.unwrap()
+			.keep_blocks()
+	}
+
+	#[test]
+	fn blocks_pruning_is_parsed() {
+		assert_eq!(keep_blocks(&[]).unwrap(), KeepBlocks::All);
+		assert_eq!(keep_blocks(&["--blocks-pruning", "archive"]).unwrap(), KeepBlocks::All);
+		assert_eq!(
+			keep_blocks(&["--blocks-pruning", "archive-canonical"]).unwrap(),
+			KeepBlocks::Finalized,
+		);
+		assert_eq!(keep_blocks(&["--blocks-pruning", "42"]).unwrap(), KeepBlocks::Some(42));
+		assert_eq!(keep_blocks(&["--keep-blocks", "42"]).unwrap(), KeepBlocks::Some(42));
+		assert!(keep_blocks(&["--blocks-pruning", "all"]).is_err());
+	}
+
+	#[test]
+	fn blocks_pruning_conflicts_with_keep_blocks() {
+		assert!(PruningParams::from_iter_safe(
+			&["test", "--blocks-pruning", "42", "--keep-blocks", "42"],
+		).is_err());
+	}
+}
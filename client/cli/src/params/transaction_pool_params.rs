@@ -16,9 +16,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use sc_service::config::TransactionPoolOptions;
+use sc_service::config::{
+	TransactionPoolOptions, TransactionPoolJournalOptions, TransactionPoolSenderLimit,
+};
+use std::{path::PathBuf, time::Duration};
 use structopt::StructOpt;
 
+/// Name of the transaction pool journal file in the configuration directory.
+const DEFAULT_JOURNAL_FILE: &str = "txpool_journal";
+
 /// Parameters used to create the pool configuration.
 #[derive(Debug, StructOpt, Clone)]
 pub struct TransactionPoolParams {
@@ -29,6 +35,28 @@ pub struct TransactionPoolParams {
 	/// Maximum number of kilobytes of all transactions stored in the pool.
 	#[structopt(long = "pool-kbytes", value_name = "COUNT", default_value = "20480")]
 	pub pool_kbytes: usize,
+
+	/// Maximum number of transactions of a single sender in the transaction pool.
+	///
+	/// Transactions are not limited per sender if not given.
+	#[structopt(long = "pool-sender-limit", value_name = "COUNT")]
+	pub pool_sender_limit: Option<usize>,
+
+	/// Length in bytes of the prefix of a transaction `provides` tag identifying its sender.
+	#[structopt(long = "pool-sender-tag-prefix", value_name = "BYTES", default_value = "32")]
+	pub pool_sender_tag_prefix: usize,
+
+	/// Persist pending transactions on disk and re-submit them after a restart of the node.
+	#[structopt(long = "pool-journal")]
+	pub pool_journal: bool,
+
+	/// Maximum age in seconds of the journaled transactions re-submitted after a restart.
+	#[structopt(long = "pool-journal-max-age", value_name = "SECONDS", default_value = "3600")]
+	pub pool_journal_max_age: u64,
+
+	/// Maximum number of kilobytes of transactions kept in the journal.
+	#[structopt(long = "pool-journal-kbytes", value_name = "COUNT", default_value = "20480")]
+	pub pool_journal_kbytes: usize,
 }
 
 impl TransactionPoolParams {
@@ -45,6 +73,25 @@ impl TransactionPoolParams {
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
+		// per-sender limit
+		opts.sender = self.pool_sender_limit.map(|count| TransactionPoolSenderLimit {
+			tag_prefix_len: self.pool_sender_tag_prefix,
+			count,
+		});
+
 		opts
 	}
+
+	/// Get the transaction pool journal options, if journaling is enabled.
+	pub fn transaction_pool_journal(&self, config_dir: &PathBuf) -> Option<TransactionPoolJournalOptions> {
+		if !self.pool_journal {
+			return None
+		}
+
+		Some(TransactionPoolJournalOptions {
+			path: config_dir.join(DEFAULT_JOURNAL_FILE),
+			max_age: Duration::from_secs(self.pool_journal_max_age),
+			total_bytes: self.pool_journal_kbytes * 1024,
+		})
+	}
 }
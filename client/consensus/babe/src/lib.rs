@@ -176,6 +176,19 @@ impl EpochT for Epoch {
 	}
 }
 
+impl From<sp_consensus_babe::Epoch> for Epoch {
+	fn from(epoch: sp_consensus_babe::Epoch) -> Self {
+		Epoch {
+			epoch_index: epoch.epoch_index,
+			start_slot: epoch.start_slot,
+			duration: epoch.duration,
+			authorities: epoch.authorities,
+			randomness: epoch.randomness,
+			config: epoch.config,
+		}
+	}
+}
+
 impl Epoch {
 	/// Create the genesis epoch (epoch #0). This is defined to start at the slot of
 	/// the first block, so that has to be provided.
@@ -440,6 +453,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CIDP, BS, CAW, L, Error>(BabeParams {
 		+ BlockchainEvents<B>
 		+ HeaderBackend<B>
 		+ HeaderMetadata<B, Error = ClientError>
+		+ AuxStore
 		+ Send
 		+ Sync
 		+ 'static,
@@ -637,7 +651,11 @@ where
 	C: ProvideRuntimeApi<B> +
 		ProvideCache<B> +
 		HeaderBackend<B> +
-		HeaderMetadata<B, Error = ClientError>,
+		HeaderMetadata<B, Error = ClientError> +
+		AuxStore +
+		Send +
+		Sync +
+		'static,
 	C::Api: BabeApi<B>,
 	E: Environment<B, Error = Error>,
 	E::Proposer: Proposer<B, Error = Error, Transaction = sp_api::TransactionFor<C, B>>,
@@ -754,11 +772,19 @@ where
 		sp_consensus::Error> + Send + 'static>
 	{
 		let keystore = self.keystore.clone();
-		Box::new(move |header, header_hash, body, storage_changes, (_, public), epoch_descriptor| {
+		let client = self.client.clone();
+		Box::new(move |header, header_hash, body, storage_changes, (pre_digest, public), epoch_descriptor| {
 			// sign the pre-sealed hash of the block and then
 			// add it to a digest item.
 			let public_type_pair = public.clone().into();
 			let public = public.to_raw_vec();
+			sc_slashing_protection::check_and_record_slot(
+				&*client,
+				BABE_ENGINE_ID,
+				&public,
+				*pre_digest.slot(),
+				header_hash.as_ref(),
+			).map_err(|e| sp_consensus::Error::CannotSign(public.clone(), e.to_string()))?;
 			let signature = SyncCryptoStore::sign_with(
 				&*keystore,
 				<AuthorityId as AppKey>::ID,
@@ -1296,11 +1322,20 @@ impl<Block, Client, Inner> BlockImport<Block> for BabeBlockImport<Block, Client,
 		// early exit if block already in chain, otherwise the check for
 		// epoch changes will error when trying to re-import an epoch change
 		match self.client.status(BlockId::Hash(hash)) {
+			Ok(sp_blockchain::BlockStatus::InChain) if block.state.is_some() => {
+				// the block is re-imported together with its state, epoch changes
+				// have already been processed when it was first imported.
+				return self.inner.import_block(block, new_cache).await.map_err(Into::into)
+			},
 			Ok(sp_blockchain::BlockStatus::InChain) => return Ok(ImportResult::AlreadyInChain),
 			Ok(sp_blockchain::BlockStatus::Unknown) => {},
 			Err(e) => return Err(ConsensusError::ClientImport(e.to_string())),
 		}
 
+		if block.state.is_some() {
+			return self.import_state(block, new_cache).await
+		}
+
 		let pre_digest = find_pre_digest::<Block>(&block.header)
 			.expect("valid babe headers must contain a predigest; \
 					 header has been already verified; qed");
@@ -1533,6 +1568,61 @@ impl<Block, Client, Inner> BlockImport<Block> for BabeBlockImport<Block, Client,
 	}
 }
 
+impl<Block, Client, Inner> BabeBlockImport<Block, Client, Inner> where
+	Block: BlockT,
+	Inner: BlockImport<Block, Transaction = sp_api::TransactionFor<Client, Block>> + Send + Sync,
+	Inner::Error: Into<ConsensusError>,
+	Client: HeaderBackend<Block> + HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ AuxStore + ProvideRuntimeApi<Block> + ProvideCache<Block> + Send + Sync,
+	Client::Api: BabeApi<Block> + ApiExt<Block>,
+{
+	/// Import a block together with its state, without its ancestry (e.g. the
+	/// target block of a warp sync).
+	///
+	/// The epoch changes can't be derived from the parent chain, so they are reset
+	/// to the current and next epochs read from the imported state.
+	async fn import_state(
+		&mut self,
+		mut block: BlockImportParams<Block, sp_api::TransactionFor<Client, Block>>,
+		new_cache: HashMap<CacheKeyId, Vec<u8>>,
+	) -> Result<ImportResult, ConsensusError> {
+		let hash = block.post_hash();
+		let parent_hash = *block.header.parent_hash();
+		let number = *block.header.number();
+
+		block.fork_choice = Some(ForkChoiceStrategy::Custom(true));
+		// chain weight is counted from the imported block onwards.
+		aux_schema::write_block_weight(
+			hash,
+			0,
+			|values| block.auxiliary.extend(
+				values.iter().map(|(k, v)| (k.to_vec(), Some(v.to_vec())))
+			),
+		);
+
+		let aux = match self.inner.import_block(block, new_cache).await {
+			Ok(ImportResult::Imported(aux)) => aux,
+			Ok(r) => return Ok(r),
+			Err(e) => return Err(e.into()),
+		};
+
+		let block_id = BlockId::hash(hash);
+		let current_epoch = self.client.runtime_api().current_epoch(&block_id)
+			.map_err(|e| ConsensusError::ClientImport(babe_err::<Block>(Error::RuntimeApi(e)).into()))?;
+		let next_epoch = self.client.runtime_api().next_epoch(&block_id)
+			.map_err(|e| ConsensusError::ClientImport(babe_err::<Block>(Error::RuntimeApi(e)).into()))?;
+
+		let mut epoch_changes = self.epoch_changes.shared_data_locked();
+		epoch_changes.reset(parent_hash, hash, number, current_epoch.into(), next_epoch.into());
+		aux_schema::write_epoch_changes::<Block, _, _>(
+			&*epoch_changes,
+			|insert| self.client.insert_aux(insert, []),
+		).map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+
+		Ok(ImportResult::Imported(aux))
+	}
+}
+
 /// Gets the best finalized block and its slot, and prunes the given epoch tree.
 fn prune_finalized<Block, Client>(
 	client: Arc<Client>,
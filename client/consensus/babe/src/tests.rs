@@ -896,3 +896,111 @@ fn babe_transcript_generation_match() {
 	};
 	debug_assert!(test(orig_transcript) == test(transcript_from_data(new_transcript)));
 }
+
+/// Storage key of a `pallet_babe` storage value in the test runtime.
+fn babe_storage_key(item: &[u8]) -> Vec<u8> {
+	[sp_core::hashing::twox_128(b"Babe"), sp_core::hashing::twox_128(item)].concat()
+}
+
+#[test]
+fn importing_state_resets_epoch_changes() {
+	use sc_client_api::StorageProvider;
+	use sp_keyring::Sr25519Keyring;
+	use substrate_test_runtime_client::{
+		ClientBlockImportExt, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	let mut net = BabeTestNet::new(1);
+
+	let peer = net.peer(0);
+	let data = peer.data.as_ref().expect("babe link set up during initialization");
+	let client = peer.client().as_full().expect("Only full clients are used in tests").clone();
+	let mut block_import = data.block_import.lock().take().expect("import set up during init");
+
+	// a chain in its fifth epoch, as it would be found by a warp sync
+	let authorities = |key: Sr25519Keyring| vec![(AuthorityId::from(key.public()), 1u64)];
+	let epoch_config = BabeEpochConfiguration {
+		c: (1, 4),
+		allowed_slots: AllowedSlots::PrimaryAndSecondaryPlainSlots,
+	};
+	let mut source = TestClientBuilder::new()
+		.add_extra_storage(babe_storage_key(b"EpochIndex"), 4u64.encode())
+		.add_extra_storage(babe_storage_key(b"GenesisSlot"), Slot::from(100).encode())
+		.add_extra_storage(
+			babe_storage_key(b"Authorities"),
+			authorities(Sr25519Keyring::Alice).encode(),
+		)
+		.add_extra_storage(
+			babe_storage_key(b"NextAuthorities"),
+			authorities(Sr25519Keyring::Bob).encode(),
+		)
+		.add_extra_storage(babe_storage_key(b"Randomness"), [1u8; 32].encode())
+		.add_extra_storage(babe_storage_key(b"NextRandomness"), [2u8; 32].encode())
+		.add_extra_storage(babe_storage_key(b"EpochConfig"), epoch_config.encode())
+		.build();
+
+	let mut block = None;
+	for _ in 0..3 {
+		let new_block = source.new_block(Default::default()).unwrap().build().unwrap().block;
+		block_on(source.import(BlockOrigin::Own, new_block.clone())).unwrap();
+		block = Some(new_block);
+	}
+	let block = block.unwrap();
+	let hash = block.hash();
+
+	let key_values = source
+		.storage_pairs(&BlockId::Hash(hash), &sp_core::storage::StorageKey(Vec::new()))
+		.unwrap()
+		.into_iter()
+		.map(|(key, value)| (key.0, value.0))
+		.collect();
+	let mut import = BlockImportParams::new(BlockOrigin::NetworkInitialSync, block.header);
+	import.body = Some(block.extrinsics);
+	import.state = Some(sp_consensus::ImportedState {
+		block: hash,
+		state: sc_client_api::KeyValueStates(vec![sc_client_api::KeyValueStorageLevel {
+			storage_key: Vec::new(),
+			key_values,
+			complete: true,
+		}]),
+	});
+
+	assert!(matches!(
+		block_on(block_import.import_block(import, Default::default())),
+		Ok(ImportResult::Imported(_)),
+	));
+
+	let at = BlockId::Hash(hash);
+	let current_epoch: Epoch = source.runtime_api().current_epoch(&at).unwrap().into();
+	let next_epoch: Epoch = source.runtime_api().next_epoch(&at).unwrap().into();
+	assert_eq!(current_epoch.epoch_index, 4);
+	assert_eq!(current_epoch.start_slot, Slot::from(124));
+	assert_eq!(current_epoch.authorities, authorities(Sr25519Keyring::Alice));
+	assert_eq!(next_epoch.start_slot, Slot::from(130));
+	assert_eq!(next_epoch.authorities, authorities(Sr25519Keyring::Bob));
+
+	// children of the imported block use the epoch covering their slot
+	let epoch_for_child = |epoch_changes: &EpochChangesFor<TestBlock, Epoch>, slot: u64| {
+		epoch_changes.epoch_data_for_child_of(
+			descendent_query(&*client),
+			&hash,
+			3,
+			slot.into(),
+			|slot| Epoch::genesis(&data.link.config, slot),
+		).unwrap().unwrap()
+	};
+
+	{
+		let epoch_changes = data.link.epoch_changes.shared_data();
+		assert_eq!(epoch_for_child(&epoch_changes, 125), current_epoch);
+		assert_eq!(epoch_for_child(&epoch_changes, 131), next_epoch);
+	}
+
+	// the reset epoch changes are persisted
+	let epoch_changes = aux_schema::load_epoch_changes::<TestBlock, _>(&*client, &data.link.config)
+		.unwrap();
+	let epoch_changes = epoch_changes.shared_data();
+	assert_eq!(epoch_for_child(&epoch_changes, 125), current_epoch);
+	assert_eq!(epoch_for_child(&epoch_changes, 131), next_epoch);
+	assert_eq!(aux_schema::load_block_weight(&*client, hash).unwrap(), Some(0));
+}
@@ -20,7 +20,7 @@
 
 pub mod migration;
 
-use std::{ops::Add, collections::BTreeMap, borrow::{Borrow, BorrowMut}};
+use std::{ops::{Add, Sub}, collections::BTreeMap, borrow::{Borrow, BorrowMut}};
 use codec::{Encode, Decode};
 use fork_tree::ForkTree;
 use sc_client_api::utils::is_descendent_of;
@@ -323,7 +323,7 @@ impl<Hash, Number, E: Epoch> Default for EpochChanges<Hash, Number, E> where
 
 impl<Hash, Number, E: Epoch> EpochChanges<Hash, Number, E> where
 	Hash: PartialEq + Ord + AsRef<[u8]> + AsMut<[u8]> + Copy,
-	Number: Ord + One + Zero + Add<Output=Number> + Copy,
+	Number: Ord + One + Zero + Add<Output=Number> + Sub<Output=Number> + Copy,
 {
 	/// Create a new epoch change.
 	pub fn new() -> Self {
@@ -637,6 +637,37 @@ impl<Hash, Number, E: Epoch> EpochChanges<Hash, Number, E> where
 		}
 	}
 
+	/// Reset to the given pair of epochs, as if they were announced at blocks
+	/// `parent_hash` and `hash` respectively.
+	///
+	/// Used when a block is imported together with its state, without its
+	/// ancestry, and the epoch changes can't be derived from the chain.
+	pub fn reset(&mut self, parent_hash: Hash, hash: Hash, number: Number, current: E, next: E) {
+		self.inner = ForkTree::new();
+		self.epochs.clear();
+
+		let parent_number = number - One::one();
+		let persisted = PersistedEpoch::Regular(current);
+		let header = PersistedEpochHeader::from(&persisted);
+		let _res = self.inner.import(
+			parent_hash,
+			parent_number,
+			header,
+			&|_, _| Ok(false) as Result<bool, ClientError>,
+		);
+		self.epochs.insert((parent_hash, parent_number), persisted);
+
+		let persisted = PersistedEpoch::Regular(next);
+		let header = PersistedEpochHeader::from(&persisted);
+		let _res = self.inner.import(
+			hash,
+			number,
+			header,
+			&|_, _| Ok(true) as Result<bool, ClientError>,
+		);
+		self.epochs.insert((hash, number), persisted);
+	}
+
 	/// Return the inner fork tree.
 	pub fn tree(&self) -> &ForkTree<Hash, Number, PersistedEpochHeader<E>> {
 		&self.inner
@@ -153,6 +153,9 @@ impl<Block: BlockT> DbChangesTrieStorage<Block> {
 	}
 
 	/// Commit new changes trie.
+	///
+	/// A `detached` block has no known ancestry, e.g. the target block of a warp sync. When it
+	/// is finalized, the configuration cache starts anew from this block.
 	pub fn commit(
 		&self,
 		tx: &mut Transaction<DbHash>,
@@ -161,6 +164,7 @@ impl<Block: BlockT> DbChangesTrieStorage<Block> {
 		block: ComplexBlockId<Block>,
 		new_header: &Block::Header,
 		finalized: bool,
+		detached: bool,
 		new_configuration: Option<Option<ChangesTrieConfiguration>>,
 		cache_tx: Option<DbChangesTrieStorageTransaction<Block>>,
 	) -> ClientResult<DbChangesTrieStorageTransaction<Block>> {
@@ -184,6 +188,11 @@ impl<Block: BlockT> DbChangesTrieStorage<Block> {
 		};
 
 		// update configuration cache
+		let entry_type = match (finalized, detached) {
+			(false, _) => CacheEntryType::NonFinal,
+			(true, false) => CacheEntryType::Final,
+			(true, true) => CacheEntryType::Genesis,
+		};
 		let mut cache_at = HashMap::new();
 		cache_at.insert(well_known_cache_keys::CHANGES_TRIE_CONFIG, new_configuration.encode());
 		Ok(DbChangesTrieStorageTransaction::from(match cache_tx {
@@ -193,7 +202,7 @@ impl<Block: BlockT> DbChangesTrieStorage<Block> {
 					parent_block,
 					block,
 					cache_at,
-					if finalized { CacheEntryType::Final } else { CacheEntryType::NonFinal },
+					entry_type,
 				)?
 				.into_ops(),
 			None => self.cache.0.write()
@@ -202,7 +211,7 @@ impl<Block: BlockT> DbChangesTrieStorage<Block> {
 					parent_block,
 					block,
 					cache_at,
-					if finalized { CacheEntryType::Final } else { CacheEntryType::NonFinal },
+					entry_type,
 				)?
 				.into_ops(),
 		}).with_new_config(Some(new_configuration)))
@@ -42,6 +42,7 @@ mod storage_cache;
 mod upgrade;
 mod utils;
 mod stats;
+mod migration;
 #[cfg(feature = "with-parity-db")]
 mod parity_db;
 
@@ -90,6 +91,7 @@ use crate::stats::StateUsageStats;
 // Re-export the Database trait so that one can pass an implementation of it.
 pub use sp_database::Database;
 pub use sc_state_db::PruningMode;
+pub use migration::{migrate_to_parity_db, MigrationProgress};
 
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
 pub use bench::BenchmarkingState;
@@ -285,10 +287,14 @@ pub struct DatabaseSettings {
 }
 
 /// Block pruning settings.
-#[derive(Debug, Clone, Copy)]
+///
+/// Independent from the state pruning, which is configured by [`PruningMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeepBlocks {
 	/// Keep full block history.
 	All,
+	/// Keep all finalized blocks, discarding the blocks of displaced forks.
+	Finalized,
 	/// Keep N recent finalized blocks.
 	Some(u32),
 }
@@ -723,6 +729,8 @@ pub struct BlockImportOperation<Block: BlockT> {
 	finalized_blocks: Vec<(BlockId<Block>, Option<Justification>)>,
 	set_head: Option<BlockId<Block>>,
 	commit_state: bool,
+	/// Whether the state was set as a whole, for the genesis block or when importing a state.
+	reset_storage: bool,
 	index_ops: Vec<IndexOperation>,
 }
 
@@ -821,6 +829,7 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block> for Bloc
 		self.db_updates = transaction;
 		self.changes_trie_config_update = Some(changes_trie_config);
 		self.commit_state = true;
+		self.reset_storage = true;
 		Ok(root)
 	}
 
@@ -1006,15 +1015,31 @@ impl<Block: BlockT> Backend<Block> {
 		keep_blocks: u32,
 		canonicalization_delay: u64,
 		transaction_storage: TransactionStorageMode,
+	) -> Self {
+		Self::new_test_with_pruning(
+			PruningMode::keep_blocks(keep_blocks),
+			KeepBlocks::Some(keep_blocks),
+			canonicalization_delay,
+			transaction_storage,
+		)
+	}
+
+	/// Create new memory-backed client backend for tests, with independent state and block pruning.
+	#[cfg(any(test, feature = "test-helpers"))]
+	fn new_test_with_pruning(
+		state_pruning: PruningMode,
+		keep_blocks: KeepBlocks,
+		canonicalization_delay: u64,
+		transaction_storage: TransactionStorageMode,
 	) -> Self {
 		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
 		let db = sp_database::as_database(db);
 		let db_setting = DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
-			state_pruning: PruningMode::keep_blocks(keep_blocks),
+			state_pruning,
 			source: DatabaseSettingsSrc::Custom(db),
-			keep_blocks: KeepBlocks::Some(keep_blocks),
+			keep_blocks,
 			transaction_storage,
 		};
 
@@ -1094,8 +1119,11 @@ impl<Block: BlockT> Backend<Block> {
 
 		let meta = self.blockchain.meta.read();
 
-		// cannot find tree route with empty DB.
-		if meta.best_hash != Default::default() {
+		let route_to_exists = self.blockchain.status(BlockId::Hash(route_to))?
+			== sp_blockchain::BlockStatus::InChain;
+
+		// cannot find tree route with empty DB or when importing a detached block.
+		if meta.best_hash != Default::default() && route_to_exists {
 			let tree_route = sp_blockchain::tree_route(
 				&self.blockchain,
 				meta.best_hash,
@@ -1238,7 +1266,10 @@ impl<Block: BlockT> Backend<Block> {
 		operation.apply_offchain(&mut transaction);
 
 		let mut meta_updates = Vec::with_capacity(operation.finalized_blocks.len());
-		let mut last_finalized_hash = self.blockchain.meta.read().finalized_hash;
+		let (best_num, mut last_finalized_hash, mut last_finalized_num) = {
+			let meta = self.blockchain.meta.read();
+			(meta.best_number, meta.finalized_hash, meta.finalized_number)
+		};
 
 		let mut changes_trie_cache_ops = None;
 		for (block, justification) in operation.finalized_blocks {
@@ -1255,6 +1286,7 @@ impl<Block: BlockT> Backend<Block> {
 				&mut finalization_displaced_leaves,
 			)?);
 			last_finalized_hash = block_hash;
+			last_finalized_num = block_header.number().clone();
 		}
 
 		let imported = if let Some(pending_block) = operation.pending_block {
@@ -1265,6 +1297,11 @@ impl<Block: BlockT> Backend<Block> {
 			// blocks are keyed by number + hash.
 			let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
 
+			// The header may already be in the database when the block is re-imported
+			// together with its state.
+			let existing_header = number <= best_num &&
+				sc_client_api::blockchain::HeaderBackend::header(&self.blockchain, BlockId::hash(hash))?.is_some();
+
 			let (enacted, retracted) = if pending_block.leaf_state.is_best() {
 				self.set_head_with_transaction(&mut transaction, parent_hash, (number, hash))?
 			} else {
@@ -1353,23 +1390,45 @@ impl<Block: BlockT> Backend<Block> {
 				}
 				self.state_usage.tally_writes(ops, bytes);
 				let number_u64 = number.saturated_into::<u64>();
-				let commit = self.storage.state_db.insert_block(
-					&hash,
-					number_u64,
-					&pending_block.header.parent_hash(),
-					changeset,
-				).map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
+				let commit = if operation.reset_storage && number_u64 > 0 {
+					// The state was imported, so its parent is not necessarily known.
+					self.storage.state_db.insert_imported_block(
+						&hash,
+						number_u64,
+						&pending_block.header.parent_hash(),
+						changeset,
+					)
+				} else {
+					self.storage.state_db.insert_block(
+						&hash,
+						number_u64,
+						&pending_block.header.parent_hash(),
+						changeset,
+					)
+				}.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
 				apply_state_commit(&mut transaction, commit);
+				if number <= last_finalized_num {
+					// The block is already finalized, canonicalize its state right away.
+					let commit = self.storage.state_db.canonicalize_block(&hash)
+						.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
+					apply_state_commit(&mut transaction, commit);
+				}
 
 				// Check if need to finalize. Genesis is always finalized instantly.
 				let finalized = number_u64 == 0 || pending_block.leaf_state.is_final();
 				finalized
 			} else {
-				false
+				(number.is_zero() && last_finalized_num.is_zero()) || pending_block.leaf_state.is_final()
 			};
 
 			let header = &pending_block.header;
 			let is_best = pending_block.leaf_state.is_best();
+			// A block imported with state and without its ancestry (e.g. after a warp sync)
+			// is finalized right away.
+			let detached = !number.is_zero() && sc_client_api::blockchain::HeaderBackend::header(
+				&self.blockchain,
+				BlockId::hash(parent_hash),
+			)?.is_none();
 			let changes_trie_updates = operation.changes_trie_updates;
 			let changes_trie_config_update = operation.changes_trie_config_update;
 			changes_trie_cache_ops = Some(self.changes_tries_storage.commit(
@@ -1382,6 +1441,7 @@ impl<Block: BlockT> Backend<Block> {
 				cache::ComplexBlockId::new(hash, number),
 				header,
 				finalized,
+				detached,
 				changes_trie_config_update,
 				changes_trie_cache_ops,
 			)?);
@@ -1391,7 +1451,9 @@ impl<Block: BlockT> Backend<Block> {
 
 			if finalized {
 				// TODO: ensure best chain contains this block.
-				self.ensure_sequential_finalization(header, Some(last_finalized_hash))?;
+				if !detached {
+					self.ensure_sequential_finalization(header, Some(last_finalized_hash))?;
+				}
 				self.note_finalized(
 					&mut transaction,
 					true,
@@ -1407,29 +1469,31 @@ impl<Block: BlockT> Backend<Block> {
 
 			debug!(target: "db", "DB Commit {:?} ({}), best = {}", hash, number, is_best);
 
-			let displaced_leaf = {
+			let displaced_leaf = if !existing_header {
 				let mut leaves = self.blockchain.leaves.write();
 				let displaced_leaf = leaves.import(hash, number, parent_hash);
 				leaves.prepare_transaction(&mut transaction, columns::META, meta_keys::LEAF_PREFIX);
 
+				let mut children = children::read_children(
+					&*self.storage.db,
+					columns::META,
+					meta_keys::CHILDREN_PREFIX,
+					parent_hash,
+				)?;
+				children.push(hash);
+				children::write_children(
+					&mut transaction,
+					columns::META,
+					meta_keys::CHILDREN_PREFIX,
+					parent_hash,
+					children,
+				);
+
 				displaced_leaf
+			} else {
+				None
 			};
 
-			let mut children = children::read_children(
-				&*self.storage.db,
-				columns::META,
-				meta_keys::CHILDREN_PREFIX,
-				parent_hash,
-			)?;
-			children.push(hash);
-			children::write_children(
-				&mut transaction,
-				columns::META,
-				meta_keys::CHILDREN_PREFIX,
-				parent_hash,
-				children,
-			);
-
 			meta_updates.push((hash, number, pending_block.leaf_state.is_best(), finalized));
 
 			Some((pending_block.header, number, hash, enacted, retracted, displaced_leaf, is_best, cache))
@@ -1522,9 +1586,12 @@ impl<Block: BlockT> Backend<Block> {
 			let lookup_key = utils::number_and_hash_to_lookup_key(f_num, f_hash.clone())?;
 			transaction.set_from_vec(columns::META, meta_keys::FINALIZED_BLOCK, lookup_key);
 
-			let commit = self.storage.state_db.canonicalize_block(&f_hash)
-				.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
-			apply_state_commit(transaction, commit);
+			// Blocks imported without state are finalized without touching the state database.
+			if sc_client_api::Backend::have_state_at(self, &f_hash, f_num) {
+				let commit = self.storage.state_db.canonicalize_block(&f_hash)
+					.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
+				apply_state_commit(transaction, commit);
+			}
 
 			if !f_num.is_zero() {
 				let new_changes_trie_cache_ops = self.changes_tries_storage.finalize(
@@ -1555,32 +1622,36 @@ impl<Block: BlockT> Backend<Block> {
 		finalized: NumberFor<Block>,
 		displaced: &FinalizationDisplaced<Block::Hash, NumberFor<Block>>,
 	) -> ClientResult<()> {
-		if let KeepBlocks::Some(keep_blocks) = self.keep_blocks {
-			// Always keep the last finalized block
-			let keep = std::cmp::max(keep_blocks, 1);
-			if finalized >= keep.into() {
-				let number = finalized.saturating_sub(keep.into());
-				self.prune_block(transaction, BlockId::<Block>::number(number))?;
-			}
+		match self.keep_blocks {
+			KeepBlocks::All => return Ok(()),
+			KeepBlocks::Finalized => {},
+			KeepBlocks::Some(keep_blocks) => {
+				// Always keep the last finalized block
+				let keep = std::cmp::max(keep_blocks, 1);
+				if finalized >= keep.into() {
+					let number = finalized.saturating_sub(keep.into());
+					self.prune_block(transaction, BlockId::<Block>::number(number))?;
+				}
+			},
+		}
 
-			// Also discard all blocks from displaced branches
-			for h in displaced.leaves() {
-				let mut number = finalized;
-				let mut hash = h.clone();
-				// Follow displaced chains back until we reach a finalized block.
-				// Since leaves are discarded due to finality, they can't have parents
-				// that are canonical, but not yet finalized. So we stop deletig as soon as
-				// we reach canonical chain.
-				while self.blockchain.hash(number)? != Some(hash.clone()) {
-					let id = BlockId::<Block>::hash(hash.clone());
-					match self.blockchain.header(id)? {
-						Some(header) => {
-							self.prune_block(transaction, id)?;
-							number = header.number().saturating_sub(One::one());
-							hash = header.parent_hash().clone();
-						},
-						None => break,
-					}
+		// Also discard all blocks from displaced branches
+		for h in displaced.leaves() {
+			let mut number = finalized;
+			let mut hash = h.clone();
+			// Follow displaced chains back until we reach a finalized block.
+			// Since leaves are discarded due to finality, they can't have parents
+			// that are canonical, but not yet finalized. So we stop deletig as soon as
+			// we reach canonical chain.
+			while self.blockchain.hash(number)? != Some(hash.clone()) {
+				let id = BlockId::<Block>::hash(hash.clone());
+				match self.blockchain.header(id)? {
+					Some(header) => {
+						self.prune_block(transaction, id)?;
+						number = header.number().saturating_sub(One::one());
+						hash = header.parent_hash().clone();
+					},
+					None => break,
 				}
 			}
 		}
@@ -1592,6 +1663,13 @@ impl<Block: BlockT> Backend<Block> {
 		transaction: &mut Transaction<DbHash>,
 		id: BlockId<Block>,
 	) -> ClientResult<()> {
+		utils::remove_from_db(
+			transaction,
+			&*self.storage.db,
+			columns::KEY_LOOKUP,
+			columns::JUSTIFICATIONS,
+			id,
+		)?;
 		match read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY, id)? {
 			Some(body) => {
 				debug!(target: "db", "Removing block #{}", id);
@@ -1753,6 +1831,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			finalized_blocks: Vec::new(),
 			set_head: None,
 			commit_state: false,
+			reset_storage: false,
 			index_ops: Default::default(),
 		})
 	}
@@ -3040,6 +3119,107 @@ pub(crate) mod tests {
 		assert_eq!(Some(vec![4.into()]), bc.body(BlockId::hash(blocks[4])).unwrap());
 	}
 
+	#[test]
+	fn prune_justifications_with_blocks() {
+		let backend = Backend::<Block>::new_test(2, 0);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0 .. 5 {
+			let hash = insert_block(&backend, i, prev_hash, None, Default::default(), vec![i.into()], None);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		for i in 1 .. 5 {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+			op.mark_finalized(BlockId::Hash(blocks[i]), Some((CONS0_ENGINE_ID, vec![i as u8]))).unwrap();
+			backend.commit_operation(op).unwrap();
+		}
+
+		let bc = backend.blockchain();
+		assert_eq!(None, bc.justifications(BlockId::hash(blocks[1])).unwrap());
+		assert_eq!(None, bc.justifications(BlockId::hash(blocks[2])).unwrap());
+		assert_eq!(
+			Some(Justifications::from((CONS0_ENGINE_ID, vec![3]))),
+			bc.justifications(BlockId::hash(blocks[3])).unwrap(),
+		);
+	}
+
+	#[test]
+	fn keep_finalized_blocks_with_state_pruning() {
+		let backend = Backend::<Block>::new_test_with_pruning(
+			PruningMode::keep_blocks(1),
+			KeepBlocks::Finalized,
+			0,
+			TransactionStorageMode::BlockBody,
+		);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0 .. 5 {
+			let hash = insert_block(&backend, i, prev_hash, None, Default::default(), vec![i.into()], None);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		// insert a fork at block 2
+		let fork_hash_root = insert_block(
+			&backend,
+			2,
+			blocks[1],
+			None,
+			sp_core::H256::random(),
+			vec![2.into()],
+			None
+		);
+		let fork_hash = insert_block(&backend, 3, fork_hash_root, None, H256::random(), vec![3.into(), 11.into()], None);
+
+		for i in 1 .. 5 {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+			op.mark_finalized(BlockId::Hash(blocks[i]), None).unwrap();
+			backend.commit_operation(op).unwrap();
+		}
+
+		let bc = backend.blockchain();
+		for i in 0 .. 5 {
+			assert_eq!(Some(vec![(i as u64).into()]), bc.body(BlockId::hash(blocks[i])).unwrap());
+		}
+		assert_eq!(None, bc.body(BlockId::hash(fork_hash_root)).unwrap());
+		assert_eq!(None, bc.body(BlockId::hash(fork_hash)).unwrap());
+		assert!(backend.have_state_at(&blocks[4], 4));
+		assert!(!backend.have_state_at(&blocks[1], 1));
+	}
+
+	#[test]
+	fn prune_blocks_with_archive_state() {
+		let backend = Backend::<Block>::new_test_with_pruning(
+			PruningMode::ArchiveAll,
+			KeepBlocks::Some(2),
+			0,
+			TransactionStorageMode::BlockBody,
+		);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0 .. 5 {
+			let hash = insert_block(&backend, i, prev_hash, None, Default::default(), vec![i.into()], None);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+		for i in 1 .. 5 {
+			op.mark_finalized(BlockId::Hash(blocks[i]), None).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+
+		let bc = backend.blockchain();
+		assert_eq!(None, bc.body(BlockId::hash(blocks[1])).unwrap());
+		assert_eq!(Some(vec![3.into()]), bc.body(BlockId::hash(blocks[3])).unwrap());
+		assert!(!backend.storage.state_db.is_pruned(&blocks[1], 1));
+	}
+
 	#[test]
 	fn renew_transaction_storage() {
 		let backend = Backend::<Block>::new_test_with_tx_storage(
@@ -0,0 +1,552 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Migration of a RocksDB database to ParityDB.
+//!
+//! Every column is streamed from the source database to the target one in batches. The meta
+//! column, which references the best and finalized blocks, is copied last, so that the target
+//! database can't be used before the migration is complete. The progress is recorded in the
+//! target database along with each batch, which allows an interrupted migration to be resumed.
+//!
+//! RocksDB stores the state trie nodes under their hash prefixed with their position in the
+//! trie, while ParityDB reference counts them by hash alone. The prefix is therefore stripped
+//! from the keys of the state column and from the node keys referenced by the state journals,
+//! and each prefixed copy of a node adds a reference to it. RocksDB doesn't count references, so
+//! the journal of the pruning window is rewritten to keep the nodes that are inserted again by a
+//! later block.
+
+use std::path::Path;
+
+use sp_runtime::traits::Block as BlockT;
+
+use crate::PruningMode;
+
+/// Progress of a database migration.
+#[derive(Debug, Clone)]
+pub struct MigrationProgress {
+	/// Column being copied.
+	pub column: u32,
+	/// Number of keys of the column copied so far.
+	pub keys: u64,
+	/// Number of bytes of the column copied so far.
+	pub bytes: u64,
+	/// Whether the column is completely copied.
+	pub done: bool,
+}
+
+/// Migrate the RocksDB database at `source` to a ParityDB database at `target`.
+///
+/// The source database is left untouched and must be at the current database version, i.e. it
+/// must have been opened by this version of the node before. If a previous migration to `target`
+/// was interrupted, it is resumed as long as the source database was not modified since. Once
+/// all columns are copied, the target database is opened and its best and finalized headers and
+/// their state are read to check the migration with the `state_pruning` mode the node runs with.
+///
+/// `on_progress` is called after each batch of copied keys and at the end of each column.
+pub fn migrate_to_parity_db<Block: BlockT>(
+	source: &Path,
+	target: &Path,
+	state_pruning: PruningMode,
+	on_progress: impl FnMut(MigrationProgress),
+) -> sp_blockchain::Result<()> {
+	#[cfg(all(feature = "with-kvdb-rocksdb", feature = "with-parity-db"))]
+	return imp::migrate::<Block>(source, target, state_pruning, on_progress);
+
+	#[cfg(not(all(feature = "with-kvdb-rocksdb", feature = "with-parity-db")))]
+	{
+		let _ = (source, target, state_pruning, on_progress);
+		Err(sp_blockchain::Error::Backend(
+			"`with-kvdb-rocksdb` and `with-parity-db` features are required to migrate the database"
+				.into(),
+		))
+	}
+}
+
+#[cfg(all(feature = "with-kvdb-rocksdb", feature = "with-parity-db"))]
+mod imp {
+	use std::{io, path::Path};
+
+	use codec::{Decode, Encode};
+	use log::info;
+	use sc_client_api::backend::Backend as _;
+	use sp_blockchain::HeaderBackend;
+	use sp_database::Transaction;
+	use sp_runtime::{generic::BlockId, traits::{Block as BlockT, Header as HeaderT}};
+	use sp_state_machine::Backend as _;
+
+	use super::MigrationProgress;
+	use crate::{
+		columns, light, upgrade,
+		utils::{self, meta_keys, DatabaseType, COLUMN_META, NUM_COLUMNS},
+		DatabaseSettings, DatabaseSettingsSrc, DbHash, KeepBlocks, PruningMode,
+		TransactionStorageMode, DB_HASH_LEN,
+	};
+
+	/// Key of the migration state in the meta column of the target database.
+	const MIGRATION_STATE_KEY: &[u8; 9] = b"migration";
+
+	/// Number of bytes of keys and values written to the target database in a single commit.
+	const BATCH_BYTES: usize = 64 * 1024 * 1024;
+
+	/// State of an unfinished migration.
+	#[derive(Encode, Decode)]
+	struct MigrationState {
+		/// Best and finalized block entries of the source meta column when the migration started.
+		source_meta: Vec<Option<Vec<u8>>>,
+		/// Index of the column being copied in `column_order`.
+		column: u32,
+		/// Last key of the column copied.
+		last_key: Option<Vec<u8>>,
+	}
+
+	/// Order in which the columns are copied, with the meta column last.
+	fn column_order() -> impl Iterator<Item = u32> {
+		(0..NUM_COLUMNS).filter(|c| *c != COLUMN_META).chain(std::iter::once(COLUMN_META))
+	}
+
+	fn db_err(err: impl std::fmt::Display) -> sp_blockchain::Error {
+		sp_blockchain::Error::Backend(format!("{}", err))
+	}
+
+	/// Meta database of the state db in the source database.
+	struct SourceMetaDb<'a>(&'a kvdb_rocksdb::Database);
+
+	impl<'a> sc_state_db::MetaDb for SourceMetaDb<'a> {
+		type Error = io::Error;
+
+		fn get_meta(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+			self.0.get(columns::STATE_META, key)
+		}
+	}
+
+	/// Strip the trie prefix from the key of a state node.
+	fn strip_prefix(mut key: Vec<u8>) -> Vec<u8> {
+		key.drain(..key.len().saturating_sub(DB_HASH_LEN));
+		key
+	}
+
+	pub(super) fn migrate<Block: BlockT>(
+		source_path: &Path,
+		target_path: &Path,
+		state_pruning: PruningMode,
+		mut on_progress: impl FnMut(MigrationProgress),
+	) -> sp_blockchain::Result<()> {
+		if !source_path.exists() {
+			return Err(db_err(format!("No database found at {}", source_path.display())))
+		}
+		upgrade::ensure_current_version(source_path)?;
+
+		let path = source_path.to_str().ok_or_else(|| db_err("Invalid database path"))?;
+		let source = kvdb_rocksdb::Database::open(
+			&kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS),
+			path,
+		).map_err(db_err)?;
+
+		let db_type = match source.get(COLUMN_META, meta_keys::TYPE).map_err(db_err)?.as_deref() {
+			Some(t) if t == DatabaseType::Full.as_str().as_bytes() => DatabaseType::Full,
+			Some(t) if t == DatabaseType::Light.as_str().as_bytes() => DatabaseType::Light,
+			_ => return Err(db_err("Unknown database type")),
+		};
+		if db_type == DatabaseType::Full && source.iter(columns::TRANSACTION).next().is_some() {
+			return Err(db_err("Indexed transactions are not supported by ParityDB"))
+		}
+
+		let target = crate::parity_db::open::<DbHash>(target_path, db_type).map_err(db_err)?;
+
+		let source_meta = [&meta_keys::BEST_BLOCK[..], &meta_keys::FINALIZED_BLOCK[..]]
+			.iter()
+			.map(|key| source.get(COLUMN_META, key))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(db_err)?;
+		let mut state = match target.get(COLUMN_META, MIGRATION_STATE_KEY) {
+			Some(state) => {
+				let state = MigrationState::decode(&mut &state[..]).map_err(db_err)?;
+				if state.source_meta != source_meta {
+					return Err(db_err(
+						"The source database was modified since the migration started, \
+						 remove the target database to start over",
+					))
+				}
+				info!(target: "db", "Resuming migration to {}", target_path.display());
+				state
+			},
+			None if target.get(COLUMN_META, meta_keys::TYPE).is_some() => {
+				return Err(db_err(format!("A database already exists at {}", target_path.display())))
+			},
+			None => MigrationState { source_meta, column: 0, last_key: None },
+		};
+
+		for (index, column) in column_order().enumerate().skip(state.column as usize) {
+			let last_key = state.last_key.take();
+			let mut progress = MigrationProgress { column, keys: 0, bytes: 0, done: false };
+			let mut transaction = Transaction::new();
+			let mut batch_bytes = 0;
+
+			// RocksDB iterates in key order, so skipping the keys up to the last one copied
+			// resumes the migration of the column.
+			let entries = source.iter(column)
+				.filter(|(key, _)| last_key.as_ref().map_or(true, |last| &key[..] > &last[..]));
+			for (key, value) in entries {
+				batch_bytes += key.len() + value.len();
+				progress.keys += 1;
+				progress.bytes += (key.len() + value.len()) as u64;
+				match (db_type, column) {
+					// Every prefixed copy of a node adds a reference to the node in ParityDB.
+					(DatabaseType::Full, columns::STATE) => {
+						if key.len() < DB_HASH_LEN {
+							return Err(db_err(format!("Invalid state node key {:?}", key)))
+						}
+						transaction.set(column, &key[key.len() - DB_HASH_LEN..], &value);
+					},
+					(DatabaseType::Full, columns::STATE_META) => {
+						let record = sc_state_db::map_journal_keys::<Block::Hash>(
+							&key,
+							&value,
+							strip_prefix,
+						).map_err(db_err)?;
+						transaction.set_from_vec(column, &key, record.unwrap_or_else(|| value.to_vec()));
+					},
+					_ => transaction.set(column, &key, &value),
+				}
+
+				if batch_bytes >= BATCH_BYTES {
+					state.column = index as u32;
+					state.last_key = Some(key.to_vec());
+					transaction.set_from_vec(COLUMN_META, MIGRATION_STATE_KEY, state.encode());
+					target.commit(std::mem::take(&mut transaction))?;
+					batch_bytes = 0;
+					on_progress(progress.clone());
+				}
+			}
+
+			// The pruning journal records copied above still delete the nodes that a later block
+			// of the window inserted again, which would remove the only reference to them.
+			if (db_type, column) == (DatabaseType::Full, columns::STATE_META) {
+				let journal = sc_state_db::map_pruning_journal::<Block::Hash, _>(
+					&SourceMetaDb(&source),
+					strip_prefix,
+				).map_err(sp_blockchain::Error::from_state_db)?;
+				for (key, record) in journal {
+					transaction.set_from_vec(column, &key, record);
+				}
+			}
+
+			state.column = index as u32 + 1;
+			state.last_key = None;
+			transaction.set_from_vec(COLUMN_META, MIGRATION_STATE_KEY, state.encode());
+			target.commit(transaction)?;
+			progress.done = true;
+			on_progress(progress);
+		}
+
+		// The target is opened again by the backend below.
+		drop(target);
+		let settings = DatabaseSettings {
+			state_cache_size: 0,
+			state_cache_child_ratio: None,
+			state_pruning,
+			source: DatabaseSettingsSrc::ParityDb { path: target_path.to_path_buf() },
+			keep_blocks: KeepBlocks::All,
+			transaction_storage: TransactionStorageMode::BlockBody,
+		};
+		check_target::<Block>(settings, db_type, &state.source_meta)?;
+
+		let target = crate::parity_db::open::<DbHash>(target_path, db_type).map_err(db_err)?;
+		let mut transaction = Transaction::new();
+		transaction.remove(COLUMN_META, MIGRATION_STATE_KEY);
+		target.commit(transaction)?;
+		info!(target: "db", "Migrated database to {}", target_path.display());
+
+		Ok(())
+	}
+
+	/// Open the migrated database, check that its best and finalized blocks are the ones of the
+	/// source database given by `source_meta` and read their headers and state.
+	fn check_target<Block: BlockT>(
+		settings: DatabaseSettings,
+		db_type: DatabaseType,
+		source_meta: &[Option<Vec<u8>>],
+	) -> sp_blockchain::Result<()> {
+		let check_err = |what: &str, hash: Block::Hash, err: &dyn std::fmt::Display| db_err(format!(
+			"Integrity check failed: can't read the {} of block {:?} from the target database: {}",
+			what,
+			hash,
+			err,
+		));
+
+		let info = match db_type {
+			DatabaseType::Full => {
+				let backend = crate::Backend::<Block>::new(settings, 0)?;
+				let info = backend.blockchain().info();
+				for hash in [info.best_hash, info.finalized_hash].iter() {
+					let header = backend.blockchain().header(BlockId::Hash(*hash))?
+						.ok_or_else(|| check_err("header", *hash, &"not found"))?;
+					let state = backend.state_at(BlockId::Hash(*hash))
+						.map_err(|e| check_err("state", *hash, &e))?;
+					// Looking up any key starts at the root node, which fails if the state
+					// nodes are not found under the keys the backend expects.
+					state.storage(sp_core::storage::well_known_keys::CODE)
+						.map_err(|e| check_err("state", *hash, &e))?;
+					info!(target: "db", "Checked state root {:?} of block {:?}", header.state_root(), hash);
+				}
+				info
+			},
+			DatabaseType::Light => {
+				let storage = light::LightStorage::<Block>::new(settings)?;
+				let info = storage.info();
+				for hash in [info.best_hash, info.finalized_hash].iter() {
+					storage.header(BlockId::Hash(*hash))?
+						.ok_or_else(|| check_err("header", *hash, &"not found"))?;
+				}
+				info
+			},
+		};
+
+		let target_blocks = [("best", info.best_hash), ("finalized", info.finalized_hash)];
+		for ((what, target_hash), source_key) in target_blocks.iter().zip(source_meta) {
+			// Without an entry the source database is at genesis.
+			let source_hash = match source_key {
+				Some(key) => utils::lookup_key_to_hash::<Block::Hash>(key)?,
+				None => info.genesis_hash,
+			};
+			if *target_hash != source_hash {
+				return Err(db_err(format!(
+					"Integrity check failed: the {} block of the target database is {:?}, \
+					 but {:?} in the source database",
+					what,
+					target_hash,
+					source_hash,
+				)))
+			}
+		}
+
+		info!(
+			target: "db",
+			"Checked best block #{} ({:?}) and finalized block #{} ({:?})",
+			info.best_number,
+			info.best_hash,
+			info.finalized_number,
+			info.finalized_hash,
+		);
+		Ok(())
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use sc_client_api::backend::{Backend as _, BlockImportOperation as _, NewBlockState};
+		use sp_core::H256;
+		use sp_runtime::{generic::BlockId, testing::Header, traits::Header as _, Storage};
+		use sp_state_machine::Backend as _;
+
+		use crate::{
+			tests::Block, utils::{self, DatabaseType}, Backend, DatabaseSettings,
+			DatabaseSettingsSrc, KeepBlocks, PruningMode, TransactionStorageMode,
+		};
+
+		fn settings(source: DatabaseSettingsSrc) -> DatabaseSettings {
+			DatabaseSettings {
+				state_cache_size: 0,
+				state_cache_child_ratio: None,
+				state_pruning: PruningMode::keep_blocks(2),
+				source,
+				keep_blocks: KeepBlocks::All,
+				transaction_storage: TransactionStorageMode::BlockBody,
+			}
+		}
+
+		fn import(
+			backend: &Backend<Block>,
+			number: u64,
+			parent_hash: H256,
+			storage: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+			state: NewBlockState,
+		) -> H256 {
+			let mut op = backend.begin_operation().unwrap();
+			let parent = match number {
+				0 => BlockId::Hash(Default::default()),
+				_ => BlockId::Hash(parent_hash),
+			};
+			backend.begin_state_operation(&mut op, parent).unwrap();
+			let (root, transaction) = op.old_state.storage_root(
+				storage.iter().map(|(k, v)| (&k[..], v.as_ref().map(|v| &v[..]))),
+			);
+			let header = Header {
+				number,
+				parent_hash,
+				state_root: root,
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			if number == 0 {
+				op.reset_storage(Storage {
+					top: storage.into_iter().map(|(k, v)| (k, v.unwrap())).collect(),
+					children_default: Default::default(),
+				}).unwrap();
+			} else {
+				op.update_db_storage(transaction).unwrap();
+				op.update_storage(storage, Vec::new()).unwrap();
+			}
+			let hash = header.hash();
+			op.set_block_data(header, Some(Vec::new()), None, state).unwrap();
+			backend.commit_operation(op).unwrap();
+			hash
+		}
+
+		#[test]
+		fn migrated_state_can_be_read() {
+			let source = tempfile::tempdir().unwrap();
+			let target = tempfile::tempdir().unwrap();
+			let target = target.path().join("paritydb");
+
+			{
+				// With a canonicalization delay of one block, the state of the best block is
+				// only referenced by the state journal.
+				let backend = Backend::<Block>::new(
+					settings(DatabaseSettingsSrc::RocksDb {
+						path: source.path().to_path_buf(),
+						cache_size: 16,
+					}),
+					1,
+				).unwrap();
+				let genesis = import(&backend, 0, Default::default(), vec![
+					(vec![1, 3, 5], Some(vec![2, 4, 6])),
+					(vec![1, 2, 3], Some(vec![9, 9, 9])),
+				], NewBlockState::Best);
+				import(&backend, 1, genesis, vec![
+					(vec![1, 3, 5], None),
+					(vec![5, 5, 5], Some(vec![4, 5, 6])),
+				], NewBlockState::Best);
+			}
+
+			super::super::migrate_to_parity_db::<Block>(
+				source.path(),
+				&target,
+				PruningMode::keep_blocks(2),
+				|_| (),
+			).unwrap();
+
+			let backend = Backend::<Block>::new(
+				settings(DatabaseSettingsSrc::ParityDb { path: target }),
+				1,
+			).unwrap();
+			let genesis = backend.state_at(BlockId::Number(0)).unwrap();
+			assert_eq!(genesis.storage(&[1, 3, 5]).unwrap(), Some(vec![2, 4, 6]));
+			assert_eq!(genesis.storage(&[1, 2, 3]).unwrap(), Some(vec![9, 9, 9]));
+			let best = backend.state_at(BlockId::Number(1)).unwrap();
+			assert_eq!(best.storage(&[1, 3, 5]).unwrap(), None);
+			assert_eq!(best.storage(&[1, 2, 3]).unwrap(), Some(vec![9, 9, 9]));
+			assert_eq!(best.storage(&[5, 5, 5]).unwrap(), Some(vec![4, 5, 6]));
+		}
+
+		#[test]
+		fn check_target_compares_best_and_finalized_blocks() {
+			let source = tempfile::tempdir().unwrap();
+			let target = tempfile::tempdir().unwrap();
+			let target = target.path().join("paritydb");
+
+			let (genesis, block1) = {
+				let backend = Backend::<Block>::new(
+					settings(DatabaseSettingsSrc::RocksDb {
+						path: source.path().to_path_buf(),
+						cache_size: 16,
+					}),
+					0,
+				).unwrap();
+				let genesis = import(&backend, 0, Default::default(), vec![
+					(vec![1, 3, 5], Some(vec![2, 4, 6])),
+				], NewBlockState::Final);
+				let block1 = import(&backend, 1, genesis, vec![
+					(vec![5, 5, 5], Some(vec![4, 5, 6])),
+				], NewBlockState::Final);
+				(genesis, block1)
+			};
+
+			super::super::migrate_to_parity_db::<Block>(
+				source.path(),
+				&target,
+				PruningMode::keep_blocks(2),
+				|_| (),
+			).unwrap();
+
+			let key = |number: u64, hash: H256| {
+				Some(utils::number_and_hash_to_lookup_key(number, hash).unwrap())
+			};
+			let check = |best, finalized| super::check_target::<Block>(
+				settings(DatabaseSettingsSrc::ParityDb { path: target.clone() }),
+				DatabaseType::Full,
+				&[best, finalized],
+			);
+
+			assert!(check(key(1, block1), key(1, block1)).is_ok());
+			assert!(check(key(0, genesis), key(1, block1)).is_err());
+			assert!(check(key(1, block1), key(0, genesis)).is_err());
+		}
+
+		#[test]
+		fn reinserted_state_survives_pruning() {
+			let source = tempfile::tempdir().unwrap();
+			let target = tempfile::tempdir().unwrap();
+			let target = target.path().join("paritydb");
+
+			let block2 = {
+				let backend = Backend::<Block>::new(
+					settings(DatabaseSettingsSrc::RocksDb {
+						path: source.path().to_path_buf(),
+						cache_size: 16,
+					}),
+					0,
+				).unwrap();
+				let genesis = import(&backend, 0, Default::default(), vec![
+					(vec![1, 3, 5], Some(vec![2, 4, 6])),
+				], NewBlockState::Final);
+				let block1 = import(&backend, 1, genesis, vec![
+					(vec![1, 3, 5], None),
+					(vec![5, 5, 5], Some(vec![4, 5, 6])),
+				], NewBlockState::Final);
+				// Block 2 inserts the root node of the genesis state again, which block 1 deletes
+				// when it is pruned. Block 0 is pruned, block 1 is still in the pruning window.
+				import(&backend, 2, block1, vec![
+					(vec![1, 3, 5], Some(vec![2, 4, 6])),
+					(vec![5, 5, 5], None),
+				], NewBlockState::Final)
+			};
+
+			super::super::migrate_to_parity_db::<Block>(
+				source.path(),
+				&target,
+				PruningMode::keep_blocks(2),
+				|_| (),
+			).unwrap();
+
+			let backend = Backend::<Block>::new(
+				settings(DatabaseSettingsSrc::ParityDb { path: target }),
+				0,
+			).unwrap();
+			// Pruning block 1 must not delete the nodes of the state of block 2.
+			import(&backend, 3, block2, vec![
+				(vec![1, 2, 3], Some(vec![9, 9, 9])),
+			], NewBlockState::Final);
+			let state = backend.state_at(BlockId::Hash(block2)).unwrap();
+			assert_eq!(state.storage(&[1, 3, 5]).unwrap(), Some(vec![2, 4, 6]));
+			assert_eq!(state.storage(&[5, 5, 5]).unwrap(), None);
+			let state = backend.state_at(BlockId::Number(3)).unwrap();
+			assert_eq!(state.storage(&[1, 3, 5]).unwrap(), Some(vec![2, 4, 6]));
+			assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![9, 9, 9]));
+		}
+	}
+}
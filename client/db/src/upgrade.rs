@@ -114,6 +114,20 @@ fn current_version(path: &Path) -> sp_blockchain::Result<u32> {
 	}
 }
 
+/// Ensures that the database at given path is at the current version, without upgrading it.
+#[cfg(all(feature = "with-kvdb-rocksdb", feature = "with-parity-db"))]
+pub fn ensure_current_version(db_path: &Path) -> sp_blockchain::Result<()> {
+	match current_version(db_path)? {
+		CURRENT_VERSION => Ok(()),
+		version => Err(sp_blockchain::Error::Backend(format!(
+			"Database version {} is not supported, open the database with the node to upgrade it \
+			 to version {}",
+			version,
+			CURRENT_VERSION,
+		))),
+	}
+}
+
 /// Maps database error to client error
 fn db_err(err: std::io::Error) -> sp_blockchain::Error {
 	sp_blockchain::Error::Backend(format!("{}", err))
@@ -134,6 +134,14 @@ pub fn lookup_key_to_number<N>(key: &[u8]) -> sp_blockchain::Result<N> where
 		| (key[3] as u32)).map(Into::into)
 }
 
+/// Convert long block lookup key into block hash.
+pub fn lookup_key_to_hash<H: Decode>(key: &[u8]) -> sp_blockchain::Result<H> {
+	if key.len() < 4 {
+		return Err(sp_blockchain::Error::Backend("Invalid block key".into()));
+	}
+	H::decode(&mut &key[4..]).map_err(|_| sp_blockchain::Error::Backend("Invalid block key".into()))
+}
+
 /// Delete number to hash mapping in DB transaction.
 pub fn remove_number_to_key_mapping<N: TryInto<u32>>(
 	transaction: &mut Transaction<DbHash>,
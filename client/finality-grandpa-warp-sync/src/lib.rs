@@ -14,165 +14,100 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Helper for handling (i.e. answering) grandpa warp sync requests from a remote peer.
+//! Grandpa warp sync proof provider, answering and verifying warp sync requests for the
+//! [`sc_network`] warp sync protocol.
 
 use codec::{Decode, Encode};
-use sc_network::config::{IncomingRequest, OutgoingResponse, ProtocolId, RequestResponseConfig};
 use sc_client_api::Backend;
-use sp_runtime::traits::NumberFor;
-use futures::channel::{mpsc, oneshot};
-use futures::stream::StreamExt;
-use log::debug;
-use sp_runtime::traits::Block as BlockT;
-use std::time::Duration;
+use sc_network::warp_request_handler::{
+	EncodedProof, VerificationResult, WarpSyncProvider,
+};
+use sp_finality_grandpa::{AuthorityList, SetId};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
 use std::sync::Arc;
-use sc_service::{SpawnTaskHandle, config::{Configuration, Role}};
 use sc_finality_grandpa::SharedAuthoritySet;
 
 mod proof;
 
 pub use proof::{WarpSyncFragment, WarpSyncProof};
 
-/// Generates the appropriate [`RequestResponseConfig`] for a given chain configuration.
-pub fn request_response_config_for_chain<TBlock: BlockT, TBackend: Backend<TBlock> + 'static>(
-	config: &Configuration,
-	spawn_handle: SpawnTaskHandle,
+/// Grandpa warp sync implementation of [`WarpSyncProvider`].
+pub struct NetworkProvider<TBlock: BlockT, TBackend: Backend<TBlock>> {
 	backend: Arc<TBackend>,
 	authority_set: SharedAuthoritySet<TBlock::Hash, NumberFor<TBlock>>,
-) -> RequestResponseConfig
-	where NumberFor<TBlock>: sc_finality_grandpa::BlockNumberOps,
-{
-	let protocol_id = config.protocol_id();
-
-	if matches!(config.role, Role::Light) {
-		// Allow outgoing requests but deny incoming requests.
-		generate_request_response_config(protocol_id.clone())
-	} else {
-		// Allow both outgoing and incoming requests.
-		let (handler, request_response_config) = GrandpaWarpSyncRequestHandler::new(
-			protocol_id.clone(),
-			backend.clone(),
-			authority_set,
-		);
-		spawn_handle.spawn("grandpa_warp_sync_request_handler", handler.run());
-		request_response_config
-	}
-}
-
-const LOG_TARGET: &str = "finality-grandpa-warp-sync-request-handler";
-
-/// Generates a [`RequestResponseConfig`] for the grandpa warp sync request protocol, refusing incoming requests.
-pub fn generate_request_response_config(protocol_id: ProtocolId) -> RequestResponseConfig {
-	RequestResponseConfig {
-		name: generate_protocol_name(protocol_id).into(),
-		max_request_size: 32,
-		max_response_size: proof::MAX_WARP_SYNC_PROOF_SIZE as u64,
-		request_timeout: Duration::from_secs(10),
-		inbound_queue: None,
-	}
-}
-
-/// Generate the grandpa warp sync protocol name from chain specific protocol identifier.
-fn generate_protocol_name(protocol_id: ProtocolId) -> String {
-	let mut s = String::new();
-	s.push_str("/");
-	s.push_str(protocol_id.as_ref());
-	s.push_str("/sync/warp");
-	s
-}
-
-#[derive(Decode)]
-struct Request<B: BlockT> {
-	begin: B::Hash,
-}
-
-/// Handler for incoming grandpa warp sync requests from a remote peer.
-pub struct GrandpaWarpSyncRequestHandler<TBackend, TBlock: BlockT> {
-	backend: Arc<TBackend>,
-	authority_set: SharedAuthoritySet<TBlock::Hash, NumberFor<TBlock>>,
-	request_receiver: mpsc::Receiver<IncomingRequest>,
-	_phantom: std::marker::PhantomData<TBlock>,
 }
 
-impl<TBlock: BlockT, TBackend: Backend<TBlock>> GrandpaWarpSyncRequestHandler<TBackend, TBlock> {
-	/// Create a new [`GrandpaWarpSyncRequestHandler`].
+impl<TBlock: BlockT, TBackend: Backend<TBlock>> NetworkProvider<TBlock, TBackend>
+	where NumberFor<TBlock>: sc_finality_grandpa::BlockNumberOps,
+{
+	/// Create a new instance for a given backend and authority set.
 	pub fn new(
-		protocol_id: ProtocolId,
 		backend: Arc<TBackend>,
 		authority_set: SharedAuthoritySet<TBlock::Hash, NumberFor<TBlock>>,
-	) -> (Self, RequestResponseConfig) {
-		let (tx, request_receiver) = mpsc::channel(20);
-
-		let mut request_response_config = generate_request_response_config(protocol_id);
-		request_response_config.inbound_queue = Some(tx);
-
-		(
-			Self {
-				backend,
-				request_receiver,
-				_phantom: std::marker::PhantomData,
-				authority_set,
-			},
-			request_response_config,
-		)
+	) -> Self {
+		NetworkProvider { backend, authority_set }
 	}
+}
 
-	fn handle_request(
+impl<TBlock: BlockT, TBackend: Backend<TBlock>> WarpSyncProvider<TBlock>
+	for NetworkProvider<TBlock, TBackend>
+	where NumberFor<TBlock>: sc_finality_grandpa::BlockNumberOps,
+{
+	fn generate(
 		&self,
-		payload: Vec<u8>,
-		pending_response: oneshot::Sender<OutgoingResponse>,
-	) -> Result<(), HandleRequestError>
-		where NumberFor<TBlock>: sc_finality_grandpa::BlockNumberOps,
-	{
-		let request = Request::<TBlock>::decode(&mut &payload[..])?;
-
-		let proof = WarpSyncProof::generate(
+		start: TBlock::Hash,
+	) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>> {
+		let proof = WarpSyncProof::<TBlock>::generate(
 			&*self.backend,
-			request.begin,
+			start,
 			&self.authority_set.authority_set_changes(),
-		)?;
-
-		pending_response.send(OutgoingResponse {
-			result: Ok(proof.encode()),
-			reputation_changes: Vec::new(),
-			sent_feedback: None,
-		}).map_err(|_| HandleRequestError::SendResponse)
+		).map_err(Box::new)?;
+		Ok(EncodedProof(proof.encode()))
 	}
 
-	/// Run [`GrandpaWarpSyncRequestHandler`].
-	pub async fn run(mut self)
-		where NumberFor<TBlock>: sc_finality_grandpa::BlockNumberOps,
-	{
-		while let Some(request) = self.request_receiver.next().await {
-			let IncomingRequest { peer, payload, pending_response } = request;
-
-			match self.handle_request(payload, pending_response) {
-				Ok(()) => debug!(target: LOG_TARGET, "Handled grandpa warp sync request from {}.", peer),
-				Err(e) => debug!(
-					target: LOG_TARGET,
-					"Failed to handle grandpa warp sync request from {}: {}",
-					peer, e,
-				),
-			}
+	fn verify(
+		&self,
+		proof: &EncodedProof,
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> Result<VerificationResult<TBlock>, Box<dyn std::error::Error + Send + Sync>> {
+		let EncodedProof(proof) = proof;
+		let proof = WarpSyncProof::<TBlock>::decode(&mut proof.as_slice())
+			.map_err(|e| format!("Proof decoding error: {:?}", e))?;
+		let last_header = proof.proofs.last()
+			.map(|p| p.header.clone())
+			.ok_or_else(|| "Empty proof".to_string())?;
+		let (next_set_id, next_authorities) = proof.verify(set_id, authorities)
+			.map_err(Box::new)?;
+		if proof.is_finished {
+			Ok(VerificationResult::Complete(next_set_id, next_authorities, last_header))
+		} else {
+			Ok(VerificationResult::Partial(next_set_id, next_authorities, last_header.hash()))
 		}
 	}
+
+	fn current_authorities(&self) -> AuthorityList {
+		self.authority_set.current_authority_list()
+	}
 }
 
+/// Warp sync proof generation or verification error.
 #[derive(Debug, derive_more::Display, derive_more::From)]
-pub enum HandleRequestError {
-	#[display(fmt = "Failed to decode request: {}.", _0)]
-	DecodeProto(prost::DecodeError),
-	#[display(fmt = "Failed to encode response: {}.", _0)]
-	EncodeProto(prost::EncodeError),
+pub enum Error {
+	/// Decoding error.
 	#[display(fmt = "Failed to decode block hash: {}.", _0)]
 	DecodeScale(codec::Error),
+	/// Client backend error.
 	Client(sp_blockchain::Error),
+	/// Invalid request data.
 	#[from(ignore)]
 	InvalidRequest(String),
+	/// Invalid warp proof.
 	#[from(ignore)]
 	InvalidProof(String),
-	#[display(fmt = "Failed to send response.")]
-	SendResponse,
+	/// Missing header or authority set change data.
 	#[display(fmt = "Missing required data to be able to answer request.")]
 	MissingData,
 }
+
+impl std::error::Error for Error {}
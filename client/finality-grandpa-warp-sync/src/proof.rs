@@ -27,7 +27,7 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
 };
 
-use crate::HandleRequestError;
+use crate::Error;
 
 /// The maximum size in bytes of the `WarpSyncProof`.
 pub(super) const MAX_WARP_SYNC_PROOF_SIZE: usize = 16 * 1024 * 1024;
@@ -46,8 +46,10 @@ pub struct WarpSyncFragment<Block: BlockT> {
 /// An accumulated proof of multiple authority set changes.
 #[derive(Decode, Encode)]
 pub struct WarpSyncProof<Block: BlockT> {
-	proofs: Vec<WarpSyncFragment<Block>>,
-	is_finished: bool,
+	/// Authority set change proofs, in order.
+	pub proofs: Vec<WarpSyncFragment<Block>>,
+	/// Whether the proof reaches the latest finalized block known to the prover.
+	pub is_finished: bool,
 }
 
 impl<Block: BlockT> WarpSyncProof<Block> {
@@ -58,7 +60,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 		backend: &Backend,
 		begin: Block::Hash,
 		set_changes: &AuthoritySetChanges<NumberFor<Block>>,
-	) -> Result<WarpSyncProof<Block>, HandleRequestError>
+	) -> Result<WarpSyncProof<Block>, Error>
 	where
 		Backend: ClientBackend<Block>,
 	{
@@ -67,10 +69,10 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 
 		let begin_number = blockchain
 			.block_number_from_id(&BlockId::Hash(begin))?
-			.ok_or_else(|| HandleRequestError::InvalidRequest("Missing start block".to_string()))?;
+			.ok_or_else(|| Error::InvalidRequest("Missing start block".to_string()))?;
 
 		if begin_number > blockchain.info().finalized_number {
-			return Err(HandleRequestError::InvalidRequest(
+			return Err(Error::InvalidRequest(
 				"Start block is not finalized".to_string(),
 			));
 		}
@@ -82,7 +84,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 		);
 
 		if canon_hash != begin {
-			return Err(HandleRequestError::InvalidRequest(
+			return Err(Error::InvalidRequest(
 				"Start block is not in the finalized chain".to_string(),
 			));
 		}
@@ -92,7 +94,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 		let mut proof_limit_reached = false;
 
 		let set_changes = set_changes.iter_from(begin_number)
-			.ok_or(HandleRequestError::MissingData)?;
+			.ok_or(Error::MissingData)?;
 
 		for (_, last_block) in set_changes {
 			let header = blockchain.header(BlockId::Number(*last_block))?.expect(
@@ -181,7 +183,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 		&self,
 		set_id: SetId,
 		authorities: AuthorityList,
-	) -> Result<(SetId, AuthorityList), HandleRequestError>
+	) -> Result<(SetId, AuthorityList), Error>
 	where
 		NumberFor<Block>: BlockNumberOps,
 	{
@@ -192,10 +194,10 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 			proof
 				.justification
 				.verify(current_set_id, &current_authorities)
-				.map_err(|err| HandleRequestError::InvalidProof(err.to_string()))?;
+				.map_err(|err| Error::InvalidProof(err.to_string()))?;
 
 			if proof.justification.target().1 != proof.header.hash() {
-				return Err(HandleRequestError::InvalidProof(
+				return Err(Error::InvalidProof(
 					"mismatch between header and justification".to_owned()
 				));
 			}
@@ -206,7 +208,7 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 			} else if fragment_num != self.proofs.len() - 1 {
 				// Only the last fragment of the proof is allowed to be missing the authority
 				// set change.
-				return Err(HandleRequestError::InvalidProof(
+				return Err(Error::InvalidProof(
 					"Header is missing authority set change digest".to_string(),
 				));
 			}
@@ -218,31 +220,31 @@ impl<Block: BlockT> WarpSyncProof<Block> {
 
 #[cfg(test)]
 mod tests {
-	use crate::WarpSyncProof;
+	use crate::{Error, WarpSyncProof};
 	use codec::Encode;
 	use rand::prelude::*;
 	use sc_block_builder::BlockBuilderProvider;
 	use sc_finality_grandpa::{AuthoritySetChanges, GrandpaJustification};
 	use sp_blockchain::HeaderBackend;
 	use sp_consensus::BlockOrigin;
-	use sp_finality_grandpa::GRANDPA_ENGINE_ID;
+	use sp_finality_grandpa::{AuthorityList, SetId, GRANDPA_ENGINE_ID};
 	use sp_keyring::Ed25519Keyring;
 	use sp_runtime::{generic::BlockId, traits::Header as _};
 	use std::sync::Arc;
 	use substrate_test_runtime_client::{
-		ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, TestClientBuilder,
+		runtime::Block, ClientBlockImportExt, ClientExt, DefaultTestClientBuilderExt, TestClientBuilder,
 		TestClientBuilderExt,
 	};
 
-	#[test]
-	fn warp_sync_proof_generate_verify() {
+	/// Build a chain of 100 blocks with an authority set change every 10 blocks and return a
+	/// warp sync proof starting at genesis, together with the last set id and authorities.
+	fn generate_proof() -> (WarpSyncProof<Block>, SetId, AuthorityList) {
 		let mut rng = rand::rngs::StdRng::from_seed([0; 32]);
 		let builder = TestClientBuilder::new();
 		let backend = builder.backend();
 		let mut client = Arc::new(builder.build());
 
 		let available_authorities = Ed25519Keyring::iter().collect::<Vec<_>>();
-		let genesis_authorities = vec![(Ed25519Keyring::Alice.public().into(), 1)];
 
 		let mut current_authorities = vec![Ed25519Keyring::Alice];
 		let mut current_set_id = 0;
@@ -348,15 +350,85 @@ mod tests {
 		let warp_sync_proof =
 			WarpSyncProof::generate(&*backend, genesis_hash, &authority_set_changes).unwrap();
 
-		// verifying the proof should yield the last set id and authorities
-		let (new_set_id, new_authorities) = warp_sync_proof.verify(0, genesis_authorities).unwrap();
-
-		let expected_authorities = current_authorities
+		let current_authorities = current_authorities
 			.iter()
 			.map(|keyring| (keyring.public().into(), 1))
 			.collect::<Vec<_>>();
 
+		(warp_sync_proof, current_set_id, current_authorities)
+	}
+
+	fn genesis_authorities() -> AuthorityList {
+		vec![(Ed25519Keyring::Alice.public().into(), 1)]
+	}
+
+	#[test]
+	fn warp_sync_proof_generate_verify() {
+		let (warp_sync_proof, current_set_id, current_authorities) = generate_proof();
+
+		// verifying the proof should yield the last set id and authorities
+		let (new_set_id, new_authorities) =
+			warp_sync_proof.verify(0, genesis_authorities()).unwrap();
+
 		assert_eq!(new_set_id, current_set_id);
-		assert_eq!(new_authorities, expected_authorities);
+		assert_eq!(new_authorities, current_authorities);
+	}
+
+	#[test]
+	fn warp_sync_proof_with_wrong_authorities_is_rejected() {
+		let (warp_sync_proof, _, _) = generate_proof();
+
+		let authorities = vec![(Ed25519Keyring::Bob.public().into(), 1)];
+		assert!(matches!(warp_sync_proof.verify(0, authorities), Err(Error::InvalidProof(_))));
+		assert!(matches!(
+			warp_sync_proof.verify(1, genesis_authorities()),
+			Err(Error::InvalidProof(_)),
+		));
+	}
+
+	#[test]
+	fn warp_sync_proof_with_forged_set_change_is_rejected() {
+		let (mut warp_sync_proof, _, _) = generate_proof();
+
+		// replace the authority set change signaled by the first fragment with our own
+		let forged = sp_runtime::generic::DigestItem::Consensus(
+			GRANDPA_ENGINE_ID,
+			sp_finality_grandpa::ConsensusLog::ScheduledChange(
+				sp_finality_grandpa::ScheduledChange {
+					delay: 0u64,
+					next_authorities: vec![(Ed25519Keyring::Ferdie.public().into(), 1)],
+				},
+			)
+			.encode(),
+		);
+		let header = &mut warp_sync_proof.proofs[0].header;
+		header.digest_mut().logs.retain(|log| log.as_consensus().is_none());
+		header.digest_mut().logs.push(forged);
+
+		assert!(matches!(
+			warp_sync_proof.verify(0, genesis_authorities()),
+			Err(Error::InvalidProof(_)),
+		));
+	}
+
+	#[test]
+	fn warp_sync_proof_with_missing_fragment_is_rejected() {
+		let (mut warp_sync_proof, _, _) = generate_proof();
+
+		// skipping a set change leaves the next justification signed by an unknown set
+		warp_sync_proof.proofs.remove(0);
+		assert!(matches!(
+			warp_sync_proof.verify(0, genesis_authorities()),
+			Err(Error::InvalidProof(_)),
+		));
+
+		// swapping justifications between fragments is detected as well
+		let (mut warp_sync_proof, _, _) = generate_proof();
+		let justification = warp_sync_proof.proofs[2].justification.clone();
+		warp_sync_proof.proofs[1].justification = justification;
+		assert!(matches!(
+			warp_sync_proof.verify(0, genesis_authorities()),
+			Err(Error::InvalidProof(_)),
+		));
 	}
 }
@@ -123,6 +123,11 @@ where N: Add<Output=N> + Ord + Clone + Debug,
 		)
 	}
 
+	/// Clone the current authority list (for the current set ID).
+	pub fn current_authority_list(&self) -> AuthorityList {
+		self.inner().current_authorities.clone()
+	}
+
 	/// Clone the inner `AuthoritySet`.
 	pub fn clone_inner(&self) -> AuthoritySet<H, N> {
 		self.inner().clone()
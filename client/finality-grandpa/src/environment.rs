@@ -29,7 +29,8 @@ use log::{debug, warn};
 use parity_scale_codec::{Decode, Encode};
 use parking_lot::RwLock;
 
-use sc_client_api::{backend::{Backend, apply_aux}, utils::is_descendent_of};
+use sc_client_api::{backend::{AuxStore, Backend, apply_aux}, utils::is_descendent_of};
+use sc_slashing_protection::VoteKind;
 use finality_grandpa::{
 	BlockNumberOps, Error as GrandpaError, round::State as RoundState,
 	voter, voter_set::VoterSet,
@@ -627,6 +628,26 @@ where
 	Ok(tree_route.retracted().iter().skip(1).map(|e| e.hash).collect())
 }
 
+/// Check with the signing history that the local voter doesn't equivocate by signing the given
+/// vote, and record it.
+fn check_and_record_vote<Client: AuxStore>(
+	client: &Client,
+	local_id: &AuthorityId,
+	set_id: SetId,
+	round: RoundNumber,
+	kind: VoteKind,
+	target: impl Encode,
+) -> Result<(), Error> {
+	sc_slashing_protection::check_and_record_vote(
+		client,
+		local_id.as_ref(),
+		set_id,
+		round,
+		kind,
+		&target.encode(),
+	).map_err(|e| Error::Safety(e.to_string()))
+}
+
 impl<B, Block: BlockT, C, N, SC, VR> voter::Environment<Block::Hash, NumberFor<Block>>
 	for Environment<B, Block, C, N, SC, VR>
 where
@@ -854,6 +875,15 @@ where
 			None => return Ok(()),
 		};
 
+		check_and_record_vote(
+			&*self.client,
+			&local_id,
+			self.set_id,
+			round,
+			VoteKind::PrimaryPropose,
+			(propose.target_hash, propose.target_number),
+		)?;
+
 		self.update_voter_set_state(|voter_set_state| {
 			let (completed_rounds, current_rounds) = voter_set_state.with_current_round(round)?;
 			let current_round = current_rounds.get(&round)
@@ -891,6 +921,15 @@ where
 			None => return Ok(()),
 		};
 
+		check_and_record_vote(
+			&*self.client,
+			&local_id,
+			self.set_id,
+			round,
+			VoteKind::Prevote,
+			(prevote.target_hash, prevote.target_number),
+		)?;
+
 		let report_prevote_metrics = |prevote: &Prevote<Block>| {
 			telemetry!(
 				self.telemetry;
@@ -953,6 +992,15 @@ where
 			None => return Ok(()),
 		};
 
+		check_and_record_vote(
+			&*self.client,
+			&local_id,
+			self.set_id,
+			round,
+			VoteKind::Precommit,
+			(precommit.target_hash, precommit.target_number),
+		)?;
+
 		let report_precommit_metrics = |precommit: &Precommit<Block>| {
 			telemetry!(
 				self.telemetry;
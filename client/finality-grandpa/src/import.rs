@@ -19,10 +19,12 @@
 use std::{sync::Arc, collections::HashMap};
 
 use log::debug;
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 
 use sp_blockchain::{BlockStatus, well_known_cache_keys};
-use sc_client_api::{backend::Backend, utils::is_descendent_of};
+use sc_client_api::{
+	backend::Backend, utils::is_descendent_of, CallExecutor, ExecutionStrategy,
+};
 use sc_telemetry::TelemetryHandle;
 use sp_utils::mpsc::TracingUnboundedSender;
 use sp_api::TransactionFor;
@@ -33,7 +35,7 @@ use sp_consensus::{
 	BlockCheckParams, BlockImportParams, BlockOrigin, ImportResult, JustificationImport,
 	SelectChain,
 };
-use sp_finality_grandpa::{ConsensusLog, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
+use sp_finality_grandpa::{AuthorityList, ConsensusLog, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
 use sp_runtime::Justification;
 use sp_runtime::generic::{BlockId, OpaqueDigestItemId};
 use sp_runtime::traits::{
@@ -41,7 +43,9 @@ use sp_runtime::traits::{
 };
 
 use crate::{Error, CommandOrError, NewAuthoritySet, VoterCommand};
-use crate::authorities::{AuthoritySet, SharedAuthoritySet, DelayKind, PendingChange};
+use crate::authorities::{
+	AuthoritySet, AuthoritySetChanges, SharedAuthoritySet, DelayKind, PendingChange,
+};
 use crate::environment::finalize_block;
 use crate::justification::GrandpaJustification;
 use crate::notification::GrandpaJustificationSender;
@@ -446,11 +450,21 @@ impl<BE, Block: BlockT, Client, SC> BlockImport<Block>
 		// early exit if block already in chain, otherwise the check for
 		// authority changes will error when trying to re-import a change block
 		match self.inner.status(BlockId::Hash(hash)) {
+			Ok(BlockStatus::InChain) if block.state.is_some() => {
+				// the block is re-imported together with its state, there are no
+				// authority set changes to process.
+				block.justifications.take();
+				return (&*self.inner).import_block(block, new_cache).await
+			},
 			Ok(BlockStatus::InChain) => return Ok(ImportResult::AlreadyInChain),
 			Ok(BlockStatus::Unknown) => {},
 			Err(e) => return Err(ConsensusError::ClientImport(e.to_string())),
 		}
 
+		if block.state.is_some() {
+			return self.import_state(block, new_cache).await
+		}
+
 		// on initial sync we will restrict logging under info to avoid spam.
 		let initial_sync = block.origin == BlockOrigin::NetworkInitialSync;
 
@@ -627,6 +641,92 @@ impl<Backend, Block: BlockT, Client, SC> GrandpaBlockImport<Backend, Block, Clie
 	}
 }
 
+impl<BE, Block: BlockT, Client, SC> GrandpaBlockImport<BE, Block, Client, SC>
+where
+	BE: Backend<Block>,
+	Client: crate::ClientForGrandpa<Block, BE>,
+	for<'a> &'a Client:
+		BlockImport<Block, Error = ConsensusError, Transaction = TransactionFor<Client, Block>>,
+	NumberFor<Block>: finality_grandpa::BlockNumberOps,
+{
+	/// Import a block together with its state, e.g. the target block of a warp sync.
+	///
+	/// The sync module is trusted to have verified the finality of the block, so the
+	/// block is finalized right away and the authority set is reset to the one found
+	/// in the imported state.
+	async fn import_state(
+		&mut self,
+		mut block: BlockImportParams<Block, TransactionFor<Client, Block>>,
+		new_cache: HashMap<well_known_cache_keys::Id, Vec<u8>>,
+	) -> Result<ImportResult, ConsensusError> {
+		let hash = block.post_hash();
+		let number = *block.header.number();
+
+		block.finalized = true;
+		block.justifications.take();
+
+		let imported_aux = match (&*self.inner).import_block(block, new_cache).await {
+			Ok(ImportResult::Imported(aux)) => aux,
+			Ok(r) => return Ok(r),
+			Err(e) => return Err(ConsensusError::ClientImport(e.to_string())),
+		};
+
+		let block_id = BlockId::hash(hash);
+		let authorities: AuthorityList = self.call_runtime(&block_id, "GrandpaApi_grandpa_authorities")?;
+		let set_id: SetId = self.call_runtime(&block_id, "GrandpaApi_current_set_id")?;
+
+		let authority_set = AuthoritySet::new(
+			authorities.clone(),
+			set_id,
+			fork_tree::ForkTree::new(),
+			Vec::new(),
+			AuthoritySetChanges::empty(),
+		).ok_or_else(|| ConsensusError::ClientImport(
+			"Invalid authority list in the imported state".to_string()
+		))?;
+
+		let new_set = NewAuthoritySet { canon_number: number, canon_hash: hash, set_id, authorities };
+
+		{
+			let mut locked = self.authority_set.inner_locked();
+			*locked = authority_set;
+
+			crate::aux_schema::update_authority_set::<Block, _, _>(
+				&locked,
+				Some(&new_set),
+				|insert| self.inner.insert_aux(insert, &[]),
+			).map_err(|e| ConsensusError::ClientImport(e.to_string()))?;
+		}
+
+		debug!(
+			target: "afg",
+			"Imported state for block #{} ({:?}), reset authority set to set id {}.",
+			number,
+			hash,
+			set_id,
+		);
+
+		let _ = self.send_voter_commands.unbounded_send(VoterCommand::ChangeAuthorities(new_set));
+
+		Ok(ImportResult::Imported(imported_aux))
+	}
+
+	fn call_runtime<T: Decode>(
+		&self,
+		at: &BlockId<Block>,
+		method: &str,
+	) -> Result<T, ConsensusError> {
+		self.inner.executor()
+			.call(at, method, &[], ExecutionStrategy::NativeElseWasm, None)
+			.and_then(|result| Decode::decode(&mut &result[..])
+				.map_err(|err| sp_blockchain::Error::CallResultDecode(
+					"failed to decode GRANDPA runtime call result", err
+				)))
+			.map_err(|e| ConsensusError::ClientImport(e.to_string()))
+	}
+
+}
+
 impl<BE, Block: BlockT, Client, SC> GrandpaBlockImport<BE, Block, Client, SC>
 where
 	BE: Backend<Block>,
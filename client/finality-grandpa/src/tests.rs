@@ -205,6 +205,10 @@ sp_api::mock_impl_runtime_apis! {
 			self.inner.genesis_authorities.clone()
 		}
 
+		fn current_set_id(&self) -> SetId {
+			0
+		}
+
 		fn submit_report_equivocation_unsigned_extrinsic(
 			_equivocation_proof: EquivocationProof<Hash, BlockNumber>,
 			_key_owner_proof: OpaqueKeyOwnershipProof,
@@ -1678,3 +1682,128 @@ fn grandpa_environment_doesnt_send_equivocation_reports_for_itself() {
 	let equivocation_proof = sp_finality_grandpa::Equivocation::Prevote(equivocation);
 	assert!(environment.report_equivocation(equivocation_proof).is_ok());
 }
+
+/// Build a chain whose genesis state contains the given GRANDPA authorities and set id and
+/// return its third block together with its state, as it would be downloaded by warp sync.
+fn block_with_state(
+	authorities: AuthorityList,
+	set_id: SetId,
+) -> (Block, sp_consensus::ImportedState<Block>) {
+	use sc_client_api::StorageProvider;
+	use substrate_test_runtime_client::{
+		ClientBlockImportExt, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	let mut source = TestClientBuilder::new()
+		.add_extra_storage(
+			sp_finality_grandpa::GRANDPA_AUTHORITIES_KEY.to_vec(),
+			sp_finality_grandpa::VersionedAuthorityList::from(authorities).encode(),
+		)
+		.add_extra_storage(
+			substrate_test_runtime_client::runtime::GRANDPA_SET_ID_KEY.to_vec(),
+			set_id.encode(),
+		)
+		.build();
+	let mut block = None;
+	for _ in 0..3 {
+		let new_block = source.new_block(Default::default()).unwrap().build().unwrap().block;
+		block_on(source.import(BlockOrigin::Own, new_block.clone())).unwrap();
+		block = Some(new_block);
+	}
+	let block = block.unwrap();
+
+	let key_values = source
+		.storage_pairs(&BlockId::Hash(block.hash()), &sp_core::storage::StorageKey(Vec::new()))
+		.unwrap()
+		.into_iter()
+		.map(|(key, value)| (key.0, value.0))
+		.collect();
+	let state = sp_consensus::ImportedState {
+		block: block.hash(),
+		state: sp_state_machine::KeyValueStates(vec![sp_state_machine::KeyValueStorageLevel {
+			storage_key: Vec::new(),
+			key_values,
+			complete: true,
+		}]),
+	};
+
+	(block, state)
+}
+
+fn state_import_params(
+	block: Block,
+	state: sp_consensus::ImportedState<Block>,
+) -> BlockImportParams<Block, ()> {
+	let mut import = BlockImportParams::new(BlockOrigin::NetworkInitialSync, block.header);
+	import.body = Some(block.extrinsics);
+	import.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+	import.state = Some(state);
+	import
+}
+
+#[test]
+fn importing_state_resets_authority_set() {
+	let voters = make_ids(&[Ed25519Keyring::Alice]);
+	let mut net = GrandpaTestNet::new(TestApi::new(voters), 1, 0);
+
+	let client = net.peer(0).client().clone();
+	let (mut block_import, _, link) = net.make_block_import(client.clone());
+	let mut link = link.lock().take().unwrap();
+
+	let new_authorities = make_ids(&[Ed25519Keyring::Bob, Ed25519Keyring::Charlie]);
+	let (block, state) = block_with_state(new_authorities.clone(), 7);
+	let hash = block.hash();
+
+	assert_matches!(
+		block_on(block_import.import_block(state_import_params(block, state), HashMap::new())),
+		Ok(ImportResult::Imported(_))
+	);
+
+	// the imported block is finalized and the authority set is reset from its state
+	let full_client = client.as_full().unwrap();
+	assert_eq!(full_client.info().finalized_hash, hash);
+
+	let expected_set = AuthoritySet::genesis(new_authorities.clone()).map(|mut set| {
+		set.set_id = 7;
+		set
+	}).unwrap();
+	assert_eq!(link.shared_authority_set().set_id(), 7);
+	assert_eq!(*link.shared_authority_set().inner(), expected_set);
+	assert_eq!(
+		aux_schema::load_authorities::<_, Hash, BlockNumber>(&*full_client),
+		Some(expected_set),
+	);
+
+	match link.voter_commands_rx.try_next() {
+		Ok(Some(VoterCommand::ChangeAuthorities(new_set))) => {
+			assert_eq!(new_set.set_id, 7);
+			assert_eq!(new_set.authorities, new_authorities);
+			assert_eq!(new_set.canon_hash, hash);
+			assert_eq!(new_set.canon_number, 3);
+		},
+		_ => panic!("Expected the voter to be restarted with the new authorities"),
+	}
+}
+
+#[test]
+fn importing_state_without_authorities_fails() {
+	let voters = make_ids(&[Ed25519Keyring::Alice]);
+	let mut net = GrandpaTestNet::new(TestApi::new(voters.clone()), 1, 0);
+
+	let client = net.peer(0).client().clone();
+	let (mut block_import, _, link) = net.make_block_import(client.clone());
+	let link = link.lock().take().unwrap();
+
+	let (block, state) = block_with_state(Vec::new(), 7);
+	assert_matches!(
+		block_on(block_import.import_block(state_import_params(block, state), HashMap::new())),
+		Err(sp_consensus::Error::ClientImport(_))
+	);
+
+	// the previous authority set is left untouched
+	assert_eq!(link.shared_authority_set().set_id(), 0);
+	assert_eq!(
+		*link.shared_authority_set().inner(),
+		AuthoritySet::genesis(voters).unwrap(),
+	);
+}
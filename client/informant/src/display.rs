@@ -93,10 +93,25 @@ impl<B: BlockT> InformantDisplay<B> {
 				(diff_bytes_inbound, diff_bytes_outbound)
 			};
 
-		let (level, status, target) = match (net_status.sync_state, net_status.best_seen_block) {
-			(SyncState::Idle, _) => ("💤", "Idle".into(), "".into()),
-			(SyncState::Downloading, None) => ("⚙️ ", format!("Preparing{}", speed), "".into()),
-			(SyncState::Downloading, Some(n)) => (
+		let (level, status, target) = match (
+			net_status.sync_state,
+			net_status.best_seen_block,
+			net_status.state_sync,
+			net_status.warp_sync,
+		) {
+			(_, _, _, Some(warp)) => (
+				"⏩",
+				"Warping".into(),
+				format!(", {}, ({:.2}) Mib", warp.phase, (warp.total_bytes as f32) / (1024f32 * 1024f32)),
+			),
+			(_, _, Some(state), _) => (
+				"⚙️ ",
+				"Downloading state".into(),
+				format!(", {}%, ({:.2}) Mib", state.percentage, (state.size as f32) / (1024f32 * 1024f32)),
+			),
+			(SyncState::Idle, _, _, _) => ("💤", "Idle".into(), "".into()),
+			(SyncState::Downloading, None, _, _) => ("⚙️ ", format!("Preparing{}", speed), "".into()),
+			(SyncState::Downloading, Some(n), None, _) => (
 				"⚙️ ",
 				format!("Syncing{}", speed),
 				format!(", target=#{}", n),
@@ -0,0 +1,220 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Encryption of the key files of the local keystore.
+//!
+//! A key file either contains the secret URI of the key as a JSON string, for keystores without
+//! password, or a JSON object with the secret encrypted with XChaCha20-Poly1305. The encryption
+//! key is derived from the keystore password with scrypt, whose parameters are stored in each
+//! file. The name of the key file is authenticated along with the secret, so an encrypted secret
+//! can't be moved to another key file.
+
+use std::collections::HashMap;
+
+use chacha20poly1305::{
+	aead::{generic_array::GenericArray, Aead, NewAead, Payload},
+	XChaCha20Poly1305,
+};
+use parking_lot::Mutex;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{ExposeSecret, SecretString};
+
+use crate::{Error, Result};
+
+/// Version of the encrypted key file format.
+const VERSION: u32 = 1;
+
+/// Scrypt cost parameter of new key files.
+#[cfg(not(test))]
+const SCRYPT_LOG_N: u8 = 15;
+#[cfg(test)]
+const SCRYPT_LOG_N: u8 = 4;
+
+/// Contents of a key file.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyFile {
+	/// Secret URI stored in plain text.
+	Plain(String),
+	/// Encrypted [`KeySecret`].
+	Encrypted(EncryptedSecret),
+}
+
+/// Secret of a key.
+#[derive(Serialize, Deserialize)]
+pub struct KeySecret {
+	/// Secret URI of the key.
+	pub suri: String,
+	/// Password of the secret URI, overriding any password in `suri`.
+	pub password: Option<String>,
+}
+
+/// Scrypt parameters.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KdfParams {
+	log_n: u8,
+	r: u32,
+	p: u32,
+	/// Hex encoded salt.
+	salt: String,
+}
+
+/// A [`KeySecret`] encrypted with a key derived from the keystore password.
+#[derive(Serialize, Deserialize)]
+pub struct EncryptedSecret {
+	version: u32,
+	kdf: KdfParams,
+	/// Hex encoded nonce.
+	nonce: String,
+	/// Hex encoded ciphertext.
+	ciphertext: String,
+}
+
+/// Encrypts and decrypts key secrets with a password.
+///
+/// Deriving an encryption key is slow by design, so the derived keys are cached, and all the
+/// secrets encrypted by a cipher share the same derived key.
+pub struct Cipher {
+	password: SecretString,
+	params: KdfParams,
+	keys: Mutex<HashMap<KdfParams, [u8; 32]>>,
+}
+
+impl Cipher {
+	/// Create a cipher for the given password.
+	pub fn new(password: SecretString) -> Self {
+		let mut salt = [0u8; 32];
+		rand::rngs::OsRng.fill_bytes(&mut salt);
+		Cipher {
+			password,
+			params: KdfParams { log_n: SCRYPT_LOG_N, r: 8, p: 1, salt: hex::encode(salt) },
+			keys: Mutex::new(HashMap::new()),
+		}
+	}
+
+	fn key(&self, params: &KdfParams) -> Result<[u8; 32]> {
+		if let Some(key) = self.keys.lock().get(params) {
+			return Ok(*key)
+		}
+
+		let invalid = || Error::InvalidKeyFile("Invalid key derivation parameters".into());
+		let salt = hex::decode(&params.salt).map_err(|_| invalid())?;
+		let scrypt_params = scrypt::ScryptParams::new(params.log_n, params.r, params.p)
+			.map_err(|_| invalid())?;
+		let mut key = [0u8; 32];
+		scrypt::scrypt(
+			self.password.expose_secret().as_bytes(),
+			&salt,
+			&scrypt_params,
+			&mut key,
+		).map_err(|_| invalid())?;
+
+		self.keys.lock().insert(params.clone(), key);
+		Ok(key)
+	}
+
+	/// Encrypt a secret, authenticating `aad` along with it.
+	pub fn encrypt(&self, secret: &KeySecret, aad: &[u8]) -> Result<EncryptedSecret> {
+		let key = self.key(&self.params)?;
+		let mut nonce = [0u8; 24];
+		rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+		let plaintext = serde_json::to_vec(secret)?;
+		let ciphertext = XChaCha20Poly1305::new(GenericArray::from_slice(&key))
+			.encrypt(GenericArray::from_slice(&nonce), Payload { msg: &plaintext, aad })
+			.map_err(|_| Error::InvalidKeyFile("Encryption failed".into()))?;
+
+		Ok(EncryptedSecret {
+			version: VERSION,
+			kdf: self.params.clone(),
+			nonce: hex::encode(nonce),
+			ciphertext: hex::encode(ciphertext),
+		})
+	}
+
+	/// Decrypt a secret, checking that it was encrypted along with `aad`.
+	///
+	/// Fails with [`Error::InvalidPassword`] if the secret was encrypted with another password or
+	/// was tampered with.
+	pub fn decrypt(&self, encrypted: &EncryptedSecret, aad: &[u8]) -> Result<KeySecret> {
+		if encrypted.version != VERSION {
+			return Err(Error::InvalidKeyFile(format!(
+				"Unsupported key file version {}",
+				encrypted.version,
+			)))
+		}
+		let nonce = hex::decode(&encrypted.nonce).ok().filter(|n| n.len() == 24)
+			.ok_or_else(|| Error::InvalidKeyFile("Invalid nonce".into()))?;
+		let ciphertext = hex::decode(&encrypted.ciphertext)
+			.map_err(|_| Error::InvalidKeyFile("Invalid ciphertext".into()))?;
+
+		let key = self.key(&encrypted.kdf)?;
+		let plaintext = XChaCha20Poly1305::new(GenericArray::from_slice(&key))
+			.decrypt(GenericArray::from_slice(&nonce), Payload { msg: &ciphertext, aad })
+			.map_err(|_| Error::InvalidPassword)?;
+
+		serde_json::from_slice(&plaintext).map_err(Into::into)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn secret() -> KeySecret {
+		KeySecret { suri: "//Alice".into(), password: Some("password".into()) }
+	}
+
+	#[test]
+	fn encrypt_decrypt_works() {
+		let cipher = Cipher::new(SecretString::new("password".into()));
+		let encrypted = cipher.encrypt(&secret(), b"file").unwrap();
+		assert!(!encrypted.ciphertext.contains(&hex::encode("//Alice")));
+
+		// A new cipher has to derive the key again.
+		let cipher = Cipher::new(SecretString::new("password".into()));
+		let decrypted = cipher.decrypt(&encrypted, b"file").unwrap();
+		assert_eq!(decrypted.suri, "//Alice");
+		assert_eq!(decrypted.password.as_deref(), Some("password"));
+	}
+
+	#[test]
+	fn decrypt_checks_password_and_file() {
+		let cipher = Cipher::new(SecretString::new("password".into()));
+		let encrypted = cipher.encrypt(&secret(), b"file").unwrap();
+
+		assert!(matches!(cipher.decrypt(&encrypted, b"other file"), Err(Error::InvalidPassword)));
+		let cipher = Cipher::new(SecretString::new("wrong".into()));
+		assert!(matches!(cipher.decrypt(&encrypted, b"file"), Err(Error::InvalidPassword)));
+	}
+
+	#[test]
+	fn key_file_formats_are_distinguished() {
+		let cipher = Cipher::new(SecretString::new("password".into()));
+		let encrypted = serde_json::to_string(
+			&KeyFile::Encrypted(cipher.encrypt(&secret(), b"file").unwrap()),
+		).unwrap();
+
+		assert!(matches!(serde_json::from_str(&encrypted).unwrap(), KeyFile::Encrypted(_)));
+		assert!(matches!(
+			serde_json::from_str("\"//Alice\"").unwrap(),
+			KeyFile::Plain(suri) if suri == "//Alice"
+		));
+	}
+}
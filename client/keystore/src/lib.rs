@@ -23,10 +23,17 @@ use std::io;
 use sp_core::crypto::KeyTypeId;
 use sp_keystore::Error as TraitError;
 
+/// Encryption of the key files
+mod encryption;
+
 /// Local keystore implementation
 mod local;
 pub use local::LocalKeystore;
 
+/// Remote keystore implementation
+pub mod remote;
+pub use remote::RemoteKeystore;
+
 /// Keystore error.
 #[derive(Debug, derive_more::Display, derive_more::From)]
 pub enum Error {
@@ -49,6 +56,17 @@ pub enum Error {
 	/// Keystore unavailable
 	#[display(fmt="Keystore unavailable")]
 	Unavailable,
+	/// The keystore is encrypted and no password was given
+	#[display(fmt="The keystore is encrypted, a password is required")]
+	PasswordRequired,
+	/// Invalid key file
+	#[display(fmt="Invalid key file: {}", _0)]
+	#[from(ignore)]
+	InvalidKeyFile(String),
+	/// Invalid remote signer configuration
+	#[display(fmt="Invalid remote signer configuration: {}", _0)]
+	#[from(ignore)]
+	InvalidRemoteSigner(String),
 }
 
 /// Keystore Result
@@ -58,10 +76,14 @@ impl From<Error> for TraitError {
 	fn from(error: Error) -> Self {
 		match error {
 			Error::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
-			Error::InvalidSeed | Error::InvalidPhrase | Error::InvalidPassword => {
+			Error::InvalidSeed | Error::InvalidPhrase | Error::InvalidPassword
+				| Error::PasswordRequired => {
 				TraitError::ValidationError(error.to_string())
 			},
 			Error::Unavailable => TraitError::Unavailable,
+			Error::InvalidKeyFile(_) | Error::InvalidRemoteSigner(_) => {
+				TraitError::Other(error.to_string())
+			},
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
 		}
@@ -21,7 +21,7 @@ use std::{
 	collections::{HashMap, HashSet},
 	fs::{self, File},
 	io::Write,
-	path::PathBuf,
+	path::{Path, PathBuf},
 	sync::Arc,
 };
 use async_trait::async_trait;
@@ -40,7 +40,7 @@ use sp_keystore::{
 };
 use sp_application_crypto::{ed25519, sr25519, ecdsa, AppPair, AppKey, IsWrappedBy};
 
-use crate::{Result, Error};
+use crate::{Result, Error, encryption::{Cipher, KeyFile, KeySecret}};
 
 /// A local based keystore that is either memory-based or filesystem-based.
 pub struct LocalKeystore(RwLock<KeystoreInner>);
@@ -65,6 +65,14 @@ impl LocalKeystore {
 	pub fn key_pair<Pair: AppPair>(&self, public: &<Pair as AppKey>::Public) -> Result<Option<Pair>> {
 		self.0.read().key_pair::<Pair>(public)
 	}
+
+	/// Encrypt the key files stored in plain text with the keystore password.
+	///
+	/// The keys are unchanged: the password is still used as the password of their secret URI.
+	/// Returns the number of encrypted key files.
+	pub fn encrypt_plain_keys(&self) -> Result<usize> {
+		self.0.read().encrypt_plain_keys()
+	}
 }
 
 #[async_trait]
@@ -288,7 +296,7 @@ impl SyncCryptoStore for LocalKeystore {
 
 	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
 		public_keys.iter()
-			.all(|(p, t)| self.0.read().key_phrase_by_type(&p, *t).ok().flatten().is_some())
+			.all(|(p, t)| self.0.read().key_secret_by_type(&p, *t).ok().flatten().is_some())
 	}
 
 	fn sr25519_vrf_sign(
@@ -346,20 +354,43 @@ struct KeystoreInner {
 	/// Map over `(KeyTypeId, Raw public key)` -> `Key phrase/seed`
 	additional: HashMap<(KeyTypeId, Vec<u8>), String>,
 	password: Option<SecretString>,
+	/// Cipher of the key files, if a password is set.
+	cipher: Option<Cipher>,
 }
 
 impl KeystoreInner {
 	/// Open the store at the given path.
 	///
-	/// Optionally takes a password that will be used to encrypt/decrypt the keys.
+	/// Optionally takes a password that will be used to encrypt/decrypt the keys. Fails if the
+	/// store contains encrypted keys and the password is wrong.
 	fn open<T: Into<PathBuf>>(path: T, password: Option<SecretString>) -> Result<Self> {
 		let path = path.into();
 		fs::create_dir_all(&path)?;
 
-		let instance = Self { path: Some(path), additional: HashMap::new(), password };
+		let cipher = password.clone().map(Cipher::new);
+		let instance = Self { path: Some(path), additional: HashMap::new(), password, cipher };
+		instance.check_password()?;
 		Ok(instance)
 	}
 
+	/// Check that the first encrypted key file found can be decrypted.
+	fn check_password(&self) -> Result<()> {
+		for path in self.key_file_paths()? {
+			if let KeyFile::Encrypted(_) = Self::read_key_file(&path)? {
+				if self.cipher.is_none() {
+					log::warn!(
+						target: "keystore",
+						"The keystore contains encrypted keys, they can't be used without a password",
+					);
+				} else {
+					self.key_secret_from_file(&path)?;
+				}
+				break
+			}
+		}
+		Ok(())
+	}
+
 	/// Get the password for this store.
 	fn password(&self) -> Option<&str> {
 		self.password.as_ref()
@@ -372,7 +403,8 @@ impl KeystoreInner {
 		Self {
 			path: None,
 			additional: HashMap::new(),
-			password: None
+			password: None,
+			cipher: None,
 		}
 	}
 
@@ -399,9 +431,7 @@ impl KeystoreInner {
 	/// Places it into the file system store, if a path is configured.
 	fn insert_unknown(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<()> {
 		if let Some(path) = self.key_file_path(public, key_type) {
-			let mut file = File::create(path).map_err(Error::Io)?;
-			serde_json::to_writer(&file, &suri).map_err(Error::Json)?;
-			file.flush().map_err(Error::Io)?;
+			self.write_key_file(&path, suri)?;
 		}
 		Ok(())
 	}
@@ -413,9 +443,7 @@ impl KeystoreInner {
 	fn generate_by_type<Pair: PairT>(&mut self, key_type: KeyTypeId) -> Result<Pair> {
 		let (pair, phrase, _) = Pair::generate_with_phrase(self.password());
 		if let Some(path) = self.key_file_path(pair.public().as_slice(), key_type) {
-			let mut file = File::create(path)?;
-			serde_json::to_writer(&file, &phrase)?;
-			file.flush()?;
+			self.write_key_file(&path, &phrase)?;
 		} else {
 			self.insert_ephemeral_pair(&pair, &phrase, key_type);
 		}
@@ -435,10 +463,56 @@ impl KeystoreInner {
 		Ok(pair)
 	}
 
-	/// Get the key phrase for a given public key and key type.
-	fn key_phrase_by_type(&self, public: &[u8], key_type: KeyTypeId) -> Result<Option<String>> {
+	/// Write the secret URI of a key to its file, encrypted if a password is set.
+	///
+	/// The file is replaced atomically.
+	fn write_key_file(&self, path: &Path, suri: &str) -> Result<()> {
+		let contents = match &self.cipher {
+			Some(cipher) => {
+				let secret = KeySecret {
+					suri: suri.into(),
+					password: self.password().map(Into::into),
+				};
+				KeyFile::Encrypted(cipher.encrypt(&secret, Self::key_file_name(path)?.as_bytes())?)
+			},
+			None => KeyFile::Plain(suri.into()),
+		};
+
+		let tmp_path = path.with_extension("tmp");
+		let mut file = File::create(&tmp_path)?;
+		serde_json::to_writer(&file, &contents)?;
+		file.flush()?;
+		file.sync_all()?;
+		fs::rename(tmp_path, path)?;
+		Ok(())
+	}
+
+	/// Read a key file.
+	fn read_key_file(path: &Path) -> Result<KeyFile> {
+		let file = File::open(path)?;
+		serde_json::from_reader(&file).map_err(Into::into)
+	}
+
+	fn key_file_name(path: &Path) -> Result<&str> {
+		path.file_name()
+			.and_then(|n| n.to_str())
+			.ok_or_else(|| Error::InvalidKeyFile(format!("Invalid key file path {}", path.display())))
+	}
+
+	/// Read the secret of a key from its file, decrypting it if needed.
+	fn key_secret_from_file(&self, path: &Path) -> Result<KeySecret> {
+		match Self::read_key_file(path)? {
+			KeyFile::Plain(suri) => Ok(KeySecret { suri, password: self.password().map(Into::into) }),
+			KeyFile::Encrypted(encrypted) => self.cipher.as_ref()
+				.ok_or(Error::PasswordRequired)?
+				.decrypt(&encrypted, Self::key_file_name(path)?.as_bytes()),
+		}
+	}
+
+	/// Get the secret for a given public key and key type.
+	fn key_secret_by_type(&self, public: &[u8], key_type: KeyTypeId) -> Result<Option<KeySecret>> {
 		if let Some(phrase) = self.get_additional_pair(public, key_type) {
-			return Ok(Some(phrase.clone()))
+			return Ok(Some(KeySecret { suri: phrase.clone(), password: None }))
 		}
 
 		let path = if let Some(path) = self.key_file_path(public, key_type) {
@@ -448,29 +522,43 @@ impl KeystoreInner {
 		};
 
 		if path.exists() {
-			let file = File::open(path)?;
-
-			serde_json::from_reader(&file).map_err(Into::into).map(Some)
+			self.key_secret_from_file(&path).map(Some)
 		} else {
 			Ok(None)
 		}
 	}
 
+	/// Encrypt the key files stored in plain text.
+	fn encrypt_plain_keys(&self) -> Result<usize> {
+		if self.cipher.is_none() {
+			return Err(Error::PasswordRequired)
+		}
+
+		let mut encrypted = 0;
+		for path in self.key_file_paths()? {
+			if let KeyFile::Plain(suri) = Self::read_key_file(&path)? {
+				self.write_key_file(&path, &suri)?;
+				encrypted += 1;
+			}
+		}
+		Ok(encrypted)
+	}
+
 	/// Get a key pair for the given public key and key type.
 	fn key_pair_by_type<Pair: PairT>(
 		&self,
 		public: &Pair::Public,
 		key_type: KeyTypeId,
 	) -> Result<Option<Pair>> {
-		let phrase = if let Some(p) = self.key_phrase_by_type(public.as_slice(), key_type)? {
-			p
+		let secret = if let Some(s) = self.key_secret_by_type(public.as_slice(), key_type)? {
+			s
 		} else {
 			return Ok(None)
 		};
 
 		let pair = Pair::from_string(
-			&phrase,
-			self.password(),
+			&secret.suri,
+			secret.password.as_deref(),
 		).map_err(|_| Error::InvalidPhrase)?;
 
 		if &pair.public() == public {
@@ -491,6 +579,23 @@ impl KeystoreInner {
 		Some(buf)
 	}
 
+	/// Returns the paths of all the key files.
+	fn key_file_paths(&self) -> Result<Vec<PathBuf>> {
+		let mut paths = Vec::new();
+		if let Some(path) = &self.path {
+			for entry in fs::read_dir(&path)? {
+				let path = entry?.path();
+				let is_key_file = path.file_name()
+					.and_then(|n| n.to_str())
+					.map_or(false, |n| hex::decode(n).map_or(false, |hex| hex.len() > 4));
+				if is_key_file {
+					paths.push(path);
+				}
+			}
+		}
+		Ok(paths)
+	}
+
 	/// Returns a list of raw public keys filtered by `KeyTypeId`
 	fn raw_public_keys(&self, id: KeyTypeId) -> Result<Vec<Vec<u8>>> {
 		let mut public_keys: Vec<Vec<u8>> = self.additional.keys()
@@ -659,6 +764,68 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn keys_are_encrypted_with_password() {
+		let temp_dir = TempDir::new().unwrap();
+		let password = || Some(FromStr::from_str("password").unwrap());
+		let mut store = KeystoreInner::open(temp_dir.path(), password()).unwrap();
+
+		let pair: sr25519::AppPair = store.generate().unwrap();
+		let path = store.key_file_path(pair.public().as_slice(), SR25519).unwrap();
+		let contents = fs::read_to_string(&path).unwrap();
+		assert!(contents.starts_with("{"));
+
+		// The secret is bound to its key file.
+		let other_path = store.key_file_path(&[0; 32], SR25519).unwrap();
+		fs::copy(&path, &other_path).unwrap();
+		assert!(matches!(store.key_secret_from_file(&other_path), Err(Error::InvalidPassword)));
+		fs::remove_file(other_path).unwrap();
+
+		let store = KeystoreInner::open(temp_dir.path(), None).unwrap();
+		assert!(matches!(
+			store.key_pair::<sr25519::AppPair>(&pair.public()),
+			Err(Error::PasswordRequired),
+		));
+
+		assert!(matches!(
+			KeystoreInner::open(temp_dir.path(), Some(FromStr::from_str("wrong").unwrap())),
+			Err(Error::InvalidPassword),
+		));
+
+		let store = KeystoreInner::open(temp_dir.path(), password()).unwrap();
+		assert_eq!(
+			pair.public(),
+			store.key_pair::<sr25519::AppPair>(&pair.public()).unwrap().unwrap().public(),
+		);
+	}
+
+	#[test]
+	fn encrypt_plain_keys_works() {
+		let temp_dir = TempDir::new().unwrap();
+		let password = || Some(FromStr::from_str("password").unwrap());
+
+		// Key generated in plain text with a password, as done before keystore encryption.
+		let (pair, phrase, _) = sr25519::AppPair::generate_with_phrase(Some("password"));
+		let store = KeystoreInner::open(temp_dir.path(), None).unwrap();
+		let path = store.key_file_path(pair.public().as_slice(), SR25519).unwrap();
+		fs::write(&path, serde_json::to_string(&phrase).unwrap()).unwrap();
+		store.insert_unknown(SR25519, "//Alice", &[1; 32]).unwrap();
+
+		assert!(matches!(store.encrypt_plain_keys(), Err(Error::PasswordRequired)));
+
+		let store = KeystoreInner::open(temp_dir.path(), password()).unwrap();
+		assert_eq!(store.encrypt_plain_keys().unwrap(), 2);
+		assert_eq!(store.encrypt_plain_keys().unwrap(), 0);
+		assert!(!fs::read_to_string(&path).unwrap().contains(&phrase));
+
+		let store = KeystoreInner::open(temp_dir.path(), password()).unwrap();
+		assert_eq!(
+			pair.public(),
+			store.key_pair::<sr25519::AppPair>(&pair.public()).unwrap().unwrap().public(),
+		);
+		assert_eq!(store.raw_public_keys(SR25519).unwrap().len(), 2);
+	}
+
 	#[test]
 	fn public_keys_are_returned() {
 		let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,706 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Keystore forwarding signing requests to a separate signer process.
+//!
+//! The [`RemoteKeystore`] never sees any private key: keys are listed, generated and used by a
+//! [`RemoteSigner`] it connects to over a Unix socket or TCP. Both sides authenticate each other
+//! with a shared secret, see the [`protocol`] module.
+
+use std::{
+	fmt,
+	io::{self, Read, Write},
+	net::{TcpStream, ToSocketAddrs},
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::mpsc,
+	time::Duration,
+};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+use async_trait::async_trait;
+use codec::Decode;
+use futures::channel::oneshot;
+use parking_lot::Mutex;
+use sp_application_crypto::{ecdsa, ed25519, sr25519};
+use sp_core::crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId};
+use sp_keystore::{
+	vrf::{VRFSignature, VRFTranscriptData},
+	CryptoStore, Error as TraitError, SyncCryptoStore,
+};
+
+use crate::{Error, Result};
+
+pub mod protocol;
+mod server;
+
+pub use server::RemoteSigner;
+use protocol::{Channel, EncodedVrfSignature, Request, Response};
+
+/// Default timeout of the requests sent to the signer.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Address of a remote signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSignerAddress {
+	/// Path of a Unix socket, written `unix://<path>`.
+	#[cfg(unix)]
+	Unix(PathBuf),
+	/// TCP address, written `tcp://<host>:<port>`.
+	Tcp(String),
+}
+
+impl FromStr for RemoteSignerAddress {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		#[cfg(unix)]
+		if let Some(path) = s.strip_prefix("unix://") {
+			return Ok(RemoteSignerAddress::Unix(path.into()))
+		}
+		if let Some(address) = s.strip_prefix("tcp://") {
+			return Ok(RemoteSignerAddress::Tcp(address.into()))
+		}
+		Err(Error::InvalidRemoteSigner(format!("Unsupported signer address: {}", s)))
+	}
+}
+
+impl fmt::Display for RemoteSignerAddress {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			#[cfg(unix)]
+			RemoteSignerAddress::Unix(path) => write!(f, "unix://{}", path.display()),
+			RemoteSignerAddress::Tcp(address) => write!(f, "tcp://{}", address),
+		}
+	}
+}
+
+/// Secret shared by a [`RemoteKeystore`] and a [`RemoteSigner`].
+#[derive(Clone)]
+pub struct RemoteSignerSecret(Vec<u8>);
+
+impl RemoteSignerSecret {
+	/// Minimum length of a secret.
+	pub const MIN_LEN: usize = 32;
+	/// Maximum length of a secret.
+	pub const MAX_LEN: usize = 64;
+
+	/// Create a secret from raw bytes.
+	pub fn new(secret: Vec<u8>) -> Result<Self> {
+		if secret.len() < Self::MIN_LEN || secret.len() > Self::MAX_LEN {
+			return Err(Error::InvalidRemoteSigner(format!(
+				"The secret must be {} to {} bytes long",
+				Self::MIN_LEN,
+				Self::MAX_LEN,
+			)))
+		}
+		Ok(Self(secret))
+	}
+
+	/// Read a hex encoded secret from a file.
+	pub fn from_file(path: &Path) -> Result<Self> {
+		let secret = std::fs::read_to_string(path)?;
+		let secret = hex::decode(secret.trim())
+			.map_err(|_| Error::InvalidRemoteSigner("The secret must be hex encoded".into()))?;
+		Self::new(secret)
+	}
+}
+
+impl AsRef<[u8]> for RemoteSignerSecret {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl fmt::Debug for RemoteSignerSecret {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "RemoteSignerSecret(<redacted>)")
+	}
+}
+
+/// A stream to a signer.
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+/// Encoded response to a request, or why it could not be obtained.
+type EncodedResponse = std::result::Result<Vec<u8>, TraitError>;
+
+/// A request waiting to be sent to the signer, together with where to deliver its response.
+type PendingRequest = (Request, oneshot::Sender<EncodedResponse>);
+
+/// The connection to a signer, owned by the thread sending the requests.
+struct Connection {
+	address: RemoteSignerAddress,
+	secret: RemoteSignerSecret,
+	timeout: Duration,
+	channel: Option<Channel<Box<dyn Stream>>>,
+}
+
+impl Connection {
+	fn connect(&self) -> io::Result<Channel<Box<dyn Stream>>> {
+		let stream: Box<dyn Stream> = match &self.address {
+			#[cfg(unix)]
+			RemoteSignerAddress::Unix(path) => {
+				let stream = UnixStream::connect(path)?;
+				stream.set_read_timeout(Some(self.timeout))?;
+				stream.set_write_timeout(Some(self.timeout))?;
+				Box::new(stream)
+			},
+			RemoteSignerAddress::Tcp(address) => {
+				let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+					io::Error::new(io::ErrorKind::NotFound, "Could not resolve the signer address")
+				})?;
+				let stream = TcpStream::connect_timeout(&address, self.timeout)?;
+				stream.set_nodelay(true)?;
+				stream.set_read_timeout(Some(self.timeout))?;
+				stream.set_write_timeout(Some(self.timeout))?;
+				Box::new(stream)
+			},
+		};
+		Channel::connect(stream, &self.secret)
+	}
+
+	/// Send a request to the signer and wait for the encoded response.
+	fn call(&mut self, request: &Request) -> EncodedResponse {
+		loop {
+			let reused = self.channel.is_some();
+			let channel = match self.channel.take() {
+				Some(channel) => Ok(channel),
+				None => self.connect(),
+			};
+			// Whether the whole request was written, in which case the signer may have served it.
+			let mut sent = false;
+			let result = channel.and_then(|mut channel| {
+				channel.send(request)?;
+				sent = true;
+				let response = channel.receive_encoded()?;
+				self.channel = Some(channel);
+				Ok(response)
+			});
+
+			match result {
+				Ok(response) => return Ok(response),
+				// A connection kept from a previous request may have been closed by the signer in
+				// the meantime, in which case the request is retried once on a new connection.
+				// Requests that the signer may have served already are only retried if serving
+				// them again has no effect, e.g. so that no second key is generated.
+				Err(e) => if !reused || (sent && !request.is_read_only()) {
+					log::warn!(
+						target: "keystore",
+						"Request to remote signer {} failed: {}",
+						self.address,
+						e,
+					);
+					return Err(TraitError::Unavailable)
+				},
+			}
+		}
+	}
+
+	/// Spawn the thread serving the requests sent over the returned channel.
+	///
+	/// The thread stops once the channel is closed.
+	fn spawn(mut self) -> io::Result<mpsc::Sender<PendingRequest>> {
+		let (sender, receiver) = mpsc::channel::<PendingRequest>();
+		std::thread::Builder::new()
+			.name("remote-keystore".into())
+			.spawn(move || {
+				for (request, response) in receiver {
+					let _ = response.send(self.call(&request));
+				}
+			})?;
+		Ok(sender)
+	}
+}
+
+/// A keystore whose keys are held by a [`RemoteSigner`].
+///
+/// A single connection is kept open and re-established on failure. It is owned by a dedicated
+/// thread sending the requests one at a time, so that the async methods wait for the response
+/// instead of blocking the executor, while the sync ones block until it arrives. Requests have a
+/// timeout; when the signer can't be reached, they fail with [`TraitError::Unavailable`].
+///
+/// Keys can only be generated by the signer: generating keys from a seed or inserting keys would
+/// require sending private keys to the signer and is not supported.
+pub struct RemoteKeystore {
+	address: RemoteSignerAddress,
+	secret: RemoteSignerSecret,
+	timeout: Duration,
+	requests: Mutex<Option<mpsc::Sender<PendingRequest>>>,
+}
+
+impl RemoteKeystore {
+	/// Create a keystore connecting to the signer at the given address.
+	///
+	/// The connection is only established with the first request.
+	pub fn new(address: RemoteSignerAddress, secret: RemoteSignerSecret) -> Self {
+		RemoteKeystore { address, secret, timeout: DEFAULT_TIMEOUT, requests: Mutex::new(None) }
+	}
+
+	/// Create a keystore from an URI of the form `<address>?secret-file=<path>`, where `address`
+	/// is a [`RemoteSignerAddress`] and `path` is a file containing the hex encoded secret.
+	///
+	/// Fails if the signer can't be reached.
+	pub fn open(uri: &str) -> Result<Self> {
+		let (address, secret_file) = uri.split_once("?secret-file=")
+			.ok_or_else(|| Error::InvalidRemoteSigner(
+				"The URI of the signer must specify a `secret-file`".into(),
+			))?;
+		let keystore = Self::new(
+			address.parse()?,
+			RemoteSignerSecret::from_file(Path::new(secret_file))?,
+		);
+
+		let mut connection = keystore.connection();
+		connection.channel = Some(connection.connect()?);
+		*keystore.requests.lock() = Some(connection.spawn()?);
+
+		Ok(keystore)
+	}
+
+	/// Set the timeout of the requests.
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	fn connection(&self) -> Connection {
+		Connection {
+			address: self.address.clone(),
+			secret: self.secret.clone(),
+			timeout: self.timeout,
+			channel: None,
+		}
+	}
+
+	/// Queue a request for the signer, returning a receiver of its response.
+	fn request(&self, request: Request) -> oneshot::Receiver<EncodedResponse> {
+		let (sender, receiver) = oneshot::channel();
+		let mut requests = self.requests.lock();
+		if requests.is_none() {
+			match self.connection().spawn() {
+				Ok(spawned) => *requests = Some(spawned),
+				Err(e) => log::warn!(target: "keystore", "Failed to spawn the remote keystore thread: {}", e),
+			}
+		}
+		// When the request can't be queued, the response sender is dropped and the request
+		// fails as unavailable.
+		if let Some(requests) = requests.as_ref() {
+			let _ = requests.send((request, sender));
+		}
+		receiver
+	}
+
+	/// Send a request to the signer and wait for the response.
+	async fn call<T: Decode>(&self, request: Request) -> std::result::Result<T, TraitError> {
+		decode_response(self.request(request).await)
+	}
+
+	/// Send a request to the signer and block until the response arrives.
+	fn call_blocking<T: Decode>(&self, request: Request) -> std::result::Result<T, TraitError> {
+		decode_response(futures::executor::block_on(self.request(request)))
+	}
+}
+
+fn decode_response<T: Decode>(
+	response: std::result::Result<EncodedResponse, oneshot::Canceled>,
+) -> std::result::Result<T, TraitError> {
+	let payload = response.map_err(|_| TraitError::Unavailable)??;
+	let response = Response::<T>::decode(&mut &payload[..])
+		.map_err(|_| TraitError::Other("Invalid response returned by the signer".into()))?;
+	response.map_err(Into::into)
+}
+
+fn filter_public_keys<Public: for<'a> std::convert::TryFrom<&'a [u8]>>(
+	keys: std::result::Result<Vec<CryptoTypePublicPair>, TraitError>,
+	crypto: CryptoTypeId,
+) -> Vec<Public> {
+	keys
+		.map(|keys| {
+			keys.into_iter()
+				.filter(|key| key.0 == crypto)
+				.filter_map(|key| Public::try_from(&key.1[..]).ok())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn generate_new_request(
+	id: KeyTypeId,
+	crypto: CryptoTypeId,
+	seed: Option<&str>,
+) -> std::result::Result<Request, TraitError> {
+	if seed.is_some() {
+		return Err(TraitError::ValidationError(
+			"Generating keys from a seed is not supported by the remote keystore".into(),
+		))
+	}
+	Ok(Request::GenerateNew(id, crypto))
+}
+
+fn decode_public<Public: for<'a> std::convert::TryFrom<&'a [u8]>>(
+	public: Vec<u8>,
+) -> std::result::Result<Public, TraitError> {
+	Public::try_from(&public[..])
+		.map_err(|_| TraitError::Other("Invalid public key returned by the signer".into()))
+}
+
+fn decode_vrf_signature(
+	signature: Option<EncodedVrfSignature>,
+) -> std::result::Result<Option<VRFSignature>, TraitError> {
+	let signature = match signature {
+		Some(signature) => signature,
+		None => return Ok(None),
+	};
+
+	let invalid = |_| TraitError::Other("Invalid VRF signature returned by the signer".into());
+	Ok(Some(VRFSignature {
+		output: schnorrkel::vrf::VRFOutput::from_bytes(&signature.0).map_err(invalid)?,
+		proof: schnorrkel::vrf::VRFProof::from_bytes(&signature.1).map_err(invalid)?,
+	}))
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn keys(&self, id: KeyTypeId) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		self.call(Request::Keys(id)).await
+	}
+
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		filter_public_keys(CryptoStore::keys(self, id).await, sr25519::CRYPTO_ID)
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		let request = generate_new_request(id, sr25519::CRYPTO_ID, seed)?;
+		decode_public(self.call(request).await?)
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		filter_public_keys(CryptoStore::keys(self, id).await, ed25519::CRYPTO_ID)
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		let request = generate_new_request(id, ed25519::CRYPTO_ID, seed)?;
+		decode_public(self.call(request).await?)
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		filter_public_keys(CryptoStore::keys(self, id).await, ecdsa::CRYPTO_ID)
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		let request = generate_new_request(id, ecdsa::CRYPTO_ID, seed)?;
+		decode_public(self.call(request).await?)
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> std::result::Result<(), ()> {
+		SyncCryptoStore::insert_unknown(self, id, suri, public)
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		self.call(Request::HasKeys(public_keys.to_vec())).await.unwrap_or(false)
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let all_keys = CryptoStore::keys(self, id).await?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect())
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError> {
+		self.call(Request::SignWith(id, key.clone(), msg.to_vec())).await
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> std::result::Result<Option<VRFSignature>, TraitError> {
+		let request = Request::Sr25519VrfSign(key_type, public.clone(), transcript_data.into());
+		decode_vrf_signature(self.call(request).await?)
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.call(Request::EcdsaSignPrehashed(id, public.clone(), *msg)).await
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn keys(
+		&self,
+		id: KeyTypeId
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		self.call_blocking(Request::Keys(id))
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>
+	) -> std::result::Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let all_keys = SyncCryptoStore::keys(self, id)?;
+		Ok(keys.into_iter().filter(|key| all_keys.contains(key)).collect())
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> std::result::Result<Option<Vec<u8>>, TraitError> {
+		self.call_blocking(Request::SignWith(id, key.clone(), msg.to_vec()))
+	}
+
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		filter_public_keys(SyncCryptoStore::keys(self, key_type), sr25519::CRYPTO_ID)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		let request = generate_new_request(id, sr25519::CRYPTO_ID, seed)?;
+		decode_public(self.call_blocking(request)?)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		filter_public_keys(SyncCryptoStore::keys(self, key_type), ed25519::CRYPTO_ID)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		let request = generate_new_request(id, ed25519::CRYPTO_ID, seed)?;
+		decode_public(self.call_blocking(request)?)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		filter_public_keys(SyncCryptoStore::keys(self, key_type), ecdsa::CRYPTO_ID)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		let request = generate_new_request(id, ecdsa::CRYPTO_ID, seed)?;
+		decode_public(self.call_blocking(request)?)
+	}
+
+	fn insert_unknown(&self, _key_type: KeyTypeId, _suri: &str, _public: &[u8])
+		-> std::result::Result<(), ()>
+	{
+		log::warn!(
+			target: "keystore",
+			"Keys can't be inserted into the remote keystore, insert them into the signer instead",
+		);
+		Err(())
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		self.call_blocking(Request::HasKeys(public_keys.to_vec())).unwrap_or(false)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> std::result::Result<Option<VRFSignature>, TraitError> {
+		let request = Request::Sr25519VrfSign(key_type, public.clone(), transcript_data.into());
+		decode_vrf_signature(self.call_blocking(request)?)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.call_blocking(Request::EcdsaSignPrehashed(id, public.clone(), *msg))
+	}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+	use sp_application_crypto::key_types::BABE;
+	use sp_core::{crypto::Public, Pair};
+	use sp_keystore::vrf::VRFTranscriptValue;
+	use tempfile::TempDir;
+	use crate::LocalKeystore;
+
+	fn start_signer(secret: RemoteSignerSecret) -> (TempDir, RemoteSignerAddress) {
+		let dir = TempDir::new().unwrap();
+		let address = RemoteSignerAddress::Unix(dir.path().join("signer.sock"));
+		let signer = RemoteSigner::new(Arc::new(LocalKeystore::in_memory()), secret);
+		let listener = std::os::unix::net::UnixListener::bind(dir.path().join("signer.sock")).unwrap();
+		std::thread::spawn(move || {
+			for stream in listener.incoming() {
+				let _ = signer.serve_connection(stream.unwrap());
+			}
+		});
+		(dir, address)
+	}
+
+	fn secret(byte: u8) -> RemoteSignerSecret {
+		RemoteSignerSecret::new(vec![byte; 32]).unwrap()
+	}
+
+	#[test]
+	fn sign_with_remote_keys_works() {
+		let (_dir, address) = start_signer(secret(1));
+		let keystore = RemoteKeystore::new(address, secret(1));
+
+		let public = SyncCryptoStore::sr25519_generate_new(&keystore, BABE, None).unwrap();
+		assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, BABE), vec![public.clone()]);
+		assert!(SyncCryptoStore::has_keys(&keystore, &[(public.to_raw_vec(), BABE)]));
+		assert!(!SyncCryptoStore::has_keys(&keystore, &[(vec![0; 32], BABE)]));
+
+		let key = CryptoTypePublicPair(sr25519::CRYPTO_ID, public.to_raw_vec());
+		let signature = SyncCryptoStore::sign_with(&keystore, BABE, &key, b"message")
+			.unwrap()
+			.unwrap();
+		let signature = sr25519::Signature::decode(&mut &signature[..]).unwrap();
+		assert!(sp_core::sr25519::Pair::verify(&signature, b"message", &public));
+
+		let transcript_data = || VRFTranscriptData {
+			label: b"label",
+			items: vec![("one", VRFTranscriptValue::U64(1))],
+		};
+		let signature = SyncCryptoStore::sr25519_vrf_sign(&keystore, BABE, &public, transcript_data())
+			.unwrap()
+			.unwrap();
+		let vrf_public = schnorrkel::PublicKey::from_bytes(public.as_ref()).unwrap();
+		assert!(vrf_public.vrf_verify(
+			sp_keystore::vrf::make_transcript(transcript_data()),
+			&signature.output,
+			&signature.proof,
+		).is_ok());
+
+		let public = SyncCryptoStore::ecdsa_generate_new(&keystore, BABE, None).unwrap();
+		let signature = SyncCryptoStore::ecdsa_sign_prehashed(&keystore, BABE, &public, &[1; 32])
+			.unwrap()
+			.unwrap();
+		assert!(sp_core::ecdsa::Pair::verify_prehashed(&signature, &[1; 32], &public));
+
+		assert!(SyncCryptoStore::sr25519_generate_new(&keystore, BABE, Some("//Alice")).is_err());
+		assert!(SyncCryptoStore::insert_unknown(&keystore, BABE, "//Alice", &[]).is_err());
+	}
+
+	#[test]
+	fn async_requests_work() {
+		let (_dir, address) = start_signer(secret(1));
+		let keystore = RemoteKeystore::new(address, secret(1));
+
+		futures::executor::block_on(async {
+			let public = CryptoStore::ed25519_generate_new(&keystore, BABE, None).await.unwrap();
+			assert_eq!(CryptoStore::ed25519_public_keys(&keystore, BABE).await, vec![public.clone()]);
+
+			let key = CryptoTypePublicPair(ed25519::CRYPTO_ID, public.to_raw_vec());
+			let signature = CryptoStore::sign_with(&keystore, BABE, &key, b"message")
+				.await
+				.unwrap()
+				.unwrap();
+			let signature = ed25519::Signature::decode(&mut &signature[..]).unwrap();
+			assert!(sp_core::ed25519::Pair::verify(&signature, b"message", &public));
+		});
+	}
+
+	#[test]
+	fn wrong_secret_is_unavailable() {
+		let (_dir, address) = start_signer(secret(1));
+		let keystore = RemoteKeystore::new(address, secret(2));
+
+		assert!(matches!(SyncCryptoStore::keys(&keystore, BABE), Err(TraitError::Unavailable)));
+		assert!(!SyncCryptoStore::has_keys(&keystore, &[]));
+	}
+
+	#[test]
+	fn served_request_is_not_sent_again() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("signer.sock");
+		let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+		let keystore = RemoteKeystore::new(RemoteSignerAddress::Unix(path), secret(1));
+
+		// A signer closing the connection after receiving the second request.
+		let signer = std::thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut channel = Channel::accept(stream, &secret(1)).unwrap();
+			assert!(matches!(channel.receive::<Request>().unwrap(), Request::Keys(BABE)));
+			channel.send(&Response::<Vec<CryptoTypePublicPair>>::Ok(Vec::new())).unwrap();
+			assert!(matches!(channel.receive::<Request>().unwrap(), Request::GenerateNew(BABE, _)));
+			listener
+		});
+
+		assert_eq!(SyncCryptoStore::keys(&keystore, BABE).unwrap(), Vec::new());
+		assert!(matches!(
+			SyncCryptoStore::sr25519_generate_new(&keystore, BABE, None),
+			Err(TraitError::Unavailable),
+		));
+
+		let listener = signer.join().unwrap();
+		listener.set_nonblocking(true).unwrap();
+		assert_eq!(listener.accept().unwrap_err().kind(), io::ErrorKind::WouldBlock);
+	}
+
+	#[test]
+	fn parse_address_works() {
+		assert_eq!(
+			"unix:///run/signer.sock".parse::<RemoteSignerAddress>().unwrap(),
+			RemoteSignerAddress::Unix("/run/signer.sock".into()),
+		);
+		assert_eq!(
+			"tcp://127.0.0.1:9955".parse::<RemoteSignerAddress>().unwrap(),
+			RemoteSignerAddress::Tcp("127.0.0.1:9955".into()),
+		);
+		assert!("http://127.0.0.1:9955".parse::<RemoteSignerAddress>().is_err());
+	}
+}
@@ -0,0 +1,372 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Wire protocol spoken between a [`RemoteKeystore`](super::RemoteKeystore) and a
+//! [`RemoteSigner`](super::RemoteSigner).
+//!
+//! A connection starts with a handshake in which both sides prove the knowledge of the shared
+//! secret:
+//!
+//! 1. the keystore sends the protocol magic, the protocol version and a random nonce;
+//! 2. the signer answers with its own random nonce and a MAC over both nonces;
+//! 3. the keystore answers with a MAC over both nonces in the reverse order.
+//!
+//! Both sides then derive a session key from the secret and the nonces. Every following message
+//! is a length prefixed frame authenticated with a MAC over the direction, a sequence number and
+//! the SCALE encoded payload, so frames can't be forged, replayed or reordered. Requests and
+//! responses are not encrypted: they only contain public keys, signatures and payloads to sign.
+
+use std::io::{self, Read, Write};
+
+use codec::{Decode, Encode};
+use rand::RngCore;
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, sr25519,
+};
+use sp_keystore::{
+	vrf::{VRFTranscriptData, VRFTranscriptValue},
+	Error as TraitError,
+};
+use subtle::ConstantTimeEq;
+
+use super::RemoteSignerSecret;
+
+/// Magic sent by the keystore at the start of a connection.
+const MAGIC: &[u8; 8] = b"sc-sign\0";
+
+/// Version of the protocol.
+const VERSION: u8 = 1;
+
+/// Length of the handshake nonces.
+const NONCE_LEN: usize = 32;
+
+/// Length of a MAC.
+const MAC_LEN: usize = 32;
+
+/// Maximum length of a frame payload.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// A request sent by the keystore to the signer.
+#[derive(Debug, Encode, Decode)]
+pub enum Request {
+	/// List the keys of the given type.
+	Keys(KeyTypeId),
+	/// Check whether all the given keys exist.
+	HasKeys(Vec<(Vec<u8>, KeyTypeId)>),
+	/// Generate a new key of the given type and crypto.
+	GenerateNew(KeyTypeId, CryptoTypeId),
+	/// Sign a message.
+	SignWith(KeyTypeId, CryptoTypePublicPair, Vec<u8>),
+	/// Generate a VRF signature of a transcript.
+	Sr25519VrfSign(KeyTypeId, sr25519::Public, TranscriptData),
+	/// Sign a pre-hashed message with an ECDSA key.
+	EcdsaSignPrehashed(KeyTypeId, ecdsa::Public, [u8; 32]),
+}
+
+impl Request {
+	/// Whether serving the request more than once has no other effect than serving it once.
+	pub fn is_read_only(&self) -> bool {
+		matches!(self, Request::Keys(_) | Request::HasKeys(_))
+	}
+}
+
+/// An error returned by the signer.
+#[derive(Debug, Encode, Decode)]
+pub enum RemoteError {
+	/// The key type is not supported.
+	KeyNotSupported(KeyTypeId),
+	/// The request is invalid.
+	Validation(String),
+	/// The keystore of the signer is unavailable.
+	Unavailable,
+	/// Any other error.
+	Other(String),
+}
+
+impl From<TraitError> for RemoteError {
+	fn from(error: TraitError) -> Self {
+		match error {
+			TraitError::KeyNotSupported(id) => RemoteError::KeyNotSupported(id),
+			TraitError::ValidationError(e) => RemoteError::Validation(e),
+			TraitError::Unavailable => RemoteError::Unavailable,
+			TraitError::Other(e) => RemoteError::Other(e),
+		}
+	}
+}
+
+impl From<RemoteError> for TraitError {
+	fn from(error: RemoteError) -> Self {
+		match error {
+			RemoteError::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
+			RemoteError::Validation(e) => TraitError::ValidationError(e),
+			RemoteError::Unavailable => TraitError::Unavailable,
+			RemoteError::Other(e) => TraitError::Other(e),
+		}
+	}
+}
+
+/// Result of a request, as sent back by the signer.
+pub type Response<T> = Result<T, RemoteError>;
+
+/// Owned version of [`VRFTranscriptData`].
+///
+/// It has the same encoding, the labels being sent as bytes.
+#[derive(Debug, Encode, Decode)]
+pub struct TranscriptData {
+	/// The transcript's label.
+	pub label: Vec<u8>,
+	/// Additional data registered into the transcript.
+	pub items: Vec<(Vec<u8>, TranscriptValue)>,
+}
+
+/// Owned version of [`VRFTranscriptValue`].
+#[derive(Debug, Encode, Decode)]
+pub enum TranscriptValue {
+	/// Value is an array of bytes.
+	Bytes(Vec<u8>),
+	/// Value is a u64 integer.
+	U64(u64),
+}
+
+impl From<VRFTranscriptData> for TranscriptData {
+	fn from(data: VRFTranscriptData) -> Self {
+		TranscriptData {
+			label: data.label.to_vec(),
+			items: data.items.into_iter()
+				.map(|(label, value)| {
+					let value = match value {
+						VRFTranscriptValue::Bytes(bytes) => TranscriptValue::Bytes(bytes),
+						VRFTranscriptValue::U64(val) => TranscriptValue::U64(val),
+					};
+					(label.as_bytes().to_vec(), value)
+				})
+				.collect(),
+		}
+	}
+}
+
+/// A VRF signature, as the bytes of the output and of the proof.
+pub type EncodedVrfSignature = ([u8; 32], [u8; 64]);
+
+/// Direction of a frame, part of its MAC.
+#[derive(Clone, Copy)]
+enum Direction {
+	Request = 0,
+	Response = 1,
+}
+
+fn mac(key: &[u8], parts: &[&[u8]]) -> [u8; MAC_LEN] {
+	let mut state = blake2_rfc::blake2b::Blake2b::with_key(MAC_LEN, key);
+	for part in parts {
+		state.update(part);
+	}
+	let mut mac = [0u8; MAC_LEN];
+	mac.copy_from_slice(state.finalize().as_bytes());
+	mac
+}
+
+fn nonce() -> [u8; NONCE_LEN] {
+	let mut nonce = [0u8; NONCE_LEN];
+	rand::rngs::OsRng.fill_bytes(&mut nonce);
+	nonce
+}
+
+fn verify(expected: &[u8], received: &[u8]) -> io::Result<()> {
+	if bool::from(expected.ct_eq(received)) {
+		Ok(())
+	} else {
+		Err(io::Error::new(io::ErrorKind::PermissionDenied, "Authentication failed"))
+	}
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// An authenticated channel over a stream.
+pub struct Channel<S> {
+	stream: S,
+	key: [u8; MAC_LEN],
+	send: Direction,
+	send_seq: u64,
+	recv_seq: u64,
+}
+
+impl<S: Read + Write> Channel<S> {
+	/// Perform the handshake on the keystore side of a new connection.
+	pub fn connect(mut stream: S, secret: &RemoteSignerSecret) -> io::Result<Self> {
+		let client_nonce = nonce();
+		stream.write_all(&MAGIC[..])?;
+		stream.write_all(&[VERSION])?;
+		stream.write_all(&client_nonce)?;
+		stream.flush()?;
+
+		let mut server_nonce = [0u8; NONCE_LEN];
+		let mut server_mac = [0u8; MAC_LEN];
+		stream.read_exact(&mut server_nonce)?;
+		stream.read_exact(&mut server_mac)?;
+		verify(&mac(secret.as_ref(), &[b"signer", &client_nonce, &server_nonce]), &server_mac)?;
+
+		let client_mac = mac(secret.as_ref(), &[b"keystore", &server_nonce, &client_nonce]);
+		stream.write_all(&client_mac)?;
+		stream.flush()?;
+
+		Ok(Self::new(stream, secret, &client_nonce, &server_nonce, Direction::Request))
+	}
+
+	/// Perform the handshake on the signer side of a new connection.
+	pub fn accept(mut stream: S, secret: &RemoteSignerSecret) -> io::Result<Self> {
+		let mut magic = [0u8; MAGIC.len()];
+		let mut version = [0u8; 1];
+		let mut client_nonce = [0u8; NONCE_LEN];
+		stream.read_exact(&mut magic)?;
+		if &magic != MAGIC {
+			return Err(invalid_data("Not a remote keystore"))
+		}
+		stream.read_exact(&mut version)?;
+		if version[0] != VERSION {
+			return Err(invalid_data("Unsupported protocol version"))
+		}
+		stream.read_exact(&mut client_nonce)?;
+
+		let server_nonce = nonce();
+		stream.write_all(&server_nonce)?;
+		stream.write_all(&mac(secret.as_ref(), &[b"signer", &client_nonce, &server_nonce]))?;
+		stream.flush()?;
+
+		let mut client_mac = [0u8; MAC_LEN];
+		stream.read_exact(&mut client_mac)?;
+		verify(&mac(secret.as_ref(), &[b"keystore", &server_nonce, &client_nonce]), &client_mac)?;
+
+		Ok(Self::new(stream, secret, &client_nonce, &server_nonce, Direction::Response))
+	}
+
+	fn new(
+		stream: S,
+		secret: &RemoteSignerSecret,
+		client_nonce: &[u8],
+		server_nonce: &[u8],
+		send: Direction,
+	) -> Self {
+		Channel {
+			stream,
+			key: mac(secret.as_ref(), &[b"session", client_nonce, server_nonce]),
+			send,
+			send_seq: 0,
+			recv_seq: 0,
+		}
+	}
+
+	fn frame_mac(&self, direction: Direction, seq: u64, payload: &[u8]) -> [u8; MAC_LEN] {
+		mac(&self.key, &[&[direction as u8], &seq.to_le_bytes(), payload])
+	}
+
+	/// Send a message.
+	pub fn send(&mut self, message: &impl Encode) -> io::Result<()> {
+		self.send_encoded(&message.encode())
+	}
+
+	/// Send an already encoded message.
+	pub fn send_encoded(&mut self, payload: &[u8]) -> io::Result<()> {
+		if payload.len() > MAX_FRAME_LEN {
+			return Err(invalid_data("Message too large"))
+		}
+		let mac = self.frame_mac(self.send, self.send_seq, payload);
+		self.send_seq += 1;
+
+		self.stream.write_all(&(payload.len() as u32).to_le_bytes())?;
+		self.stream.write_all(payload)?;
+		self.stream.write_all(&mac)?;
+		self.stream.flush()
+	}
+
+	/// Receive a message.
+	pub fn receive<T: Decode>(&mut self) -> io::Result<T> {
+		let payload = self.receive_encoded()?;
+		T::decode(&mut &payload[..]).map_err(|_| invalid_data("Invalid message"))
+	}
+
+	/// Receive a message without decoding it.
+	pub fn receive_encoded(&mut self) -> io::Result<Vec<u8>> {
+		let mut len = [0u8; 4];
+		self.stream.read_exact(&mut len)?;
+		let len = u32::from_le_bytes(len) as usize;
+		if len > MAX_FRAME_LEN {
+			return Err(invalid_data("Message too large"))
+		}
+		let mut payload = vec![0u8; len];
+		let mut received_mac = [0u8; MAC_LEN];
+		self.stream.read_exact(&mut payload)?;
+		self.stream.read_exact(&mut received_mac)?;
+
+		let direction = match self.send {
+			Direction::Request => Direction::Response,
+			Direction::Response => Direction::Request,
+		};
+		verify(&self.frame_mac(direction, self.recv_seq, &payload), &received_mac)?;
+		self.recv_seq += 1;
+
+		Ok(payload)
+	}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use std::os::unix::net::UnixStream;
+
+	fn secret(byte: u8) -> RemoteSignerSecret {
+		RemoteSignerSecret::new(vec![byte; 32]).unwrap()
+	}
+
+	#[test]
+	fn handshake_requires_same_secret() {
+		let (keystore, signer) = UnixStream::pair().unwrap();
+		let handle = std::thread::spawn(move || Channel::accept(signer, &secret(2)).map(|_| ()));
+
+		assert_eq!(
+			Channel::connect(keystore, &secret(1)).err().unwrap().kind(),
+			io::ErrorKind::PermissionDenied,
+		);
+		assert!(handle.join().unwrap().is_err());
+	}
+
+	#[test]
+	fn tampered_frames_are_rejected() {
+		let (keystore, signer) = UnixStream::pair().unwrap();
+		let handle = std::thread::spawn(move || {
+			let mut channel = Channel::accept(signer, &secret(1)).unwrap();
+			let request = channel.receive::<Request>().unwrap();
+			assert!(matches!(request, Request::Keys(KeyTypeId(id)) if &id == b"test"));
+			channel.receive::<Request>()
+		});
+
+		let mut channel = Channel::connect(keystore, &secret(1)).unwrap();
+		channel.send(&Request::Keys(KeyTypeId(*b"test"))).unwrap();
+
+		// Replay the previous frame with a MAC that belongs to another sequence number.
+		let payload = Request::Keys(KeyTypeId(*b"test")).encode();
+		let mac = channel.frame_mac(Direction::Request, 0, &payload);
+		channel.stream.write_all(&(payload.len() as u32).to_le_bytes()).unwrap();
+		channel.stream.write_all(&payload).unwrap();
+		channel.stream.write_all(&mac).unwrap();
+
+		assert_eq!(handle.join().unwrap().err().unwrap().kind(), io::ErrorKind::PermissionDenied);
+	}
+}
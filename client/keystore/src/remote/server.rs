@@ -0,0 +1,239 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signer serving the requests of remote keystores.
+
+use std::{
+	io::{self, Read, Write},
+	net::{TcpListener, TcpStream},
+	sync::{Arc, atomic::{AtomicUsize, Ordering}},
+	thread,
+	time::Duration,
+};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use codec::Encode;
+use parking_lot::{const_mutex, Mutex};
+use sp_application_crypto::{ecdsa, ed25519, sr25519};
+use sp_core::crypto::Public;
+use sp_keystore::{
+	vrf::{VRFTranscriptData, VRFTranscriptValue},
+	SyncCryptoStore,
+};
+
+use super::{
+	protocol::{Channel, EncodedVrfSignature, RemoteError, Request, Response, TranscriptData, TranscriptValue},
+	RemoteSignerAddress, RemoteSignerSecret,
+};
+
+/// Maximum number of connections a signer serves at the same time.
+const MAX_CONNECTIONS: usize = 64;
+
+/// Time a new connection has to complete the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time a response has to be written to the keystore.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum number of distinct VRF transcript labels a signer accepts.
+const MAX_TRANSCRIPT_LABELS: usize = 1024;
+
+/// Labels of the VRF transcripts signed so far.
+///
+/// Transcripts only accept static labels, so the labels received from the keystores are leaked
+/// and reused. Their number is bounded as they are not expected to change between requests.
+static TRANSCRIPT_LABELS: Mutex<Vec<&'static [u8]>> = const_mutex(Vec::new());
+
+fn transcript_label(label: Vec<u8>) -> Response<&'static [u8]> {
+	let mut labels = TRANSCRIPT_LABELS.lock();
+	if let Some(label) = labels.iter().find(|l| ***l == label[..]) {
+		return Ok(*label)
+	}
+	if labels.len() >= MAX_TRANSCRIPT_LABELS {
+		return Err(RemoteError::Validation("Too many distinct transcript labels".into()))
+	}
+	let label: &'static [u8] = Box::leak(label.into_boxed_slice());
+	labels.push(label);
+	Ok(label)
+}
+
+impl TranscriptData {
+	fn into_vrf_transcript_data(self) -> Response<VRFTranscriptData> {
+		let label = transcript_label(self.label)?;
+		let items = self.items.into_iter()
+			.map(|(label, value)| {
+				let label = std::str::from_utf8(transcript_label(label)?)
+					.map_err(|_| RemoteError::Validation("Invalid transcript label".into()))?;
+				let value = match value {
+					TranscriptValue::Bytes(bytes) => VRFTranscriptValue::Bytes(bytes),
+					TranscriptValue::U64(val) => VRFTranscriptValue::U64(val),
+				};
+				Ok((label, value))
+			})
+			.collect::<Response<_>>()?;
+		Ok(VRFTranscriptData { label, items })
+	}
+}
+
+/// A socket accepted by the signer.
+trait Socket: Send + 'static where for<'a> &'a Self: Read + Write {
+	fn set_timeouts(&self, read: Option<Duration>, write: Option<Duration>) -> io::Result<()>;
+}
+
+impl Socket for TcpStream {
+	fn set_timeouts(&self, read: Option<Duration>, write: Option<Duration>) -> io::Result<()> {
+		self.set_read_timeout(read)?;
+		self.set_write_timeout(write)
+	}
+}
+
+#[cfg(unix)]
+impl Socket for UnixStream {
+	fn set_timeouts(&self, read: Option<Duration>, write: Option<Duration>) -> io::Result<()> {
+		self.set_read_timeout(read)?;
+		self.set_write_timeout(write)
+	}
+}
+
+/// Releases the slot of a connection when it is closed.
+struct ConnectionSlot<'a>(&'a AtomicUsize);
+
+impl<'a> Drop for ConnectionSlot<'a> {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+/// Signer serving the requests of [`RemoteKeystore`](super::RemoteKeystore)s with the keys of
+/// another keystore, usually a [`LocalKeystore`](crate::LocalKeystore).
+pub struct RemoteSigner<K> {
+	keystore: Arc<K>,
+	secret: RemoteSignerSecret,
+	connections: AtomicUsize,
+}
+
+impl<K: SyncCryptoStore + 'static> RemoteSigner<K> {
+	/// Create a signer using the keys of `keystore`.
+	pub fn new(keystore: Arc<K>, secret: RemoteSignerSecret) -> Self {
+		RemoteSigner { keystore, secret, connections: AtomicUsize::new(0) }
+	}
+
+	/// Listen on the given address and serve each connection on its own thread.
+	///
+	/// At most `MAX_CONNECTIONS` connections are served at the same time, further ones are closed
+	/// right away. A connection that doesn't complete the handshake in time is closed.
+	///
+	/// Only returns if listening fails.
+	pub fn serve(self, address: &RemoteSignerAddress) -> io::Result<()> {
+		let this = Arc::new(self);
+		match address {
+			#[cfg(unix)]
+			RemoteSignerAddress::Unix(path) => {
+				let listener = UnixListener::bind(path)?;
+				for stream in listener.incoming() {
+					this.spawn_connection(stream?);
+				}
+			},
+			RemoteSignerAddress::Tcp(address) => {
+				let listener = TcpListener::bind(address)?;
+				for stream in listener.incoming() {
+					let stream = stream?;
+					stream.set_nodelay(true)?;
+					this.spawn_connection(stream);
+				}
+			},
+		}
+		Ok(())
+	}
+
+	fn spawn_connection<S: Socket>(self: &Arc<Self>, stream: S) where for<'a> &'a S: Read + Write {
+		if self.connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+			self.connections.fetch_sub(1, Ordering::SeqCst);
+			log::warn!(target: "keystore", "Too many remote keystore connections, closing the new one");
+			return
+		}
+		let this = self.clone();
+		thread::spawn(move || {
+			let _slot = ConnectionSlot(&this.connections);
+			if let Err(e) = this.serve_socket(stream) {
+				if e.kind() != io::ErrorKind::UnexpectedEof {
+					log::warn!(target: "keystore", "Remote keystore connection closed: {}", e);
+				}
+			}
+		});
+	}
+
+	/// Serve a connection accepted by the signer, with a timeout on the handshake.
+	fn serve_socket<S: Socket>(&self, stream: S) -> io::Result<()> where for<'a> &'a S: Read + Write {
+		stream.set_timeouts(Some(HANDSHAKE_TIMEOUT), Some(HANDSHAKE_TIMEOUT))?;
+		let channel = Channel::accept(&stream, &self.secret)?;
+		// The keystore keeps the connection open between requests.
+		stream.set_timeouts(None, Some(WRITE_TIMEOUT))?;
+		self.serve_channel(channel)
+	}
+
+	/// Authenticate the keystore on the other side of `stream`, then serve its requests until the
+	/// connection is closed.
+	pub fn serve_connection<S: Read + Write>(&self, stream: S) -> io::Result<()> {
+		self.serve_channel(Channel::accept(stream, &self.secret)?)
+	}
+
+	fn serve_channel<S: Read + Write>(&self, mut channel: Channel<S>) -> io::Result<()> {
+		log::debug!(target: "keystore", "Remote keystore connected");
+		loop {
+			let request = channel.receive::<Request>()?;
+			channel.send_encoded(&self.handle(request))?;
+		}
+	}
+
+	/// Handle a request, returning the encoded response.
+	fn handle(&self, request: Request) -> Vec<u8> {
+		let keystore = &*self.keystore;
+		match request {
+			Request::Keys(id) => respond(SyncCryptoStore::keys(keystore, id)),
+			Request::HasKeys(keys) => respond(Ok(SyncCryptoStore::has_keys(keystore, &keys))),
+			Request::GenerateNew(id, crypto) => respond(match crypto {
+				sr25519::CRYPTO_ID => SyncCryptoStore::sr25519_generate_new(keystore, id, None)
+					.map(|public| public.to_raw_vec()),
+				ed25519::CRYPTO_ID => SyncCryptoStore::ed25519_generate_new(keystore, id, None)
+					.map(|public| public.to_raw_vec()),
+				ecdsa::CRYPTO_ID => SyncCryptoStore::ecdsa_generate_new(keystore, id, None)
+					.map(|public| public.to_raw_vec()),
+				_ => Err(sp_keystore::Error::KeyNotSupported(id)),
+			}),
+			Request::SignWith(id, key, msg) => respond(SyncCryptoStore::sign_with(keystore, id, &key, &msg)),
+			Request::Sr25519VrfSign(id, public, transcript_data) => {
+				let response: Response<Option<EncodedVrfSignature>> = transcript_data
+					.into_vrf_transcript_data()
+					.and_then(|data| {
+						SyncCryptoStore::sr25519_vrf_sign(keystore, id, &public, data).map_err(Into::into)
+					})
+					.map(|signature| signature.map(|s| (s.output.to_bytes(), s.proof.to_bytes())));
+				response.encode()
+			},
+			Request::EcdsaSignPrehashed(id, public, msg) => {
+				respond(SyncCryptoStore::ecdsa_sign_prehashed(keystore, id, &public, &msg))
+			},
+		}
+	}
+}
+
+fn respond<T: Encode>(result: Result<T, sp_keystore::Error>) -> Vec<u8> {
+	result.map_err(RemoteError::from).encode()
+}
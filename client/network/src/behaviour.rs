@@ -79,6 +79,16 @@ pub struct Behaviour<B: BlockT> {
 	/// [`request_responses::RequestResponsesBehaviour`].
 	#[behaviour(ignore)]
 	block_request_protocol_name: String,
+
+	/// Protocol name used to send out state requests via
+	/// [`request_responses::RequestResponsesBehaviour`].
+	#[behaviour(ignore)]
+	state_request_protocol_name: String,
+
+	/// Protocol name used to send out warp sync requests via
+	/// [`request_responses::RequestResponsesBehaviour`].
+	#[behaviour(ignore)]
+	warp_sync_protocol_name: Option<String>,
 }
 
 /// Event generated by `Behaviour`.
@@ -186,6 +196,8 @@ impl<B: BlockT> Behaviour<B> {
 		light_client_request_sender: light_client_requests::sender::LightClientRequestSender<B>,
 		disco_config: DiscoveryConfig,
 		block_request_protocol_config: request_responses::ProtocolConfig,
+		state_request_protocol_config: request_responses::ProtocolConfig,
+		warp_sync_protocol_config: Option<request_responses::ProtocolConfig>,
 		bitswap: Option<Bitswap<B>>,
 		light_client_request_protocol_config: request_responses::ProtocolConfig,
 		// All remaining request protocol configs.
@@ -195,6 +207,18 @@ impl<B: BlockT> Behaviour<B> {
 		let block_request_protocol_name = block_request_protocol_config.name.to_string();
 		request_response_protocols.push(block_request_protocol_config);
 
+		let state_request_protocol_name = state_request_protocol_config.name.to_string();
+		request_response_protocols.push(state_request_protocol_config);
+
+		let warp_sync_protocol_name = match warp_sync_protocol_config {
+			Some(config) => {
+				let name = config.name.to_string();
+				request_response_protocols.push(config);
+				Some(name)
+			},
+			None => None,
+		};
+
 		request_response_protocols.push(light_client_request_protocol_config);
 
 		Ok(Behaviour {
@@ -208,6 +232,8 @@ impl<B: BlockT> Behaviour<B> {
 			events: VecDeque::new(),
 
 			block_request_protocol_name,
+			state_request_protocol_name,
+			warp_sync_protocol_name,
 		})
 	}
 
@@ -329,6 +355,39 @@ Behaviour<B> {
 					&target, &self.block_request_protocol_name, buf, pending_response, IfDisconnected::ImmediateError,
 				);
 			},
+			CustomMessageOutcome::StateRequest { target, request, pending_response } => {
+				let mut buf = Vec::with_capacity(request.encoded_len());
+				if let Err(err) = request.encode(&mut buf) {
+					log::warn!(
+						target: "sync",
+						"Failed to encode state request {:?}: {:?}",
+						request, err
+					);
+					return
+				}
+
+				self.request_responses.send_request(
+					&target, &self.state_request_protocol_name, buf, pending_response, IfDisconnected::ImmediateError,
+				);
+			},
+			CustomMessageOutcome::WarpSyncRequest { target, request, pending_response } => {
+				match &self.warp_sync_protocol_name {
+					Some(name) => self.request_responses.send_request(
+						&target,
+						name,
+						codec::Encode::encode(&request),
+						pending_response,
+						IfDisconnected::ImmediateError,
+					),
+					None => {
+						log::warn!(
+							target: "sync",
+							"Trying to send warp sync request when no protocol is configured {:?}",
+							request,
+						);
+					}
+				}
+			},
 			CustomMessageOutcome::NotificationStreamOpened {
 				remote, protocol, negotiated_fallback, roles, notifications_sink
 			} => {
@@ -258,6 +258,8 @@ mod transport;
 mod utils;
 
 pub mod block_request_handler;
+pub mod state_request_handler;
+pub mod warp_request_handler;
 pub mod bitswap;
 pub mod light_client_requests;
 pub mod config;
@@ -268,7 +270,7 @@ pub mod transactions;
 
 #[doc(inline)]
 pub use libp2p::{multiaddr, Multiaddr, PeerId};
-pub use protocol::{event::{DhtEvent, Event, ObservedRole}, sync::SyncState, PeerInfo};
+pub use protocol::{event::{DhtEvent, Event, ObservedRole}, sync::{SyncState, StateDownloadProgress, WarpSyncPhase, WarpSyncProgress}, PeerInfo};
 pub use service::{
 	NetworkService, NetworkWorker, RequestFailure, OutboundFailure, NotificationSender,
 	NotificationSenderReady, IfDisconnected,
@@ -321,4 +323,8 @@ pub struct NetworkStatus<B: BlockT> {
 	pub total_bytes_inbound: u64,
 	/// The total number of bytes sent.
 	pub total_bytes_outbound: u64,
+	/// State sync in progress.
+	pub state_sync: Option<StateDownloadProgress>,
+	/// Warp sync in progress.
+	pub warp_sync: Option<WarpSyncProgress>,
 }
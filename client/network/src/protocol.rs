@@ -22,6 +22,7 @@ use crate::{
 	error,
 	request_responses::RequestFailure,
 	utils::{interval, LruHashSet},
+	warp_request_handler::WarpSyncProvider,
 };
 
 use bytes::Bytes;
@@ -188,6 +189,11 @@ struct Peer<B: BlockT> {
 		message::BlockRequest<B>,
 		oneshot::Receiver<Result<Vec<u8>, RequestFailure>>,
 	)>,
+	/// Current state request, if any. Started by emitting [`CustomMessageOutcome::StateRequest`].
+	state_request: Option<oneshot::Receiver<Result<Vec<u8>, RequestFailure>>>,
+	/// Current warp sync request, if any. Started by emitting
+	/// [`CustomMessageOutcome::WarpSyncRequest`].
+	warp_request: Option<oneshot::Receiver<Result<Vec<u8>, RequestFailure>>>,
 	/// Holds a set of blocks known to this peer.
 	known_blocks: LruHashSet<B::Hash>,
 }
@@ -210,6 +216,8 @@ pub struct ProtocolConfig {
 	pub roles: Roles,
 	/// Maximum number of peers to ask the same blocks in parallel.
 	pub max_parallel_downloads: u32,
+	/// Initial syncing mode.
+	pub sync_mode: config::SyncMode,
 }
 
 impl Default for ProtocolConfig {
@@ -217,6 +225,7 @@ impl Default for ProtocolConfig {
 		ProtocolConfig {
 			roles: Roles::FULL,
 			max_parallel_downloads: 5,
+			sync_mode: config::SyncMode::Full,
 		}
 	}
 }
@@ -260,14 +269,17 @@ impl<B: BlockT> Protocol<B> {
 		notifications_protocols_handshakes: Vec<Vec<u8>>,
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		metrics_registry: Option<&Registry>,
+		warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
 	) -> error::Result<(Protocol<B>, sc_peerset::PeersetHandle, Vec<(PeerId, Multiaddr)>)> {
 		let info = chain.info();
 		let sync = ChainSync::new(
 			config.roles,
+			config.sync_mode,
 			chain.clone(),
 			&info,
 			block_announce_validator,
 			config.max_parallel_downloads,
+			warp_sync_provider,
 		);
 
 		let boot_node_ids = {
@@ -454,7 +466,9 @@ impl<B: BlockT> Protocol<B> {
 	pub fn num_active_peers(&self) -> usize {
 		self.peers
 			.values()
-			.filter(|p| p.block_request.is_some())
+			.filter(|p| {
+				p.block_request.is_some() || p.state_request.is_some() || p.warp_request.is_some()
+			})
 			.count()
 	}
 
@@ -478,6 +492,16 @@ impl<B: BlockT> Protocol<B> {
 		self.sync.status().queued_blocks
 	}
 
+	/// State download progress, if a state sync is in progress.
+	pub fn state_sync_progress(&self) -> Option<sync::StateDownloadProgress> {
+		self.sync.status().state_sync
+	}
+
+	/// Warp sync progress, if a warp sync is in progress.
+	pub fn warp_sync_progress(&self) -> Option<sync::WarpSyncProgress> {
+		self.sync.status().warp_sync
+	}
+
 	/// Number of downloaded blocks.
 	pub fn num_downloaded_blocks(&self) -> usize {
 		self.sync.num_downloaded_blocks()
@@ -656,6 +680,44 @@ impl<B: BlockT> Protocol<B> {
 		}
 	}
 
+	/// Must be called in response to a [`CustomMessageOutcome::StateRequest`] being emitted.
+	/// Must contain the same `PeerId` and request that have been emitted.
+	pub fn on_state_response(
+		&mut self,
+		peer_id: PeerId,
+		response: crate::schema::v1::StateResponse,
+	) -> CustomMessageOutcome<B> {
+		match self.sync.on_state_data(&peer_id, response) {
+			Ok(sync::OnStateData::Import(origin, block)) =>
+				CustomMessageOutcome::BlockImport(origin, vec![block]),
+			Ok(sync::OnStateData::Request(peer, req)) => {
+				prepare_state_request::<B>(&mut self.peers, peer, req)
+			}
+			Err(sync::BadPeer(id, repu)) => {
+				self.behaviour.disconnect_peer(&id, HARDCODED_PEERSETS_SYNC);
+				self.peerset_handle.report_peer(id, repu);
+				CustomMessageOutcome::None
+			}
+		}
+	}
+
+	/// Must be called in response to a [`CustomMessageOutcome::WarpSyncRequest`] being emitted.
+	/// Must contain the same `PeerId` and request that have been emitted.
+	pub fn on_warp_sync_response(
+		&mut self,
+		peer_id: PeerId,
+		response: crate::warp_request_handler::EncodedProof,
+	) -> CustomMessageOutcome<B> {
+		match self.sync.on_warp_sync_data(&peer_id, response) {
+			Ok(()) => CustomMessageOutcome::None,
+			Err(sync::BadPeer(id, repu)) => {
+				self.behaviour.disconnect_peer(&id, HARDCODED_PEERSETS_SYNC);
+				self.peerset_handle.report_peer(id, repu);
+				CustomMessageOutcome::None
+			}
+		}
+	}
+
 	/// Perform time based maintenance.
 	///
 	/// > **Note**: This method normally doesn't have to be called except for testing purposes.
@@ -737,6 +799,8 @@ impl<B: BlockT> Protocol<B> {
 				best_number: status.best_number
 			},
 			block_request: None,
+			state_request: None,
+			warp_request: None,
 			known_blocks: LruHashSet::new(NonZeroUsize::new(MAX_KNOWN_BLOCKS)
 				.expect("Constant is nonzero")),
 		};
@@ -1161,6 +1225,80 @@ fn prepare_block_request<B: BlockT>(
 	}
 }
 
+/// Handles a failed outgoing sync request, reporting and disconnecting the peer if needed.
+fn on_request_failure(
+	behaviour: &mut Notifications,
+	peerset_handle: &sc_peerset::PeersetHandle,
+	id: &PeerId,
+	e: RequestFailure,
+) {
+	match e {
+		RequestFailure::Network(OutboundFailure::Timeout) => {
+			peerset_handle.report_peer(id.clone(), rep::TIMEOUT);
+			behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+		}
+		RequestFailure::Network(OutboundFailure::UnsupportedProtocols) => {
+			peerset_handle.report_peer(id.clone(), rep::BAD_PROTOCOL);
+			behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+		}
+		RequestFailure::Network(OutboundFailure::DialFailure) => {
+			behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+		}
+		RequestFailure::Refused => {
+			peerset_handle.report_peer(id.clone(), rep::REFUSED);
+			behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+		}
+		RequestFailure::Network(OutboundFailure::ConnectionClosed)
+		| RequestFailure::NotConnected => {
+			behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+		},
+		RequestFailure::UnknownProtocol => {
+			debug_assert!(false, "Sync request protocols should always be known.");
+		}
+		RequestFailure::Obsolete => {
+			debug_assert!(
+				false,
+				"Can not receive `RequestFailure::Obsolete` after dropping the \
+				 response receiver.",
+			);
+		}
+	}
+}
+
+fn prepare_state_request<B: BlockT>(
+	peers: &mut HashMap<PeerId, Peer<B>>,
+	who: PeerId,
+	request: crate::schema::v1::StateRequest,
+) -> CustomMessageOutcome<B> {
+	let (tx, rx) = oneshot::channel();
+
+	if let Some(ref mut peer) = peers.get_mut(&who) {
+		peer.state_request = Some(rx);
+	}
+	CustomMessageOutcome::StateRequest {
+		target: who,
+		request: request,
+		pending_response: tx,
+	}
+}
+
+fn prepare_warp_sync_request<B: BlockT>(
+	peers: &mut HashMap<PeerId, Peer<B>>,
+	who: PeerId,
+	request: crate::warp_request_handler::Request<B>,
+) -> CustomMessageOutcome<B> {
+	let (tx, rx) = oneshot::channel();
+
+	if let Some(ref mut peer) = peers.get_mut(&who) {
+		peer.warp_request = Some(rx);
+	}
+	CustomMessageOutcome::WarpSyncRequest {
+		target: who,
+		request: request,
+		pending_response: tx,
+	}
+}
+
 /// Outcome of an incoming custom message.
 #[derive(Debug)]
 #[must_use]
@@ -1192,6 +1330,18 @@ pub enum CustomMessageOutcome<B: BlockT> {
 		request: crate::schema::v1::BlockRequest,
 		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
 	},
+	/// A new storage request must be emitted.
+	StateRequest {
+		target: PeerId,
+		request: crate::schema::v1::StateRequest,
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+	},
+	/// A new warp sync request must be emitted.
+	WarpSyncRequest {
+		target: PeerId,
+		request: crate::warp_request_handler::Request<B>,
+		pending_response: oneshot::Sender<Result<Vec<u8>, RequestFailure>>,
+	},
 	/// Peer has a reported a new head of chain.
 	PeerNewBest(PeerId, NumberFor<B>),
 	/// Now connected to a new peer for syncing purposes.
@@ -1254,6 +1404,8 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 
 		// Check for finished outgoing requests.
 		let mut finished_block_requests = Vec::new();
+		let mut finished_state_requests = Vec::new();
+		let mut finished_warp_sync_requests = Vec::new();
 		for (id, peer) in self.peers.iter_mut() {
 			if let Peer { block_request: Some((_, pending_response)), .. } = peer {
 				match pending_response.poll_unpin(cx) {
@@ -1275,44 +1427,71 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 					Poll::Ready(Ok(Err(e))) => {
 						peer.block_request.take();
 						debug!(target: "sync", "Block request to peer {:?} failed: {:?}.", id, e);
+						on_request_failure(&mut self.behaviour, &self.peerset_handle, id, e);
+					},
+					Poll::Ready(Err(oneshot::Canceled)) => {
+						peer.block_request.take();
+						trace!(
+							target: "sync",
+							"Block request to peer {:?} failed due to oneshot being canceled.",
+							id,
+						);
+						self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+					},
+					Poll::Pending => {},
+				}
+			}
+			if let Peer { state_request: Some(pending_response), .. } = peer {
+				match pending_response.poll_unpin(cx) {
+					Poll::Ready(Ok(Ok(resp))) => {
+						peer.state_request.take();
 
-						match e {
-							RequestFailure::Network(OutboundFailure::Timeout) => {
-								self.peerset_handle.report_peer(id.clone(), rep::TIMEOUT);
-								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
-							}
-							RequestFailure::Network(OutboundFailure::UnsupportedProtocols) => {
-								self.peerset_handle.report_peer(id.clone(), rep::BAD_PROTOCOL);
-								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
-							}
-							RequestFailure::Network(OutboundFailure::DialFailure) => {
-								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
-							}
-							RequestFailure::Refused => {
-								self.peerset_handle.report_peer(id.clone(), rep::REFUSED);
-								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
-							}
-							RequestFailure::Network(OutboundFailure::ConnectionClosed)
-							| RequestFailure::NotConnected => {
+						let protobuf_response = match crate::schema::v1::StateResponse::decode(&resp[..]) {
+							Ok(proto) => proto,
+							Err(e) => {
+								debug!(target: "sync", "Failed to decode state response from peer {:?}: {:?}.", id, e);
+								self.peerset_handle.report_peer(id.clone(), rep::BAD_MESSAGE);
 								self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
-							},
-							RequestFailure::UnknownProtocol => {
-								debug_assert!(false, "Block request protocol should always be known.");
-							}
-							RequestFailure::Obsolete => {
-								debug_assert!(
-									false,
-									"Can not receive `RequestFailure::Obsolete` after dropping the \
-									 response receiver.",
-								);
+								continue;
 							}
-						}
+						};
+
+						finished_state_requests.push((id.clone(), protobuf_response));
+					},
+					Poll::Ready(Ok(Err(e))) => {
+						peer.state_request.take();
+						debug!(target: "sync", "State request to peer {:?} failed: {:?}.", id, e);
+						on_request_failure(&mut self.behaviour, &self.peerset_handle, id, e);
 					},
 					Poll::Ready(Err(oneshot::Canceled)) => {
-						peer.block_request.take();
+						peer.state_request.take();
 						trace!(
 							target: "sync",
-							"Block request to peer {:?} failed due to oneshot being canceled.",
+							"State request to peer {:?} failed due to oneshot being canceled.",
+							id,
+						);
+						self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
+					},
+					Poll::Pending => {},
+				}
+			}
+			if let Peer { warp_request: Some(pending_response), .. } = peer {
+				match pending_response.poll_unpin(cx) {
+					Poll::Ready(Ok(Ok(resp))) => {
+						peer.warp_request.take();
+						let response = crate::warp_request_handler::EncodedProof(resp);
+						finished_warp_sync_requests.push((id.clone(), response));
+					},
+					Poll::Ready(Ok(Err(e))) => {
+						peer.warp_request.take();
+						debug!(target: "sync", "Warp sync request to peer {:?} failed: {:?}.", id, e);
+						on_request_failure(&mut self.behaviour, &self.peerset_handle, id, e);
+					},
+					Poll::Ready(Err(oneshot::Canceled)) => {
+						peer.warp_request.take();
+						trace!(
+							target: "sync",
+							"Warp sync request to peer {:?} failed due to oneshot being canceled.",
 							id,
 						);
 						self.behaviour.disconnect_peer(id, HARDCODED_PEERSETS_SYNC);
@@ -1325,6 +1504,14 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 			let ev = self.on_block_response(id, req, protobuf_response);
 			self.pending_messages.push_back(ev);
 		}
+		for (id, protobuf_response) in finished_state_requests {
+			let ev = self.on_state_response(id, protobuf_response);
+			self.pending_messages.push_back(ev);
+		}
+		for (id, response) in finished_warp_sync_requests {
+			let ev = self.on_warp_sync_response(id, response);
+			self.pending_messages.push_back(ev);
+		}
 
 		while let Poll::Ready(Some(())) = self.tick_timeout.poll_next_unpin(cx) {
 			self.tick();
@@ -1338,6 +1525,14 @@ impl<B: BlockT> NetworkBehaviour for Protocol<B> {
 			let event = prepare_block_request(&mut self.peers, id, request);
 			self.pending_messages.push_back(event);
 		}
+		if let Some((id, request)) = self.sync.state_request() {
+			let event = prepare_state_request(&mut self.peers, id, request);
+			self.pending_messages.push_back(event);
+		}
+		if let Some((id, request)) = self.sync.warp_sync_request() {
+			let event = prepare_warp_sync_request(&mut self.peers, id, request);
+			self.pending_messages.push_back(event);
+		}
 
 		// Check if there is any block announcement validation finished.
 		while let Poll::Ready(result) = self.sync.poll_block_announce_validation(cx) {
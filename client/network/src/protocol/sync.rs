@@ -39,6 +39,11 @@ use sp_consensus::{BlockOrigin, BlockStatus,
 use crate::protocol::message::{
 	self, BlockAnnounce, BlockAttributes, BlockRequest, BlockResponse, Roles,
 };
+use crate::schema::v1::{StateResponse, StateRequest};
+use crate::config::SyncMode;
+use crate::warp_request_handler::{
+	EncodedProof, Request as WarpProofRequest, WarpSyncProvider,
+};
 use either::Either;
 use extra_requests::ExtraRequests;
 use libp2p::PeerId;
@@ -59,6 +64,13 @@ use futures::{task::Poll, Future, stream::FuturesUnordered, FutureExt, StreamExt
 
 mod blocks;
 mod extra_requests;
+mod state;
+mod warp;
+
+pub use state::StateDownloadProgress;
+pub use warp::{WarpSyncPhase, WarpSyncProgress};
+use state::StateSync;
+use warp::{WarpSync, WarpProofImportResult};
 
 /// Maximum blocks to request in a single packet.
 const MAX_BLOCKS_TO_REQUEST: usize = 128;
@@ -91,6 +103,14 @@ const MAX_CONCURRENT_BLOCK_ANNOUNCE_VALIDATIONS_PER_PEER: usize = 4;
 /// so far behind.
 const MAJOR_SYNC_BLOCKS: u8 = 5;
 
+/// In fast sync mode, the state is downloaded for the first finalized block that is at most
+/// this many blocks behind the median best block of our peers.
+const STATE_SYNC_FINALITY_THRESHOLD: u32 = 8;
+
+/// Minimum number of peers to be connected to before requesting warp sync proofs, so that
+/// the proofs are not all requested from the first peer to connect.
+const MIN_PEERS_TO_START_WARP_SYNC: usize = 3;
+
 mod rep {
 	use sc_peerset::ReputationChange as Rep;
 	/// Reputation change when a peer sent us a message that led to a
@@ -124,6 +144,12 @@ mod rep {
 
 	/// Peer response data does not have requested bits.
 	pub const BAD_RESPONSE: Rep = Rep::new(-(1 << 12), "Incomplete response");
+
+	/// Reputation change for peers which send us invalid state data.
+	pub const BAD_STATE: Rep = Rep::new(-(1 << 29), "Bad state");
+
+	/// Reputation change for peers which send us an invalid warp sync proof.
+	pub const BAD_WARP_PROOF: Rep = Rep::new(-(1 << 29), "Bad warp proof");
 }
 
 enum PendingRequests {
@@ -209,6 +235,17 @@ pub struct ChainSync<B: BlockT> {
 	>,
 	/// Stats per peer about the number of concurrent block announce validations.
 	block_announce_validation_per_peer_stats: HashMap<PeerId, usize>,
+	/// Current sync mode.
+	mode: SyncMode,
+	/// State sync in progress, if any.
+	state_sync: Option<StateSync<B>>,
+	/// Warp sync in progress, if any.
+	warp_sync: Option<WarpSync<B>>,
+	/// Warp sync provider, used to verify warp sync proofs.
+	warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
+	/// Enable importing existing blocks. This is used after the state download to
+	/// catch up to the latest state while re-importing blocks.
+	import_existing: bool,
 }
 
 /// All the data we have about a Peer that we are trying to sync with
@@ -281,6 +318,10 @@ pub enum PeerSyncState<B: BlockT> {
 	DownloadingStale(B::Hash),
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
+	/// Downloading state.
+	DownloadingState,
+	/// Downloading warp sync proof.
+	DownloadingWarpProof,
 }
 
 impl<B: BlockT> PeerSyncState<B> {
@@ -309,6 +350,10 @@ pub struct Status<B: BlockT> {
 	pub num_peers: u32,
 	/// Number of blocks queued for import
 	pub queued_blocks: u32,
+	/// State sync status in progress, if any.
+	pub state_sync: Option<StateDownloadProgress>,
+	/// Warp sync status in progress, if any.
+	pub warp_sync: Option<WarpSyncProgress>,
 }
 
 /// A peer did not behave as expected and should be reported.
@@ -415,6 +460,15 @@ enum PreValidateBlockAnnounce<H> {
 	Skip,
 }
 
+/// Result of [`ChainSync::on_state_data`].
+#[derive(Debug)]
+pub enum OnStateData<B: BlockT> {
+	/// The block and state that should be imported.
+	Import(BlockOrigin, IncomingBlock<B>),
+	/// A new state request needs to be made to the given peer.
+	Request(PeerId, StateRequest)
+}
+
 /// Result of [`ChainSync::on_block_justification`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OnBlockJustification<B: BlockT> {
@@ -443,18 +497,34 @@ impl<B: BlockT> ChainSync<B> {
 	/// Create a new instance.
 	pub fn new(
 		role: Roles,
+		mode: SyncMode,
 		client: Arc<dyn crate::chain::Client<B>>,
 		info: &BlockchainInfo<B>,
 		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
 		max_parallel_downloads: u32,
+		warp_sync_provider: Option<Arc<dyn WarpSyncProvider<B>>>,
 	) -> Self {
-		let mut required_block_attributes = BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION;
-
-		if role.is_full() {
-			required_block_attributes |= BlockAttributes::BODY
-		}
+		// Only full nodes are able to sync state.
+		let mut mode = if role.is_full() { mode } else { SyncMode::Full };
+
+		// Warp sync can only jump ahead of a fresh node.
+		let warp_sync = match (&mode, &warp_sync_provider) {
+			(SyncMode::Warp, Some(provider)) if info.best_number.is_zero() =>
+				Some(WarpSync::new(client.clone(), provider.clone())),
+			(SyncMode::Warp, Some(_)) => {
+				info!(target: "sync", "Existing chain found, warp sync is disabled.");
+				mode = SyncMode::Full;
+				None
+			},
+			(SyncMode::Warp, None) => {
+				warn!(target: "sync", "No warp sync provider configured, warp sync is disabled.");
+				mode = SyncMode::Full;
+				None
+			},
+			_ => None,
+		};
 
-		ChainSync {
+		let mut sync = ChainSync {
 			client,
 			peers: HashMap::new(),
 			blocks: BlockCollection::new(),
@@ -462,7 +532,7 @@ impl<B: BlockT> ChainSync<B> {
 			best_queued_number: info.best_number,
 			extra_justifications: ExtraRequests::new("justification"),
 			role,
-			required_block_attributes,
+			required_block_attributes: BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION,
 			queue_blocks: Default::default(),
 			fork_targets: Default::default(),
 			pending_requests: Default::default(),
@@ -471,6 +541,56 @@ impl<B: BlockT> ChainSync<B> {
 			downloaded_blocks: 0,
 			block_announce_validation: Default::default(),
 			block_announce_validation_per_peer_stats: Default::default(),
+			mode,
+			state_sync: None,
+			warp_sync,
+			warp_sync_provider,
+			import_existing: false,
+		};
+		sync.reset_sync_start_point(info);
+		sync
+	}
+
+	/// Block attributes to request, depending on the role and on the sync mode.
+	fn update_required_block_attributes(&mut self) {
+		let mut attributes = BlockAttributes::HEADER | BlockAttributes::JUSTIFICATION;
+		if self.role.is_full() && self.mode == SyncMode::Full {
+			attributes |= BlockAttributes::BODY;
+		}
+		self.required_block_attributes = attributes;
+	}
+
+	/// Whether blocks are imported without being executed.
+	fn skip_execution(&self) -> bool {
+		match self.mode {
+			SyncMode::Full => false,
+			SyncMode::Fast { .. } | SyncMode::Warp => true,
+		}
+	}
+
+	/// Set the block to sync from. In full mode, when the state of our best block is missing
+	/// (i.e. after a state sync), syncing restarts from the last finalized block that has state
+	/// and the blocks above it are imported again.
+	fn reset_sync_start_point(&mut self, info: &BlockchainInfo<B>) {
+		self.update_required_block_attributes();
+		self.import_existing = false;
+		self.best_queued_hash = info.best_hash;
+		self.best_queued_number = info.best_number;
+		if self.role.is_full() && self.mode == SyncMode::Full && !info.best_number.is_zero() {
+			let best_status = self.client.block_status(&BlockId::Hash(info.best_hash));
+			if !matches!(best_status, Ok(BlockStatus::InChainWithState)) {
+				self.import_existing = true;
+				let finalized_status = self.client.block_status(&BlockId::Hash(info.finalized_hash));
+				if matches!(finalized_status, Ok(BlockStatus::InChainWithState)) {
+					debug!(target: "sync", "Starting from finalized state #{}", info.finalized_number);
+					self.best_queued_hash = info.finalized_hash;
+					self.best_queued_number = info.finalized_number;
+				} else {
+					debug!(target: "sync", "Restarting from genesis");
+					self.best_queued_hash = info.genesis_hash;
+					self.best_queued_number = Zero::zero();
+				}
+			}
 		}
 	}
 
@@ -484,6 +604,16 @@ impl<B: BlockT> ChainSync<B> {
 	/// Returns the current sync status.
 	pub fn status(&self) -> Status<B> {
 		let best_seen = self.peers.values().map(|p| p.best_number).max();
+		let warp_sync_progress = self.warp_sync.as_ref().map(|w| {
+			let mut progress = w.progress();
+			if progress.phase == WarpSyncPhase::DownloadingWarpProofs
+				&& progress.total_bytes == 0
+				&& self.peers.len() < MIN_PEERS_TO_START_WARP_SYNC
+			{
+				progress.phase = WarpSyncPhase::AwaitingPeers;
+			}
+			progress
+		});
 		let sync_state =
 			if let Some(n) = best_seen {
 				// A chain is classified as downloading if the provided best block is
@@ -502,6 +632,8 @@ impl<B: BlockT> ChainSync<B> {
 			best_seen_block: best_seen,
 			num_peers: self.peers.len() as u32,
 			queued_blocks: self.queue_blocks.len() as u32,
+			state_sync: self.state_sync.as_ref().map(|s| s.progress()),
+			warp_sync: warp_sync_progress,
 		}
 	}
 
@@ -605,9 +737,16 @@ impl<B: BlockT> ChainSync<B> {
 					best_hash,
 					best_number,
 				);
+				// When existing blocks are imported again, the blocks above the one we sync from
+				// still have to be downloaded even though they are known.
+				let common_number = if self.import_existing {
+					std::cmp::min(best_number, self.best_queued_number)
+				} else {
+					best_number
+				};
 				self.peers.insert(who.clone(), PeerSync {
 					peer_id: who.clone(),
-					common_number: best_number,
+					common_number,
 					best_hash,
 					best_number,
 					state: PeerSyncState::Available,
@@ -718,7 +857,7 @@ impl<B: BlockT> ChainSync<B> {
 
 	/// Get an iterator over all block requests of all peers.
 	pub fn block_requests(&mut self) -> impl Iterator<Item = (&PeerId, BlockRequest<B>)> + '_ {
-		if self.pending_requests.is_empty() {
+		if self.pending_requests.is_empty() || self.state_sync.is_some() || self.warp_sync.is_some() {
 			return Either::Left(std::iter::empty())
 		}
 		if self.queue_blocks.len() > MAX_IMPORTING_BLOCKS {
@@ -818,6 +957,8 @@ impl<B: BlockT> ChainSync<B> {
 		response: BlockResponse<B>
 	) -> Result<OnBlockData<B>, BadPeer> {
 		self.downloaded_blocks += response.blocks.len();
+		let import_existing = self.import_existing;
+		let skip_execution = self.skip_execution();
 		let new_blocks: Vec<IncomingBlock<B>> =
 			if let Some(peer) = self.peers.get_mut(who) {
 				let mut blocks = response.blocks;
@@ -848,7 +989,9 @@ impl<B: BlockT> ChainSync<B> {
 										justifications,
 										origin: block_data.origin,
 										allow_missing_state: true,
-										import_existing: false,
+										import_existing,
+										state: None,
+										skip_execution,
 									}
 								}).collect()
 						}
@@ -870,7 +1013,9 @@ impl<B: BlockT> ChainSync<B> {
 									justifications,
 									origin: Some(who.clone()),
 									allow_missing_state: true,
-									import_existing: false,
+									import_existing,
+									state: None,
+									skip_execution,
 								}
 							}).collect()
 						}
@@ -966,7 +1111,9 @@ impl<B: BlockT> ChainSync<B> {
 						}
 
 						| PeerSyncState::Available
-						| PeerSyncState::DownloadingJustification(..) => Vec::new()
+						| PeerSyncState::DownloadingJustification(..)
+						| PeerSyncState::DownloadingState
+						| PeerSyncState::DownloadingWarpProof => Vec::new()
 					}
 				} else {
 					// When request.is_none() this is a block announcement. Just accept blocks.
@@ -982,7 +1129,9 @@ impl<B: BlockT> ChainSync<B> {
 							justifications,
 							origin: Some(who.clone()),
 							allow_missing_state: true,
-							import_existing: false,
+							import_existing,
+							state: None,
+							skip_execution,
 						}
 					}).collect()
 				}
@@ -1079,6 +1228,175 @@ impl<B: BlockT> ChainSync<B> {
 		Ok(OnBlockJustification::Nothing)
 	}
 
+	/// Get a state request, if any.
+	pub fn state_request(&mut self) -> Option<(PeerId, StateRequest)> {
+		if self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState) {
+			// Only one pending state request is allowed.
+			return None
+		}
+		if let Some(sync) = &self.state_sync {
+			if sync.is_complete() {
+				return None
+			}
+			for (id, peer) in self.peers.iter_mut() {
+				if peer.state.is_available() && peer.common_number >= sync.target_block_num() {
+					trace!(target: "sync", "New StateRequest for {}", id);
+					peer.state = PeerSyncState::DownloadingState;
+					return Some((id.clone(), sync.next_request()))
+				}
+			}
+		}
+		if let Some(sync) = &self.warp_sync {
+			if sync.is_complete() {
+				return None
+			}
+			if let (Some(request), Some(target)) = (sync.next_state_request(), sync.target_block_number()) {
+				// We don't have the blocks below the target, only ask peers that have it.
+				for (id, peer) in self.peers.iter_mut() {
+					if peer.state.is_available() && peer.best_number >= target {
+						trace!(target: "sync", "New StateRequest for {}", id);
+						peer.state = PeerSyncState::DownloadingState;
+						return Some((id.clone(), request))
+					}
+				}
+			}
+		}
+		None
+	}
+
+	/// Get a warp sync request, if any.
+	pub fn warp_sync_request(&mut self) -> Option<(PeerId, WarpProofRequest<B>)> {
+		if self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingWarpProof) {
+			// Only one pending warp proof request is allowed.
+			return None
+		}
+		if self.peers.len() < MIN_PEERS_TO_START_WARP_SYNC {
+			return None
+		}
+		if let Some(request) = self.warp_sync.as_ref().and_then(|sync| sync.next_warp_proof_request()) {
+			// Ask one of the peers with the highest best block, they are the most likely to have
+			// the latest finalized block.
+			let median = median_best_number(&self.peers);
+			for (id, peer) in self.peers.iter_mut() {
+				if peer.state.is_available() && peer.best_number >= median {
+					trace!(target: "sync", "New WarpProofRequest for {}", id);
+					peer.state = PeerSyncState::DownloadingWarpProof;
+					return Some((id.clone(), request))
+				}
+			}
+		}
+		None
+	}
+
+	/// Handle a response from the remote to a state request that we made.
+	///
+	/// Returns next request if any.
+	pub fn on_state_data(
+		&mut self,
+		who: &PeerId,
+		response: StateResponse,
+	) -> Result<OnStateData<B>, BadPeer> {
+		if let Some(peer) = self.peers.get_mut(who) {
+			if let PeerSyncState::DownloadingState = peer.state {
+				peer.state = PeerSyncState::Available;
+			}
+		}
+
+		let import_result = if let Some(sync) = &mut self.state_sync {
+			debug!(
+				target: "sync",
+				"Importing state data from {} with {} keys, {} proof nodes.",
+				who,
+				response.entries.iter().map(|entry| entry.entries.len()).sum::<usize>(),
+				response.proof.len(),
+			);
+			sync.import(response)
+		} else if let Some(sync) = &mut self.warp_sync {
+			debug!(
+				target: "sync",
+				"Importing state data from {} with {} keys, {} proof nodes.",
+				who,
+				response.entries.iter().map(|entry| entry.entries.len()).sum::<usize>(),
+				response.proof.len(),
+			);
+			sync.import_state(response)
+		} else {
+			debug!(target: "sync", "Ignored obsolete state response from {}", who);
+			return Err(BadPeer(who.clone(), rep::NOT_REQUESTED));
+		};
+
+		match import_result {
+			state::ImportResult::Import(hash, header, state) => {
+				let origin = if self.status().state != SyncState::Downloading {
+					BlockOrigin::NetworkBroadcast
+				} else {
+					BlockOrigin::NetworkInitialSync
+				};
+
+				let block = IncomingBlock {
+					hash,
+					header: Some(header),
+					body: None,
+					justifications: None,
+					origin: None,
+					allow_missing_state: true,
+					import_existing: true,
+					skip_execution: self.skip_execution(),
+					state: Some(state),
+				};
+				debug!(target: "sync", "State sync is complete. Import is queued");
+				Ok(OnStateData::Import(origin, block))
+			}
+			state::ImportResult::Continue(request) => {
+				if let Some(peer) = self.peers.get_mut(who) {
+					peer.state = PeerSyncState::DownloadingState;
+				}
+				Ok(OnStateData::Request(who.clone(), request))
+			}
+			state::ImportResult::BadResponse => {
+				debug!(target: "sync", "Bad state data received from {}", who);
+				Err(BadPeer(who.clone(), rep::BAD_STATE))
+			}
+		}
+	}
+
+	/// Handle a response from the remote to a warp proof request that we made.
+	///
+	/// The next request, if any, is returned by [`Self::warp_sync_request`] or
+	/// [`Self::state_request`].
+	pub fn on_warp_sync_data(
+		&mut self,
+		who: &PeerId,
+		response: EncodedProof,
+	) -> Result<(), BadPeer> {
+		if let Some(peer) = self.peers.get_mut(who) {
+			if let PeerSyncState::DownloadingWarpProof = peer.state {
+				peer.state = PeerSyncState::Available;
+			}
+		}
+
+		let import_result = if let Some(sync) = &mut self.warp_sync {
+			debug!(
+				target: "sync",
+				"Importing warp proof data from {}, {} bytes.",
+				who,
+				response.0.len(),
+			);
+			sync.import_warp_proof(response)
+		} else {
+			debug!(target: "sync", "Ignored obsolete warp sync response from {}", who);
+			return Err(BadPeer(who.clone(), rep::NOT_REQUESTED));
+		};
+
+		match import_result {
+			WarpProofImportResult::WarpProofRequest(_) | WarpProofImportResult::StateRequest(_) => Ok(()),
+			WarpProofImportResult::BadResponse => {
+				debug!(target: "sync", "Bad proof data received from {}", who);
+				Err(BadPeer(who.clone(), rep::BAD_WARP_PROOF))
+			}
+		}
+	}
+
 	/// A batch of blocks have been processed, with or without errors.
 	///
 	/// Call this when a batch of blocks have been processed by the import
@@ -1106,6 +1424,17 @@ impl<B: BlockT> ChainSync<B> {
 
 			if result.is_err() {
 				has_error = true;
+				if self.state_sync.as_ref().map_or(false, |s| s.target() == hash) {
+					// Download the state again, for the next finalized block.
+					warn!(target: "sync", "💔 Error importing downloaded state for block {:?}", hash);
+					self.state_sync = None;
+				}
+				if self.warp_sync.as_ref().map_or(false, |s| s.target_block_hash() == Some(hash)) {
+					// Start over, the next attempt may target a more recent finalized block.
+					warn!(target: "sync", "💔 Error importing warp synced state for block {:?}", hash);
+					self.warp_sync = self.warp_sync_provider.as_ref()
+						.map(|provider| WarpSync::new(self.client.clone(), provider.clone()));
+				}
 			}
 
 			match result {
@@ -1145,6 +1474,30 @@ impl<B: BlockT> ChainSync<B> {
 					if let Some(peer) = who.and_then(|p| self.peers.get_mut(&p)) {
 						peer.update_common_number(number);
 					}
+
+					let state_sync_complete = self.state_sync.as_ref().map_or(false, |s| s.target() == hash);
+					if state_sync_complete {
+						info!(
+							target: "sync",
+							"State sync is complete ({} MiB), restarting block sync.",
+							self.state_sync.as_ref().map_or(0, |s| s.progress().size / (1024 * 1024)),
+						);
+						self.state_sync = None;
+						self.mode = SyncMode::Full;
+						output.extend(self.restart());
+					}
+					let warp_sync_complete = self.warp_sync.as_ref()
+						.map_or(false, |s| s.target_block_hash() == Some(hash));
+					if warp_sync_complete {
+						info!(
+							target: "sync",
+							"Warp sync is complete ({} MiB), restarting block sync.",
+							self.warp_sync.as_ref().map_or(0, |s| s.progress().total_bytes / (1024 * 1024)),
+						);
+						self.warp_sync = None;
+						self.mode = SyncMode::Full;
+						output.extend(self.restart());
+					}
 				},
 				Err(BlockImportError::IncompleteHeader(who)) => {
 					if let Some(peer) = who {
@@ -1221,6 +1574,19 @@ impl<B: BlockT> ChainSync<B> {
 				err,
 			);
 		}
+
+		if let SyncMode::Fast { skip_proofs } = self.mode {
+			if self.state_sync.is_none() && !self.peers.is_empty() && self.queue_blocks.is_empty() {
+				// Finalized a recent block.
+				let median = median_best_number(&self.peers);
+				if number + STATE_SYNC_FINALITY_THRESHOLD.saturated_into() >= median {
+					if let Ok(Some(header)) = self.client.header(BlockId::hash(hash.clone())) {
+						debug!(target: "sync", "Starting state sync for #{} ({})", number, hash);
+						self.state_sync = Some(StateSync::new(self.client.clone(), header, skip_proofs));
+					}
+				}
+			}
+		}
 	}
 
 	/// Called when a block has been queued for import.
@@ -1573,6 +1939,8 @@ impl<B: BlockT> ChainSync<B> {
 	/// Canceled obsolete block request may result in some blocks being ready for
 	/// import, so this functions checks for such blocks and returns them.
 	pub fn peer_disconnected(&mut self, who: &PeerId) -> Option<OnBlockData<B>> {
+		let import_existing = self.import_existing;
+		let skip_execution = self.skip_execution();
 		self.blocks.clear_peer_download(who);
 		self.peers.remove(who);
 		self.extra_justifications.peer_disconnected(who);
@@ -1594,7 +1962,9 @@ impl<B: BlockT> ChainSync<B> {
 					justifications,
 					origin: block_data.origin,
 					allow_missing_state: true,
-					import_existing: false,
+					import_existing,
+					state: None,
+					skip_execution,
 				}
 			}).collect();
 		if !blocks.is_empty() {
@@ -1612,19 +1982,20 @@ impl<B: BlockT> ChainSync<B> {
 	) -> impl Iterator<Item = Result<(PeerId, BlockRequest<B>), BadPeer>> + 'a {
 		self.blocks.clear();
 		let info = self.client.info();
-		self.best_queued_hash = info.best_hash;
-		self.best_queued_number = info.best_number;
+		self.reset_sync_start_point(&info);
 		self.pending_requests.set_all();
 		debug!(target:"sync", "Restarted with {} ({})", self.best_queued_number, self.best_queued_hash);
 		let old_peers = std::mem::take(&mut self.peers);
 
 		old_peers.into_iter().filter_map(move |(id, mut p)| {
-			// peers that were downloading justifications
+			// peers that were downloading justifications or state
 			// should be kept in that state.
 			match p.state {
-				PeerSyncState::DownloadingJustification(_) => {
+				PeerSyncState::DownloadingJustification(_)
+				| PeerSyncState::DownloadingState
+				| PeerSyncState::DownloadingWarpProof => {
 					// We make sure our commmon number is at least something we have.
-					p.common_number = info.best_number;
+					p.common_number = self.best_queued_number;
 					self.peers.insert(id, p);
 					return None;
 				}
@@ -1686,6 +2057,13 @@ pub(crate) struct Metrics {
 	_priv: ()
 }
 
+/// Returns the median best block number of the given peers, which must not be empty.
+fn median_best_number<B: BlockT>(peers: &HashMap<PeerId, PeerSync<B>>) -> NumberFor<B> {
+	let mut heads: Vec<_> = peers.values().map(|peer| peer.best_number).collect();
+	heads.sort();
+	heads[heads.len() / 2]
+}
+
 /// Request the ancestry for a block. Sends a request for header and justification for the given
 /// block number. Used during ancestry search.
 fn ancestry_request<B: BlockT>(block: NumberFor<B>) -> BlockRequest<B> {
@@ -2000,10 +2378,12 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			block_announce_validator,
 			1,
+			None,
 		);
 
 		let (a1_hash, a1_number) = {
@@ -2071,10 +2451,12 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
 			1,
+			None,
 		);
 
 		let peer_id1 = PeerId::random();
@@ -2246,10 +2628,12 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
 			5,
+			None,
 		);
 
 		let peer_id1 = PeerId::random();
@@ -2360,10 +2744,12 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
 			5,
+			None,
 		);
 
 		let peer_id1 = PeerId::random();
@@ -2482,10 +2868,12 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
 			5,
+			None,
 		);
 
 		let finalized_block = blocks[MAX_BLOCKS_TO_LOOK_BACKWARDS as usize * 2 - 1].clone();
@@ -2596,10 +2984,12 @@ mod test {
 
 		let mut sync = ChainSync::new(
 			Roles::AUTHORITY,
+			SyncMode::Full,
 			client.clone(),
 			&info,
 			Box::new(DefaultBlockAnnounceValidator),
 			1,
+			None,
 		);
 
 		let peer_id1 = PeerId::random();
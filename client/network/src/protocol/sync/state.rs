@@ -0,0 +1,224 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! State sync support.
+//!
+//! Downloads the full state of a target block in chunks, verifying each chunk against the
+//! state root of the target header.
+
+use std::sync::Arc;
+use std::collections::BTreeMap;
+use codec::{Encode, Decode};
+use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+use sc_client_api::{StorageProof, KeyValueStates, KeyValueStorageLevel};
+use sp_consensus::ImportedState;
+use crate::schema::v1::{StateRequest, StateResponse};
+use crate::chain::Client;
+
+/// State sync support.
+pub struct StateSync<B: BlockT> {
+	target_block: B::Hash,
+	target_header: B::Header,
+	target_root: B::Hash,
+	/// Last read key, either a top trie key or a child trie prefixed key followed by
+	/// the last key read from the child trie.
+	last_key: Vec<Vec<u8>>,
+	top: Vec<(Vec<u8>, Vec<u8>)>,
+	children: BTreeMap<Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>>,
+	complete: bool,
+	client: Arc<dyn Client<B>>,
+	imported_bytes: u64,
+	skip_proof: bool,
+}
+
+/// Import state chunk result.
+pub enum ImportResult<B: BlockT> {
+	/// State is complete and ready for import.
+	Import(B::Hash, B::Header, ImportedState<B>),
+	/// Continue downloading.
+	Continue(StateRequest),
+	/// Bad state chunk.
+	BadResponse,
+}
+
+/// Reported state download progress.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct StateDownloadProgress {
+	/// Estimated download percentage.
+	pub percentage: u32,
+	/// Total state size in bytes downloaded so far.
+	pub size: u64,
+}
+
+impl<B: BlockT> StateSync<B> {
+	/// Create a new instance.
+	pub fn new(client: Arc<dyn Client<B>>, target: B::Header, skip_proof: bool) -> Self {
+		StateSync {
+			client,
+			target_block: target.hash(),
+			target_root: target.state_root().clone(),
+			target_header: target,
+			last_key: Vec::default(),
+			top: Vec::default(),
+			children: BTreeMap::default(),
+			complete: false,
+			imported_bytes: 0,
+			skip_proof,
+		}
+	}
+
+	/// Validate and import a state response.
+	pub fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+		if response.entries.is_empty() && response.proof.is_empty() {
+			log::debug!(target: "sync", "Bad state response");
+			return ImportResult::BadResponse;
+		}
+		if !self.skip_proof && response.proof.is_empty() {
+			log::debug!(target: "sync", "Missing proof");
+			return ImportResult::BadResponse;
+		}
+
+		let (values, size) = if !self.skip_proof {
+			let proof_size = response.proof.len() as u64;
+			let proof = match StorageProof::decode(&mut response.proof.as_ref()) {
+				Ok(proof) => proof,
+				Err(e) => {
+					log::debug!(target: "sync", "Error decoding proof: {:?}", e);
+					return ImportResult::BadResponse;
+				}
+			};
+			match self.client.verify_range_proof(self.target_root, proof, &self.last_key) {
+				Ok(values) => (values, proof_size),
+				Err(e) => {
+					log::debug!(target: "sync", "StateResponse failed proof verification: {:?}", e);
+					return ImportResult::BadResponse;
+				},
+			}
+		} else {
+			let values = KeyValueStates(response.entries.into_iter().map(|entry| KeyValueStorageLevel {
+				storage_key: entry.storage_key,
+				key_values: entry.entries.into_iter().map(|e| (e.key, e.value)).collect(),
+				complete: entry.complete,
+			}).collect());
+			let size = values.0.iter()
+				.flat_map(|level| level.key_values.iter())
+				.map(|(k, v)| (k.len() + v.len()) as u64)
+				.sum();
+			(values, size)
+		};
+
+		let complete = values.is_complete();
+		let mut next_key = None;
+		let mut num_keys = 0;
+		for level in values.0 {
+			num_keys += level.key_values.len();
+			if level.storage_key.is_empty() {
+				if let Some((key, _)) = level.key_values.last() {
+					next_key = Some(vec![key.clone()]);
+				}
+				self.top.extend(level.key_values);
+			} else {
+				if !level.complete {
+					// Resume reading the child trie where this response stopped.
+					let last = match level.key_values.last() {
+						Some((key, _)) => key.clone(),
+						None if self.last_key.first() == Some(&level.storage_key) =>
+							self.last_key.get(1).cloned().unwrap_or_default(),
+						None => Vec::new(),
+					};
+					next_key = Some(vec![level.storage_key.clone(), last]);
+				}
+				self.children.entry(level.storage_key).or_default().extend(level.key_values);
+			}
+		}
+		log::debug!(target: "sync", "Imported with {} keys", num_keys);
+
+		if num_keys == 0 && !complete {
+			log::debug!(target: "sync", "Empty incomplete state response");
+			return ImportResult::BadResponse;
+		}
+		if let Some(next_key) = next_key {
+			self.last_key = next_key;
+		}
+		self.imported_bytes += size;
+
+		if complete {
+			self.complete = true;
+			let mut state = vec![KeyValueStorageLevel {
+				storage_key: Vec::new(),
+				key_values: std::mem::take(&mut self.top),
+				complete: true,
+			}];
+			state.extend(std::mem::take(&mut self.children).into_iter().map(|(storage_key, key_values)| {
+				KeyValueStorageLevel { storage_key, key_values, complete: true }
+			}));
+			ImportResult::Import(
+				self.target_block,
+				self.target_header.clone(),
+				ImportedState { block: self.target_block, state: KeyValueStates(state) },
+			)
+		} else {
+			ImportResult::Continue(self.next_request())
+		}
+	}
+
+	/// Produce next state request.
+	pub fn next_request(&self) -> StateRequest {
+		StateRequest {
+			block: self.target_block.encode(),
+			start: self.last_key.clone(),
+			no_proof: self.skip_proof,
+		}
+	}
+
+	/// Check if the state is complete.
+	pub fn is_complete(&self) -> bool {
+		self.complete
+	}
+
+	/// Returns target block number.
+	pub fn target_block_num(&self) -> NumberFor<B> {
+		*self.target_header.number()
+	}
+
+	/// Returns target block hash.
+	pub fn target(&self) -> B::Hash {
+		self.target_block
+	}
+
+	/// Returns state sync estimated progress.
+	///
+	/// The estimate is based on the position in the top trie only, assuming its keys are evenly
+	/// distributed. Child tries are read when their root is reached in the top trie, so while
+	/// reading one the position is the one of its prefixed storage key, not of the last key read
+	/// from the child trie itself.
+	pub fn progress(&self) -> StateDownloadProgress {
+		let percent_done = self.last_key.first()
+			.map_or(0, |top_key| {
+				let position = top_key.iter()
+					.chain(std::iter::repeat(&0))
+					.take(2)
+					.fold(0u32, |position, b| (position << 8) | *b as u32);
+				position * 100 / 0x10000
+			});
+		StateDownloadProgress {
+			percentage: percent_done,
+			size: self.imported_bytes,
+		}
+	}
+}
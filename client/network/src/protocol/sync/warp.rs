@@ -0,0 +1,407 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Warp sync support.
+//!
+//! Follows the chain of authority set handoffs from genesis to the latest finalized block
+//! using warp sync proofs, then downloads the state of that block.
+
+use std::sync::Arc;
+use std::fmt;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_finality_grandpa::{AuthorityList, SetId};
+use crate::schema::v1::{StateRequest, StateResponse};
+use crate::chain::Client;
+use crate::warp_request_handler::{
+	EncodedProof, Request as WarpProofRequest, VerificationResult, WarpSyncProvider,
+};
+use super::state::{StateSync, ImportResult};
+
+enum Phase<B: BlockT> {
+	WarpProof { set_id: SetId, authorities: AuthorityList, last_hash: B::Hash },
+	State(StateSync<B>),
+}
+
+/// Import warp proof result.
+pub enum WarpProofImportResult<B: BlockT> {
+	/// Start downloading state data.
+	StateRequest(StateRequest),
+	/// Continue downloading warp sync proofs.
+	WarpProofRequest(WarpProofRequest<B>),
+	/// Bad proof.
+	BadResponse,
+}
+
+/// Reported warp sync phase.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum WarpSyncPhase {
+	/// Waiting for peers to connect.
+	AwaitingPeers,
+	/// Downloading and verifying grandpa warp proofs.
+	DownloadingWarpProofs,
+	/// Downloading state data.
+	DownloadingState,
+	/// Importing state.
+	ImportingState,
+}
+
+impl fmt::Display for WarpSyncPhase {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::AwaitingPeers => write!(f, "Waiting for peers"),
+			Self::DownloadingWarpProofs => write!(f, "Downloading finality proofs"),
+			Self::DownloadingState => write!(f, "Downloading state"),
+			Self::ImportingState => write!(f, "Importing state"),
+		}
+	}
+}
+
+/// Reported warp sync progress.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct WarpSyncProgress {
+	/// Current warp sync phase.
+	pub phase: WarpSyncPhase,
+	/// Total bytes downloaded so far.
+	pub total_bytes: u64,
+}
+
+/// Warp sync state machine. Accumulates warp proofs and state.
+pub struct WarpSync<B: BlockT> {
+	phase: Phase<B>,
+	client: Arc<dyn Client<B>>,
+	warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	total_proof_bytes: u64,
+}
+
+impl<B: BlockT> WarpSync<B> {
+	/// Create a new instance.
+	pub fn new(
+		client: Arc<dyn Client<B>>,
+		warp_sync_provider: Arc<dyn WarpSyncProvider<B>>,
+	) -> Self {
+		let last_hash = client.info().genesis_hash;
+		let phase = Phase::WarpProof {
+			set_id: 0,
+			authorities: warp_sync_provider.current_authorities(),
+			last_hash,
+		};
+		WarpSync {
+			client,
+			warp_sync_provider,
+			phase,
+			total_proof_bytes: 0,
+		}
+	}
+
+	/// Validate and import a state response.
+	pub fn import_state(&mut self, response: StateResponse) -> ImportResult<B> {
+		match &mut self.phase {
+			Phase::WarpProof { .. } => {
+				log::debug!(target: "sync", "Unexpected state response");
+				ImportResult::BadResponse
+			},
+			Phase::State(sync) => sync.import(response),
+		}
+	}
+
+	/// Validate and import a warp proof response.
+	pub fn import_warp_proof(&mut self, response: EncodedProof) -> WarpProofImportResult<B> {
+		match &mut self.phase {
+			Phase::State(_) => {
+				log::debug!(target: "sync", "Unexpected warp proof response");
+				WarpProofImportResult::BadResponse
+			},
+			Phase::WarpProof { set_id, authorities, last_hash } => {
+				match self.warp_sync_provider.verify(&response, *set_id, authorities.clone()) {
+					Err(e) => {
+						log::debug!(target: "sync", "Bad warp proof response: {}", e);
+						WarpProofImportResult::BadResponse
+					},
+					Ok(VerificationResult::Partial(new_set_id, new_authorities, new_last_hash)) => {
+						log::debug!(target: "sync", "Verified partial proof, set_id={:?}", new_set_id);
+						*set_id = new_set_id;
+						*authorities = new_authorities;
+						*last_hash = new_last_hash.clone();
+						self.total_proof_bytes += response.0.len() as u64;
+						WarpProofImportResult::WarpProofRequest(WarpProofRequest { begin: new_last_hash })
+					},
+					Ok(VerificationResult::Complete(new_set_id, _, header)) => {
+						log::debug!(target: "sync", "Verified complete proof, set_id={:?}", new_set_id);
+						self.total_proof_bytes += response.0.len() as u64;
+						let state_sync = StateSync::new(self.client.clone(), header, false);
+						let request = state_sync.next_request();
+						self.phase = Phase::State(state_sync);
+						WarpProofImportResult::StateRequest(request)
+					},
+				}
+			},
+		}
+	}
+
+	/// Produce next state request.
+	pub fn next_state_request(&self) -> Option<StateRequest> {
+		match &self.phase {
+			Phase::WarpProof { .. } => None,
+			Phase::State(sync) => Some(sync.next_request()),
+		}
+	}
+
+	/// Produce next warp proof request.
+	pub fn next_warp_proof_request(&self) -> Option<WarpProofRequest<B>> {
+		match &self.phase {
+			Phase::State(_) => None,
+			Phase::WarpProof { last_hash, .. } => {
+				Some(WarpProofRequest { begin: last_hash.clone() })
+			},
+		}
+	}
+
+	/// Return target block hash if it is known.
+	pub fn target_block_hash(&self) -> Option<B::Hash> {
+		match &self.phase {
+			Phase::State(s) => Some(s.target()),
+			Phase::WarpProof { .. } => None,
+		}
+	}
+
+	/// Return target block number if it is known.
+	pub fn target_block_number(&self) -> Option<NumberFor<B>> {
+		match &self.phase {
+			Phase::State(s) => Some(s.target_block_num()),
+			Phase::WarpProof { .. } => None,
+		}
+	}
+
+	/// Check if the state is complete.
+	pub fn is_complete(&self) -> bool {
+		match &self.phase {
+			Phase::WarpProof { .. } => false,
+			Phase::State(sync) => sync.is_complete(),
+		}
+	}
+
+	/// Returns warp sync estimated progress.
+	pub fn progress(&self) -> WarpSyncProgress {
+		match &self.phase {
+			Phase::WarpProof { .. } => WarpSyncProgress {
+				phase: WarpSyncPhase::DownloadingWarpProofs,
+				total_bytes: self.total_proof_bytes,
+			},
+			Phase::State(sync) => WarpSyncProgress {
+				phase: if self.is_complete() {
+					WarpSyncPhase::ImportingState
+				} else {
+					WarpSyncPhase::DownloadingState
+				},
+				total_bytes: self.total_proof_bytes + sync.progress().size,
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::collections::VecDeque;
+	use codec::{Encode, Decode};
+	use parking_lot::Mutex;
+	use sc_block_builder::BlockBuilderProvider;
+	use sc_client_api::{ProofProvider, StorageProvider};
+	use sp_blockchain::HeaderBackend;
+	use sp_consensus::BlockOrigin;
+	use sp_keyring::Ed25519Keyring;
+	use sp_core::storage::StorageKey;
+	use sp_runtime::generic::BlockId;
+	use substrate_test_runtime_client::{
+		runtime::{Block, Hash, Header},
+		BlockBuilderExt, ClientBlockImportExt, DefaultTestClientBuilderExt, TestClientBuilder,
+		TestClientBuilderExt, TestClient,
+	};
+	use futures::executor::block_on;
+
+	/// Warp sync provider returning pre-recorded verification results.
+	#[derive(Default)]
+	struct TestProvider {
+		results: Mutex<VecDeque<Result<VerificationResult<Block>, String>>>,
+		verified: Mutex<Vec<(SetId, AuthorityList)>>,
+	}
+
+	impl TestProvider {
+		fn new(results: Vec<Result<VerificationResult<Block>, String>>) -> Arc<Self> {
+			Arc::new(TestProvider { results: Mutex::new(results.into()), ..Default::default() })
+		}
+	}
+
+	impl WarpSyncProvider<Block> for TestProvider {
+		fn generate(
+			&self,
+			_start: Hash,
+		) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>> {
+			unimplemented!()
+		}
+
+		fn verify(
+			&self,
+			_proof: &EncodedProof,
+			set_id: SetId,
+			authorities: AuthorityList,
+		) -> Result<VerificationResult<Block>, Box<dyn std::error::Error + Send + Sync>> {
+			self.verified.lock().push((set_id, authorities));
+			self.results.lock().pop_front().expect("Unexpected verification").map_err(Into::into)
+		}
+
+		fn current_authorities(&self) -> AuthorityList {
+			authorities(Ed25519Keyring::Alice)
+		}
+	}
+
+	fn authorities(key: Ed25519Keyring) -> AuthorityList {
+		vec![(key.public().into(), 1)]
+	}
+
+	/// Build a chain of two blocks and return the client with the header of the last one.
+	fn source_chain() -> (TestClient, Header) {
+		let mut client = TestClientBuilder::new().build();
+		for i in 0u8..2 {
+			let mut builder = client.new_block(Default::default()).unwrap();
+			builder.push_storage_change(vec![i; 4], Some(vec![i; 64])).unwrap();
+			let block = builder.build().unwrap().block;
+			block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		}
+		let header = client.header(&BlockId::Number(2)).unwrap().unwrap();
+		(client, header)
+	}
+
+	/// Answer a state request the way the state request handler does.
+	fn state_response(client: &TestClient, request: &StateRequest) -> StateResponse {
+		let block = Hash::decode(&mut request.block.as_ref()).unwrap();
+		let (proof, _) = client
+			.read_proof_collection(&BlockId::Hash(block), &request.start, 4 * 1024)
+			.unwrap();
+		StateResponse { entries: Vec::new(), proof: proof.encode() }
+	}
+
+	#[test]
+	fn partial_proof_advances_authority_set() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let last_hash = Hash::repeat_byte(1);
+		let provider = TestProvider::new(vec![
+			Ok(VerificationResult::Partial(1, authorities(Ed25519Keyring::Bob), last_hash)),
+			Err("Bad proof".into()),
+		]);
+		let mut sync = WarpSync::new(client.clone(), provider.clone());
+		assert_eq!(sync.next_warp_proof_request().unwrap().begin, client.info().genesis_hash);
+
+		match sync.import_warp_proof(EncodedProof(vec![0; 10])) {
+			WarpProofImportResult::WarpProofRequest(request) => assert_eq!(request.begin, last_hash),
+			_ => panic!("Expected a warp proof request"),
+		}
+		assert_eq!(sync.next_warp_proof_request().unwrap().begin, last_hash);
+		assert!(sync.next_state_request().is_none());
+		assert!(sync.target_block_hash().is_none());
+
+		// A proof failing verification does not move the sync forward.
+		assert!(matches!(
+			sync.import_warp_proof(EncodedProof(vec![0; 5])),
+			WarpProofImportResult::BadResponse,
+		));
+		assert_eq!(sync.next_warp_proof_request().unwrap().begin, last_hash);
+		assert_eq!(
+			*provider.verified.lock(),
+			vec![
+				(0, authorities(Ed25519Keyring::Alice)),
+				(1, authorities(Ed25519Keyring::Bob)),
+			],
+		);
+		assert_eq!(
+			sync.progress(),
+			WarpSyncProgress { phase: WarpSyncPhase::DownloadingWarpProofs, total_bytes: 10 },
+		);
+	}
+
+	#[test]
+	fn state_response_before_complete_proof_is_rejected() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let mut sync = WarpSync::new(client, TestProvider::new(Vec::new()));
+		let response = StateResponse { entries: Vec::new(), proof: vec![1, 2, 3] };
+		assert!(matches!(sync.import_state(response), ImportResult::BadResponse));
+		assert!(!sync.is_complete());
+	}
+
+	#[test]
+	fn complete_proof_hands_off_to_state_sync() {
+		let (source, header) = source_chain();
+		let client = Arc::new(TestClientBuilder::new().build());
+		let provider = TestProvider::new(vec![
+			Ok(VerificationResult::Complete(1, authorities(Ed25519Keyring::Bob), header.clone())),
+		]);
+		let mut sync = WarpSync::new(client, provider);
+
+		let mut request = match sync.import_warp_proof(EncodedProof(vec![0; 10])) {
+			WarpProofImportResult::StateRequest(request) => request,
+			_ => panic!("Expected a state request"),
+		};
+		assert_eq!(request.block, header.hash().encode());
+		assert!(request.start.is_empty());
+		assert!(!request.no_proof);
+		assert_eq!(sync.target_block_hash(), Some(header.hash()));
+		assert_eq!(sync.target_block_number(), Some(2));
+		assert!(sync.next_warp_proof_request().is_none());
+		assert_eq!(sync.progress().phase, WarpSyncPhase::DownloadingState);
+
+		// Further proofs are not expected once the target is known.
+		assert!(matches!(
+			sync.import_warp_proof(EncodedProof(vec![0; 10])),
+			WarpProofImportResult::BadResponse,
+		));
+
+		// State proven against another block's root is rejected.
+		let genesis = source.info().genesis_hash;
+		let forged = StateRequest { block: genesis.encode(), ..request.clone() };
+		assert!(matches!(
+			sync.import_state(state_response(&source, &forged)),
+			ImportResult::BadResponse,
+		));
+
+		let imported = loop {
+			match sync.import_state(state_response(&source, &request)) {
+				ImportResult::Continue(next) => {
+					assert_eq!(sync.next_state_request(), Some(next.clone()));
+					request = next;
+				},
+				ImportResult::Import(hash, imported_header, state) => {
+					assert_eq!(hash, header.hash());
+					assert_eq!(imported_header, header);
+					break state;
+				},
+				ImportResult::BadResponse => panic!("Valid state response rejected"),
+			}
+		};
+		assert!(sync.is_complete());
+		assert_eq!(sync.progress().phase, WarpSyncPhase::ImportingState);
+
+		let top = &imported.state.0[0];
+		assert!(top.storage_key.is_empty());
+		assert!(top.key_values.contains(&(vec![0; 4], vec![0; 64])));
+		assert!(top.key_values.contains(&(vec![1; 4], vec![1; 64])));
+		let expected = source
+			.storage_keys(&BlockId::Hash(header.hash()), &StorageKey(Vec::new()))
+			.unwrap();
+		assert_eq!(top.key_values.len(), expected.len());
+	}
+}
@@ -48,7 +48,7 @@ use crate::{
 		Protocol,
 		Ready,
 		event::Event,
-		sync::SyncState,
+		sync::{SyncState, StateDownloadProgress, WarpSyncProgress},
 	},
 	transactions,
 	transport, ReputationChange,
@@ -196,6 +196,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			protocol::ProtocolConfig {
 				roles: From::from(&params.role),
 				max_parallel_downloads: params.network_config.max_parallel_downloads,
+				sync_mode: params.network_config.sync_mode,
 			},
 			params.chain.clone(),
 			params.protocol_id.clone(),
@@ -204,6 +205,7 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 				.map(|_| default_notif_handshake_message.clone())).collect(),
 			params.block_announce_validator,
 			params.metrics_registry.as_ref(),
+			params.warp_sync.as_ref().map(|(provider, _)| provider.clone()),
 		)?;
 
 		// List of multiaddresses that we know in the network.
@@ -339,6 +341,8 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 					light_client_request_sender,
 					discovery_config,
 					params.block_request_protocol_config,
+					params.state_request_protocol_config,
+					params.warp_sync.as_ref().map(|(_, config)| config.clone()),
 					bitswap,
 					params.light_client_request_protocol_config,
 					params.network_config.request_response_protocols,
@@ -450,6 +454,8 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 			num_active_peers: self.num_active_peers(),
 			total_bytes_inbound: self.total_bytes_inbound(),
 			total_bytes_outbound: self.total_bytes_outbound(),
+			state_sync: self.state_sync_progress(),
+			warp_sync: self.warp_sync_progress(),
 		}
 	}
 
@@ -488,6 +494,16 @@ impl<B: BlockT + 'static, H: ExHashT> NetworkWorker<B, H> {
 		self.network_service.behaviour().user_protocol().num_sync_peers()
 	}
 
+	/// State download progress, if a state sync is in progress.
+	pub fn state_sync_progress(&self) -> Option<StateDownloadProgress> {
+		self.network_service.behaviour().user_protocol().state_sync_progress()
+	}
+
+	/// Warp sync progress, if a warp sync is in progress.
+	pub fn warp_sync_progress(&self) -> Option<WarpSyncProgress> {
+		self.network_service.behaviour().user_protocol().warp_sync_progress()
+	}
+
 	/// Number of blocks in the import queue.
 	pub fn num_queued_blocks(&self) -> u32 {
 		self.network_service.behaviour().user_protocol().num_queued_blocks()
@@ -0,0 +1,157 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helper for handling (i.e. answering) grandpa warp sync requests from a remote peer via the
+//! [`crate::request_responses::RequestResponsesBehaviour`].
+
+use codec::{Encode, Decode};
+use crate::config::{IncomingRequest, OutgoingResponse, ProtocolId, RequestResponseConfig};
+use futures::channel::{mpsc, oneshot};
+use futures::stream::StreamExt;
+use log::debug;
+use sp_runtime::traits::Block as BlockT;
+use std::time::Duration;
+use std::sync::Arc;
+
+pub use sp_finality_grandpa::{AuthorityList, SetId};
+
+const LOG_TARGET: &str = "sync";
+
+/// The maximum size in bytes of a warp sync response.
+const MAX_RESPONSE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// SCALE-encoded warp sync proof, as produced and verified by a [`WarpSyncProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedProof(pub Vec<u8>);
+
+/// Warp sync request.
+#[derive(Encode, Decode, Debug)]
+pub struct Request<B: BlockT> {
+	/// Start collecting proofs from this block.
+	pub begin: B::Hash,
+}
+
+/// Result of verifying a warp sync proof.
+pub enum VerificationResult<B: BlockT> {
+	/// The proof is valid, but does not reach the latest finalized block yet. Contains the
+	/// new authority set and the hash of the last block of the proof.
+	Partial(SetId, AuthorityList, B::Hash),
+	/// The proof is valid and proves the finality of the given header.
+	Complete(SetId, AuthorityList, B::Header),
+}
+
+/// Warp sync backend. Handles generating and verifying warp sync proofs.
+pub trait WarpSyncProvider<B: BlockT>: Send + Sync {
+	/// Generate a proof starting at the given block hash. The proof is accumulated until the
+	/// maximum proof size is reached.
+	fn generate(
+		&self,
+		start: B::Hash,
+	) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>>;
+
+	/// Verify a warp sync proof against the given authority set.
+	fn verify(
+		&self,
+		proof: &EncodedProof,
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> Result<VerificationResult<B>, Box<dyn std::error::Error + Send + Sync>>;
+
+	/// Get the current authority set. This is the genesis authority set when starting a warp sync.
+	fn current_authorities(&self) -> AuthorityList;
+}
+
+/// Generates a [`RequestResponseConfig`] for the grandpa warp sync request protocol, refusing
+/// incoming requests.
+pub fn generate_request_response_config(protocol_id: ProtocolId) -> RequestResponseConfig {
+	RequestResponseConfig {
+		name: generate_protocol_name(protocol_id).into(),
+		max_request_size: 32,
+		max_response_size: MAX_RESPONSE_SIZE,
+		request_timeout: Duration::from_secs(10),
+		inbound_queue: None,
+	}
+}
+
+/// Generate the grandpa warp sync protocol name from chain specific protocol identifier.
+fn generate_protocol_name(protocol_id: ProtocolId) -> String {
+	format!("/{}/sync/warp", protocol_id.as_ref())
+}
+
+/// Handler for incoming grandpa warp sync requests from a remote peer.
+pub struct RequestHandler<B: BlockT> {
+	backend: Arc<dyn WarpSyncProvider<B>>,
+	request_receiver: mpsc::Receiver<IncomingRequest>,
+}
+
+impl<B: BlockT> RequestHandler<B> {
+	/// Create a new [`RequestHandler`].
+	pub fn new(
+		protocol_id: ProtocolId,
+		backend: Arc<dyn WarpSyncProvider<B>>,
+	) -> (Self, RequestResponseConfig) {
+		let (tx, request_receiver) = mpsc::channel(20);
+
+		let mut request_response_config = generate_request_response_config(protocol_id);
+		request_response_config.inbound_queue = Some(tx);
+
+		(Self { backend, request_receiver }, request_response_config)
+	}
+
+	fn handle_request(
+		&self,
+		payload: Vec<u8>,
+		pending_response: oneshot::Sender<OutgoingResponse>,
+	) -> Result<(), HandleRequestError> {
+		let request = Request::<B>::decode(&mut &payload[..])?;
+
+		let EncodedProof(proof) = self.backend.generate(request.begin)
+			.map_err(HandleRequestError::InvalidRequest)?;
+
+		pending_response.send(OutgoingResponse {
+			result: Ok(proof),
+			reputation_changes: Vec::new(),
+			sent_feedback: None,
+		}).map_err(|_| HandleRequestError::SendResponse)
+	}
+
+	/// Run [`RequestHandler`].
+	pub async fn run(mut self) {
+		while let Some(request) = self.request_receiver.next().await {
+			let IncomingRequest { peer, payload, pending_response } = request;
+
+			match self.handle_request(payload, pending_response) {
+				Ok(()) => debug!(target: LOG_TARGET, "Handled grandpa warp sync request from {}.", peer),
+				Err(e) => debug!(
+					target: LOG_TARGET,
+					"Failed to handle grandpa warp sync request from {}: {}",
+					peer, e,
+				),
+			}
+		}
+	}
+}
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+enum HandleRequestError {
+	#[display(fmt = "Failed to decode request: {}.", _0)]
+	DecodeScale(codec::Error),
+	#[from(ignore)]
+	#[display(fmt = "Invalid request: {}.", _0)]
+	InvalidRequest(Box<dyn std::error::Error + Send + Sync>),
+	#[display(fmt = "Failed to send response.")]
+	SendResponse,
+}
@@ -29,6 +29,7 @@ use std::{
 use libp2p::build_multiaddr;
 use log::trace;
 use sc_network::block_request_handler::{self, BlockRequestHandler};
+use sc_network::state_request_handler::{self, StateRequestHandler};
 use sc_network::light_client_requests::{self, handler::LightClientRequestHandler};
 use sp_blockchain::{
 	HeaderBackend, Result as ClientResult,
@@ -55,7 +56,7 @@ use sc_network::{
 	NetworkWorker, NetworkService, config::{ProtocolId, MultiaddrWithPeerId, NonReservedPeerMode},
 	Multiaddr,
 };
-use sc_network::config::{NetworkConfiguration, NonDefaultSetConfig, TransportConfig};
+use sc_network::config::{NetworkConfiguration, NonDefaultSetConfig, TransportConfig, SyncMode};
 use libp2p::PeerId;
 use parking_lot::Mutex;
 use sp_core::H256;
@@ -186,6 +187,17 @@ impl PeersClient {
 		}
 	}
 
+	pub fn has_state_at(&self, block: &BlockId<Block>) -> bool {
+		let header = match self.header(block).unwrap() {
+			Some(header) => header,
+			None => return false,
+		};
+		match self {
+			PeersClient::Full(_client, backend) => backend.have_state_at(&header.hash(), *header.number()),
+			PeersClient::Light(_client, backend) => backend.have_state_at(&header.hash(), *header.number()),
+		}
+	}
+
 	pub fn finality_notification_stream(&self) -> FinalityNotifications<Block> {
 		match *self {
 			PeersClient::Full(ref client, ref _backend) => client.finality_notification_stream(),
@@ -644,6 +656,8 @@ pub struct FullPeerConfig {
 	pub connect_to_peers: Option<Vec<usize>>,
 	/// Whether the full peer should have the authority role.
 	pub is_authority: bool,
+	/// Syncing mode
+	pub sync_mode: SyncMode,
 }
 
 pub trait TestNetFactory: Sized where <Self::BlockImport as BlockImport<Block>>::Transaction: Send {
@@ -736,6 +750,7 @@ pub trait TestNetFactory: Sized where <Self::BlockImport as BlockImport<Block>>:
 			Default::default(),
 			None,
 		);
+		network_config.sync_mode = config.sync_mode;
 		network_config.transport = TransportConfig::MemoryOnly;
 		network_config.listen_addresses = vec![listen_addr.clone()];
 		network_config.allow_non_globals_in_dht = true;
@@ -769,6 +784,16 @@ pub trait TestNetFactory: Sized where <Self::BlockImport as BlockImport<Block>>:
 			protocol_config
 		};
 
+		let state_request_protocol_config = {
+			let (handler, protocol_config) = StateRequestHandler::new(
+				&protocol_id,
+				client.clone(),
+				50,
+			);
+			self.spawn_task(handler.run().boxed());
+			protocol_config
+		};
+
 		let light_client_request_protocol_config = {
 			let (handler, protocol_config) = LightClientRequestHandler::new(&protocol_id, client.clone());
 			self.spawn_task(handler.run().boxed());
@@ -789,6 +814,8 @@ pub trait TestNetFactory: Sized where <Self::BlockImport as BlockImport<Block>>:
 				.unwrap_or_else(|| Box::new(DefaultBlockAnnounceValidator)),
 			metrics_registry: None,
 			block_request_protocol_config,
+			state_request_protocol_config,
+			warp_sync: None,
 			light_client_request_protocol_config,
 		}).unwrap();
 
@@ -863,6 +890,10 @@ pub trait TestNetFactory: Sized where <Self::BlockImport as BlockImport<Block>>:
 			&protocol_id,
 		);
 
+		let state_request_protocol_config = state_request_handler::generate_protocol_config(
+			&protocol_id,
+		);
+
 		let light_client_request_protocol_config =
 			light_client_requests::generate_protocol_config(&protocol_id);
 
@@ -879,6 +910,8 @@ pub trait TestNetFactory: Sized where <Self::BlockImport as BlockImport<Block>>:
 			block_announce_validator: Box::new(DefaultBlockAnnounceValidator),
 			metrics_registry: None,
 			block_request_protocol_config,
+			state_request_protocol_config,
+			warp_sync: None,
 			light_client_request_protocol_config,
 		}).unwrap();
 
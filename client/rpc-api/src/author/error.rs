@@ -20,7 +20,7 @@
 
 use crate::errors;
 use jsonrpc_core as rpc;
-use sp_runtime::transaction_validity::InvalidTransaction;
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionPriority};
 
 /// Author RPC Result type.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -62,6 +62,18 @@ pub enum Error {
 	InvalidSessionKeys,
 	/// Call to an unsafe RPC was denied.
 	UnsafeRpcCalled(crate::policy::UnsafeRpcError),
+	/// The transaction to replace is not ready in the pool.
+	#[display(fmt="The transaction to replace is not ready in the pool")]
+	ReplacedNotFound,
+	/// The replacing transaction has too low priority to replace a transaction in the pool.
+	#[display(fmt="Priority is too low to replace the transaction: ({} vs {})", old, new)]
+	#[from(ignore)]
+	ReplacementTooLowPriority {
+		/// Priority of the transaction in the pool.
+		old: TransactionPriority,
+		/// Priority of the replacing transaction.
+		new: TransactionPriority,
+	},
 }
 
 impl std::error::Error for Error {
@@ -102,6 +114,8 @@ const UNSUPPORTED_KEY_TYPE: i64 = POOL_INVALID_TX + 7;
 /// The transaction was not included to the pool since it is unactionable,
 /// it is not propagable and the local node does not author blocks.
 const POOL_UNACTIONABLE: i64 = POOL_INVALID_TX + 8;
+/// The transaction to replace is not ready in the pool.
+const POOL_REPLACED_NOT_FOUND: i64 = POOL_INVALID_TX + 9;
 
 impl From<Error> for rpc::Error {
 	fn from(e: Error) -> Self {
@@ -151,6 +165,15 @@ impl From<Error> for rpc::Error {
 				message: format!("Priority is too low: ({} vs {})", old, new),
 				data: Some("The transaction has too low priority to replace another transaction already in the pool.".into()),
 			},
+			Error::ReplacementTooLowPriority { old, new } => rpc::Error {
+				code: rpc::ErrorCode::ServerError(POOL_TOO_LOW_PRIORITY),
+				message: format!("Priority is too low: ({} vs {})", old, new),
+				data: Some(serde_json::json!({
+					"old": old,
+					"new": new,
+					"minimum": old.saturating_add(1),
+				})),
+			},
 			Error::Pool(PoolError::CycleDetected) => rpc::Error {
 				code: rpc::ErrorCode::ServerError(POOL_CYCLE_DETECTED),
 				message: "Cycle Detected".into(),
@@ -169,6 +192,11 @@ impl From<Error> for rpc::Error {
 					 the local node does not author blocks".into(),
 				),
 			},
+			Error::ReplacedNotFound => rpc::Error {
+				code: rpc::ErrorCode::ServerError(POOL_REPLACED_NOT_FOUND),
+				message: "Replaced Transaction Not Found".into(),
+				data: Some("The transaction to replace is not ready in the pool".into()),
+			},
 			Error::UnsupportedKeyType => rpc::Error {
 				code: rpc::ErrorCode::ServerError(UNSUPPORTED_KEY_TYPE),
 				message: "Unknown key type crypto" .into(),
@@ -32,3 +32,15 @@ pub enum ExtrinsicOrHash<Hash> {
 	/// Raw extrinsic bytes.
 	Extrinsic(Bytes),
 }
+
+/// Extrinsic submitted to replace a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmittedReplacement<Hash> {
+	/// The hash of the submitted extrinsic.
+	pub hash: Hash,
+	/// Whether the extrinsic replaced the given transaction.
+	///
+	/// If it didn't (e.g. because it comes from another sender), both transactions stay in the pool.
+	pub replaced: bool,
+}
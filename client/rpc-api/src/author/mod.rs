@@ -39,6 +39,21 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[rpc(name = "author_submitExtrinsic")]
 	fn submit_extrinsic(&self, extrinsic: Bytes) -> FutureResult<Hash>;
 
+	/// Submit hex-encoded extrinsic replacing a ready transaction already in the pool.
+	///
+	/// The extrinsic has to provide the same tags as the replaced transaction (e.g. come from the
+	/// same sender with the same nonce) and have a higher priority. If the priority is too low, the
+	/// error data contains the minimal priority required to replace the transaction.
+	///
+	/// An extrinsic that turns out not to replace the transaction is kept in the pool, and reported
+	/// as such in the result.
+	#[rpc(name = "author_submitAndReplace")]
+	fn submit_and_replace(
+		&self,
+		extrinsic: Bytes,
+		replaced: Hash,
+	) -> FutureResult<hash::SubmittedReplacement<Hash>>;
+
 	/// Insert a key into the keystore.
 	#[rpc(name = "author_insertKey")]
 	fn insert_key(
@@ -30,3 +30,52 @@ pub struct ReadProof<Hash> {
 	/// A proof used to prove that storage entries are included in the storage trie
 	pub proof: Vec<Bytes>,
 }
+
+/// Number of keys and bytes of a part of the state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Usage {
+	/// Number of keys.
+	pub keys: u64,
+	/// Total size of the keys and values, in bytes.
+	pub bytes: u64,
+}
+
+impl Usage {
+	/// Account for a key-value pair.
+	pub fn add(&mut self, key: &[u8], value: &[u8]) {
+		self.keys += 1;
+		self.bytes += (key.len() + value.len()) as u64;
+	}
+}
+
+/// Storage usage of a storage item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageItemUsage {
+	/// Name of the pallet, `None` for keys that match no pallet storage prefix.
+	pub pallet: Option<String>,
+	/// Name of the storage item, `None` for keys that match no storage item of the pallet.
+	///
+	/// Well-known keys (e.g. `:code`) are reported as items without a pallet.
+	pub item: Option<String>,
+	/// Usage of the item.
+	#[serde(flatten)]
+	pub usage: Usage,
+}
+
+/// StorageUsage struct returned by the RPC
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage<Hash> {
+	/// Block hash of the inspected state
+	pub at: Hash,
+	/// Usage of the whole state, including the child tries.
+	pub total: Usage,
+	/// Usage of the top trie by storage item, largest first.
+	pub items: Vec<StorageItemUsage>,
+	/// Number of default child tries.
+	pub child_tries: u64,
+	/// Usage of the content of the default child tries.
+	pub child_tries_usage: Usage,
+}
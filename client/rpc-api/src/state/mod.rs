@@ -30,7 +30,7 @@ use sp_version::RuntimeVersion;
 use self::error::FutureResult;
 
 pub use self::gen_client::Client as StateClient;
-pub use self::helpers::ReadProof;
+pub use self::helpers::{ReadProof, StorageItemUsage, StorageUsage, Usage};
 
 /// Substrate state API
 #[rpc]
@@ -107,6 +107,13 @@ pub trait StateApi<Hash> {
 	#[rpc(name = "state_getReadProof")]
 	fn read_proof(&self, keys: Vec<StorageKey>, hash: Option<Hash>) -> FutureResult<ReadProof<Hash>>;
 
+	/// Returns the number of keys and bytes used by each storage item at a block's state,
+	/// including the content of the child tries.
+	///
+	/// Keys are attributed to pallets and storage items using the runtime metadata.
+	#[rpc(name = "state_storageUsage")]
+	fn storage_usage(&self, hash: Option<Hash>) -> FutureResult<StorageUsage<Hash>>;
+
 	/// New runtime version subscription
 	#[pubsub(
 		subscription = "state_runtimeVersion",
@@ -38,7 +38,7 @@ use sp_api::ProvideRuntimeApi;
 use sp_runtime::generic;
 use sp_transaction_pool::{
 	TransactionPool, InPoolTransaction, TransactionStatus, TransactionSource,
-	BlockHash, TxHash, TransactionFor, error::IntoPoolError,
+	BlockHash, TxHash, TransactionFor, error::{IntoPoolError, Error as PoolError},
 };
 use sp_session::SessionKeys;
 
@@ -153,6 +153,36 @@ impl<P, Client> AuthorApi<TxHash<P>, BlockHash<P>> for Author<P, Client>
 		)
 	}
 
+	fn submit_and_replace(
+		&self,
+		ext: Bytes,
+		replaced: TxHash<P>,
+	) -> FutureResult<hash::SubmittedReplacement<TxHash<P>>> {
+		let xt = match Decode::decode(&mut &ext[..]) {
+			Ok(xt) => xt,
+			Err(err) => return Box::new(result(Err(err.into()))),
+		};
+		if self.pool.ready_transaction(&replaced).is_none() {
+			return Box::new(result(Err(Error::ReplacedNotFound)))
+		}
+
+		let best_block_hash = self.client.info().best_hash;
+		Box::new(self.pool
+			.submit_replacing(&generic::BlockId::hash(best_block_hash), TX_SOURCE, xt)
+			.map_err(|e| match e.into_pool_error() {
+				Ok(PoolError::TooLowPriority { old, new }) =>
+					Error::ReplacementTooLowPriority { old, new },
+				Ok(e) => e.into(),
+				Err(e) => error::Error::Verification(Box::new(e)),
+			})
+			.map_ok(move |(hash, removed)| hash::SubmittedReplacement {
+				replaced: removed.contains(&replaced),
+				hash,
+			})
+			.compat()
+		)
+	}
+
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>> {
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
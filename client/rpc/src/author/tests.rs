@@ -189,6 +189,78 @@ fn should_return_watch_validation_error() {
 	assert!(res.is_err(), "Expected the transaction to be rejected as invalid.");
 }
 
+fn transfer(sender: AccountKeyring, nonce: u64, amount: u64) -> Extrinsic {
+	let tx = Transfer {
+		amount,
+		nonce,
+		from: sender.into(),
+		to: Default::default(),
+	};
+	tx.into_signed_tx()
+}
+
+#[test]
+fn should_replace_extrinsic() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let hash = p.submit_extrinsic(uxt(AccountKeyring::Alice, 0).encode().into()).wait().unwrap();
+	let replacement = transfer(AccountKeyring::Alice, 0, 5);
+
+	assert_matches!(
+		p.submit_and_replace(replacement.encode().into(), hash).wait(),
+		Ok(hash::SubmittedReplacement { hash: h, replaced: true })
+			if h == blake2_256(&replacement.encode()).into()
+	);
+	assert_matches!(
+		p.pending_extrinsics(),
+		Ok(ref pending) if *pending == vec![Bytes(replacement.encode())]
+	);
+}
+
+#[test]
+fn should_return_required_priority_when_replacing() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let hash = p.submit_extrinsic(transfer(AccountKeyring::Alice, 0, 5).encode().into()).wait().unwrap();
+
+	let res = p.submit_and_replace(transfer(AccountKeyring::Alice, 0, 3).encode().into(), hash).wait();
+	assert_matches!(res, Err(Error::ReplacementTooLowPriority { old: 5, new: 3 }));
+	let err: rpc::Error = res.unwrap_err().into();
+	assert_eq!(err.data, Some(serde_json::json!({ "old": 5, "new": 3, "minimum": 6 })));
+
+	// `author_submitExtrinsic` keeps reporting the error as before.
+	let res = p.submit_extrinsic(transfer(AccountKeyring::Alice, 0, 3).encode().into()).wait();
+	assert_matches!(
+		res,
+		Err(Error::Pool(sp_transaction_pool::error::Error::TooLowPriority { old: 5, new: 3 }))
+	);
+	let err: rpc::Error = res.unwrap_err().into();
+	assert_eq!(
+		err.data,
+		Some("The transaction has too low priority to replace another transaction already in the pool.".into()),
+	);
+}
+
+#[test]
+fn should_not_replace_unrelated_extrinsic() {
+	let setup = TestSetup::default();
+	let p = setup.author();
+
+	let hash = p.submit_extrinsic(uxt(AccountKeyring::Alice, 0).encode().into()).wait().unwrap();
+
+	assert_matches!(
+		p.submit_and_replace(uxt(AccountKeyring::Bob, 0).encode().into(), H256::repeat_byte(1)).wait(),
+		Err(Error::ReplacedNotFound)
+	);
+	assert_matches!(
+		p.submit_and_replace(transfer(AccountKeyring::Bob, 0, 5).encode().into(), hash).wait(),
+		Ok(hash::SubmittedReplacement { replaced: false, .. })
+	);
+	assert_eq!(setup.pool.status().ready, 2);
+}
+
 #[test]
 fn should_return_pending_extrinsics() {
 	let p = TestSetup::default().author();
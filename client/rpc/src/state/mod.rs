@@ -20,6 +20,7 @@
 
 mod state_full;
 mod state_light;
+mod usage;
 
 #[cfg(test)]
 mod tests;
@@ -40,6 +41,7 @@ use self::error::{Error, FutureResult};
 
 pub use sc_rpc_api::state::*;
 pub use sc_rpc_api::child_state::*;
+pub use self::usage::storage_usage;
 use sc_client_api::{
 	ExecutorProvider, StorageProvider, BlockchainEvents, Backend, BlockBackend, ProofProvider
 };
@@ -139,6 +141,9 @@ pub trait StateBackend<Block: BlockT, Client>: Send + Sync + 'static
 		keys: Vec<StorageKey>,
 	) -> FutureResult<ReadProof<Block::Hash>>;
 
+	/// Returns the number of keys and bytes used by each storage item at a block's state.
+	fn storage_usage(&self, block: Option<Block::Hash>) -> FutureResult<StorageUsage<Block::Hash>>;
+
 	/// New runtime version subscription
 	fn subscribe_runtime_version(
 		&self,
@@ -329,6 +334,14 @@ impl<Block, Client> StateApi<Block::Hash> for State<Block, Client>
 		self.backend.read_proof(block, keys)
 	}
 
+	fn storage_usage(&self, block: Option<Block::Hash>) -> FutureResult<StorageUsage<Block::Hash>> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return Box::new(result(Err(err.into())))
+		}
+
+		self.backend.storage_usage(block)
+	}
+
 	fn subscribe_storage(
 		&self,
 		meta: Self::Metadata,
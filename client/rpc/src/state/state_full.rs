@@ -21,12 +21,12 @@
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::ops::Range;
-use futures::{future, StreamExt as _, TryStreamExt as _};
+use futures::{channel::oneshot, compat::Compat, future, StreamExt as _, TryStreamExt as _};
 use log::warn;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionManager};
 use rpc::{Result as RpcResult, futures::{stream, Future, Sink, Stream, future::result}};
 
-use sc_rpc_api::state::ReadProof;
+use sc_rpc_api::state::{ReadProof, StorageUsage};
 use sp_blockchain::{
 	Result as ClientResult, Error as ClientError, HeaderMetadata, CachedHeaderMetadata,
 	HeaderBackend
@@ -415,6 +415,30 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 		))
 	}
 
+	fn storage_usage(&self, block: Option<Block::Hash>) -> FutureResult<StorageUsage<Block::Hash>> {
+		let block = match self.block_or_best(block) {
+			Ok(block) => block,
+			Err(e) => return Box::new(result(Err(client_err(e)))),
+		};
+
+		// Walking the whole state is a long, blocking trie iteration, so it runs on its own thread
+		// instead of holding up the executor.
+		let client = self.client.clone();
+		let (tx, rx) = oneshot::channel();
+		let res = std::thread::Builder::new()
+			.name("rpc-storage-usage".into())
+			.spawn(move || {
+				let _ = tx.send(super::storage_usage(&*client, block).map_err(client_err));
+			});
+		if res.is_err() {
+			warn!("Error spawning storage usage RPC thread.");
+		}
+
+		Box::new(Compat::new(rx)
+			.map_err(|e| Error::Client(Box::new(e)))
+			.and_then(|usage| usage))
+	}
+
 	fn subscribe_runtime_version(
 		&self,
 		_meta: crate::Metadata,
@@ -0,0 +1,174 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage usage statistics.
+
+use std::collections::{BTreeMap, HashMap};
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_client_api::{Backend, StorageProvider};
+use sp_api::{Metadata, ProvideRuntimeApi};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_core::{hashing::twox_128, storage::{well_known_keys, ChildInfo, StorageKey}};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use super::{StorageItemUsage, StorageUsage, Usage};
+
+/// Storage items of a pallet, by hash of their name.
+struct PalletItems {
+	name: String,
+	items: HashMap<[u8; 16], String>,
+}
+
+/// Storage items of the runtime, by hash of their pallet storage prefix.
+///
+/// Only V14 metadata is supported. The keys of runtimes with older metadata are not attributed
+/// to pallets.
+fn storage_items(metadata: &[u8]) -> ClientResult<HashMap<[u8; 16], PalletItems>> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+		.map_err(|e| ClientError::Application(Box::new(e)))?;
+	let pallets = match metadata.1 {
+		RuntimeMetadata::V14(metadata) => metadata.pallets,
+		_ => return Ok(HashMap::new()),
+	};
+
+	Ok(pallets.into_iter()
+		.filter_map(|pallet| {
+			let name = pallet.name;
+			pallet.storage.map(|storage| (name, storage))
+		})
+		.map(|(name, storage)| {
+			let items = storage.entries.into_iter()
+				.map(|entry| (twox_128(entry.name.as_bytes()), entry.name))
+				.collect();
+			(twox_128(storage.prefix.as_bytes()), PalletItems { name, items })
+		})
+		.collect())
+}
+
+/// Name of the pallet and storage item of a top trie key.
+fn item_of(
+	key: &[u8],
+	pallets: &HashMap<[u8; 16], PalletItems>,
+) -> (Option<String>, Option<String>) {
+	if key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+		let prefix = String::from_utf8_lossy(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX);
+		return (None, Some(prefix.into_owned()))
+	}
+	if key.starts_with(b":") {
+		return (None, Some(String::from_utf8_lossy(key).into_owned()))
+	}
+
+	let hash = |range: std::ops::Range<usize>| {
+		key.get(range).map(|hash| {
+			let mut out = [0u8; 16];
+			out.copy_from_slice(hash);
+			out
+		})
+	};
+	match hash(0..16).and_then(|hash| pallets.get(&hash)) {
+		Some(pallet) => (
+			Some(pallet.name.clone()),
+			hash(16..32).and_then(|hash| pallet.items.get(&hash)).cloned(),
+		),
+		None => (None, None),
+	}
+}
+
+/// Walk the state at the given block, and aggregate the number of keys and bytes by storage item.
+pub fn storage_usage<Block, BE, Client>(
+	client: &Client,
+	block: Block::Hash,
+) -> ClientResult<StorageUsage<Block::Hash>>
+where
+	Block: BlockT,
+	BE: Backend<Block>,
+	Client: StorageProvider<Block, BE> + ProvideRuntimeApi<Block>,
+	Client::Api: Metadata<Block>,
+{
+	let id = BlockId::Hash(block);
+	let pallets = storage_items(&client.runtime_api().metadata(&id)?)?;
+
+	let mut total = Usage::default();
+	let mut items = BTreeMap::<_, Usage>::new();
+	let mut child_storage_keys = Vec::new();
+	for key in client.storage_keys_iter(&id, None, None)? {
+		let value = client.storage(&id, &key)?.unwrap_or_default();
+		total.add(&key.0, &value.0);
+		items.entry(item_of(&key.0, &pallets)).or_default().add(&key.0, &value.0);
+		if let Some(child_key) = key.0.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			child_storage_keys.push(ChildInfo::new_default(child_key));
+		}
+	}
+
+	let mut child_tries_usage = Usage::default();
+	let empty_key = StorageKey(Vec::new());
+	for child_info in &child_storage_keys {
+		for key in client.child_storage_keys(&id, child_info, &empty_key)? {
+			let value = client.child_storage(&id, child_info, &key)?.unwrap_or_default();
+			total.add(&key.0, &value.0);
+			child_tries_usage.add(&key.0, &value.0);
+		}
+	}
+
+	let mut items = items.into_iter()
+		.map(|((pallet, item), usage)| StorageItemUsage { pallet, item, usage })
+		.collect::<Vec<_>>();
+	items.sort_by(|a, b| b.usage.bytes.cmp(&a.usage.bytes));
+
+	Ok(StorageUsage {
+		at: block,
+		total,
+		items,
+		child_tries: child_storage_keys.len() as u64,
+		child_tries_usage,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn pallets() -> HashMap<[u8; 16], PalletItems> {
+		let items = vec![(twox_128(b"Account"), "Account".to_string())].into_iter().collect();
+		vec![(twox_128(b"System"), PalletItems { name: "System".into(), items })]
+			.into_iter()
+			.collect()
+	}
+
+	#[test]
+	fn item_of_works() {
+		let pallets = pallets();
+		let key = |pallet: &[u8], item: &[u8]| [twox_128(pallet), twox_128(item)].concat();
+
+		assert_eq!(
+			item_of(&[key(b"System", b"Account"), vec![1, 2, 3]].concat(), &pallets),
+			(Some("System".into()), Some("Account".into())),
+		);
+		assert_eq!(item_of(&key(b"System", b"Number"), &pallets), (Some("System".into()), None));
+		assert_eq!(item_of(&twox_128(b"System"), &pallets), (Some("System".into()), None));
+		assert_eq!(item_of(&key(b"Balances", b"Account"), &pallets), (None, None));
+		assert_eq!(item_of(b"short", &pallets), (None, None));
+		assert_eq!(item_of(well_known_keys::CODE, &pallets), (None, Some(":code".into())));
+		assert_eq!(
+			item_of(&[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, b"trie"].concat(), &pallets),
+			(None, Some(":child_storage:default:".into())),
+		);
+	}
+}
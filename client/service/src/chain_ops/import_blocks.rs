@@ -172,6 +172,8 @@ fn import_block_to_queue<TBl, TImpQu>(
 			origin: None,
 			allow_missing_state: false,
 			import_existing: force,
+			state: None,
+			skip_execution: false,
 		}
 	]);
 }
@@ -0,0 +1,58 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{config::{DatabaseConfig, PruningMode}, error::Error};
+use log::info;
+use sc_client_db::{migrate_to_parity_db, MigrationProgress};
+use sp_runtime::traits::Block as BlockT;
+
+/// Migrates the RocksDb database `source` to the ParityDb database `target`.
+///
+/// The migrated database is checked with the `state_pruning` mode of the node.
+pub fn migrate_database<B: BlockT>(
+	source: &DatabaseConfig,
+	target: &DatabaseConfig,
+	state_pruning: PruningMode,
+) -> Result<(), Error> {
+	let (source, target) = match (source, target) {
+		(DatabaseConfig::RocksDb { path: source, .. }, DatabaseConfig::ParityDb { path: target }) =>
+			(source, target),
+		_ => return Err("Only RocksDb databases can be migrated to ParityDb".into()),
+	};
+
+	info!("Migrating database from {} to {}", source.display(), target.display());
+	migrate_to_parity_db::<B>(source, target, state_pruning, |progress: MigrationProgress| {
+		if progress.done {
+			info!(
+				"Copied column {}: {} keys ({} bytes)",
+				progress.column,
+				progress.keys,
+				progress.bytes,
+			);
+		} else {
+			info!(
+				"Copying column {}: {} keys ({} bytes) so far",
+				progress.column,
+				progress.keys,
+				progress.bytes,
+			);
+		}
+	})?;
+
+	Ok(())
+}
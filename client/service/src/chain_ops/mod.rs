@@ -22,10 +22,14 @@ mod check_block;
 mod export_blocks;
 mod export_raw_state;
 mod import_blocks;
+mod migrate_database;
 mod revert_chain;
+mod snapshot;
 
 pub use check_block::*;
 pub use export_blocks::*;
 pub use export_raw_state::*;
 pub use import_blocks::*;
+pub use migrate_database::*;
 pub use revert_chain::*;
+pub use snapshot::*;
@@ -0,0 +1,550 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! State snapshots.
+//!
+//! A snapshot holds the header of a block, its justifications and the whole state at this
+//! block. It is a sequence of frames, each prefixed by its length as a little-endian `u32`:
+//!
+//! - a header frame with the snapshot version, the block header and its justifications;
+//! - chunk frames, each holding SCALE-encoded key-value pairs of the top trie or of a default
+//!   child trie, compressed with zstd;
+//! - an empty frame marking the end of the snapshot.
+//!
+//! The child trie roots are not part of the snapshot, they are computed from the content of the
+//! child tries when importing it.
+//!
+//! Frames are limited to [`MAX_FRAME_BYTES`] so that reading a snapshot never allocates more than
+//! this for a single frame, whatever length prefix the input claims.
+
+use crate::error::Error;
+use codec::{Decode, Encode};
+use futures::{future, prelude::*};
+use log::info;
+use sc_client_api::{BlockBackend, StorageProvider, UsageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::{
+	BlockOrigin,
+	import_queue::{BlockImportError, BlockImportResult, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sp_core::storage::{well_known_keys, ChildInfo, ChildType, PrefixedStorageKey, StorageKey};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashFor, Header as HeaderT, NumberFor},
+	Justifications,
+};
+use sp_state_machine::{KeyValueStates, KeyValueStorageLevel};
+use std::{collections::HashMap, io::{Read, Write}, pin::Pin, sync::Arc, task::Poll};
+
+/// Magic bytes starting a snapshot.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"snapshot";
+
+/// Version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Size in bytes of the key-value pairs collected in a chunk before writing it.
+const CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+/// Maximal size in bytes of a decompressed chunk.
+const MAX_CHUNK_BYTES: usize = 64 * 1024 * 1024;
+
+/// Maximal size in bytes of a frame as written to or read from a snapshot.
+pub const MAX_FRAME_BYTES: usize = MAX_CHUNK_BYTES;
+
+/// Key-value pairs of a trie: the prefixed storage key of a default child trie, or an empty key
+/// for the top trie, and the pairs in key order.
+type Chunk = (Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>);
+
+/// Writes length-prefixed frames, compressing chunks.
+struct FrameWriter<W> {
+	output: W,
+	chunks: u64,
+	bytes: u64,
+}
+
+impl<W: Write> FrameWriter<W> {
+	fn write_frame(&mut self, frame: &[u8]) -> Result<(), Error> {
+		if frame.len() > MAX_FRAME_BYTES {
+			return Err(Error::Other(format!(
+				"Snapshot frame of {} bytes exceeds the limit of {} bytes",
+				frame.len(),
+				MAX_FRAME_BYTES,
+			)))
+		}
+		self.output.write_all(&(frame.len() as u32).to_le_bytes())?;
+		self.output.write_all(frame)?;
+		self.bytes += 4 + frame.len() as u64;
+		Ok(())
+	}
+
+	fn write_chunk(&mut self, storage_key: &[u8], pairs: &mut Vec<(Vec<u8>, Vec<u8>)>) -> Result<(), Error> {
+		if pairs.is_empty() {
+			return Ok(())
+		}
+
+		let chunk = (storage_key, &*pairs).encode();
+		// Chunks that are too big to be decompressed within the limit can't be read back.
+		let frame = sp_maybe_compressed_blob::compress(&chunk, MAX_CHUNK_BYTES)
+			.ok_or_else(|| Error::Other(format!(
+				"Snapshot chunk of {} bytes exceeds the limit of {} bytes",
+				chunk.len(),
+				MAX_CHUNK_BYTES,
+			)))?;
+		self.write_frame(&frame)?;
+		self.chunks += 1;
+		pairs.clear();
+		Ok(())
+	}
+}
+
+/// Reads a length-prefixed frame, refusing frames above [`MAX_FRAME_BYTES`].
+fn read_frame(input: &mut impl Read) -> Result<Vec<u8>, Error> {
+	let mut len = [0u8; 4];
+	input.read_exact(&mut len)?;
+	let len = u32::from_le_bytes(len) as usize;
+	if len > MAX_FRAME_BYTES {
+		return Err(Error::Other(format!(
+			"Invalid snapshot: frame of {} bytes exceeds the limit of {} bytes",
+			len,
+			MAX_FRAME_BYTES,
+		)))
+	}
+	let mut frame = vec![0u8; len];
+	input.read_exact(&mut frame)?;
+	Ok(frame)
+}
+
+/// Reads a snapshot one frame at a time.
+struct SnapshotReader<B: BlockT, R> {
+	input: R,
+	header: B::Header,
+	justifications: Option<Justifications>,
+	chunks: u64,
+}
+
+impl<B: BlockT, R: Read> SnapshotReader<B, R> {
+	/// Read the header frame of the snapshot.
+	fn new(mut input: R) -> Result<Self, Error> {
+		let frame = read_frame(&mut input)?;
+		let (magic, version) = <([u8; 8], u32)>::decode(&mut &frame[..])
+			.map_err(|e| Error::Other(format!("Invalid snapshot: {}", e)))?;
+		if magic != SNAPSHOT_MAGIC {
+			return Err("Invalid snapshot: not a state snapshot".into())
+		}
+		if version != SNAPSHOT_VERSION {
+			return Err(Error::Other(format!("Unsupported snapshot version {}", version)))
+		}
+		let (_, _, header, justifications) =
+			<([u8; 8], u32, B::Header, Option<Justifications>)>::decode(&mut &frame[..])
+				.map_err(|e| Error::Other(format!("Invalid snapshot header: {}", e)))?;
+		Ok(SnapshotReader { input, header, justifications, chunks: 0 })
+	}
+
+	/// Read the next chunk, or `None` at the end of the snapshot.
+	fn next_chunk(&mut self) -> Result<Option<Chunk>, Error> {
+		let frame = read_frame(&mut self.input)?;
+		if frame.is_empty() {
+			return Ok(None)
+		}
+		let chunk = sp_maybe_compressed_blob::decompress(&frame, MAX_CHUNK_BYTES)
+			.map_err(|e| Error::Other(format!("Invalid snapshot chunk #{}: {}", self.chunks, e)))?;
+		let chunk = Chunk::decode(&mut &chunk[..])
+			.map_err(|e| Error::Other(format!("Invalid snapshot chunk #{}: {}", self.chunks, e)))?;
+		self.chunks += 1;
+		Ok(Some(chunk))
+	}
+}
+
+/// Compute the state root of `state`, the top trie coming first.
+///
+/// The root is computed from the sorted key-value pairs without building the trie in memory.
+fn state_root<B: BlockT>(state: &KeyValueStates) -> B::Hash {
+	let child_roots = state.0.iter()
+		.skip(1)
+		.filter(|level| !level.key_values.is_empty())
+		.map(|level| {
+			let root = sp_trie::child_trie_root::<sp_trie::Layout<HashFor<B>>, _, _, _>(
+				level.key_values.iter().map(|(k, v)| (k, v)),
+			);
+			(level.storage_key.clone(), root.encode())
+		})
+		.collect::<Vec<_>>();
+
+	let top = state.0.first().map(|level| &level.key_values[..]).unwrap_or_default();
+	<sp_trie::Layout<HashFor<B>> as sp_trie::TrieConfiguration>::trie_root(
+		top.iter().chain(child_roots.iter()).map(|(k, v)| (k, v)),
+	)
+}
+
+/// Export a snapshot of the state at the given `block` to `output`. If `block` is `None`, the
+/// last finalized block is used.
+pub fn export_snapshot<B, BA, C>(
+	client: Arc<C>,
+	block: Option<BlockId<B>>,
+	output: impl Write,
+) -> Result<(), Error>
+where
+	C: UsageProvider<B> + StorageProvider<B, BA> + HeaderBackend<B> + BlockBackend<B>,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+{
+	let block = block.unwrap_or_else(
+		|| BlockId::Hash(client.usage_info().chain.finalized_hash)
+	);
+	let header = client.header(block)?
+		.ok_or_else(|| Error::Other(format!("Unknown block {}", block)))?;
+	let justifications = client.justifications(&block)?;
+	info!("Exporting snapshot of the state at block #{} ({})", header.number(), header.hash());
+
+	let mut writer = FrameWriter { output, chunks: 0, bytes: 0 };
+	writer.write_frame(&(SNAPSHOT_MAGIC, SNAPSHOT_VERSION, &header, &justifications).encode())?;
+
+	let mut pairs = Vec::new();
+	let mut size = 0;
+	let mut child_storage_keys = Vec::new();
+	for key in client.storage_keys_iter(&block, None, None)? {
+		// Child trie roots are recomputed from the child tries content on import.
+		if key.0.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			child_storage_keys.push(key.0);
+			continue
+		}
+
+		let value = client.storage(&block, &key)?
+			.ok_or_else(|| Error::Other(format!("Missing value of key {:?}", key)))?;
+		size += key.0.len() + value.0.len();
+		pairs.push((key.0, value.0));
+		if size >= CHUNK_BYTES {
+			writer.write_chunk(&[], &mut pairs)?;
+			size = 0;
+		}
+	}
+	writer.write_chunk(&[], &mut pairs)?;
+	size = 0;
+
+	let empty_key = StorageKey(Vec::new());
+	for prefixed_key in child_storage_keys {
+		let child_info = ChildInfo::new_default(
+			&prefixed_key[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..],
+		);
+		for key in client.child_storage_keys(&block, &child_info, &empty_key)? {
+			let value = client.child_storage(&block, &child_info, &key)?
+				.ok_or_else(|| Error::Other(format!("Missing value of child key {:?}", key)))?;
+			size += key.0.len() + value.0.len();
+			pairs.push((key.0, value.0));
+			if size >= CHUNK_BYTES {
+				writer.write_chunk(&prefixed_key, &mut pairs)?;
+				size = 0;
+			}
+		}
+		writer.write_chunk(&prefixed_key, &mut pairs)?;
+		size = 0;
+	}
+
+	writer.write_frame(&[])?;
+	writer.output.flush()?;
+	info!("Exported {} chunks ({} bytes)", writer.chunks, writer.bytes);
+	Ok(())
+}
+
+/// Read a snapshot and check that its state matches the state root of its block header.
+///
+/// The chunks are moved into the returned state as they are read, so that the state is only held
+/// once in memory.
+///
+/// Returns the header and justifications of the block along with its state.
+pub fn read_snapshot<B: BlockT>(
+	input: impl Read,
+) -> Result<(B::Header, Option<Justifications>, KeyValueStates), Error> {
+	let mut reader = SnapshotReader::<B, _>::new(input)?;
+
+	let mut state = vec![
+		KeyValueStorageLevel { storage_key: Vec::new(), key_values: Vec::new(), complete: true },
+	];
+	let mut levels = HashMap::new();
+	levels.insert(Vec::new(), 0);
+	while let Some((storage_key, pairs)) = reader.next_chunk()? {
+		let index = match levels.get(&storage_key) {
+			Some(index) => *index,
+			None => {
+				match ChildType::from_prefixed_key(PrefixedStorageKey::new_ref(&storage_key)) {
+					Some((ChildType::ParentKeyId, _)) => {},
+					None => return Err(Error::Other(
+						format!("Invalid child storage key {:?}", storage_key)
+					)),
+				}
+				levels.insert(storage_key.clone(), state.len());
+				state.push(KeyValueStorageLevel { storage_key, key_values: Vec::new(), complete: true });
+				state.len() - 1
+			},
+		};
+		state[index].key_values.extend(pairs);
+	}
+	let SnapshotReader { header, justifications, chunks, .. } = reader;
+	info!("Read {} chunks of the state at block #{} ({})", chunks, header.number(), header.hash());
+
+	let state = KeyValueStates(state);
+	let root = state_root::<B>(&state);
+	if root != *header.state_root() {
+		return Err(Error::Other(format!(
+			"Snapshot state root {:?} does not match the state root {:?} of block #{}",
+			root,
+			header.state_root(),
+			header.number(),
+		)))
+	}
+
+	Ok((header, justifications, state))
+}
+
+/// Import a snapshot read from `input`.
+///
+/// The block of the snapshot is imported together with its state, without its ancestry, and
+/// finalized, so that the node can start from it.
+pub fn import_snapshot<B, IQ, C>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	input: impl Read,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: UsageProvider<B> + Send + Sync + 'static,
+	B: BlockT,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink<B: BlockT> {
+		result: Option<Result<BlockImportResult<NumberFor<B>>, BlockImportError>>,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink<B> {
+		fn blocks_processed(
+			&mut self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportResult<NumberFor<B>>, BlockImportError>, B::Hash)>
+		) {
+			if let Some((result, _)) = results.into_iter().next() {
+				self.result = Some(result);
+			}
+		}
+	}
+
+	let (header, justifications, state) = match read_snapshot::<B>(input) {
+		Ok(snapshot) => snapshot,
+		Err(e) => return future::ready(Err(e)).boxed(),
+	};
+	let hash = header.hash();
+	let number = *header.number();
+
+	import_queue.import_blocks(BlockOrigin::File, vec![
+		IncomingBlock::<B> {
+			hash,
+			header: Some(header),
+			body: None,
+			justifications,
+			origin: None,
+			allow_missing_state: true,
+			import_existing: true,
+			state: Some(ImportedState { block: hash, state }),
+			skip_execution: true,
+		}
+	]);
+
+	let mut link = WaitLink::<B> { result: None };
+	let import = future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &mut link);
+
+		match link.result.take() {
+			Some(Ok(_)) => {
+				let info = client.usage_info().chain;
+				info!(
+					"🎉 Imported snapshot of block #{} ({}). Best: #{}, finalized: #{}",
+					number,
+					hash,
+					info.best_number,
+					info.finalized_number,
+				);
+				Poll::Ready(Ok(()))
+			},
+			Some(Err(e)) => Poll::Ready(Err(Error::Other(
+				format!("Error importing snapshot of block #{} ({}): {:?}", number, hash, e)
+			))),
+			None => {
+				cx.waker().wake_by_ref();
+				Poll::Pending
+			},
+		}
+	});
+	Box::pin(import)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_state_machine::Backend as _;
+	use substrate_test_runtime::{Block, Header};
+
+	fn snapshot(header: &Header, tries: &[Chunk]) -> Vec<u8> {
+		let mut writer = FrameWriter { output: Vec::new(), chunks: 0, bytes: 0 };
+		writer.write_frame(&(SNAPSHOT_MAGIC, SNAPSHOT_VERSION, header, None::<Justifications>).encode())
+			.unwrap();
+		for (storage_key, pairs) in tries {
+			writer.write_chunk(storage_key, &mut pairs.clone()).unwrap();
+		}
+		writer.write_frame(&[]).unwrap();
+		writer.output
+	}
+
+	fn state() -> (Vec<Chunk>, <Header as HeaderT>::Hash) {
+		let child_info = ChildInfo::new_default(b"child");
+		let top = vec![(b"a".to_vec(), b"1".to_vec()), (b"b".to_vec(), vec![2; 100])];
+		let child = vec![(b"c".to_vec(), b"3".to_vec())];
+		let (root, _) = sp_state_machine::new_in_mem::<HashFor<Block>>().full_storage_root(
+			top.iter().map(|(k, v)| (&k[..], Some(&v[..]))),
+			std::iter::once((&child_info, child.iter().map(|(k, v)| (&k[..], Some(&v[..]))))),
+		);
+		let tries = vec![
+			(Vec::new(), top),
+			(child_info.prefixed_storage_key().into_inner(), child),
+		];
+		(tries, root)
+	}
+
+	fn header(state_root: <Header as HeaderT>::Hash) -> Header {
+		Header::new(5, Default::default(), state_root, Default::default(), Default::default())
+	}
+
+	#[test]
+	fn read_snapshot_works() {
+		let (tries, root) = state();
+		let header = header(root);
+
+		let (read_header, justifications, state) =
+			read_snapshot::<Block>(&snapshot(&header, &tries)[..]).unwrap();
+		assert_eq!(read_header, header);
+		assert_eq!(justifications, None);
+		assert_eq!(state.0.len(), 2);
+		assert!(state.is_complete());
+		for (level, (storage_key, pairs)) in state.0.iter().zip(tries.iter()) {
+			assert_eq!(&level.storage_key, storage_key);
+			assert_eq!(&level.key_values, pairs);
+		}
+	}
+
+	#[test]
+	fn read_snapshot_checks_state_root() {
+		let (tries, _) = state();
+		let header = header(Default::default());
+
+		assert!(read_snapshot::<Block>(&snapshot(&header, &tries)[..]).is_err());
+	}
+
+	#[test]
+	fn read_snapshot_rejects_truncated_input() {
+		let (tries, root) = state();
+		let mut data = snapshot(&header(root), &tries);
+		data.truncate(data.len() - 4);
+
+		assert!(read_snapshot::<Block>(&data[..]).is_err());
+	}
+
+	#[test]
+	fn read_snapshot_rejects_oversized_frame() {
+		let (tries, root) = state();
+		let mut data = snapshot(&header(root), &tries);
+		let header_len = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+		// Claim a frame of 4 GiB right after the header, which must not be allocated.
+		data[4 + header_len..8 + header_len].copy_from_slice(&u32::MAX.to_le_bytes());
+
+		let err = read_snapshot::<Block>(&data[..]).unwrap_err();
+		assert!(err.to_string().contains("exceeds the limit"), "{}", err);
+	}
+
+	#[test]
+	fn export_import_round_trip() {
+		use sc_block_builder::BlockBuilderProvider;
+		use sp_consensus::{
+			import_queue::{BasicQueue, CacheKeyId, Verifier},
+			BlockImportParams,
+		};
+		use substrate_test_runtime_client::{
+			ClientBlockImportExt, DefaultTestClientBuilderExt, TestClientBuilder,
+			TestClientBuilderExt,
+		};
+
+		struct NoVerifier;
+
+		#[async_trait::async_trait]
+		impl Verifier<Block> for NoVerifier {
+			async fn verify(
+				&mut self,
+				_origin: BlockOrigin,
+				_header: Header,
+				_justifications: Option<Justifications>,
+				_body: Option<Vec<<Block as BlockT>::Extrinsic>>,
+			) -> Result<(BlockImportParams<Block, ()>, Option<Vec<(CacheKeyId, Vec<u8>)>>), String> {
+				Err("Blocks imported with their state are not verified".into())
+			}
+		}
+
+		let child_info = ChildInfo::new_default(b"child");
+		let mut source = TestClientBuilder::new()
+			.add_extra_storage(b"snapshot_key".to_vec(), b"snapshot_value".to_vec())
+			.add_extra_child_storage(&child_info, b"child_key".to_vec(), b"child_value".to_vec())
+			.build();
+		for _ in 0..3 {
+			let block = source.new_block(Default::default()).unwrap().build().unwrap().block;
+			futures::executor::block_on(source.import(BlockOrigin::Own, block)).unwrap();
+		}
+		let source = Arc::new(source);
+		let best = source.usage_info().chain.best_hash;
+		let header = source.header(&BlockId::Hash(best)).unwrap().unwrap();
+
+		let mut data = Vec::new();
+		export_snapshot(source.clone(), Some(BlockId::Hash(best)), &mut data).unwrap();
+
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let import_queue = BasicQueue::new(
+			NoVerifier,
+			Box::new(client.clone()),
+			None,
+			&sp_core::testing::TaskExecutor::new(),
+			None,
+		);
+		futures::executor::block_on(import_snapshot(client.clone(), import_queue, &data[..]))
+			.unwrap();
+
+		let info = client.usage_info().chain;
+		assert_eq!(info.finalized_hash, best);
+		assert_eq!(info.best_hash, best);
+		let block = BlockId::Hash(best);
+		assert_eq!(
+			client.header(&block).unwrap().map(|h| *h.state_root()),
+			Some(*header.state_root()),
+		);
+		assert_eq!(
+			client.storage(&block, &StorageKey(b"snapshot_key".to_vec())).unwrap().map(|v| v.0),
+			Some(b"snapshot_value".to_vec()),
+		);
+		assert_eq!(
+			client.child_storage(&block, &child_info, &StorageKey(b"child_key".to_vec()))
+				.unwrap()
+				.map(|v| v.0),
+			Some(b"child_value".to_vec()),
+		);
+	}
+}
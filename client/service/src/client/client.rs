@@ -30,7 +30,10 @@ use codec::{Encode, Decode};
 use hash_db::Prefix;
 use sp_core::{
 	convert_hash,
-	storage::{well_known_keys, ChildInfo, PrefixedStorageKey, StorageData, StorageKey},
+	storage::{
+		well_known_keys, ChildInfo, ChildType, PrefixedStorageKey, Storage, StorageChild,
+		StorageData, StorageKey,
+	},
 	ChangesTrieConfiguration, ExecutionContext, NativeOrEncoded,
 };
 #[cfg(feature="test-helpers")]
@@ -52,11 +55,13 @@ use sp_state_machine::{
 	DBValue, Backend as StateBackend, ChangesTrieAnchorBlockId,
 	prove_read, prove_child_read, ChangesTrieRootsStorage, ChangesTrieStorage,
 	ChangesTrieConfigurationRange, key_changes, key_changes_proof,
+	prove_range_read_with_child_with_size, read_range_with_child_with_size,
+	read_range_proof_check_with_child, KeyValueStates,
 };
 use sc_executor::RuntimeVersion;
 use sp_consensus::{
 	Error as ConsensusError, BlockStatus, BlockImportParams, BlockCheckParams,
-	ImportResult, BlockOrigin, ForkChoiceStrategy,
+	ImportResult, BlockOrigin, ForkChoiceStrategy, ImportedState,
 };
 use sp_blockchain::{
 	self as blockchain,
@@ -646,6 +651,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			fork_choice,
 			intermediates,
 			import_existing,
+			state,
 			..
 		} = import_block;
 
@@ -685,6 +691,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			auxiliary,
 			fork_choice,
 			import_existing,
+			state,
 		);
 
 		if let Ok(ImportResult::Imported(ref aux)) = result {
@@ -724,6 +731,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		aux: Vec<(Vec<u8>, Option<Vec<u8>>)>,
 		fork_choice: ForkChoiceStrategy,
 		import_existing: bool,
+		imported_state: Option<ImportedState<Block>>,
 	) -> sp_blockchain::Result<ImportResult> where
 		Self: ProvideRuntimeApi<Block>,
 		<Self as ProvideRuntimeApi<Block>>::Api: CoreApi<Block> +
@@ -731,22 +739,27 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 	{
 		let parent_hash = import_headers.post().parent_hash().clone();
 		let status = self.backend.blockchain().status(BlockId::Hash(hash))?;
-		match (import_existing, status) {
+		match (import_existing, &status) {
 			(false, blockchain::BlockStatus::InChain) => return Ok(ImportResult::AlreadyInChain),
 			(false, blockchain::BlockStatus::Unknown) => {},
 			(true, blockchain::BlockStatus::InChain) =>  {},
-			(true, blockchain::BlockStatus::Unknown) =>
-				return Err(Error::UnknownBlock(format!("{:?}", hash))),
+			(true, blockchain::BlockStatus::Unknown) => {},
 		}
 
 		let info = self.backend.blockchain().info();
 
 		// the block is lower than our last finalized block so it must revert
 		// finality, refusing import.
-		if *import_headers.post().number() <= info.finalized_number {
+		if status == blockchain::BlockStatus::Unknown
+			&& *import_headers.post().number() <= info.finalized_number
+		{
 			return Err(sp_blockchain::Error::NotInFinalizedChain);
 		}
 
+		// an existing block of the finalized chain is only re-imported to
+		// add its state, it must not be finalized again.
+		let finalized = finalized && *import_headers.post().number() > info.finalized_number;
+
 		// this is a fairly arbitrary choice of where to draw the line on making notifications,
 		// but the general goal is to only make notifications when we are already fully synced
 		// and get a new chain head.
@@ -755,22 +768,27 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			BlockOrigin::Genesis | BlockOrigin::NetworkInitialSync | BlockOrigin::File => false,
 		};
 
+		// a block imported together with its state may have no known
+		// ancestry, e.g. the target block of a warp sync.
+		let parent_exists = self.backend.blockchain().status(BlockId::Hash(parent_hash))?
+			== blockchain::BlockStatus::InChain;
+
+		// ensure parent block is finalized to maintain invariant that
+		// finality is called sequentially.
+		if finalized && parent_exists {
+			self.apply_finality_with_block_hash(
+				operation,
+				parent_hash,
+				None,
+				info.best_hash,
+				make_notifications,
+			)?;
+		}
+
 		let storage_changes = match storage_changes {
 			Some(storage_changes) => {
 				self.backend.begin_state_operation(&mut operation.op, BlockId::Hash(parent_hash))?;
 
-				// ensure parent block is finalized to maintain invariant that
-				// finality is called sequentially.
-				if finalized {
-					self.apply_finality_with_block_hash(
-						operation,
-						parent_hash,
-						None,
-						info.best_hash,
-						make_notifications,
-					)?;
-				}
-
 				operation.op.update_cache(new_cache);
 
 				let (
@@ -796,7 +814,47 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 
 				Some((main_sc, child_sc))
 			},
-			None => None,
+			None => {
+				if let Some(imported_state) = imported_state {
+					// The state is imported as is, on top of an empty state.
+					self.backend.begin_state_operation(&mut operation.op, BlockId::Hash(Default::default()))?;
+
+					let mut storage = Storage::default();
+					for level in imported_state.state.0 {
+						if level.storage_key.is_empty() {
+							// Child trie roots are computed from the child tries content.
+							storage.top.extend(level.key_values.into_iter()
+								.filter(|(k, _)| !well_known_keys::is_child_storage_key(k)));
+						} else {
+							let child_info = match ChildType::from_prefixed_key(
+								PrefixedStorageKey::new_ref(&level.storage_key),
+							) {
+								Some((ChildType::ParentKeyId, storage_key)) =>
+									ChildInfo::new_default(storage_key),
+								None => return Err(Error::InvalidChildStorageKey),
+							};
+							storage.children_default
+								.entry(child_info.storage_key().to_vec())
+								.or_insert_with(|| StorageChild {
+									data: Default::default(),
+									child_info,
+								})
+								.data
+								.extend(level.key_values.into_iter());
+						}
+					}
+
+					let state_root = operation.op.reset_storage(storage)?;
+					if state_root != *import_headers.post().state_root() {
+						// State root mismatch when importing state. This should not happen in
+						// safe fast sync mode, but may happen in unsafe mode.
+						warn!("Error importing state: State root mismatch.");
+						return Err(Error::InvalidStateRoot);
+					}
+					operation.op.update_cache(new_cache);
+				}
+				None
+			},
 		};
 
 		let is_new_best = finalized || match fork_choice {
@@ -812,7 +870,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			NewBlockState::Normal
 		};
 
-		let tree_route = if is_new_best && info.best_hash != parent_hash {
+		let tree_route = if is_new_best && info.best_hash != parent_hash && parent_exists {
 			let route_from_best = sp_blockchain::tree_route(
 				self.backend.blockchain(),
 				info.best_hash,
@@ -876,12 +934,17 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		let parent_hash = import_block.header.parent_hash();
 		let at = BlockId::Hash(*parent_hash);
 		let enact_state = match self.block_status(&at)? {
+			// a block imported together with its state may have no known
+			// ancestry, e.g. the target block of a warp sync.
+			BlockStatus::Unknown if import_block.state.is_some() => false,
 			BlockStatus::Unknown => return Ok(Some(ImportResult::UnknownParent)),
 			BlockStatus::InChainWithState | BlockStatus::Queued => true,
 			BlockStatus::InChainPruned if import_block.allow_missing_state => false,
 			BlockStatus::InChainPruned => return Ok(Some(ImportResult::MissingState)),
 			BlockStatus::KnownBad => return Ok(Some(ImportResult::KnownBad)),
 		};
+		// The state is either imported as is or not required at all.
+		let enact_state = enact_state && import_block.state.is_none() && !import_block.skip_execution;
 
 		match (enact_state, &mut import_block.storage_changes, &mut import_block.body) {
 			// We have storage changes and should enact the state, so we don't need to do anything
@@ -1307,6 +1370,43 @@ impl<B, E, Block, RA> ProofProvider<Block> for Client<B, E, Block, RA> where
 			cht::size(),
 		)
 	}
+
+	fn read_proof_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[Vec<u8>],
+		size_limit: usize,
+	) -> sp_blockchain::Result<(StorageProof, u32)> {
+		let state = self.state_at(id)?;
+		Ok(prove_range_read_with_child_with_size::<_, HashFor<Block>>(
+			state,
+			size_limit,
+			start_key,
+		)?)
+	}
+
+	fn storage_collection(
+		&self,
+		id: &BlockId<Block>,
+		start_key: &[Vec<u8>],
+		size_limit: usize,
+	) -> sp_blockchain::Result<KeyValueStates> {
+		let state = self.state_at(id)?;
+		Ok(read_range_with_child_with_size::<_, HashFor<Block>>(
+			state,
+			size_limit,
+			start_key,
+		)?)
+	}
+
+	fn verify_range_proof(
+		&self,
+		root: Block::Hash,
+		proof: StorageProof,
+		start_key: &[Vec<u8>],
+	) -> sp_blockchain::Result<KeyValueStates> {
+		Ok(read_range_proof_check_with_child::<HashFor<Block>>(root, proof, start_key)?)
+	}
 }
 
 
@@ -1771,7 +1871,14 @@ impl<B, E, Block, RA> sp_consensus::BlockImport<Block> for &Client<B, E, Block,
 		&mut self,
 		block: BlockCheckParams<Block>,
 	) -> Result<ImportResult, Self::Error> {
-		let BlockCheckParams { hash, number, parent_hash, allow_missing_state, import_existing } = block;
+		let BlockCheckParams {
+			hash,
+			number,
+			parent_hash,
+			allow_missing_state,
+			import_existing,
+			allow_missing_parent,
+		} = block;
 
 		// Check the block against white and black lists if any are defined
 		// (i.e. fork blocks and bad blocks respectively)
@@ -1803,7 +1910,8 @@ impl<B, E, Block, RA> sp_consensus::BlockImport<Block> for &Client<B, E, Block,
 		{
 			BlockStatus::InChainWithState | BlockStatus::Queued if !import_existing  => return Ok(ImportResult::AlreadyInChain),
 			BlockStatus::InChainWithState | BlockStatus::Queued => {},
-			BlockStatus::InChainPruned => return Ok(ImportResult::AlreadyInChain),
+			BlockStatus::InChainPruned if !import_existing => return Ok(ImportResult::AlreadyInChain),
+			BlockStatus::InChainPruned => {},
 			BlockStatus::Unknown => {},
 			BlockStatus::KnownBad => return Ok(ImportResult::KnownBad),
 		}
@@ -1812,6 +1920,7 @@ impl<B, E, Block, RA> sp_consensus::BlockImport<Block> for &Client<B, E, Block,
 			.map_err(|e| ConsensusError::ClientImport(e.to_string()))?
 			{
 				BlockStatus::InChainWithState | BlockStatus::Queued => {},
+				BlockStatus::Unknown if allow_missing_parent => {},
 				BlockStatus::Unknown => return Ok(ImportResult::UnknownParent),
 				BlockStatus::InChainPruned if allow_missing_state => {},
 				BlockStatus::InChainPruned => return Ok(ImportResult::MissingState),
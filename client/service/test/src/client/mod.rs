@@ -1402,6 +1402,7 @@ fn respects_block_rules() {
 			parent_hash: block_ok.header().parent_hash().clone(),
 			allow_missing_state: false,
 			import_existing: false,
+			allow_missing_parent: false,
 		};
 		assert_eq!(block_on(client.check_block(params)).unwrap(), ImportResult::imported(false));
 
@@ -1417,6 +1418,7 @@ fn respects_block_rules() {
 			parent_hash: block_not_ok.header().parent_hash().clone(),
 			allow_missing_state: false,
 			import_existing: false,
+			allow_missing_parent: false,
 		};
 		if record_only {
 			known_bad.insert(block_not_ok.hash());
@@ -1439,6 +1441,7 @@ fn respects_block_rules() {
 			parent_hash: block_ok.header().parent_hash().clone(),
 			allow_missing_state: false,
 			import_existing: false,
+			allow_missing_parent: false,
 		};
 		if record_only {
 			fork_rules.push((1, block_ok.hash().clone()));
@@ -1457,6 +1460,7 @@ fn respects_block_rules() {
 			parent_hash: block_not_ok.header().parent_hash().clone(),
 			allow_missing_state: false,
 			import_existing: false,
+			allow_missing_parent: false,
 		};
 
 		if !record_only {
@@ -1521,6 +1525,7 @@ fn returns_status_for_pruned_blocks() {
 		parent_hash: a1.header().parent_hash().clone(),
 		allow_missing_state: false,
 		import_existing: false,
+		allow_missing_parent: false,
 	};
 
 	assert_eq!(
@@ -1556,6 +1561,7 @@ fn returns_status_for_pruned_blocks() {
 		parent_hash: a1.header().parent_hash().clone(),
 		allow_missing_state: false,
 		import_existing: false,
+		allow_missing_parent: false,
 	};
 
 	assert_eq!(
@@ -1588,6 +1594,7 @@ fn returns_status_for_pruned_blocks() {
 		parent_hash: a2.header().parent_hash().clone(),
 		allow_missing_state: false,
 		import_existing: false,
+		allow_missing_parent: false,
 	};
 
 	// a1 and a2 are both pruned at this point
@@ -1622,6 +1629,7 @@ fn returns_status_for_pruned_blocks() {
 		parent_hash: b1.header().parent_hash().clone(),
 		allow_missing_state: false,
 		import_existing: false,
+		allow_missing_parent: false,
 	};
 	assert_eq!(
 		block_on(client.check_block(check_block_b1.clone())).unwrap(),
@@ -0,0 +1,487 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Signing history of the local authorities, preventing a validator from equivocating.
+//!
+//! The consensus engines call [`check_and_record_slot`] before signing a block and
+//! [`check_and_record_vote`] before signing a GRANDPA vote. Both refuse to sign anything that
+//! conflicts with what was signed before by the same authority, and otherwise record it in the
+//! auxiliary database before the signature is made. Since the history survives restarts, and can
+//! be moved to another machine with [`export`] and [`import`], a validator can't be slashed for
+//! signing twice the same slot or round, even when a backup node is started with the same keys.
+//!
+//! Only the last signed slot of each authority is kept, so an authority never signs a block for
+//! an older slot than the last one it signed a block for. The votes of the last [`MAX_ROUNDS`]
+//! rounds of the current authority set are kept, and votes for older rounds are refused.
+
+#![warn(missing_docs)]
+
+use codec::{Decode, Encode};
+use parking_lot::{const_mutex, Mutex};
+use sc_client_api::backend::AuxStore;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::Error as ClientError;
+use sp_runtime::ConsensusEngineId;
+
+/// Key of the signing history in the auxiliary database.
+const HISTORY_KEY: &[u8] = b"slashing_protection_history";
+
+/// Version of the exported signing history format.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Number of rounds whose GRANDPA votes are kept for each authority.
+pub const MAX_ROUNDS: u64 = 16;
+
+/// Serializes the checks of the signing history with its updates.
+static LOCK: Mutex<()> = const_mutex(());
+
+/// Slashing protection errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Signing would equivocate.
+	#[error("Refusing to sign: {0}")]
+	Equivocation(String),
+	/// Imported signing history is invalid.
+	#[error("Invalid signing history: {0}")]
+	InvalidHistory(String),
+	/// Client error.
+	#[error(transparent)]
+	Client(#[from] ClientError),
+	/// JSON error.
+	#[error(transparent)]
+	Json(#[from] serde_json::Error),
+}
+
+/// Slashing protection result.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Kind of a GRANDPA vote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteKind {
+	/// Primary proposal.
+	PrimaryPropose,
+	/// Prevote.
+	Prevote,
+	/// Precommit.
+	Precommit,
+}
+
+/// Last slot an authority signed a block for.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedSlot {
+	/// Consensus engine of the block.
+	#[serde(with = "engine_id")]
+	pub engine_id: ConsensusEngineId,
+	/// Encoded public key of the authority.
+	#[serde(with = "sp_core::bytes")]
+	pub authority: Vec<u8>,
+	/// Slot of the block.
+	pub slot: u64,
+	/// Hash of the block, before being sealed.
+	#[serde(with = "sp_core::bytes")]
+	pub header_hash: Vec<u8>,
+}
+
+/// GRANDPA vote signed by an authority.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedVote {
+	/// Round of the vote.
+	pub round: u64,
+	/// Kind of the vote.
+	pub kind: VoteKind,
+	/// Encoded target of the vote.
+	#[serde(with = "sp_core::bytes")]
+	pub target: Vec<u8>,
+}
+
+/// GRANDPA votes signed by an authority in the last rounds of an authority set.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedVotes {
+	/// Encoded public key of the authority.
+	#[serde(with = "sp_core::bytes")]
+	pub authority: Vec<u8>,
+	/// Authority set of the votes.
+	pub set_id: u64,
+	/// Votes of the last [`MAX_ROUNDS`] rounds.
+	pub votes: Vec<SignedVote>,
+}
+
+impl SignedVotes {
+	fn last_round(&self) -> Option<u64> {
+		self.votes.iter().map(|vote| vote.round).max()
+	}
+
+	fn prune(&mut self) {
+		if let Some(last_round) = self.last_round() {
+			self.votes.retain(|vote| vote.round + MAX_ROUNDS > last_round);
+		}
+	}
+}
+
+/// Signing history of the local authorities.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode, Serialize, Deserialize)]
+pub struct History {
+	/// Last slot each authority signed a block for, per consensus engine.
+	pub slots: Vec<SignedSlot>,
+	/// GRANDPA votes of each authority.
+	pub votes: Vec<SignedVotes>,
+}
+
+/// Signing history exported from a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedHistory {
+	/// Version of the format.
+	pub version: u32,
+	/// Genesis hash of the chain of the node.
+	#[serde(with = "sp_core::bytes")]
+	pub genesis_hash: Vec<u8>,
+	/// Signing history.
+	#[serde(flatten)]
+	pub history: History,
+}
+
+mod engine_id {
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+	use sp_runtime::ConsensusEngineId;
+
+	pub fn serialize<S: Serializer>(id: &ConsensusEngineId, serializer: S) -> Result<S::Ok, S::Error> {
+		match std::str::from_utf8(id) {
+			Ok(id) => serializer.serialize_str(id),
+			Err(_) => serializer.serialize_str(&sp_core::bytes::to_hex(id, false)),
+		}
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ConsensusEngineId, D::Error> {
+		let id = String::deserialize(deserializer)?;
+		let bytes = if id.starts_with("0x") {
+			sp_core::bytes::from_hex(&id).map_err(D::Error::custom)?
+		} else {
+			id.into_bytes()
+		};
+		let mut engine_id = ConsensusEngineId::default();
+		if bytes.len() != engine_id.len() {
+			return Err(D::Error::custom("Invalid consensus engine id"))
+		}
+		engine_id.copy_from_slice(&bytes);
+		Ok(engine_id)
+	}
+}
+
+fn load_history<B: AuxStore>(backend: &B) -> Result<History> {
+	match backend.get_aux(HISTORY_KEY)? {
+		None => Ok(History::default()),
+		Some(history) => History::decode(&mut &history[..])
+			.map_err(|e| ClientError::Backend(
+				format!("Slashing protection DB is corrupted. Decode error: {}", e),
+			).into()),
+	}
+}
+
+fn write_history<B: AuxStore>(backend: &B, history: &History) -> Result<()> {
+	backend.insert_aux(&[(HISTORY_KEY, history.encode().as_slice())], &[]).map_err(Into::into)
+}
+
+/// Check that `authority` can sign the block with the given pre-seal hash for `slot`, and record
+/// it as its last signed block.
+///
+/// Signing the same block again is allowed, but not another block for the same slot or a block
+/// for an older slot.
+pub fn check_and_record_slot<B: AuxStore>(
+	backend: &B,
+	engine_id: ConsensusEngineId,
+	authority: &[u8],
+	slot: u64,
+	header_hash: &[u8],
+) -> Result<()> {
+	let _lock = LOCK.lock();
+	let mut history = load_history(backend)?;
+
+	let signed = history.slots.iter_mut()
+		.find(|signed| signed.engine_id == engine_id && signed.authority == authority);
+	match signed {
+		Some(signed) if slot < signed.slot => return Err(Error::Equivocation(format!(
+			"slot {} is older than the last signed slot {}",
+			slot,
+			signed.slot,
+		))),
+		Some(signed) if slot == signed.slot => return if signed.header_hash == header_hash {
+			Ok(())
+		} else {
+			Err(Error::Equivocation(format!("another block was signed for slot {}", slot)))
+		},
+		Some(signed) => {
+			signed.slot = slot;
+			signed.header_hash = header_hash.to_vec();
+		},
+		None => history.slots.push(SignedSlot {
+			engine_id,
+			authority: authority.to_vec(),
+			slot,
+			header_hash: header_hash.to_vec(),
+		}),
+	}
+
+	write_history(backend, &history)
+}
+
+/// Check that `authority` can sign a GRANDPA vote of the given kind for `target` in a round of
+/// the authority set `set_id`, and record it.
+///
+/// Signing the same vote again is allowed, but not a vote of the same kind for another target,
+/// nor a vote for an older authority set or for a round older than the last [`MAX_ROUNDS`]
+/// rounds the authority voted in.
+pub fn check_and_record_vote<B: AuxStore>(
+	backend: &B,
+	authority: &[u8],
+	set_id: u64,
+	round: u64,
+	kind: VoteKind,
+	target: &[u8],
+) -> Result<()> {
+	let _lock = LOCK.lock();
+	let mut history = load_history(backend)?;
+
+	let index = match history.votes.iter().position(|signed| signed.authority == authority) {
+		Some(index) => index,
+		None => {
+			history.votes.push(SignedVotes { authority: authority.to_vec(), set_id, votes: Vec::new() });
+			history.votes.len() - 1
+		},
+	};
+	let signed = &mut history.votes[index];
+
+	if set_id < signed.set_id {
+		return Err(Error::Equivocation(format!(
+			"authority set {} is older than the last authority set {} voted in",
+			set_id,
+			signed.set_id,
+		)))
+	}
+	if set_id > signed.set_id {
+		signed.set_id = set_id;
+		signed.votes.clear();
+	}
+
+	if let Some(vote) = signed.votes.iter().find(|vote| vote.round == round && vote.kind == kind) {
+		return if vote.target == target {
+			Ok(())
+		} else {
+			Err(Error::Equivocation(format!("another {:?} was signed in round {}", kind, round)))
+		}
+	}
+	if let Some(last_round) = signed.last_round() {
+		if round + MAX_ROUNDS <= last_round {
+			return Err(Error::Equivocation(format!(
+				"round {} is too old, the last round voted in is {}",
+				round,
+				last_round,
+			)))
+		}
+	}
+
+	signed.votes.push(SignedVote { round, kind, target: target.to_vec() });
+	signed.prune();
+
+	write_history(backend, &history)
+}
+
+/// Export the signing history of the node whose chain has the given genesis hash.
+pub fn export<B: AuxStore>(backend: &B, genesis_hash: &[u8]) -> Result<ExportedHistory> {
+	let _lock = LOCK.lock();
+	Ok(ExportedHistory {
+		version: EXPORT_VERSION,
+		genesis_hash: genesis_hash.to_vec(),
+		history: load_history(backend)?,
+	})
+}
+
+/// Import a signing history exported from a node of the chain with the given genesis hash.
+///
+/// The imported history is merged with the local one, keeping for each authority the most
+/// recent slot and authority set, so nothing signed before by either node can be signed again
+/// differently.
+pub fn import<B: AuxStore>(backend: &B, exported: ExportedHistory, genesis_hash: &[u8]) -> Result<()> {
+	if exported.version != EXPORT_VERSION {
+		return Err(Error::InvalidHistory(format!("unsupported version {}", exported.version)))
+	}
+	if exported.genesis_hash != genesis_hash {
+		return Err(Error::InvalidHistory("history was exported from another chain".into()))
+	}
+
+	let _lock = LOCK.lock();
+	let mut history = load_history(backend)?;
+
+	for imported in exported.history.slots {
+		let signed = history.slots.iter_mut().find(|signed| {
+			signed.engine_id == imported.engine_id && signed.authority == imported.authority
+		});
+		match signed {
+			Some(signed) if imported.slot > signed.slot => *signed = imported,
+			Some(_) => {},
+			None => history.slots.push(imported),
+		}
+	}
+
+	for imported in exported.history.votes {
+		let signed = history.votes.iter_mut().find(|signed| signed.authority == imported.authority);
+		match signed {
+			Some(signed) if imported.set_id > signed.set_id => *signed = imported,
+			Some(signed) if imported.set_id == signed.set_id => {
+				for vote in imported.votes {
+					let known = signed.votes.iter()
+						.any(|signed| signed.round == vote.round && signed.kind == vote.kind);
+					if !known {
+						signed.votes.push(vote);
+					}
+				}
+				signed.prune();
+			},
+			Some(_) => {},
+			None => history.votes.push(imported),
+		}
+	}
+
+	write_history(backend, &history)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	#[derive(Default)]
+	struct TestAux(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for TestAux {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item=&'a(&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item=&'a &'b [u8]>,
+		>(&self, insert: I, delete: D) -> sp_blockchain::Result<()> {
+			let mut aux = self.0.lock();
+			for (key, value) in insert {
+				aux.insert(key.to_vec(), value.to_vec());
+			}
+			for key in delete {
+				aux.remove(*key);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			Ok(self.0.lock().get(key).cloned())
+		}
+	}
+
+	const ENGINE_ID: ConsensusEngineId = *b"TEST";
+	const ALICE: &[u8] = b"alice";
+	const BOB: &[u8] = b"bob";
+
+	#[test]
+	fn equivocating_slots_are_refused() {
+		let client = TestAux::default();
+
+		check_and_record_slot(&client, ENGINE_ID, ALICE, 2, b"block").unwrap();
+		// signing the same block again is fine.
+		check_and_record_slot(&client, ENGINE_ID, ALICE, 2, b"block").unwrap();
+		assert!(matches!(
+			check_and_record_slot(&client, ENGINE_ID, ALICE, 2, b"other block"),
+			Err(Error::Equivocation(_)),
+		));
+		assert!(matches!(
+			check_and_record_slot(&client, ENGINE_ID, ALICE, 1, b"old block"),
+			Err(Error::Equivocation(_)),
+		));
+
+		// other authorities and engines are independent.
+		check_and_record_slot(&client, ENGINE_ID, BOB, 1, b"old block").unwrap();
+		check_and_record_slot(&client, *b"OTHR", ALICE, 1, b"old block").unwrap();
+		check_and_record_slot(&client, ENGINE_ID, ALICE, 3, b"new block").unwrap();
+	}
+
+	#[test]
+	fn equivocating_votes_are_refused() {
+		let client = TestAux::default();
+
+		check_and_record_vote(&client, ALICE, 1, 1, VoteKind::Prevote, b"a").unwrap();
+		check_and_record_vote(&client, ALICE, 1, 1, VoteKind::Prevote, b"a").unwrap();
+		check_and_record_vote(&client, ALICE, 1, 1, VoteKind::Precommit, b"b").unwrap();
+		assert!(matches!(
+			check_and_record_vote(&client, ALICE, 1, 1, VoteKind::Prevote, b"b"),
+			Err(Error::Equivocation(_)),
+		));
+		check_and_record_vote(&client, BOB, 1, 1, VoteKind::Prevote, b"b").unwrap();
+
+		// votes of rounds too old to be remembered are refused.
+		check_and_record_vote(&client, ALICE, 1, 1 + MAX_ROUNDS, VoteKind::Prevote, b"c").unwrap();
+		assert!(matches!(
+			check_and_record_vote(&client, ALICE, 1, 1, VoteKind::Prevote, b"a"),
+			Err(Error::Equivocation(_)),
+		));
+		check_and_record_vote(&client, ALICE, 1, 2, VoteKind::Prevote, b"c").unwrap();
+
+		// a new authority set starts a new history, and older sets are refused.
+		check_and_record_vote(&client, ALICE, 2, 1, VoteKind::Prevote, b"b").unwrap();
+		assert!(matches!(
+			check_and_record_vote(&client, ALICE, 1, 3, VoteKind::Prevote, b"c"),
+			Err(Error::Equivocation(_)),
+		));
+	}
+
+	#[test]
+	fn export_import_works() {
+		let genesis_hash = b"genesis";
+		let client = TestAux::default();
+		check_and_record_slot(&client, ENGINE_ID, ALICE, 5, b"block").unwrap();
+		check_and_record_vote(&client, ALICE, 1, 1, VoteKind::Prevote, b"a").unwrap();
+
+		let exported = export(&client, genesis_hash).unwrap();
+		let json = serde_json::to_string(&exported).unwrap();
+		assert!(json.contains(r#""engineId":"TEST""#));
+		let exported: ExportedHistory = serde_json::from_str(&json).unwrap();
+
+		let backup = TestAux::default();
+		check_and_record_slot(&backup, ENGINE_ID, ALICE, 3, b"block").unwrap();
+		check_and_record_vote(&backup, ALICE, 1, 1, VoteKind::Precommit, b"b").unwrap();
+		assert!(matches!(
+			import(&backup, exported.clone(), b"other genesis"),
+			Err(Error::InvalidHistory(_)),
+		));
+		import(&backup, exported, genesis_hash).unwrap();
+
+		assert!(matches!(
+			check_and_record_slot(&backup, ENGINE_ID, ALICE, 5, b"other block"),
+			Err(Error::Equivocation(_)),
+		));
+		assert!(matches!(
+			check_and_record_vote(&backup, ALICE, 1, 1, VoteKind::Prevote, b"b"),
+			Err(Error::Equivocation(_)),
+		));
+		assert!(matches!(
+			check_and_record_vote(&backup, ALICE, 1, 1, VoteKind::Precommit, b"a"),
+			Err(Error::Equivocation(_)),
+		));
+	}
+}
@@ -259,6 +259,7 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 		number: u64,
 		parent_hash: &BlockHash,
 		mut changeset: ChangeSet<Key>,
+		imported: bool,
 	) -> Result<CommitSet<Key>, Error<E>> {
 		let mut meta = ChangeSet::default();
 		if number == 0 {
@@ -276,7 +277,11 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 				})
 			},
 			PruningMode::Constrained(_) | PruningMode::ArchiveCanonical => {
-				let commit = self.non_canonical.insert(hash, number, parent_hash, changeset);
+				let commit = if imported {
+					self.non_canonical.insert_imported(hash, number, parent_hash, changeset)
+				} else {
+					self.non_canonical.insert(hash, number, parent_hash, changeset)
+				};
 				commit.map(|mut c| {
 					c.meta.inserted.extend(meta.inserted);
 					c
@@ -456,6 +461,35 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDbSync<Block
 	}
 }
 
+/// Re-encode a journal record of the meta database with `map` applied to its node keys.
+///
+/// Returns `None` if `key` is not the meta key of a journal record of the canonicalization or
+/// pruning window. This is needed to move the database to a backend that stores the nodes
+/// under different keys.
+pub fn map_journal_keys<BlockHash: Hash>(
+	key: &[u8],
+	value: &[u8],
+	map: impl Fn(Vec<u8>) -> Vec<u8>,
+) -> Result<Option<Vec<u8>>, codec::Error> {
+	match noncanonical::map_journal_keys::<BlockHash>(key, value, &map)? {
+		Some(record) => Ok(Some(record)),
+		None => pruning::map_journal_keys::<BlockHash>(key, value, &map),
+	}
+}
+
+/// Read the journal records of the pruning window of the meta database and re-encode them for a
+/// database that counts references to the nodes, with `map` applied to their node keys.
+///
+/// Nodes deleted by a block of the window and inserted again by a later block are kept by a
+/// database that doesn't count references, so they are removed from the deleted nodes of the
+/// earlier block. Returns the meta keys and the values of the records.
+pub fn map_pruning_journal<BlockHash: Hash, D: MetaDb>(
+	db: &D,
+	map: impl Fn(Vec<u8>) -> Vec<u8>,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Error<D::Error>> {
+	pruning::map_journal::<BlockHash, D>(db, &map)
+}
+
 /// State DB maintenance. See module description.
 /// Can be shared across threads.
 pub struct StateDb<BlockHash: Hash, Key: Hash> {
@@ -482,7 +516,21 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDb<BlockHash
 		parent_hash: &BlockHash,
 		changeset: ChangeSet<Key>,
 	) -> Result<CommitSet<Key>, Error<E>> {
-		self.db.write().insert_block(hash, number, parent_hash, changeset)
+		self.db.write().insert_block(hash, number, parent_hash, changeset, false)
+	}
+
+	/// Add a new non-canonical block whose state was imported as a whole, e.g. by state sync.
+	///
+	/// The parent of the block doesn't have to be known. This is only allowed while there are no
+	/// other non-canonical blocks.
+	pub fn insert_imported_block<E: fmt::Debug>(
+		&self,
+		hash: &BlockHash,
+		number: u64,
+		parent_hash: &BlockHash,
+		changeset: ChangeSet<Key>,
+	) -> Result<CommitSet<Key>, Error<E>> {
+		self.db.write().insert_block(hash, number, parent_hash, changeset, true)
 	}
 
 	/// Finalize a previously inserted block.
@@ -556,7 +604,7 @@ impl<BlockHash: Hash + MallocSizeOf, Key: Hash + MallocSizeOf> StateDb<BlockHash
 mod tests {
 	use std::io;
 	use sp_core::H256;
-	use crate::{StateDb, PruningMode, Constraints};
+	use crate::{StateDb, PruningMode, Constraints, ChangeSet, map_journal_keys};
 	use crate::test::{make_db, make_changeset, TestDb};
 
 	fn make_test_db(settings: PruningMode) -> (TestDb, StateDb<H256, H256>) {
@@ -691,4 +739,41 @@ mod tests {
 		let state_db: Result<StateDb<H256, H256>, _> = StateDb::new(new_mode, false, &db);
 		assert!(state_db.is_err());
 	}
+
+	#[test]
+	fn maps_journal_keys() {
+		let mode = PruningMode::Constrained(Constraints { max_blocks: Some(1), max_mem: None });
+		let prefixed = StateDb::<H256, Vec<u8>>::new(mode.clone(), true, &make_db(&[])).unwrap();
+		let stripped = StateDb::<H256, Vec<u8>>::new(mode, true, &make_db(&[])).unwrap();
+		let changeset = |prefix: &[u8]| ChangeSet {
+			inserted: vec![([prefix, &[1]].concat(), vec![1])],
+			deleted: vec![[prefix, &[2]].concat()],
+		};
+		let hash = H256::from_low_u64_be(1);
+		let parent = H256::from_low_u64_be(0);
+
+		let mut journals = Vec::new();
+		for (db, prefix) in [(&prefixed, &[9u8, 9][..]), (&stripped, &[][..])].iter() {
+			let mut meta = db.insert_block::<io::Error>(&hash, 1, &parent, changeset(prefix))
+				.unwrap()
+				.meta
+				.inserted;
+			db.apply_pending();
+			meta.extend(db.canonicalize_block::<io::Error>(&hash).unwrap().meta.inserted);
+			journals.push(meta);
+		}
+
+		let mut mapped = 0;
+		for ((key, value), (_, expected)) in journals[0].iter().zip(journals[1].iter()) {
+			match map_journal_keys::<H256>(key, value, |key| key[2..].to_vec()).unwrap() {
+				Some(value) => {
+					assert_eq!(&value, expected);
+					mapped += 1;
+				},
+				None => assert_eq!(value, expected),
+			}
+		}
+		// The canonicalization and the pruning journal records.
+		assert_eq!(mapped, 2);
+	}
 }
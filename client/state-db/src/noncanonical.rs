@@ -88,6 +88,27 @@ fn to_journal_key(block: u64, index: u64) -> Vec<u8> {
 	to_meta_key(NON_CANONICAL_JOURNAL, &(block, index))
 }
 
+/// Re-encode the journal record stored under the meta `key` with `map` applied to its node keys.
+///
+/// Returns `None` if `key` is not the key of a journal record.
+pub(crate) fn map_journal_keys<BlockHash: Hash>(
+	key: &[u8],
+	value: &[u8],
+	map: &dyn Fn(Vec<u8>) -> Vec<u8>,
+) -> Result<Option<Vec<u8>>, codec::Error> {
+	if key.len() != to_journal_key(0, 0).len() || !key.ends_with(NON_CANONICAL_JOURNAL) {
+		return Ok(None);
+	}
+	let record: JournalRecord<BlockHash, Vec<u8>> = Decode::decode(&mut &value[..])?;
+	let record = JournalRecord {
+		hash: record.hash,
+		parent_hash: record.parent_hash,
+		inserted: record.inserted.into_iter().map(|(k, v)| (map(k), v)).collect(),
+		deleted: record.deleted.into_iter().map(map).collect(),
+	};
+	Ok(Some(record.encode()))
+}
+
 #[cfg_attr(test, derive(PartialEq, Debug))]
 #[derive(parity_util_mem_derive::MallocSizeOf)]
 struct BlockOverlay<BlockHash: Hash, Key: Hash> {
@@ -300,6 +321,33 @@ impl<BlockHash: Hash, Key: Hash> NonCanonicalOverlay<BlockHash, Key> {
 		Ok(commit)
 	}
 
+	/// Insert a block whose state was imported as a whole rather than built on top of its parent,
+	/// e.g. when syncing the state of a finalized block.
+	///
+	/// The parent is assumed to be canonicalized, so this is only allowed while the overlay is
+	/// empty and for blocks past the last canonicalized one.
+	pub fn insert_imported<E: fmt::Debug>(&mut self, hash: &BlockHash, number: u64, parent_hash: &BlockHash, changeset: ChangeSet<Key>) -> Result<CommitSet<Key>, Error<E>> {
+		if !self.levels.is_empty() {
+			return Err(Error::InvalidParent);
+		}
+		if number == 0 || self.last_canonicalized.as_ref().map_or(false, |&(_, n)| number <= n) {
+			return Err(Error::InvalidBlockNumber);
+		}
+
+		let last_canonicalized = (parent_hash.clone(), number - 1);
+		let previous = std::mem::replace(&mut self.last_canonicalized, Some(last_canonicalized.clone()));
+		match self.insert(hash, number, parent_hash, changeset) {
+			Ok(mut commit) => {
+				commit.meta.inserted.push((to_meta_key(LAST_CANONICAL, &()), last_canonicalized.encode()));
+				Ok(commit)
+			},
+			Err(e) => {
+				self.last_canonicalized = previous;
+				Err(e)
+			},
+		}
+	}
+
 	fn discard_journals(
 		&self,
 		level_index: usize,
@@ -578,7 +626,7 @@ mod tests {
 	use std::io;
 	use sp_core::H256;
 	use super::{NonCanonicalOverlay, to_journal_key};
-	use crate::{ChangeSet, CommitSet, MetaDb};
+	use crate::{ChangeSet, CommitSet, Error, MetaDb};
 	use crate::test::{make_db, make_changeset};
 
 	fn contains(overlay: &NonCanonicalOverlay<H256, H256>, key: u64) -> bool {
@@ -625,6 +673,45 @@ mod tests {
 		overlay.insert::<io::Error>(&h2, 3, &h1, ChangeSet::default()).unwrap();
 	}
 
+	#[test]
+	fn insert_ahead_of_canonicalized_works() {
+		let h1 = H256::random();
+		let h2 = H256::random();
+		let h3 = H256::random();
+		let mut db = make_db(&[]);
+		let mut overlay = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		db.commit(&overlay.insert::<io::Error>(&h1, 1, &H256::default(), ChangeSet::default()).unwrap());
+		let mut commit = CommitSet::default();
+		overlay.canonicalize::<io::Error>(&h1, &mut commit).unwrap();
+		db.commit(&commit);
+		overlay.apply_pending();
+		assert_eq!(overlay.levels.len(), 0);
+
+		// Inserting a block with unknown parent far ahead of the last canonicalized
+		// block is only allowed for imported state.
+		assert!(matches!(
+			overlay.insert::<io::Error>(&h2, 10, &H256::random(), ChangeSet::default()),
+			Err(Error::InvalidBlockNumber)
+		));
+		assert!(matches!(
+			overlay.insert_imported::<io::Error>(&h2, 1, &H256::random(), ChangeSet::default()),
+			Err(Error::InvalidBlockNumber)
+		));
+		db.commit(&overlay.insert_imported::<io::Error>(&h2, 10, &H256::random(), ChangeSet::default()).unwrap());
+		db.commit(&overlay.insert::<io::Error>(&h3, 11, &h2, ChangeSet::default()).unwrap());
+		overlay.apply_pending();
+		assert_eq!(overlay.levels.len(), 2);
+		assert_eq!(overlay.last_canonicalized_block_number(), Some(9));
+		assert!(matches!(
+			overlay.insert_imported::<io::Error>(&H256::random(), 20, &H256::random(), ChangeSet::default()),
+			Err(Error::InvalidParent)
+		));
+
+		let overlay2 = NonCanonicalOverlay::<H256, H256>::new(&db).unwrap();
+		assert_eq!(overlay.levels, overlay2.levels);
+		assert_eq!(overlay.last_canonicalized, overlay2.last_canonicalized);
+	}
+
 	#[test]
 	#[should_panic]
 	fn insert_unknown_parent_panics() {
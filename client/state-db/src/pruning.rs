@@ -71,6 +71,47 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+/// Re-encode the journal record stored under the meta `key` with `map` applied to its node keys.
+///
+/// Returns `None` if `key` is not the key of a journal record.
+pub(crate) fn map_journal_keys<BlockHash: Hash>(
+	key: &[u8],
+	value: &[u8],
+	map: &dyn Fn(Vec<u8>) -> Vec<u8>,
+) -> Result<Option<Vec<u8>>, codec::Error> {
+	if key.len() != to_journal_key(0).len() || !key.ends_with(PRUNING_JOURNAL) {
+		return Ok(None);
+	}
+	let record: JournalRecord<BlockHash, Vec<u8>> = Decode::decode(&mut &value[..])?;
+	let record = JournalRecord {
+		hash: record.hash,
+		inserted: record.inserted.into_iter().map(map).collect(),
+		deleted: record.deleted.into_iter().map(map).collect(),
+	};
+	Ok(Some(record.encode()))
+}
+
+/// Read the journal records of the pruning window and re-encode them with `map` applied to their
+/// node keys, for a database that counts references to the nodes.
+///
+/// The window is loaded as it is with `count_insertions`, so that keys inserted again by a later
+/// block are no longer deleted by the earlier one. The inserted keys are not journaled without
+/// `count_insertions` and are dropped.
+pub(crate) fn map_journal<BlockHash: Hash, D: MetaDb>(
+	db: &D,
+	map: &dyn Fn(Vec<u8>) -> Vec<u8>,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Error<D::Error>> {
+	let window: RefWindow<BlockHash, Vec<u8>> = RefWindow::new(db, true)?;
+	Ok(window.death_rows.into_iter().map(|row| {
+		let record = JournalRecord::<BlockHash, Vec<u8>> {
+			hash: row.hash,
+			inserted: Vec::new(),
+			deleted: row.deleted.into_iter().map(map).collect(),
+		};
+		(row.journal_key, record.encode())
+	}).collect())
+}
+
 impl<BlockHash: Hash, Key: Hash> RefWindow<BlockHash, Key> {
 	pub fn new<D: MetaDb>(db: &D, count_insertions: bool) -> Result<RefWindow<BlockHash, Key>, Error<D::Error>> {
 		let last_pruned = db.get_meta(&to_meta_key(LAST_PRUNED, &()))
@@ -218,7 +259,8 @@ impl<BlockHash: Hash, Key: Hash> RefWindow<BlockHash, Key> {
 
 #[cfg(test)]
 mod tests {
-	use super::RefWindow;
+	use super::{map_journal, JournalRecord, RefWindow};
+	use codec::Decode;
 	use sp_core::H256;
 	use crate::CommitSet;
 	use crate::test::{make_db, make_commit, TestDb};
@@ -420,4 +462,33 @@ mod tests {
 		assert!(pruning.death_index.is_empty());
 	}
 
+	#[test]
+	fn mapped_journal_keeps_reinserted() {
+		let commit = |inserted: &[u8], deleted: &[u8]| {
+			let mut commit = CommitSet::<Vec<u8>>::default();
+			commit.data.inserted = inserted.iter().map(|k| (vec![0, *k], vec![*k])).collect();
+			commit.data.deleted = deleted.iter().map(|k| vec![0, *k]).collect();
+			commit
+		};
+		let mut db = make_db(&[]);
+		let mut pruning: RefWindow<H256, Vec<u8>> = RefWindow::new(&db, true).unwrap();
+		for (inserted, deleted) in [(&[][..], &[1, 2][..]), (&[2], &[3])].iter() {
+			let mut commit = commit(inserted, deleted);
+			pruning.note_canonical(&H256::random(), &mut commit);
+			db.meta.extend(commit.meta.inserted);
+		}
+		pruning.apply_pending();
+
+		let journal = map_journal::<H256, _>(&db, &|key| key[1..].to_vec()).unwrap();
+		let deleted = journal.into_iter()
+			.map(|(_, record)| {
+				let record = JournalRecord::<H256, Vec<u8>>::decode(&mut &record[..]).unwrap();
+				assert!(record.inserted.is_empty());
+				let mut deleted = record.deleted;
+				deleted.sort();
+				deleted
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(deleted, vec![vec![vec![1]], vec![vec![3]]]);
+	}
 }
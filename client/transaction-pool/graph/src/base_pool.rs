@@ -21,7 +21,8 @@
 //! For a more full-featured pool, have a look at the `pool` module.
 
 use std::{
-	collections::HashSet,
+	cmp::Reverse,
+	collections::{HashMap, HashSet},
 	fmt,
 	hash,
 	sync::Arc,
@@ -218,23 +219,34 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Per-sender limit, if any.
+	sender_limit: Option<SenderLimit>,
+	/// Number of transactions of each sender in both queues.
+	///
+	/// Only maintained when there is a sender limit, and updated as transactions enter and leave the pool.
+	sender_counts: HashMap<Vec<u8>, usize>,
+	/// Senders with more transactions in the pool than allowed by the limit.
+	exceeding_senders: HashSet<Vec<u8>>,
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
 	fn default() -> Self {
-		Self::new(false)
+		Self::new(false, None)
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
-	/// Create new pool given reject_future_transactions flag.
-	pub fn new(reject_future_transactions: bool) -> Self {
+	/// Create new pool given reject_future_transactions flag and an optional per-sender limit.
+	pub fn new(reject_future_transactions: bool, sender_limit: Option<SenderLimit>) -> Self {
 		Self {
 			reject_future_transactions,
 			future: Default::default(),
 			ready: Default::default(),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			sender_limit,
+			sender_counts: Default::default(),
+			exceeding_senders: Default::default(),
 		}
 	}
 
@@ -291,16 +303,19 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			}
 
 			let hash = tx.transaction.hash.clone();
+			self.sender_added(&tx.transaction);
 			self.future.import(tx);
 			return Ok(Imported::Future { hash });
 		}
 
+		self.sender_added(&tx.transaction);
 		self.import_to_ready(tx)
 	}
 
 	/// Imports transaction to ready queue.
 	///
-	/// NOTE the transaction has to have all requirements satisfied.
+	/// NOTE the transaction has to have all requirements satisfied and has to be already
+	/// accounted for in the per-sender counts.
 	fn import_to_ready(&mut self, tx: WaitingTransaction<Hash, Ex>) -> error::Result<Imported<Hash, Ex>> {
 		let hash = tx.transaction.hash.clone();
 		let mut promoted = vec![];
@@ -317,20 +332,27 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 
 			// import this transaction
 			let current_hash = tx.transaction.hash.clone();
+			let current_tx = tx.transaction.clone();
 			match self.ready.import(tx) {
 				Ok(mut replaced) => {
 					if !first {
 						promoted.push(current_hash);
 					}
+					for tx in &replaced {
+						self.sender_removed(tx);
+					}
 					// The transactions were removed from the ready pool. We might attempt to re-import them.
 					removed.append(&mut replaced);
 				},
 				// transaction failed to be imported.
-				Err(e) => if first {
-					debug!(target: "txpool", "[{:?}] Error importing: {:?}", current_hash, e);
-					return Err(e)
-				} else {
-					failed.push(current_hash);
+				Err(e) => {
+					self.sender_removed(&current_tx);
+					if first {
+						debug!(target: "txpool", "[{:?}] Error importing: {:?}", current_hash, e);
+						return Err(e)
+					} else {
+						failed.push(current_hash);
+					}
 				},
 			}
 			first = false;
@@ -344,7 +366,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		if removed.iter().any(|tx| tx.hash == hash) {
 			// We still need to remove all transactions that we promoted
 			// since they depend on each other and will never get to the best iterator.
-			self.ready.remove_subtree(&promoted);
+			for tx in self.ready.remove_subtree(&promoted) {
+				self.sender_removed(&tx);
+			}
 
 			debug!(target: "txpool", "[{:?}] Cycle detected, bailing.", hash);
 			return Err(error::Error::CycleDetected)
@@ -390,11 +414,23 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 
 	/// Makes sure that the transactions in the queues stay within provided limits.
 	///
-	/// Removes and returns worst transactions from the queues and all transactions that depend on them.
+	/// Removes and returns worst transactions from the queues and all transactions that depend on them,
+	/// together with the reason of their eviction.
 	/// Technically the worst transaction should be evaluated by computing the entire pending set.
-	/// We use a simplified approach to remove the transaction that occupies the pool for the longest time.
-	pub fn enforce_limits(&mut self, ready: &Limit, future: &Limit) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		let mut removed = vec![];
+	/// We use a simplified approach to remove the transaction with the lowest priority, and among those
+	/// the one that occupies the pool for the longest time.
+	///
+	/// The per-sender limit, if any, is enforced first, so that a single sender exceeding it does not
+	/// push transactions of other senders out of the pool.
+	pub fn enforce_limits(
+		&mut self,
+		ready: &Limit,
+		future: &Limit,
+	) -> Vec<(Arc<Transaction<Hash, Ex>>, EvictionReason)> {
+		let mut removed = self.enforce_sender_limit()
+			.into_iter()
+			.map(|tx| (tx, EvictionReason::SenderLimit))
+			.collect::<Vec<_>>();
 
 		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
 			// find the worst transaction
@@ -403,7 +439,8 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 					let transaction = &current.transaction;
 					match minimal {
 						None => Some(transaction.clone()),
-						Some(ref tx) if tx.insertion_id > transaction.insertion_id => {
+						Some(ref tx) if (tx.transaction.priority, tx.insertion_id)
+							> (transaction.transaction.priority, transaction.insertion_id) => {
 							Some(transaction.clone())
 						},
 						other => other,
@@ -411,7 +448,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 				});
 
 			if let Some(minimal) = minimal {
-				removed.append(&mut self.remove_subtree(&[minimal.transaction.hash.clone()]))
+				removed.extend(
+					self.remove_subtree(&[minimal.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::ReadyLimit))
+				)
 			} else {
 				break;
 			}
@@ -423,7 +464,8 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 				.fold(|minimal, current| {
 					match minimal {
 						None => Some(current.clone()),
-						Some(ref tx) if tx.imported_at > current.imported_at => {
+						Some(ref tx) if (tx.transaction.priority, tx.imported_at)
+							> (current.transaction.priority, current.imported_at) => {
 							Some(current.clone())
 						},
 						other => other,
@@ -431,7 +473,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 				});
 
 			if let Some(minimal) = minimal {
-				removed.append(&mut self.remove_subtree(&[minimal.transaction.hash.clone()]))
+				removed.extend(
+					self.remove_subtree(&[minimal.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (tx, EvictionReason::FutureLimit))
+				)
 			} else {
 				break;
 			}
@@ -440,6 +486,100 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Makes sure that no sender has more transactions in the pool than allowed by the limit.
+	///
+	/// Future transactions of an offending sender are evicted first, since they can't be included
+	/// yet. Within each queue the transactions with the lowest priority go first, and among those
+	/// the most recently imported ones, so that the transactions they depend on are kept.
+	fn enforce_sender_limit(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		fn worst_of<Hash: Clone, Ex, K: Ord>(
+			worst: Option<(Priority, K, Hash)>,
+			tx: &Transaction<Hash, Ex>,
+			key: K,
+		) -> Option<(Priority, K, Hash)> {
+			match worst {
+				Some(worst) if (worst.0, &worst.1) <= (tx.priority, &key) => Some(worst),
+				_ => Some((tx.priority, key, tx.hash.clone())),
+			}
+		}
+
+		let limit = match self.sender_limit.clone() {
+			Some(limit) => limit,
+			None => return vec![],
+		};
+
+		let mut removed = vec![];
+		let exceeding = self.exceeding_senders.iter().cloned().collect::<Vec<_>>();
+		for sender in exceeding {
+			while self.exceeding_senders.contains(&sender) {
+				let is_sender = |tx: &Transaction<Hash, Ex>| limit.sender_of(tx) == Some(&sender[..]);
+				let worst = self.future
+					.fold(|worst, current| if is_sender(&current.transaction) {
+						worst_of(worst, &current.transaction, Reverse(current.imported_at))
+					} else {
+						worst
+					})
+					.map(|(_, _, hash)| hash)
+					.or_else(|| self.ready.fold(|worst, current| {
+						let transaction = &current.transaction;
+						if is_sender(&transaction.transaction) {
+							worst_of(worst, &transaction.transaction, Reverse(transaction.insertion_id))
+						} else {
+							worst
+						}
+					}).map(|(_, _, hash)| hash));
+
+				let hash = match worst {
+					Some(hash) => hash,
+					None => break,
+				};
+				debug!(target: "txpool", "[{:?}] Sender limit exceeded, evicting.", hash);
+				removed.extend(self.remove_subtree(&[hash]));
+			}
+		}
+
+		removed
+	}
+
+	/// Returns true if any sender has more transactions in the pool than allowed by the limit.
+	pub fn is_sender_limit_exceeded(&self) -> bool {
+		!self.exceeding_senders.is_empty()
+	}
+
+	/// Accounts for a transaction of its sender entering the pool.
+	fn sender_added(&mut self, tx: &Transaction<Hash, Ex>) {
+		let limit = match self.sender_limit {
+			Some(ref limit) => limit,
+			None => return,
+		};
+		if let Some(sender) = limit.sender_of(tx) {
+			let count = self.sender_counts.entry(sender.to_vec()).or_default();
+			*count += 1;
+			if *count > limit.count {
+				self.exceeding_senders.insert(sender.to_vec());
+			}
+		}
+	}
+
+	/// Accounts for a transaction of its sender leaving the pool.
+	fn sender_removed(&mut self, tx: &Transaction<Hash, Ex>) {
+		let limit = match self.sender_limit {
+			Some(ref limit) => limit,
+			None => return,
+		};
+		if let Some(sender) = limit.sender_of(tx) {
+			if let Some(count) = self.sender_counts.get_mut(sender) {
+				*count -= 1;
+				if *count <= limit.count {
+					self.exceeding_senders.remove(sender);
+				}
+				if *count == 0 {
+					self.sender_counts.remove(sender);
+				}
+			}
+		}
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -451,12 +591,19 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	pub fn remove_subtree(&mut self, hashes: &[Hash]) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = self.ready.remove_subtree(hashes);
 		removed.extend(self.future.remove(hashes));
+		for tx in &removed {
+			self.sender_removed(tx);
+		}
 		removed
 	}
 
 	/// Removes and returns all transactions from the future queue.
 	pub fn clear_future(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		self.future.clear()
+		let removed = self.future.clear();
+		for tx in &removed {
+			self.sender_removed(tx);
+		}
+		removed
 	}
 
 	/// Prunes transactions that provide given list of tags.
@@ -480,6 +627,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			// store the tags for next submission
 			recently_pruned.insert(tag);
 		}
+		for tx in &pruned {
+			self.sender_removed(tx);
+		}
 
 		let mut promoted = vec![];
 		let mut failed = vec![];
@@ -528,6 +678,48 @@ impl Limit {
 	}
 }
 
+/// Per-sender limits
+#[derive(Debug, Clone)]
+#[cfg_attr(not(target_os = "unknown"), derive(parity_util_mem::MallocSizeOf))]
+pub struct SenderLimit {
+	/// Length of the prefix of the first `provides` tag identifying the sender of a transaction.
+	///
+	/// FRAME-based runtimes provide `(AccountId, Nonce)` tags, so this is the length of an encoded
+	/// account id. Transactions with a shorter first tag are not subject to the limit.
+	pub tag_prefix_len: usize,
+	/// Maximal number of transactions of a single sender in both queues.
+	pub count: usize,
+}
+
+impl SenderLimit {
+	/// Returns the sender of the transaction, if it can be determined.
+	pub fn sender_of<'a, Hash, Ex>(&self, tx: &'a Transaction<Hash, Ex>) -> Option<&'a [u8]> {
+		tx.provides.first().and_then(|tag| tag.get(..self.tag_prefix_len))
+	}
+}
+
+/// Reason of a transaction eviction when enforcing the pool limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictionReason {
+	/// The ready queue exceeded its limits.
+	ReadyLimit,
+	/// The future queue exceeded its limits.
+	FutureLimit,
+	/// The sender of the transaction exceeded its limit.
+	SenderLimit,
+}
+
+impl EvictionReason {
+	/// Returns a short name of the reason, suitable as a metric label.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::ReadyLimit => "ready_limit",
+			Self::FutureLimit => "future_limit",
+			Self::SenderLimit => "sender_limit",
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1186,4 +1378,111 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	fn transaction(
+		hash: Hash,
+		priority: Priority,
+		requires: Vec<Tag>,
+		provides: Vec<Tag>,
+	) -> Transaction<Hash, Vec<u8>> {
+		Transaction {
+			data: vec![hash as u8],
+			bytes: 1,
+			hash,
+			priority,
+			valid_till: 64u64,
+			requires,
+			provides,
+			propagate: true,
+			source: Source::External,
+		}
+	}
+
+	#[test]
+	fn should_evict_lowest_priority_transactions_first() {
+		// given
+		let mut pool = pool();
+		pool.import(transaction(1, 5, vec![], vec![vec![1]])).unwrap();
+		pool.import(transaction(2, 1, vec![], vec![vec![2]])).unwrap();
+		pool.import(transaction(3, 5, vec![], vec![vec![3]])).unwrap();
+		let future = Limit { count: 10, total_bytes: 10 };
+
+		// when
+		let removed = pool.enforce_limits(&Limit { count: 2, total_bytes: 10 }, &future);
+
+		// then
+		assert_eq!(
+			removed.iter().map(|(tx, reason)| (tx.hash, *reason)).collect::<Vec<_>>(),
+			vec![(2, EvictionReason::ReadyLimit)],
+		);
+
+		// and when
+		let removed = pool.enforce_limits(&Limit { count: 1, total_bytes: 10 }, &future);
+
+		// then the longest waiting one goes first
+		assert_eq!(
+			removed.iter().map(|(tx, reason)| (tx.hash, *reason)).collect::<Vec<_>>(),
+			vec![(1, EvictionReason::ReadyLimit)],
+		);
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<Vec<_>>(), vec![3]);
+	}
+
+	#[test]
+	fn should_enforce_sender_limit() {
+		// given
+		let mut pool = BasePool::new(false, Some(SenderLimit { tag_prefix_len: 1, count: 2 }));
+		pool.import(transaction(1, 5, vec![], vec![vec![1, 0]])).unwrap();
+		pool.import(transaction(2, 5, vec![vec![1, 0]], vec![vec![1, 1]])).unwrap();
+		pool.import(transaction(3, 5, vec![vec![1, 1]], vec![vec![1, 2]])).unwrap();
+		pool.import(transaction(4, 5, vec![vec![1, 8]], vec![vec![1, 9]])).unwrap();
+		pool.import(transaction(5, 5, vec![], vec![vec![2, 0]])).unwrap();
+		pool.import(transaction(6, 5, vec![vec![2, 0]], vec![vec![2, 1]])).unwrap();
+		assert_eq!(pool.ready.len(), 5);
+		assert_eq!(pool.future.len(), 1);
+		assert!(pool.is_sender_limit_exceeded());
+		let limit = Limit { count: 10, total_bytes: 10 };
+
+		// when
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then the future transaction goes first, and then the most recent ready one
+		assert_eq!(
+			removed.iter().map(|(tx, reason)| (tx.hash, *reason)).collect::<Vec<_>>(),
+			vec![(4, EvictionReason::SenderLimit), (3, EvictionReason::SenderLimit)],
+		);
+		assert_eq!(pool.ready.len(), 4);
+		assert_eq!(pool.future.len(), 0);
+		assert!(!pool.is_sender_limit_exceeded());
+		assert!(pool.enforce_limits(&limit, &limit).is_empty());
+	}
+
+	#[test]
+	fn should_keep_sender_counts_up_to_date() {
+		// given
+		let mut pool = BasePool::new(false, Some(SenderLimit { tag_prefix_len: 1, count: 2 }));
+		pool.import(transaction(1, 5, vec![], vec![vec![1, 0]])).unwrap();
+		pool.import(transaction(2, 5, vec![vec![1, 0]], vec![vec![1, 1]])).unwrap();
+		pool.import(transaction(3, 5, vec![vec![1, 3]], vec![vec![1, 4]])).unwrap();
+		assert!(pool.is_sender_limit_exceeded());
+
+		// when the first transaction is included
+		pool.prune_tags(vec![vec![1, 0]]);
+
+		// then
+		assert!(!pool.is_sender_limit_exceeded());
+
+		// and when a transaction replaces another one of the same sender
+		pool.import(transaction(4, 10, vec![vec![1, 0]], vec![vec![1, 1]])).unwrap();
+		pool.import(transaction(5, 5, vec![vec![1, 1]], vec![vec![1, 2]])).unwrap();
+
+		// then
+		assert_eq!(pool.ready().map(|tx| tx.hash).collect::<Vec<_>>(), vec![4, 5]);
+		assert!(pool.is_sender_limit_exceeded());
+
+		// and when the future queue is cleared
+		pool.clear_future();
+
+		// then
+		assert!(!pool.is_sender_limit_exceeded());
+	}
 }
@@ -37,8 +37,8 @@ mod tracked_map;
 pub mod base_pool;
 pub mod watcher;
 
-pub use self::base_pool::Transaction;
+pub use self::base_pool::{EvictionReason, SenderLimit, Transaction};
 pub use self::pool::{
-	BlockHash, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash, IsValidator, NumberFor, Options,
-	Pool, TransactionFor, ValidatedTransaction,
+	BlockHash, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash, IsValidator, JournalOptions,
+	NumberFor, Options, Pool, TransactionFor, ValidatedTransaction,
 };
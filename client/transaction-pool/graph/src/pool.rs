@@ -18,7 +18,9 @@
 
 use std::{
 	collections::HashMap,
+	path::PathBuf,
 	sync::Arc,
+	time::Duration,
 };
 
 use futures::Future;
@@ -110,8 +112,27 @@ pub struct Options {
 	pub ready: base::Limit,
 	/// Future queue limits.
 	pub future: base::Limit,
+	/// Per-sender limits.
+	///
+	/// Transactions are not limited per sender when `None`.
+	pub sender: Option<base::SenderLimit>,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
+	/// Persist pending transactions on disk, so they survive a restart of the node.
+	///
+	/// Journaling is disabled when `None`.
+	pub journal: Option<JournalOptions>,
+}
+
+/// Transaction journal configuration options.
+#[derive(Debug, Clone)]
+pub struct JournalOptions {
+	/// Path of the journal file.
+	pub path: PathBuf,
+	/// Transactions that have been pending for longer than this are not re-submitted.
+	pub max_age: Duration,
+	/// Maximal total size in bytes of the transactions kept in the journal.
+	pub total_bytes: usize,
 }
 
 impl Default for Options {
@@ -125,7 +146,9 @@ impl Default for Options {
 				count: 512,
 				total_bytes: 1 * 1024 * 1024,
 			},
+			sender: None,
 			reject_future_transactions: false,
+			journal: None,
 		}
 	}
 }
@@ -198,6 +221,25 @@ impl<B: ChainApi> Pool<B> {
 		res.expect("One extrinsic passed; one result returned; qed")
 	}
 
+	/// Imports one unverified extrinsic to the pool and returns its hash along with the hashes of
+	/// the transactions it replaced.
+	pub async fn submit_replacing(
+		&self,
+		at: &BlockId<B::Block>,
+		source: TransactionSource,
+		xt: ExtrinsicFor<B>,
+	) -> Result<(ExtrinsicHash<B>, Vec<ExtrinsicHash<B>>), B::Error> {
+		let block_number = self.resolve_block_number(at)?;
+		let (_, tx) = self.verify_one(
+			at,
+			block_number,
+			source,
+			xt,
+			CheckBannedBeforeVerify::Yes,
+		).await;
+		self.validated_pool.submit_replacing(tx)
+	}
+
 	/// Import a single extrinsic and starts to watch its progress in the pool.
 	pub async fn submit_and_watch(
 		&self,
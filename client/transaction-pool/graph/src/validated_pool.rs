@@ -115,6 +115,7 @@ pub struct ValidatedPool<B: ChainApi> {
 	>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
+	evictions: Mutex<HashMap<base::EvictionReason, usize>>,
 }
 
 #[cfg(not(target_os = "unknown"))]
@@ -131,7 +132,10 @@ where
 impl<B: ChainApi> ValidatedPool<B> {
 	/// Create a new transaction pool.
 	pub fn new(options: Options, is_validator: IsValidator, api: Arc<B>) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let base_pool = base::BasePool::new(
+			options.reject_future_transactions,
+			options.sender.clone(),
+		);
 		Self {
 			is_validator,
 			options,
@@ -140,6 +144,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
 			rotator: Default::default(),
+			evictions: Default::default(),
 		}
 	}
 
@@ -178,7 +183,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 		txs: impl IntoIterator<Item=ValidatedTransactionFor<B>>,
 	) -> Vec<Result<ExtrinsicHash<B>, B::Error>> {
 		let results = txs.into_iter()
-			.map(|validated_tx| self.submit_one(validated_tx))
+			.map(|validated_tx| self.submit_one(validated_tx).map(|imported| *imported.hash()))
 			.collect::<Vec<_>>();
 
 		// only enforce limits if there is at least one imported transaction
@@ -194,8 +199,29 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}).collect()
 	}
 
+	/// Submit a single pre-validated transaction and return its hash along with the hashes of the
+	/// transactions it replaced in the ready queue.
+	pub fn submit_replacing(
+		&self,
+		tx: ValidatedTransactionFor<B>,
+	) -> Result<(ExtrinsicHash<B>, Vec<ExtrinsicHash<B>>), B::Error> {
+		let imported = self.submit_one(tx)?;
+		if self.enforce_limits().contains(imported.hash()) {
+			return Err(error::Error::ImmediatelyDropped.into())
+		}
+
+		Ok(match imported {
+			base::Imported::Ready { hash, removed, .. } =>
+				(hash, removed.iter().map(|tx| tx.hash).collect()),
+			base::Imported::Future { hash } => (hash, Vec::new()),
+		})
+	}
+
 	/// Submit single pre-validated transaction to the pool.
-	fn submit_one(&self, tx: ValidatedTransactionFor<B>) -> Result<ExtrinsicHash<B>, B::Error> {
+	fn submit_one(
+		&self,
+		tx: ValidatedTransactionFor<B>,
+	) -> Result<base::Imported<ExtrinsicHash<B>, ExtrinsicFor<B>>, B::Error> {
 		match tx {
 			ValidatedTransaction::Valid(tx) => {
 				if !tx.propagate && !(self.is_validator.0)() {
@@ -223,7 +249,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 
 				let mut listener = self.listener.write();
 				fire_events(&mut *listener, &imported);
-				Ok(*imported.hash())
+				Ok(imported)
 			},
 			ValidatedTransaction::Invalid(hash, err) => {
 				self.rotator.ban(&Instant::now(), std::iter::once(hash));
@@ -237,26 +263,38 @@ impl<B: ChainApi> ValidatedPool<B> {
 	}
 
 	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
-		let status = self.pool.read().status();
+		let (status, sender_limit_exceeded) = {
+			let pool = self.pool.read();
+			(pool.status(), pool.is_sender_limit_exceeded())
+		};
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
+		let sender_limit = self.options.sender.as_ref();
 
 		log::debug!(target: "txpool", "Pool Status: {:?}", status);
-		if ready_limit.is_exceeded(status.ready, status.ready_bytes)
+		if sender_limit_exceeded
+			|| ready_limit.is_exceeded(status.ready, status.ready_bytes)
 			|| future_limit.is_exceeded(status.future, status.future_bytes)
 		{
 			log::debug!(
 				target: "txpool",
-				"Enforcing limits ({}/{}kB ready, {}/{}kB future",
+				"Enforcing limits ({}/{}kB ready, {}/{}kB future, {:?} per sender",
 				ready_limit.count, ready_limit.total_bytes / 1024,
 				future_limit.count, future_limit.total_bytes / 1024,
+				sender_limit.map(|limit| limit.count),
 			);
 
 			// clean up the pool
 			let removed = {
 				let mut pool = self.pool.write();
+				let mut evictions = self.evictions.lock();
 				let removed = pool.enforce_limits(ready_limit, future_limit)
-					.into_iter().map(|x| x.hash).collect::<HashSet<_>>();
+					.into_iter()
+					.map(|(tx, reason)| {
+						*evictions.entry(reason).or_default() += 1;
+						tx.hash
+					})
+					.collect::<HashSet<_>>();
 				// ban all removed transactions
 				self.rotator.ban(&Instant::now(), removed.iter().copied());
 				removed
@@ -277,6 +315,12 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
+	/// Returns the number of transactions evicted when enforcing the limits, by reason, since the
+	/// last call.
+	pub fn take_evictions(&self) -> HashMap<base::EvictionReason, usize> {
+		std::mem::take(&mut *self.evictions.lock())
+	}
+
 	/// Import a single extrinsic and starts to watch their progress in the pool.
 	pub fn submit_and_watch(
 		&self,
@@ -619,6 +663,13 @@ impl<B: ChainApi> ValidatedPool<B> {
 			.collect()
 	}
 
+	/// Returns a Vec of hashes, sources and extrinsics in the future pool.
+	pub fn futures_with_source(&self) -> Vec<(ExtrinsicHash<B>, TransactionSource, ExtrinsicFor<B>)> {
+		self.pool.read().futures()
+			.map(|tx| (tx.hash.clone(), tx.source, tx.data.clone()))
+			.collect()
+	}
+
 	/// Returns pool status.
 	pub fn status(&self) -> PoolStatus {
 		self.pool.read().status()
@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Transaction pool journal.
+//!
+//! Keeps a copy of the pending (ready and future) transactions on disk, so that they can be
+//! re-submitted to the pool after a restart of the node.
+
+use std::{
+	collections::HashMap,
+	fs, io,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sc_transaction_graph::{ChainApi, ExtrinsicFor, ExtrinsicHash, JournalOptions, Pool};
+use sp_runtime::transaction_validity::TransactionSource;
+use wasm_timer::Instant;
+
+/// Version of the on-disk format; journals of another version are ignored.
+const JOURNAL_VERSION: u32 = 1;
+
+/// Minimal time between two writes of the journal when maintaining the pool.
+#[cfg(not(test))]
+const JOURNAL_WRITE_INTERVAL: Duration = Duration::from_secs(30);
+#[cfg(test)]
+const JOURNAL_WRITE_INTERVAL: Duration = Duration::from_millis(0);
+
+/// A transaction as stored in the journal.
+#[derive(Encode, Decode)]
+struct JournalEntry {
+	/// Source the transaction was originally submitted from.
+	source: TransactionSource,
+	/// Unix timestamp (in seconds) of when the transaction was first seen by the pool.
+	first_seen: u64,
+	/// SCALE-encoded extrinsic.
+	extrinsic: Vec<u8>,
+}
+
+/// On-disk journal of the pending transactions of a pool.
+pub struct Journal<Api: ChainApi> {
+	options: JournalOptions,
+	/// When each journaled transaction was first seen, to carry its age across restarts.
+	first_seen: Mutex<HashMap<ExtrinsicHash<Api>, u64>>,
+	last_write: Mutex<Option<Instant>>,
+}
+
+fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+impl<Api: ChainApi> Journal<Api> {
+	/// Create a new journal with the given options.
+	pub fn new(options: JournalOptions) -> Self {
+		Self {
+			options,
+			first_seen: Default::default(),
+			last_write: Default::default(),
+		}
+	}
+
+	/// Read the journaled transactions from disk.
+	///
+	/// Transactions older than the configured maximal age or that cannot be decoded are skipped.
+	/// A missing or corrupted journal yields no transactions.
+	pub fn load(&self, pool: &Pool<Api>) -> Vec<(TransactionSource, ExtrinsicFor<Api>)> {
+		let entries = match self.read() {
+			Ok(entries) => entries,
+			Err(e) => {
+				if e.kind() != io::ErrorKind::NotFound {
+					log::warn!(
+						target: "txpool",
+						"Failed to read transaction journal at {}: {}",
+						self.options.path.display(),
+						e,
+					);
+				}
+				return Vec::new()
+			}
+		};
+
+		let oldest = now().saturating_sub(self.options.max_age.as_secs());
+		let mut first_seen = self.first_seen.lock();
+		entries.into_iter()
+			.filter(|entry| entry.first_seen >= oldest)
+			.filter_map(|entry| {
+				let xt = ExtrinsicFor::<Api>::decode(&mut &entry.extrinsic[..]).ok()?;
+				first_seen.insert(pool.hash_of(&xt), entry.first_seen);
+				Some((entry.source, xt))
+			})
+			.collect()
+	}
+
+	/// Write the pending transactions of the pool to disk, unless the journal was written
+	/// recently.
+	pub fn maybe_write(&self, pool: &Pool<Api>) {
+		let due = self.last_write.lock()
+			.map(|at| at.elapsed() >= JOURNAL_WRITE_INTERVAL)
+			.unwrap_or(true);
+		if due {
+			self.write(pool);
+		}
+	}
+
+	/// Write the pending transactions of the pool to disk.
+	///
+	/// Ready transactions are written first, by priority, and then the future ones. Transactions
+	/// exceeding the configured total size are left out.
+	pub fn write(&self, pool: &Pool<Api>) {
+		let ready = pool.validated_pool().ready()
+			.map(|tx| (tx.hash.clone(), tx.source, tx.data.clone()));
+		let future = pool.validated_pool().futures_with_source().into_iter();
+
+		let now = now();
+		let mut total_bytes = 0;
+		let mut first_seen = self.first_seen.lock();
+		let mut still_pending = HashMap::new();
+		let mut entries = Vec::new();
+		for (hash, source, xt) in ready.chain(future) {
+			let extrinsic = xt.encode();
+			total_bytes += extrinsic.len();
+			if total_bytes > self.options.total_bytes {
+				break
+			}
+
+			let seen = first_seen.get(&hash).copied().unwrap_or(now);
+			still_pending.insert(hash, seen);
+			entries.push(JournalEntry { source, first_seen: seen, extrinsic });
+		}
+		*first_seen = still_pending;
+		drop(first_seen);
+
+		*self.last_write.lock() = Some(Instant::now());
+		match self.store(&entries) {
+			Ok(()) => log::trace!(
+				target: "txpool",
+				"Journaled {} transactions to {}",
+				entries.len(),
+				self.options.path.display(),
+			),
+			Err(e) => log::warn!(
+				target: "txpool",
+				"Failed to write transaction journal at {}: {}",
+				self.options.path.display(),
+				e,
+			),
+		}
+	}
+
+	fn read(&self) -> io::Result<Vec<JournalEntry>> {
+		let data = fs::read(&self.options.path)?;
+		let (version, entries) = <(u32, Vec<JournalEntry>)>::decode(&mut &data[..])
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		if version != JOURNAL_VERSION {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("unsupported journal version {}", version),
+			))
+		}
+		Ok(entries)
+	}
+
+	fn store(&self, entries: &[JournalEntry]) -> io::Result<()> {
+		if let Some(parent) = self.options.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		// Write to a temporary file first, so that a crash never leaves a truncated journal.
+		let tmp_path = self.options.path.with_extension("tmp");
+		fs::write(&tmp_path, (JOURNAL_VERSION, entries).encode())?;
+		fs::rename(&tmp_path, &self.options.path)
+	}
+}
@@ -23,6 +23,7 @@
 #![warn(unused_extern_crates)]
 
 mod api;
+mod journal;
 mod revalidation;
 mod metrics;
 
@@ -81,6 +82,7 @@ pub struct BasicPool<PoolApi, Block>
 	revalidation_queue: Arc<revalidation::RevalidationQueue<PoolApi>>,
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
+	journal: Option<Arc<journal::Journal<PoolApi>>>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -181,6 +183,7 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 				revalidation_strategy: Arc::new(Mutex::new(RevalidationStrategy::Always)),
 				ready_poll: Default::default(),
 				metrics: Default::default(),
+				journal: None,
 			},
 			background_task,
 			notifier,
@@ -189,6 +192,9 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 
 	/// Create new basic transaction pool with provided api and custom
 	/// revalidation type.
+	///
+	/// If journaling is enabled in `options`, the journaled transactions are re-submitted to the
+	/// pool in the background.
 	pub fn with_revalidation_type(
 		options: sc_transaction_graph::Options,
 		is_validator: txpool::IsValidator,
@@ -198,6 +204,7 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 		spawner: impl SpawnNamed,
 		best_block_number: NumberFor<Block>,
 	) -> Self {
+		let journal = options.journal.clone().map(|options| Arc::new(journal::Journal::new(options)));
 		let pool = Arc::new(sc_transaction_graph::Pool::new(options, is_validator, pool_api.clone()));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light => (revalidation::RevalidationQueue::new(pool_api.clone(), pool.clone()), None),
@@ -211,7 +218,7 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 			spawner.spawn("txpool-background", background_task);
 		}
 
-		Self {
+		let basic_pool = Self {
 			api: pool_api,
 			pool,
 			revalidation_queue: Arc::new(revalidation_queue),
@@ -223,7 +230,62 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 			)),
 			ready_poll: Arc::new(Mutex::new(ReadyPoll::new(best_block_number))),
 			metrics: PrometheusMetrics::new(prometheus),
+			journal,
+		};
+
+		if let Some(resubmit_task) = basic_pool.resubmit_journal(best_block_number) {
+			spawner.spawn("txpool-journal", resubmit_task);
 		}
+
+		basic_pool
+	}
+
+	/// Re-submit the transactions kept in the journal, if any, at the given block.
+	fn resubmit_journal(
+		&self,
+		at: NumberFor<Block>,
+	) -> Option<Pin<Box<dyn Future<Output=()> + Send>>> {
+		let transactions = self.journal.as_ref()?.load(&self.pool);
+		if transactions.is_empty() {
+			return None
+		}
+
+		let count = transactions.len();
+		let (mut local, mut in_block, mut external) = (Vec::new(), Vec::new(), Vec::new());
+		for (source, xt) in transactions {
+			match source {
+				TransactionSource::Local => local.push(xt),
+				TransactionSource::InBlock => in_block.push(xt),
+				TransactionSource::External => external.push(xt),
+			}
+		}
+
+		let at = BlockId::Number(at);
+		let submissions = vec![
+			self.submit_at(&at, TransactionSource::Local, local),
+			self.submit_at(&at, TransactionSource::InBlock, in_block),
+			self.submit_at(&at, TransactionSource::External, external),
+		];
+
+		Some(async move {
+			let mut imported = 0;
+			for submission in submissions {
+				match submission.await {
+					Ok(results) => imported += results.into_iter().filter(|r| r.is_ok()).count(),
+					Err(e) => log::debug!(
+						target: "txpool",
+						"Error re-submitting journaled transactions: {:?}",
+						e,
+					),
+				}
+			}
+			log::info!(
+				target: "txpool",
+				"Re-submitted {} of {} journaled transactions",
+				imported,
+				count,
+			);
+		}.boxed())
 	}
 
 	/// Gets shared reference to the underlying pool.
@@ -232,6 +294,18 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 	}
 }
 
+impl<PoolApi, Block> Drop for BasicPool<PoolApi, Block>
+	where
+		Block: BlockT,
+		PoolApi: ChainApi<Block=Block>,
+{
+	fn drop(&mut self) {
+		if let Some(journal) = self.journal.as_ref() {
+			journal.write(&self.pool);
+		}
+	}
+}
+
 impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 	where
 		Block: BlockT,
@@ -272,6 +346,20 @@ impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 		async move { pool.submit_one(&at, source, xt).await }.boxed()
 	}
 
+	fn submit_replacing(
+		&self,
+		at: &BlockId<Self::Block>,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<(TxHash<Self>, Vec<TxHash<Self>>), Self::Error> {
+		let pool = self.pool.clone();
+		let at = *at;
+
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		async move { pool.submit_replacing(&at, source, xt).await }.boxed()
+	}
+
 	fn submit_and_watch(
 		&self,
 		at: &BlockId<Self::Block>,
@@ -622,6 +710,7 @@ impl<PoolApi, Block> MaintainedTransactionPool for BasicPool<PoolApi, Block>
 				let revalidation_queue = self.revalidation_queue.clone();
 				let ready_poll = self.ready_poll.clone();
 				let metrics = self.metrics.clone();
+				let journal = self.journal.clone();
 
 				async move {
 					// We keep track of everything we prune so that later we won't add
@@ -661,6 +750,13 @@ impl<PoolApi, Block> MaintainedTransactionPool for BasicPool<PoolApi, Block>
 						|metrics| metrics.block_transactions_pruned.inc_by(pruned_log.len() as u64)
 					);
 
+					let evictions = pool.validated_pool().take_evictions();
+					metrics.report(|metrics| for (reason, count) in &evictions {
+						metrics.evicted_transactions
+							.with_label_values(&[reason.as_str()])
+							.inc_by(*count as u64)
+					});
+
 					if let (true, Some(tree_route)) = (next_action.resubmit, tree_route) {
 						let mut resubmit_transactions = Vec::new();
 
@@ -737,6 +833,10 @@ impl<PoolApi, Block> MaintainedTransactionPool for BasicPool<PoolApi, Block>
 
 						revalidation_strategy.lock().clear();
 					}
+
+					if let Some(journal) = journal {
+						journal.maybe_write(&pool);
+					}
 				}.boxed()
 			}
 			ChainEvent::Finalized { hash } => {
@@ -1081,3 +1081,72 @@ fn stale_transactions_are_pruned() {
 	assert_eq!(pool.status().future, 0);
 	assert_eq!(pool.status().ready, 0);
 }
+
+fn journaled_pool(api: Arc<TestApi>, path: &std::path::Path, total_bytes: usize) -> BasicPool<TestApi, Block> {
+	let options = txpool::Options {
+		journal: Some(txpool::JournalOptions {
+			path: path.into(),
+			max_age: std::time::Duration::from_secs(3600),
+			total_bytes,
+		}),
+		..Default::default()
+	};
+	BasicPool::with_revalidation_type(
+		options,
+		true.into(),
+		api,
+		None,
+		RevalidationType::Light,
+		sp_core::testing::TaskExecutor::new(),
+		0,
+	)
+}
+
+fn wait_for_status(pool: &BasicPool<TestApi, Block>, ready: usize, future: usize) {
+	for _ in 0..100 {
+		let status = pool.status();
+		if status.ready == ready && status.future == future {
+			return
+		}
+		std::thread::sleep(std::time::Duration::from_millis(20));
+	}
+	panic!("unexpected pool status: {:?}", pool.status());
+}
+
+#[test]
+fn journaled_transactions_are_resubmitted_after_restart() {
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("txpool").join("journal");
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+
+	let pool = journaled_pool(api.clone(), &path, 1024 * 1024);
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Bob, 1))).unwrap();
+	wait_for_status(&pool, 1, 1);
+	drop(pool);
+	assert!(path.exists());
+
+	let pool = journaled_pool(api, &path, 1024 * 1024);
+	wait_for_status(&pool, 1, 1);
+	let ready: Vec<_> = pool.ready().map(|tx| tx.data.transfer().nonce).collect();
+	assert_eq!(ready, vec![209]);
+}
+
+#[test]
+fn journal_respects_size_limit() {
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("journal");
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let size = uxt(Alice, 209).encode().len();
+
+	let pool = journaled_pool(api.clone(), &path, size);
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 210))).unwrap();
+	wait_for_status(&pool, 2, 0);
+	drop(pool);
+
+	let pool = journaled_pool(api, &path, size);
+	wait_for_status(&pool, 1, 0);
+	let ready: Vec<_> = pool.ready().map(|tx| tx.data.transfer().nonce).collect();
+	assert_eq!(ready, vec![209]);
+}
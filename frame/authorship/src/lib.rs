@@ -27,6 +27,7 @@ use frame_support::{
 	inherent::{InherentData, ProvideInherent, InherentIdentifier},
 };
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_runtime::traits::{Header as HeaderT, One, Zero};
 use sp_authorship::{INHERENT_IDENTIFIER, UnclesInherentData, InherentError};
 
@@ -119,7 +120,7 @@ where
 	}
 }
 
-#[derive(Encode, Decode, sp_runtime::RuntimeDebug)]
+#[derive(Encode, Decode, sp_runtime::RuntimeDebug, TypeInfo)]
 #[cfg_attr(any(feature = "std", test), derive(PartialEq))]
 enum UncleEntryItem<BlockNumber, Hash, Author> {
 	InclusionHeight(BlockNumber),
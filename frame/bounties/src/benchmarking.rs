@@ -26,8 +26,8 @@ use frame_system::RawOrigin;
 use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
 use frame_support::traits::OnInitialize;
 
-use crate::Module as Bounties;
-use pallet_treasury::Module as Treasury;
+use crate::Pallet as Bounties;
+use pallet_treasury::Pallet as Treasury;
 
 const SEED: u32 = 0;
 
@@ -36,10 +36,10 @@ fn create_approved_bounties<T: Config>(n: u32) -> Result<(), &'static str> {
 	for i in 0 .. n {
 		let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(i, MAX_BYTES);
 		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
 	}
-	ensure!(BountyApprovals::get().len() == n as usize, "Not all bounty approved");
+	ensure!(BountyApprovals::<T>::get().len() == n as usize, "Not all bounty approved");
 	Ok(())
 }
 
@@ -69,7 +69,7 @@ fn create_bounty<T: Config>() -> Result<(
 	let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
 	let curator_lookup = T::Lookup::unlookup(curator.clone());
 	Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
-	let bounty_id = BountyCount::get() - 1;
+	let bounty_id = BountyCount::<T>::get() - 1;
 	Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
 	Treasury::<T>::on_initialize(T::BlockNumber::zero());
 	Bounties::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup.clone(), fee)?;
@@ -99,7 +99,7 @@ benchmarks! {
 	approve_bounty {
 		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
 		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 	}: _(RawOrigin::Root, bounty_id)
 
 	propose_curator {
@@ -107,7 +107,7 @@ benchmarks! {
 		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
 		let curator_lookup = T::Lookup::unlookup(curator.clone());
 		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
 	}: _(RawOrigin::Root, bounty_id, curator_lookup, fee)
@@ -117,7 +117,7 @@ benchmarks! {
 		setup_pot_account::<T>();
 		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		frame_system::Pallet::<T>::set_block_number(T::BountyUpdatePeriod::get() + 1u32.into());
 		let caller = whitelisted_caller();
 	}: _(RawOrigin::Signed(caller), bounty_id)
@@ -127,7 +127,7 @@ benchmarks! {
 		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
 		let curator_lookup = T::Lookup::unlookup(curator.clone());
 		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
 		Bounties::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
@@ -138,7 +138,7 @@ benchmarks! {
 		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
 
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		let curator = T::Lookup::lookup(curator_lookup)?;
 		let beneficiary = T::Lookup::unlookup(account("beneficiary", 0, SEED));
 	}: _(RawOrigin::Signed(curator), bounty_id, beneficiary)
@@ -148,7 +148,7 @@ benchmarks! {
 		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
 
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		let curator = T::Lookup::lookup(curator_lookup)?;
 
 		let beneficiary_account: T::AccountId = account("beneficiary", 0, SEED);
@@ -167,17 +167,17 @@ benchmarks! {
 		setup_pot_account::<T>();
 		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, 0);
 		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 	}: close_bounty(RawOrigin::Root, bounty_id)
 
 	close_bounty_active {
 		setup_pot_account::<T>();
 		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 	}: close_bounty(RawOrigin::Root, bounty_id)
 	verify {
-		assert_last_event::<T>(RawEvent::BountyCanceled(bounty_id).into())
+		assert_last_event::<T>(Event::BountyCanceled(bounty_id).into())
 	}
 
 	extend_bounty_expiry {
@@ -185,11 +185,11 @@ benchmarks! {
 		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
 
-		let bounty_id = BountyCount::get() - 1;
+		let bounty_id = BountyCount::<T>::get() - 1;
 		let curator = T::Lookup::lookup(curator_lookup)?;
 	}: _(RawOrigin::Signed(curator), bounty_id, Vec::new())
 	verify {
-		assert_last_event::<T>(RawEvent::BountyExtended(bounty_id).into())
+		assert_last_event::<T>(Event::BountyExtended(bounty_id).into())
 	}
 
 	spend_funds {
@@ -212,7 +212,7 @@ benchmarks! {
 	verify {
 		ensure!(budget_remaining < BalanceOf::<T>::max_value(), "Budget not used");
 		ensure!(missed_any == false, "Missed some");
-		assert_last_event::<T>(RawEvent::BountyBecameActive(b - 1).into())
+		assert_last_event::<T>(Event::BountyBecameActive(b - 1).into())
 	}
 }
 
@@ -77,67 +77,41 @@
 mod tests;
 mod benchmarking;
 pub mod weights;
+pub mod migrations;
 
 use sp_std::prelude::*;
 
-use frame_support::{decl_module, decl_storage, decl_event, ensure, decl_error};
+use frame_support::ensure;
 
 use frame_support::traits::{
 	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::{AllowDeath},
 	ReservableCurrency};
 
-use sp_runtime::{Permill, RuntimeDebug, DispatchResult, traits::{
+use sp_runtime::{Permill, RuntimeDebug, traits::{
 	Zero, StaticLookup, AccountIdConversion, Saturating, BadOrigin
 }};
 
-use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::dispatch::{DispatchResult, DispatchResultWithPostInfo};
 use frame_support::traits::{EnsureOrigin};
 
 use frame_support::weights::{Weight};
 
 use codec::{Encode, Decode};
-use frame_system::{self as system, ensure_signed};
+use scale_info::TypeInfo;
+use frame_system::{self as system};
 pub use weights::WeightInfo;
 
+pub use pallet::*;
+
 type BalanceOf<T> = pallet_treasury::BalanceOf<T>;
 
 type PositiveImbalanceOf<T> = pallet_treasury::PositiveImbalanceOf<T>;
 
-pub trait Config: frame_system::Config + pallet_treasury::Config {
-
-	/// The amount held on deposit for placing a bounty proposal.
-	type BountyDepositBase: Get<BalanceOf<Self>>;
-
-	/// The delay period for which a bounty beneficiary need to wait before claim the payout.
-	type BountyDepositPayoutDelay: Get<Self::BlockNumber>;
-
-	/// Bounty duration in blocks.
-	type BountyUpdatePeriod: Get<Self::BlockNumber>;
-
-	/// Percentage of the curator fee that will be reserved upfront as deposit for bounty curator.
-	type BountyCuratorDeposit: Get<Permill>;
-
-	/// Minimum value for a bounty.
-	type BountyValueMinimum: Get<BalanceOf<Self>>;
-
-	/// The amount held on deposit per byte within the tip report reason or bounty description.
-	type DataDepositPerByte: Get<BalanceOf<Self>>;
-
-	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-
-	/// Maximum acceptable reason length.
-	type MaximumReasonLength: Get<u32>;
-
-	/// Weight information for extrinsics in this pallet.
-	type WeightInfo: WeightInfo;
-}
-
 /// An index of a bounty. Just a `u32`.
 pub type BountyIndex = u32;
 
 /// A bounty proposal.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct Bounty<AccountId, Balance, BlockNumber> {
 	/// The account proposing it.
 	proposer: AccountId,
@@ -154,7 +128,7 @@ pub struct Bounty<AccountId, Balance, BlockNumber> {
 }
 
 /// The status of a bounty proposal.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub enum BountyStatus<AccountId, BlockNumber> {
 	/// The bounty is proposed and waiting for approval.
 	Proposed,
@@ -185,55 +159,76 @@ pub enum BountyStatus<AccountId, BlockNumber> {
 	},
 }
 
-// Note :: For backward compatibility reasons,
-// pallet-bounties uses Treasury for storage.
-// This is temporary solution, soon will get replaced with
-// Own storage identifier.
-decl_storage! {
-	trait Store for Module<T: Config> as Treasury {
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_treasury::Config {
+		/// The amount held on deposit for placing a bounty proposal.
+		#[pallet::constant]
+		type BountyDepositBase: Get<BalanceOf<Self>>;
+
+		/// The delay period for which a bounty beneficiary need to wait before claim the payout.
+		#[pallet::constant]
+		type BountyDepositPayoutDelay: Get<Self::BlockNumber>;
 
-		/// Number of bounty proposals that have been made.
-		pub BountyCount get(fn bounty_count): BountyIndex;
+		/// Bounty duration in blocks.
+		#[pallet::constant]
+		type BountyUpdatePeriod: Get<Self::BlockNumber>;
 
-		/// Bounties that have been made.
-		pub Bounties get(fn bounties):
-		map hasher(twox_64_concat) BountyIndex
-		=> Option<Bounty<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+		/// Percentage of the curator fee that will be reserved upfront as deposit for bounty curator.
+		#[pallet::constant]
+		type BountyCuratorDeposit: Get<Permill>;
+
+		/// Minimum value for a bounty.
+		#[pallet::constant]
+		type BountyValueMinimum: Get<BalanceOf<Self>>;
 
-		/// The description of each bounty.
-		pub BountyDescriptions get(fn bounty_descriptions): map hasher(twox_64_concat) BountyIndex => Option<Vec<u8>>;
+		/// The amount held on deposit per byte within the tip report reason or bounty description.
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
 
-		/// Bounty indices that have been approved but not yet funded.
-		pub BountyApprovals get(fn bounty_approvals): Vec<BountyIndex>;
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Maximum acceptable reason length.
+		#[pallet::constant]
+		type MaximumReasonLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
-}
 
-decl_event!(
-	pub enum Event<T>
-	where
-		Balance = BalanceOf<T>,
-		<T as frame_system::Config>::AccountId,
-	{
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
 		/// New bounty proposal. \[index\]
 		BountyProposed(BountyIndex),
 		/// A bounty proposal was rejected; funds were slashed. \[index, bond\]
-		BountyRejected(BountyIndex, Balance),
+		BountyRejected(BountyIndex, BalanceOf<T>),
 		/// A bounty proposal is funded and became active. \[index\]
 		BountyBecameActive(BountyIndex),
 		/// A bounty is awarded to a beneficiary. \[index, beneficiary\]
-		BountyAwarded(BountyIndex, AccountId),
+		BountyAwarded(BountyIndex, T::AccountId),
 		/// A bounty is claimed by beneficiary. \[index, payout, beneficiary\]
-		BountyClaimed(BountyIndex, Balance, AccountId),
+		BountyClaimed(BountyIndex, BalanceOf<T>, T::AccountId),
 		/// A bounty is cancelled. \[index\]
 		BountyCanceled(BountyIndex),
 		/// A bounty expiry is extended. \[index\]
 		BountyExtended(BountyIndex),
 	}
-);
 
-decl_error! {
-	/// Error for the treasury module.
-	pub enum Error for Module<T: Config> {
+	/// Error for the bounties pallet.
+	#[pallet::error]
+	pub enum Error<T> {
 		/// Proposer's balance is too low.
 		InsufficientProposersBalance,
 		/// No proposal or bounty at that index.
@@ -254,38 +249,38 @@ decl_error! {
 		/// The bounties cannot be claimed/closed because it's still in the countdown period.
 		Premature,
 	}
-}
-
-decl_module! {
-	pub struct Module<T: Config>
-		for enum Call
-		where origin: T::Origin
-	{
-		/// The amount held on deposit per byte within bounty description.
-		const DataDepositPerByte: BalanceOf<T> = T::DataDepositPerByte::get();
-
-		/// The amount held on deposit for placing a bounty proposal.
-		const BountyDepositBase: BalanceOf<T> = T::BountyDepositBase::get();
-
-		/// The delay period for which a bounty beneficiary need to wait before claim the payout.
-		const BountyDepositPayoutDelay: T::BlockNumber = T::BountyDepositPayoutDelay::get();
-
-		/// Bounty duration in blocks.
-		const BountyUpdatePeriod: T::BlockNumber = T::BountyUpdatePeriod::get();
-
-		/// Percentage of the curator fee that will be reserved upfront as deposit for bounty curator.
-		const BountyCuratorDeposit: Permill = T::BountyCuratorDeposit::get();
-
-		/// Minimum value for a bounty.
-		const BountyValueMinimum: BalanceOf<T> = T::BountyValueMinimum::get();
-
-		/// Maximum acceptable reason length.
-		const MaximumReasonLength: u32 = T::MaximumReasonLength::get();
-
-		type Error = Error<T>;
-
-		fn deposit_event() = default;
 
+	/// Number of bounty proposals that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_count)]
+	pub type BountyCount<T> = StorageValue<_, BountyIndex, ValueQuery>;
+
+	/// Bounties that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn bounties)]
+	pub type Bounties<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BountyIndex,
+		Bounty<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		OptionQuery,
+	>;
+
+	/// The description of each bounty.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_descriptions)]
+	pub type BountyDescriptions<T> = StorageMap<_, Twox64Concat, BountyIndex, Vec<u8>, OptionQuery>;
+
+	/// Bounty indices that have been approved but not yet funded.
+	#[pallet::storage]
+	#[pallet::getter(fn bounty_approvals)]
+	pub type BountyApprovals<T> = StorageValue<_, Vec<BountyIndex>, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
 		/// Propose a new bounty.
 		///
 		/// The dispatch origin for this call must be _Signed_.
@@ -298,14 +293,15 @@ decl_module! {
 		/// - `fee`: The curator fee.
 		/// - `value`: The total payment amount of this bounty, curator fee included.
 		/// - `description`: The description of this bounty.
-		#[weight = <T as Config>::WeightInfo::propose_bounty(description.len() as u32)]
-		fn propose_bounty(
-			origin,
-			#[compact] value: BalanceOf<T>,
+		#[pallet::weight(<T as Config>::WeightInfo::propose_bounty(description.len() as u32))]
+		pub fn propose_bounty(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
 			description: Vec<u8>,
-		) {
+		) -> DispatchResult {
 			let proposer = ensure_signed(origin)?;
 			Self::create_bounty(proposer, description, value)?;
+			Ok(())
 		}
 
 		/// Approve a bounty proposal. At a later time, the bounty will be funded and become active
@@ -316,8 +312,8 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::approve_bounty()]
-		fn approve_bounty(origin, #[compact] bounty_id: BountyIndex) {
+		#[pallet::weight(<T as Config>::WeightInfo::approve_bounty())]
+		pub fn approve_bounty(origin: OriginFor<T>, #[pallet::compact] bounty_id: BountyIndex) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
@@ -326,10 +322,11 @@ decl_module! {
 
 				bounty.status = BountyStatus::Approved;
 
-				BountyApprovals::append(bounty_id);
+				BountyApprovals::<T>::append(bounty_id);
 
 				Ok(())
 			})?;
+			Ok(())
 		}
 
 		/// Assign a curator to a funded bounty.
@@ -339,13 +336,13 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::propose_curator()]
-		fn propose_curator(
-			origin,
-			#[compact] bounty_id: BountyIndex,
+		#[pallet::weight(<T as Config>::WeightInfo::propose_curator())]
+		pub fn propose_curator(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
 			curator: <T::Lookup as StaticLookup>::Source,
-			#[compact] fee: BalanceOf<T>,
-		) {
+			#[pallet::compact] fee: BalanceOf<T>,
+		) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
 			let curator = T::Lookup::lookup(curator)?;
@@ -364,6 +361,7 @@ decl_module! {
 
 				Ok(())
 			})?;
+			Ok(())
 		}
 
 		/// Unassign curator from a bounty.
@@ -384,11 +382,11 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::unassign_curator()]
-		fn unassign_curator(
-			origin,
-			#[compact] bounty_id: BountyIndex,
-		) {
+		#[pallet::weight(<T as Config>::WeightInfo::unassign_curator())]
+		pub fn unassign_curator(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
 			let maybe_sender = ensure_signed(origin.clone())
 				.map(Some)
 				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
@@ -455,6 +453,7 @@ decl_module! {
 				bounty.status = BountyStatus::Funded;
 				Ok(())
 			})?;
+			Ok(())
 		}
 
 		/// Accept the curator role for a bounty.
@@ -465,8 +464,8 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::accept_curator()]
-		fn accept_curator(origin, #[compact] bounty_id: BountyIndex) {
+		#[pallet::weight(<T as Config>::WeightInfo::accept_curator())]
+		pub fn accept_curator(origin: OriginFor<T>, #[pallet::compact] bounty_id: BountyIndex) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
@@ -488,6 +487,7 @@ decl_module! {
 					_ => Err(Error::<T>::UnexpectedStatus.into()),
 				}
 			})?;
+			Ok(())
 		}
 
 		/// Award bounty to a beneficiary account. The beneficiary will be able to claim the funds after a delay.
@@ -500,8 +500,8 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::award_bounty()]
-		fn award_bounty(origin, #[compact] bounty_id: BountyIndex, beneficiary: <T::Lookup as StaticLookup>::Source) {
+		#[pallet::weight(<T as Config>::WeightInfo::award_bounty())]
+		pub fn award_bounty(origin: OriginFor<T>, #[pallet::compact] bounty_id: BountyIndex, beneficiary: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
 
@@ -526,6 +526,7 @@ decl_module! {
 			})?;
 
 			Self::deposit_event(Event::<T>::BountyAwarded(bounty_id, beneficiary));
+			Ok(())
 		}
 
 		/// Claim the payout from an awarded bounty after payout delay.
@@ -537,8 +538,8 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::claim_bounty()]
-		fn claim_bounty(origin, #[compact] bounty_id: BountyIndex) {
+		#[pallet::weight(<T as Config>::WeightInfo::claim_bounty())]
+		pub fn claim_bounty(origin: OriginFor<T>, #[pallet::compact] bounty_id: BountyIndex) -> DispatchResult {
 			let _ = ensure_signed(origin)?; // anyone can trigger claim
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
@@ -558,7 +559,7 @@ decl_module! {
 
 					*maybe_bounty = None;
 
-					BountyDescriptions::remove(bounty_id);
+					BountyDescriptions::<T>::remove(bounty_id);
 
 					Self::deposit_event(Event::<T>::BountyClaimed(bounty_id, payout, beneficiary));
 					Ok(())
@@ -566,6 +567,7 @@ decl_module! {
 					Err(Error::<T>::UnexpectedStatus.into())
 				}
 			})?;
+			Ok(())
 		}
 
 		/// Cancel a proposed or active bounty. All the funds will be sent to treasury and
@@ -578,8 +580,8 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::close_bounty_proposed().max(<T as Config>::WeightInfo::close_bounty_active())]
-		fn close_bounty(origin, #[compact] bounty_id: BountyIndex) -> DispatchResultWithPostInfo {
+		#[pallet::weight(<T as Config>::WeightInfo::close_bounty_proposed().max(<T as Config>::WeightInfo::close_bounty_active()))]
+		pub fn close_bounty(origin: OriginFor<T>, #[pallet::compact] bounty_id: BountyIndex) -> DispatchResultWithPostInfo {
 			T::RejectOrigin::ensure_origin(origin)?;
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResultWithPostInfo {
@@ -588,7 +590,7 @@ decl_module! {
 				match &bounty.status {
 					BountyStatus::Proposed => {
 						// The reject origin would like to cancel a proposed bounty.
-						BountyDescriptions::remove(bounty_id);
+						BountyDescriptions::<T>::remove(bounty_id);
 						let value = bounty.bond;
 						let imbalance = T::Currency::slash_reserved(&bounty.proposer, value).0;
 						T::OnSlash::on_unbalanced(imbalance);
@@ -624,7 +626,7 @@ decl_module! {
 
 				let bounty_account = Self::bounty_account_id(bounty_id);
 
-				BountyDescriptions::remove(bounty_id);
+				BountyDescriptions::<T>::remove(bounty_id);
 
 				let balance = T::Currency::free_balance(&bounty_account);
 				let res = T::Currency::transfer(&bounty_account, &Self::account_id(), balance, AllowDeath); // should not fail
@@ -646,8 +648,8 @@ decl_module! {
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::extend_bounty_expiry()]
-		fn extend_bounty_expiry(origin, #[compact] bounty_id: BountyIndex, _remark: Vec<u8>) {
+		#[pallet::weight(<T as Config>::WeightInfo::extend_bounty_expiry())]
+		pub fn extend_bounty_expiry(origin: OriginFor<T>, #[pallet::compact] bounty_id: BountyIndex, _remark: Vec<u8>) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
@@ -665,11 +667,12 @@ decl_module! {
 			})?;
 
 			Self::deposit_event(Event::<T>::BountyExtended(bounty_id));
+			Ok(())
 		}
 	}
 }
 
-impl<T: Config> Module<T> {
+impl<T: Config> Pallet<T> {
 	// Add public immutables and private mutables.
 
 	/// The account ID of the treasury pot.
@@ -703,7 +706,7 @@ impl<T: Config> Module<T> {
 		T::Currency::reserve(&proposer, bond)
 			.map_err(|_| Error::<T>::InsufficientProposersBalance)?;
 
-		BountyCount::put(index + 1);
+		BountyCount::<T>::put(index + 1);
 
 		let bounty = Bounty {
 			proposer,
@@ -715,23 +718,23 @@ impl<T: Config> Module<T> {
 		};
 
 		Bounties::<T>::insert(index, &bounty);
-		BountyDescriptions::insert(index, description);
+		BountyDescriptions::<T>::insert(index, description);
 
-		Self::deposit_event(RawEvent::BountyProposed(index));
+		Self::deposit_event(Event::BountyProposed(index));
 
 		Ok(())
 	}
 
 }
 
-impl<T: Config> pallet_treasury::SpendFunds<T> for Module<T> {
+impl<T: Config> pallet_treasury::SpendFunds<T> for Pallet<T> {
 	fn spend_funds(
 		budget_remaining: &mut BalanceOf<T>,
 		imbalance: &mut PositiveImbalanceOf<T>,
 		total_weight: &mut Weight,
 		missed_any: &mut bool
 	) {
-		let bounties_len = BountyApprovals::mutate(|v| {
+		let bounties_len = BountyApprovals::<T>::mutate(|v| {
 			let bounties_approval_len = v.len() as u32;
 			v.retain(|&index| {
 				Bounties::<T>::mutate(index, |bounty| {
@@ -749,7 +752,7 @@ impl<T: Config> pallet_treasury::SpendFunds<T> for Module<T> {
 							// fund the bounty account
 							imbalance.subsume(T::Currency::deposit_creating(&Self::bounty_account_id(index), bounty.value));
 
-							Self::deposit_event(RawEvent::BountyBecameActive(index));
+							Self::deposit_event(Event::BountyBecameActive(index));
 							false
 						} else {
 							*missed_any = true;
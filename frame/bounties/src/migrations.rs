@@ -0,0 +1,60 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migrations for the bounties pallet.
+
+use frame_support::{
+	storage::{migration::move_storage_from_pallet, unhashed},
+	traits::{Get, PalletInfoAccess},
+	weights::Weight,
+	StorageHasher, Twox128,
+};
+
+/// The prefix under which the bounties storage was kept before the pallet got its own prefix.
+pub const OLD_PREFIX: &[u8] = b"Treasury";
+
+/// The storage items owned by this pallet.
+const STORAGE_ITEMS: &[&[u8]] = &[b"BountyCount", b"Bounties", b"BountyDescriptions", b"BountyApprovals"];
+
+/// Move the storage items of this pallet from the `Treasury` prefix to the prefix of the pallet,
+/// that is the name given to it in `construct_runtime`.
+///
+/// The storage of the treasury pallet itself is left untouched. Nothing is moved if the pallet is
+/// named `Treasury` in the runtime or if the items were already moved, so this can be called on
+/// every runtime upgrade.
+pub fn migrate_to_pallet_prefix<T: crate::Config>() -> Weight {
+	let new_prefix = <crate::Pallet<T> as PalletInfoAccess>::name();
+	if new_prefix.as_bytes() == OLD_PREFIX {
+		return 0;
+	}
+
+	let mut moved = false;
+	for item in STORAGE_ITEMS {
+		// A storage value is stored at the prefix of the item, a map under it.
+		let prefix = [Twox128::hash(OLD_PREFIX), Twox128::hash(item)].concat();
+		if unhashed::exists(&prefix) || unhashed::contains_prefixed_key(&prefix) {
+			move_storage_from_pallet(item, OLD_PREFIX, new_prefix.as_bytes());
+			moved = true;
+		}
+	}
+
+	if moved {
+		<T as frame_system::Config>::BlockWeights::get().max_block
+	} else {
+		T::DbWeight::get().reads(STORAGE_ITEMS.len() as Weight)
+	}
+}
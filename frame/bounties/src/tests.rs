@@ -21,10 +21,11 @@
 
 use crate as pallet_bounties;
 use super::*;
+use super::Event as BountiesEvent;
 use std::cell::RefCell;
 
 use frame_support::{
-	assert_noop, assert_ok, parameter_types, weights::Weight, traits::OnInitialize,
+	assert_noop, assert_ok, parameter_types, weights::Weight, traits::{GenesisBuild, OnInitialize},
 	PalletId
 };
 
@@ -146,7 +147,7 @@ impl Config for Test {
 	type WeightInfo = ();
 }
 
-type TreasuryError = pallet_treasury::Error::<Test, pallet_treasury::DefaultInstance>;
+type TreasuryError = pallet_treasury::Error::<Test>;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
@@ -154,11 +155,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		// Total issuance will be 200 with treasury account initialized at ED.
 		balances: vec![(0, 100), (1, 98), (2, 1)],
 	}.assimilate_storage(&mut t).unwrap();
-	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test, _>(&mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
 	t.into()
 }
 
-fn last_event() -> RawEvent<u64, u128> {
+fn last_event() -> BountiesEvent<Test> {
 	System::events().into_iter().map(|r| r.event)
 		.filter_map(|e| {
 			if let Event::pallet_bounties(inner) = e { Some(inner) } else { None }
@@ -268,7 +269,7 @@ fn reject_already_rejected_spend_proposal_fails() {
 fn reject_non_existent_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(Treasury::reject_proposal(Origin::root(), 0),
-		pallet_treasury::Error::<Test, pallet_treasury::DefaultInstance>::InvalidIndex);
+		pallet_treasury::Error::<Test>::InvalidIndex);
 	});
 }
 
@@ -392,7 +393,7 @@ fn propose_bounty_works() {
 
 		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"1234567890".to_vec()));
 
-		assert_eq!(last_event(), RawEvent::BountyProposed(0));
+		assert_eq!(last_event(), BountiesEvent::BountyProposed(0));
 
 		let deposit: u64 = 85 + 5;
 		assert_eq!(Balances::reserved_balance(0), deposit);
@@ -451,7 +452,7 @@ fn close_bounty_works() {
 
 		let deposit: u64 = 80 + 5;
 
-		assert_eq!(last_event(), RawEvent::BountyRejected(0, deposit));
+		assert_eq!(last_event(), BountiesEvent::BountyRejected(0, deposit));
 
 		assert_eq!(Balances::reserved_balance(0), 0);
 		assert_eq!(Balances::free_balance(0), 100 - deposit);
@@ -660,7 +661,7 @@ fn award_and_claim_bounty_works() {
 
 		assert_ok!(Bounties::claim_bounty(Origin::signed(1), 0));
 
-		assert_eq!(last_event(), RawEvent::BountyClaimed(0, 56, 3));
+		assert_eq!(last_event(), BountiesEvent::BountyClaimed(0, 56, 3));
 
 		assert_eq!(Balances::free_balance(4), 14); // initial 10 + fee 4
 
@@ -699,7 +700,7 @@ fn claim_handles_high_fee() {
 
 		assert_ok!(Bounties::claim_bounty(Origin::signed(1), 0));
 
-		assert_eq!(last_event(), RawEvent::BountyClaimed(0, 0, 3));
+		assert_eq!(last_event(), BountiesEvent::BountyClaimed(0, 0, 3));
 
 		assert_eq!(Balances::free_balance(4), 70); // 30 + 50 - 10
 		assert_eq!(Balances::free_balance(3), 0);
@@ -775,7 +776,7 @@ fn award_and_cancel() {
 		assert_ok!(Bounties::unassign_curator(Origin::root(), 0));
 		assert_ok!(Bounties::close_bounty(Origin::root(), 0));
 
-		assert_eq!(last_event(), RawEvent::BountyCanceled(0));
+		assert_eq!(last_event(), BountiesEvent::BountyCanceled(0));
 
 		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
 
@@ -897,7 +898,7 @@ fn genesis_funding_works() {
 		// Total issuance will be 200 with treasury account initialized with 100.
 		balances: vec![(0, 100), (Treasury::account_id(), initial_funding)],
 	}.assimilate_storage(&mut t).unwrap();
-	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test, _>(&mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
 	let mut t: sp_io::TestExternalities = t.into();
 
 	t.execute_with(|| {
@@ -905,3 +906,22 @@ fn genesis_funding_works() {
 		assert_eq!(Treasury::pot(), initial_funding - Balances::minimum_balance());
 	});
 }
+
+#[test]
+fn migrate_to_pallet_prefix_works() {
+	use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+	new_test_ext().execute_with(|| {
+		put_storage_value(b"Treasury", b"BountyCount", &[], 10u32);
+		put_storage_value(b"Treasury", b"BountyApprovals", &[], vec![3u32]);
+		put_storage_value(b"Treasury", b"ProposalCount", &[], 2u32);
+
+		crate::migrations::migrate_to_pallet_prefix::<Test>();
+
+		assert_eq!(Bounties::bounty_count(), 10);
+		assert_eq!(Bounties::bounty_approvals(), vec![3]);
+		assert_eq!(get_storage_value::<u32>(b"Treasury", b"BountyCount", &[]), None);
+		// treasury storage is left where it is.
+		assert_eq!(Treasury::proposal_count(), 2);
+	});
+}
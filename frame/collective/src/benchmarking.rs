@@ -21,7 +21,7 @@ use super::*;
 
 use frame_system::RawOrigin as SystemOrigin;
 use frame_benchmarking::{
-	benchmarks_instance,
+	benchmarks_instance_pallet,
 	account,
 	whitelisted_caller,
 	impl_benchmark_test_suite,
@@ -31,17 +31,17 @@ use sp_std::mem::size_of;
 
 use frame_system::Call as SystemCall;
 use frame_system::Pallet as System;
-use crate::Module as Collective;
+use crate::Pallet as Collective;
 
 const SEED: u32 = 0;
 
 const MAX_BYTES: u32 = 1_024;
 
-fn assert_last_event<T: Config<I>, I: Instance>(generic_event: <T as Config<I>>::Event) {
+fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
-benchmarks_instance! {
+benchmarks_instance_pallet! {
 	set_members {
 		let m in 1 .. T::MaxMembers::get();
 		let n in 1 .. T::MaxMembers::get();
@@ -133,7 +133,7 @@ benchmarks_instance! {
 		let proposal_hash = T::Hashing::hash_of(&proposal);
 		// Note that execution fails due to mis-matched origin
 		assert_last_event::<T, I>(
-			RawEvent::MemberExecuted(proposal_hash, Err(DispatchError::BadOrigin)).into()
+			Event::MemberExecuted(proposal_hash, Err(DispatchError::BadOrigin)).into()
 		);
 	}
 
@@ -164,7 +164,7 @@ benchmarks_instance! {
 		let proposal_hash = T::Hashing::hash_of(&proposal);
 		// Note that execution fails due to mis-matched origin
 		assert_last_event::<T, I>(
-			RawEvent::Executed(proposal_hash, Err(DispatchError::BadOrigin)).into()
+			Event::Executed(proposal_hash, Err(DispatchError::BadOrigin)).into()
 		);
 	}
 
@@ -208,7 +208,7 @@ benchmarks_instance! {
 		// New proposal is recorded
 		assert_eq!(Collective::<T, _>::proposals().len(), p as usize);
 		let proposal_hash = T::Hashing::hash_of(&proposal);
-		assert_last_event::<T, I>(RawEvent::Proposed(caller, p - 1, proposal_hash, threshold).into());
+		assert_last_event::<T, I>(Event::Proposed(caller, p - 1, proposal_hash, threshold).into());
 	}
 
 	vote {
@@ -364,7 +364,7 @@ benchmarks_instance! {
 	verify {
 		// The last proposal is removed.
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Disapproved(last_hash).into());
+		assert_last_event::<T, I>(Event::Disapproved(last_hash).into());
 	}
 
 	close_early_approved {
@@ -445,7 +445,7 @@ benchmarks_instance! {
 	verify {
 		// The last proposal is removed.
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Executed(last_hash, Err(DispatchError::BadOrigin)).into());
+		assert_last_event::<T, I>(Event::Executed(last_hash, Err(DispatchError::BadOrigin)).into());
 	}
 
 	close_disapproved {
@@ -517,7 +517,7 @@ benchmarks_instance! {
 	}: close(SystemOrigin::Signed(caller), last_hash, index, Weight::max_value(), bytes_in_storage)
 	verify {
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Disapproved(last_hash).into());
+		assert_last_event::<T, I>(Event::Disapproved(last_hash).into());
 	}
 
 	close_approved {
@@ -581,7 +581,7 @@ benchmarks_instance! {
 	}: close(SystemOrigin::Signed(caller), last_hash, p - 1, Weight::max_value(), bytes_in_storage)
 	verify {
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Executed(last_hash, Err(DispatchError::BadOrigin)).into());
+		assert_last_event::<T, I>(Event::Executed(last_hash, Err(DispatchError::BadOrigin)).into());
 	}
 
 	disapprove_proposal {
@@ -629,7 +629,7 @@ benchmarks_instance! {
 	}: _(SystemOrigin::Root, last_hash)
 	verify {
 		assert_eq!(Collective::<T, _>::proposals().len(), (p - 1) as usize);
-		assert_last_event::<T, I>(RawEvent::Disapproved(last_hash).into());
+		assert_last_event::<T, I>(Event::Disapproved(last_hash).into());
 	}
 }
 
@@ -21,8 +21,11 @@
 
 use bitflags::bitflags;
 use codec::{Decode, Encode};
-use sp_core::Bytes;
-use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_core::{Bytes, H256};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchError, RuntimeDebug,
+};
 use sp_std::prelude::*;
 
 #[cfg(feature = "std")]
@@ -34,9 +37,14 @@ use serde::{Serialize, Deserialize};
 #[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct ContractResult<T> {
+pub struct ContractResult<R, Balance> {
 	/// How much gas was consumed during execution.
 	pub gas_consumed: u64,
+	/// How much balance was deposited and reserved during execution in order to pay for storage.
+	///
+	/// The storage deposit is never actually charged from the caller in case of [`Self::result`]
+	/// is `Err`. This is because on error all storage changes are rolled back.
+	pub storage_deposit: StorageDeposit<Balance>,
 	/// An optional debug message. This message is only filled when explicitly requested
 	/// by the code that calls into the contract. Otherwise it is empty.
 	///
@@ -54,42 +62,109 @@ pub struct ContractResult<T> {
 	#[cfg_attr(feature = "std", serde(with = "as_string"))]
 	pub debug_message: Vec<u8>,
 	/// The execution result of the wasm code.
-	pub result: T,
+	pub result: R,
 }
 
 /// Result type of a `bare_call` call.
-pub type ContractExecResult = ContractResult<Result<ExecReturnValue, DispatchError>>;
+pub type ContractExecResult<Balance> =
+	ContractResult<Result<ExecReturnValue, DispatchError>, Balance>;
+
+/// Result type of a `bare_call_traced` call.
+///
+/// It contains the same information as a [`ContractExecResult`] and additionally
+/// the trace of all calls and instantiations performed during execution.
+#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractTracedExecResult<AccountId, Balance> {
+	/// The result of the call as returned by `bare_call`.
+	#[cfg_attr(feature = "std", serde(flatten))]
+	pub exec: ContractExecResult<Balance>,
+	/// The trace of the called contract.
+	///
+	/// It is `None` if no trace was requested or the execution failed before entering the
+	/// called contract. This happens when the called account is not a contract, for example.
+	#[cfg_attr(feature = "std", serde(skip_serializing_if = "Option::is_none"))]
+	pub trace: Option<CallTrace<AccountId, Balance>>,
+}
 
 /// Result type of a `bare_instantiate` call.
-pub type ContractInstantiateResult<AccountId, BlockNumber> =
-	ContractResult<Result<InstantiateReturnValue<AccountId, BlockNumber>, DispatchError>>;
+pub type ContractInstantiateResult<AccountId, Balance> =
+	ContractResult<Result<InstantiateReturnValue<AccountId>, DispatchError>, Balance>;
 
 /// Result type of a `get_storage` call.
 pub type GetStorageResult = Result<Option<Vec<u8>>, ContractAccessError>;
 
-/// Result type of a `rent_projection` call.
-pub type RentProjectionResult<BlockNumber> =
-	Result<RentProjection<BlockNumber>, ContractAccessError>;
-
 /// The possible errors that can happen querying the storage of a contract.
 #[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
 pub enum ContractAccessError {
 	/// The given address doesn't point to a contract.
 	DoesntExist,
-	/// The specified contract is a tombstone and thus cannot have any storage.
-	IsTombstone,
 }
 
-#[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+/// The amount of balance that was either charged or refunded in order to pay for storage.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, Clone, Copy)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub enum RentProjection<BlockNumber> {
-	/// Eviction is projected to happen at the specified block number.
-	EvictionAt(BlockNumber),
-	/// No eviction is scheduled.
+pub enum StorageDeposit<Balance> {
+	/// The transaction reduced storage consumption.
+	///
+	/// This means that the specified amount of balance was transferred from the involved
+	/// contracts to the call origin.
+	Refund(Balance),
+	/// The transaction increased overall storage usage.
+	///
+	/// This means that the specified amount of balance was transferred from the call origin
+	/// to the contracts involved.
+	Charge(Balance),
+}
+
+impl<Balance: Zero> Default for StorageDeposit<Balance> {
+	fn default() -> Self {
+		Self::Charge(Zero::zero())
+	}
+}
+
+impl<Balance: Zero + Copy> StorageDeposit<Balance> {
+	/// Returns how much balance is charged or `0` in case of a refund.
+	pub fn charge_or_zero(&self) -> Balance {
+		match self {
+			Self::Charge(amount) => *amount,
+			Self::Refund(_) => Zero::zero(),
+		}
+	}
+
+	/// Returns `true` if neither a charge nor a refund took place.
+	pub fn is_zero(&self) -> bool {
+		match self {
+			Self::Charge(amount) => amount.is_zero(),
+			Self::Refund(amount) => amount.is_zero(),
+		}
+	}
+}
+
+impl<Balance> StorageDeposit<Balance>
+where
+	Balance: Saturating + Ord + Copy,
+{
+	/// Combines `self` with `rhs`.
 	///
-	/// E.g. Contract accumulated enough funds to offset the rent storage costs.
-	NoEviction,
+	/// A charge and a refund of the same amount cancel each other out. All arithmetic
+	/// saturates.
+	pub fn saturating_add(&self, rhs: &Self) -> Self {
+		use StorageDeposit::*;
+		match (self, rhs) {
+			(Charge(lhs), Charge(rhs)) => Charge(lhs.saturating_add(*rhs)),
+			(Refund(lhs), Refund(rhs)) => Refund(lhs.saturating_add(*rhs)),
+			(Charge(lhs), Refund(rhs)) | (Refund(rhs), Charge(lhs)) => {
+				if lhs >= rhs {
+					Charge(lhs.saturating_sub(*rhs))
+				} else {
+					Refund(rhs.saturating_sub(*lhs))
+				}
+			},
+		}
+	}
 }
 
 bitflags! {
@@ -104,7 +179,7 @@ bitflags! {
 }
 
 /// Output of a contract call or instantiation which ran to completion.
-#[derive(PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct ExecReturnValue {
@@ -121,23 +196,73 @@ impl ExecReturnValue {
 	}
 }
 
+/// The way in which a contract was entered.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CallKind {
+	/// The contract was called.
+	Call,
+	/// The code of the contract was executed on behalf of the caller by `seal_delegate_call`.
+	DelegateCall,
+	/// The contract was created and its constructor was run.
+	Instantiate,
+}
+
+/// An access to the storage of a contract that was recorded while tracing.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum StorageAccess {
+	/// The value stored under `key` was read. `None` if no value was stored.
+	Read { key: H256, value: Option<Bytes> },
+	/// The value under `key` was overwritten. `None` if the value was removed.
+	Write { key: H256, value: Option<Bytes> },
+}
+
+/// The trace of a single call or instantiation together with all nested ones.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[codec(encode_bound(AccountId: Encode, Balance: Encode))]
+#[codec(decode_bound(AccountId: Decode, Balance: Decode))]
+pub struct CallTrace<AccountId, Balance> {
+	/// How the contract was entered.
+	pub kind: CallKind,
+	/// The account that performed the call. For a delegate call this is the caller of
+	/// the delegating contract.
+	pub caller: AccountId,
+	/// The contract whose code was executed. For a delegate call this is the delegating
+	/// contract because the code runs in its context.
+	pub callee: AccountId,
+	/// The balance transferred along with the call.
+	pub value: Balance,
+	/// The input data passed to the contract.
+	pub input: Bytes,
+	/// The gas consumed by this call including all nested calls.
+	pub gas_consumed: u64,
+	/// The storage accesses performed by this call in the order they happened.
+	///
+	/// Accesses of nested calls are only contained in their own traces.
+	pub storage: Vec<StorageAccess>,
+	/// The result of the call.
+	///
+	/// A contract reverted when the returned flags contain `REVERT`. The returned data
+	/// then usually describes the reason.
+	pub result: Result<ExecReturnValue, DispatchError>,
+	/// The calls and instantiations performed by this call in the order they happened.
+	pub calls: Vec<CallTrace<AccountId, Balance>>,
+}
+
 /// The result of a successful contract instantiation.
 #[derive(PartialEq, Eq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
-pub struct InstantiateReturnValue<AccountId, BlockNumber> {
+pub struct InstantiateReturnValue<AccountId> {
 	/// The output of the called constructor.
 	pub result: ExecReturnValue,
 	/// The account id of the new contract.
 	pub account_id: AccountId,
-	/// Information about when and if the new project will be evicted.
-	///
-	/// # Note
-	///
-	/// `None` if `bare_instantiate` was called with
-	/// `compute_projection` set to false. From the perspective of an RPC this means that
-	/// the runtime API did not request this value and this feature is therefore unsupported.
-	pub rent_projection: Option<RentProjection<BlockNumber>>,
 }
 
 /// Reference to an existing code hash or a new wasm module.
@@ -151,6 +276,21 @@ pub enum Code<Hash> {
 	Existing(Hash),
 }
 
+/// An event emitted by a contract through `seal_deposit_event`.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ContractEvent<AccountId, Hash> {
+	/// The index of the event within all events deposited in its block.
+	pub event_index: u32,
+	/// The contract that emitted the event.
+	pub contract: AccountId,
+	/// The topics the contract supplied when emitting the event.
+	pub topics: Vec<Hash>,
+	/// The data the contract supplied when emitting the event.
+	pub data: Bytes,
+}
+
 #[cfg(feature = "std")]
 mod as_string {
 	use super::*;
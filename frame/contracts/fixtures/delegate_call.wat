@@ -0,0 +1,73 @@
+;; This calls the code hash passed as input via `seal_delegate_call`. The called code is
+;; expected to write the value 2 to the storage key 1 of this contract.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_get_storage" (func $seal_get_storage (param i32 i32 i32) (result i32)))
+	(import "seal0" "seal_delegate_call" (func $seal_delegate_call (param i32 i32 i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) storage key
+	(data (i32.const 0) "\01")
+
+	;; [32, 64) buffer where the code hash is copied to
+
+	;; [64, 96) buffer where the storage value is copied to
+
+	;; [96, 100) size of the input and output buffers
+	(data (i32.const 96) "\20")
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 32) (i32.const 96))
+		(call $assert (i32.eq (i32.load (i32.const 96)) (i32.const 32)))
+
+		;; The storage is empty before the delegate call.
+		(call $assert
+			(i32.eq
+				(call $seal_get_storage
+					(i32.const 0)	;; Pointer to storage key
+					(i32.const 64)	;; Pointer to output buffer
+					(i32.const 96)	;; Pointer to the size of the output buffer
+				)
+				(i32.const 3)	;; ReturnCode::KeyNotFound
+			)
+		)
+
+		(call $assert
+			(i32.eqz
+				(call $seal_delegate_call
+					(i32.const 0)	;; No flags are set
+					(i32.const 32)	;; Pointer to the code hash
+					(i32.const 32)	;; Length of the code hash
+					(i32.const 0)	;; Pointer to input data buffer
+					(i32.const 0)	;; Length of input data buffer
+					(i32.const 4294967295)	;; u32 max sentinel value: do not copy output
+					(i32.const 0)	;; Length is ignored in this case
+				)
+			)
+		)
+
+		;; The delegated code wrote to our storage.
+		(call $assert
+			(i32.eqz
+				(call $seal_get_storage
+					(i32.const 0)	;; Pointer to storage key
+					(i32.const 64)	;; Pointer to output buffer
+					(i32.const 96)	;; Pointer to the size of the output buffer
+				)
+			)
+		)
+		(call $assert (i32.eq (i32.load (i32.const 96)) (i32.const 1)))
+		(call $assert (i32.eq (i32.load8_u (i32.const 64)) (i32.const 2)))
+	)
+)
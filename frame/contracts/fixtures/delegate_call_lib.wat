@@ -0,0 +1,21 @@
+;; Writes the value 2 to the storage key 1. Used as the target of a delegate call.
+(module
+	(import "seal0" "seal_set_storage" (func $seal_set_storage (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) storage key
+	(data (i32.const 0) "\01")
+
+	;; [32, 33) storage value
+	(data (i32.const 32) "\02")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_set_storage
+			(i32.const 0)	;; Pointer to storage key
+			(i32.const 32)	;; Pointer to value
+			(i32.const 1)	;; Size of value
+		)
+	)
+)
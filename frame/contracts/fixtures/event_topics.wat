@@ -0,0 +1,24 @@
+;; Emits an event with two topics on every call.
+(module
+	(import "seal0" "seal_deposit_event" (func $seal_deposit_event (param i32 i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 65) the encoded topics: a compact length followed by two 32 byte hashes
+	(data (i32.const 0) "\08")
+	(data (i32.const 1) "\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01\01")
+	(data (i32.const 33) "\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02\02")
+
+	;; [65, 69) the event data
+	(data (i32.const 65) "\de\ad\be\ef")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_deposit_event
+			(i32.const 0) ;; topics_ptr
+			(i32.const 65) ;; topics_len
+			(i32.const 65) ;; data_ptr
+			(i32.const 4) ;; data_len
+		)
+	)
+)
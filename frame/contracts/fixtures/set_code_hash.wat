@@ -0,0 +1,34 @@
+;; Replaces the code of this contract with the code hash passed as input.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_set_code_hash" (func $seal_set_code_hash (param i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) buffer where the code hash is copied to
+
+	;; [32, 36) size of the input buffer
+	(data (i32.const 32) "\20")
+
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 0) (i32.const 32))
+		(call $assert
+			(i32.eqz
+				(call $seal_set_code_hash
+					(i32.const 0)	;; Pointer to the code hash
+					(i32.load (i32.const 32))	;; Length of the code hash
+				)
+			)
+		)
+	)
+)
@@ -26,11 +26,16 @@
 use codec::Codec;
 use sp_std::vec::Vec;
 use pallet_contracts_primitives::{
-	ContractExecResult, GetStorageResult, RentProjectionResult, Code, ContractInstantiateResult,
+	ContractExecResult, GetStorageResult, Code, ContractInstantiateResult, ContractEvent,
+	ContractTracedExecResult,
 };
 
 sp_api::decl_runtime_apis! {
 	/// The API to interact with contracts without using executive.
+	///
+	/// Version 2 added the storage deposit limit to `call` and `instantiate`, version 3 added
+	/// `contract_events` and version 4 added `call_traced`.
+	#[api_version(4)]
 	pub trait ContractsApi<AccountId, Balance, BlockNumber, Hash> where
 		AccountId: Codec,
 		Balance: Codec,
@@ -45,8 +50,21 @@ sp_api::decl_runtime_apis! {
 			dest: AccountId,
 			value: Balance,
 			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
 			input_data: Vec<u8>,
-		) -> ContractExecResult;
+		) -> ContractExecResult<Balance>;
+
+		/// Perform a call like [`Self::call`] and record the trace of its execution.
+		///
+		/// See [`pallet_contracts::Pallet::bare_call_traced`].
+		fn call_traced(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
+			input_data: Vec<u8>,
+		) -> ContractTracedExecResult<AccountId, Balance>;
 
 		/// Instantiate a new contract.
 		///
@@ -55,28 +73,25 @@ sp_api::decl_runtime_apis! {
 			origin: AccountId,
 			endowment: Balance,
 			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
 			code: Code<Hash>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
-		) -> ContractInstantiateResult<AccountId, BlockNumber>;
+		) -> ContractInstantiateResult<AccountId, Balance>;
 
 		/// Query a given storage key in a given contract.
 		///
 		/// Returns `Ok(Some(Vec<u8>))` if the storage value exists under the given key in the
 		/// specified account and `Ok(None)` if it doesn't. If the account specified by the address
-		/// doesn't exist, or doesn't have a contract or if the contract is a tombstone, then `Err`
-		/// is returned.
+		/// doesn't exist, or doesn't have a contract then `Err` is returned.
 		fn get_storage(
 			address: AccountId,
 			key: [u8; 32],
 		) -> GetStorageResult;
 
-		/// Returns the projected time a given contract will be able to sustain paying its rent.
-		///
-		/// The returned projection is relevant for the current block, i.e. it is as if the contract
-		/// was accessed at the current block.
+		/// Return all events emitted by contracts in the block this is called at.
 		///
-		/// Returns `Err` if the contract is in a tombstone state or doesn't exist.
-		fn rent_projection(address: AccountId) -> RentProjectionResult<BlockNumber>;
+		/// See [`pallet_contracts::Pallet::contract_events`].
+		fn contract_events() -> Vec<ContractEvent<AccountId, Hash>>;
 	}
 }
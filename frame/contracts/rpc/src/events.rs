@@ -0,0 +1,489 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client side index of the events emitted by contracts.
+//!
+//! `frame_system::EventTopics` only covers the events of the current block. This module
+//! keeps a persistent copy of the contract events of every finalized block in the aux-db
+//! together with lookup entries by emitting contract and by topic. Indexing is opt-in:
+//! a node has to spawn [`index_contract_events`] for the index to be populated.
+//!
+//! Lookup entries map a key to the numbers of the blocks containing matching events.
+//! Block numbers are grouped into buckets of [`BUCKET_SIZE`] so that neither indexing
+//! nor querying a range has to touch entries whose size grows with the chain. Blocks
+//! whose events could not be collected are kept in separate entries, bucketed the same
+//! way, so that queries can tell them apart from blocks without events.
+
+use std::{collections::BTreeSet, marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Decode, Encode};
+use futures::StreamExt;
+use log::{debug, error, warn};
+use pallet_contracts_primitives::ContractEvent;
+use sc_client_api::{AuxStore, BlockchainEvents};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, One, Saturating, UniqueSaturatedInto},
+};
+
+use crate::ContractsRuntimeApi;
+
+const LOG_TARGET: &str = "contracts-event-index";
+
+const INDEXED_RANGE_KEY: &[u8] = b"contracts_event_index_range";
+
+/// Number of consecutive blocks sharing one lookup entry.
+const BUCKET_SIZE: u64 = 4096;
+
+fn block_key<N: Encode>(number: N) -> Vec<u8> {
+	(b"contracts_event_index_block", number).encode()
+}
+
+fn lookup_key(key: &LookupKey, bucket: u64) -> Vec<u8> {
+	(b"contracts_event_index_lookup", key, bucket).encode()
+}
+
+fn unindexed_key(bucket: u64) -> Vec<u8> {
+	(b"contracts_event_index_unindexed", bucket).encode()
+}
+
+fn bucket<N: UniqueSaturatedInto<u64>>(number: N) -> u64 {
+	number.unique_saturated_into() / BUCKET_SIZE
+}
+
+/// What a lookup entry is keyed by.
+#[derive(Encode, PartialEq, Eq, PartialOrd, Ord)]
+enum LookupKey {
+	/// Blocks with any contract event.
+	Any,
+	/// Blocks with an event emitted by the encoded contract address.
+	Contract(Vec<u8>),
+	/// Blocks with an event carrying the encoded topic.
+	Topic(Vec<u8>),
+}
+
+/// A contract event together with the block it was emitted in.
+#[derive(Debug, PartialEq)]
+pub struct IndexedEvent<Block: BlockT, AccountId, Hash> {
+	/// Number of the block containing the event.
+	pub block_number: NumberFor<Block>,
+	/// Hash of the block containing the event.
+	pub block_hash: Block::Hash,
+	/// The event itself.
+	pub event: ContractEvent<AccountId, Hash>,
+}
+
+/// Read and write access to the contract event index stored in the aux-db of `client`.
+pub struct ContractEventIndex<C, Block, AccountId, Hash> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, AccountId, Hash)>,
+}
+
+impl<C, Block, AccountId, Hash> ContractEventIndex<C, Block, AccountId, Hash>
+where
+	C: AuxStore,
+	Block: BlockT,
+	AccountId: Codec + PartialEq,
+	Hash: Codec + PartialEq,
+{
+	/// Create a new handle to the index.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: PhantomData,
+		}
+	}
+
+	/// The first and last block covered by the index or `None` if nothing was indexed yet.
+	pub fn indexed_range(&self) -> ClientResult<Option<(NumberFor<Block>, NumberFor<Block>)>> {
+		self.load_decode(INDEXED_RANGE_KEY)
+	}
+
+	/// Events emitted within the blocks `from..=to` by `contract` carrying all of `topics`.
+	///
+	/// When no `contract` is given events of all contracts are considered. Blocks outside
+	/// of the [`Self::indexed_range`] are ignored. Events are returned in the order they were
+	/// emitted. Collection stops as soon as more than `limit` events matched so that callers
+	/// can detect an overly broad query.
+	///
+	/// Events of blocks that could not be indexed are missing, see [`Self::unindexed`].
+	pub fn events(
+		&self,
+		contract: Option<&AccountId>,
+		topics: &[Hash],
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+		limit: usize,
+	) -> ClientResult<Vec<IndexedEvent<Block, AccountId, Hash>>> {
+		let (from, to) = match self.indexed_range()? {
+			Some((first, last)) => (from.max(first), to.min(last)),
+			None => return Ok(Vec::new()),
+		};
+		if from > to {
+			return Ok(Vec::new());
+		}
+
+		let key = match (contract, topics.first()) {
+			(Some(contract), _) => LookupKey::Contract(contract.encode()),
+			(None, Some(topic)) => LookupKey::Topic(topic.encode()),
+			(None, None) => LookupKey::Any,
+		};
+		let matches = |event: &ContractEvent<AccountId, Hash>| {
+			contract.map_or(true, |contract| event.contract == *contract) &&
+				topics.iter().all(|topic| event.topics.contains(topic))
+		};
+
+		let mut result = Vec::new();
+		for bucket in bucket(from)..=bucket(to) {
+			let numbers: Vec<NumberFor<Block>> = self
+				.load_decode(&lookup_key(&key, bucket))?
+				.unwrap_or_default();
+			for number in numbers.into_iter().filter(|n| *n >= from && *n <= to) {
+				let (block_hash, events): (Block::Hash, Vec<ContractEvent<AccountId, Hash>>) =
+					match self.load_decode(&block_key(number))? {
+						Some(entry) => entry,
+						None => continue,
+					};
+				result.extend(events.into_iter().filter(|e| matches(e)).map(|event| IndexedEvent {
+					block_number: number,
+					block_hash,
+					event,
+				}));
+				if result.len() > limit {
+					return Ok(result);
+				}
+			}
+		}
+		Ok(result)
+	}
+
+	/// The blocks within `from..=to` whose events are not covered by the index.
+	///
+	/// These are the blocks outside of the [`Self::indexed_range`] and the ones whose events
+	/// could not be collected when they were indexed. Consecutive blocks are merged into
+	/// inclusive ranges, in ascending order.
+	pub fn unindexed(
+		&self,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> ClientResult<Vec<(NumberFor<Block>, NumberFor<Block>)>> {
+		fn push<N: Copy + PartialEq + One + Saturating>(ranges: &mut Vec<(N, N)>, start: N, end: N) {
+			match ranges.last_mut() {
+				Some(last) if last.1.saturating_add(One::one()) == start => last.1 = end,
+				_ => ranges.push((start, end)),
+			}
+		}
+
+		let mut ranges = Vec::new();
+		if from > to {
+			return Ok(ranges);
+		}
+		let (first, last) = match self.indexed_range()? {
+			Some(range) => range,
+			None => return Ok(vec![(from, to)]),
+		};
+
+		if from < first {
+			push(&mut ranges, from, to.min(first.saturating_sub(One::one())));
+		}
+		let (start, end) = (from.max(first), to.min(last));
+		if start <= end {
+			for bucket in bucket(start)..=bucket(end) {
+				let numbers: Vec<NumberFor<Block>> = self
+					.load_decode(&unindexed_key(bucket))?
+					.unwrap_or_default();
+				for number in numbers.into_iter().filter(|n| *n >= start && *n <= end) {
+					push(&mut ranges, number, number);
+				}
+			}
+		}
+		if to > last {
+			push(&mut ranges, from.max(last + One::one()), to);
+		}
+		Ok(ranges)
+	}
+
+	/// Record the contract events of the block `number` and extend the indexed range to it.
+	///
+	/// `None` records that the events of the block could not be collected. Blocks need to be
+	/// inserted in ascending order and at most once.
+	pub fn insert_block(
+		&self,
+		number: NumberFor<Block>,
+		hash: Block::Hash,
+		events: Option<Vec<ContractEvent<AccountId, Hash>>>,
+	) -> ClientResult<()> {
+		let first = self.indexed_range()?.map_or(number, |(first, _)| first);
+		let mut inserts = vec![(INDEXED_RANGE_KEY.to_vec(), (first, number).encode())];
+
+		let events = match events {
+			Some(events) => events,
+			None => {
+				let key = unindexed_key(bucket(number));
+				let mut numbers: Vec<NumberFor<Block>> =
+					self.load_decode(&key)?.unwrap_or_default();
+				numbers.push(number);
+				inserts.push((key, numbers.encode()));
+				Vec::new()
+			},
+		};
+
+		if !events.is_empty() {
+			let mut keys = BTreeSet::new();
+			keys.insert(LookupKey::Any);
+			for event in &events {
+				keys.insert(LookupKey::Contract(event.contract.encode()));
+				keys.extend(event.topics.iter().map(|topic| LookupKey::Topic(topic.encode())));
+			}
+			for key in keys {
+				let key = lookup_key(&key, bucket(number));
+				let mut numbers: Vec<NumberFor<Block>> =
+					self.load_decode(&key)?.unwrap_or_default();
+				numbers.push(number);
+				inserts.push((key, numbers.encode()));
+			}
+			inserts.push((block_key(number), (hash, events).encode()));
+		}
+
+		let inserts: Vec<_> = inserts.iter().map(|(k, v)| (&k[..], &v[..])).collect();
+		self.client.insert_aux(&inserts, &[])
+	}
+
+	fn load_decode<T: Decode>(&self, key: &[u8]) -> ClientResult<Option<T>> {
+		match self.client.get_aux(key)? {
+			None => Ok(None),
+			Some(t) => T::decode(&mut &t[..]).map(Some).map_err(|e| {
+				ClientError::Backend(format!("Contract event index is corrupted: {}", e))
+			}),
+		}
+	}
+}
+
+/// Keep the contract event index up to date with the finalized chain.
+///
+/// When started for the first time indexing begins at the currently finalized block.
+/// Afterwards it resumes after the last indexed block. Blocks whose events cannot be
+/// collected, e.g. because their state was pruned in the meantime, are recorded as not
+/// indexed rather than as having no events.
+///
+/// The returned future only resolves when the finality notification stream ends or the
+/// index could not be written.
+pub async fn index_contract_events<C, Block, AccountId, Balance, Hash>(client: Arc<C>)
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block> + AuxStore,
+	C::Api: ContractsRuntimeApi<Block, AccountId, Balance, NumberFor<Block>, Hash>,
+	Block: BlockT,
+	AccountId: Codec + PartialEq,
+	Balance: Codec,
+	Hash: Codec + PartialEq,
+{
+	let index = ContractEventIndex::<_, Block, AccountId, Hash>::new(client.clone());
+	let mut finality_notifications = client.finality_notification_stream();
+
+	let mut next = match index.indexed_range() {
+		Ok(Some((_, last))) => last + One::one(),
+		Ok(None) => client.info().finalized_number,
+		Err(e) => {
+			error!(target: LOG_TARGET, "Failed to read the contract event index: {}", e);
+			return
+		},
+	};
+
+	loop {
+		while next <= client.info().finalized_number {
+			let hash = match client.hash(next) {
+				Ok(Some(hash)) => hash,
+				Ok(None) => break,
+				Err(e) => {
+					error!(target: LOG_TARGET, "Failed to look up finalized block #{}: {}", next, e);
+					return
+				},
+			};
+			let events = block_events::<_, _, AccountId, Balance, Hash>(&*client, next, hash);
+			if let Err(e) = index.insert_block(next, hash, events) {
+				error!(target: LOG_TARGET, "Failed to write the contract event index: {}", e);
+				return
+			}
+			next += One::one();
+		}
+
+		if finality_notifications.next().await.is_none() {
+			return
+		}
+	}
+}
+
+fn block_events<C, Block, AccountId, Balance, Hash>(
+	client: &C,
+	number: NumberFor<Block>,
+	hash: Block::Hash,
+) -> Option<Vec<ContractEvent<AccountId, Hash>>>
+where
+	C: ProvideRuntimeApi<Block>,
+	C::Api: ContractsRuntimeApi<Block, AccountId, Balance, NumberFor<Block>, Hash>,
+	Block: BlockT,
+	AccountId: Codec,
+	Balance: Codec,
+	Hash: Codec,
+{
+	let api = client.runtime_api();
+	let at = BlockId::hash(hash);
+	let supported = api.has_api_with::<
+		dyn ContractsRuntimeApi<Block, AccountId, Balance, NumberFor<Block>, Hash>, _
+	>(&at, |version| version >= 3);
+
+	match supported {
+		Ok(true) => api.contract_events(&at).map_err(|e| {
+			warn!(target: LOG_TARGET, "Failed to collect contract events of block #{}: {}", number, e);
+		}).ok(),
+		Ok(false) => {
+			debug!(target: LOG_TARGET, "Runtime of block #{} cannot list contract events", number);
+			None
+		},
+		Err(e) => {
+			warn!(target: LOG_TARGET, "Failed to query the runtime of block #{}: {}", number, e);
+			None
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper};
+	use std::{collections::HashMap, sync::Mutex};
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+	type Index = ContractEventIndex<MemoryAux, Block, u64, H256>;
+
+	#[derive(Default)]
+	struct MemoryAux(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl AuxStore for MemoryAux {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item=&'a(&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item=&'a &'b [u8]>,
+		>(&self, insert: I, delete: D) -> ClientResult<()> {
+			let mut storage = self.0.lock().unwrap();
+			for (k, v) in insert {
+				storage.insert(k.to_vec(), v.to_vec());
+			}
+			for k in delete {
+				storage.remove(*k);
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> ClientResult<Option<Vec<u8>>> {
+			Ok(self.0.lock().unwrap().get(key).cloned())
+		}
+	}
+
+	fn event(event_index: u32, contract: u64, topics: &[u8]) -> ContractEvent<u64, H256> {
+		ContractEvent {
+			event_index,
+			contract,
+			topics: topics.iter().map(|t| H256::repeat_byte(*t)).collect(),
+			data: vec![event_index as u8].into(),
+		}
+	}
+
+	fn query(
+		index: &Index,
+		contract: Option<u64>,
+		topics: &[u8],
+		from: u64,
+		to: u64,
+	) -> Vec<(u64, u32)> {
+		let topics: Vec<_> = topics.iter().map(|t| H256::repeat_byte(*t)).collect();
+		index.events(contract.as_ref(), &topics, from, to, 100)
+			.unwrap()
+			.into_iter()
+			.map(|e| (e.block_number, e.event.event_index))
+			.collect()
+	}
+
+	#[test]
+	fn events_are_found_by_contract_and_topics() {
+		let index = Index::new(Arc::new(MemoryAux::default()));
+		assert_eq!(index.indexed_range().unwrap(), None);
+
+		index.insert_block(10, H256::repeat_byte(10), Some(vec![
+			event(1, 1, &[0xaa]),
+			event(3, 2, &[0xaa, 0xbb]),
+		])).unwrap();
+		index.insert_block(11, H256::repeat_byte(11), Some(vec![])).unwrap();
+		index.insert_block(BUCKET_SIZE + 1, H256::repeat_byte(12), Some(vec![
+			event(0, 1, &[0xbb]),
+		])).unwrap();
+
+		assert_eq!(index.indexed_range().unwrap(), Some((10, BUCKET_SIZE + 1)));
+		assert_eq!(query(&index, None, &[], 0, u64::MAX), vec![(10, 1), (10, 3), (BUCKET_SIZE + 1, 0)]);
+		assert_eq!(query(&index, Some(1), &[], 0, u64::MAX), vec![(10, 1), (BUCKET_SIZE + 1, 0)]);
+		assert_eq!(query(&index, Some(2), &[], 0, u64::MAX), vec![(10, 3)]);
+		assert_eq!(query(&index, None, &[0xbb], 0, u64::MAX), vec![(10, 3), (BUCKET_SIZE + 1, 0)]);
+		assert_eq!(query(&index, None, &[0xbb, 0xaa], 0, u64::MAX), vec![(10, 3)]);
+		assert_eq!(query(&index, Some(1), &[0xbb], 0, u64::MAX), vec![(BUCKET_SIZE + 1, 0)]);
+		assert_eq!(query(&index, None, &[0xcc], 0, u64::MAX), vec![]);
+		assert_eq!(query(&index, None, &[], 11, BUCKET_SIZE), vec![]);
+		assert_eq!(query(&index, None, &[], 11, BUCKET_SIZE + 1), vec![(BUCKET_SIZE + 1, 0)]);
+	}
+
+	#[test]
+	fn events_stop_after_limit() {
+		let index = Index::new(Arc::new(MemoryAux::default()));
+		for number in 1..=5 {
+			index.insert_block(number, H256::repeat_byte(number as u8), Some(vec![
+				event(0, 1, &[]),
+			])).unwrap();
+		}
+
+		assert_eq!(index.events(None, &[], 1, 5, 5).unwrap().len(), 5);
+		assert_eq!(index.events(None, &[], 1, 5, 2).unwrap().len(), 3);
+	}
+
+	#[test]
+	fn unindexed_blocks_are_reported() {
+		let index = Index::new(Arc::new(MemoryAux::default()));
+		assert_eq!(index.unindexed(1, 5).unwrap(), vec![(1, 5)]);
+
+		for number in 10..=20 {
+			let events = match number {
+				12 | 13 | 20 => None,
+				_ => Some(vec![event(0, 1, &[])]),
+			};
+			index.insert_block(number, H256::repeat_byte(number as u8), events).unwrap();
+		}
+		index.insert_block(BUCKET_SIZE, H256::repeat_byte(21), None).unwrap();
+
+		assert_eq!(index.indexed_range().unwrap(), Some((10, BUCKET_SIZE)));
+		assert_eq!(
+			index.unindexed(0, u64::MAX).unwrap(),
+			vec![(0, 9), (12, 13), (20, 20), (BUCKET_SIZE, u64::MAX)],
+		);
+		assert_eq!(index.unindexed(10, 19).unwrap(), vec![(12, 13)]);
+		assert_eq!(index.unindexed(14, 19).unwrap(), vec![]);
+		assert_eq!(index.unindexed(20, 21).unwrap(), vec![(20, 20)]);
+		assert_eq!(query(&index, None, &[], 10, 20).len(), 8);
+	}
+}
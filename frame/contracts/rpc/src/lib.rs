@@ -22,9 +22,9 @@ use std::sync::Arc;
 use codec::Codec;
 use jsonrpc_core::{Error, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use pallet_contracts_primitives::RentProjection;
+use sc_client_api::AuxStore;
 use serde::{Deserialize, Serialize};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{Bytes, H256};
 use sp_rpc::number::NumberOrHex;
@@ -33,13 +33,23 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT},
 };
 use std::convert::{TryFrom, TryInto};
-use pallet_contracts_primitives::{Code, ContractExecResult, ContractInstantiateResult};
+use pallet_contracts_primitives::{
+	Code, ContractEvent, ContractInstantiateResult, ContractTracedExecResult,
+};
 
 pub use pallet_contracts_rpc_runtime_api::ContractsApi as ContractsRuntimeApi;
+pub use events::{index_contract_events, ContractEventIndex, IndexedEvent};
+
+mod events;
 
 const RUNTIME_ERROR: i64 = 1;
 const CONTRACT_DOESNT_EXIST: i64 = 2;
-const CONTRACT_IS_A_TOMBSTONE: i64 = 3;
+// 3 was `CONTRACT_IS_A_TOMBSTONE` before contract rent was removed. It is not reused so that
+// existing clients don't misread a different error.
+const DEPOSIT_UNSUPPORTED: i64 = 4;
+const EVENT_INDEX_UNAVAILABLE: i64 = 5;
+const TOO_MANY_EVENTS: i64 = 6;
+const TRACE_UNSUPPORTED: i64 = 7;
 
 pub type Weight = u64;
 
@@ -57,6 +67,9 @@ const GAS_PER_SECOND: Weight = 1_000_000_000_000;
 /// This puts a ceiling on the weight limit that is supplied to the rpc as an argument.
 const GAS_LIMIT: Weight = 5 * GAS_PER_SECOND;
 
+/// The maximum number of events a single `contracts_getEvents` query is allowed to return.
+const MAX_EVENTS: usize = 1000;
+
 /// A private newtype for converting `ContractAccessError` into an RPC error.
 struct ContractAccessError(pallet_contracts_primitives::ContractAccessError);
 impl From<ContractAccessError> for Error {
@@ -68,11 +81,6 @@ impl From<ContractAccessError> for Error {
 				message: "The specified contract doesn't exist.".into(),
 				data: None,
 			},
-			IsTombstone => Error {
-				code: ErrorCode::ServerError(CONTRACT_IS_A_TOMBSTONE),
-				message: "The contract is a tombstone and doesn't have any storage.".into(),
-				data: None,
-			},
 		}
 	}
 }
@@ -86,7 +94,11 @@ pub struct CallRequest<AccountId> {
 	dest: AccountId,
 	value: NumberOrHex,
 	gas_limit: NumberOrHex,
+	storage_deposit_limit: Option<NumberOrHex>,
 	input_data: Bytes,
+	/// Whether to record the trace of all nested calls.
+	#[serde(default)]
+	trace: bool,
 }
 
 /// A struct that encodes RPC parameters required to instantiate a new smart-contract.
@@ -97,11 +109,38 @@ pub struct InstantiateRequest<AccountId, Hash> {
 	origin: AccountId,
 	endowment: NumberOrHex,
 	gas_limit: NumberOrHex,
+	storage_deposit_limit: Option<NumberOrHex>,
 	code: Code<Hash>,
 	data: Bytes,
 	salt: Bytes,
 }
 
+/// A contract event as returned by `contracts_getEvents`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractEventResult<BlockHash, BlockNumber, AccountId, Hash> {
+	/// Number of the block containing the event.
+	block_number: BlockNumber,
+	/// Hash of the block containing the event.
+	block_hash: BlockHash,
+	/// The event itself.
+	#[serde(flatten)]
+	event: ContractEvent<AccountId, Hash>,
+}
+
+/// The result of a `contracts_getEvents` query.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractEventsResult<BlockHash, BlockNumber, AccountId, Hash> {
+	/// The matching events in the order they were emitted.
+	events: Vec<ContractEventResult<BlockHash, BlockNumber, AccountId, Hash>>,
+	/// Inclusive ranges of the queried blocks that are not covered by the index.
+	///
+	/// Events of these blocks are missing from `events`, either because the blocks were
+	/// finalized before the index was enabled or because their state was no longer available.
+	unindexed: Vec<(BlockNumber, BlockNumber)>,
+}
+
 /// Contracts RPC methods.
 #[rpc]
 pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
@@ -111,12 +150,16 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 	/// won't change any state. Nonetheless, the calling state-changing contracts is still possible.
 	///
 	/// This method is useful for calling getter-like methods on contracts.
+	///
+	/// When `trace` is set in the request the result additionally contains the tree of all
+	/// calls and instantiations performed including their inputs, outputs, gas consumption
+	/// and storage accesses.
 	#[rpc(name = "contracts_call")]
 	fn call(
 		&self,
 		call_request: CallRequest<AccountId>,
 		at: Option<BlockHash>,
-	) -> Result<ContractExecResult>;
+	) -> Result<ContractTracedExecResult<AccountId, Balance>>;
 
 	/// Instantiate a new contract.
 	///
@@ -129,7 +172,7 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		&self,
 		instantiate_request: InstantiateRequest<AccountId, Hash>,
 		at: Option<BlockHash>,
-	) -> Result<ContractInstantiateResult<AccountId, BlockNumber>>;
+	) -> Result<ContractInstantiateResult<AccountId, Balance>>;
 
 	/// Returns the value under a specified storage `key` in a contract given by `address` param,
 	/// or `None` if it is not set.
@@ -141,18 +184,22 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		at: Option<BlockHash>,
 	) -> Result<Option<Bytes>>;
 
-	/// Returns the projected time a given contract will be able to sustain paying its rent.
+	/// Returns the events emitted by contracts in the finalized blocks `from..=to`.
 	///
-	/// The returned projection is relevant for the given block, i.e. it is as if the contract was
-	/// accessed at the beginning of that block.
+	/// Only events emitted by `address` and carrying all of the given `topics` are returned.
+	/// Without an `address` events of all contracts are considered. `to` defaults to the
+	/// latest indexed block.
 	///
-	/// Returns `None` if the contract is exempted from rent.
-	#[rpc(name = "contracts_rentProjection")]
-	fn rent_projection(
+	/// This is served from a client side index which needs to be enabled on the node.
+	/// The blocks of the range the index does not cover are listed in the result.
+	#[rpc(name = "contracts_getEvents")]
+	fn get_events(
 		&self,
-		address: AccountId,
-		at: Option<BlockHash>,
-	) -> Result<Option<BlockNumber>>;
+		address: Option<AccountId>,
+		topics: Vec<Hash>,
+		from: BlockNumber,
+		to: Option<BlockNumber>,
+	) -> Result<ContractEventsResult<BlockHash, BlockNumber, AccountId, Hash>>;
 }
 
 /// An implementation of contract specific RPC methods.
@@ -180,7 +227,7 @@ impl<C, Block, AccountId, Balance, Hash>
 	> for Contracts<C, Block>
 where
 	Block: BlockT,
-	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + AuxStore,
 	C::Api: ContractsRuntimeApi<
 		Block,
 		AccountId,
@@ -188,15 +235,15 @@ where
 		<<Block as BlockT>::Header as HeaderT>::Number,
 		Hash,
 	>,
-	AccountId: Codec,
+	AccountId: Codec + PartialEq,
 	Balance: Codec + TryFrom<NumberOrHex>,
-	Hash: Codec,
+	Hash: Codec + PartialEq,
 {
 	fn call(
 		&self,
 		call_request: CallRequest<AccountId>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<ContractExecResult> {
+	) -> Result<ContractTracedExecResult<AccountId, Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
@@ -207,25 +254,77 @@ where
 			dest,
 			value,
 			gas_limit,
+			storage_deposit_limit,
 			input_data,
+			trace,
 		} = call_request;
 
 		let value: Balance = decode_hex(value, "balance")?;
 		let gas_limit: Weight = decode_hex(gas_limit, "weight")?;
+		let storage_deposit_limit: Option<Balance> = storage_deposit_limit
+			.map(|l| decode_hex(l, "balance"))
+			.transpose()?;
 		limit_gas(gas_limit)?;
 
-		let exec_result = api
-			.call(&at, origin, dest, value, gas_limit, input_data.to_vec())
+		// Runtimes before version 2 take no storage deposit limit and return the results of
+		// contract rent, neither of which can be encoded or decoded here anymore.
+		let supports_deposit = api
+			.has_api_with::<dyn ContractsRuntimeApi<
+				Block,
+				AccountId,
+				Balance,
+				<<Block as BlockT>::Header as HeaderT>::Number,
+				Hash,
+			>, _>(&at, |version| version >= 2)
 			.map_err(runtime_error_into_rpc_err)?;
+		if !supports_deposit {
+			return Err(deposit_unsupported());
+		}
 
-		Ok(exec_result)
+		if !trace {
+			let exec = api
+				.call(&at, origin, dest, value, gas_limit, storage_deposit_limit, input_data.to_vec())
+				.map_err(runtime_error_into_rpc_err)?;
+			return Ok(ContractTracedExecResult { exec, trace: None });
+		}
+
+		let supports_trace = api
+			.has_api_with::<dyn ContractsRuntimeApi<
+				Block,
+				AccountId,
+				Balance,
+				<<Block as BlockT>::Header as HeaderT>::Number,
+				Hash,
+			>, _>(&at, |version| version >= 4)
+			.map_err(runtime_error_into_rpc_err)?;
+		if !supports_trace {
+			return Err(Error {
+				code: ErrorCode::ServerError(TRACE_UNSUPPORTED),
+				message: "The runtime does not support tracing contract calls.".into(),
+				data: None,
+			});
+		}
+
+		let traced_result = api
+			.call_traced(
+				&at,
+				origin,
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				input_data.to_vec(),
+			)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(traced_result)
 	}
 
 	fn instantiate(
 		&self,
 		instantiate_request: InstantiateRequest<AccountId, Hash>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<ContractInstantiateResult<AccountId, <<Block as BlockT>::Header as HeaderT>::Number>> {
+	) -> Result<ContractInstantiateResult<AccountId, Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
@@ -235,6 +334,7 @@ where
 			origin,
 			endowment,
 			gas_limit,
+			storage_deposit_limit,
 			code,
 			data,
 			salt,
@@ -242,10 +342,35 @@ where
 
 		let endowment: Balance = decode_hex(endowment, "balance")?;
 		let gas_limit: Weight = decode_hex(gas_limit, "weight")?;
+		let storage_deposit_limit: Option<Balance> = storage_deposit_limit
+			.map(|l| decode_hex(l, "balance"))
+			.transpose()?;
 		limit_gas(gas_limit)?;
 
+		let supports_deposit = api
+			.has_api_with::<dyn ContractsRuntimeApi<
+				Block,
+				AccountId,
+				Balance,
+				<<Block as BlockT>::Header as HeaderT>::Number,
+				Hash,
+			>, _>(&at, |version| version >= 2)
+			.map_err(runtime_error_into_rpc_err)?;
+		if !supports_deposit {
+			return Err(deposit_unsupported());
+		}
+
 		let exec_result = api
-			.instantiate(&at, origin, endowment, gas_limit, code, data.to_vec(), salt.to_vec())
+			.instantiate(
+				&at,
+				origin,
+				endowment,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data.to_vec(),
+				salt.to_vec(),
+			)
 			.map_err(runtime_error_into_rpc_err)?;
 
 		Ok(exec_result)
@@ -271,24 +396,62 @@ where
 		Ok(result)
 	}
 
-	fn rent_projection(
+	fn get_events(
 		&self,
-		address: AccountId,
-		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<Option<<<Block as BlockT>::Header as HeaderT>::Number>> {
-		let api = self.client.runtime_api();
-		let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
+		address: Option<AccountId>,
+		topics: Vec<Hash>,
+		from: <<Block as BlockT>::Header as HeaderT>::Number,
+		to: Option<<<Block as BlockT>::Header as HeaderT>::Number>,
+	) -> Result<ContractEventsResult<
+		<Block as BlockT>::Hash,
+		<<Block as BlockT>::Header as HeaderT>::Number,
+		AccountId,
+		Hash,
+	>> {
+		let index = ContractEventIndex::<_, Block, AccountId, Hash>::new(self.client.clone());
+		let last = match index.indexed_range().map_err(event_index_error_into_rpc_err)? {
+			Some((_, last)) => last,
+			None => return Err(Error {
+				code: ErrorCode::ServerError(EVENT_INDEX_UNAVAILABLE),
+				message: "The contract event index is not enabled on this node.".into(),
+				data: None,
+			}),
+		};
+		let to = to.unwrap_or(last);
+		if from > to {
+			return Err(Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("Invalid block range: {} > {}", from, to),
+				data: None,
+			});
+		}
 
-		let result = api
-			.rent_projection(&at, address)
-			.map_err(runtime_error_into_rpc_err)?
-			.map_err(ContractAccessError)?;
+		let events = index
+			.events(address.as_ref(), &topics, from, to, MAX_EVENTS)
+			.map_err(event_index_error_into_rpc_err)?;
+		if events.len() > MAX_EVENTS {
+			return Err(Error {
+				code: ErrorCode::ServerError(TOO_MANY_EVENTS),
+				message: format!(
+					"Query matches more than {} events. Narrow down the block range.",
+					MAX_EVENTS,
+				),
+				data: None,
+			});
+		}
 
-		Ok(match result {
-			RentProjection::NoEviction => None,
-			RentProjection::EvictionAt(block_num) => Some(block_num),
+		let unindexed = index.unindexed(from, to).map_err(event_index_error_into_rpc_err)?;
+
+		Ok(ContractEventsResult {
+			events: events
+				.into_iter()
+				.map(|IndexedEvent { block_number, block_hash, event }| ContractEventResult {
+					block_number,
+					block_hash,
+					event,
+				})
+				.collect(),
+			unindexed,
 		})
 	}
 }
@@ -302,6 +465,25 @@ fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
 	}
 }
 
+/// The error returned when the runtime's contracts API predates the storage deposit.
+fn deposit_unsupported() -> Error {
+	Error {
+		code: ErrorCode::ServerError(DEPOSIT_UNSUPPORTED),
+		message: "The runtime's contracts API predates the storage deposit and is not supported."
+			.into(),
+		data: None,
+	}
+}
+
+/// Converts a failure to read the contract event index into an RPC error.
+fn event_index_error_into_rpc_err(err: sp_blockchain::Error) -> Error {
+	Error {
+		code: ErrorCode::ServerError(EVENT_INDEX_UNAVAILABLE),
+		message: "Failed to read the contract event index".into(),
+		data: Some(err.to_string().into()),
+	}
+}
+
 fn decode_hex<H: std::fmt::Debug + Copy, T: TryFrom<H>>(from: H, name: &str) -> Result<T> {
 	from.try_into().map_err(|_| Error {
 		code: ErrorCode::InvalidParams,
@@ -329,6 +511,7 @@ fn limit_gas(gas_limit: Weight) -> Result<()> {
 mod tests {
 	use super::*;
 	use sp_core::U256;
+	use pallet_contracts_primitives::ContractExecResult;
 
 	fn trim(json: &str) -> String {
 		json.chars().filter(|c| !c.is_whitespace()).collect()
@@ -343,11 +526,30 @@ mod tests {
 			"dest": "5DRakbLVnjVrW6niwLfHGW24EeCEvDAFGEXrtaYS5M4ynoom",
 			"value": "0x112210f4B16c1cb1",
 			"gasLimit": 1000000000000,
+			"storageDepositLimit": 5000,
 			"inputData": "0x8c97db39"
 		}
 		"#).unwrap();
 		assert_eq!(req.gas_limit.into_u256(), U256::from(0xe8d4a51000u64));
+		assert_eq!(req.storage_deposit_limit.map(|l| l.into_u256()), Some(5000.into()));
 		assert_eq!(req.value.into_u256(), U256::from(1234567890987654321u128));
+		assert!(!req.trace);
+	}
+
+	#[test]
+	fn call_request_with_trace_should_deserialize_properly() {
+		type Req = CallRequest<String>;
+		let req: Req = serde_json::from_str(r#"
+		{
+			"origin": "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL",
+			"dest": "5DRakbLVnjVrW6niwLfHGW24EeCEvDAFGEXrtaYS5M4ynoom",
+			"value": 0,
+			"gasLimit": 1000000000000,
+			"inputData": "0x8c97db39",
+			"trace": true
+		}
+		"#).unwrap();
+		assert!(req.trace);
 	}
 
 	#[test]
@@ -367,6 +569,7 @@ mod tests {
 		assert_eq!(req.origin, "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL");
 		assert_eq!(req.endowment.into_u256(), 0x88.into());
 		assert_eq!(req.gas_limit.into_u256(), 42.into());
+		assert!(req.storage_deposit_limit.is_none());
 		assert_eq!(&*req.data, [0x42, 0x99].as_ref());
 		assert_eq!(&*req.salt, [0x99, 0x88].as_ref());
 		let code = match req.code {
@@ -376,15 +579,50 @@ mod tests {
 		assert_eq!(&code, "0x1122");
 	}
 
+	#[test]
+	fn event_result_should_serialize_deserialize_properly() {
+		let expected = r#"{
+			"blockNumber": 42,
+			"blockHash": "0x1234",
+			"eventIndex": 3,
+			"contract": "5CiPP",
+			"topics": ["0xaa", "0xbb"],
+			"data": "0x0102"
+		}"#;
+		let res: ContractEventResult<String, u32, String, String> =
+			serde_json::from_str(expected).unwrap();
+		assert_eq!(res.block_number, 42);
+		assert_eq!(res.event.event_index, 3);
+		assert_eq!(&*res.event.data, [0x01, 0x02].as_ref());
+		let actual = serde_json::to_string(&res).unwrap();
+		assert_eq!(actual, trim(expected).as_str());
+	}
+
+	#[test]
+	fn events_result_should_serialize_deserialize_properly() {
+		let expected = r#"{
+			"events": [],
+			"unindexed": [[1, 9], [12, 12]]
+		}"#;
+		let res: ContractEventsResult<String, u32, String, String> =
+			serde_json::from_str(expected).unwrap();
+		assert_eq!(res.unindexed, vec![(1, 9), (12, 12)]);
+		let actual = serde_json::to_string(&res).unwrap();
+		assert_eq!(actual, trim(expected).as_str());
+	}
+
 	#[test]
 	fn call_result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: ContractExecResult = serde_json::from_str(expected).unwrap();
+			let res: ContractExecResult<u32> = serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, trim(expected).as_str());
 		}
 		test(r#"{
 			"gasConsumed": 5000,
+			"storageDeposit": {
+				"charge": 42000
+			},
 			"debugMessage": "0x68656c704f6b",
 			"result": {
 			  "Ok": {
@@ -395,6 +633,9 @@ mod tests {
 		}"#);
 		test(r#"{
 			"gasConsumed": 3400,
+			"storageDeposit": {
+				"refund": 12000
+			},
 			"debugMessage": "0x68656c70457272",
 			"result": {
 			  "Err": "BadOrigin"
@@ -402,15 +643,92 @@ mod tests {
 		}"#);
 	}
 
+	#[test]
+	fn traced_call_result_should_serialize_deserialize_properly() {
+		fn test(expected: &str) {
+			let res: ContractTracedExecResult<String, u32> =
+				serde_json::from_str(expected).unwrap();
+			let actual = serde_json::to_string(&res).unwrap();
+			assert_eq!(actual, trim(expected).as_str());
+		}
+		test(r#"{
+			"gasConsumed": 5000,
+			"storageDeposit": {
+				"charge": 42000
+			},
+			"debugMessage": "0x68656c704f6b",
+			"result": {
+			  "Ok": {
+				"flags": 0,
+				"data": "0x1234"
+			  }
+			}
+		}"#);
+		test(r#"{
+			"gasConsumed": 5000,
+			"storageDeposit": {
+				"charge": 42000
+			},
+			"debugMessage": "0x68656c704f6b",
+			"result": {
+			  "Ok": {
+				"flags": 0,
+				"data": "0x1234"
+			  }
+			},
+			"trace": {
+				"kind": "call",
+				"caller": "5CiPP",
+				"callee": "5DRak",
+				"value": 100,
+				"input": "0x8c97db39",
+				"gasConsumed": 5000,
+				"storage": [
+					{
+						"write": {
+							"key": "0x0101010101010101010101010101010101010101010101010101010101010101",
+							"value": "0x2a"
+						}
+					}
+				],
+				"result": {
+					"Ok": {
+						"flags": 0,
+						"data": "0x1234"
+					}
+				},
+				"calls": [
+					{
+						"kind": "delegateCall",
+						"caller": "5CiPP",
+						"callee": "5DRak",
+						"value": 100,
+						"input": "0x",
+						"gasConsumed": 1000,
+						"storage": [],
+						"result": {
+							"Err": "BadOrigin"
+						},
+						"calls": []
+					}
+				]
+			}
+		}"#);
+	}
+
 	#[test]
 	fn instantiate_result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: ContractInstantiateResult<String, u64> = serde_json::from_str(expected).unwrap();
+			let res: ContractInstantiateResult<String, u64> =
+				serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, trim(expected).as_str());
 		}
 		test(r#"{
 			"gasConsumed": 5000,
+			"storageDeposit": {
+				"charge": 42000
+			},
 			"debugMessage": "0x68656c704f6b",
 			"result": {
 			   "Ok": {
@@ -418,13 +736,15 @@ mod tests {
 					 "flags": 5,
 					 "data": "0x1234"
 				  },
-				  "accountId": "5CiPP",
-				  "rentProjection": null
+				  "accountId": "5CiPP"
 			   }
 			}
 		}"#);
 		test(r#"{
 			"gasConsumed": 3400,
+			"storageDeposit": {
+				"refund": 12000
+			},
 			"debugMessage": "0x68656c70457272",
 			"result": {
 			  "Err": "BadOrigin"
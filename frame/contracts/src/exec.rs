@@ -17,8 +17,8 @@
 
 use crate::{
 	CodeHash, Event, Config, Pallet as Contracts,
-	BalanceOf, ContractInfo, gas::GasMeter, rent::{Rent, RentStatus}, storage::Storage,
-	Error, ContractInfoOf, Schedule, AliveContractInfo, AccountCounter,
+	BalanceOf, ContractInfo, gas::GasMeter, storage::{Storage, StorageMeter, DepositOf},
+	Error, ContractInfoOf, Schedule, AccountCounter, trace::CallTracer,
 };
 use sp_core::crypto::UncheckedFrom;
 use sp_std::{
@@ -26,15 +26,15 @@ use sp_std::{
 	marker::PhantomData,
 	mem,
 };
-use sp_runtime::{Perbill, traits::{Convert, Saturating}};
+use sp_runtime::traits::{Convert, Saturating};
 use frame_support::{
 	dispatch::{DispatchResult, DispatchError},
 	storage::{with_transaction, TransactionOutcome},
 	traits::{ExistenceRequirement, Currency, Time, Randomness, Get},
 	weights::Weight,
-	ensure, DefaultNoBound,
+	ensure,
 };
-use pallet_contracts_primitives::{ExecReturnValue};
+use pallet_contracts_primitives::{CallKind, ExecReturnValue, StorageDeposit};
 use smallvec::{SmallVec, Array};
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -81,67 +81,6 @@ impl<T: Into<DispatchError>> From<T> for ExecError {
 	}
 }
 
-/// Information needed for rent calculations that can be requested by a contract.
-#[derive(codec::Encode, DefaultNoBound)]
-#[cfg_attr(test, derive(Debug, PartialEq))]
-pub struct RentParams<T: Config> {
-	/// The total balance of the contract. Includes the balance transferred from the caller.
-	total_balance: BalanceOf<T>,
-	/// The free balance of the contract. Includes the balance transferred from the caller.
-	free_balance: BalanceOf<T>,
-	/// See crate [`Contracts::subsistence_threshold()`].
-	subsistence_threshold: BalanceOf<T>,
-	/// See crate [`Config::DepositPerContract`].
-	deposit_per_contract: BalanceOf<T>,
-	/// See crate [`Config::DepositPerStorageByte`].
-	deposit_per_storage_byte: BalanceOf<T>,
-	/// See crate [`Config::DepositPerStorageItem`].
-	deposit_per_storage_item: BalanceOf<T>,
-	/// See crate [`Ext::rent_allowance()`].
-	rent_allowance: BalanceOf<T>,
-	/// See crate [`Config::RentFraction`].
-	rent_fraction: Perbill,
-	/// See crate [`AliveContractInfo::storage_size`].
-	storage_size: u32,
-	/// See crate [`Executable::aggregate_code_len()`].
-	code_size: u32,
-	/// See crate [`Executable::refcount()`].
-	code_refcount: u32,
-	/// Reserved for backwards compatible changes to this data structure.
-	_reserved: Option<()>,
-}
-
-impl<T> RentParams<T>
-where
-	T: Config,
-	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
-{
-	/// Derive new `RentParams` from the passed in data.
-	///
-	/// `value` is added to the current free and total balance of the contracts' account.
-	fn new<E: Executable<T>>(
-		account_id: &T::AccountId,
-		value: &BalanceOf<T>,
-		contract: &AliveContractInfo<T>,
-		executable: &E
-	) -> Self {
-		Self {
-			total_balance: T::Currency::total_balance(account_id).saturating_add(*value),
-			free_balance: T::Currency::free_balance(account_id).saturating_add(*value),
-			subsistence_threshold: <Contracts<T>>::subsistence_threshold(),
-			deposit_per_contract: T::DepositPerContract::get(),
-			deposit_per_storage_byte: T::DepositPerStorageByte::get(),
-			deposit_per_storage_item: T::DepositPerStorageItem::get(),
-			rent_allowance: contract.rent_allowance,
-			rent_fraction: T::RentFraction::get(),
-			storage_size: contract.storage_size,
-			code_size: executable.aggregate_code_len(),
-			code_refcount: executable.refcount(),
-			_reserved: None,
-		}
-	}
-}
-
 /// An interface that provides access to the external environment in which the
 /// smart-contract is executed.
 ///
@@ -170,6 +109,22 @@ pub trait Ext: sealing::Sealed {
 		allows_reentry: bool,
 	) -> Result<(ExecReturnValue, u32), (ExecError, u32)>;
 
+	/// Execute code in the current frame.
+	///
+	/// The code identified by `code` is executed with the storage, address, balance and caller
+	/// of the currently executing contract. No balance is transferred.
+	///
+	/// Returns the original code size of the executed code.
+	///
+	/// # Return Value
+	///
+	/// Result<(ExecReturnValue, CodeSize), (ExecError, CodeSize)>
+	fn delegate_call(
+		&mut self,
+		code: CodeHash<Self::T>,
+		input_data: Vec<u8>,
+	) -> Result<(ExecReturnValue, u32), (ExecError, u32)>;
+
 	/// Instantiate a contract from the given code.
 	///
 	/// Returns the original code size of the called contract.
@@ -205,24 +160,17 @@ pub trait Ext: sealing::Sealed {
 		beneficiary: &AccountIdOf<Self::T>,
 	) -> Result<u32, (DispatchError, u32)>;
 
-	/// Restores the given destination contract sacrificing the current one.
+	/// Replace the code hash of the currently executing contract with `hash`.
 	///
-	/// Since this function removes the self contract eagerly, if succeeded, no further actions should
-	/// be performed on this `Ext` instance.
+	/// The new code is used starting with the next call into the contract. The code that is
+	/// currently executing is not affected.
 	///
-	/// This function will fail if the same contract is present
-	/// on the contract call stack.
+	/// Returns the combined original code size of the new and the replaced code.
 	///
 	/// # Return Value
 	///
-	/// Result<(CallerCodeSize, DestCodeSize), (DispatchError, CallerCodeSize, DestCodesize)>
-	fn restore_to(
-		&mut self,
-		dest: AccountIdOf<Self::T>,
-		code_hash: CodeHash<Self::T>,
-		rent_allowance: BalanceOf<Self::T>,
-		delta: Vec<StorageKey>,
-	) -> Result<(u32, u32), (DispatchError, u32, u32)>;
+	/// Result<CodeSize, (DispatchError, CodeSize)>
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<u32, (DispatchError, u32)>;
 
 	/// Transfer some amount of funds into the specified account.
 	fn transfer(
@@ -261,9 +209,6 @@ pub trait Ext: sealing::Sealed {
 	/// Returns the minimum balance that is required for creating an account.
 	fn minimum_balance(&self) -> BalanceOf<Self::T>;
 
-	/// Returns the deposit required to create a tombstone upon contract eviction.
-	fn tombstone_deposit(&self) -> BalanceOf<Self::T>;
-
 	/// Returns a random number for the current block with the given subject.
 	fn random(&self, subject: &[u8]) -> (SeedOf<Self::T>, BlockNumberOf<Self::T>);
 
@@ -272,12 +217,6 @@ pub trait Ext: sealing::Sealed {
 	/// There should not be any duplicates in `topics`.
 	fn deposit_event(&mut self, topics: Vec<TopicOf<Self::T>>, data: Vec<u8>);
 
-	/// Set rent allowance of the contract
-	fn set_rent_allowance(&mut self, rent_allowance: BalanceOf<Self::T>);
-
-	/// Rent allowance of the contract
-	fn rent_allowance(&mut self) -> BalanceOf<Self::T>;
-
 	/// Returns the current block number.
 	fn block_number(&self) -> BlockNumberOf<Self::T>;
 
@@ -290,12 +229,6 @@ pub trait Ext: sealing::Sealed {
 	/// Get a reference to the schedule used by the current call.
 	fn schedule(&self) -> &Schedule<Self::T>;
 
-	/// Information needed for rent calculations.
-	fn rent_params(&self) -> &RentParams<Self::T>;
-
-	/// Information about the required deposit and resulting rent.
-	fn rent_status(&mut self, at_refcount: u32) -> RentStatus<Self::T>;
-
 	/// Get a mutable reference to the nested gas meter.
 	fn gas_meter(&mut self) -> &mut GasMeter<Self::T>;
 
@@ -308,6 +241,10 @@ pub trait Ext: sealing::Sealed {
 	///
 	/// Returns `true` if debug message recording is enabled. Otherwise `false` is returned.
 	fn append_debug_buffer(&mut self, msg: &str) -> bool;
+
+	/// Get a mutable reference to the contract info of the currently executing contract.
+	#[cfg(test)]
+	fn contract_info(&mut self) -> &mut ContractInfo<Self::T>;
 }
 
 /// Describes the different functions that can be exported by an [`Executable`].
@@ -372,29 +309,6 @@ pub trait Executable<T: Config>: Sized {
 
 	/// Size of the instrumented code in bytes.
 	fn code_len(&self) -> u32;
-
-	/// Sum of instrumented and pristine code len.
-	fn aggregate_code_len(&self) -> u32;
-
-	// The number of contracts using this executable.
-	fn refcount(&self) -> u32;
-
-	/// The storage that is occupied by the instrumented executable and its pristine source.
-	///
-	/// The returned size is already divided by the number of users who share the code.
-	/// This is essentially `aggregate_code_len() / refcount()`.
-	///
-	/// # Note
-	///
-	/// This works with the current in-memory value of refcount. When calling any contract
-	/// without refetching this from storage the result can be inaccurate as it might be
-	/// working with a stale value. Usually this inaccuracy is tolerable.
-	fn occupied_storage(&self) -> u32 {
-		// We disregard the size of the struct itself as the size is completely
-		// dominated by the code size.
-		let len = self.aggregate_code_len();
-		len.checked_div(self.refcount()).unwrap_or(len)
-	}
 }
 
 /// The complete call stack of a contract execution.
@@ -415,6 +329,8 @@ pub struct Stack<'a, T: Config, E> {
 	schedule: &'a Schedule<T>,
 	/// The gas meter where costs are charged to.
 	gas_meter: &'a mut GasMeter<T>,
+	/// The meter that tracks the storage deposit charged from or refunded to the origin.
+	storage_meter: &'a mut StorageMeter<T>,
 	/// The timestamp at the point of call stack instantiation.
 	timestamp: MomentOf<T>,
 	/// The block number at the time of call stack instantiation.
@@ -432,6 +348,8 @@ pub struct Stack<'a, T: Config, E> {
 	/// All the bytes added to this field should be valid UTF-8. The buffer has no defined
 	/// structure and is intended to be shown to users as-is for debugging purposes.
 	debug_message: Option<&'a mut Vec<u8>>,
+	/// Records the tree of calls and instantiations performed by this call stack.
+	tracer: Option<&'a mut CallTracer<T>>,
 	/// No executable is held by the struct but influences its behaviour.
 	_phantom: PhantomData<E>,
 }
@@ -452,14 +370,29 @@ pub struct Frame<T: Config> {
 	contract_info: CachedContract<T>,
 	/// The amount of balance transferred by the caller as part of the call.
 	value_transferred: BalanceOf<T>,
-	/// Snapshotted rent information that can be copied to the contract if requested.
-	rent_params: RentParams<T>,
+	/// The value of the storage meter when this frame was pushed.
+	///
+	/// The meter is reset to this value when the frame is rolled back.
+	storage_deposit_snapshot: DepositOf<T>,
 	/// Determines whether this is a call or instantiate frame.
 	entry_point: ExportedFunction,
 	/// The gas meter capped to the supplied gas limit.
 	nested_meter: GasMeter<T>,
 	/// If `false` the contract enabled its defense against reentrance attacks.
 	allows_reentry: bool,
+	/// If `Some` this frame executes code on behalf of its predecessor.
+	///
+	/// It holds the caller of the delegating frame which is also reported as the caller
+	/// of this frame.
+	delegate_caller: Option<T::AccountId>,
+}
+
+/// Used in a delegate call frame arguments in order to override the executable and caller.
+struct DelegatedCall<T: Config, E> {
+	/// The executable which is run instead of the contracts own `executable`.
+	executable: E,
+	/// The account id of the caller of the delegating contract.
+	caller: T::AccountId,
 }
 
 /// Parameter passed in when creating a new `Frame`.
@@ -470,7 +403,12 @@ enum FrameArgs<'a, T: Config, E> {
 		/// The account id of the contract that is to be called.
 		dest: T::AccountId,
 		/// If `None` the contract info needs to be reloaded from storage.
-		cached_info: Option<AliveContractInfo<T>>,
+		cached_info: Option<ContractInfo<T>>,
+		/// This frame is created by a delegate call if this is `Some`.
+		///
+		/// The executable and caller of this frame are then taken from here instead
+		/// of being derived from `dest`.
+		delegated_call: Option<DelegatedCall<T, E>>,
 	},
 	Instantiate {
 		/// The contract or signed origin which instantiates the new contract.
@@ -487,12 +425,12 @@ enum FrameArgs<'a, T: Config, E> {
 /// Describes the different states of a contract as contained in a `Frame`.
 enum CachedContract<T: Config> {
 	/// The cached contract is up to date with the in-storage value.
-	Cached(AliveContractInfo<T>),
+	Cached(ContractInfo<T>),
 	/// A recursive call into the same contract did write to the contract info.
 	///
 	/// In this case the cached contract is stale and needs to be reloaded from storage.
 	Invalidated,
-	/// The current contract executed `terminate` or `restore_to` and removed the contract.
+	/// The current contract executed `terminate` and removed the contract.
 	///
 	/// In this case a reload is neither allowed nor possible. Please note that recursive
 	/// calls cannot remove a contract as this is checked and denied.
@@ -501,13 +439,8 @@ enum CachedContract<T: Config> {
 
 impl<T: Config> Frame<T> {
 	/// Return the `contract_info` of the current contract.
-	fn contract_info(&mut self) -> &mut AliveContractInfo<T> {
-		self.contract_info.as_alive(&self.account_id)
-	}
-
-	/// Invalidate and return the `contract_info` of the current contract.
-	fn invalidate(&mut self) -> AliveContractInfo<T> {
-		self.contract_info.invalidate(&self.account_id)
+	fn contract_info(&mut self) -> &mut ContractInfo<T> {
+		self.contract_info.get(&self.account_id)
 	}
 
 	/// Terminate and return the `contract_info` of the current contract.
@@ -516,7 +449,7 @@ impl<T: Config> Frame<T> {
 	///
 	/// Under no circumstances the contract is allowed to access the `contract_info` after
 	/// a call to this function. This would constitute a programming error in the exec module.
-	fn terminate(&mut self) -> AliveContractInfo<T> {
+	fn terminate(&mut self) -> ContractInfo<T> {
 		self.contract_info.terminate(&self.account_id)
 	}
 }
@@ -531,7 +464,7 @@ macro_rules! get_cached_or_panic_after_load {
 		} else {
 			panic!(
 				"It is impossible to remove a contract that is on the call stack;\
-				See implementations of terminate and restore_to;\
+				See implementation of terminate;\
 				Therefore fetching a contract will never fail while using an account id
 				that is currently active on the call stack;\
 				qed"
@@ -544,28 +477,20 @@ impl<T: Config> CachedContract<T> {
 	/// Load the `contract_info` from storage if necessary.
 	fn load(&mut self, account_id: &T::AccountId) {
 		if let CachedContract::Invalidated = self {
-			let contract = <ContractInfoOf<T>>::get(&account_id)
-				.and_then(|contract| contract.get_alive());
-			if let Some(contract) = contract {
+			if let Some(contract) = <ContractInfoOf<T>>::get(&account_id) {
 				*self = CachedContract::Cached(contract);
 			}
 		}
 	}
 
-	/// Return the cached contract_info as alive contract info.
-	fn as_alive(&mut self, account_id: &T::AccountId) -> &mut AliveContractInfo<T> {
+	/// Return the cached contract_info.
+	fn get(&mut self, account_id: &T::AccountId) -> &mut ContractInfo<T> {
 		self.load(account_id);
 		get_cached_or_panic_after_load!(self)
 	}
 
-	/// Invalidate and return the contract info.
-	fn invalidate(&mut self, account_id: &T::AccountId) -> AliveContractInfo<T> {
-		self.load(account_id);
-		get_cached_or_panic_after_load!(mem::replace(self, Self::Invalidated))
-	}
-
 	/// Terminate and return the contract info.
-	fn terminate(&mut self, account_id: &T::AccountId) -> AliveContractInfo<T> {
+	fn terminate(&mut self, account_id: &T::AccountId) -> ContractInfo<T> {
 		self.load(account_id);
 		get_cached_or_panic_after_load!(mem::replace(self, Self::Terminated))
 	}
@@ -581,8 +506,8 @@ where
 	///
 	/// # Note
 	///
-	/// `debug_message` should only ever be set to `Some` when executing as an RPC because
-	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	/// `debug_message` and `tracer` should only ever be set to `Some` when executing as an RPC
+	/// because they add allocations and could be abused to drive the runtime into an OOM panic.
 	///
 	/// # Return Value
 	///
@@ -591,18 +516,22 @@ where
 		origin: T::AccountId,
 		dest: T::AccountId,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut StorageMeter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
 		debug_message: Option<&'a mut Vec<u8>>,
+		tracer: Option<&'a mut CallTracer<T>>,
 	) -> Result<(ExecReturnValue, u32), (ExecError, u32)> {
 		let (mut stack, executable) = Self::new(
-			FrameArgs::Call{dest, cached_info: None},
+			FrameArgs::Call{dest, cached_info: None, delegated_call: None},
 			origin,
 			gas_meter,
+			storage_meter,
 			schedule,
 			value,
 			debug_message,
+			tracer,
 		)?;
 		stack.run(executable, input_data)
 	}
@@ -611,8 +540,8 @@ where
 	///
 	/// # Note
 	///
-	/// `debug_message` should only ever be set to `Some` when executing as an RPC because
-	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
+	/// `debug_message` and `tracer` should only ever be set to `Some` when executing as an RPC
+	/// because they add allocations and could be abused to drive the runtime into an OOM panic.
 	///
 	/// # Return Value
 	///
@@ -621,11 +550,13 @@ where
 		origin: T::AccountId,
 		executable: E,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut StorageMeter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
 		salt: &[u8],
 		debug_message: Option<&'a mut Vec<u8>>,
+		tracer: Option<&'a mut CallTracer<T>>,
 	) -> Result<(T::AccountId, ExecReturnValue), ExecError> {
 		let (mut stack, executable) = Self::new(
 			FrameArgs::Instantiate {
@@ -636,9 +567,11 @@ where
 			},
 			origin,
 			gas_meter,
+			storage_meter,
 			schedule,
 			value,
 			debug_message,
+			tracer,
 		).map_err(|(e, _code_len)| e)?;
 		let account_id = stack.top_frame().account_id.clone();
 		stack.run(executable, input_data)
@@ -651,21 +584,27 @@ where
 		args: FrameArgs<T, E>,
 		origin: T::AccountId,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut StorageMeter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		debug_message: Option<&'a mut Vec<u8>>,
+		tracer: Option<&'a mut CallTracer<T>>,
 	) -> Result<(Self, E), (ExecError, u32)> {
-		let (first_frame, executable) = Self::new_frame(args, value, gas_meter, 0, &schedule)?;
+		let (first_frame, executable) = Self::new_frame(
+			args, value, gas_meter, storage_meter.total(), 0, &schedule,
+		)?;
 		let stack = Self {
 			origin,
 			schedule,
 			gas_meter,
+			storage_meter,
 			timestamp: T::Time::now(),
 			block_number: <frame_system::Pallet<T>>::block_number(),
 			account_counter: None,
 			first_frame,
 			frames: Default::default(),
 			debug_message,
+			tracer,
 			_phantom: Default::default(),
 		};
 
@@ -680,35 +619,29 @@ where
 		frame_args: FrameArgs<T, E>,
 		value_transferred: BalanceOf<T>,
 		gas_meter: &mut GasMeter<T>,
+		storage_deposit_snapshot: DepositOf<T>,
 		gas_limit: Weight,
 		schedule: &Schedule<T>
 	) -> Result<(Frame<T>, E), (ExecError, u32)> {
-		let (account_id, contract_info, executable, entry_point) = match frame_args {
-			FrameArgs::Call{dest, cached_info} => {
+		let (account_id, contract_info, executable, delegate_caller, entry_point) = match frame_args {
+			FrameArgs::Call{dest, cached_info, delegated_call} => {
 				let contract = if let Some(contract) = cached_info {
 					contract
 				} else {
 					<ContractInfoOf<T>>::get(&dest)
-						.ok_or((<Error<T>>::ContractNotFound.into(), 0))
-						.and_then(|contract|
-							contract.get_alive()
-								.ok_or((<Error<T>>::ContractIsTombstone.into(), 0))
-						)?
+						.ok_or((<Error<T>>::ContractNotFound.into(), 0))?
 				};
 
-				let executable = E::from_storage(contract.code_hash, schedule, gas_meter)
-					.map_err(|e| (e.into(), 0))?;
-
-				// This charges the rent and denies access to a contract that is in need of
-				// eviction by returning `None`. We cannot evict eagerly here because those
-				// changes would be rolled back in case this contract is called by another
-				// contract.
-				// See: https://github.com/paritytech/substrate/issues/6439#issuecomment-648754324
-				let contract = Rent::<T, E>
-					::charge(&dest, contract, executable.occupied_storage())
-					.map_err(|e| (e.into(), executable.code_len()))?
-					.ok_or((Error::<T>::RentNotPaid.into(), executable.code_len()))?;
-				(dest, contract, executable, ExportedFunction::Call)
+				let (executable, delegate_caller) = match delegated_call {
+					Some(DelegatedCall{executable, caller}) => (executable, Some(caller)),
+					None => (
+						E::from_storage(contract.code_hash, schedule, gas_meter)
+							.map_err(|e| (e.into(), 0))?,
+						None,
+					),
+				};
+
+				(dest, contract, executable, delegate_caller, ExportedFunction::Call)
 			}
 			FrameArgs::Instantiate{sender, trie_seed, executable, salt} => {
 				let account_id = <Contracts<T>>::contract_address(
@@ -720,21 +653,20 @@ where
 					trie_id,
 					executable.code_hash().clone(),
 				).map_err(|e| (e.into(), executable.code_len()))?;
-				(account_id, contract, executable, ExportedFunction::Constructor)
+				(account_id, contract, executable, None, ExportedFunction::Constructor)
 			}
 		};
 
 		let frame = Frame {
-			rent_params: RentParams::new(
-				&account_id, &value_transferred, &contract_info, &executable,
-			),
 			value_transferred,
+			storage_deposit_snapshot,
 			contract_info: CachedContract::Cached(contract_info),
 			account_id,
 			entry_point,
 			nested_meter: gas_meter.nested(gas_limit)
 				.map_err(|e| (e.into(), executable.code_len()))?,
 			allows_reentry: true,
+			delegate_caller,
 		};
 
 		Ok((frame, executable))
@@ -761,7 +693,7 @@ where
 		{
 			<ContractInfoOf<T>>::insert(
 				frame.account_id.clone(),
-				ContractInfo::Alive(contract.clone()),
+				contract.clone(),
 			);
 		}
 
@@ -773,6 +705,7 @@ where
 			frame_args,
 			value_transferred,
 			nested_meter,
+			self.storage_meter.total(),
 			gas_limit,
 			self.schedule,
 		)?;
@@ -789,16 +722,17 @@ where
 		input_data: Vec<u8>
 	) -> Result<(ExecReturnValue, u32), (ExecError, u32)> {
 		let entry_point = self.top_frame().entry_point;
+		self.trace_enter(&input_data);
 		let do_transaction = || {
 			// Cache the value before calling into the constructor because that
-			// consumes the value. If the constructor creates additional contracts using
-			// the same code hash we still charge the "1 block rent" as if they weren't
-			// spawned. This is OK as overcharging is always safe.
-			let occupied_storage = executable.occupied_storage();
+			// consumes the value.
 			let code_len = executable.code_len();
 
-			// Every call or instantiate also optionally transferres balance.
-			self.initial_transfer().map_err(|e| (ExecError::from(e), code_len))?;
+			// Every call or instantiate also optionally transferres balance. A delegate call
+			// merely executes code on behalf of its caller and therefore transfers nothing.
+			if self.top_frame().delegate_caller.is_none() {
+				self.initial_transfer().map_err(|e| (ExecError::from(e), code_len))?;
+			}
 
 			// Call into the wasm blob.
 			let output = executable.execute(
@@ -817,17 +751,6 @@ where
 					return Err((Error::<T>::TerminatedInConstructor.into(), code_len));
 				}
 
-				// Collect the rent for the first block to prevent the creation of very large
-				// contracts that never intended to pay for even one block.
-				// This also makes sure that it is above the subsistence threshold
-				// in order to keep up the guarantuee that we always leave a tombstone behind
-				// with the exception of a contract that called `seal_terminate`.
-				let contract = Rent::<T, E>
-					::charge(&account_id, frame.invalidate(), occupied_storage)
-					.map_err(|e| (e.into(), code_len))?
-					.ok_or((Error::<T>::NewContractNotFunded.into(), code_len))?;
-				frame.contract_info = CachedContract::Cached(contract);
-
 				// Deposit an instantiation event.
 				deposit_event::<T>(vec![], Event::Instantiated(
 					self.caller().clone(),
@@ -850,18 +773,79 @@ where
 				_ => TransactionOutcome::Rollback((false, output)),
 			}
 		});
+		self.trace_exit(&output);
 		self.pop_frame(success);
 		output
 	}
 
+	/// Record the start of the current (top) frame in the trace if tracing is enabled.
+	fn trace_enter(&mut self, input_data: &[u8]) {
+		if self.tracer.is_none() {
+			return;
+		}
+		let frame = self.top_frame();
+		let kind = match (frame.entry_point, &frame.delegate_caller) {
+			(ExportedFunction::Constructor, _) => CallKind::Instantiate,
+			(ExportedFunction::Call, Some(_)) => CallKind::DelegateCall,
+			(ExportedFunction::Call, None) => CallKind::Call,
+		};
+		let callee = frame.account_id.clone();
+		let value = frame.value_transferred;
+		let caller = self.caller().clone();
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.enter(kind, caller, callee, value, input_data.to_vec());
+		}
+	}
+
+	/// Record a nested frame that failed before it was entered in the trace if tracing is
+	/// enabled.
+	fn trace_not_entered(
+		&mut self,
+		kind: CallKind,
+		callee: T::AccountId,
+		value: BalanceOf<T>,
+		input_data: Vec<u8>,
+		err: &(ExecError, u32),
+	) {
+		if self.tracer.is_none() {
+			return;
+		}
+		// The current frame is the caller of the nested one, unless the nested one is a
+		// delegate call which keeps the caller of the current frame.
+		let caller = match kind {
+			CallKind::DelegateCall => self.caller().clone(),
+			_ => self.top_frame().account_id.clone(),
+		};
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.enter(kind, caller, callee, value, input_data);
+			tracer.exit(0, Err(err.0.error.clone()));
+		}
+	}
+
+	/// Record the outcome of the current (top) frame in the trace if tracing is enabled.
+	fn trace_exit(&mut self, output: &Result<(ExecReturnValue, u32), (ExecError, u32)>) {
+		let gas_consumed = self.top_frame().nested_meter.gas_spent();
+		if let Some(tracer) = self.tracer.as_mut() {
+			let result = match output {
+				Ok((output, _)) => Ok(output.clone()),
+				Err((err, _)) => Err(err.error.clone()),
+			};
+			tracer.exit(gas_consumed, result);
+		}
+	}
+
 	/// Remove the current (top) frame from the stack.
 	///
 	/// This is called after running the current frame. It commits cached values to storage
 	/// and invalidates all stale references to it that might exist further down the call stack.
 	fn pop_frame(&mut self, persist: bool) {
-		// Revert the account counter in case of a failed instantiation.
-		if !persist && self.top_frame().entry_point == ExportedFunction::Constructor {
-			self.account_counter.as_mut().map(|c| *c = c.wrapping_sub(1));
+		if !persist {
+			// Revert the account counter in case of a failed instantiation.
+			if self.top_frame().entry_point == ExportedFunction::Constructor {
+				self.account_counter.as_mut().map(|c| *c = c.wrapping_sub(1));
+			}
+			// The balance movements were rolled back together with the storage transaction.
+			self.storage_meter.revert_to(self.top_frame().storage_deposit_snapshot);
 		}
 
 		// Pop the current frame from the stack and return it in case it needs to interact
@@ -892,7 +876,7 @@ where
 				// because that case is already handled by the optimization above. Only the first
 				// cache needs to be invalidated because that one will invalidate the next cache
 				// when it is popped from the stack.
-				<ContractInfoOf<T>>::insert(account_id, ContractInfo::Alive(contract));
+				<ContractInfoOf<T>>::insert(account_id, contract);
 				if let Some(c) = self.frames_mut().skip(1).find(|f| f.account_id == *account_id) {
 					c.contract_info = CachedContract::Invalidated;
 				}
@@ -912,10 +896,7 @@ where
 				return;
 			}
 			if let CachedContract::Cached(contract) = &self.first_frame.contract_info {
-				<ContractInfoOf<T>>::insert(
-					&self.first_frame.account_id,
-					ContractInfo::Alive(contract.clone())
-				);
+				<ContractInfoOf<T>>::insert(&self.first_frame.account_id, contract.clone());
 			}
 			if let Some(counter) = self.account_counter {
 				<AccountCounter<T>>::set(counter);
@@ -1062,27 +1043,34 @@ where
 		self.top_frame_mut().allows_reentry = allows_reentry;
 
 		let try_call = || {
-			if !self.allows_reentry(&to) {
-				return Err((<Error<T>>::ReentranceDenied.into(), 0));
+			let executable = if !self.allows_reentry(&to) {
+				Err((<Error<T>>::ReentranceDenied.into(), 0))
+			} else {
+				// We ignore instantiate frames in our search for a cached contract.
+				// Otherwise it would be possible to recursively call a contract from its own
+				// constructor: We disallow calling not fully constructed contracts.
+				let cached_info = self
+					.frames()
+					.find(|f| f.entry_point == ExportedFunction::Call && f.account_id == to)
+					.and_then(|f| {
+						match &f.contract_info {
+							CachedContract::Cached(contract) => Some(contract.clone()),
+							_ => None,
+						}
+					});
+				self.push_frame(
+					FrameArgs::Call{dest: to.clone(), cached_info, delegated_call: None},
+					value,
+					gas_limit
+				)
+			};
+			match executable {
+				Ok(executable) => self.run(executable, input_data),
+				Err(err) => {
+					self.trace_not_entered(CallKind::Call, to, value, input_data, &err);
+					Err(err)
+				},
 			}
-			// We ignore instantiate frames in our search for a cached contract.
-			// Otherwise it would be possible to recursively call a contract from its own
-			// constructor: We disallow calling not fully constructed contracts.
-			let cached_info = self
-				.frames()
-				.find(|f| f.entry_point == ExportedFunction::Call && f.account_id == to)
-				.and_then(|f| {
-					match &f.contract_info {
-						CachedContract::Cached(contract) => Some(contract.clone()),
-						_ => None,
-					}
-				});
-			let executable = self.push_frame(
-				FrameArgs::Call{dest: to, cached_info},
-				value,
-				gas_limit
-			)?;
-			self.run(executable, input_data)
 		};
 
 		// We need to make sure to reset `allows_reentry` even on failure.
@@ -1094,6 +1082,39 @@ where
 		result
 	}
 
+	fn delegate_call(
+		&mut self,
+		code_hash: CodeHash<Self::T>,
+		input_data: Vec<u8>,
+	) -> Result<(ExecReturnValue, u32), (ExecError, u32)> {
+		let frame = self.top_frame();
+		let (dest, value) = (frame.account_id.clone(), frame.value_transferred);
+		let executable = E::from_storage(code_hash, &self.schedule, self.gas_meter())
+			.map_err(|e| (e.into(), 0))
+			.and_then(|executable| {
+				let caller = self.caller().clone();
+				let cached_info = Some(self.top_frame_mut().contract_info().clone());
+				// The delegated code runs with all the gas that is left: It acts as if it was part
+				// of the calling contract.
+				self.push_frame(
+					FrameArgs::Call {
+						dest: dest.clone(),
+						cached_info,
+						delegated_call: Some(DelegatedCall { executable, caller }),
+					},
+					value,
+					0,
+				)
+			});
+		match executable {
+			Ok(executable) => self.run(executable, input_data),
+			Err(err) => {
+				self.trace_not_entered(CallKind::DelegateCall, dest, value, input_data, &err);
+				Err(err)
+			},
+		}
+	}
+
 	fn instantiate(
 		&mut self,
 		gas_limit: Weight,
@@ -1103,18 +1124,30 @@ where
 		salt: &[u8],
 	) -> Result<(AccountIdOf<T>, ExecReturnValue, u32), (ExecError, u32)> {
 		let executable = E::from_storage(code_hash, &self.schedule, self.gas_meter())
-			.map_err(|e| (e.into(), 0))?;
-		let trie_seed = self.next_trie_seed();
-		let executable = self.push_frame(
-			FrameArgs::Instantiate {
-				sender: self.top_frame().account_id.clone(),
-				trie_seed,
-				executable,
-				salt,
+			.map_err(|e| (e.into(), 0))
+			.and_then(|executable| {
+				let trie_seed = self.next_trie_seed();
+				self.push_frame(
+					FrameArgs::Instantiate {
+						sender: self.top_frame().account_id.clone(),
+						trie_seed,
+						executable,
+						salt,
+					},
+					endowment,
+					gas_limit,
+				)
+			});
+		let executable = match executable {
+			Ok(executable) => executable,
+			Err(err) => {
+				let account_id = <Contracts<T>>::contract_address(
+					&self.top_frame().account_id, &code_hash, salt,
+				);
+				self.trace_not_entered(CallKind::Instantiate, account_id, endowment, input_data, &err);
+				return Err(err);
 			},
-			endowment,
-			gas_limit,
-		)?;
+		};
 		let account_id = self.top_frame().account_id.clone();
 		self.run(executable, input_data)
 			.map(|(ret, code_len)| (account_id, ret, code_len))
@@ -1127,9 +1160,13 @@ where
 		if self.is_recursive() {
 			return Err((Error::<T>::TerminatedWhileReentrant.into(), 0));
 		}
-		let frame = self.top_frame_mut();
-		let info = frame.terminate();
+		let frame = self.frames.last_mut().unwrap_or(&mut self.first_frame);
+		let mut info = frame.terminate();
 		Storage::<T>::queue_trie_for_deletion(&info).map_err(|e| (e, 0))?;
+		// All the storage is freed: The origin gets the whole deposit back.
+		let deposit = StorageDeposit::Refund(info.storage_deposit);
+		self.storage_meter.charge(&self.origin, &frame.account_id, &mut info, deposit)
+			.map_err(|e| (e, 0))?;
 		<Stack<'a, T, E>>::transfer(
 			true,
 			true,
@@ -1145,38 +1182,15 @@ where
 		Ok(code_len)
 	}
 
-	fn restore_to(
-		&mut self,
-		dest: AccountIdOf<Self::T>,
-		code_hash: CodeHash<Self::T>,
-		rent_allowance: BalanceOf<Self::T>,
-		delta: Vec<StorageKey>,
-	) -> Result<(u32, u32), (DispatchError, u32, u32)> {
-		if self.is_recursive() {
-			return Err((Error::<T>::TerminatedWhileReentrant.into(), 0, 0));
-		}
-		let origin_contract = self.top_frame_mut().contract_info().clone();
-		let result = Rent::<T, E>::restore_to(
-			&self.top_frame().account_id,
-			origin_contract,
-			dest.clone(),
-			code_hash.clone(),
-			rent_allowance,
-			delta,
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<u32, (DispatchError, u32)> {
+		let new_code_len = E::add_user(hash).map_err(|e| (e, 0))?;
+		let frame = self.top_frame_mut();
+		let prev_hash = mem::replace(&mut frame.contract_info().code_hash, hash);
+		let prev_code_len = E::remove_user(prev_hash);
+		Contracts::<T>::deposit_event(
+			Event::ContractCodeUpdated(frame.account_id.clone(), hash, prev_hash),
 		);
-		if let Ok(_) = result {
-			deposit_event::<Self::T>(
-				vec![],
-				Event::Restored(
-					self.top_frame().account_id.clone(),
-					dest,
-					code_hash,
-					rent_allowance,
-				),
-			);
-			self.top_frame_mut().terminate();
-		}
-		result
+		Ok(new_code_len.saturating_add(prev_code_len))
 	}
 
 	fn transfer(
@@ -1188,15 +1202,24 @@ where
 	}
 
 	fn get_storage(&mut self, key: &StorageKey) -> Option<Vec<u8>> {
-		Storage::<T>::read(&self.top_frame_mut().contract_info().trie_id, key)
+		let value = Storage::<T>::read(&self.top_frame_mut().contract_info().trie_id, key);
+		if let Some(tracer) = self.tracer.as_mut() {
+			tracer.storage_read(key, value.as_ref());
+		}
+		value
 	}
 
 	fn set_storage(&mut self, key: StorageKey, value: Option<Vec<u8>>) -> DispatchResult {
-		let block_number = self.block_number;
-		let frame = self.top_frame_mut();
-		Storage::<T>::write(
-			block_number, frame.contract_info(), &key, value,
-		)
+		let traced_value = self.tracer.as_ref().map(|_| value.clone());
+		let frame = self.frames.last_mut().unwrap_or(&mut self.first_frame);
+		let info = frame.contract_info.get(&frame.account_id);
+		let deposit = Storage::<T>::write(info, &key, value)?;
+		self.storage_meter.charge(&self.origin, &frame.account_id, info, deposit)?;
+		// Only writes that succeeded are recorded.
+		if let (Some(tracer), Some(value)) = (self.tracer.as_mut(), traced_value) {
+			tracer.storage_write(&key, value.as_ref());
+		}
+		Ok(())
 	}
 
 	fn address(&self) -> &T::AccountId {
@@ -1204,6 +1227,9 @@ where
 	}
 
 	fn caller(&self) -> &T::AccountId {
+		if let Some(caller) = &self.top_frame().delegate_caller {
+			return caller;
+		}
 		self.frames().nth(1).map(|f| &f.account_id).unwrap_or(&self.origin)
 	}
 
@@ -1227,10 +1253,6 @@ where
 		T::Currency::minimum_balance()
 	}
 
-	fn tombstone_deposit(&self) -> BalanceOf<T> {
-		T::TombstoneDeposit::get()
-	}
-
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
 		deposit_event::<Self::T>(
 			topics,
@@ -1238,14 +1260,6 @@ where
 		);
 	}
 
-	fn set_rent_allowance(&mut self, rent_allowance: BalanceOf<T>) {
-		self.top_frame_mut().contract_info().rent_allowance = rent_allowance;
-	}
-
-	fn rent_allowance(&mut self) -> BalanceOf<T> {
-		self.top_frame_mut().contract_info().rent_allowance
-	}
-
 	fn block_number(&self) -> T::BlockNumber { self.block_number }
 
 	fn max_value_size(&self) -> u32 {
@@ -1260,24 +1274,6 @@ where
 		&self.schedule
 	}
 
-	fn rent_params(&self) -> &RentParams<Self::T> {
-		&self.top_frame().rent_params
-	}
-
-	fn rent_status(&mut self, at_refcount: u32) -> RentStatus<Self::T> {
-		let frame = self.top_frame_mut();
-		let balance = T::Currency::free_balance(&frame.account_id);
-		let code_size = frame.rent_params.code_size;
-		let refcount = frame.rent_params.code_refcount;
-		<Rent<T, E>>::rent_status(
-			&balance,
-			&frame.contract_info(),
-			code_size,
-			refcount,
-			at_refcount,
-		)
-	}
-
 	fn gas_meter(&mut self) -> &mut GasMeter<Self::T> {
 		&mut self.top_frame_mut().nested_meter
 	}
@@ -1292,6 +1288,11 @@ where
 			false
 		}
 	}
+
+	#[cfg(test)]
+	fn contract_info(&mut self) -> &mut ContractInfo<Self::T> {
+		self.top_frame_mut().contract_info()
+	}
 }
 
 fn deposit_event<T: Config>(
@@ -1328,7 +1329,7 @@ mod tests {
 	use super::*;
 	use crate::{
 		gas::GasMeter, tests::{ExtBuilder, Test, Event as MetaEvent},
-		storage::Storage,
+		storage::{Storage, StorageMeter},
 		tests::{
 			ALICE, BOB, CHARLIE,
 			test_utils::{place_contract, set_balance, get_balance},
@@ -1342,7 +1343,7 @@ mod tests {
 	use assert_matches::assert_matches;
 	use std::{cell::RefCell, collections::HashMap, rc::Rc};
 	use pretty_assertions::{assert_eq, assert_ne};
-	use pallet_contracts_primitives::ReturnFlags;
+	use pallet_contracts_primitives::{ReturnFlags, CallTrace, StorageAccess};
 	use frame_support::{assert_ok, assert_err};
 
 	type MockStack<'a> = Stack<'a, Test, MockExecutable>;
@@ -1428,14 +1429,9 @@ mod tests {
 			});
 		}
 
-		fn refcount(code_hash: &CodeHash<Test>) -> u32 {
+		fn refcount(code_hash: &CodeHash<Test>) -> u64 {
 			LOADER.with(|loader| {
-				loader
-					.borrow()
-					.map
-					.get(code_hash)
-					.expect("code_hash does not exist")
-					.refcount()
+				loader.borrow().map.get(code_hash).map(|e| e.refcount).unwrap_or(0)
 			})
 		}
 	}
@@ -1499,14 +1495,6 @@ mod tests {
 		fn code_len(&self) -> u32 {
 			0
 		}
-
-		fn aggregate_code_len(&self) -> u32 {
-			0
-		}
-
-		fn refcount(&self) -> u32 {
-			self.refcount as u32
-		}
 	}
 
 	fn exec_success() -> ExecResult {
@@ -1536,7 +1524,7 @@ mod tests {
 
 			assert_matches!(
 				MockStack::run_call(
-					ALICE, BOB, &mut gas_meter, &schedule, value, vec![], None,
+					ALICE, BOB, &mut gas_meter, &mut StorageMeter::new(None), &schedule, value, vec![], None, None,
 				),
 				Ok(_)
 			);
@@ -1591,17 +1579,17 @@ mod tests {
 				origin.clone(),
 				dest.clone(),
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				55,
 				vec![],
 				None,
+				None,
 			).unwrap();
 
 			assert!(!output.0.is_success());
 			assert_eq!(get_balance(&origin), 100);
-
-			// the rent is still charged
-			assert!(get_balance(&dest) < balance);
+			assert_eq!(get_balance(&dest), balance);
 		});
 	}
 
@@ -1651,10 +1639,12 @@ mod tests {
 				origin,
 				dest,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![],
 				None,
+				None,
 			);
 
 			let output = result.unwrap();
@@ -1682,10 +1672,12 @@ mod tests {
 				origin,
 				dest,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![],
 				None,
+				None,
 			);
 
 			let output = result.unwrap();
@@ -1710,10 +1702,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![1, 2, 3, 4],
 				None,
+				None,
 			);
 			assert_matches!(result, Ok(_));
 		});
@@ -1741,11 +1735,13 @@ mod tests {
 				ALICE,
 				executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(None),
 				&schedule,
 				subsistence * 3,
 				vec![1, 2, 3, 4],
 				&[],
 				None,
+				None,
 			);
 			assert_matches!(result, Ok(_));
 		});
@@ -1791,10 +1787,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				value,
 				vec![],
 				None,
+				None,
 			);
 
 			assert_matches!(result, Ok(_));
@@ -1841,10 +1839,12 @@ mod tests {
 				origin.clone(),
 				dest.clone(),
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![],
 				None,
+				None,
 			);
 
 			assert_matches!(result, Ok(_));
@@ -1881,10 +1881,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![],
 				None,
+				None,
 			);
 
 			assert_matches!(result, Ok(_));
@@ -1907,11 +1909,13 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(None),
 					&schedule,
 					0, // <- zero endowment
 					vec![],
 					&[],
 					None,
+					None,
 				),
 				Err(_)
 			);
@@ -1938,11 +1942,13 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(None),
 					&schedule,
 					100,
 					vec![],
 					&[],
 					None,
+					None,
 				),
 				Ok((address, ref output)) if output.data == Bytes(vec![80, 65, 83, 83]) => address
 			);
@@ -1976,11 +1982,13 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(None),
 					&schedule,
 					100,
 					vec![],
 					&[],
 					None,
+					None,
 				),
 				Ok((address, ref output)) if output.data == Bytes(vec![70, 65, 73, 76]) => address
 			);
@@ -2020,7 +2028,7 @@ mod tests {
 
 			assert_matches!(
 				MockStack::run_call(
-					ALICE, BOB, &mut GasMeter::<Test>::new(GAS_LIMIT), &schedule, 20, vec![], None,
+					ALICE, BOB, &mut GasMeter::<Test>::new(GAS_LIMIT), &mut StorageMeter::new(None), &schedule, 20, vec![], None, None,
 				),
 				Ok(_)
 			);
@@ -2071,7 +2079,7 @@ mod tests {
 
 			assert_matches!(
 				MockStack::run_call(
-					ALICE, BOB, &mut GasMeter::<Test>::new(GAS_LIMIT), &schedule, 20, vec![], None,
+					ALICE, BOB, &mut GasMeter::<Test>::new(GAS_LIMIT), &mut StorageMeter::new(None), &schedule, 20, vec![], None, None,
 				),
 				Ok(_)
 			);
@@ -2105,11 +2113,13 @@ mod tests {
 						ALICE,
 						executable,
 						&mut gas_meter,
+						&mut StorageMeter::new(None),
 						&schedule,
 						100,
 						vec![],
 						&[],
 						None,
+						None,
 					),
 					Err(Error::<Test>::TerminatedInConstructor.into())
 				);
@@ -2122,157 +2132,38 @@ mod tests {
 	}
 
 	#[test]
-	fn rent_allowance() {
-		let rent_allowance_ch = MockLoader::insert(Constructor, |ctx, _| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let allowance = subsistence * 3;
-			assert_eq!(ctx.ext.rent_allowance(), <BalanceOf<Test>>::max_value());
-			ctx.ext.set_rent_allowance(allowance);
-			assert_eq!(ctx.ext.rent_allowance(), allowance);
-			exec_success()
-		});
-
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			let executable = MockExecutable::from_storage(
-				rent_allowance_ch, &schedule, &mut gas_meter
-			).unwrap();
-			set_balance(&ALICE, subsistence * 10);
-
-			let result = MockStack::run_instantiate(
-				ALICE,
-				executable,
-				&mut gas_meter,
-				&schedule,
-				subsistence * 5,
-				vec![],
-				&[],
-				None,
-			);
-			assert_matches!(result, Ok(_));
-		});
-	}
-
-	#[test]
-	fn rent_params_works() {
-		let code_hash = MockLoader::insert(Call, |ctx, executable| {
-			let address = ctx.ext.address();
-			let contract = <ContractInfoOf<Test>>::get(address)
-				.and_then(|c| c.get_alive())
-				.unwrap();
-			assert_eq!(ctx.ext.rent_params(), &RentParams::new(address, &0, &contract, executable));
+	fn terminate_refunds_storage_deposit() {
+		let code_hash = MockLoader::insert(Call, |ctx, _| {
+			assert_ok!(ctx.ext.set_storage([1; 32], Some(vec![1, 2, 3, 4])));
+			assert_ne!(ctx.ext.contract_info().storage_deposit, 0);
+			ctx.ext.terminate(&CHARLIE).map_err(|(err, _)| err)?;
 			exec_success()
 		});
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
 			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
+			let mut storage_meter = StorageMeter::new(None);
+			set_balance(&ALICE, 100);
 			place_contract(&BOB, code_hash);
-			MockStack::run_call(
+			let balance = get_balance(&BOB);
+
+			let result = MockStack::run_call(
 				ALICE,
 				BOB,
-				&mut gas_meter,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut storage_meter,
 				&schedule,
 				0,
 				vec![],
 				None,
-			).unwrap();
-		});
-	}
-
-	#[test]
-	fn rent_params_snapshotted() {
-		let code_hash = MockLoader::insert(Call, |ctx, executable| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let address = ctx.ext.address();
-			let contract = <ContractInfoOf<Test>>::get(address)
-				.and_then(|c| c.get_alive())
-				.unwrap();
-			let rent_params = RentParams::new(address, &0, &contract, executable);
-
-			// Changing the allowance during the call: rent params stay unchanged.
-			let allowance = 42;
-			assert_ne!(allowance, rent_params.rent_allowance);
-			ctx.ext.set_rent_allowance(allowance);
-			assert_eq!(ctx.ext.rent_params(), &rent_params);
-
-			// Creating another instance from the same code_hash increases the refcount.
-			// This is also not reflected in the rent params.
-			assert_eq!(MockLoader::refcount(&executable.code_hash), 1);
-			ctx.ext.instantiate(
-				0,
-				executable.code_hash,
-				subsistence * 25,
-				vec![],
-				&[],
-			).unwrap();
-			assert_eq!(MockLoader::refcount(&executable.code_hash), 2);
-			assert_eq!(ctx.ext.rent_params(), &rent_params);
-
-			exec_success()
-		});
-
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 100);
-			place_contract(&BOB, code_hash);
-			MockStack::run_call(
-				ALICE,
-				BOB,
-				&mut gas_meter,
-				&schedule,
-				subsistence * 50,
-				vec![],
 				None,
-			).unwrap();
-		});
-	}
-
-	#[test]
-	fn rent_status_works() {
-		let code_hash = MockLoader::insert(Call, |ctx, _| {
-			assert_eq!(ctx.ext.rent_status(0), RentStatus {
-				max_deposit: 80000,
-				current_deposit: 80000,
-				custom_refcount_deposit: None,
-				max_rent: 32,
-				current_rent: 32,
-				custom_refcount_rent: None,
-				_reserved: None,
-			});
-			assert_eq!(ctx.ext.rent_status(1), RentStatus {
-				max_deposit: 80000,
-				current_deposit: 80000,
-				custom_refcount_deposit: Some(80000),
-				max_rent: 32,
-				current_rent: 32,
-				custom_refcount_rent: Some(32),
-				_reserved: None,
-			});
-			exec_success()
-		});
+			);
+			assert_matches!(result, Ok(_));
 
-		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
-			let schedule = <Test as Config>::Schedule::get();
-			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
-			place_contract(&BOB, code_hash);
-			MockStack::run_call(
-				ALICE,
-				BOB,
-				&mut gas_meter,
-				&schedule,
-				0,
-				vec![],
-				None,
-			).unwrap();
+			// The deposit went back to the origin and not to the beneficiary.
+			assert!(storage_meter.total().is_zero());
+			assert_eq!(get_balance(&ALICE), 100);
+			assert_eq!(get_balance(&CHARLIE), balance);
 		});
 	}
 
@@ -2288,16 +2179,14 @@ mod tests {
 		// are made before calling into CHARLIE are not discarded.
 		let code_bob = MockLoader::insert(Call, |ctx, _| {
 			if ctx.input_data[0] == 0 {
-				let original_allowance = ctx.ext.rent_allowance();
-				let changed_allowance = <BalanceOf<Test>>::max_value() / 2;
-				assert_ne!(original_allowance, changed_allowance);
-				ctx.ext.set_rent_allowance(changed_allowance);
+				let info = ctx.ext.contract_info();
+				assert_eq!(info.storage_deposit, 0);
+				info.storage_deposit = 42;
 				assert_eq!(
 					ctx.ext.call(0, CHARLIE, 0, vec![], true).map(|v| v.0).map_err(|e| e.0),
 					exec_trapped()
 				);
-				assert_eq!(ctx.ext.rent_allowance(), changed_allowance);
-				assert_ne!(ctx.ext.rent_allowance(), original_allowance);
+				assert_eq!(ctx.ext.contract_info().storage_deposit, 42);
 			}
 			exec_success()
 		});
@@ -2316,10 +2205,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![0],
 				None,
+				None,
 			);
 			assert_matches!(result, Ok(_));
 		});
@@ -2350,11 +2241,13 @@ mod tests {
 				ALICE,
 				executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(None),
 				&schedule,
 				subsistence * 3,
 				vec![],
 				&[],
 				None,
+				None,
 			);
 			assert_matches!(result, Ok(_));
 		});
@@ -2380,10 +2273,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut gas_meter,
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![],
 				Some(&mut debug_buffer),
+				None,
 			).unwrap();
 		});
 
@@ -2410,10 +2305,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut gas_meter,
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				vec![],
 				Some(&mut debug_buffer),
+				None,
 			);
 			assert!(result.is_err());
 		});
@@ -2443,10 +2340,12 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
 				&schedule,
 				0,
 				CHARLIE.encode(),
 				None,
+				None,
 			));
 
 			// Calling into oneself fails
@@ -2455,10 +2354,12 @@ mod tests {
 					ALICE,
 					BOB,
 					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut StorageMeter::new(None),
 					&schedule,
 					0,
 					BOB.encode(),
 					None,
+					None,
 				).map_err(|e| e.0.error),
 				<Error<Test>>::ReentranceDenied,
 			);
@@ -2491,13 +2392,355 @@ mod tests {
 					ALICE,
 					BOB,
 					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut StorageMeter::new(None),
 					&schedule,
 					0,
 					vec![0],
 					None,
+					None,
 				).map_err(|e| e.0.error),
 				<Error<Test>>::ReentranceDenied,
 			);
 		});
 	}
+
+	#[test]
+	fn delegate_call_works() {
+		let value = 55;
+		let delegated_ch = MockLoader::insert(Call, move |ctx, _| {
+			// The delegated code runs in the context of the delegating contract.
+			assert_eq!(ctx.ext.address(), &BOB);
+			assert_eq!(ctx.ext.caller(), &ALICE);
+			assert_eq!(ctx.ext.value_transferred(), value);
+			assert_eq!(ctx.input_data, vec![1, 2, 3]);
+			ctx.ext.set_storage([1; 32], Some(vec![42]))?;
+			exec_success()
+		});
+		let bob_ch = MockLoader::insert(Call, move |ctx, _| {
+			ctx.ext.delegate_call(delegated_ch, vec![1, 2, 3]).map(|v| v.0).map_err(|e| e.0)?;
+			assert_eq!(ctx.ext.get_storage(&[1; 32]), Some(vec![42]));
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			set_balance(&ALICE, 100);
+			place_contract(&BOB, bob_ch);
+			let balance = get_balance(&BOB);
+
+			assert_ok!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				value,
+				vec![],
+				None,
+				None,
+			));
+
+			// The value is only transferred once. On top of that the origin pays the
+			// deposit for the one byte item written by the delegated code.
+			let deposit = 1 + 2;
+			assert_eq!(get_balance(&ALICE), 100 - value - deposit);
+			assert_eq!(get_balance(&BOB), balance + value);
+
+			// The delegated code wrote to the storage of the delegating contract.
+			let info = <ContractInfoOf<Test>>::get(&BOB).unwrap();
+			assert_eq!(info.code_hash, bob_ch);
+			assert_eq!(Storage::<Test>::read(&info.trie_id, &[1; 32]), Some(vec![42]));
+		});
+	}
+
+	#[test]
+	fn delegate_call_missing_code_fails() {
+		let bob_ch = MockLoader::insert(Call, |ctx, _| {
+			let missing = <Test as frame_system::Config>::Hash::repeat_byte(0xff);
+			assert_eq!(
+				ctx.ext.delegate_call(missing, vec![]).map_err(|e| e.0.error),
+				Err(<Error<Test>>::CodeNotFound.into()),
+			);
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+
+			assert_ok!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				0,
+				vec![],
+				None,
+				None,
+			));
+		});
+	}
+
+	#[test]
+	fn set_code_hash_works() {
+		let new_ch = MockLoader::insert(Call, |_, _| exec_success());
+		let bob_ch = MockLoader::insert(Call, move |ctx, _| {
+			ctx.ext.set_code_hash(new_ch).map_err(|e| e.0)?;
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+			assert_eq!(MockLoader::refcount(&bob_ch), 1);
+			assert_eq!(MockLoader::refcount(&new_ch), 1);
+
+			assert_ok!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				0,
+				vec![],
+				None,
+				None,
+			));
+
+			assert_eq!(<ContractInfoOf<Test>>::get(&BOB).unwrap().code_hash, new_ch);
+			assert_eq!(MockLoader::refcount(&bob_ch), 0);
+			assert_eq!(MockLoader::refcount(&new_ch), 2);
+			assert_eq!(&events(), &[
+				Event::ContractCodeUpdated(BOB, new_ch, bob_ch),
+			]);
+		});
+	}
+
+	#[test]
+	fn tracer_records_call_tree() {
+		let delegated_ch = MockLoader::insert(Call, |ctx, _| {
+			assert_eq!(ctx.ext.get_storage(&[1; 32]), Some(vec![1]));
+			exec_success()
+		});
+		let bob_ch = MockLoader::insert(Call, move |ctx, _| {
+			ctx.ext.set_storage([1; 32], Some(vec![1]))?;
+			assert_eq!(ctx.ext.get_storage(&[2; 32]), None);
+			let output = ctx.ext.call(0, CHARLIE, 0, vec![7], true).map_err(|e| e.0)?;
+			assert!(!output.0.is_success());
+			ctx.ext.delegate_call(delegated_ch, vec![3]).map_err(|e| e.0)?;
+			Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Bytes(vec![9]) })
+		});
+		let charlie_ch = MockLoader::insert(Call, |_, _| {
+			Ok(ExecReturnValue { flags: ReturnFlags::REVERT, data: Bytes(vec![2]) })
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			set_balance(&ALICE, 100);
+			place_contract(&BOB, bob_ch);
+			place_contract(&CHARLIE, charlie_ch);
+			let mut tracer = CallTracer::new();
+
+			assert_ok!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				10,
+				vec![],
+				None,
+				Some(&mut tracer),
+			));
+
+			let mut trace = tracer.into_trace().unwrap();
+			let nested_gas: Weight = trace.calls.iter().map(|c| c.gas_consumed).sum();
+			assert!(trace.gas_consumed >= nested_gas);
+			trace.gas_consumed = 0;
+			trace.calls.iter_mut().for_each(|c| c.gas_consumed = 0);
+
+			let success = |data: Vec<u8>| Ok(ExecReturnValue {
+				flags: ReturnFlags::empty(),
+				data: Bytes(data),
+			});
+			assert_eq!(trace, CallTrace {
+				kind: CallKind::Call,
+				caller: ALICE,
+				callee: BOB,
+				value: 10,
+				input: Bytes(vec![]),
+				gas_consumed: 0,
+				storage: vec![
+					StorageAccess::Write { key: [1; 32].into(), value: Some(Bytes(vec![1])) },
+					StorageAccess::Read { key: [2; 32].into(), value: None },
+				],
+				result: success(vec![9]),
+				calls: vec![
+					CallTrace {
+						kind: CallKind::Call,
+						caller: BOB,
+						callee: CHARLIE,
+						value: 0,
+						input: Bytes(vec![7]),
+						gas_consumed: 0,
+						storage: vec![],
+						result: Ok(ExecReturnValue {
+							flags: ReturnFlags::REVERT,
+							data: Bytes(vec![2]),
+						}),
+						calls: vec![],
+					},
+					CallTrace {
+						kind: CallKind::DelegateCall,
+						caller: ALICE,
+						callee: BOB,
+						value: 10,
+						input: Bytes(vec![3]),
+						gas_consumed: 0,
+						storage: vec![
+							StorageAccess::Read { key: [1; 32].into(), value: Some(Bytes(vec![1])) },
+						],
+						result: success(vec![]),
+						calls: vec![],
+					},
+				],
+			});
+		});
+	}
+
+	#[test]
+	fn tracer_records_failed_call() {
+		let bob_ch = MockLoader::insert(Call, |_, _| exec_trapped());
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+			let mut tracer = CallTracer::new();
+
+			assert!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				0,
+				vec![1],
+				None,
+				Some(&mut tracer),
+			).is_err());
+
+			let trace = tracer.into_trace().unwrap();
+			assert_eq!(trace.callee, BOB);
+			assert_eq!(trace.result, Err(<Error<Test>>::ContractTrapped.into()));
+		});
+	}
+
+	#[test]
+	fn tracer_records_nested_frames_that_failed_to_start() {
+		let bob_ch = MockLoader::insert(Call, |ctx, _| {
+			assert!(ctx.ext.call(0, CHARLIE, 0, vec![1], true).is_err());
+			let missing = <Test as frame_system::Config>::Hash::repeat_byte(0xff);
+			assert!(ctx.ext.delegate_call(missing, vec![2]).is_err());
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			place_contract(&BOB, bob_ch);
+			let mut tracer = CallTracer::new();
+
+			assert_ok!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				0,
+				vec![],
+				None,
+				Some(&mut tracer),
+			));
+
+			let trace = tracer.into_trace().unwrap();
+			assert_eq!(trace.calls, vec![
+				CallTrace {
+					kind: CallKind::Call,
+					caller: BOB,
+					callee: CHARLIE,
+					value: 0,
+					input: Bytes(vec![1]),
+					gas_consumed: 0,
+					storage: vec![],
+					result: Err(<Error<Test>>::ContractNotFound.into()),
+					calls: vec![],
+				},
+				CallTrace {
+					kind: CallKind::DelegateCall,
+					caller: ALICE,
+					callee: BOB,
+					value: 0,
+					input: Bytes(vec![2]),
+					gas_consumed: 0,
+					storage: vec![],
+					result: Err(<Error<Test>>::CodeNotFound.into()),
+					calls: vec![],
+				},
+			]);
+		});
+	}
+
+	#[test]
+	fn tracer_records_only_successful_writes() {
+		let bob_ch = MockLoader::insert(Call, |ctx, _| {
+			assert_eq!(
+				ctx.ext.set_storage([1; 32], Some(vec![1])),
+				Err(<Error<Test>>::StorageDepositLimitExhausted.into()),
+			);
+			exec_success()
+		});
+
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			set_balance(&ALICE, 1_000);
+			place_contract(&BOB, bob_ch);
+			let mut tracer = CallTracer::new();
+
+			assert_ok!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(Some(0)),
+				&schedule,
+				0,
+				vec![],
+				None,
+				Some(&mut tracer),
+			));
+
+			assert_eq!(tracer.into_trace().unwrap().storage, vec![]);
+		});
+	}
+
+	#[test]
+	fn tracer_records_nothing_for_missing_contract() {
+		ExtBuilder::default().build().execute_with(|| {
+			let schedule = <Test as Config>::Schedule::get();
+			let mut tracer = CallTracer::new();
+
+			assert!(MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(None),
+				&schedule,
+				0,
+				vec![],
+				None,
+				Some(&mut tracer),
+			).is_err());
+
+			assert_eq!(tracer.into_trace(), None);
+		});
+	}
 }
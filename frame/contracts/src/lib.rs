@@ -39,6 +39,14 @@
 //! Finally, when an account is reaped, its associated code and storage of the smart-contract account
 //! will also be deleted.
 //!
+//! ### Storage Deposit
+//!
+//! Contracts do not pay rent for the storage they occupy. Instead, the origin of a call pays a
+//! deposit for every byte and every item that the call adds to the storage of the contracts it
+//! executes. The deposit is reserved on the contract's account and refunded to the origin of
+//! the call that frees up the storage again. Callers can cap the amount they are willing to pay
+//! by supplying a `storage_deposit_limit`.
+//!
 //! ### Gas
 //!
 //! Senders must specify a gas limit with every call, as all instructions invoked by the smart-contract require gas.
@@ -66,7 +74,10 @@
 //! * [`Pallet::instantiate`] - The same as `instantiate_with_code` but instead of uploading new
 //! code an existing `code_hash` is supplied.
 //! * [`Pallet::call`] - Makes a call to an account, optionally transferring some balance.
-//! * [`Pallet::claim_surcharge`] - Evict a contract that cannot pay rent anymore.
+//! * [`Pallet::upload_code`] - Uploads new code without instantiating a contract from it. The
+//! uploader pays a deposit for storing the code.
+//! * [`Pallet::remove_code`] - Removes uploaded code that is no longer used by any contract and
+//! refunds the deposit to its uploader.
 //!
 //! ## Usage
 //!
@@ -85,10 +96,10 @@ mod gas;
 mod storage;
 mod exec;
 mod wasm;
-mod rent;
 mod benchmarking;
 mod schedule;
 mod migration;
+mod trace;
 
 pub mod chain_extension;
 pub mod weights;
@@ -104,35 +115,33 @@ pub use crate::{
 use crate::{
 	gas::GasMeter,
 	exec::{Stack as ExecStack, Executable},
-	rent::Rent,
-	storage::{Storage, DeletedContract, ContractInfo, AliveContractInfo, TombstoneContractInfo},
+	storage::{Storage, StorageMeter, DeletedContract, ContractInfo},
 	weights::WeightInfo,
-	wasm::PrefabWasmModule,
+	wasm::{PrefabWasmModule, OwnerInfo},
+	trace::CallTracer,
 };
 use sp_core::{Bytes, crypto::UncheckedFrom};
-use sp_std::prelude::*;
+use sp_std::{prelude::*, convert::TryInto};
 use sp_runtime::{
 	traits::{
-		Hash, StaticLookup, Convert, Saturating, Zero,
+		Hash, StaticLookup, Convert,
 	},
-	Perbill,
 };
 use frame_support::{
-	traits::{OnUnbalanced, Currency, Get, Time, Randomness},
-	weights::{Weight, PostDispatchInfo, WithPostDispatchInfo},
+	traits::{Currency, ReservableCurrency, Get, Time, Randomness},
+	weights::Weight,
 };
 use frame_system::Pallet as System;
 use pallet_contracts_primitives::{
-	RentProjectionResult, GetStorageResult, ContractAccessError, ContractExecResult,
-	ContractInstantiateResult, Code, InstantiateReturnValue,
+	GetStorageResult, ContractAccessError, ContractExecResult,
+	ContractInstantiateResult, Code, InstantiateReturnValue, ContractEvent,
+	ContractTracedExecResult,
 };
 
 type CodeHash<T> = <T as frame_system::Config>::Hash;
 type TrieId = Vec<u8>;
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-type NegativeImbalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -149,14 +158,13 @@ pub mod pallet {
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
 
 		/// The currency in which fees are paid and contract balances are held.
-		type Currency: Currency<Self::AccountId>;
+		///
+		/// Storage deposits are reserved on the contract's account.
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Handler for rent payments.
-		type RentPayment: OnUnbalanced<NegativeImbalanceOf<Self>>;
-
 		/// Used to answer contracts' queries regarding the current weight price. This is **not**
 		/// used to calculate the actual fee and is only for informational purposes.
 		type WeightPrice: Convert<Weight, BalanceOf<Self>>;
@@ -172,55 +180,21 @@ pub mod pallet {
 		#[pallet::constant]
 		type Schedule: Get<Schedule<Self>>;
 
-		/// Number of block delay an extrinsic claim surcharge has.
+		/// The amount of balance a caller has to pay for each byte of storage.
 		///
-		/// When claim surcharge is called by an extrinsic the rent is checked
-		/// for current_block - delay
-		#[pallet::constant]
-		type SignedClaimHandicap: Get<Self::BlockNumber>;
-
-		/// The minimum amount required to generate a tombstone.
-		#[pallet::constant]
-		type TombstoneDeposit: Get<BalanceOf<Self>>;
-
-		/// The balance every contract needs to deposit to stay alive indefinitely.
-		///
-		/// This is different from the [`Self::TombstoneDeposit`] because this only needs to be
-		/// deposited while the contract is alive. Costs for additional storage are added to
-		/// this base cost.
-		///
-		/// This is a simple way to ensure that contracts with empty storage eventually get deleted by
-		/// making them pay rent. This creates an incentive to remove them early in order to save rent.
-		#[pallet::constant]
-		type DepositPerContract: Get<BalanceOf<Self>>;
-
-		/// The balance a contract needs to deposit per storage byte to stay alive indefinitely.
+		/// # Note
 		///
-		/// Let's suppose the deposit is 1,000 BU (balance units)/byte and the rent is 1 BU/byte/day,
-		/// then a contract with 1,000,000 BU that uses 1,000 bytes of storage would pay no rent.
-		/// But if the balance reduced to 500,000 BU and the storage stayed the same at 1,000,
-		/// then it would pay 500 BU/day.
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type DepositPerStorageByte: Get<BalanceOf<Self>>;
+		type DepositPerByte: Get<BalanceOf<Self>>;
 
-		/// The balance a contract needs to deposit per storage item to stay alive indefinitely.
+		/// The amount of balance a caller has to pay for each storage item.
 		///
-		/// It works the same as [`Self::DepositPerStorageByte`] but for storage items.
-		#[pallet::constant]
-		type DepositPerStorageItem: Get<BalanceOf<Self>>;
-
-		/// The fraction of the deposit that should be used as rent per block.
+		/// # Note
 		///
-		/// When a contract hasn't enough balance deposited to stay alive indefinitely it needs
-		/// to pay per block for the storage it consumes that is not covered by the deposit.
-		/// This determines how high this rent payment is per block as a fraction of the deposit.
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type RentFraction: Get<Perbill>;
-
-		/// Reward that is received by the party whose touch has led
-		/// to removal of a contract.
-		#[pallet::constant]
-		type SurchargeReward: Get<BalanceOf<Self>>;
+		type DepositPerItem: Get<BalanceOf<Self>>;
 
 		/// The type of the call stack determines the maximum nesting depth of contract calls.
 		///
@@ -275,6 +249,10 @@ pub mod pallet {
 		/// * If the account is a regular account, any value will be transferred.
 		/// * If no account exists and the call value is not less than `existential_deposit`,
 		/// a regular account will be created and any value will be transferred.
+		///
+		/// The `storage_deposit_limit` caps the net amount of balance that is charged from the
+		/// `origin` in order to pay for the storage that is added by this call. `None` means
+		/// that there is no limit apart from the free balance of the `origin`.
 		#[pallet::weight(T::WeightInfo::call(T::Schedule::get().limits.code_len / 1024)
 			.saturating_add(*gas_limit)
 		)]
@@ -283,14 +261,17 @@ pub mod pallet {
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] value: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			data: Vec<u8>
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
+			let mut storage_meter = StorageMeter::new(storage_deposit_limit);
 			let schedule = T::Schedule::get();
 			let (result, code_len) = match ExecStack::<T, PrefabWasmModule<T>>::run_call(
-				origin, dest, &mut gas_meter, &schedule, value, data, None,
+				origin, dest, &mut gas_meter, &mut storage_meter, &schedule,
+				value, data, None, None,
 			) {
 				Ok((output, len)) => (Ok(output), len),
 				Err((err, len)) => (Err(err), len),
@@ -307,6 +288,8 @@ pub mod pallet {
 		///
 		/// * `endowment`: The balance to transfer from the `origin` to the newly created contract.
 		/// * `gas_limit`: The gas limit enforced when executing the constructor.
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged from the
+		///   `origin` to pay for the storage added by the constructor. See [`Self::call`].
 		/// * `code`: The contract code to deploy in raw bytes.
 		/// * `data`: The input data to pass to the contract constructor.
 		/// * `salt`: Used for the address derivation. See [`Pallet::contract_address`].
@@ -330,6 +313,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			code: Vec<u8>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
@@ -338,12 +322,14 @@ pub mod pallet {
 			let code_len = code.len() as u32;
 			ensure!(code_len <= T::Schedule::get().limits.code_len, Error::<T>::CodeTooLarge);
 			let mut gas_meter = GasMeter::new(gas_limit);
+			let mut storage_meter = StorageMeter::new(storage_deposit_limit);
 			let schedule = T::Schedule::get();
 			let executable = PrefabWasmModule::from_code(code, &schedule)?;
 			let code_len = executable.code_len();
 			ensure!(code_len <= T::Schedule::get().limits.code_len, Error::<T>::CodeTooLarge);
 			let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
-				origin, executable, &mut gas_meter, &schedule, endowment, data, &salt, None,
+				origin, executable, &mut gas_meter, &mut storage_meter, &schedule,
+				endowment, data, &salt, None, None,
 			).map(|(_address, output)| output);
 			gas_meter.into_dispatch_result(
 				result,
@@ -366,17 +352,20 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<BalanceOf<T>>,
 			code_hash: CodeHash<T>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let mut gas_meter = GasMeter::new(gas_limit);
+			let mut storage_meter = StorageMeter::new(storage_deposit_limit);
 			let schedule = T::Schedule::get();
 			let executable = PrefabWasmModule::from_storage(code_hash, &schedule, &mut gas_meter)?;
 			let code_len = executable.code_len();
 			let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
-				origin, executable, &mut gas_meter, &schedule, endowment, data, &salt, None,
+				origin, executable, &mut gas_meter, &mut storage_meter, &schedule,
+				endowment, data, &salt, None, None,
 			).map(|(_address, output)| output);
 			gas_meter.into_dispatch_result(
 				result,
@@ -384,58 +373,47 @@ pub mod pallet {
 			)
 		}
 
-		/// Allows block producers to claim a small reward for evicting a contract. If a block
-		/// producer fails to do so, a regular users will be allowed to claim the reward.
+		/// Upload new `code` without instantiating a contract from it.
 		///
-		/// In case of a successful eviction no fees are charged from the sender. However, the
-		/// reward is capped by the total amount of rent that was paid by the contract while
-		/// it was alive.
+		/// The code is stored under its hash and can be used by [`Self::instantiate`] or
+		/// by contracts calling `seal_instantiate`. The sender pays a deposit for storing
+		/// the code which is reserved from its balance. The deposit may not exceed the
+		/// `storage_deposit_limit` if one is supplied.
 		///
-		/// If contract is not evicted as a result of this call, [`Error::ContractNotEvictable`]
-		/// is returned and the sender is not eligible for the reward.
-		#[pallet::weight(T::WeightInfo::claim_surcharge(T::Schedule::get().limits.code_len / 1024))]
-		pub fn claim_surcharge(
+		/// Other than code stored via [`Self::instantiate_with_code`], uploaded code is not
+		/// removed when the last contract using it is removed. It stays in storage until the
+		/// sender calls [`Self::remove_code`].
+		///
+		/// Fails with `CodeAlreadyExists` if the code was already stored.
+		#[pallet::weight(T::WeightInfo::upload_code(code.len() as u32 / 1024))]
+		pub fn upload_code(
 			origin: OriginFor<T>,
-			dest: T::AccountId,
-			aux_sender: Option<T::AccountId>
-		) -> DispatchResultWithPostInfo {
-			let origin = origin.into();
-			let (signed, rewarded) = match (origin, aux_sender) {
-				(Ok(frame_system::RawOrigin::Signed(account)), None) => {
-					(true, account)
-				},
-				(Ok(frame_system::RawOrigin::None), Some(aux_sender)) => {
-					(false, aux_sender)
-				},
-				_ => Err(Error::<T>::InvalidSurchargeClaim)?,
-			};
-
-			// Add some advantage for block producers (who send unsigned extrinsics) by
-			// adding a handicap: for signed extrinsics we use a slightly older block number
-			// for the eviction check. This can be viewed as if we pushed regular users back in past.
-			let handicap = if signed {
-				T::SignedClaimHandicap::get()
-			} else {
-				Zero::zero()
-			};
+			code: Vec<u8>,
+			storage_deposit_limit: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let schedule = T::Schedule::get();
+			ensure!(code.len() as u32 <= schedule.limits.code_len, Error::<T>::CodeTooLarge);
+			let module = PrefabWasmModule::from_code(code, &schedule)?;
+			ensure!(module.code_len() <= schedule.limits.code_len, Error::<T>::CodeTooLarge);
+			module.store_owned(origin, storage_deposit_limit)?;
+			Ok(())
+		}
 
-			// If poking the contract has lead to eviction of the contract, give out the rewards.
-			match Rent::<T, PrefabWasmModule<T>>::try_eviction(&dest, handicap)? {
-				(Some(rent_paid), code_len) => {
-					T::Currency::deposit_into_existing(
-						&rewarded,
-						T::SurchargeReward::get().min(rent_paid),
-					)
-					.map(|_| PostDispatchInfo {
-						actual_weight: Some(T::WeightInfo::claim_surcharge(code_len / 1024)),
-						pays_fee: Pays::No,
-					})
-					.map_err(Into::into)
-				}
-				(None, code_len) => Err(Error::<T>::ContractNotEvictable.with_weight(
-					T::WeightInfo::claim_surcharge(code_len / 1024)
-				)),
-			}
+		/// Remove the code stored under `code_hash` and refund the deposit to its owner.
+		///
+		/// Only code that was uploaded by the sender via [`Self::upload_code`] can be removed.
+		/// Fails with `CodeInUse` as long as any contract uses the code.
+		///
+		/// The dispatch fee is waived when the code was removed.
+		#[pallet::weight(T::WeightInfo::remove_code())]
+		pub fn remove_code(
+			origin: OriginFor<T>,
+			code_hash: CodeHash<T>,
+		) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+			PrefabWasmModule::<T>::remove(&origin, code_hash)?;
+			Ok(Pays::No.into())
 		}
 	}
 
@@ -446,11 +424,7 @@ pub mod pallet {
 		/// Contract deployed by address at the specified address. \[deployer, contract\]
 		Instantiated(T::AccountId, T::AccountId),
 
-		/// Contract has been evicted and is now in tombstone state. \[contract\]
-		Evicted(T::AccountId),
-
-		/// Contract has been terminated without leaving a tombstone.
-		/// \[contract, beneficiary\]
+		/// Contract has been terminated. \[contract, beneficiary\]
 		///
 		/// # Params
 		///
@@ -459,22 +433,14 @@ pub mod pallet {
 		///
 		/// # Note
 		///
-		/// The only way for a contract to be removed without a tombstone and emitting
-		/// this event is by calling `seal_terminate`.
+		/// The only way for a contract to be removed and emitting this event is by calling
+		/// `seal_terminate`. The storage deposit held by the contract is refunded to the
+		/// origin of the call.
 		Terminated(T::AccountId, T::AccountId),
 
-		/// Restoration of a contract has been successful.
-		/// \[restorer, dest, code_hash, rent_allowance\]
-		///
-		/// # Params
-		///
-		/// - `restorer`: Account ID of the restoring contract.
-		/// - `dest`: Account ID of the restored contract.
-		/// - `code_hash`: Code hash of the restored contract.
-		/// - `rent_allowance`: Rent allowance of the restored contract.
-		Restored(T::AccountId, T::AccountId, T::Hash, BalanceOf<T>),
-
 		/// Code with the specified hash has been stored. \[code_hash\]
+		///
+		/// This happens when new code is supplied to `instantiate_with_code` or `upload_code`.
 		CodeStored(T::Hash),
 
 		/// Triggered when the current schedule is updated.
@@ -498,35 +464,35 @@ pub mod pallet {
 		/// A code with the specified hash was removed.
 		/// \[code_hash\]
 		///
-		/// This happens when the last contract that uses this code hash was removed or evicted.
+		/// This happens when the last contract that uses this code hash was removed or
+		/// when the owner of uploaded code removed it via `remove_code`.
 		CodeRemoved(T::Hash),
+
+		/// A contract's code was updated.
+		/// \[contract, new_code_hash, old_code_hash\]
+		///
+		/// # Params
+		///
+		/// - `contract`: The contract that replaced its code by calling `seal_set_code_hash`.
+		/// - `new_code_hash`: The code hash the contract uses from now on.
+		/// - `old_code_hash`: The code hash the contract used before.
+		ContractCodeUpdated(T::AccountId, T::Hash, T::Hash),
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		/// A new schedule must have a greater version than the current one.
 		InvalidScheduleVersion,
-		/// An origin must be signed or inherent and auxiliary sender only provided on inherent.
-		InvalidSurchargeClaim,
-		/// Cannot restore from nonexisting or tombstone contract.
-		InvalidSourceContract,
-		/// Cannot restore to nonexisting or alive contract.
-		InvalidDestinationContract,
-		/// Tombstones don't match.
-		InvalidTombstone,
-		/// An origin TrieId written in the current block.
-		InvalidContractOrigin,
 		/// The executed contract exhausted its gas limit.
 		OutOfGas,
 		/// The output buffer supplied to a contract API call was too small.
 		OutputBufferTooSmall,
 		/// Performing the requested transfer would have brought the contract below
-		/// the subsistence threshold. No transfer is allowed to do this in order to allow
-		/// for a tombstone to be created. Use `seal_terminate` to remove a contract without
-		/// leaving a tombstone behind.
+		/// the subsistence threshold. No transfer is allowed to do this. Use `seal_terminate`
+		/// to remove a contract.
 		BelowSubsistenceThreshold,
-		/// The newly created contract is below the subsistence threshold after executing
-		/// its contructor. No contracts are allowed to exist below that threshold.
+		/// The endowment of a newly created contract is below the subsistence threshold.
+		/// No contracts are allowed to exist below that threshold.
 		NewContractNotFunded,
 		/// Performing the requested transfer failed for a reason originating in the
 		/// chosen currency implementation of the runtime. Most probably the balance is
@@ -537,18 +503,6 @@ pub mod pallet {
 		MaxCallDepthReached,
 		/// No contract was found at the specified address.
 		ContractNotFound,
-		/// A tombstone exist at the specified address.
-		///
-		/// Tombstone cannot be called. Anyone can use `seal_restore_to` in order to revive
-		/// the contract, though.
-		ContractIsTombstone,
-		/// The called contract does not have enough balance to pay for its storage.
-		///
-		/// The contract ran out of balance and is therefore eligible for eviction into a
-		/// tombstone. Anyone can evict the contract by submitting a `claim_surcharge`
-		/// extrinsic. Alternatively, a plain balance transfer can be used in order to
-		/// increase the contracts funds so that it can be called again.
-		RentNotPaid,
 		/// The code supplied to `instantiate_with_code` exceeds the limit specified in the
 		/// current schedule.
 		CodeTooLarge,
@@ -563,7 +517,7 @@ pub mod pallet {
 		/// The size defined in `T::MaxValueSize` was exceeded.
 		ValueTooLarge,
 		/// Termination of a contract is not allowed while the contract is already
-		/// on the call stack. Can be triggered by `seal_terminate` or `seal_restore_to.
+		/// on the call stack. Can be triggered by `seal_terminate`.
 		TerminatedWhileReentrant,
 		/// `seal_call` forwarded this contracts input. It therefore is no longer available.
 		InputForwarded,
@@ -579,15 +533,10 @@ pub mod pallet {
 		NoChainExtension,
 		/// Removal of a contract failed because the deletion queue is full.
 		///
-		/// This can happen when either calling [`Pallet::claim_surcharge`] or `seal_terminate`.
+		/// This can happen when calling `seal_terminate`.
 		/// The queue is filled by deleting contracts and emptied by a fixed amount each block.
 		/// Trying again during another block is the only way to resolve this issue.
 		DeletionQueueFull,
-		/// A contract could not be evicted because it has enough balance to pay rent.
-		///
-		/// This can be returned from [`Pallet::claim_surcharge`] because the target
-		/// contract has enough balance to pay for its rent.
-		ContractNotEvictable,
 		/// A storage modification exhausted the 32bit type that holds the storage size.
 		///
 		/// This can either happen when the accumulated storage in bytes is too large or
@@ -597,12 +546,25 @@ pub mod pallet {
 		DuplicateContract,
 		/// A contract self destructed in its constructor.
 		///
-		/// This can be triggered by a call to `seal_terminate` or `seal_restore_to`.
+		/// This can be triggered by a call to `seal_terminate`.
 		TerminatedInConstructor,
 		/// The debug message specified to `seal_debug_message` does contain invalid UTF-8.
 		DebugMessageInvalidUTF8,
 		/// A call tried to invoke a contract that is flagged as non-reentrant.
 		ReentranceDenied,
+		/// The origin does not have enough free balance to pay the storage deposit.
+		StorageDepositNotEnoughFunds,
+		/// More storage was created than allowed by the storage deposit limit.
+		StorageDepositLimitExhausted,
+		/// Code removal was denied because the code is still in use by at least one contract.
+		CodeInUse,
+		/// The code supplied to `upload_code` is already stored.
+		CodeAlreadyExists,
+		/// The flags passed to `seal_delegate_call` are not supported by a delegate call.
+		///
+		/// This happens when `ALLOW_REENTRY` is set because a delegate call executes in the
+		/// context of the current contract.
+		InvalidCallFlags,
 	}
 
 	/// A mapping from an original code hash to the original code, untouched by instrumentation.
@@ -613,6 +575,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type CodeStorage<T: Config> = StorageMap<_, Identity, CodeHash<T>, PrefabWasmModule<T>>;
 
+	/// A mapping from the hash of uploaded code to the account that uploaded it.
+	///
+	/// Only code that was uploaded via `upload_code` has an entry here.
+	#[pallet::storage]
+	pub(crate) type OwnerInfoOf<T: Config> = StorageMap<_, Identity, CodeHash<T>, OwnerInfo<T>>;
+
 	/// The subtrie counter.
 	#[pallet::storage]
 	pub(crate) type AccountCounter<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -646,16 +614,65 @@ where
 	/// it adds allocations and could be abused to drive the runtime into an OOM panic.
 	/// If set to `true` it returns additional human readable debugging information.
 	///
-	/// It returns the execution result and the amount of used weight.
+	/// It returns the execution result, the amount of used weight and the storage deposit
+	/// that was charged or refunded.
 	pub fn bare_call(
 		origin: T::AccountId,
 		dest: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		input_data: Vec<u8>,
+		debug: bool,
+	) -> ContractExecResult<BalanceOf<T>> {
+		Self::internal_call(
+			origin, dest, value, gas_limit, storage_deposit_limit, input_data, debug, None,
+		)
+	}
+
+	/// Perform a call to a specified contract and record a trace of its execution.
+	///
+	/// This function is identical to [`Self::bare_call`] but additionally returns the tree
+	/// of all calls and instantiations performed together with their inputs, outputs, gas
+	/// consumption and storage accesses.
+	///
+	/// # Note
+	///
+	/// This should only ever be called when executing as an RPC because recording the trace
+	/// adds allocations and could be abused to drive the runtime into an OOM panic.
+	pub fn bare_call_traced(
+		origin: T::AccountId,
+		dest: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		input_data: Vec<u8>,
 		debug: bool,
-	) -> ContractExecResult {
+	) -> ContractTracedExecResult<T::AccountId, BalanceOf<T>> {
+		let mut tracer = CallTracer::new();
+		let exec = Self::internal_call(
+			origin, dest, value, gas_limit, storage_deposit_limit, input_data, debug,
+			Some(&mut tracer),
+		);
+		ContractTracedExecResult {
+			exec,
+			trace: tracer.into_trace(),
+		}
+	}
+
+	/// Shared implementation of [`Self::bare_call`] and [`Self::bare_call_traced`].
+	fn internal_call(
+		origin: T::AccountId,
+		dest: T::AccountId,
+		value: BalanceOf<T>,
+		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+		input_data: Vec<u8>,
+		debug: bool,
+		tracer: Option<&mut CallTracer<T>>,
+	) -> ContractExecResult<BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_meter = StorageMeter::new(storage_deposit_limit);
 		let schedule = T::Schedule::get();
 		let mut debug_message = if debug {
 			Some(Vec::new())
@@ -663,11 +680,13 @@ where
 			None
 		};
 		let result = ExecStack::<T, PrefabWasmModule<T>>::run_call(
-			origin, dest, &mut gas_meter, &schedule, value, input_data, debug_message.as_mut(),
+			origin, dest, &mut gas_meter, &mut storage_meter, &schedule,
+			value, input_data, debug_message.as_mut(), tracer,
 		);
 		ContractExecResult {
 			result: result.map(|r| r.0).map_err(|r| r.0.error),
 			gas_consumed: gas_meter.gas_spent(),
+			storage_deposit: storage_meter.total(),
 			debug_message: debug_message.unwrap_or_default(),
 		}
 	}
@@ -677,11 +696,8 @@ where
 	/// This function is similar to [`Self::instantiate`], but doesn't perform any address lookups
 	/// and better suitable for calling directly from Rust.
 	///
-	/// It returns the execution result, account id and the amount of used weight.
-	///
-	/// If `compute_projection` is set to `true` the result also contains the rent projection.
-	/// This is optional because some non trivial and stateful work is performed to compute
-	/// the projection. See [`Self::rent_projection`].
+	/// It returns the execution result, account id, the amount of used weight and the
+	/// storage deposit that was charged or refunded.
 	///
 	/// # Note
 	///
@@ -692,13 +708,14 @@ where
 		origin: T::AccountId,
 		endowment: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		code: Code<CodeHash<T>>,
 		data: Vec<u8>,
 		salt: Vec<u8>,
-		compute_projection: bool,
 		debug: bool,
-	) -> ContractInstantiateResult<T::AccountId, T::BlockNumber> {
+	) -> ContractInstantiateResult<T::AccountId, BalanceOf<T>> {
 		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_meter = StorageMeter::new(storage_deposit_limit);
 		let schedule = T::Schedule::get();
 		let executable = match code {
 			Code::Upload(Bytes(binary)) => PrefabWasmModule::from_code(binary, &schedule),
@@ -709,6 +726,7 @@ where
 			Err(error) => return ContractInstantiateResult {
 				result: Err(error.into()),
 				gas_consumed: gas_meter.gas_spent(),
+				storage_deposit: Default::default(),
 				debug_message: Vec::new(),
 			}
 		};
@@ -718,25 +736,13 @@ where
 			None
 		};
 		let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
-			origin, executable, &mut gas_meter, &schedule,
-			endowment, data, &salt, debug_message.as_mut(),
-		).and_then(|(account_id, result)| {
-			let rent_projection = if compute_projection {
-				Some(Rent::<T, PrefabWasmModule<T>>::compute_projection(&account_id)
-					.map_err(|_| <Error<T>>::NewContractNotFunded)?)
-			} else {
-				None
-			};
-
-			Ok(InstantiateReturnValue {
-				result,
-				account_id,
-				rent_projection,
-			})
-		});
+			origin, executable, &mut gas_meter, &mut storage_meter, &schedule,
+			endowment, data, &salt, debug_message.as_mut(), None,
+		).map(|(account_id, result)| InstantiateReturnValue { result, account_id });
 		ContractInstantiateResult {
 			result: result.map_err(|e| e.error),
 			gas_consumed: gas_meter.gas_spent(),
+			storage_deposit: storage_meter.total(),
 			debug_message: debug_message.unwrap_or_default(),
 		}
 	}
@@ -744,18 +750,36 @@ where
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(address: T::AccountId, key: [u8; 32]) -> GetStorageResult {
 		let contract_info = ContractInfoOf::<T>::get(&address)
-			.ok_or(ContractAccessError::DoesntExist)?
-			.get_alive()
-			.ok_or(ContractAccessError::IsTombstone)?;
+			.ok_or(ContractAccessError::DoesntExist)?;
 
 		let maybe_value = Storage::<T>::read(&contract_info.trie_id, &key);
 		Ok(maybe_value)
 	}
 
-	/// Query how many blocks the contract stays alive given that the amount endowment
-	/// and consumed storage does not change.
-	pub fn rent_projection(address: T::AccountId) -> RentProjectionResult<T::BlockNumber> {
-		Rent::<T, PrefabWasmModule<T>>::compute_projection(&address)
+	/// Collect all events emitted by contracts in the current block.
+	///
+	/// The topics are taken from the event record and therefore match what is indexed
+	/// in `frame_system::EventTopics`.
+	pub fn contract_events() -> Vec<ContractEvent<T::AccountId, T::Hash>>
+	where
+		<T as Config>::Event: TryInto<Event<T>>,
+	{
+		System::<T>::events()
+			.into_iter()
+			.enumerate()
+			.filter_map(|(index, record)| {
+				let event = <<T as Config>::Event as From<_>>::from(record.event);
+				match event.try_into() {
+					Ok(Event::ContractEmitted(contract, data)) => Some(ContractEvent {
+						event_index: index as u32,
+						contract,
+						topics: record.topics,
+						data: data.into(),
+					}),
+					_ => None,
+				}
+			})
+			.collect()
 	}
 
 	/// Determine the address of a contract,
@@ -780,15 +804,14 @@ where
 		UncheckedFrom::unchecked_from(T::Hashing::hash(&buf))
 	}
 
-	/// Subsistence threshold is the extension of the minimum balance (aka existential deposit)
-	/// by the tombstone deposit, required for leaving a tombstone.
+	/// Subsistence threshold is the minimum balance a contract needs to hold.
 	///
-	/// Rent or any contract initiated balance transfer mechanism cannot make the balance lower
-	/// than the subsistence threshold in order to guarantee that a tombstone is created.
+	/// It is equal to the minimum balance (aka existential deposit). No contract initiated
+	/// balance transfer can make the balance lower than this threshold.
 	///
-	/// The only way to completely kill a contract without a tombstone is calling `seal_terminate`.
+	/// The only way to completely kill a contract is calling `seal_terminate`.
 	pub fn subsistence_threshold() -> BalanceOf<T> {
-		T::Currency::minimum_balance().saturating_add(T::TombstoneDeposit::get())
+		T::Currency::minimum_balance()
 	}
 
 	/// The in-memory size in bytes of the data structure associated with each contract.
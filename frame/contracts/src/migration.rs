@@ -14,12 +14,13 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-
-use crate::{Config, Weight, Pallet};
+use crate::{BalanceOf, CodeHash, Config, ContractInfo, ContractInfoOf, Pallet, TrieId, Weight};
+use codec::{Decode, Encode};
 use frame_support::{
 	storage::migration,
-	traits::{GetPalletVersion, PalletVersion, PalletInfoAccess, Get},
+	traits::{Currency, GetPalletVersion, PalletVersion, PalletInfoAccess, Get, ReservableCurrency},
 };
+use sp_runtime::traits::{Saturating, Zero};
 
 pub fn migrate<T: Config>() -> Weight {
 	let mut weight: Weight = 0;
@@ -32,9 +33,140 @@ pub fn migrate<T: Config>() -> Weight {
 				b"CurrentSchedule",
 				b"",
 			);
+			weight = weight.saturating_add(v4::migrate::<T>());
 		}
 		_ => (),
 	}
 
 	weight
 }
+
+/// Migration of contract information from the rent based model to the storage deposit model.
+mod v4 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	struct OldAliveContractInfo<T: Config> {
+		trie_id: TrieId,
+		storage_size: u32,
+		pair_count: u32,
+		code_hash: CodeHash<T>,
+		rent_allowance: BalanceOf<T>,
+		rent_paid: BalanceOf<T>,
+		deduct_block: <T as frame_system::Config>::BlockNumber,
+		last_write: Option<<T as frame_system::Config>::BlockNumber>,
+		_reserved: Option<()>,
+	}
+
+	#[derive(Encode, Decode)]
+	enum OldContractInfo<T: Config> {
+		Alive(OldAliveContractInfo<T>),
+		Tombstone(<T as frame_system::Config>::Hash),
+	}
+
+	/// Converts every alive contract into the new format and removes all tombstones.
+	///
+	/// The storage of existing contracts was never paid for by a deposit. Because refunds are
+	/// capped by the deposit a contract holds, a contract without a deposit could free its
+	/// storage without anything being refunded, but it could also never pay back anybody for
+	/// it. Instead, the deposit for the storage a contract occupies is reserved from the balance
+	/// that the contract kept in order to pay rent. A contract that cannot afford the whole
+	/// deposit only reserves what is left above the subsistence threshold.
+	///
+	/// Tombstones are removed without replacement: Their child trie and code were already
+	/// removed on eviction and there is no longer a way to restore them. The balance left on
+	/// the account of an evicted contract is not touched.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = 0;
+
+		<ContractInfoOf<T>>::translate(|account, old: OldContractInfo<T>| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			match old {
+				OldContractInfo::Alive(old) => {
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+					let deposit = T::DepositPerByte::get()
+						.saturating_mul(old.storage_size.into())
+						.saturating_add(T::DepositPerItem::get().saturating_mul(old.pair_count.into()));
+					let available = T::Currency::free_balance(&account)
+						.saturating_sub(T::Currency::minimum_balance());
+					let storage_deposit = deposit.min(available);
+					let storage_deposit = match T::Currency::reserve(&account, storage_deposit) {
+						Ok(()) => storage_deposit,
+						Err(_) => Zero::zero(),
+					};
+					Some(ContractInfo::<T> {
+						trie_id: old.trie_id,
+						storage_size: old.storage_size,
+						pair_count: old.pair_count,
+						code_hash: old.code_hash,
+						storage_deposit,
+						_reserved: old._reserved,
+					})
+				},
+				OldContractInfo::Tombstone(_) => None,
+			}
+		});
+
+		weight
+	}
+
+	#[cfg(test)]
+	mod test {
+		use super::*;
+		use crate::tests::{Balances, ExtBuilder, Test, ALICE, BOB, CHARLIE};
+		use frame_support::storage::unhashed;
+		use sp_core::H256;
+
+		fn put_old(account: &<Test as frame_system::Config>::AccountId, old: OldContractInfo<Test>) {
+			unhashed::put(&<ContractInfoOf<Test>>::hashed_key_for(account), &old);
+		}
+
+		fn alive(storage_size: u32, pair_count: u32) -> OldContractInfo<Test> {
+			OldContractInfo::Alive(OldAliveContractInfo {
+				trie_id: vec![storage_size as u8],
+				storage_size,
+				pair_count,
+				code_hash: H256::repeat_byte(1),
+				rent_allowance: 0,
+				rent_paid: 0,
+				deduct_block: 0,
+				last_write: None,
+				_reserved: None,
+			})
+		}
+
+		#[test]
+		fn migrate_charges_storage_deposit_and_removes_tombstones() {
+			ExtBuilder::default().existential_deposit(10).build().execute_with(|| {
+				let _ = Balances::deposit_creating(&ALICE, 1_000);
+				let _ = Balances::deposit_creating(&BOB, 60);
+				let _ = Balances::deposit_creating(&CHARLIE, 100);
+				// Storage deposit of 100 * 1 + 10 * 2 = 120.
+				put_old(&ALICE, alive(100, 10));
+				// Only 60 - 10 = 50 are available above the subsistence threshold.
+				put_old(&BOB, alive(100, 0));
+				put_old(&CHARLIE, OldContractInfo::Tombstone(H256::repeat_byte(2)));
+
+				migrate::<Test>();
+
+				let alice = <ContractInfoOf<Test>>::get(&ALICE).unwrap();
+				assert_eq!(alice.storage_size, 100);
+				assert_eq!(alice.pair_count, 10);
+				assert_eq!(alice.trie_id, vec![100]);
+				assert_eq!(alice.code_hash, H256::repeat_byte(1));
+				assert_eq!(alice.storage_deposit, 120);
+				assert_eq!(Balances::reserved_balance(&ALICE), 120);
+				assert_eq!(Balances::free_balance(&ALICE), 880);
+
+				let bob = <ContractInfoOf<Test>>::get(&BOB).unwrap();
+				assert_eq!(bob.storage_deposit, 50);
+				assert_eq!(Balances::reserved_balance(&BOB), 50);
+				assert_eq!(Balances::free_balance(&BOB), 10);
+
+				assert!(<ContractInfoOf<Test>>::get(&CHARLIE).is_none());
+				assert!(!unhashed::exists(&<ContractInfoOf<Test>>::hashed_key_for(&CHARLIE)));
+				assert_eq!(Balances::free_balance(&CHARLIE), 100);
+			});
+		}
+	}
+}
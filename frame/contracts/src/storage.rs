@@ -22,69 +22,32 @@ use crate::{
 	BalanceOf, CodeHash, ContractInfoOf, Config, TrieId, DeletionQueue, Error,
 	weights::WeightInfo,
 };
-use codec::{Codec, Encode, Decode};
+use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_std::prelude::*;
-use sp_std::{marker::PhantomData, fmt::Debug};
+use sp_std::marker::PhantomData;
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
 	RuntimeDebug,
-	traits::{Bounded, Saturating, Zero, Hash, Member, MaybeSerializeDeserialize},
+	traits::{Saturating, Zero, Hash},
 };
 use sp_core::crypto::UncheckedFrom;
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	storage::child::{self, KillChildStorageResult, ChildInfo},
-	traits::Get,
+	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
 	weights::Weight,
+	ensure,
 };
+use pallet_contracts_primitives::StorageDeposit;
 
-pub type AliveContractInfo<T> =
-	RawAliveContractInfo<CodeHash<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
-pub type TombstoneContractInfo<T> =
-	RawTombstoneContractInfo<<T as frame_system::Config>::Hash, <T as frame_system::Config>::Hashing>;
-
-/// Information for managing an account and its sub trie abstraction.
-/// This is the required info to cache for an account
-#[derive(Encode, Decode, RuntimeDebug)]
-pub enum ContractInfo<T: Config> {
-	Alive(AliveContractInfo<T>),
-	Tombstone(TombstoneContractInfo<T>),
-}
-
-impl<T: Config> ContractInfo<T> {
-	/// If contract is alive then return some alive info
-	pub fn get_alive(self) -> Option<AliveContractInfo<T>> {
-		if let ContractInfo::Alive(alive) = self {
-			Some(alive)
-		} else {
-			None
-		}
-	}
-
-	/// If contract is alive then return some reference to alive info
-	#[cfg(test)]
-	pub fn as_alive(&self) -> Option<&AliveContractInfo<T>> {
-		if let ContractInfo::Alive(ref alive) = self {
-			Some(alive)
-		} else {
-			None
-		}
-	}
-
-	/// If contract is tombstone then return some tombstone info
-	pub fn get_tombstone(self) -> Option<TombstoneContractInfo<T>> {
-		if let ContractInfo::Tombstone(tombstone) = self {
-			Some(tombstone)
-		} else {
-			None
-		}
-	}
-}
+pub type ContractInfo<T> = RawContractInfo<CodeHash<T>, BalanceOf<T>>;
+pub type DepositOf<T> = StorageDeposit<BalanceOf<T>>;
 
 /// Information for managing an account and its sub trie abstraction.
 /// This is the required info to cache for an account.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RawContractInfo<CodeHash, Balance> {
 	/// Unique ID for the subtree encoded as a bytes vector.
 	pub trie_id: TrieId,
 	/// The total number of bytes used by this contract.
@@ -95,22 +58,18 @@ pub struct RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
 	pub pair_count: u32,
 	/// The code associated with a given account.
 	pub code_hash: CodeHash,
-	/// Pay rent at most up to this value.
-	pub rent_allowance: Balance,
-	/// The amount of rent that was paid by the contract over its whole lifetime.
+	/// The amount of balance that is currently reserved from the contract's account
+	/// in order to pay for its storage.
 	///
-	/// A restored contract starts with a value of zero just like a new contract.
-	pub rent_paid: Balance,
-	/// Last block rent has been paid.
-	pub deduct_block: BlockNumber,
-	/// Last block child storage has been written.
-	pub last_write: Option<BlockNumber>,
+	/// It is refunded to the origin of the call that frees up the storage.
+	pub storage_deposit: Balance,
 	/// This field is reserved for future evolution of format.
 	pub _reserved: Option<()>,
 }
 
-impl<CodeHash, Balance, BlockNumber> RawAliveContractInfo<CodeHash, Balance, BlockNumber> {
+impl<CodeHash, Balance> RawContractInfo<CodeHash, Balance> {
 	/// Associated child trie unique id is built from the hash part of the trie id.
+	#[cfg(test)]
 	pub fn child_trie_info(&self) -> ChildInfo {
 		child_trie_info(&self.trie_id[..])
 	}
@@ -121,31 +80,7 @@ fn child_trie_info(trie_id: &[u8]) -> ChildInfo {
 	ChildInfo::new_default(trie_id)
 }
 
-#[derive(Encode, Decode, PartialEq, Eq, RuntimeDebug)]
-pub struct RawTombstoneContractInfo<H, Hasher>(H, PhantomData<Hasher>);
-
-impl<H, Hasher> RawTombstoneContractInfo<H, Hasher>
-where
-	H: Member + MaybeSerializeDeserialize+ Debug
-		+ AsRef<[u8]> + AsMut<[u8]> + Copy + Default
-		+ sp_std::hash::Hash + Codec,
-	Hasher: Hash<Output=H>,
-{
-	pub fn new(storage_root: &[u8], code_hash: H) -> Self {
-		let mut buf = Vec::new();
-		storage_root.using_encoded(|encoded| buf.extend_from_slice(encoded));
-		buf.extend_from_slice(code_hash.as_ref());
-		RawTombstoneContractInfo(<Hasher as Hash>::hash(&buf[..]), PhantomData)
-	}
-}
-
-impl<T: Config> From<AliveContractInfo<T>> for ContractInfo<T> {
-	fn from(alive_info: AliveContractInfo<T>) -> Self {
-		Self::Alive(alive_info)
-	}
-}
-
-#[derive(Encode, Decode)]
+#[derive(Encode, Decode, TypeInfo)]
 pub struct DeletedContract {
 	pair_count: u32,
 	trie_id: TrieId,
@@ -171,28 +106,35 @@ where
 	/// If the `opt_new_value` is `None` then the kv pair is removed.
 	///
 	/// This function also updates the bookkeeping info such as: number of total non-empty pairs a
-	/// contract owns, the last block the storage was written to, etc. That's why, in contrast to
-	/// `read`, this function also requires the `account` ID.
+	/// contract owns, the total number of bytes stored, etc. That's why, in contrast to
+	/// `read`, this function also requires the contract info.
+	///
+	/// It returns the storage deposit that becomes due (or can be refunded) by this change.
+	/// It is not charged by this function: See [`StorageMeter::charge`].
 	pub fn write(
-		block_number: T::BlockNumber,
-		new_info: &mut AliveContractInfo<T>,
+		new_info: &mut ContractInfo<T>,
 		key: &StorageKey,
 		opt_new_value: Option<Vec<u8>>,
-	) -> DispatchResult {
+	) -> Result<DepositOf<T>, DispatchError> {
 		let hashed_key = blake2_256(key);
 		let child_trie_info = &child_trie_info(&new_info.trie_id);
 
 		let opt_prev_len = child::len(&child_trie_info, &hashed_key);
 
+		let mut charge = <BalanceOf<T>>::zero();
+		let mut refund = <BalanceOf<T>>::zero();
+
 		// Update the total number of KV pairs and the number of empty pairs.
 		match (&opt_prev_len, &opt_new_value) {
 			(Some(_), None) => {
 				new_info.pair_count = new_info.pair_count.checked_sub(1)
 					.ok_or_else(|| Error::<T>::StorageExhausted)?;
+				refund = T::DepositPerItem::get();
 			},
 			(None, Some(_)) => {
 				new_info.pair_count = new_info.pair_count.checked_add(1)
 					.ok_or_else(|| Error::<T>::StorageExhausted)?;
+				charge = T::DepositPerItem::get();
 			},
 			(Some(_), Some(_)) => {},
 			(None, None) => {},
@@ -210,7 +152,14 @@ where
 			.and_then(|val| val.checked_add(new_value_len))
 			.ok_or_else(|| Error::<T>::StorageExhausted)?;
 
-		new_info.last_write = Some(block_number);
+		let per_byte = T::DepositPerByte::get();
+		if new_value_len > prev_value_len {
+			let bytes: BalanceOf<T> = (new_value_len - prev_value_len).into();
+			charge = charge.saturating_add(per_byte.saturating_mul(bytes));
+		} else {
+			let bytes: BalanceOf<T> = (prev_value_len - new_value_len).into();
+			refund = refund.saturating_add(per_byte.saturating_mul(bytes));
+		}
 
 		// Finally, perform the change on the storage.
 		match opt_new_value {
@@ -218,34 +167,27 @@ where
 			None => child::kill(&child_trie_info, &hashed_key),
 		}
 
-		Ok(())
+		Ok(StorageDeposit::Charge(charge).saturating_add(&StorageDeposit::Refund(refund)))
 	}
 
 	/// Creates a new contract descriptor in the storage with the given code hash at the given address.
 	///
-	/// Returns `Err` if there is already a contract (or a tombstone) exists at the given address.
+	/// Returns `Err` if there is already a contract exists at the given address.
 	pub fn new_contract(
 		account: &AccountIdOf<T>,
 		trie_id: TrieId,
 		ch: CodeHash<T>,
-	) -> Result<AliveContractInfo<T>, DispatchError> {
+	) -> Result<ContractInfo<T>, DispatchError> {
 		if <ContractInfoOf<T>>::contains_key(account) {
 			return Err(Error::<T>::DuplicateContract.into());
 		}
 
-		let contract = AliveContractInfo::<T> {
+		let contract = ContractInfo::<T> {
 			code_hash: ch,
 			storage_size: 0,
 			trie_id,
-			deduct_block:
-				// We want to charge rent for the first block in advance. Therefore we
-				// treat the contract as if it was created in the last block and then
-				// charge rent for it during instantiation.
-				<frame_system::Pallet<T>>::block_number().saturating_sub(1u32.into()),
-			rent_allowance: <BalanceOf<T>>::max_value(),
-			rent_paid: <BalanceOf<T>>::zero(),
 			pair_count: 0,
-			last_write: None,
+			storage_deposit: <BalanceOf<T>>::zero(),
 			_reserved: None,
 		};
 
@@ -254,9 +196,8 @@ where
 
 	/// Push a contract's trie to the deletion queue for lazy removal.
 	///
-	/// You must make sure that the contract is also removed or converted into a tombstone
-	/// when queuing the trie for deletion.
-	pub fn queue_trie_for_deletion(contract: &AliveContractInfo<T>) -> DispatchResult {
+	/// You must make sure that the contract is also removed when queuing the trie for deletion.
+	pub fn queue_trie_for_deletion(contract: &ContractInfo<T>) -> DispatchResult {
 		if <DeletionQueue<T>>::decode_len().unwrap_or(0) >= T::DeletionQueueDepth::get() as usize {
 			Err(Error::<T>::DeletionQueueFull.into())
 		} else {
@@ -363,8 +304,7 @@ where
 	#[cfg(test)]
 	pub fn code_hash(account: &AccountIdOf<T>) -> Option<CodeHash<T>>
 	{
-		<ContractInfoOf<T>>::get(account)
-			.and_then(|i| i.as_alive().map(|i| i.code_hash))
+		<ContractInfoOf<T>>::get(account).map(|i| i.code_hash)
 	}
 
 	/// Fill up the queue in order to exercise the limits during testing.
@@ -378,3 +318,89 @@ where
 		<DeletionQueue<T>>::put(queue);
 	}
 }
+
+/// Keeps track of the storage deposit that is charged from or refunded to the origin of a
+/// call stack.
+///
+/// Every storage modification performed by a contract moves the resulting deposit between
+/// the origin and the contract eagerly. Because those transfers happen inside the storage
+/// transaction of the frame that performed them they are rolled back together with it.
+/// The meter only needs to be reverted to the value it had when the failed frame was pushed.
+pub struct StorageMeter<T: Config> {
+	/// The maximum amount of balance the origin is willing to pay for storage.
+	///
+	/// `None` means that there is no limit apart from the free balance of the origin.
+	limit: Option<BalanceOf<T>>,
+	/// The net amount that was charged or refunded so far.
+	total: DepositOf<T>,
+}
+
+impl<T> StorageMeter<T>
+where
+	T: Config,
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
+{
+	/// Create a new meter that enforces the supplied `limit` on the net charge.
+	pub fn new(limit: Option<BalanceOf<T>>) -> Self {
+		Self {
+			limit,
+			total: Default::default(),
+		}
+	}
+
+	/// The net amount that was charged or refunded by this meter so far.
+	pub fn total(&self) -> DepositOf<T> {
+		self.total
+	}
+
+	/// Reset the meter to a value previously obtained by [`Self::total`].
+	///
+	/// This is used when a frame is rolled back.
+	pub fn revert_to(&mut self, snapshot: DepositOf<T>) {
+		self.total = snapshot;
+	}
+
+	/// Move the supplied `deposit` between `origin` and `contract` and record it in `info`.
+	///
+	/// A charge is transferred from the `origin` and reserved on the `contract`. A refund
+	/// is unreserved and transferred back to the `origin`. Refunds are capped by the deposit
+	/// that the contract currently holds.
+	pub fn charge(
+		&mut self,
+		origin: &T::AccountId,
+		contract: &T::AccountId,
+		info: &mut ContractInfo<T>,
+		deposit: DepositOf<T>,
+	) -> DispatchResult {
+		let deposit = match deposit {
+			StorageDeposit::Refund(amount) => StorageDeposit::Refund(amount.min(info.storage_deposit)),
+			charge => charge,
+		};
+		if deposit.is_zero() {
+			return Ok(());
+		}
+
+		let total = self.total.saturating_add(&deposit);
+		if let Some(limit) = self.limit {
+			ensure!(total.charge_or_zero() <= limit, Error::<T>::StorageDepositLimitExhausted);
+		}
+
+		match deposit {
+			StorageDeposit::Charge(amount) => {
+				T::Currency::transfer(origin, contract, amount, ExistenceRequirement::KeepAlive)
+					.and_then(|_| T::Currency::reserve(contract, amount))
+					.map_err(|_| Error::<T>::StorageDepositNotEnoughFunds)?;
+				info.storage_deposit = info.storage_deposit.saturating_add(amount);
+			},
+			StorageDeposit::Refund(amount) => {
+				T::Currency::unreserve(contract, amount);
+				T::Currency::transfer(contract, origin, amount, ExistenceRequirement::KeepAlive)
+					.map_err(|_| Error::<T>::TransferFailed)?;
+				info.storage_deposit = info.storage_deposit.saturating_sub(amount);
+			},
+		}
+
+		self.total = total;
+		Ok(())
+	}
+}
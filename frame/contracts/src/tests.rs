@@ -16,32 +16,32 @@
 // limitations under the License.
 
 use crate::{
-	BalanceOf, ContractInfo, ContractInfoOf, Pallet,
+	BalanceOf, ContractInfo, ContractInfoOf, CodeStorage, PristineCode, OwnerInfoOf, Pallet,
 	Config, Schedule,
 	Error, storage::Storage,
 	chain_extension::{
 		Result as ExtensionResult, Environment, ChainExtension, Ext, SysConfig, RetVal,
 		UncheckedFrom, InitState, ReturnFlags,
 	},
-	exec::{AccountIdOf, Executable, Frame}, wasm::PrefabWasmModule,
+	exec::{AccountIdOf, Frame}, wasm::PrefabWasmModule,
 	weights::WeightInfo,
 	wasm::ReturnCode as RuntimeReturnCode,
-	storage::RawAliveContractInfo,
 };
+use pallet_contracts_primitives::{StorageDeposit, ContractEvent, CallKind, StorageAccess};
 use assert_matches::assert_matches;
 use codec::Encode;
 use sp_core::Bytes;
 use sp_runtime::{
 	traits::{BlakeTwo256, Hash, IdentityLookup, Convert},
 	testing::{Header, H256},
-	AccountId32, Perbill,
+	AccountId32,
 };
 use sp_io::hashing::blake2_256;
 use frame_support::{
-	assert_ok, assert_err, assert_err_ignore_postinfo,
-	parameter_types, assert_storage_noop,
+	assert_ok, assert_err, assert_err_ignore_postinfo, assert_noop,
+	parameter_types,
 	traits::{Currency, ReservableCurrency, OnInitialize},
-	weights::{Weight, PostDispatchInfo, DispatchClass, constants::WEIGHT_PER_SECOND},
+	weights::{Weight, PostDispatchInfo, DispatchClass, Pays, constants::WEIGHT_PER_SECOND},
 	dispatch::DispatchErrorWithPostInfo,
 	storage::child,
 };
@@ -69,10 +69,10 @@ frame_support::construct_runtime!(
 
 #[macro_use]
 pub mod test_utils {
-	use super::{Test, Balances, System};
+	use super::{Test, Balances};
 	use crate::{
 		ContractInfoOf, CodeHash,
-		storage::{Storage, ContractInfo},
+		storage::Storage,
 		exec::{StorageKey, AccountIdOf},
 		Pallet as Contracts,
 		TrieId, AccountCounter,
@@ -80,12 +80,11 @@ pub mod test_utils {
 	use frame_support::traits::Currency;
 
 	pub fn set_storage(addr: &AccountIdOf<Test>, key: &StorageKey, value: Option<Vec<u8>>) {
-		let mut contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
-		let block_number = System::block_number();
-		Storage::<Test>::write(block_number, &mut contract_info, key, value).unwrap();
+		let mut contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
+		Storage::<Test>::write(&mut contract_info, key, value).unwrap();
 	}
 	pub fn get_storage(addr: &AccountIdOf<Test>, key: &StorageKey) -> Option<Vec<u8>> {
-		let contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let contract_info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		Storage::<Test>::read(&contract_info.trie_id, key)
 	}
 	pub fn generate_trie_id(address: &AccountIdOf<Test>) -> TrieId {
@@ -99,7 +98,7 @@ pub mod test_utils {
 		let trie_id = generate_trie_id(address);
 		set_balance(address, Contracts::<Test>::subsistence_threshold() * 10);
 		let contract = Storage::<Test>::new_contract(&address, trie_id, code_hash).unwrap();
-		<ContractInfoOf<Test>>::insert(address, ContractInfo::Alive(contract));
+		<ContractInfoOf<Test>>::insert(address, contract);
 	}
 	pub fn set_balance(who: &AccountIdOf<Test>, amount: u64) {
 		let imbalance = Balances::deposit_creating(who, amount);
@@ -256,13 +255,8 @@ impl pallet_timestamp::Config for Test {
 	type WeightInfo = ();
 }
 parameter_types! {
-	pub const SignedClaimHandicap: u64 = 2;
-	pub const TombstoneDeposit: u64 = 16;
-	pub const DepositPerContract: u64 = 8 * DepositPerStorageByte::get();
-	pub const DepositPerStorageByte: u64 = 10_000;
-	pub const DepositPerStorageItem: u64 = 10_000;
-	pub RentFraction: Perbill = Perbill::from_rational(4u32, 10_000u32);
-	pub const SurchargeReward: u64 = 500_000;
+	pub const DepositPerByte: u64 = 1;
+	pub const DepositPerItem: u64 = 2;
 	pub const MaxValueSize: u32 = 16_384;
 	pub const DeletionQueueDepth: u32 = 1024;
 	pub const DeletionWeightLimit: Weight = 500_000_000_000;
@@ -285,14 +279,8 @@ impl Config for Test {
 	type Randomness = Randomness;
 	type Currency = Balances;
 	type Event = Event;
-	type RentPayment = ();
-	type SignedClaimHandicap = SignedClaimHandicap;
-	type TombstoneDeposit = TombstoneDeposit;
-	type DepositPerContract = DepositPerContract;
-	type DepositPerStorageByte = DepositPerStorageByte;
-	type DepositPerStorageItem = DepositPerStorageItem;
-	type RentFraction = RentFraction;
-	type SurchargeReward = SurchargeReward;
+	type DepositPerByte = DepositPerByte;
+	type DepositPerItem = DepositPerItem;
 	type CallStack = [Frame<Self>; 31];
 	type WeightPrice = Self;
 	type WeightInfo = ();
@@ -365,7 +353,7 @@ fn calling_plain_account_fails() {
 		let base_cost = <<Test as Config>::WeightInfo as WeightInfo>::call(0);
 
 		assert_eq!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, Vec::new()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, Vec::new()),
 			Err(
 				DispatchErrorWithPostInfo {
 					error: Error::<Test>::ContractNotFound.into(),
@@ -391,33 +379,27 @@ fn account_removal_does_not_remove_storage() {
 
 		// Set up two accounts with free balance above the existential threshold.
 		{
-			let alice_contract_info = ContractInfo::Alive(RawAliveContractInfo {
+			let alice_contract_info = ContractInfo::<Test> {
 				trie_id: trie_id1.clone(),
 				storage_size: 0,
 				pair_count: 0,
-				deduct_block: System::block_number(),
 				code_hash: H256::repeat_byte(1),
-				rent_allowance: 40,
-				rent_paid: 0,
-				last_write: None,
+				storage_deposit: 0,
 				_reserved: None,
-			});
+			};
 			let _ = Balances::deposit_creating(&ALICE, 110);
 			ContractInfoOf::<Test>::insert(ALICE, &alice_contract_info);
 			set_storage(&ALICE, &key1, Some(b"1".to_vec()));
 			set_storage(&ALICE, &key2, Some(b"2".to_vec()));
 
-			let bob_contract_info = ContractInfo::Alive(RawAliveContractInfo {
+			let bob_contract_info = ContractInfo::<Test> {
 				trie_id: trie_id2.clone(),
 				storage_size: 0,
 				pair_count: 0,
-				deduct_block: System::block_number(),
 				code_hash: H256::repeat_byte(2),
-				rent_allowance: 40,
-				rent_paid: 0,
-				last_write: None,
+				storage_deposit: 0,
 				_reserved: None,
-			});
+			};
 			let _ = Balances::deposit_creating(&BOB, 110);
 			ContractInfoOf::<Test>::insert(BOB, &bob_contract_info);
 			set_storage(&BOB, &key1, Some(b"3".to_vec()));
@@ -472,6 +454,7 @@ fn instantiate_and_call_and_deposit_event() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -534,6 +517,47 @@ fn instantiate_and_call_and_deposit_event() {
 		});
 }
 
+#[test]
+fn contract_events_lists_emitted_events() {
+	let (wasm, code_hash) = compile_module::<Test>("event_topics").unwrap();
+
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			assert_ok!(Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				wasm,
+				vec![],
+				vec![],
+			));
+			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+			// Deploying does not emit any contract events.
+			assert_eq!(Contracts::contract_events(), vec![]);
+
+			let first_index = System::events().len() as u32;
+			assert_ok!(Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, None, vec![]));
+			assert_ok!(Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, None, vec![]));
+			let event = |event_index| ContractEvent {
+				event_index,
+				contract: addr.clone(),
+				topics: vec![H256::repeat_byte(1), H256::repeat_byte(2)],
+				data: vec![0xde, 0xad, 0xbe, 0xef].into(),
+			};
+
+			assert_eq!(Contracts::contract_events(), vec![event(first_index), event(first_index + 1)]);
+			assert_eq!(
+				System::event_topics(&H256::repeat_byte(1)),
+				vec![(1, first_index), (1, first_index + 1)],
+			);
+		});
+}
+
 #[test]
 fn deposit_event_max_value_limit() {
 	let (wasm, code_hash) = compile_module::<Test>("event_size").unwrap();
@@ -548,6 +572,7 @@ fn deposit_event_max_value_limit() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -560,6 +585,7 @@ fn deposit_event_max_value_limit() {
 				addr.clone(),
 				0,
 				GAS_LIMIT * 2, // we are copying a huge buffer,
+				None,
 				<Test as Config>::Schedule::get().limits.payload_len.encode(),
 			));
 
@@ -570,6 +596,7 @@ fn deposit_event_max_value_limit() {
 					addr,
 					0,
 					GAS_LIMIT,
+					None,
 					(<Test as Config>::Schedule::get().limits.payload_len + 1).encode(),
 				),
 				Error::<Test>::ValueTooLarge,
@@ -580,18 +607,19 @@ fn deposit_event_max_value_limit() {
 #[test]
 fn run_out_of_gas() {
 	let (wasm, code_hash) = compile_module::<Test>("run_out_of_gas").unwrap();
-	let subsistence = Pallet::<Test>::subsistence_threshold();
 
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
+			let subsistence = Pallet::<Test>::subsistence_threshold();
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
 				100 * subsistence,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -606,6 +634,7 @@ fn run_out_of_gas() {
 					addr, // newly created account
 					0,
 					67_500_000,
+					None,
 					vec![],
 				),
 				Error::<Test>::OutOfGas,
@@ -640,6 +669,7 @@ fn storage_size() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				// rent_allowance
 				<Test as pallet_balances::Config>::Balance::from(10_000u32).encode(),
@@ -647,8 +677,6 @@ fn storage_size() {
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 			assert_eq!(
 				bob_contract.storage_size,
@@ -664,11 +692,10 @@ fn storage_size() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				call::set_storage_4_byte()
 			));
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 			assert_eq!(
 				bob_contract.storage_size,
@@ -684,11 +711,10 @@ fn storage_size() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				call::remove_storage_4_byte()
 			));
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 			assert_eq!(
 				bob_contract.storage_size,
@@ -713,14 +739,13 @@ fn empty_kv_pairs() {
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 			let bob_contract = ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_alive()
 				.unwrap();
 
 			assert_eq!(
@@ -734,169 +759,183 @@ fn empty_kv_pairs() {
 		});
 }
 
-fn initialize_block(number: u64) {
-	System::initialize(
-		&number,
-		&[0u8; 32].into(),
-		&Default::default(),
-		Default::default(),
-	);
-}
-
 #[test]
-fn deduct_blocks() {
+fn storage_deposit_is_charged_and_refunded() {
 	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
-	let endowment: BalanceOf<Test> = 100_000;
-	let allowance: BalanceOf<Test> = 70_000;
 
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
+			// The constructor writes one item of 4 bytes.
+			let item_deposit = DepositPerItem::get() + 4 * DepositPerByte::get();
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				endowment,
+				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
-				allowance.encode(),
+				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			let code_len: BalanceOf<Test> =
-				PrefabWasmModule::<Test>::from_storage_noinstr(contract.code_hash)
-					.unwrap()
-					.occupied_storage()
-					.into();
-
-			// The instantiation deducted the rent for one block immediately
-			let rent0 = <Test as Config>::RentFraction::get()
-				// (base_deposit(8) + bytes in storage(4) + size of code) * byte_price
-				// + 1 storage item (10_000) - free_balance
-				.mul_ceil((8 + 4 + code_len) * 10_000 + 10_000 - endowment)
-				// blocks to rent
-				* 1;
-			assert!(rent0 > 0);
-			assert_eq!(contract.rent_allowance, allowance - rent0);
-			assert_eq!(contract.deduct_block, 1);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0);
-
-			// Advance 4 blocks
-			initialize_block(5);
-
-			// Trigger rent through call
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
-			);
-
-			// Check result
-			let rent = <Test as Config>::RentFraction::get()
-				.mul_ceil((8 + 4 + code_len) * 10_000 + 10_000 - (endowment - rent0))
-				* 4;
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			assert_eq!(contract.rent_allowance, allowance - rent0 - rent);
-			assert_eq!(contract.deduct_block, 5);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0 - rent);
-
-			// Advance 2 blocks more
-			initialize_block(7);
+			assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().storage_deposit, item_deposit);
+			assert_eq!(Balances::reserved_balance(&addr), item_deposit);
+			assert_eq!(Balances::free_balance(&addr), 30_000);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000_000 - 30_000 - item_deposit);
 
-			// Trigger rent through call
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
+			// Adding another item charges the origin of the call.
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				call::set_storage_4_byte(),
+				false,
 			);
-
-			// Check result
-			let rent_2 = <Test as Config>::RentFraction::get()
-				.mul_ceil((8 + 4 + code_len) * 10_000 + 10_000 - (endowment - rent0 - rent))
-				* 2;
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			assert_eq!(contract.rent_allowance, allowance - rent0 - rent - rent_2);
-			assert_eq!(contract.deduct_block, 7);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0 - rent - rent_2);
-
-			// Second call on same block should have no effect on rent
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
+			assert_ok!(result.result);
+			assert_eq!(result.storage_deposit, StorageDeposit::Charge(item_deposit));
+			assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().storage_deposit, 2 * item_deposit);
+			assert_eq!(Balances::reserved_balance(&addr), 2 * item_deposit);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000_000 - 30_000 - 2 * item_deposit);
+
+			// Removing it refunds the origin of the call.
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				call::remove_storage_4_byte(),
+				false,
+			);
+			assert_ok!(result.result);
+			assert_eq!(result.storage_deposit, StorageDeposit::Refund(item_deposit));
+			assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().storage_deposit, item_deposit);
+			assert_eq!(Balances::reserved_balance(&addr), item_deposit);
+			assert_eq!(Balances::free_balance(&ALICE), 1_000_000 - 30_000 - item_deposit);
+
+			// Calls that do not touch the storage are free of any deposit.
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				None,
+				call::null(),
+				false,
 			);
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			assert_eq!(contract.rent_allowance, allowance - rent0 - rent - rent_2);
-			assert_eq!(contract.deduct_block, 7);
-			assert_eq!(Balances::free_balance(&addr), endowment - rent0 - rent - rent_2)
+			assert_ok!(result.result);
+			assert!(result.storage_deposit.is_zero());
 		});
 }
 
 #[test]
-fn inherent_claim_surcharge_contract_removals() {
-	removals(|addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok());
-}
-
-#[test]
-fn signed_claim_surcharge_contract_removals() {
-	removals(|addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok());
-}
-
-#[test]
-fn claim_surcharge_malus() {
-	// Test surcharge malus for inherent
-	claim_surcharge(8, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), true);
-	claim_surcharge(7, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), true);
-	claim_surcharge(6, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), true);
-	claim_surcharge(5, |addr| Contracts::claim_surcharge(Origin::none(), addr, Some(ALICE)).is_ok(), false);
-
-	// Test surcharge malus for signed
-	claim_surcharge(8, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), true);
-	claim_surcharge(7, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), false);
-	claim_surcharge(6, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), false);
-	claim_surcharge(5, |addr| Contracts::claim_surcharge(Origin::signed(ALICE), addr, None).is_ok(), false);
-}
-
-/// Claim surcharge with the given trigger_call at the given blocks.
-/// If `removes` is true then assert that the contract is a tombstone.
-fn claim_surcharge(blocks: u64, trigger_call: impl Fn(AccountIdOf<Test>) -> bool, removes: bool) {
+fn storage_deposit_limit_is_enforced() {
 	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
 
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
+			let item_deposit = DepositPerItem::get() + 4 * DepositPerByte::get();
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+			// The limit is too low for the storage written by the constructor.
+			assert_err_ignore_postinfo!(
+				Contracts::instantiate_with_code(
+					Origin::signed(ALICE),
+					30_000,
+					GAS_LIMIT,
+					Some(item_deposit - 1),
+					wasm.clone(),
+					vec![],
+					vec![],
+				),
+				Error::<Test>::StorageDepositLimitExhausted,
+			);
+			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+			assert!(ContractInfoOf::<Test>::get(&addr).is_none());
+			assert_eq!(Balances::free_balance(&ALICE), 1_000_000);
+
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				100_000,
+				30_000,
 				GAS_LIMIT,
+				Some(item_deposit),
 				wasm,
-				<Test as pallet_balances::Config>::Balance::from(30_000u32).encode(), // rent allowance
+				vec![],
 				vec![],
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-
-			// Advance blocks
-			initialize_block(blocks);
 
-			// Trigger rent through call
-			assert_eq!(trigger_call(addr.clone()), removes);
+			let result = Contracts::bare_call(
+				ALICE,
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				Some(item_deposit - 1),
+				call::set_storage_4_byte(),
+				false,
+			);
+			assert_err!(result.result, Error::<Test>::StorageDepositLimitExhausted);
+			assert!(result.storage_deposit.is_zero());
+			assert_eq!(Balances::reserved_balance(&addr), item_deposit);
 
-			if removes {
-				assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-			} else {
-				assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().is_some());
-			}
+			// A refund never counts against the limit.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				Some(0),
+				call::remove_storage_4_byte(),
+			));
 		});
 }
 
-/// Test for all kind of removals for the given trigger:
-/// * if balance is reached and balance > subsistence threshold
-/// * if allowance is exceeded
-/// * if balance is reached and balance < subsistence threshold
-///	    * this case cannot be triggered by a contract: we check whether a tombstone is left
-fn removals(trigger_call: impl Fn(AccountIdOf<Test>) -> bool) {
+#[test]
+fn storage_deposit_needs_free_balance() {
 	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
 
-	// Balance reached and superior to subsistence threshold
+	ExtBuilder::default()
+		.existential_deposit(50)
+		.build()
+		.execute_with(|| {
+			// After the endowment is transferred there is not enough left to pay the
+			// deposit without killing the origin account.
+			let _ = Balances::deposit_creating(&ALICE, 30_000 + 50 + 1);
+			assert_err_ignore_postinfo!(
+				Contracts::instantiate_with_code(
+					Origin::signed(ALICE),
+					30_000,
+					GAS_LIMIT,
+					None,
+					wasm,
+					vec![],
+					vec![],
+				),
+				Error::<Test>::StorageDepositNotEnoughFunds,
+			);
+			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+			assert!(ContractInfoOf::<Test>::get(&addr).is_none());
+		});
+}
+
+fn initialize_block(number: u64) {
+	System::initialize(
+		&number,
+		&[0u8; 32].into(),
+		&Default::default(),
+		Default::default(),
+	);
+}
+
+#[test]
+fn storage_max_value_limit() {
+	let (wasm, code_hash) = compile_module::<Test>("storage_size").unwrap();
+
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
@@ -905,45 +944,46 @@ fn removals(trigger_call: impl Fn(AccountIdOf<Test>) -> bool) {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				70_000,
+				30_000,
 				GAS_LIMIT,
-				wasm.clone(),
-				<Test as pallet_balances::Config>::Balance::from(100_000u32).encode(), // rent allowance
+				None,
+				wasm,
+				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let allowance = ContractInfoOf::<Test>::get(&addr)
-				.unwrap().get_alive().unwrap().rent_allowance;
-			let balance = Balances::free_balance(&addr);
+			ContractInfoOf::<Test>::get(&addr).unwrap();
 
-			let subsistence_threshold = Pallet::<Test>::subsistence_threshold();
+			// Call contract with allowed storage value.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				addr.clone(),
+				0,
+				GAS_LIMIT * 2, // we are copying a huge buffer
+				None,
+				<Test as Config>::Schedule::get().limits.payload_len.encode(),
+			));
 
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_eq!(
-				ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap().rent_allowance,
-				allowance,
+			// Call contract with too large a storage value.
+			assert_err_ignore_postinfo!(
+				Contracts::call(
+					Origin::signed(ALICE),
+					addr,
+					0,
+					GAS_LIMIT,
+					None,
+					(<Test as Config>::Schedule::get().limits.payload_len + 1).encode(),
+				),
+				Error::<Test>::ValueTooLarge,
 			);
-			assert_eq!(Balances::free_balance(&addr), balance);
-
-			// Advance blocks
-			initialize_block(27);
-
-			// Trigger rent through call (should remove the contract)
-			assert!(trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
-
-			// Advance blocks
-			initialize_block(30);
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
 		});
+}
+
+#[test]
+fn deploy_and_call_other_contract() {
+	let (callee_wasm, callee_code_hash) = compile_module::<Test>("return_with_data").unwrap();
+	let (caller_wasm, caller_code_hash) = compile_module::<Test>("caller_contract").unwrap();
 
-	// Allowance exceeded
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
@@ -954,568 +994,212 @@ fn removals(trigger_call: impl Fn(AccountIdOf<Test>) -> bool) {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
-				wasm.clone(),
-				<Test as pallet_balances::Config>::Balance::from(70_000u32).encode(), // rent allowance
+				None,
+				caller_wasm,
+				vec![],
 				vec![],
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let allowance = ContractInfoOf::<Test>::get(&addr)
-				.unwrap().get_alive().unwrap().rent_allowance;
-			let balance = Balances::free_balance(&addr);
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_eq!(
-				ContractInfoOf::<Test>::get(&addr)
-					.unwrap()
-					.get_alive()
-					.unwrap()
-					.rent_allowance,
-				allowance,
-			);
-			assert_eq!(Balances::free_balance(&addr), balance);
-
-			// Advance blocks
-			initialize_block(27);
-
-			// Trigger rent through call
-			assert!(trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_tombstone()
-				.is_some());
-			// Balance should be initial balance - initial rent_allowance
-			assert_eq!(Balances::free_balance(&addr), 30_000);
-
-			// Advance blocks
-			initialize_block(20);
+			assert_ok!(Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				100_000,
+				GAS_LIMIT,
+				None,
+				callee_wasm,
+				0u32.to_le_bytes().encode(),
+				vec![42],
+			));
 
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert!(ContractInfoOf::<Test>::get(&addr)
-				.unwrap()
-				.get_tombstone()
-				.is_some());
-			assert_eq!(Balances::free_balance(&addr), 30_000);
+			// Call BOB contract, which attempts to instantiate and call the callee contract and
+			// makes various assertions on the results from those calls.
+			assert_ok!(Contracts::call(
+				Origin::signed(ALICE),
+				Contracts::contract_address(&ALICE, &caller_code_hash, &[]),
+				0,
+				GAS_LIMIT,
+				None,
+				callee_code_hash.as_ref().to_vec(),
+			));
 		});
+}
 
-	// Balance reached and inferior to subsistence threshold
+#[test]
+fn cannot_self_destruct_through_draning() {
+	let (wasm, code_hash) = compile_module::<Test>("drain").unwrap();
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
-			let subsistence_threshold = Pallet::<Test>::subsistence_threshold();
-			let _ = Balances::deposit_creating(&ALICE, subsistence_threshold * 1000);
+			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+			// Instantiate the BOB contract.
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				subsistence_threshold * 100,
+				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
-				(subsistence_threshold * 100).encode(), // rent allowance
+				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let allowance = ContractInfoOf::<Test>::get(&addr)
-				.unwrap().get_alive().unwrap().rent_allowance;
-			let balance = Balances::free_balance(&addr);
 
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_eq!(
-				ContractInfoOf::<Test>::get(&addr)
-					.unwrap()
-					.get_alive()
-					.unwrap()
-					.rent_allowance,
-				allowance,
-			);
-			assert_eq!(
-				Balances::free_balance(&addr),
-				balance,
+			// Check that the BOB contract has been instantiated.
+			assert_matches!(
+				ContractInfoOf::<Test>::get(&addr),
+				Some(_)
 			);
 
-			// Make contract have exactly the subsistence threshold
-			Balances::make_free_balance_be(&addr, subsistence_threshold);
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
-
-			// Advance blocks (should remove as balance is exactly subsistence)
-			initialize_block(10);
-
-			// Trigger rent through call
-			assert!(trigger_call(addr.clone()));
-			assert_matches!(ContractInfoOf::<Test>::get(&addr), Some(ContractInfo::Tombstone(_)));
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
-
-			// Advance blocks
-			initialize_block(20);
-
-			// Trigger rent must have no effect
-			assert!(!trigger_call(addr.clone()));
-			assert_matches!(ContractInfoOf::<Test>::get(&addr), Some(ContractInfo::Tombstone(_)));
-			assert_eq!(Balances::free_balance(&addr), subsistence_threshold);
+			// Call BOB which makes it send all funds to the zero address
+			// The contract code asserts that the correct error value is returned.
+			assert_ok!(
+				Contracts::call(
+					Origin::signed(ALICE),
+					addr,
+					0,
+					GAS_LIMIT,
+					None,
+					vec![],
+				)
+			);
 		});
 }
 
 #[test]
-fn call_removed_contract() {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
-
-	// Balance reached and superior to subsistence threshold
+fn cannot_self_destruct_while_live() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+			// Instantiate the BOB contract.
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				30_000,
+				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
-				// rent allowance
-				<Test as pallet_balances::Config>::Balance::from(10_000u32).encode(),
+				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 
-			// Calling contract should succeed.
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
+			// Check that the BOB contract has been instantiated.
+			assert_matches!(
+				ContractInfoOf::<Test>::get(&addr),
+				Some(_)
 			);
 
-			// Advance blocks
-			initialize_block(27);
-
-			// Calling contract should deny access because rent cannot be paid.
+			// Call BOB with input data, forcing it make a recursive call to itself to
+			// self-destruct, resulting in a trap.
 			assert_err_ignore_postinfo!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null()),
-				Error::<Test>::RentNotPaid,
+				Contracts::call(
+					Origin::signed(ALICE),
+					addr.clone(),
+					0,
+					GAS_LIMIT,
+					None,
+					vec![0],
+				),
+				Error::<Test>::ContractTrapped,
 			);
-			// No event is generated because the contract is not actually removed.
-			assert_eq!(System::events(), vec![]);
 
-			// Subsequent contract calls should also fail.
-			assert_err_ignore_postinfo!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null()),
-				Error::<Test>::RentNotPaid,
+			// Check that BOB is still alive.
+			assert_matches!(
+				ContractInfoOf::<Test>::get(&addr),
+				Some(_)
 			);
-
-			// A snitch can now remove the contract
-			assert_ok!(Contracts::claim_surcharge(Origin::none(), addr.clone(), Some(ALICE)));
-			assert!(ContractInfoOf::<Test>::get(&addr).unwrap().get_tombstone().is_some());
-		})
+		});
 }
 
 #[test]
-fn default_rent_allowance_on_instantiate() {
-	let (wasm, code_hash) = compile_module::<Test>("check_default_rent_allowance").unwrap();
-
+fn self_destruct_works() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
-			// Create
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			let _ = Balances::deposit_creating(&DJANGO, 1_000_000);
+
+			// Instantiate the BOB contract.
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				30_000,
+				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
 			));
 			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-			let code_len: BalanceOf<Test> =
-			PrefabWasmModule::<Test>::from_storage_noinstr(contract.code_hash)
-				.unwrap()
-				.occupied_storage()
-				.into();
-
-			// The instantiation deducted the rent for one block immediately
-			let first_rent = <Test as Config>::RentFraction::get()
-				// (base_deposit(8) + code_len) * byte_price - free_balance
-				.mul_ceil((8 + code_len) * 10_000 - 30_000)
-				// blocks to rent
-				* 1;
-			assert_eq!(contract.rent_allowance, <BalanceOf<Test>>::max_value() - first_rent);
-
-			// Advance blocks
-			initialize_block(5);
-
-			// Trigger rent through call
-			assert_ok!(
-				Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, call::null())
-			);
-
-			// Check contract is still alive
-			let contract = ContractInfoOf::<Test>::get(&addr).unwrap().get_alive();
-			assert!(contract.is_some())
-		});
-}
-
-#[test]
-fn restorations_dirty_storage_and_different_storage() {
-	restoration(true, true, false);
-}
-
-#[test]
-fn restorations_dirty_storage() {
-	restoration(false, true, false);
-}
-
-#[test]
-fn restoration_different_storage() {
-	restoration(true, false, false);
-}
-
-#[test]
-fn restoration_code_evicted() {
-	restoration(false, false, true);
-}
-
-#[test]
-fn restoration_success() {
-	restoration(false, false, false);
-}
 
-fn restoration(
-	test_different_storage: bool,
-	test_restore_to_with_dirty_storage: bool,
-	test_code_evicted: bool
-) {
-	let (set_rent_wasm, set_rent_code_hash) = compile_module::<Test>("set_rent").unwrap();
-	let (restoration_wasm, restoration_code_hash) = compile_module::<Test>("restoration").unwrap();
-	let allowance: <Test as pallet_balances::Config>::Balance = 10_000;
+			// Check that the BOB contract has been instantiated.
+			assert_matches!(
+				ContractInfoOf::<Test>::get(&addr),
+				Some(_)
+			);
 
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			// Drop all previous events
+			initialize_block(2);
 
-			// Create an account with address `BOB` with code `CODE_SET_RENT`.
-			// The input parameter sets the rent allowance to 0.
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				30_000,
-				GAS_LIMIT,
-				set_rent_wasm.clone(),
-				allowance.encode(),
-				vec![],
-			));
-			let addr_bob = Contracts::contract_address(&ALICE, &set_rent_code_hash, &[]);
+			// Call BOB without input data which triggers termination.
+			assert_matches!(
+				Contracts::call(
+					Origin::signed(ALICE),
+					addr.clone(),
+					0,
+					GAS_LIMIT,
+					None,
+					vec![],
+				),
+				Ok(_)
+			);
 
-			let mut events = vec![
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::frame_system(frame_system::Event::NewAccount(ALICE)),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_balances(
-						pallet_balances::Event::Endowed(ALICE, 1_000_000)
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::frame_system(frame_system::Event::NewAccount(addr_bob.clone())),
-					topics: vec![],
-				},
+			pretty_assertions::assert_eq!(System::events(), vec![
 				EventRecord {
 					phase: Phase::Initialization,
-					event: Event::pallet_balances(
-						pallet_balances::Event::Endowed(addr_bob.clone(), 30_000)
+					event: Event::frame_system(
+						frame_system::Event::KilledAccount(addr.clone())
 					),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::pallet_balances(
-						pallet_balances::Event::Transfer(ALICE, addr_bob.clone(), 30_000)
+						pallet_balances::Event::Transfer(addr.clone(), DJANGO, 100_000)
 					),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
-					event: Event::pallet_contracts(
-						crate::Event::CodeStored(set_rent_code_hash.into())
-					),
+					event: Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
 					topics: vec![],
 				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::pallet_contracts(
-						crate::Event::Instantiated(ALICE, addr_bob.clone())
+						crate::Event::Terminated(addr.clone(), DJANGO)
 					),
 					topics: vec![],
 				},
-			];
-
-			// Create another contract from the same code in order to increment the codes
-			// refcounter so that it stays on chain.
-			if !test_code_evicted {
-				assert_ok!(Contracts::instantiate_with_code(
-					Origin::signed(ALICE),
-					20_000,
-					GAS_LIMIT,
-					set_rent_wasm,
-					allowance.encode(),
-					vec![1],
-				));
-				assert_refcount!(set_rent_code_hash, 2);
-				let addr_dummy = Contracts::contract_address(&ALICE, &set_rent_code_hash, &[1]);
-				events.extend([
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::frame_system(frame_system::Event::NewAccount(addr_dummy.clone())),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_balances(
-							pallet_balances::Event::Endowed(addr_dummy.clone(), 20_000)
-						),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_balances(
-							pallet_balances::Event::Transfer(ALICE, addr_dummy.clone(), 20_000)
-						),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_contracts(
-							crate::Event::Instantiated(ALICE, addr_dummy.clone())
-						),
-						topics: vec![],
-					},
-				].iter().cloned());
-			}
-
-			assert_eq!(System::events(), events);
-
-			// Check if `BOB` was created successfully and that the rent allowance is below what
-			// we specified as the first rent was already collected.
-			let bob_contract = ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_alive().unwrap();
-			assert!(bob_contract.rent_allowance < allowance);
-
-			if test_different_storage {
-				assert_ok!(Contracts::call(
-					Origin::signed(ALICE),
-					addr_bob.clone(), 0, GAS_LIMIT,
-					call::set_storage_4_byte())
-				);
-			}
-
-			// Advance blocks in order to make the contract run out of money for rent.
-			initialize_block(27);
-
-			// Call `BOB`, which makes it pay rent. Since the rent allowance is set to 20_000
-			// we expect that it is no longer callable but keeps existing until someone
-			// calls `claim_surcharge`.
-			assert_err_ignore_postinfo!(
-				Contracts::call(
-					Origin::signed(ALICE), addr_bob.clone(), 0, GAS_LIMIT, call::null()
-				),
-				Error::<Test>::RentNotPaid,
-			);
-			assert!(System::events().is_empty());
-			assert!(ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_alive().is_some());
-			assert_ok!(Contracts::claim_surcharge(Origin::none(), addr_bob.clone(), Some(ALICE)));
-			assert!(ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_tombstone().is_some());
-			if test_code_evicted {
-				assert_refcount!(set_rent_code_hash, 0);
-			} else {
-				assert_refcount!(set_rent_code_hash, 1);
-			}
-
-			// Create another account with the address `DJANGO` with `CODE_RESTORATION`.
-			//
-			// Note that we can't use `ALICE` for creating `DJANGO` so we create yet another
-			// account `CHARLIE` and create `DJANGO` with it.
-			let _ = Balances::deposit_creating(&CHARLIE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(CHARLIE),
-				30_000,
-				GAS_LIMIT,
-				restoration_wasm,
-				vec![],
-				vec![],
-			));
-			let addr_django = Contracts::contract_address(&CHARLIE, &restoration_code_hash, &[]);
-
-			// Before performing a call to `DJANGO` save its original trie id.
-			let django_trie_id = ContractInfoOf::<Test>::get(&addr_django).unwrap()
-				.get_alive().unwrap().trie_id;
-
-			// The trie is regarded as 'dirty' when it was written to in the current block.
-			if !test_restore_to_with_dirty_storage {
-				// Advance 1 block.
-				initialize_block(28);
-			}
-
-			// Perform a call to `DJANGO`. This should either perform restoration successfully or
-			// fail depending on the test parameters.
-			let perform_the_restoration = || {
-				Contracts::call(
-					Origin::signed(ALICE),
-					addr_django.clone(),
-					0,
-					GAS_LIMIT,
-					set_rent_code_hash
-						.as_ref()
-						.iter()
-						.chain(AsRef::<[u8]>::as_ref(&addr_bob))
-						.cloned()
-						.collect(),
-				)
-			};
+			]);
 
-			// The key that is used in the restorer contract but is not in the target contract.
-			// Is supplied as delta to the restoration. We need it to check whether the key
-			// is properly removed on success but still there on failure.
-			let delta_key = {
-				let mut key = [0u8; 32];
-				key[0] = 1;
-				key
-			};
+			// Check that account is gone
+			assert!(ContractInfoOf::<Test>::get(&addr).is_none());
 
-			if test_different_storage || test_restore_to_with_dirty_storage || test_code_evicted {
-				// Parametrization of the test imply restoration failure. Check that `DJANGO` aka
-				// restoration contract is still in place and also that `BOB` doesn't exist.
-				let result = perform_the_restoration();
-				assert!(ContractInfoOf::<Test>::get(&addr_bob).unwrap().get_tombstone().is_some());
-				let django_contract = ContractInfoOf::<Test>::get(&addr_django).unwrap()
-					.get_alive().unwrap();
-				assert_eq!(django_contract.storage_size, 8);
-				assert_eq!(django_contract.trie_id, django_trie_id);
-				assert_eq!(django_contract.deduct_block, System::block_number());
-				assert_eq!(
-					Storage::<Test>::read(&django_trie_id, &delta_key),
-					Some(vec![40, 0, 0, 0]),
-				);
-				match (
-					test_different_storage,
-					test_restore_to_with_dirty_storage,
-					test_code_evicted
-				) {
-					(true, false, false) => {
-						assert_err_ignore_postinfo!(
-							result, Error::<Test>::InvalidTombstone,
-						);
-						assert_eq!(System::events(), vec![]);
-					}
-					(_, true, false) => {
-						assert_err_ignore_postinfo!(
-							result, Error::<Test>::InvalidContractOrigin,
-						);
-						assert_eq!(System::events(), vec![
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_contracts(crate::Event::Evicted(addr_bob)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::frame_system(frame_system::Event::NewAccount(CHARLIE)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_balances(pallet_balances::Event::Endowed(CHARLIE, 1_000_000)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::frame_system(frame_system::Event::NewAccount(addr_django.clone())),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_balances(pallet_balances::Event::Endowed(addr_django.clone(), 30_000)),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_balances(
-									pallet_balances::Event::Transfer(CHARLIE, addr_django.clone(), 30_000)
-								),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_contracts(
-									crate::Event::CodeStored(restoration_code_hash)
-								),
-								topics: vec![],
-							},
-							EventRecord {
-								phase: Phase::Initialization,
-								event: Event::pallet_contracts(
-									crate::Event::Instantiated(CHARLIE, addr_django.clone())
-								),
-								topics: vec![],
-							},
-
-						]);
-					},
-					(false, false, true) => {
-						assert_err_ignore_postinfo!(
-							result, Error::<Test>::CodeNotFound,
-						);
-						assert_refcount!(set_rent_code_hash, 0);
-						assert_eq!(System::events(), vec![]);
-					},
-					_ => unreachable!(),
-				}
-			} else {
-				assert_ok!(perform_the_restoration());
-				assert_refcount!(set_rent_code_hash, 2);
-
-				// Here we expect that the restoration is succeeded. Check that the restoration
-				// contract `DJANGO` ceased to exist and that `BOB` returned back.
-				let bob_contract = ContractInfoOf::<Test>::get(&addr_bob).unwrap()
-					.get_alive().unwrap();
-				assert_eq!(bob_contract.rent_allowance, 50);
-				assert_eq!(bob_contract.storage_size, 4);
-				assert_eq!(bob_contract.trie_id, django_trie_id);
-				assert_eq!(bob_contract.deduct_block, System::block_number());
-				assert!(ContractInfoOf::<Test>::get(&addr_django).is_none());
-				assert_matches!(Storage::<Test>::read(&django_trie_id, &delta_key), None);
-				assert_eq!(System::events(), vec![
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_contracts(crate::Event::CodeRemoved(restoration_code_hash)),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::frame_system(system::Event::KilledAccount(addr_django.clone())),
-						topics: vec![],
-					},
-					EventRecord {
-						phase: Phase::Initialization,
-						event: Event::pallet_contracts(
-							crate::Event::Restored(
-								addr_django, addr_bob, bob_contract.code_hash, 50
-							)
-						),
-						topics: vec![],
-					},
-				]);
-			}
+			// check that the beneficiary (django) got remaining balance
+			assert_eq!(Balances::free_balance(DJANGO), 1_100_000);
 		});
 }
 
+// This tests that one contract cannot prevent another from self-destructing by sending it
+// additional funds after it has been drained.
 #[test]
-fn storage_max_value_limit() {
-	let (wasm, code_hash) = compile_module::<Test>("storage_size").unwrap();
+fn destroy_contract_and_transfer_funds() {
+	let (callee_wasm, callee_code_hash) = compile_module::<Test>("self_destruct").unwrap();
+	let (caller_wasm, caller_code_hash) = compile_module::<Test>("destroy_and_transfer").unwrap();
 
 	ExtBuilder::default()
 		.existential_deposit(50)
@@ -1525,341 +1209,92 @@ fn storage_max_value_limit() {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				30_000,
+				200_000,
 				GAS_LIMIT,
-				wasm,
-				vec![],
+				None,
+				callee_wasm,
 				vec![],
+				vec![42]
 			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-			ContractInfoOf::<Test>::get(&addr).unwrap().get_alive().unwrap();
-
-			// Call contract with allowed storage value.
-			assert_ok!(Contracts::call(
-				Origin::signed(ALICE),
-				addr.clone(),
-				0,
-				GAS_LIMIT * 2, // we are copying a huge buffer
-				<Test as Config>::Schedule::get().limits.payload_len.encode(),
-			));
-
-			// Call contract with too large a storage value.
-			assert_err_ignore_postinfo!(
-				Contracts::call(
-					Origin::signed(ALICE),
-					addr,
-					0,
-					GAS_LIMIT,
-					(<Test as Config>::Schedule::get().limits.payload_len + 1).encode(),
-				),
-				Error::<Test>::ValueTooLarge,
-			);
-		});
-}
-
-#[test]
-fn deploy_and_call_other_contract() {
-	let (callee_wasm, callee_code_hash) = compile_module::<Test>("return_with_data").unwrap();
-	let (caller_wasm, caller_code_hash) = compile_module::<Test>("caller_contract").unwrap();
 
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			// Create
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+			// This deploys the BOB contract, which in turn deploys the CHARLIE contract during
+			// construction.
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				100_000,
+				200_000,
 				GAS_LIMIT,
+				None,
 				caller_wasm,
+				callee_code_hash.as_ref().to_vec(),
 				vec![],
-				vec![],
-			));
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				100_000,
-				GAS_LIMIT,
-				callee_wasm,
-				0u32.to_le_bytes().encode(),
-				vec![42],
 			));
+			let addr_bob = Contracts::contract_address(&ALICE, &caller_code_hash, &[]);
+			let addr_charlie = Contracts::contract_address(
+				&addr_bob, &callee_code_hash, &[0x47, 0x11]
+			);
 
-			// Call BOB contract, which attempts to instantiate and call the callee contract and
-			// makes various assertions on the results from those calls.
+			// Check that the CHARLIE contract has been instantiated.
+			assert_matches!(
+				ContractInfoOf::<Test>::get(&addr_charlie),
+				Some(_)
+			);
+
+			// Call BOB, which calls CHARLIE, forcing CHARLIE to self-destruct.
 			assert_ok!(Contracts::call(
 				Origin::signed(ALICE),
-				Contracts::contract_address(&ALICE, &caller_code_hash, &[]),
+				addr_bob,
 				0,
 				GAS_LIMIT,
-				callee_code_hash.as_ref().to_vec(),
+				None,
+				addr_charlie.encode(),
 			));
+
+			// Check that CHARLIE has moved on to the great beyond (ie. died).
+			assert!(ContractInfoOf::<Test>::get(&addr_charlie).is_none());
 		});
 }
 
 #[test]
-fn cannot_self_destruct_through_draning() {
-	let (wasm, code_hash) = compile_module::<Test>("drain").unwrap();
+fn cannot_self_destruct_in_constructor() {
+	let (wasm, _) = compile_module::<Test>("self_destructing_constructor").unwrap();
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
-			// Instantiate the BOB contract.
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				100_000,
-				GAS_LIMIT,
-				wasm,
-				vec![],
-				vec![],
-			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-
-			// Check that the BOB contract has been instantiated.
-			assert_matches!(
-				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
-			);
-
-			// Call BOB which makes it send all funds to the zero address
-			// The contract code asserts that the correct error value is returned.
-			assert_ok!(
-				Contracts::call(
+			// Fail to instantiate the BOB because the contructor calls seal_terminate.
+			assert_err_ignore_postinfo!(
+				Contracts::instantiate_with_code(
 					Origin::signed(ALICE),
-					addr,
-					0,
+					100_000,
 					GAS_LIMIT,
+					None,
+					wasm,
 					vec![],
-				)
+					vec![],
+				),
+				Error::<Test>::TerminatedInConstructor,
 			);
 		});
 }
 
 #[test]
-fn cannot_self_destruct_while_live() {
-	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
+fn crypto_hashes() {
+	let (wasm, code_hash) = compile_module::<Test>("crypto_hashes").unwrap();
+
 	ExtBuilder::default()
 		.existential_deposit(50)
 		.build()
 		.execute_with(|| {
 			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
-			// Instantiate the BOB contract.
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				100_000,
-				GAS_LIMIT,
-				wasm,
-				vec![],
-				vec![],
-			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-
-			// Check that the BOB contract has been instantiated.
-			assert_matches!(
-				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
-			);
-
-			// Call BOB with input data, forcing it make a recursive call to itself to
-			// self-destruct, resulting in a trap.
-			assert_err_ignore_postinfo!(
-				Contracts::call(
-					Origin::signed(ALICE),
-					addr.clone(),
-					0,
-					GAS_LIMIT,
-					vec![0],
-				),
-				Error::<Test>::ContractTrapped,
-			);
-
-			// Check that BOB is still alive.
-			assert_matches!(
-				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
-			);
-		});
-}
-
-#[test]
-fn self_destruct_works() {
-	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			let _ = Balances::deposit_creating(&DJANGO, 1_000_000);
-
-			// Instantiate the BOB contract.
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				100_000,
-				GAS_LIMIT,
-				wasm,
-				vec![],
-				vec![],
-			));
-			let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-
-			// Check that the BOB contract has been instantiated.
-			assert_matches!(
-				ContractInfoOf::<Test>::get(&addr),
-				Some(ContractInfo::Alive(_))
-			);
-
-			// Drop all previous events
-			initialize_block(2);
-
-			// Call BOB without input data which triggers termination.
-			assert_matches!(
-				Contracts::call(
-					Origin::signed(ALICE),
-					addr.clone(),
-					0,
-					GAS_LIMIT,
-					vec![],
-				),
-				Ok(_)
-			);
-
-			pretty_assertions::assert_eq!(System::events(), vec![
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::frame_system(
-						frame_system::Event::KilledAccount(addr.clone())
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_balances(
-						pallet_balances::Event::Transfer(addr.clone(), DJANGO, 93_086)
-					),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
-					topics: vec![],
-				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::pallet_contracts(
-						crate::Event::Terminated(addr.clone(), DJANGO)
-					),
-					topics: vec![],
-				},
-			]);
-
-			// Check that account is gone
-			assert!(ContractInfoOf::<Test>::get(&addr).is_none());
-
-			// check that the beneficiary (django) got remaining balance
-			// some rent was deducted before termination
-			assert_eq!(Balances::free_balance(DJANGO), 1_093_086);
-		});
-}
-
-// This tests that one contract cannot prevent another from self-destructing by sending it
-// additional funds after it has been drained.
-#[test]
-fn destroy_contract_and_transfer_funds() {
-	let (callee_wasm, callee_code_hash) = compile_module::<Test>("self_destruct").unwrap();
-	let (caller_wasm, caller_code_hash) = compile_module::<Test>("destroy_and_transfer").unwrap();
-
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			// Create
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				200_000,
-				GAS_LIMIT,
-				callee_wasm,
-				vec![],
-				vec![42]
-			));
-
-			// This deploys the BOB contract, which in turn deploys the CHARLIE contract during
-			// construction.
-			assert_ok!(Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				200_000,
-				GAS_LIMIT,
-				caller_wasm,
-				callee_code_hash.as_ref().to_vec(),
-				vec![],
-			));
-			let addr_bob = Contracts::contract_address(&ALICE, &caller_code_hash, &[]);
-			let addr_charlie = Contracts::contract_address(
-				&addr_bob, &callee_code_hash, &[0x47, 0x11]
-			);
-
-			// Check that the CHARLIE contract has been instantiated.
-			assert_matches!(
-				ContractInfoOf::<Test>::get(&addr_charlie),
-				Some(ContractInfo::Alive(_))
-			);
-
-			// Call BOB, which calls CHARLIE, forcing CHARLIE to self-destruct.
-			assert_ok!(Contracts::call(
-				Origin::signed(ALICE),
-				addr_bob,
-				0,
-				GAS_LIMIT,
-				addr_charlie.encode(),
-			));
-
-			// Check that CHARLIE has moved on to the great beyond (ie. died).
-			assert!(ContractInfoOf::<Test>::get(&addr_charlie).is_none());
-		});
-}
-
-#[test]
-fn cannot_self_destruct_in_constructor() {
-	let (wasm, _) = compile_module::<Test>("self_destructing_constructor").unwrap();
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-
-			// Fail to instantiate the BOB because the contructor calls seal_terminate.
-			assert_err_ignore_postinfo!(
-				Contracts::instantiate_with_code(
-					Origin::signed(ALICE),
-					100_000,
-					GAS_LIMIT,
-					wasm,
-					vec![],
-					vec![],
-				),
-				Error::<Test>::TerminatedInConstructor,
-			);
-		});
-}
-
-#[test]
-fn crypto_hashes() {
-	let (wasm, code_hash) = compile_module::<Test>("crypto_hashes").unwrap();
-
-	ExtBuilder::default()
-		.existential_deposit(50)
-		.build()
-		.execute_with(|| {
-			let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-
-			// Instantiate the CRYPTO_HASHES contract.
+			// Instantiate the CRYPTO_HASHES contract.
 			assert_ok!(Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1891,6 +1326,7 @@ fn crypto_hashes() {
 					addr.clone(),
 					0,
 					GAS_LIMIT,
+					None,
 					params,
 					false,
 				).result.unwrap();
@@ -1913,6 +1349,7 @@ fn transfer_return_code() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -1927,6 +1364,7 @@ fn transfer_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 			false,
 		).result.unwrap();
@@ -1942,6 +1380,7 @@ fn transfer_return_code() {
 			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 			false,
 		).result.unwrap();
@@ -1963,6 +1402,7 @@ fn call_return_code() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				caller_code,
 				vec![0],
 				vec![],
@@ -1977,6 +1417,7 @@ fn call_return_code() {
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&DJANGO).to_vec(),
 			false,
 		).result.unwrap();
@@ -1987,6 +1428,7 @@ fn call_return_code() {
 				Origin::signed(CHARLIE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				callee_code,
 				vec![0],
 				vec![],
@@ -2001,6 +1443,7 @@ fn call_return_code() {
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&0u32.to_le_bytes()).cloned().collect(),
 			false,
 		).result.unwrap();
@@ -2016,6 +1459,7 @@ fn call_return_code() {
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&0u32.to_le_bytes()).cloned().collect(),
 			false,
 		).result.unwrap();
@@ -2028,6 +1472,7 @@ fn call_return_code() {
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&1u32.to_le_bytes()).cloned().collect(),
 			false,
 		).result.unwrap();
@@ -2039,6 +1484,7 @@ fn call_return_code() {
 			addr_bob,
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django).iter().chain(&2u32.to_le_bytes()).cloned().collect(),
 			false,
 		).result.unwrap();
@@ -2062,6 +1508,7 @@ fn instantiate_return_code() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				callee_code,
 				vec![],
 				vec![],
@@ -2073,6 +1520,7 @@ fn instantiate_return_code() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				caller_code,
 				vec![],
 				vec![],
@@ -2087,6 +1535,7 @@ fn instantiate_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.clone(),
 			false,
 		).result.unwrap();
@@ -2102,6 +1551,7 @@ fn instantiate_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.clone(),
 			false,
 		).result.unwrap();
@@ -2114,6 +1564,7 @@ fn instantiate_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![0; 33],
 			false,
 		).result.unwrap();
@@ -2125,6 +1576,7 @@ fn instantiate_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.iter().chain(&1u32.to_le_bytes()).cloned().collect(),
 			false,
 		).result.unwrap();
@@ -2136,6 +1588,7 @@ fn instantiate_return_code() {
 			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.iter().chain(&2u32.to_le_bytes()).cloned().collect(),
 			false,
 		).result.unwrap();
@@ -2156,6 +1609,7 @@ fn disabled_chain_extension_wont_deploy() {
 				Origin::signed(ALICE),
 				3 * subsistence,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2176,6 +1630,7 @@ fn disabled_chain_extension_errors_on_call() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2189,6 +1644,7 @@ fn disabled_chain_extension_errors_on_call() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			Error::<Test>::NoChainExtension,
@@ -2207,6 +1663,7 @@ fn chain_extension_works() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2224,6 +1681,7 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![0, 99],
 			false,
 		);
@@ -2237,6 +1695,7 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![1],
 			false,
 		).result.unwrap();
@@ -2249,6 +1708,7 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![2, 42],
 			false,
 		);
@@ -2261,6 +1721,7 @@ fn chain_extension_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![3],
 			false,
 		).result.unwrap();
@@ -2281,6 +1742,7 @@ fn lazy_removal_works() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2288,7 +1750,7 @@ fn lazy_removal_works() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let trie = &info.child_trie_info();
 
 		// Put value into the contracts child trie
@@ -2300,6 +1762,7 @@ fn lazy_removal_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2341,6 +1804,7 @@ fn lazy_removal_partial_remove_works() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2348,18 +1812,17 @@ fn lazy_removal_partial_remove_works() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let mut info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let mut info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 
 		// Put value into the contracts child trie
 		for val in &vals {
 			Storage::<Test>::write(
-				System::block_number(),
 				&mut info,
 				&val.0,
 				Some(val.2.clone()),
 			).unwrap();
 		}
-		<ContractInfoOf<Test>>::insert(&addr, ContractInfo::Alive(info.clone()));
+		<ContractInfoOf<Test>>::insert(&addr, info.clone());
 
 		// Terminate the contract
 		assert_ok!(Contracts::call(
@@ -2367,6 +1830,7 @@ fn lazy_removal_partial_remove_works() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2424,6 +1888,7 @@ fn lazy_removal_does_no_run_on_full_block() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2431,7 +1896,7 @@ fn lazy_removal_does_no_run_on_full_block() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let mut info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let mut info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let max_keys = 30;
 
 		// Create some storage items for the contract.
@@ -2443,13 +1908,12 @@ fn lazy_removal_does_no_run_on_full_block() {
 		// Put value into the contracts child trie
 		for val in &vals {
 			Storage::<Test>::write(
-				System::block_number(),
 				&mut info,
 				&val.0,
 				Some(val.2.clone()),
 			).unwrap();
 		}
-		<ContractInfoOf<Test>>::insert(&addr, ContractInfo::Alive(info.clone()));
+		<ContractInfoOf<Test>>::insert(&addr, info.clone());
 
 		// Terminate the contract
 		assert_ok!(Contracts::call(
@@ -2457,6 +1921,7 @@ fn lazy_removal_does_no_run_on_full_block() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2510,6 +1975,7 @@ fn lazy_removal_does_not_use_all_weight() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2517,7 +1983,7 @@ fn lazy_removal_does_not_use_all_weight() {
 		);
 
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
-		let mut info = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let mut info = <ContractInfoOf::<Test>>::get(&addr).unwrap();
 		let weight_limit = 5_000_000_000;
 		let (weight_per_key, max_keys) = Storage::<Test>::deletion_budget(1, weight_limit);
 
@@ -2530,13 +1996,12 @@ fn lazy_removal_does_not_use_all_weight() {
 		// Put value into the contracts child trie
 		for val in &vals {
 			Storage::<Test>::write(
-				System::block_number(),
 				&mut info,
 				&val.0,
 				Some(val.2.clone()),
 			).unwrap();
 		}
-		<ContractInfoOf<Test>>::insert(&addr, ContractInfo::Alive(info.clone()));
+		<ContractInfoOf<Test>>::insert(&addr, info.clone());
 
 		// Terminate the contract
 		assert_ok!(Contracts::call(
@@ -2544,6 +2009,7 @@ fn lazy_removal_does_not_use_all_weight() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
 
@@ -2582,6 +2048,7 @@ fn deletion_queue_full() {
 				Origin::signed(ALICE),
 				subsistence * 100,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -2600,340 +2067,768 @@ fn deletion_queue_full() {
 				addr.clone(),
 				0,
 				GAS_LIMIT,
+				None,
 				vec![],
 			),
 			Error::<Test>::DeletionQueueFull,
 		);
 
 		// Contract should be alive because removal failed
-		<ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		<ContractInfoOf::<Test>>::get(&addr).unwrap();
+	});
+}
 
-		// make the contract ripe for eviction
-		initialize_block(5);
+#[test]
+fn refcounter() {
+	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let subsistence = Pallet::<Test>::subsistence_threshold();
 
-		// eviction should fail for the same reason as termination
-		assert_err!(
-			Contracts::claim_surcharge(Origin::none(), addr.clone(), Some(ALICE)),
-			Error::<Test>::DeletionQueueFull,
+		// Create two contracts with the same code and check that they do in fact share it.
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			subsistence * 100,
+			GAS_LIMIT,
+			None,
+			wasm.clone(),
+			vec![],
+			vec![0],
+		));
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			subsistence * 100,
+			GAS_LIMIT,
+			None,
+			wasm.clone(),
+			vec![],
+			vec![1],
+		));
+		assert_refcount!(code_hash, 2);
+
+		// Sharing should also work with the usual instantiate call
+		assert_ok!(Contracts::instantiate(
+			Origin::signed(ALICE),
+			subsistence * 100,
+			GAS_LIMIT,
+			None,
+			code_hash,
+			vec![],
+			vec![2],
+		));
+		assert_refcount!(code_hash, 3);
+
+		// addresses of all three existing contracts
+		let addr0 = Contracts::contract_address(&ALICE, &code_hash, &[0]);
+		let addr1 = Contracts::contract_address(&ALICE, &code_hash, &[1]);
+		let addr2 = Contracts::contract_address(&ALICE, &code_hash, &[2]);
+
+		// Terminating one contract should decrement the refcount
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr0,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+		));
+		assert_refcount!(code_hash, 2);
+
+		// remove one of them
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr1,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+		));
+		assert_refcount!(code_hash, 1);
+
+		// Pristine code should still be there
+		crate::PristineCode::<Test>::get(code_hash).unwrap();
+
+		// remove the last contract
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr2,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+		));
+		assert_refcount!(code_hash, 0);
+
+		// all code should be gone
+		assert_matches!(crate::PristineCode::<Test>::get(code_hash), None);
+		assert_matches!(crate::CodeStorage::<Test>::get(code_hash), None);
+	});
+}
+
+#[test]
+fn reinstrument_does_charge() {
+	let (wasm, code_hash) = compile_module::<Test>("return_with_data").unwrap();
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let subsistence = Pallet::<Test>::subsistence_threshold();
+		let zero = 0u32.to_le_bytes().encode();
+		let code_len = wasm.len() as u32;
+
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			subsistence * 100,
+			GAS_LIMIT,
+			None,
+			wasm,
+			zero.clone(),
+			vec![],
+		));
+
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+		// Call the contract two times without reinstrument
+
+		let result0 = Contracts::bare_call(
+			ALICE,
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			zero.clone(),
+			false,
 		);
+		assert!(result0.result.unwrap().is_success());
 
-		// Contract should be alive because removal failed
-		<ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
+		let result1 = Contracts::bare_call(
+			ALICE,
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			zero.clone(),
+			false,
+		);
+		assert!(result1.result.unwrap().is_success());
+
+		// They should match because both where called with the same schedule.
+		assert_eq!(result0.gas_consumed, result1.gas_consumed);
+
+		// We cannot change the schedule. Instead, we decrease the version of the deployed
+		// contract below the current schedule's version.
+		crate::CodeStorage::mutate(&code_hash, |code: &mut Option<PrefabWasmModule<Test>>| {
+			code.as_mut().unwrap().decrement_version();
+		});
+
+		// This call should trigger reinstrumentation
+		let result2 = Contracts::bare_call(
+			ALICE,
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			zero.clone(),
+			false,
+		);
+		assert!(result2.result.unwrap().is_success());
+		assert!(result2.gas_consumed > result1.gas_consumed);
+		assert_eq!(
+			result2.gas_consumed,
+			result1.gas_consumed + <Test as Config>::WeightInfo::instrument(code_len / 1024),
+		);
 	});
 }
 
 #[test]
-fn not_deployed_if_endowment_too_low_for_first_rent() {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
+#[cfg(feature = "unstable-interface")]
+fn debug_message_works() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message_works").unwrap();
 
-	// The instantiation deducted the rent for one block immediately
-	let first_rent = <Test as Config>::RentFraction::get()
-		// base_deposit + deploy_set_storage (4 bytes in 1 item) - free_balance
-		.mul_ceil(80_000u32 + 40_000 + 10_000 - 30_000)
-		// blocks to rent
-		* 1;
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		assert_ok!(
+			Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				wasm,
+				vec![],
+				vec![],
+			),
+		);
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+		let result = Contracts::bare_call(
+			ALICE,
+			addr,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+			true,
+		);
+
+		assert_matches!(result.result, Ok(_));
+		assert_eq!(std::str::from_utf8(&result.debug_message).unwrap(), "Hello World!");
+	});
+}
+
+#[test]
+#[cfg(feature = "unstable-interface")]
+fn debug_message_logging_disabled() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message_logging_disabled").unwrap();
 
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		// Create
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		assert_storage_noop!(assert_err_ignore_postinfo!(
+		assert_ok!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
 				30_000,
 				GAS_LIMIT,
+				None,
 				wasm,
-				(BalanceOf::<Test>::from(first_rent) - BalanceOf::<Test>::from(1u32))
-					.encode(), // rent allowance
+				vec![],
 				vec![],
 			),
-			Error::<Test>::NewContractNotFunded,
+		);
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+		// disable logging by passing `false`
+		let result = Contracts::bare_call(
+			ALICE,
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+			false,
+		);
+		assert_matches!(result.result, Ok(_));
+		// the dispatchables always run without debugging
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
 		));
+		assert!(result.debug_message.is_empty());
+	});
+}
+
+#[test]
+#[cfg(feature = "unstable-interface")]
+fn debug_message_invalid_utf8() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message_invalid_utf8").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		assert_ok!(
+			Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				wasm,
+				vec![],
+				vec![],
+			),
+		);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		assert_matches!(ContractInfoOf::<Test>::get(&addr), None);
+		let result = Contracts::bare_call(
+			ALICE,
+			addr,
+			0,
+			GAS_LIMIT,
+			None,
+			vec![],
+			true,
+		);
+		assert_err!(result.result, <Error<Test>>::DebugMessageInvalidUTF8);
+	});
+}
+
+#[test]
+fn upload_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert!(!<CodeStorage<Test>>::contains_key(code_hash));
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, Some(1_000)));
+		assert!(<CodeStorage<Test>>::contains_key(code_hash));
+		assert_refcount!(code_hash, 0);
+
+		// The deposit is reserved from the uploader.
+		let deposit = Balances::reserved_balance(&ALICE);
+		assert!(deposit > 0);
+		assert_eq!(Balances::free_balance(&ALICE), 1_000_000 - deposit);
+
+		assert_eq!(System::events(), vec![
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_balances(
+					pallet_balances::Event::Reserved(ALICE, deposit)
+				),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_contracts(crate::Event::CodeStored(code_hash)),
+				topics: vec![],
+			},
+		]);
+	});
+}
+
+#[test]
+fn upload_code_limit_too_low() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(
+			Contracts::upload_code(Origin::signed(ALICE), wasm, Some(100)),
+			<Error<Test>>::StorageDepositLimitExhausted,
+		);
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn upload_code_not_enough_balance() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 150);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(
+			Contracts::upload_code(Origin::signed(ALICE), wasm, Some(1_000)),
+			<Error<Test>>::StorageDepositNotEnoughFunds,
+		);
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn upload_code_already_exists() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let _ = Balances::deposit_creating(&BOB, 1_000_000);
+
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm.clone(), None));
+		assert_noop!(
+			Contracts::upload_code(Origin::signed(BOB), wasm, None),
+			<Error<Test>>::CodeAlreadyExists,
+		);
+	});
+}
+
+#[test]
+fn remove_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, Some(1_000)));
+		let deposit = Balances::reserved_balance(&ALICE);
+
+		assert_eq!(
+			Contracts::remove_code(Origin::signed(ALICE), code_hash),
+			Ok(Pays::No.into()),
+		);
+		assert!(!<CodeStorage<Test>>::contains_key(code_hash));
+		assert!(!<PristineCode<Test>>::contains_key(code_hash));
+		assert!(!<OwnerInfoOf<Test>>::contains_key(code_hash));
+
+		// The deposit was refunded.
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(Balances::free_balance(&ALICE), 1_000_000);
+
+		assert_eq!(System::events(), vec![
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_balances(
+					pallet_balances::Event::Reserved(ALICE, deposit)
+				),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_contracts(crate::Event::CodeStored(code_hash)),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_balances(
+					pallet_balances::Event::Unreserved(ALICE, deposit)
+				),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
+				topics: vec![],
+			},
+		]);
+	});
+}
+
+#[test]
+fn remove_code_wrong_origin() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let _ = Balances::deposit_creating(&BOB, 1_000_000);
+
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, Some(1_000)));
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(BOB), code_hash),
+			sp_runtime::traits::BadOrigin,
+		);
+		assert!(<CodeStorage<Test>>::contains_key(code_hash));
 	});
 }
 
 #[test]
-fn surcharge_reward_is_capped() {
-	let (wasm, code_hash) = compile_module::<Test>("set_rent").unwrap();
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+fn remove_code_of_instantiated_code_fails() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Code stored by `instantiate_with_code` has no owner and cannot be removed.
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			wasm,
-			<BalanceOf<Test>>::from(10_000u32).encode(), // rent allowance
+			vec![],
 			vec![],
 		));
-		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		let contract = <ContractInfoOf::<Test>>::get(&addr).unwrap().get_alive().unwrap();
-		let balance = Balances::free_balance(&ALICE);
-		let reward = <Test as Config>::SurchargeReward::get();
-
-		// some rent should have paid due to instantiation
-		assert_ne!(contract.rent_paid, 0);
-
-		// the reward should be parameterized sufficiently high to make this test useful
-		assert!(reward > contract.rent_paid);
-
-		// make contract eligible for eviction
-		initialize_block(40);
-
-		// this should have removed the contract
-		assert_ok!(Contracts::claim_surcharge(Origin::none(), addr.clone(), Some(ALICE)));
-
-		// this reward does not take into account the last rent payment collected during eviction
-		let capped_reward = reward.min(contract.rent_paid);
-
-		// this is smaller than the actual reward because it does not take into account the
-		// rent collected during eviction
-		assert!(Balances::free_balance(&ALICE) > balance + capped_reward);
-
-		// the full reward is not paid out because of the cap introduced by rent_paid
-		assert!(Balances::free_balance(&ALICE) < balance + reward);
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(ALICE), code_hash),
+			sp_runtime::traits::BadOrigin,
+		);
 	});
 }
 
 #[test]
-fn refcounter() {
+fn remove_code_in_use() {
 	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
 
-		// Create two contracts with the same code and check that they do in fact share it.
-		assert_ok!(Contracts::instantiate_with_code(
-			Origin::signed(ALICE),
-			subsistence * 100,
-			GAS_LIMIT,
-			wasm.clone(),
-			vec![],
-			vec![0],
-		));
-		assert_ok!(Contracts::instantiate_with_code(
-			Origin::signed(ALICE),
-			subsistence * 100,
-			GAS_LIMIT,
-			wasm.clone(),
-			vec![],
-			vec![1],
-		));
-		assert_refcount!(code_hash, 2);
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
-		// Sharing should also work with the usual instantiate call
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, None));
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			30_000,
 			GAS_LIMIT,
+			None,
 			code_hash,
 			vec![],
-			vec![2],
+			vec![],
 		));
-		assert_refcount!(code_hash, 3);
+		assert_refcount!(code_hash, 1);
 
-		// addresses of all three existing contracts
-		let addr0 = Contracts::contract_address(&ALICE, &code_hash, &[0]);
-		let addr1 = Contracts::contract_address(&ALICE, &code_hash, &[1]);
-		let addr2 = Contracts::contract_address(&ALICE, &code_hash, &[2]);
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(ALICE), code_hash),
+			<Error<Test>>::CodeInUse,
+		);
 
-		// Terminating one contract should decrement the refcount
+		// Terminating the only contract that uses the code keeps the code around.
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 		assert_ok!(Contracts::call(
 			Origin::signed(ALICE),
-			addr0,
+			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
-		assert_refcount!(code_hash, 2);
-
-		// make remaining contracts eligible for eviction
-		initialize_block(40);
-
-		// remove one of them
-		assert_ok!(Contracts::claim_surcharge(Origin::none(), addr1, Some(ALICE)));
-		assert_refcount!(code_hash, 1);
+		assert_refcount!(code_hash, 0);
+		assert!(<CodeStorage<Test>>::contains_key(code_hash));
+		assert!(<PristineCode<Test>>::contains_key(code_hash));
 
-		// Pristine code should still be there
-		crate::PristineCode::<Test>::get(code_hash).unwrap();
+		// Now the owner can remove it.
+		assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash));
+		assert!(!<CodeStorage<Test>>::contains_key(code_hash));
+	});
+}
 
-		// remove the last contract
-		assert_ok!(Contracts::claim_surcharge(Origin::none(), addr2, Some(ALICE)));
-		assert_refcount!(code_hash, 0);
+#[test]
+fn remove_code_not_found() {
+	let (_wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
 
-		// all code should be gone
-		assert_matches!(crate::PristineCode::<Test>::get(code_hash), None);
-		assert_matches!(crate::CodeStorage::<Test>::get(code_hash), None);
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(ALICE), code_hash),
+			<Error<Test>>::CodeNotFound,
+		);
 	});
 }
 
 #[test]
-fn reinstrument_does_charge() {
-	let (wasm, code_hash) = compile_module::<Test>("return_with_data").unwrap();
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+fn delegate_call_works() {
+	let (caller_wasm, caller_code_hash) = compile_module::<Test>("delegate_call").unwrap();
+	let (lib_wasm, lib_code_hash) = compile_module::<Test>("delegate_call_lib").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let zero = 0u32.to_le_bytes().encode();
-		let code_len = wasm.len() as u32;
 
+		// The library code is only uploaded but never instantiated.
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), lib_wasm, None));
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			30_000,
 			GAS_LIMIT,
-			wasm,
-			zero.clone(),
+			None,
+			caller_wasm,
+			vec![],
 			vec![],
 		));
+		let addr = Contracts::contract_address(&ALICE, &caller_code_hash, &[]);
 
-		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-
-		// Call the contract two times without reinstrument
-
-		let result0 = Contracts::bare_call(
-			ALICE,
+		// The fixture traps if the storage was not written by the library.
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
 			addr.clone(),
 			0,
 			GAS_LIMIT,
-			zero.clone(),
-			false,
-		);
-		assert!(result0.result.unwrap().is_success());
+			None,
+			lib_code_hash.encode(),
+		));
 
-		let result1 = Contracts::bare_call(
-			ALICE,
-			addr.clone(),
-			0,
-			GAS_LIMIT,
-			zero.clone(),
-			false,
-		);
-		assert!(result1.result.unwrap().is_success());
+		let info = ContractInfoOf::<Test>::get(&addr).unwrap();
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		assert_eq!(Storage::<Test>::read(&info.trie_id, &key), Some(vec![2]));
+		assert_eq!(info.code_hash, caller_code_hash);
+		assert_refcount!(lib_code_hash, 0);
+	});
+}
 
-		// They should match because both where called with the same schedule.
-		assert_eq!(result0.gas_consumed, result1.gas_consumed);
+#[test]
+fn bare_call_traced_records_delegate_call() {
+	let (caller_wasm, caller_code_hash) = compile_module::<Test>("delegate_call").unwrap();
+	let (lib_wasm, lib_code_hash) = compile_module::<Test>("delegate_call_lib").unwrap();
 
-		// We cannot change the schedule. Instead, we decrease the version of the deployed
-		// contract below the current schedule's version.
-		crate::CodeStorage::mutate(&code_hash, |code: &mut Option<PrefabWasmModule<Test>>| {
-			code.as_mut().unwrap().decrement_version();
-		});
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), lib_wasm, None));
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			None,
+			caller_wasm,
+			vec![],
+			vec![],
+		));
+		let addr = Contracts::contract_address(&ALICE, &caller_code_hash, &[]);
 
-		// This call should trigger reinstrumentation
-		let result2 = Contracts::bare_call(
+		let result = Contracts::bare_call_traced(
 			ALICE,
 			addr.clone(),
 			0,
 			GAS_LIMIT,
-			zero.clone(),
+			None,
+			lib_code_hash.encode(),
 			false,
 		);
-		assert!(result2.result.unwrap().is_success());
-		assert!(result2.gas_consumed > result1.gas_consumed);
-		assert_eq!(
-			result2.gas_consumed,
-			result1.gas_consumed + <Test as Config>::WeightInfo::instrument(code_len / 1024),
-		);
+		assert_ok!(&result.exec.result);
+
+		let trace = result.trace.unwrap();
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		assert_eq!(trace.kind, CallKind::Call);
+		assert_eq!(trace.caller, ALICE);
+		assert_eq!(trace.callee, addr);
+		assert_eq!(&*trace.input, &lib_code_hash.encode()[..]);
+		assert_eq!(trace.gas_consumed, result.exec.gas_consumed);
+		assert_eq!(trace.result, result.exec.result);
+		assert_eq!(trace.storage, vec![
+			StorageAccess::Read { key: key.into(), value: None },
+			StorageAccess::Read { key: key.into(), value: Some(vec![2].into()) },
+		]);
+
+		assert_eq!(trace.calls.len(), 1);
+		let delegated = &trace.calls[0];
+		assert_eq!(delegated.kind, CallKind::DelegateCall);
+		assert_eq!(delegated.caller, ALICE);
+		assert_eq!(delegated.callee, addr);
+		assert!(delegated.gas_consumed < trace.gas_consumed);
+		assert_eq!(delegated.storage, vec![
+			StorageAccess::Write { key: key.into(), value: Some(vec![2].into()) },
+		]);
+		assert!(delegated.calls.is_empty());
+
+		// Calling an account without a contract fails before any trace is recorded.
+		let result = Contracts::bare_call_traced(ALICE, BOB, 0, GAS_LIMIT, None, vec![], false);
+		assert_eq!(result.exec.result, Err(Error::<Test>::ContractNotFound.into()));
+		assert_eq!(result.trace, None);
 	});
 }
 
 #[test]
-#[cfg(feature = "unstable-interface")]
-fn debug_message_works() {
-	let (wasm, code_hash) = compile_module::<Test>("debug_message_works").unwrap();
+fn delegate_call_missing_code_fails() {
+	let (caller_wasm, caller_code_hash) = compile_module::<Test>("delegate_call").unwrap();
+	let (_, lib_code_hash) = compile_module::<Test>("delegate_call_lib").unwrap();
 
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		assert_ok!(
-			Contracts::instantiate_with_code(
+
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			None,
+			caller_wasm,
+			vec![],
+			vec![],
+		));
+		let addr = Contracts::contract_address(&ALICE, &caller_code_hash, &[]);
+
+		// `seal_delegate_call` returns `CodeNotFound` which makes the fixture trap.
+		assert_err_ignore_postinfo!(
+			Contracts::call(
 				Origin::signed(ALICE),
-				30_000,
+				addr,
+				0,
 				GAS_LIMIT,
-				wasm,
-				vec![],
-				vec![],
+				None,
+				lib_code_hash.encode(),
 			),
+			Error::<Test>::ContractTrapped,
 		);
-		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		let result = Contracts::bare_call(
-			ALICE,
-			addr,
-			0,
-			GAS_LIMIT,
-			vec![],
-			true,
-		);
-
-		assert_matches!(result.result, Ok(_));
-		assert_eq!(std::str::from_utf8(&result.debug_message).unwrap(), "Hello World!");
 	});
 }
 
 #[test]
-#[cfg(feature = "unstable-interface")]
-fn debug_message_logging_disabled() {
-	let (wasm, code_hash) = compile_module::<Test>("debug_message_logging_disabled").unwrap();
+fn set_code_hash_works() {
+	let (wasm, code_hash) = compile_module::<Test>("set_code_hash").unwrap();
+	let (new_wasm, new_code_hash) = compile_module::<Test>("dummy").unwrap();
 
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		assert_ok!(
-			Contracts::instantiate_with_code(
-				Origin::signed(ALICE),
-				30_000,
-				GAS_LIMIT,
-				wasm,
-				vec![],
-				vec![],
-			),
-		);
+
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), new_wasm, None));
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			None,
+			wasm,
+			vec![],
+			vec![],
+		));
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		// disable logging by passing `false`
-		let result = Contracts::bare_call(
-			ALICE,
+		assert_refcount!(code_hash, 1);
+		assert_refcount!(new_code_hash, 0);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
 			addr.clone(),
 			0,
 			GAS_LIMIT,
-			vec![],
-			false,
-		);
-		assert_matches!(result.result, Ok(_));
-		// the dispatchables always run without debugging
+			None,
+			new_code_hash.encode(),
+		));
+
+		assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().code_hash, new_code_hash);
+		assert_refcount!(new_code_hash, 1);
+
+		// The replaced code was not uploaded and had no other user: It is removed.
+		assert!(!<CodeStorage<Test>>::contains_key(code_hash));
+		assert!(!<PristineCode<Test>>::contains_key(code_hash));
+
+		assert_eq!(System::events(), vec![
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_contracts(crate::Event::CodeRemoved(code_hash)),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::pallet_contracts(
+					crate::Event::ContractCodeUpdated(addr.clone(), new_code_hash, code_hash)
+				),
+				topics: vec![],
+			},
+		]);
+
+		// The contract now runs the new code which does not trap on empty input.
 		assert_ok!(Contracts::call(
 			Origin::signed(ALICE),
 			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			vec![],
 		));
-		assert!(result.debug_message.is_empty());
+
+		// The uploaded code is in use and cannot be removed.
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(ALICE), new_code_hash),
+			<Error<Test>>::CodeInUse,
+		);
 	});
 }
 
 #[test]
-#[cfg(feature = "unstable-interface")]
-fn debug_message_invalid_utf8() {
-	let (wasm, code_hash) = compile_module::<Test>("debug_message_invalid_utf8").unwrap();
+fn set_code_hash_missing_code_fails() {
+	let (wasm, code_hash) = compile_module::<Test>("set_code_hash").unwrap();
+	let (_, new_code_hash) = compile_module::<Test>("dummy").unwrap();
 
-	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		assert_ok!(
-			Contracts::instantiate_with_code(
+
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			None,
+			wasm,
+			vec![],
+			vec![],
+		));
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+		// `seal_set_code_hash` returns `CodeNotFound` which makes the fixture trap.
+		assert_err_ignore_postinfo!(
+			Contracts::call(
 				Origin::signed(ALICE),
-				30_000,
+				addr.clone(),
+				0,
 				GAS_LIMIT,
-				wasm,
-				vec![],
-				vec![],
+				None,
+				new_code_hash.encode(),
 			),
+			Error::<Test>::ContractTrapped,
 		);
-		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		let result = Contracts::bare_call(
-			ALICE,
-			addr,
-			0,
-			GAS_LIMIT,
-			vec![],
-			true,
-		);
-		assert_err!(result.result, <Error<Test>>::DebugMessageInvalidUTF8);
+		assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().code_hash, code_hash);
+		assert_refcount!(code_hash, 1);
 	});
 }
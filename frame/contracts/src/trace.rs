@@ -0,0 +1,112 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recording of the call tree executed by a call stack.
+//!
+//! This is only used by RPCs and never during on-chain execution because the recorded
+//! trace holds copies of all inputs, outputs and storage values.
+
+use crate::{BalanceOf, Config, exec::{AccountIdOf, StorageKey}};
+use frame_support::{dispatch::DispatchError, weights::Weight};
+use pallet_contracts_primitives::{
+	CallKind, CallTrace, ExecReturnValue, ReturnFlags, StorageAccess,
+};
+use sp_std::prelude::*;
+
+pub type CallTraceOf<T> = CallTrace<AccountIdOf<T>, BalanceOf<T>>;
+
+/// Builds a [`CallTrace`] from the frames entered and left by a call stack.
+pub struct CallTracer<T: Config> {
+	/// Traces of the frames that were entered but not yet left. The last one belongs to
+	/// the frame that is currently executing.
+	open: Vec<CallTraceOf<T>>,
+	/// The trace of the first frame once it was left.
+	finished: Option<CallTraceOf<T>>,
+}
+
+impl<T: Config> CallTracer<T> {
+	/// Create a tracer that did not record anything, yet.
+	pub fn new() -> Self {
+		Self {
+			open: Vec::new(),
+			finished: None,
+		}
+	}
+
+	/// Return the recorded trace or `None` if no frame was ever left.
+	pub fn into_trace(self) -> Option<CallTraceOf<T>> {
+		self.finished
+	}
+
+	/// Record that a new frame starts executing.
+	pub fn enter(
+		&mut self,
+		kind: CallKind,
+		caller: AccountIdOf<T>,
+		callee: AccountIdOf<T>,
+		value: BalanceOf<T>,
+		input: Vec<u8>,
+	) {
+		self.open.push(CallTrace {
+			kind,
+			caller,
+			callee,
+			value,
+			input: input.into(),
+			gas_consumed: 0,
+			storage: Vec::new(),
+			result: Ok(ExecReturnValue { flags: ReturnFlags::empty(), data: Vec::new().into() }),
+			calls: Vec::new(),
+		});
+	}
+
+	/// Record a storage read of the currently executing frame.
+	pub fn storage_read(&mut self, key: &StorageKey, value: Option<&Vec<u8>>) {
+		self.storage_access(StorageAccess::Read {
+			key: key.into(),
+			value: value.cloned().map(Into::into),
+		});
+	}
+
+	/// Record a storage write of the currently executing frame.
+	pub fn storage_write(&mut self, key: &StorageKey, value: Option<&Vec<u8>>) {
+		self.storage_access(StorageAccess::Write {
+			key: key.into(),
+			value: value.cloned().map(Into::into),
+		});
+	}
+
+	/// Record that the currently executing frame finished.
+	pub fn exit(&mut self, gas_consumed: Weight, result: Result<ExecReturnValue, DispatchError>) {
+		let mut trace = match self.open.pop() {
+			Some(trace) => trace,
+			None => return,
+		};
+		trace.gas_consumed = gas_consumed;
+		trace.result = result;
+		match self.open.last_mut() {
+			Some(parent) => parent.calls.push(trace),
+			None => self.finished = Some(trace),
+		}
+	}
+
+	fn storage_access(&mut self, access: StorageAccess) {
+		if let Some(trace) = self.open.last_mut() {
+			trace.storage.push(access);
+		}
+	}
+}
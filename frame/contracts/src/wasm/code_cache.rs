@@ -28,13 +28,18 @@
 //! Thus, before executing a contract it should be reinstrument with new schedule.
 
 use crate::{
-	CodeHash, CodeStorage, PristineCode, Schedule, Config, Error, Weight,
-	wasm::{prepare, PrefabWasmModule}, Pallet as Contracts, Event,
+	CodeHash, CodeStorage, PristineCode, OwnerInfoOf, Schedule, Config, Error, Weight, BalanceOf,
+	wasm::{prepare, PrefabWasmModule, OwnerInfo}, Pallet as Contracts, Event,
 	gas::{GasMeter, Token},
 	weights::WeightInfo,
 };
 use sp_core::crypto::UncheckedFrom;
-use frame_support::dispatch::DispatchError;
+use sp_runtime::traits::Saturating;
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::{Get, ReservableCurrency},
+	ensure,
+};
 #[cfg(feature = "runtime-benchmarks")]
 pub use self::private::reinstrument as reinstrument;
 
@@ -64,15 +69,70 @@ where
 	});
 }
 
+/// Put the instrumented module in storage and make `owner` pay a deposit for it.
+///
+/// Other than [`store`] this fails if the code already exists. The code is not removed
+/// when no contract uses it anymore but only when the `owner` calls [`try_remove`].
+/// Returns the deposit that was reserved from the `owner`.
+pub fn store_owned<T: Config>(
+	mut prefab_module: PrefabWasmModule<T>,
+	owner: T::AccountId,
+	storage_deposit_limit: Option<BalanceOf<T>>,
+) -> Result<BalanceOf<T>, DispatchError>
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
+{
+	let code_hash = prefab_module.code_hash;
+	ensure!(!<CodeStorage<T>>::contains_key(&code_hash), Error::<T>::CodeAlreadyExists);
+
+	// The deposit covers the instrumented code, the pristine code and the owner info.
+	let bytes: BalanceOf<T> = prefab_module.original_code_len
+		.saturating_add(prefab_module.code.len() as u32)
+		.into();
+	let deposit = T::DepositPerByte::get().saturating_mul(bytes)
+		.saturating_add(T::DepositPerItem::get().saturating_mul(3u32.into()));
+	if let Some(limit) = storage_deposit_limit {
+		ensure!(deposit <= limit, Error::<T>::StorageDepositLimitExhausted);
+	}
+	T::Currency::reserve(&owner, deposit)
+		.map_err(|_| Error::<T>::StorageDepositNotEnoughFunds)?;
+
+	// No contract uses this code, yet.
+	prefab_module.refcount = 0;
+	<OwnerInfoOf<T>>::insert(&code_hash, OwnerInfo { owner, deposit });
+	store(prefab_module);
+	Ok(deposit)
+}
+
+/// Remove an unused code on behalf of its `owner` and refund the deposit.
+///
+/// Fails if the code is used by any contract or if it was not uploaded by `owner`.
+pub fn try_remove<T: Config>(owner: &T::AccountId, code_hash: CodeHash<T>) -> DispatchResult
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
+{
+	let module = <CodeStorage<T>>::get(&code_hash).ok_or(Error::<T>::CodeNotFound)?;
+	let owner_info = <OwnerInfoOf<T>>::get(&code_hash).ok_or(DispatchError::BadOrigin)?;
+	ensure!(&owner_info.owner == owner, DispatchError::BadOrigin);
+	ensure!(module.refcount == 0, Error::<T>::CodeInUse);
+
+	T::Currency::unreserve(&owner_info.owner, owner_info.deposit);
+	<OwnerInfoOf<T>>::remove(&code_hash);
+	<CodeStorage<T>>::remove(&code_hash);
+	finish_removal::<T>(code_hash);
+	Ok(())
+}
+
 /// Decrement the refcount and store.
 ///
-/// Removes the code instead of storing it when the refcount drops to zero.
+/// Removes the code instead of storing it when the refcount drops to zero and the code
+/// has no owner.
 pub fn store_decremented<T: Config>(mut prefab_module: PrefabWasmModule<T>)
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
 {
 	prefab_module.refcount = prefab_module.refcount.saturating_sub(1);
-	if prefab_module.refcount > 0 {
+	if prefab_module.refcount > 0 || <OwnerInfoOf<T>>::contains_key(&prefab_module.code_hash) {
 		<CodeStorage<T>>::insert(prefab_module.code_hash, prefab_module);
 	} else {
 		<CodeStorage<T>>::remove(prefab_module.code_hash);
@@ -96,6 +156,8 @@ where
 }
 
 /// Decrement the refcount of a code in-storage by one and remove the code when it drops to zero.
+///
+/// Code that has an owner is kept in storage until its owner removes it.
 pub fn decrement_refcount<T: Config>(code_hash: CodeHash<T>) -> u32
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>
@@ -104,7 +166,7 @@ where
 		if let Some(module) = existing {
 			let code_len = module.original_code_len;
 			module.refcount = module.refcount.saturating_sub(1);
-			if module.refcount == 0 {
+			if module.refcount == 0 && !<OwnerInfoOf<T>>::contains_key(&code_hash) {
 				*existing = None;
 				finish_removal::<T>(code_hash);
 			}
@@ -25,14 +25,15 @@ mod prepare;
 mod runtime;
 
 use crate::{
-	CodeHash, Schedule, Config,
+	CodeHash, Schedule, Config, BalanceOf,
 	wasm::env_def::FunctionImplProvider,
-	exec::{Ext, Executable, ExportedFunction, ExecResult},
+	exec::{Ext, Executable, ExportedFunction, ExecResult, AccountIdOf},
 	gas::GasMeter,
 };
 use sp_std::prelude::*;
 use sp_core::crypto::UncheckedFrom;
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use frame_support::dispatch::DispatchError;
 pub use self::runtime::{ReturnCode, Runtime, RuntimeCosts};
 #[cfg(feature = "runtime-benchmarks")]
@@ -50,7 +51,8 @@ pub use tests::MockExt;
 /// `instruction_weights_version` and `code` when a contract with an outdated instrumention is
 /// called. Therefore one must be careful when holding any in-memory representation of this
 /// type while calling into a contract as those fields can get out of date.
-#[derive(Clone, Encode, Decode)]
+#[derive(Clone, Encode, Decode, TypeInfo)]
+#[scale_info(skip_type_params(T))]
 pub struct PrefabWasmModule<T: Config> {
 	/// Version of the instruction weights with which the code was instrumented.
 	#[codec(compact)]
@@ -79,7 +81,7 @@ pub struct PrefabWasmModule<T: Config> {
 	/// The size of the uninstrumented code.
 	///
 	/// We cache this value here in order to avoid the need to pull the pristine code
-	/// from storage when we only need its length for rent calculations.
+	/// from storage when we only need its length for weight calculations.
 	original_code_len: u32,
 	/// The uninstrumented, pristine version of the code.
 	///
@@ -96,6 +98,21 @@ pub struct PrefabWasmModule<T: Config> {
 	code_hash: CodeHash<T>,
 }
 
+/// Information about the account that uploaded a code via `upload_code`.
+///
+/// Code that was stored as a side effect of instantiating a contract has no owner. It is
+/// removed as soon as the last contract using it is removed. Owned code stays in storage
+/// until the owner removes it via `remove_code`.
+#[derive(Clone, Encode, Decode, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct OwnerInfo<T: Config> {
+	/// The account that uploaded the code and paid the deposit for it.
+	owner: AccountIdOf<T>,
+	/// The amount of balance that is reserved on the `owner` for storing the code.
+	#[codec(compact)]
+	deposit: BalanceOf<T>,
+}
+
 impl ExportedFunction {
 	/// The wasm export name for the function.
 	fn identifier(&self) -> &str {
@@ -135,6 +152,24 @@ where
 		Ok(())
 	}
 
+	/// Store the module and make `owner` pay a deposit for it.
+	///
+	/// Returns the deposit that was reserved from the `owner`. See [`code_cache::store_owned`].
+	pub fn store_owned(
+		self,
+		owner: AccountIdOf<T>,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		code_cache::store_owned(self, owner, storage_deposit_limit)
+	}
+
+	/// Remove the code stored under `code_hash` on behalf of its `owner`.
+	///
+	/// See [`code_cache::try_remove`].
+	pub fn remove(owner: &AccountIdOf<T>, code_hash: CodeHash<T>) -> Result<(), DispatchError> {
+		code_cache::try_remove::<T>(owner, code_hash)
+	}
+
 	/// Return the refcount of the module.
 	#[cfg(test)]
 	pub fn refcount(&self) -> u64 {
@@ -227,14 +262,6 @@ where
 	fn code_len(&self) -> u32 {
 		self.code.len() as u32
 	}
-
-	fn aggregate_code_len(&self) -> u32 {
-		self.original_code_len.saturating_add(self.code_len())
-	}
-
-	fn refcount(&self) -> u32 {
-		self.refcount as u32
-	}
 }
 
 #[cfg(test)]
@@ -244,10 +271,10 @@ mod tests {
 		CodeHash, BalanceOf, Error, Pallet as Contracts,
 		exec::{
 			Ext, StorageKey, AccountIdOf, Executable, SeedOf, BlockNumberOf,
-			RentParams, ExecError, ErrorOrigin,
+			ExecError, ErrorOrigin,
 		},
 		gas::GasMeter,
-		rent::RentStatus,
+		storage::ContractInfo,
 		tests::{Test, Call, ALICE, BOB},
 	};
 	use std::collections::HashMap;
@@ -263,14 +290,6 @@ mod tests {
 	#[derive(Debug, PartialEq, Eq)]
 	struct DispatchEntry(Call);
 
-	#[derive(Debug, PartialEq, Eq)]
-	struct RestoreEntry {
-		dest: AccountIdOf<Test>,
-		code_hash: H256,
-		rent_allowance: u64,
-		delta: Vec<StorageKey>,
-	}
-
 	#[derive(Debug, PartialEq, Eq)]
 	struct InstantiateEntry {
 		code_hash: H256,
@@ -299,18 +318,23 @@ mod tests {
 		allows_reentry: bool,
 	}
 
+	#[derive(Debug, PartialEq, Eq)]
+	struct DelegateCallEntry {
+		code_hash: H256,
+		data: Vec<u8>,
+	}
+
 	pub struct MockExt {
 		storage: HashMap<StorageKey, Vec<u8>>,
-		rent_allowance: u64,
 		instantiates: Vec<InstantiateEntry>,
 		terminations: Vec<TerminationEntry>,
 		calls: Vec<CallEntry>,
+		delegate_calls: Vec<DelegateCallEntry>,
+		code_hashes: Vec<H256>,
 		transfers: Vec<TransferEntry>,
-		restores: Vec<RestoreEntry>,
 		// (topics, data)
 		events: Vec<(Vec<H256>, Vec<u8>)>,
 		schedule: Schedule<Test>,
-		rent_params: RentParams<Test>,
 		gas_meter: GasMeter<Test>,
 		debug_buffer: Vec<u8>,
 	}
@@ -324,15 +348,14 @@ mod tests {
 		fn default() -> Self {
 			Self {
 				storage: Default::default(),
-				rent_allowance: Default::default(),
 				instantiates: Default::default(),
 				terminations: Default::default(),
 				calls: Default::default(),
+				delegate_calls: Default::default(),
+				code_hashes: Default::default(),
 				transfers: Default::default(),
-				restores: Default::default(),
 				events: Default::default(),
 				schedule: Default::default(),
-				rent_params: Default::default(),
 				gas_meter: GasMeter::new(10_000_000_000),
 				debug_buffer: Default::default(),
 			}
@@ -358,6 +381,17 @@ mod tests {
 			});
 			Ok((ExecReturnValue { flags: ReturnFlags::empty(), data: call_return_data() }, 0))
 		}
+		fn delegate_call(
+			&mut self,
+			code_hash: CodeHash<Self::T>,
+			data: Vec<u8>,
+		) -> Result<(ExecReturnValue, u32), (ExecError, u32)> {
+			self.delegate_calls.push(DelegateCallEntry {
+				code_hash,
+				data,
+			});
+			Ok((ExecReturnValue { flags: ReturnFlags::empty(), data: call_return_data() }, 0))
+		}
 		fn instantiate(
 			&mut self,
 			gas_limit: Weight,
@@ -402,20 +436,9 @@ mod tests {
 			});
 			Ok(0)
 		}
-		fn restore_to(
-			&mut self,
-			dest: AccountIdOf<Self::T>,
-			code_hash: H256,
-			rent_allowance: u64,
-			delta: Vec<StorageKey>,
-		) -> Result<(u32, u32), (DispatchError, u32, u32)> {
-			self.restores.push(RestoreEntry {
-				dest,
-				code_hash,
-				rent_allowance,
-				delta,
-			});
-			Ok((0, 0))
+		fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<u32, (DispatchError, u32)> {
+			self.code_hashes.push(hash);
+			Ok(0)
 		}
 		fn get_storage(&mut self, key: &StorageKey) -> Option<Vec<u8>> {
 			self.storage.get(key).cloned()
@@ -442,21 +465,12 @@ mod tests {
 		fn minimum_balance(&self) -> u64 {
 			666
 		}
-		fn tombstone_deposit(&self) -> u64 {
-			16
-		}
 		fn random(&self, subject: &[u8]) -> (SeedOf<Self::T>, BlockNumberOf<Self::T>) {
 			(H256::from_slice(subject), 42)
 		}
 		fn deposit_event(&mut self, topics: Vec<H256>, data: Vec<u8>) {
 			self.events.push((topics, data))
 		}
-		fn set_rent_allowance(&mut self, rent_allowance: u64) {
-			self.rent_allowance = rent_allowance;
-		}
-		fn rent_allowance(&mut self) -> u64 {
-			self.rent_allowance
-		}
 		fn block_number(&self) -> u64 { 121 }
 		fn max_value_size(&self) -> u32 { 16_384 }
 		fn get_weight_price(&self, weight: Weight) -> BalanceOf<Self::T> {
@@ -465,12 +479,6 @@ mod tests {
 		fn schedule(&self) -> &Schedule<Self::T> {
 			&self.schedule
 		}
-		fn rent_params(&self) -> &RentParams<Self::T> {
-			&self.rent_params
-		}
-		fn rent_status(&mut self, _at_refcount: u32) -> RentStatus<Self::T> {
-			Default::default()
-		}
 		fn gas_meter(&mut self) -> &mut GasMeter<Self::T> {
 			&mut self.gas_meter
 		}
@@ -478,6 +486,9 @@ mod tests {
 			self.debug_buffer.extend(msg.as_bytes());
 			true
 		}
+		fn contract_info(&mut self) -> &mut ContractInfo<Self::T> {
+			unimplemented!()
+		}
 	}
 
 	fn execute<E: BorrowMut<MockExt>>(
@@ -791,6 +802,151 @@ mod tests {
 		);
 	}
 
+	const CODE_DELEGATE_CALL: &str = r#"
+(module
+	;; seal_delegate_call(
+	;;    flags: u32,
+	;;    code_hash_ptr: u32,
+	;;    code_hash_len: u32,
+	;;    input_data_ptr: u32,
+	;;    input_data_len: u32,
+	;;    output_ptr: u32,
+	;;    output_len_ptr: u32
+	;;) -> u32
+	(import "seal0" "seal_delegate_call" (func $seal_delegate_call (param i32 i32 i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(func (export "call")
+		(drop
+			(call $seal_delegate_call
+				(i32.const 0) ;; No flags are set
+				(i32.const 4)  ;; Pointer to "code_hash".
+				(i32.const 32)  ;; Length of "code_hash".
+				(i32.const 36) ;; Pointer to input data buffer address
+				(i32.const 4)  ;; Length of input data buffer
+				(i32.const 4294967295) ;; u32 max value is the sentinel value: do not copy output
+				(i32.const 0) ;; Length is ignored in this case
+			)
+		)
+	)
+	(func (export "deploy"))
+
+	;; Code hash of the code to execute
+	(data (i32.const 4)
+		"\11\22\33\44\55\66\77\88\99\AA\BB\CC\DD\EE\FF\00"
+		"\11\22\33\44\55\66\77\88\99\AA\BB\CC\DD\EE\FF\00"
+	)
+
+	(data (i32.const 36) "\01\02\03\04")
+)
+"#;
+
+	#[test]
+	fn contract_delegate_call() {
+		let mut mock_ext = MockExt::default();
+		assert_ok!(execute(
+			CODE_DELEGATE_CALL,
+			vec![],
+			&mut mock_ext,
+		));
+
+		assert_eq!(
+			&mock_ext.delegate_calls,
+			&[DelegateCallEntry {
+				code_hash: H256::from(hex!(
+					"112233445566778899AABBCCDDEEFF00112233445566778899AABBCCDDEEFF00"
+				)),
+				data: vec![1, 2, 3, 4],
+			}]
+		);
+	}
+
+	#[test]
+	fn contract_delegate_call_allow_reentry_traps() {
+		const CODE: &str = r#"
+(module
+	(import "seal0" "seal_delegate_call" (func $seal_delegate_call (param i32 i32 i32 i32 i32 i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(func (export "call")
+		(drop
+			(call $seal_delegate_call
+				(i32.const 8) ;; Set ALLOW_REENTRY bit
+				(i32.const 4)  ;; Pointer to "code_hash".
+				(i32.const 32)  ;; Length of "code_hash".
+				(i32.const 0) ;; Pointer to input data buffer address
+				(i32.const 0)  ;; Length of input data buffer
+				(i32.const 4294967295) ;; u32 max value is the sentinel value: do not copy output
+				(i32.const 0) ;; Length is ignored in this case
+			)
+		)
+	)
+	(func (export "deploy"))
+)
+"#;
+		let mut mock_ext = MockExt::default();
+		let result = execute(
+			CODE,
+			vec![],
+			&mut mock_ext,
+		);
+
+		assert_eq!(
+			result,
+			Err(ExecError {
+				error: Error::<Test>::InvalidCallFlags.into(),
+				origin: ErrorOrigin::Caller,
+			})
+		);
+		assert!(mock_ext.delegate_calls.is_empty());
+	}
+
+	const CODE_SET_CODE_HASH: &str = r#"
+(module
+	;; seal_set_code_hash(code_hash_ptr: u32, code_hash_len: u32) -> u32
+	(import "seal0" "seal_set_code_hash" (func $seal_set_code_hash (param i32 i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+	(func (export "call")
+		(call $assert
+			(i32.eqz
+				(call $seal_set_code_hash
+					(i32.const 0) ;; Pointer to the new code hash
+					(i32.const 32) ;; Length of the new code hash
+				)
+			)
+		)
+	)
+	(func (export "deploy"))
+
+	;; New code hash
+	(data (i32.const 0)
+		"\11\22\33\44\55\66\77\88\99\AA\BB\CC\DD\EE\FF\00"
+		"\11\22\33\44\55\66\77\88\99\AA\BB\CC\DD\EE\FF\00"
+	)
+)
+"#;
+
+	#[test]
+	fn contract_set_code_hash() {
+		let mut mock_ext = MockExt::default();
+		assert_ok!(execute(
+			CODE_SET_CODE_HASH,
+			vec![],
+			&mut mock_ext,
+		));
+
+		assert_eq!(
+			&mock_ext.code_hashes,
+			&[H256::from(hex!("112233445566778899AABBCCDDEEFF00112233445566778899AABBCCDDEEFF00"))],
+		);
+	}
+
 	const CODE_INSTANTIATE: &str = r#"
 (module
 	;; seal_instantiate(
@@ -1538,11 +1694,11 @@ mod tests {
 			)
 		)
 
-		;; assert that contents of the buffer is equal to the i64 value of 16.
+		;; assert that contents of the buffer is equal to the i64 value of 0.
 		(call $assert
 			(i64.eq
 				(i64.load (i32.const 0))
-				(i64.const 16)
+				(i64.const 0)
 			)
 		)
 	)
@@ -2017,93 +2173,6 @@ mod tests {
 		);
 	}
 
-
-
-	#[test]
-	#[cfg(feature = "unstable-interface")]
-	fn rent_params_work() {
-		const CODE_RENT_PARAMS: &str = r#"
-(module
-	(import "__unstable__" "seal_rent_params" (func $seal_rent_params (param i32 i32)))
-	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
-	(import "env" "memory" (memory 1 1))
-
-	;; [0, 4) buffer size = 128 bytes
-	(data (i32.const 0) "\80")
-
-	;; [4; inf) buffer where the result is copied
-
-	(func (export "call")
-		;; Load the rent params into memory
-		(call $seal_rent_params
-			(i32.const 4)		;; Pointer to the output buffer
-			(i32.const 0)		;; Pointer to the size of the buffer
-		)
-
-		;; Return the contents of the buffer
-		(call $seal_return
-			(i32.const 0)				;; return flags
-			(i32.const 4)				;; buffer pointer
-			(i32.load (i32.const 0))	;; buffer size
-		)
-	)
-
-	(func (export "deploy"))
-)
-"#;
-		let output = execute(
-			CODE_RENT_PARAMS,
-			vec![],
-			MockExt::default(),
-		).unwrap();
-		let rent_params = Bytes(<RentParams<Test>>::default().encode());
-		assert_eq!(output, ExecReturnValue { flags: ReturnFlags::empty(), data: rent_params });
-	}
-
-
-
-	#[test]
-	#[cfg(feature = "unstable-interface")]
-	fn rent_status_works() {
-		const CODE_RENT_STATUS: &str = r#"
-(module
-	(import "__unstable__" "seal_rent_status" (func $seal_rent_status (param i32 i32 i32)))
-	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
-	(import "env" "memory" (memory 1 1))
-
-	;; [0, 4) buffer size = 128 bytes
-	(data (i32.const 0) "\80")
-
-	;; [4; inf) buffer where the result is copied
-
-	(func (export "call")
-		;; Load the rent params into memory
-		(call $seal_rent_status
-			(i32.const 1)		;; at_refcount
-			(i32.const 4)		;; Pointer to the output buffer
-			(i32.const 0)		;; Pointer to the size of the buffer
-		)
-
-		;; Return the contents of the buffer
-		(call $seal_return
-			(i32.const 0)				;; return flags
-			(i32.const 4)				;; buffer pointer
-			(i32.load (i32.const 0))	;; buffer size
-		)
-	)
-
-	(func (export "deploy"))
-)
-"#;
-		let output = execute(
-			CODE_RENT_STATUS,
-			vec![],
-			MockExt::default(),
-		).unwrap();
-		let rent_status = Bytes(<RentStatus<Test>>::default().encode());
-		assert_eq!(output, ExecReturnValue { flags: ReturnFlags::empty(), data: rent_status });
-	}
-
 	#[test]
 	#[cfg(feature = "unstable-interface")]
 	fn debug_message_works() {
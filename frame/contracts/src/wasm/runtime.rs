@@ -30,6 +30,7 @@ use frame_support::{dispatch::DispatchError, ensure, traits::Get, weights::Weigh
 use sp_std::prelude::*;
 use codec::{Decode, DecodeAll, Encode};
 use sp_core::{Bytes, crypto::UncheckedFrom};
+use sp_runtime::traits::{Bounded, Zero};
 use sp_io::hashing::{
 	keccak_256,
 	blake2_256,
@@ -58,19 +59,17 @@ pub enum ReturnCode {
 	CalleeReverted = 2,
 	/// The passed key does not exist in storage.
 	KeyNotFound = 3,
-	/// Transfer failed because it would have brought the sender's total balance below the
+	/// Transfer failed because it would have brought the sender's free balance below the
 	/// subsistence threshold.
 	BelowSubsistenceThreshold = 4,
 	/// Transfer failed for other reasons. Most probably reserved or locked balance of the
 	/// sender prevents the transfer.
 	TransferFailed = 5,
-	/// The newly created contract is below the subsistence threshold after executing
-	/// its constructor.
+	/// The endowment of the newly created contract is below the subsistence threshold.
 	NewContractNotFunded = 6,
 	/// No code could be found at the supplied code hash.
 	CodeNotFound = 7,
-	/// The contract that was called is either no contract at all (a plain account)
-	/// or is a tombstone.
+	/// The contract that was called is no contract (a plain account).
 	NotCallable = 8,
 	/// The call to `seal_debug_message` had no effect because debug message
 	/// recording was disabled.
@@ -124,8 +123,6 @@ pub enum TrapReason {
 	/// Signals that a trap was generated in response to a successful call to the
 	/// `seal_terminate` host function.
 	Termination,
-	/// Signals that a trap was generated because of a successful restoration.
-	Restoration,
 }
 
 impl<T: Into<DispatchError>> From<T> for TrapReason {
@@ -152,10 +149,6 @@ pub enum RuntimeCosts {
 	ValueTransferred,
 	/// Weight of calling `seal_minimum_balance`.
 	MinimumBalance,
-	/// Weight of calling `seal_tombstone_deposit`.
-	TombstoneDeposit,
-	/// Weight of calling `seal_rent_allowance`.
-	RentAllowance,
 	/// Weight of calling `seal_block_number`.
 	BlockNumber,
 	/// Weight of calling `seal_now`.
@@ -172,10 +165,6 @@ pub enum RuntimeCosts {
 	Terminate,
 	/// Weight that is added to `seal_terminate` for every byte of the terminated contract.
 	TerminateSurchargeCodeSize(u32),
-	/// Weight of calling `seal_restore_to` per number of supplied delta entries.
-	RestoreTo(u32),
-	/// Weight that is added to `seal_restore_to` for the involved code sizes.
-	RestoreToSurchargeCodeSize{caller_code: u32, tombstone_code: u32},
 	/// Weight of calling `seal_random`. It includes the weight for copying the subject.
 	Random,
 	/// Weight of calling `seal_deposit_event` with the given number of topics and event size.
@@ -183,8 +172,6 @@ pub enum RuntimeCosts {
 	/// Weight of calling `seal_debug_message`.
 	#[cfg(feature = "unstable-interface")]
 	DebugMessage,
-	/// Weight of calling `seal_set_rent_allowance`.
-	SetRentAllowance,
 	/// Weight of calling `seal_set_storage` for the given storage item size.
 	SetStorage(u32),
 	/// Weight of calling `seal_clear_storage`.
@@ -197,12 +184,18 @@ pub enum RuntimeCosts {
 	Transfer,
 	/// Weight of calling `seal_call` for the given input size.
 	CallBase(u32),
+	/// Weight of calling `seal_delegate_call` for the given input size.
+	DelegateCallBase(u32),
 	/// Weight that is added to `seal_call` for every byte of the called contract.
 	CallSurchargeCodeSize(u32),
 	/// Weight of the transfer performed during a call.
 	CallSurchargeTransfer,
-	/// Weight of output received through `seal_call` for the given size.
+	/// Weight of output received through `seal_call` or `seal_delegate_call` for the given size.
 	CallCopyOut(u32),
+	/// Weight of calling `seal_set_code_hash`.
+	SetCodeHash,
+	/// Weight that is added to `seal_set_code_hash` for every byte of the new and old code.
+	SetCodeHashSurchargeCodeSize(u32),
 	/// Weight of calling `seal_instantiate` for the given input and salt without output weight.
 	/// This includes the transfer as an instantiate without a value will always be below
 	/// the existential deposit and is disregarded as corner case.
@@ -240,8 +233,6 @@ impl RuntimeCosts {
 			Balance => s.balance,
 			ValueTransferred => s.value_transferred,
 			MinimumBalance => s.minimum_balance,
-			TombstoneDeposit => s.tombstone_deposit,
-			RentAllowance => s.rent_allowance,
 			BlockNumber => s.block_number,
 			Now => s.now,
 			WeightToFee => s.weight_to_fee,
@@ -251,19 +242,12 @@ impl RuntimeCosts {
 				.saturating_add(s.return_per_byte.saturating_mul(len.into())),
 			Terminate => s.terminate,
 			TerminateSurchargeCodeSize(len) => s.terminate_per_code_byte.saturating_mul(len.into()),
-			RestoreTo(delta) => s.restore_to
-				.saturating_add(s.restore_to_per_delta.saturating_mul(delta.into())),
-			RestoreToSurchargeCodeSize{caller_code, tombstone_code} =>
-				s.restore_to_per_caller_code_byte.saturating_mul(caller_code.into()).saturating_add(
-					s.restore_to_per_tombstone_code_byte.saturating_mul(tombstone_code.into())
-				),
 			Random => s.random,
 			DepositEvent{num_topic, len} => s.deposit_event
 				.saturating_add(s.deposit_event_per_topic.saturating_mul(num_topic.into()))
 				.saturating_add(s.deposit_event_per_byte.saturating_mul(len.into())),
 			#[cfg(feature = "unstable-interface")]
 			DebugMessage => s.debug_message,
-			SetRentAllowance => s.set_rent_allowance,
 			SetStorage(len) => s.set_storage
 				.saturating_add(s.set_storage_per_byte.saturating_mul(len.into())),
 			ClearStorage => s.clear_storage,
@@ -272,9 +256,14 @@ impl RuntimeCosts {
 			Transfer => s.transfer,
 			CallBase(len) => s.call
 				.saturating_add(s.call_per_input_byte.saturating_mul(len.into())),
+			DelegateCallBase(len) => s.delegate_call
+				.saturating_add(s.call_per_input_byte.saturating_mul(len.into())),
 			CallSurchargeCodeSize(len) => s.call_per_code_byte.saturating_mul(len.into()),
 			CallSurchargeTransfer => s.call_transfer_surcharge,
 			CallCopyOut(len) => s.call_per_output_byte.saturating_mul(len.into()),
+			SetCodeHash => s.set_code_hash,
+			SetCodeHashSurchargeCodeSize(len) =>
+				s.set_code_hash_per_code_byte.saturating_mul(len.into()),
 			InstantiateBase{input_data_len, salt_len} => s.instantiate
 				.saturating_add(s.instantiate_per_input_byte.saturating_mul(input_data_len.into()))
 				.saturating_add(s.instantiate_per_salt_byte.saturating_mul(salt_len.into())),
@@ -356,10 +345,32 @@ bitflags! {
 		/// Without this flag any reentrancy into the current contract that originates from
 		/// the callee (or any of its callees) is denied. This includes the first callee:
 		/// You cannot call into yourself with this flag set.
+		///
+		/// # Note
+		///
+		/// This flag is not supported by `seal_delegate_call` and leads to a trap when set.
 		const ALLOW_REENTRY = 0b0000_1000;
 	}
 }
 
+/// The kind of call that should be performed by [`Runtime::call`].
+enum CallType {
+	/// Execute another instantiated contract.
+	Call {
+		callee_ptr: u32,
+		callee_len: u32,
+		value_ptr: u32,
+		value_len: u32,
+		gas: u64,
+	},
+	/// Execute deployed code in the context (storage, address, caller, value) of the
+	/// calling contract.
+	DelegateCall {
+		code_hash_ptr: u32,
+		code_hash_len: u32,
+	},
+}
+
 /// This is only appropriate when writing out data of constant size that does not depend on user
 /// input. In this case the costs for this copy was already charged as part of the token at
 /// the beginning of the API entry point.
@@ -422,12 +433,6 @@ where
 						data: Bytes(Vec::new()),
 					})
 				},
-				TrapReason::Restoration => {
-					Ok(ExecReturnValue {
-						flags: ReturnFlags::empty(),
-						data: Bytes(Vec::new()),
-					})
-				},
 				TrapReason::SupervisorError(error) => Err(error)?,
 			}
 		}
@@ -643,15 +648,13 @@ where
 		let not_funded = Error::<E::T>::NewContractNotFunded.into();
 		let no_code = Error::<E::T>::CodeNotFound.into();
 		let not_found = Error::<E::T>::ContractNotFound.into();
-		let is_tombstone = Error::<E::T>::ContractIsTombstone.into();
-		let rent_not_paid = Error::<E::T>::RentNotPaid.into();
 
 		match from {
 			x if x == below_sub => Ok(BelowSubsistenceThreshold),
 			x if x == transfer_failed => Ok(TransferFailed),
 			x if x == not_funded => Ok(NewContractNotFunded),
 			x if x == no_code => Ok(CodeNotFound),
-			x if (x == not_found || x == is_tombstone || x == rent_not_paid) => Ok(NotCallable),
+			x if x == not_found => Ok(NotCallable),
 			err => Err(err)
 		}
 	}
@@ -674,20 +677,21 @@ where
 	fn call(
 		&mut self,
 		flags: CallFlags,
-		callee_ptr: u32,
-		callee_len: u32,
-		gas: u64,
-		value_ptr: u32,
-		value_len: u32,
+		call_type: CallType,
 		input_data_ptr: u32,
 		input_data_len: u32,
 		output_ptr: u32,
 		output_len_ptr: u32
 	) -> Result<ReturnCode, TrapReason> {
-		self.charge_gas(RuntimeCosts::CallBase(input_data_len))?;
-		let callee: <<E as Ext>::T as frame_system::Config>::AccountId =
-			self.read_sandbox_memory_as(callee_ptr, callee_len)?;
-		let value: BalanceOf<<E as Ext>::T> = self.read_sandbox_memory_as(value_ptr, value_len)?;
+		match &call_type {
+			CallType::Call{..} => self.charge_gas(RuntimeCosts::CallBase(input_data_len))?,
+			CallType::DelegateCall{..} => {
+				if flags.contains(CallFlags::ALLOW_REENTRY) {
+					Err(Error::<E::T>::InvalidCallFlags)?;
+				}
+				self.charge_gas(RuntimeCosts::DelegateCallBase(input_data_len))?
+			},
+		};
 		let input_data = if flags.contains(CallFlags::CLONE_INPUT) {
 			self.input_data.as_ref().ok_or_else(|| Error::<E::T>::InputForwarded)?.clone()
 		} else if flags.contains(CallFlags::FORWARD_INPUT) {
@@ -695,16 +699,32 @@ where
 		} else {
 			self.read_sandbox_memory(input_data_ptr, input_data_len)?
 		};
-		if value > 0u32.into() {
-			self.charge_gas(RuntimeCosts::CallSurchargeTransfer)?;
-		}
-		let charged = self.charge_gas(
-			RuntimeCosts::CallSurchargeCodeSize(<E::T as Config>::Schedule::get().limits.code_len)
-		)?;
-		let ext = &mut self.ext;
-		let call_outcome = ext.call(
-			gas, callee, value, input_data, flags.contains(CallFlags::ALLOW_REENTRY),
-		);
+		let (charged, call_outcome) = match call_type {
+			CallType::Call{callee_ptr, callee_len, value_ptr, value_len, gas} => {
+				let callee: <<E as Ext>::T as frame_system::Config>::AccountId =
+					self.read_sandbox_memory_as(callee_ptr, callee_len)?;
+				let value: BalanceOf<<E as Ext>::T> =
+					self.read_sandbox_memory_as(value_ptr, value_len)?;
+				if value > 0u32.into() {
+					self.charge_gas(RuntimeCosts::CallSurchargeTransfer)?;
+				}
+				let charged = self.charge_gas(RuntimeCosts::CallSurchargeCodeSize(
+					<E::T as Config>::Schedule::get().limits.code_len
+				))?;
+				let call_outcome = self.ext.call(
+					gas, callee, value, input_data, flags.contains(CallFlags::ALLOW_REENTRY),
+				);
+				(charged, call_outcome)
+			},
+			CallType::DelegateCall{code_hash_ptr, code_hash_len} => {
+				let code_hash: CodeHash<<E as Ext>::T> =
+					self.read_sandbox_memory_as(code_hash_ptr, code_hash_len)?;
+				let charged = self.charge_gas(RuntimeCosts::CallSurchargeCodeSize(
+					<E::T as Config>::Schedule::get().limits.code_len
+				))?;
+				(charged, self.ext.delegate_call(code_hash, input_data))
+			},
+		};
 		let code_len = match &call_outcome {
 			Ok((_, len)) => len,
 			Err((_, len)) => len,
@@ -876,11 +896,7 @@ define_env!(Env, <E: Ext>,
 	) -> ReturnCode => {
 		ctx.call(
 			CallFlags::ALLOW_REENTRY,
-			callee_ptr,
-			callee_len,
-			gas,
-			value_ptr,
-			value_len,
+			CallType::Call{callee_ptr, callee_len, value_ptr, value_len, gas},
 			input_data_ptr,
 			input_data_len,
 			output_ptr,
@@ -935,11 +951,58 @@ define_env!(Env, <E: Ext>,
 	) -> ReturnCode => {
 		ctx.call(
 			CallFlags::from_bits(flags).ok_or_else(|| "used rerved bit in CallFlags")?,
-			callee_ptr,
-			callee_len,
-			gas,
-			value_ptr,
-			value_len,
+			CallType::Call{callee_ptr, callee_len, value_ptr, value_len, gas},
+			input_data_ptr,
+			input_data_len,
+			output_ptr,
+			output_len_ptr,
+		)
+	},
+
+	// Execute code in the context (storage, address, caller, value) of the current contract.
+	//
+	// Reentrancy protection is always disabled since the callee is allowed
+	// to modify the callers storage. This makes going through a reentrancy attack
+	// unnecessary for the callee when it wants to exploit the caller.
+	//
+	// The callees output buffer is copied to `output_ptr` and its length to `output_len_ptr`.
+	// The copy of the output buffer can be skipped by supplying the sentinel value
+	// of `u32::max_value()` to `output_ptr`.
+	//
+	// # Parameters
+	//
+	// - flags: See [`CallFlags`] for a documentation of the supported flags.
+	//   `ALLOW_REENTRY` is not supported and leads to a trap.
+	// - code_hash_ptr: a pointer to the hash of the code that should be executed.
+	//   Should be decodable as a `T::Hash`. Traps otherwise.
+	// - code_hash_len: length of the code hash buffer.
+	// - input_data_ptr: a pointer to a buffer to be used as input data to the callee.
+	// - input_data_len: length of the input data buffer.
+	// - output_ptr: a pointer where the output buffer is copied to.
+	// - output_len_ptr: in-out pointer to where the length of the buffer is read from
+	//   and the actual length is written to.
+	//
+	// # Errors
+	//
+	// An error means that the call wasn't successful and no output buffer is returned unless
+	// stated otherwise.
+	//
+	// `ReturnCode::CalleeReverted`: Output buffer is returned.
+	// `ReturnCode::CalleeTrapped`
+	// `ReturnCode::CodeNotFound`
+	[seal0] seal_delegate_call(
+		ctx,
+		flags: u32,
+		code_hash_ptr: u32,
+		code_hash_len: u32,
+		input_data_ptr: u32,
+		input_data_len: u32,
+		output_ptr: u32,
+		output_len_ptr: u32
+	) -> ReturnCode => {
+		ctx.call(
+			CallFlags::from_bits(flags).ok_or_else(|| "used rerved bit in CallFlags")?,
+			CallType::DelegateCall{code_hash_ptr, code_hash_len},
 			input_data_ptr,
 			input_data_len,
 			output_ptr,
@@ -947,6 +1010,46 @@ define_env!(Env, <E: Ext>,
 		)
 	},
 
+	// Replace the contract code at the specified address with new code.
+	//
+	// The new code is used starting with the next call into the contract. The currently
+	// executing code is not affected: It keeps running until it returns. This is also
+	// true when this function is called from code executed via `seal_delegate_call`:
+	// It is the code hash of the delegating contract which is replaced.
+	//
+	// The contract does not get a chance to migrate its storage. This needs to be done
+	// by the new code. A `ContractCodeUpdated` event is emitted.
+	//
+	// # Parameters
+	//
+	// - code_hash_ptr: a pointer to the buffer that contains the new code hash.
+	//   Should be decodable as a `T::Hash`. Traps otherwise.
+	// - code_hash_len: length of the code hash buffer.
+	//
+	// # Errors
+	//
+	// `ReturnCode::CodeNotFound`
+	[seal0] seal_set_code_hash(ctx, code_hash_ptr: u32, code_hash_len: u32) -> ReturnCode => {
+		ctx.charge_gas(RuntimeCosts::SetCodeHash)?;
+		let code_hash: CodeHash<<E as Ext>::T> =
+			ctx.read_sandbox_memory_as(code_hash_ptr, code_hash_len)?;
+		// The old and the new code are both loaded.
+		let charged = ctx.charge_gas(
+			RuntimeCosts::SetCodeHashSurchargeCodeSize(
+				<E::T as Config>::Schedule::get().limits.code_len.saturating_mul(2)
+			)
+		)?;
+		let (result, code_len) = match ctx.ext.set_code_hash(code_hash) {
+			Ok(len) => (Ok(()), len),
+			Err((err, len)) => (Err(err), len),
+		};
+		ctx.adjust_gas(charged, RuntimeCosts::SetCodeHashSurchargeCodeSize(code_len));
+		match result {
+			Ok(()) => Ok(ReturnCode::Success),
+			Err(err) => Ok(Runtime::<E>::err_into_return_code(err)?),
+		}
+	},
+
 	// Instantiate a contract with the specified code hash.
 	//
 	// This function creates an account and executes the constructor defined in the code specified
@@ -1312,109 +1415,34 @@ define_env!(Env, <E: Ext>,
 	//
 	// # Note
 	//
-	// The tombstone deposit is on top of the existential deposit. So in order for
-	// a contract to leave a tombstone the balance of the contract must not go
-	// below the sum of existential deposit and the tombstone deposit. The sum
-	// is commonly referred as subsistence threshold in code.
+	// Contracts are no longer evicted into tombstones. This stub only exists for backwards
+	// compatibility and always returns zero.
 	[seal0] seal_tombstone_deposit(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		ctx.charge_gas(RuntimeCosts::TombstoneDeposit)?;
+		ctx.charge_gas(RuntimeCosts::Balance)?;
 		Ok(ctx.write_sandbox_output(
-			out_ptr, out_len_ptr, &ctx.ext.tombstone_deposit().encode(), false, already_charged
+			out_ptr, out_len_ptr, &<BalanceOf<E::T>>::zero().encode(), false, already_charged
 		)?)
 	},
 
-	// Try to restore the given destination contract sacrificing the caller.
-	//
-	// This function will compute a tombstone hash from the caller's storage and the given code hash
-	// and if the hash matches the hash found in the tombstone at the specified address - kill
-	// the caller contract and restore the destination contract and set the specified `rent_allowance`.
-	// All caller's funds are transferred to the destination.
-	//
-	// The tombstone hash is derived as `hash(code_hash, storage_root_hash)`. In order to match
-	// this hash to its own hash the restorer must make its storage equal to the one of the
-	// evicted destination contract. In order to allow for additional storage items in the
-	// restoring contract a delta can be specified to this function. All keys specified as
-	// delta are disregarded when calculating the storage root hash.
-	//
-	// On success, the destination contract is restored. This function is diverging and
-	// stops execution even on success.
+	// Was used to restore the given destination contract sacrificing the caller.
 	//
-	// - `dest_ptr`, `dest_len` - the pointer and the length of a buffer that encodes `T::AccountId`
-	//    with the address of the to be restored contract.
-	// - `code_hash_ptr`, `code_hash_len` - the pointer and the length of a buffer that encodes
-	//    a code hash of the to be restored contract.
-	// - `rent_allowance_ptr`, `rent_allowance_len` - the pointer and the length of a buffer that
-	//    encodes the rent allowance that must be set in the case of successful restoration.
-	// - `delta_ptr` is the pointer to the start of a buffer that has `delta_count` storage keys
-	//    laid out sequentially.
-	//
-	// # Traps
+	// # Note
 	//
-	// - There is no tombstone at the destination address.
-	// - Tombstone hashes do not match.
-	// - The calling contract is already present on the call stack.
-	// - The supplied code_hash does not exist on-chain.
+	// Contracts are no longer evicted into tombstones and therefore cannot be restored.
+	// This stub only exists for backwards compatibility and does nothing.
 	[seal0] seal_restore_to(
 		ctx,
-		dest_ptr: u32,
-		dest_len: u32,
-		code_hash_ptr: u32,
-		code_hash_len: u32,
-		rent_allowance_ptr: u32,
-		rent_allowance_len: u32,
-		delta_ptr: u32,
-		delta_count: u32
+		_dest_ptr: u32,
+		_dest_len: u32,
+		_code_hash_ptr: u32,
+		_code_hash_len: u32,
+		_rent_allowance_ptr: u32,
+		_rent_allowance_len: u32,
+		_delta_ptr: u32,
+		_delta_count: u32
 	) => {
-		ctx.charge_gas(RuntimeCosts::RestoreTo(delta_count))?;
-		let dest: <<E as Ext>::T as frame_system::Config>::AccountId =
-			ctx.read_sandbox_memory_as(dest_ptr, dest_len)?;
-		let code_hash: CodeHash<<E as Ext>::T> =
-			ctx.read_sandbox_memory_as(code_hash_ptr, code_hash_len)?;
-		let rent_allowance: BalanceOf<<E as Ext>::T> =
-			ctx.read_sandbox_memory_as(rent_allowance_ptr, rent_allowance_len)?;
-		let delta = {
-			const KEY_SIZE: usize = 32;
-
-			// We can eagerly allocate because we charged for the complete delta count already
-			// We still need to make sure that the allocation isn't larger than the memory
-			// allocator can handle.
-			ensure!(
-				delta_count
-					.saturating_mul(KEY_SIZE as u32) <= ctx.ext.schedule().limits.max_memory_size(),
-				Error::<E::T>::OutOfBounds,
-			);
-			let mut delta = vec![[0; KEY_SIZE]; delta_count as usize];
-			let mut key_ptr = delta_ptr;
-
-			for i in 0..delta_count {
-				// Read the delta into the provided buffer
-				// This cannot panic because of the loop condition
-				ctx.read_sandbox_memory_into_buf(key_ptr, &mut delta[i as usize])?;
-
-				// Offset key_ptr to the next element.
-				key_ptr = key_ptr.checked_add(KEY_SIZE as u32).ok_or(Error::<E::T>::OutOfBounds)?;
-			}
-
-			delta
-		};
-
-		let max_len = <E::T as Config>::Schedule::get().limits.code_len;
-		let charged = ctx.charge_gas(RuntimeCosts::RestoreToSurchargeCodeSize {
-			caller_code: max_len,
-			tombstone_code: max_len,
-		})?;
-		let (result, caller_code, tombstone_code) = match ctx.ext.restore_to(
-			dest, code_hash, rent_allowance, delta
-		) {
-			Ok((code, tomb)) => (Ok(()), code, tomb),
-			Err((err, code, tomb)) => (Err(err), code, tomb),
-		};
-		ctx.adjust_gas(charged, RuntimeCosts::RestoreToSurchargeCodeSize {
-			caller_code,
-			tombstone_code,
-		});
-		result?;
-		Err(TrapReason::Restoration)
+		ctx.charge_gas(RuntimeCosts::Balance)?;
+		Ok(())
 	},
 
 	// Deposit a contract event with the data buffer and optional list of topics. There is a limit
@@ -1482,17 +1510,14 @@ define_env!(Env, <E: Ext>,
 		Ok(())
 	},
 
-	// Set rent allowance of the contract
+	// Was used to set the rent allowance of the contract.
 	//
-	// - value_ptr: a pointer to the buffer with value, how much to allow for rent
-	//   Should be decodable as a `T::Balance`. Traps otherwise.
-	// - value_len: length of the value buffer.
-	[seal0] seal_set_rent_allowance(ctx, value_ptr: u32, value_len: u32) => {
-		ctx.charge_gas(RuntimeCosts::SetRentAllowance)?;
-		let value: BalanceOf<<E as Ext>::T> =
-			ctx.read_sandbox_memory_as(value_ptr, value_len)?;
-		ctx.ext.set_rent_allowance(value);
-
+	// # Note
+	//
+	// Contracts no longer pay rent. This stub only exists for backwards compatibility
+	// and does nothing.
+	[seal0] seal_set_rent_allowance(ctx, _value_ptr: u32, _value_len: u32) => {
+		ctx.charge_gas(RuntimeCosts::Balance)?;
 		Ok(())
 	},
 
@@ -1504,9 +1529,14 @@ define_env!(Env, <E: Ext>,
 	// space at `out_ptr` is less than the size of the value a trap is triggered.
 	//
 	// The data is encoded as T::Balance.
+	//
+	// # Note
+	//
+	// Contracts no longer pay rent. This stub only exists for backwards compatibility
+	// and always returns the maximum balance.
 	[seal0] seal_rent_allowance(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		ctx.charge_gas(RuntimeCosts::RentAllowance)?;
-		let rent_allowance = ctx.ext.rent_allowance().encode();
+		ctx.charge_gas(RuntimeCosts::Balance)?;
+		let rent_allowance = <BalanceOf<E::T>>::max_value().encode();
 		Ok(ctx.write_sandbox_output(
 			out_ptr, out_len_ptr, &rent_allowance, false, already_charged
 		)?)
@@ -1686,53 +1716,4 @@ define_env!(Env, <E: Ext>,
 		}
 		Ok(ReturnCode::LoggingDisabled)
 	},
-
-	// Stores the rent params into the supplied buffer.
-	//
-	// The value is stored to linear memory at the address pointed to by `out_ptr`.
-	// `out_len_ptr` must point to a u32 value that describes the available space at
-	// `out_ptr`. This call overwrites it with the size of the value. If the available
-	// space at `out_ptr` is less than the size of the value a trap is triggered.
-	//
-	// The data is encoded as [`crate::exec::RentParams`].
-	//
-	// # Note
-	//
-	// The returned information was collected and cached when the current contract call
-	// started execution. Any change to those values that happens due to actions of the
-	// current call or contracts that are called by this contract are not considered.
-	//
-	// # Unstable
-	//
-	// This function is unstable and subject to change (or removal) in the future. Do not
-	// deploy a contract using it to a production chain.
-	[__unstable__] seal_rent_params(ctx, out_ptr: u32, out_len_ptr: u32) => {
-		Ok(ctx.write_sandbox_output(
-			out_ptr, out_len_ptr, &ctx.ext.rent_params().encode(), false, already_charged
-		)?)
-	},
-
-	// Stores the rent status into the supplied buffer.
-	//
-	// The value is stored to linear memory at the address pointed to by `out_ptr`.
-	// `out_len_ptr` must point to a u32 value that describes the available space at
-	// `out_ptr`. This call overwrites it with the size of the value. If the available
-	// space at `out_ptr` is less than the size of the value a trap is triggered.
-	//
-	// The data is encoded as [`crate::rent::RentStatus`].
-	//
-	// # Parameters
-	//
-	// - `at_refcount`: The refcount assumed for the returned `custom_refcount_*` fields
-	//
-	// # Unstable
-	//
-	// This function is unstable and subject to change (or removal) in the future. Do not
-	// deploy a contract using it to a production chain.
-	[__unstable__] seal_rent_status(ctx, at_refcount: u32, out_ptr: u32, out_len_ptr: u32) => {
-		let rent_status = ctx.ext.rent_status(at_refcount).encode();
-		Ok(ctx.write_sandbox_output(
-			out_ptr, out_len_ptr, &rent_status, false, already_charged
-		)?)
-	},
 );
@@ -51,15 +51,14 @@ pub trait WeightInfo {
 	fn instantiate_with_code(c: u32, s: u32, ) -> Weight;
 	fn instantiate(c: u32, s: u32, ) -> Weight;
 	fn call(c: u32, ) -> Weight;
-	fn claim_surcharge(c: u32, ) -> Weight;
+	fn upload_code(c: u32, ) -> Weight;
+	fn remove_code() -> Weight;
 	fn seal_caller(r: u32, ) -> Weight;
 	fn seal_address(r: u32, ) -> Weight;
 	fn seal_gas_left(r: u32, ) -> Weight;
 	fn seal_balance(r: u32, ) -> Weight;
 	fn seal_value_transferred(r: u32, ) -> Weight;
 	fn seal_minimum_balance(r: u32, ) -> Weight;
-	fn seal_tombstone_deposit(r: u32, ) -> Weight;
-	fn seal_rent_allowance(r: u32, ) -> Weight;
 	fn seal_block_number(r: u32, ) -> Weight;
 	fn seal_now(r: u32, ) -> Weight;
 	fn seal_rent_params(r: u32, ) -> Weight;
@@ -72,12 +71,11 @@ pub trait WeightInfo {
 	fn seal_return_per_kb(n: u32, ) -> Weight;
 	fn seal_terminate(r: u32, ) -> Weight;
 	fn seal_terminate_per_code_kb(c: u32, ) -> Weight;
-	fn seal_restore_to(r: u32, ) -> Weight;
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight;
+	fn seal_set_code_hash(r: u32, ) -> Weight;
+	fn seal_set_code_hash_per_code_kb(c: u32, ) -> Weight;
 	fn seal_random(r: u32, ) -> Weight;
 	fn seal_deposit_event(r: u32, ) -> Weight;
 	fn seal_deposit_event_per_topic_and_kb(t: u32, n: u32, ) -> Weight;
-	fn seal_set_rent_allowance(r: u32, ) -> Weight;
 	fn seal_debug_message(r: u32, ) -> Weight;
 	fn seal_set_storage(r: u32, ) -> Weight;
 	fn seal_set_storage_per_kb(n: u32, ) -> Weight;
@@ -87,6 +85,7 @@ pub trait WeightInfo {
 	fn seal_transfer(r: u32, ) -> Weight;
 	fn seal_call(r: u32, ) -> Weight;
 	fn seal_call_per_code_transfer_input_output_kb(c: u32, t: u32, i: u32, o: u32, ) -> Weight;
+	fn seal_delegate_call(r: u32, ) -> Weight;
 	fn seal_instantiate(r: u32, ) -> Weight;
 	fn seal_instantiate_per_code_input_output_salt_kb(c: u32, i: u32, o: u32, s: u32, ) -> Weight;
 	fn seal_hash_sha2_256(r: u32, ) -> Weight;
@@ -204,11 +203,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
-	fn claim_surcharge(c: u32, ) -> Weight {
-		(132_551_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((4_740_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+	fn upload_code(c: u32, ) -> Weight {
+		(53_391_000 as Weight)
+			// Standard Error: 87_000
+			.saturating_add((72_156_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn remove_code() -> Weight {
+		(41_764_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	fn seal_caller(r: u32, ) -> Weight {
@@ -253,20 +257,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
-	fn seal_tombstone_deposit(r: u32, ) -> Weight {
-		(131_523_000 as Weight)
-			// Standard Error: 90_000
-			.saturating_add((237_435_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	fn seal_rent_allowance(r: u32, ) -> Weight {
-		(141_574_000 as Weight)
-			// Standard Error: 86_000
-			.saturating_add((238_102_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
 	fn seal_block_number(r: u32, ) -> Weight {
 		(140_240_000 as Weight)
 			// Standard Error: 101_000
@@ -353,27 +343,21 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
-	fn seal_restore_to(r: u32, ) -> Weight {
-		(159_200_000 as Weight)
-			// Standard Error: 261_000
-			.saturating_add((103_048_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
-	}
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight {
-		(58_389_000 as Weight)
-			// Standard Error: 131_000
-			.saturating_add((7_910_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 131_000
-			.saturating_add((4_036_000 as Weight).saturating_mul(t as Weight))
-			// Standard Error: 1_156_000
-			.saturating_add((3_714_110_000 as Weight).saturating_mul(d as Weight))
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
-			.saturating_add(T::DbWeight::get().reads((100 as Weight).saturating_mul(d as Weight)))
-			.saturating_add(T::DbWeight::get().writes(7 as Weight))
-			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+	fn seal_set_code_hash(r: u32, ) -> Weight {
+		(125_684_000 as Weight)
+			// Standard Error: 1_893_000
+			.saturating_add((4_371_250_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((200 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((200 as Weight).saturating_mul(r as Weight)))
+	}
+	fn seal_set_code_hash_per_code_kb(c: u32, ) -> Weight {
+		(221_372_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((8_102_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
 	fn seal_random(r: u32, ) -> Weight {
 		(138_794_000 as Weight)
@@ -400,13 +384,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(t as Weight)))
 	}
-	fn seal_set_rent_allowance(r: u32, ) -> Weight {
-		(132_720_000 as Weight)
-			// Standard Error: 87_000
-			.saturating_add((164_134_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
 	fn seal_debug_message(r: u32, ) -> Weight {
 		(125_834_000 as Weight)
 			// Standard Error: 142_000
@@ -486,6 +463,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(101 as Weight))
 			.saturating_add(T::DbWeight::get().writes((101 as Weight).saturating_mul(t as Weight)))
 	}
+	fn seal_delegate_call(r: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 5_471_000
+			.saturating_add((9_823_461_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((100 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((100 as Weight).saturating_mul(r as Weight)))
+	}
 	fn seal_instantiate(r: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 32_118_000
@@ -874,11 +860,16 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
-	fn claim_surcharge(c: u32, ) -> Weight {
-		(132_551_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((4_740_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+	fn upload_code(c: u32, ) -> Weight {
+		(53_391_000 as Weight)
+			// Standard Error: 87_000
+			.saturating_add((72_156_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn remove_code() -> Weight {
+		(41_764_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn seal_caller(r: u32, ) -> Weight {
@@ -923,20 +914,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
-	fn seal_tombstone_deposit(r: u32, ) -> Weight {
-		(131_523_000 as Weight)
-			// Standard Error: 90_000
-			.saturating_add((237_435_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-	}
-	fn seal_rent_allowance(r: u32, ) -> Weight {
-		(141_574_000 as Weight)
-			// Standard Error: 86_000
-			.saturating_add((238_102_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-	}
 	fn seal_block_number(r: u32, ) -> Weight {
 		(140_240_000 as Weight)
 			// Standard Error: 101_000
@@ -1023,27 +1000,21 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
-	fn seal_restore_to(r: u32, ) -> Weight {
-		(159_200_000 as Weight)
-			// Standard Error: 261_000
-			.saturating_add((103_048_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(r as Weight)))
-	}
-	fn seal_restore_to_per_code_kb_delta(c: u32, t: u32, d: u32, ) -> Weight {
-		(58_389_000 as Weight)
-			// Standard Error: 131_000
-			.saturating_add((7_910_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 131_000
-			.saturating_add((4_036_000 as Weight).saturating_mul(t as Weight))
-			// Standard Error: 1_156_000
-			.saturating_add((3_714_110_000 as Weight).saturating_mul(d as Weight))
-			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((100 as Weight).saturating_mul(d as Weight)))
-			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(d as Weight)))
+	fn seal_set_code_hash(r: u32, ) -> Weight {
+		(125_684_000 as Weight)
+			// Standard Error: 1_893_000
+			.saturating_add((4_371_250_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((200 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((200 as Weight).saturating_mul(r as Weight)))
+	}
+	fn seal_set_code_hash_per_code_kb(c: u32, ) -> Weight {
+		(221_372_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((8_102_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
 	fn seal_random(r: u32, ) -> Weight {
 		(138_794_000 as Weight)
@@ -1070,13 +1041,6 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(t as Weight)))
 	}
-	fn seal_set_rent_allowance(r: u32, ) -> Weight {
-		(132_720_000 as Weight)
-			// Standard Error: 87_000
-			.saturating_add((164_134_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-	}
 	fn seal_debug_message(r: u32, ) -> Weight {
 		(125_834_000 as Weight)
 			// Standard Error: 142_000
@@ -1156,6 +1120,15 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(101 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((101 as Weight).saturating_mul(t as Weight)))
 	}
+	fn seal_delegate_call(r: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 5_471_000
+			.saturating_add((9_823_461_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((100 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((100 as Weight).saturating_mul(r as Weight)))
+	}
 	fn seal_instantiate(r: u32, ) -> Weight {
 		(0 as Weight)
 			// Standard Error: 32_118_000
@@ -20,10 +20,11 @@
 use sp_std::{result::Result, convert::TryFrom};
 use sp_runtime::{RuntimeDebug, traits::{Zero, Bounded, CheckedMul, CheckedDiv}};
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use crate::types::Delegations;
 
 /// A value denoting the strength of conviction of a vote.
-#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo)]
 pub enum Conviction {
 	/// 0.1x votes, unlocked.
 	None,
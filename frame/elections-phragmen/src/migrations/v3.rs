@@ -18,20 +18,21 @@
 //! Migrations to version [`3.0.0`], as denoted by the changelog.
 
 use codec::{Encode, Decode, FullCodec};
+use scale_info::TypeInfo;
 use sp_std::prelude::*;
 use frame_support::{
 	RuntimeDebug, weights::Weight, Twox64Concat,
 	traits::{GetPalletVersion, PalletVersion},
 };
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
 struct SeatHolder<AccountId, Balance> {
 	who: AccountId,
 	stake: Balance,
 	deposit: Balance,
 }
 
-#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq)]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo)]
 struct Voter<AccountId, Balance> {
 	votes: Vec<AccountId>,
 	stake: Balance,
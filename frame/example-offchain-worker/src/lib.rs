@@ -58,6 +58,7 @@ use sp_runtime::{
 	transaction_validity::{InvalidTransaction, ValidTransaction, TransactionValidity},
 };
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_std::vec::Vec;
 use lite_json::json::JsonValue;
 
@@ -323,7 +324,7 @@ pub mod pallet {
 
 /// Payload used by this example crate to hold price
 /// data required to submit a transaction.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct PricePayload<Public, BlockNumber> {
 	block_number: BlockNumber,
 	price: u32,
@@ -25,6 +25,7 @@
 use sp_runtime::RuntimeDebug;
 
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_std::vec::Vec;
 
 #[cfg(test)]
@@ -94,7 +95,7 @@ pub mod pallet {
 }
 
 /// Request to enlist participant.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct EnlistedParticipant {
 	pub account: Vec<u8>,
 	pub signature: Vec<u8>,
@@ -40,6 +40,7 @@
 use sp_std::prelude::*;
 
 use codec::{self as codec, Decode, Encode};
+use scale_info::TypeInfo;
 use frame_support::traits::{Get, KeyOwnerProofSystem};
 use sp_finality_grandpa::{EquivocationProof, RoundNumber, SetId};
 use sp_runtime::{
@@ -191,7 +192,7 @@ where
 }
 
 /// A round number and set id which point on the time of an offence.
-#[derive(Copy, Clone, PartialOrd, Ord, Eq, PartialEq, Encode, Decode)]
+#[derive(Copy, Clone, PartialOrd, Ord, Eq, PartialEq, Encode, Decode, TypeInfo)]
 pub struct GrandpaTimeSlot {
 	// The order of these matters for `derive(Ord)`.
 	/// Grandpa Set ID.
@@ -67,6 +67,7 @@ use frame_support::{
 	},
 };
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 pub use weights::WeightInfo;
 pub use pallet::*;
 
@@ -76,7 +77,7 @@ type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Con
 // We use this to uniquely match someone's incoming call with the calls configured for the lottery.
 type CallIndex = (u8, u8);
 
-#[derive(Encode, Decode, Default, Eq, PartialEq, RuntimeDebug)]
+#[derive(Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct LotteryConfig<BlockNumber, Balance> {
 	/// Price per entry.
 	price: Balance,
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! # Membership Module
+//! # Membership Pallet
 //!
 //! Allows control of membership of a set of `AccountId`s, useful for managing membership of of a
 //! collective. A prime member may be set
@@ -24,78 +24,103 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
-use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error,
-	traits::{ChangeMembers, InitializeMembers, EnsureOrigin, Contains, SortedMembers, Get},
-};
+use frame_support::traits::{ChangeMembers, InitializeMembers, EnsureOrigin, Contains, SortedMembers, Get};
 use frame_system::ensure_signed;
 
+pub mod migrations;
 pub mod weights;
+
+pub use pallet::*;
 pub use weights::WeightInfo;
 
-pub trait Config<I = DefaultInstance>: frame_system::Config {
-	/// The overarching event type.
-	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Config>::Event>;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
-	/// Required origin for adding a member (though can always be Root).
-	type AddOrigin: EnsureOrigin<Self::Origin>;
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 
-	/// Required origin for removing a member (though can always be Root).
-	type RemoveOrigin: EnsureOrigin<Self::Origin>;
+		/// Required origin for adding a member (though can always be Root).
+		type AddOrigin: EnsureOrigin<Self::Origin>;
 
-	/// Required origin for adding and removing a member in a single action.
-	type SwapOrigin: EnsureOrigin<Self::Origin>;
+		/// Required origin for removing a member (though can always be Root).
+		type RemoveOrigin: EnsureOrigin<Self::Origin>;
 
-	/// Required origin for resetting membership.
-	type ResetOrigin: EnsureOrigin<Self::Origin>;
+		/// Required origin for adding and removing a member in a single action.
+		type SwapOrigin: EnsureOrigin<Self::Origin>;
 
-	/// Required origin for setting or resetting the prime member.
-	type PrimeOrigin: EnsureOrigin<Self::Origin>;
+		/// Required origin for resetting membership.
+		type ResetOrigin: EnsureOrigin<Self::Origin>;
 
-	/// The receiver of the signal for when the membership has been initialized. This happens pre-
-	/// genesis and will usually be the same as `MembershipChanged`. If you need to do something
-	/// different on initialization, then you can change this accordingly.
-	type MembershipInitialized: InitializeMembers<Self::AccountId>;
+		/// Required origin for setting or resetting the prime member.
+		type PrimeOrigin: EnsureOrigin<Self::Origin>;
 
-	/// The receiver of the signal for when the membership has changed.
-	type MembershipChanged: ChangeMembers<Self::AccountId>;
+		/// The receiver of the signal for when the membership has been initialized. This happens pre-
+		/// genesis and will usually be the same as `MembershipChanged`. If you need to do something
+		/// different on initialization, then you can change this accordingly.
+		type MembershipInitialized: InitializeMembers<Self::AccountId>;
 
-	/// The maximum number of members that this membership can have.
-	///
-	/// This is used for benchmarking. Re-run the benchmarks if this changes.
-	///
-	/// This is not enforced in the code; the membership size can exceed this limit.
-	type MaxMembers: Get<u32>;
+		/// The receiver of the signal for when the membership has changed.
+		type MembershipChanged: ChangeMembers<Self::AccountId>;
 
-	/// Weight information for extrinsics in this pallet.
-	type WeightInfo: WeightInfo;
-}
+		/// The maximum number of members that this membership can have.
+		///
+		/// This is used for benchmarking. Re-run the benchmarks if this changes.
+		///
+		/// This is not enforced in the code; the membership size can exceed this limit.
+		type MaxMembers: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The current membership, stored as an ordered Vec.
+	#[pallet::storage]
+	#[pallet::getter(fn members)]
+	pub type Members<T: Config<I>, I: 'static = ()> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
 
-decl_storage! {
-	trait Store for Module<T: Config<I>, I: Instance=DefaultInstance> as Membership {
-		/// The current membership, stored as an ordered Vec.
-		Members get(fn members): Vec<T::AccountId>;
+	/// The current prime member, if one exists.
+	#[pallet::storage]
+	#[pallet::getter(fn prime)]
+	pub type Prime<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AccountId, OptionQuery>;
 
-		/// The current prime member, if one exists.
-		Prime get(fn prime): Option<T::AccountId>;
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		pub members: Vec<T::AccountId>,
+		pub phantom: PhantomData<I>,
 	}
-	add_extra_genesis {
-		config(members): Vec<T::AccountId>;
-		config(phantom): sp_std::marker::PhantomData<I>;
-		build(|config: &Self| {
-			let mut members = config.members.clone();
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+		fn default() -> Self {
+			Self {
+				members: Vec::new(),
+				phantom: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+		fn build(&self) {
+			let mut members = self.members.clone();
 			members.sort();
 			T::MembershipInitialized::initialize_members(&members);
 			<Members<T, I>>::put(members);
-		})
+		}
 	}
-}
 
-decl_event!(
-	pub enum Event<T, I=DefaultInstance> where
-		<T as frame_system::Config>::AccountId,
-		<T as Config<I>>::Event,
-	{
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// The given member was added; see the transaction for who.
 		MemberAdded,
 		/// The given member was removed; see the transaction for who.
@@ -107,34 +132,28 @@ decl_event!(
 		/// One of the members' keys changed.
 		KeyChanged,
 		/// Phantom member, never used.
-		Dummy(sp_std::marker::PhantomData<(AccountId, Event)>),
+		Dummy(PhantomData<(T::AccountId, <T as Config<I>>::Event)>),
 	}
-);
 
-decl_error! {
-	/// Error for the nicks module.
-	pub enum Error for Module<T: Config<I>, I: Instance> {
+	/// Error for the membership pallet.
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
 		/// Already a member.
 		AlreadyMember,
 		/// Not a member.
 		NotMember,
 	}
-}
-
-decl_module! {
-	pub struct Module<T: Config<I>, I: Instance=DefaultInstance>
-		for enum Call
-		where origin: T::Origin
-	{
-		type Error = Error<T, I>;
 
-		fn deposit_event() = default;
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
 
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Add a member `who` to the set.
 		///
 		/// May only be called from `T::AddOrigin`.
-		#[weight = 50_000_000]
-		pub fn add_member(origin, who: T::AccountId) {
+		#[pallet::weight(50_000_000)]
+		pub fn add_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
 			T::AddOrigin::ensure_origin(origin)?;
 
 			let mut members = <Members<T, I>>::get();
@@ -146,14 +165,15 @@ decl_module! {
 
 			T::MembershipChanged::change_members_sorted(&[who], &[], &members[..]);
 
-			Self::deposit_event(RawEvent::MemberAdded);
+			Self::deposit_event(Event::MemberAdded);
+			Ok(())
 		}
 
 		/// Remove a member `who` from the set.
 		///
 		/// May only be called from `T::RemoveOrigin`.
-		#[weight = 50_000_000]
-		pub fn remove_member(origin, who: T::AccountId) {
+		#[pallet::weight(50_000_000)]
+		pub fn remove_member(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
 			T::RemoveOrigin::ensure_origin(origin)?;
 
 			let mut members = <Members<T, I>>::get();
@@ -166,7 +186,8 @@ decl_module! {
 			T::MembershipChanged::change_members_sorted(&[], &[who], &members[..]);
 			Self::rejig_prime(&members);
 
-			Self::deposit_event(RawEvent::MemberRemoved);
+			Self::deposit_event(Event::MemberRemoved);
+			Ok(())
 		}
 
 		/// Swap out one member `remove` for another `add`.
@@ -174,8 +195,8 @@ decl_module! {
 		/// May only be called from `T::SwapOrigin`.
 		///
 		/// Prime membership is *not* passed from `remove` to `add`, if extant.
-		#[weight = 50_000_000]
-		pub fn swap_member(origin, remove: T::AccountId, add: T::AccountId) {
+		#[pallet::weight(50_000_000)]
+		pub fn swap_member(origin: OriginFor<T>, remove: T::AccountId, add: T::AccountId) -> DispatchResult {
 			T::SwapOrigin::ensure_origin(origin)?;
 
 			if remove == add { return Ok(()) }
@@ -196,15 +217,16 @@ decl_module! {
 			);
 			Self::rejig_prime(&members);
 
-			Self::deposit_event(RawEvent::MembersSwapped);
+			Self::deposit_event(Event::MembersSwapped);
+			Ok(())
 		}
 
 		/// Change the membership to a new set, disregarding the existing membership. Be nice and
 		/// pass `members` pre-sorted.
 		///
 		/// May only be called from `T::ResetOrigin`.
-		#[weight = 50_000_000]
-		pub fn reset_members(origin, members: Vec<T::AccountId>) {
+		#[pallet::weight(50_000_000)]
+		pub fn reset_members(origin: OriginFor<T>, members: Vec<T::AccountId>) -> DispatchResult {
 			T::ResetOrigin::ensure_origin(origin)?;
 
 			let mut members = members;
@@ -216,7 +238,8 @@ decl_module! {
 				*m = members;
 			});
 
-			Self::deposit_event(RawEvent::MembersReset);
+			Self::deposit_event(Event::MembersReset);
+			Ok(())
 		}
 
 		/// Swap out the sending member for some other key `new`.
@@ -224,8 +247,8 @@ decl_module! {
 		/// May only be called from `Signed` origin of a current member.
 		///
 		/// Prime membership is passed from the origin account to `new`, if extant.
-		#[weight = 50_000_000]
-		pub fn change_key(origin, new: T::AccountId) {
+		#[pallet::weight(50_000_000)]
+		pub fn change_key(origin: OriginFor<T>, new: T::AccountId) -> DispatchResult {
 			let remove = ensure_signed(origin)?;
 
 			if remove != new {
@@ -250,33 +273,36 @@ decl_module! {
 				}
 			}
 
-			Self::deposit_event(RawEvent::KeyChanged);
+			Self::deposit_event(Event::KeyChanged);
+			Ok(())
 		}
 
 		/// Set the prime member. Must be a current member.
 		///
 		/// May only be called from `T::PrimeOrigin`.
-		#[weight = 50_000_000]
-		pub fn set_prime(origin, who: T::AccountId) {
+		#[pallet::weight(50_000_000)]
+		pub fn set_prime(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
 			T::PrimeOrigin::ensure_origin(origin)?;
 			Self::members().binary_search(&who).ok().ok_or(Error::<T, I>::NotMember)?;
 			Prime::<T, I>::put(&who);
 			T::MembershipChanged::set_prime(Some(who));
+			Ok(())
 		}
 
 		/// Remove the prime member if it exists.
 		///
 		/// May only be called from `T::PrimeOrigin`.
-		#[weight = 50_000_000]
-		pub fn clear_prime(origin) {
+		#[pallet::weight(50_000_000)]
+		pub fn clear_prime(origin: OriginFor<T>) -> DispatchResult {
 			T::PrimeOrigin::ensure_origin(origin)?;
 			Prime::<T, I>::kill();
 			T::MembershipChanged::set_prime(None);
+			Ok(())
 		}
 	}
 }
 
-impl<T: Config<I>, I: Instance> Module<T, I> {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	fn rejig_prime(members: &[T::AccountId]) {
 		if let Some(prime) = Prime::<T, I>::get() {
 			match members.binary_search(&prime) {
@@ -298,13 +324,13 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 	}
 }
 
-impl<T: Config<I>, I: Instance> Contains<T::AccountId> for Module<T, I> {
+impl<T: Config<I>, I: 'static> Contains<T::AccountId> for Pallet<T, I> {
 	fn contains(t: &T::AccountId) -> bool {
 		Self::members().binary_search(t).is_ok()
 	}
 }
 
-impl<T: Config<I>, I: Instance> SortedMembers<T::AccountId> for Module<T, I> {
+impl<T: Config<I>, I: 'static> SortedMembers<T::AccountId> for Pallet<T, I> {
 	fn sorted_members() -> Vec<T::AccountId> {
 		Self::members()
 	}
@@ -316,14 +342,14 @@ impl<T: Config<I>, I: Instance> SortedMembers<T::AccountId> for Module<T, I> {
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmark {
-	use super::{*, Module as Membership};
+	use super::{*, Pallet as Membership};
 	use frame_system::RawOrigin;
 	use frame_support::{traits::EnsureOrigin, assert_ok};
-	use frame_benchmarking::{benchmarks_instance, whitelist, account, impl_benchmark_test_suite};
+	use frame_benchmarking::{benchmarks_instance_pallet, whitelist, account, impl_benchmark_test_suite};
 
 	const SEED: u32 = 0;
 
-	fn set_members<T: Config<I>, I: Instance>(members: Vec<T::AccountId>, prime: Option<usize>) {
+	fn set_members<T: Config<I>, I: 'static>(members: Vec<T::AccountId>, prime: Option<usize>) {
 		let reset_origin = T::ResetOrigin::successful_origin();
 		let prime_origin = T::PrimeOrigin::successful_origin();
 
@@ -335,7 +361,7 @@ mod benchmark {
 		}
 	}
 
-	benchmarks_instance! {
+	benchmarks_instance_pallet! {
 		add_member {
 			let m in 1 .. T::MaxMembers::get();
 
@@ -462,7 +488,7 @@ mod tests {
 	use super::*;
 	use crate as pallet_membership;
 
-	use frame_support::{assert_ok, assert_noop, parameter_types, ord_parameter_types};
+	use frame_support::{assert_ok, assert_noop, parameter_types, ord_parameter_types, traits::GenesisBuild};
 	use sp_core::H256;
 	use sp_runtime::{traits::{BlakeTwo256, IdentityLookup, BadOrigin}, testing::Header};
 	use frame_system::EnsureSignedBy;
@@ -706,4 +732,22 @@ mod tests {
 			assert_eq!(PRIME.with(|m| *m.borrow()), Membership::prime());
 		});
 	}
+
+	#[test]
+	fn migrate_to_pallet_prefix_works() {
+		use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+		new_test_ext().execute_with(|| {
+			put_storage_value(b"Membership", b"Prime", &[], 20u64);
+			put_storage_value(b"Instance1Membership", b"Members", &[], vec![40u64, 50]);
+
+			crate::migrations::migrate_to_pallet_prefix::<Test, ()>("Membership");
+			// the default instance is already named `Membership` in the runtime.
+			assert_eq!(Membership::prime(), Some(20));
+
+			crate::migrations::migrate_to_pallet_prefix::<Test, ()>("Instance1Membership");
+			assert_eq!(Membership::members(), vec![40, 50]);
+			assert_eq!(get_storage_value::<Vec<u64>>(b"Instance1Membership", b"Members", &[]), None);
+		});
+	}
 }
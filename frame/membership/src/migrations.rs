@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Migrations for the membership pallet.
+
+use frame_support::{
+	storage::{migration::move_pallet, unhashed},
+	traits::{Get, PalletInfoAccess},
+	weights::Weight,
+	StorageHasher, Twox128,
+};
+
+/// Move the whole storage of a membership instance from `old_pallet_name` to the prefix of the
+/// pallet, that is the name given to it in `construct_runtime`.
+///
+/// Before the pallet attribute macro, the storage of an instance was kept under the instance
+/// prefix followed by `Membership`, e.g. `Instance1Membership`, and under `Membership` for the
+/// default instance. Nothing is moved if the prefix is already the right one or if the storage
+/// was already moved, so this can be called on every runtime upgrade.
+pub fn migrate_to_pallet_prefix<T: crate::Config<I>, I: 'static>(old_pallet_name: &str) -> Weight {
+	let new_pallet_name = <crate::Pallet<T, I> as PalletInfoAccess>::name();
+	if new_pallet_name == old_pallet_name {
+		return 0;
+	}
+
+	if !unhashed::contains_prefixed_key(&Twox128::hash(old_pallet_name.as_bytes())) {
+		return T::DbWeight::get().reads(1);
+	}
+
+	move_pallet(old_pallet_name.as_bytes(), new_pallet_name.as_bytes());
+
+	<T as frame_system::Config>::BlockWeights::get().max_block
+}
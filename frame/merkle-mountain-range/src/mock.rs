@@ -19,6 +19,7 @@ use crate::*;
 use crate as pallet_mmr;
 
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use frame_support::parameter_types;
 use pallet_mmr_primitives::{LeafDataProvider, Compact};
 use sp_core::H256;
@@ -84,7 +85,7 @@ impl Config for Test {
 	type WeightInfo = ();
 }
 
-#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, Debug)]
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, Debug, TypeInfo)]
 pub struct LeafData {
 	pub a: u64,
 	pub b: Vec<u8>,
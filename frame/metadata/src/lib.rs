@@ -17,9 +17,9 @@
 
 //! Decodable variant of the RuntimeMetadata.
 //!
-//! This really doesn't belong here, but is necessary for the moment. In the future
-//! it should be removed entirely to an external module for shimming on to the
-//! codec-encoded metadata.
+//! The current version of the metadata (V14) describes every type used by the runtime through a
+//! portable type registry (see [`scale_info`]), so clients no longer need to know the layout of
+//! types by their names. Version 13 is kept in [`v13`] to decode the metadata of older runtimes.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -28,317 +28,22 @@ use serde::Serialize;
 #[cfg(feature = "std")]
 use codec::{Decode, Input, Error};
 use codec::{Encode, Output};
-use sp_std::vec::Vec;
 use sp_core::RuntimeDebug;
 
-#[cfg(feature = "std")]
-type StringBuf = String;
+pub mod v13;
+pub mod v14;
+
+pub use v13::RuntimeMetadataV13;
+pub use v14::*;
 
 /// Current prefix of metadata
 pub const META_RESERVED: u32 = 0x6174656d; // 'meta' warn endianness
 
-/// On `no_std` we do not support `Decode` and thus `StringBuf` is just `&'static str`.
-/// So, if someone tries to decode this stuff on `no_std`, they will get a compilation error.
-#[cfg(not(feature = "std"))]
-type StringBuf = &'static str;
-
-/// A type that decodes to a different type than it encodes.
-/// The user needs to make sure that both types use the same encoding.
-///
-/// For example a `&'static [ &'static str ]` can be decoded to a `Vec<String>`.
-#[derive(Clone)]
-pub enum DecodeDifferent<B, O> where B: 'static, O: 'static {
-	Encode(B),
-	Decoded(O),
-}
-
-impl<B, O> Encode for DecodeDifferent<B, O> where B: Encode + 'static, O: Encode + 'static {
-	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		match self {
-			DecodeDifferent::Encode(b) => b.encode_to(dest),
-			DecodeDifferent::Decoded(o) => o.encode_to(dest),
-		}
-	}
-}
-
-impl<B, O> codec::EncodeLike for DecodeDifferent<B, O> where B: Encode + 'static, O: Encode + 'static {}
-
-#[cfg(feature = "std")]
-impl<B, O> Decode for DecodeDifferent<B, O> where B: 'static, O: Decode + 'static {
-	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
-		<O>::decode(input).map(|val| {
-			DecodeDifferent::Decoded(val)
-		})
-	}
-}
-
-impl<B, O> PartialEq for DecodeDifferent<B, O>
-where
-	B: Encode + Eq + PartialEq + 'static,
-	O: Encode + Eq + PartialEq + 'static,
-{
-	fn eq(&self, other: &Self) -> bool {
-		self.encode() == other.encode()
-	}
-}
-
-impl<B, O> Eq for DecodeDifferent<B, O>
-	where B: Encode + Eq + PartialEq + 'static, O: Encode + Eq + PartialEq + 'static
-{}
-
-impl<B, O> sp_std::fmt::Debug for DecodeDifferent<B, O>
-	where
-		B: sp_std::fmt::Debug + Eq + 'static,
-		O: sp_std::fmt::Debug + Eq + 'static,
-{
-	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
-		match self {
-			DecodeDifferent::Encode(b) => b.fmt(f),
-			DecodeDifferent::Decoded(o) => o.fmt(f),
-		}
-	}
-}
-
-#[cfg(feature = "std")]
-impl<B, O> serde::Serialize for DecodeDifferent<B, O>
-	where
-		B: serde::Serialize + 'static,
-		O: serde::Serialize + 'static,
-{
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
-		match self {
-			DecodeDifferent::Encode(b) => b.serialize(serializer),
-			DecodeDifferent::Decoded(o) => o.serialize(serializer),
-		}
-	}
-}
-
-pub type DecodeDifferentArray<B, O=B> = DecodeDifferent<&'static [B], Vec<O>>;
-
-type DecodeDifferentStr = DecodeDifferent<&'static str, StringBuf>;
-
-/// All the metadata about a function.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct FunctionMetadata {
-	pub name: DecodeDifferentStr,
-	pub arguments: DecodeDifferentArray<FunctionArgumentMetadata>,
-	pub documentation: DecodeDifferentArray<&'static str, StringBuf>,
-}
-
-/// All the metadata about a function argument.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct FunctionArgumentMetadata {
-	pub name: DecodeDifferentStr,
-	pub ty: DecodeDifferentStr,
-}
-
-/// Newtype wrapper for support encoding functions (actual the result of the function).
-#[derive(Clone, Eq)]
-pub struct FnEncode<E>(pub fn() -> E) where E: Encode + 'static;
-
-impl<E: Encode> Encode for FnEncode<E> {
-	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		self.0().encode_to(dest);
-	}
-}
-
-impl<E: Encode> codec::EncodeLike for FnEncode<E> {}
-
-impl<E: Encode + PartialEq> PartialEq for FnEncode<E> {
-	fn eq(&self, other: &Self) -> bool {
-		self.0().eq(&other.0())
-	}
-}
-
-impl<E: Encode + sp_std::fmt::Debug> sp_std::fmt::Debug for FnEncode<E> {
-	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
-		self.0().fmt(f)
-	}
-}
-
-#[cfg(feature = "std")]
-impl<E: Encode + serde::Serialize> serde::Serialize for FnEncode<E> {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
-		self.0().serialize(serializer)
-	}
-}
-
-/// All the metadata about an outer event.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct OuterEventMetadata {
-	pub name: DecodeDifferentStr,
-	pub events: DecodeDifferentArray<
-		(&'static str, FnEncode<&'static [EventMetadata]>),
-		(StringBuf, Vec<EventMetadata>)
-	>,
-}
-
-/// All the metadata about an event.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct EventMetadata {
-	pub name: DecodeDifferentStr,
-	pub arguments: DecodeDifferentArray<&'static str, StringBuf>,
-	pub documentation: DecodeDifferentArray<&'static str, StringBuf>,
-}
-
-/// All the metadata about one storage entry.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct StorageEntryMetadata {
-	pub name: DecodeDifferentStr,
-	pub modifier: StorageEntryModifier,
-	pub ty: StorageEntryType,
-	pub default: ByteGetter,
-	pub documentation: DecodeDifferentArray<&'static str, StringBuf>,
-}
-
-/// All the metadata about one module constant.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct ModuleConstantMetadata {
-	pub name: DecodeDifferentStr,
-	pub ty: DecodeDifferentStr,
-	pub value: ByteGetter,
-	pub documentation: DecodeDifferentArray<&'static str, StringBuf>,
-}
-
-/// All the metadata about a module error.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct ErrorMetadata {
-	pub name: DecodeDifferentStr,
-	pub documentation: DecodeDifferentArray<&'static str, StringBuf>,
-}
-
-/// All the metadata about errors in a module.
-pub trait ModuleErrorMetadata {
-	fn metadata() -> &'static [ErrorMetadata];
-}
-
-impl ModuleErrorMetadata for &'static str {
-	fn metadata() -> &'static [ErrorMetadata] {
-		&[]
-	}
-}
-
-/// A technical trait to store lazy initiated vec value as static dyn pointer.
-pub trait DefaultByte: Send + Sync {
-	fn default_byte(&self) -> Vec<u8>;
-}
-
-/// Wrapper over dyn pointer for accessing a cached once byte value.
-#[derive(Clone)]
-pub struct DefaultByteGetter(pub &'static dyn DefaultByte);
-
-/// Decode different for static lazy initiated byte value.
-pub type ByteGetter = DecodeDifferent<DefaultByteGetter, Vec<u8>>;
-
-impl Encode for DefaultByteGetter {
-	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
-		self.0.default_byte().encode_to(dest)
-	}
-}
-
-impl codec::EncodeLike for DefaultByteGetter {}
-
-impl PartialEq<DefaultByteGetter> for DefaultByteGetter {
-	fn eq(&self, other: &DefaultByteGetter) -> bool {
-		let left = self.0.default_byte();
-		let right = other.0.default_byte();
-		left.eq(&right)
-	}
-}
-
-impl Eq for DefaultByteGetter { }
-
-#[cfg(feature = "std")]
-impl serde::Serialize for DefaultByteGetter {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
-		self.0.default_byte().serialize(serializer)
-	}
-}
-
-impl sp_std::fmt::Debug for DefaultByteGetter {
-	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
-		self.0.default_byte().fmt(f)
-	}
-}
-
-/// Hasher used by storage maps
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub enum StorageHasher {
-	Blake2_128,
-	Blake2_256,
-	Blake2_128Concat,
-	Twox128,
-	Twox256,
-	Twox64Concat,
-	Identity,
-}
-
-/// A storage entry type.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub enum StorageEntryType {
-	Plain(DecodeDifferentStr),
-	Map {
-		hasher: StorageHasher,
-		key: DecodeDifferentStr,
-		value: DecodeDifferentStr,
-		// is_linked flag previously, unused now to keep backwards compat
-		unused: bool,
-	},
-	DoubleMap {
-		hasher: StorageHasher,
-		key1: DecodeDifferentStr,
-		key2: DecodeDifferentStr,
-		value: DecodeDifferentStr,
-		key2_hasher: StorageHasher,
-	},
-	NMap {
-		keys: DecodeDifferentArray<&'static str, StringBuf>,
-		hashers: DecodeDifferentArray<StorageHasher>,
-		value: DecodeDifferentStr,
-	},
-}
-
-/// A storage entry modifier.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub enum StorageEntryModifier {
-	Optional,
-	Default,
-}
-
-/// All metadata of the storage.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct StorageMetadata {
-	/// The common prefix used by all storage entries.
-	pub prefix: DecodeDifferent<&'static str, StringBuf>,
-	pub entries: DecodeDifferent<&'static [StorageEntryMetadata], Vec<StorageEntryMetadata>>,
-}
-
 /// Metadata prefixed by a u32 for reserved usage
 #[derive(Eq, Encode, PartialEq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Decode, Serialize))]
 pub struct RuntimeMetadataPrefixed(pub u32, pub RuntimeMetadata);
 
-/// Metadata of the extrinsic used by the runtime.
-#[derive(Eq, Encode, PartialEq, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct ExtrinsicMetadata {
-	/// Extrinsic version.
-	pub version: u8,
-	/// The signed extensions in the order they appear in the extrinsic.
-	pub signed_extensions: Vec<DecodeDifferentStr>,
-}
-
 /// The metadata of a runtime.
 /// The version ID encoded/decoded through
 /// the enum nature of `RuntimeMetadata`.
@@ -371,8 +76,33 @@ pub enum RuntimeMetadata {
 	V11(RuntimeMetadataDeprecated),
 	/// Version 12 for runtime metadata. No longer used.
 	V12(RuntimeMetadataDeprecated),
-	/// Version 13 for runtime metadata.
+	/// Version 13 for runtime metadata. No longer produced, but still decodable.
 	V13(RuntimeMetadataV13),
+	/// Version 14 for runtime metadata.
+	V14(RuntimeMetadataV14),
+}
+
+impl RuntimeMetadata {
+	/// Get the version number of the metadata.
+	pub fn version(&self) -> u32 {
+		match self {
+			RuntimeMetadata::V0(_) => 0,
+			RuntimeMetadata::V1(_) => 1,
+			RuntimeMetadata::V2(_) => 2,
+			RuntimeMetadata::V3(_) => 3,
+			RuntimeMetadata::V4(_) => 4,
+			RuntimeMetadata::V5(_) => 5,
+			RuntimeMetadata::V6(_) => 6,
+			RuntimeMetadata::V7(_) => 7,
+			RuntimeMetadata::V8(_) => 8,
+			RuntimeMetadata::V9(_) => 9,
+			RuntimeMetadata::V10(_) => 10,
+			RuntimeMetadata::V11(_) => 11,
+			RuntimeMetadata::V12(_) => 12,
+			RuntimeMetadata::V13(_) => 13,
+			RuntimeMetadata::V14(_) => 14,
+		}
+	}
 }
 
 /// Enum that should fail.
@@ -393,36 +123,8 @@ impl Decode for RuntimeMetadataDeprecated {
 	}
 }
 
-/// The metadata of a runtime.
-#[derive(Eq, Encode, PartialEq, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct RuntimeMetadataV13 {
-	/// Metadata of all the modules.
-	pub modules: DecodeDifferentArray<ModuleMetadata>,
-	/// Metadata of the extrinsic.
-	pub extrinsic: ExtrinsicMetadata,
-}
-
 /// The latest version of the metadata.
-pub type RuntimeMetadataLastVersion = RuntimeMetadataV13;
-
-/// All metadata about an runtime module.
-#[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
-#[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct ModuleMetadata {
-	pub name: DecodeDifferentStr,
-	pub storage: Option<DecodeDifferent<FnEncode<StorageMetadata>, StorageMetadata>>,
-	pub calls: ODFnA<FunctionMetadata>,
-	pub event: ODFnA<EventMetadata>,
-	pub constants: DFnA<ModuleConstantMetadata>,
-	pub errors: DFnA<ErrorMetadata>,
-	/// Define the index of the module, this index will be used for the encoding of module event,
-	/// call and origin variants.
-	pub index: u8,
-}
-
-type ODFnA<T> = Option<DFnA<T>>;
-type DFnA<T> = DecodeDifferent<FnEncode<&'static [T]>, Vec<T>>;
+pub type RuntimeMetadataLastVersion = RuntimeMetadataV14;
 
 impl Into<sp_core::OpaqueMetadata> for RuntimeMetadataPrefixed {
 	fn into(self) -> sp_core::OpaqueMetadata {
@@ -432,6 +134,6 @@ impl Into<sp_core::OpaqueMetadata> for RuntimeMetadataPrefixed {
 
 impl Into<RuntimeMetadataPrefixed> for RuntimeMetadataLastVersion {
 	fn into(self) -> RuntimeMetadataPrefixed {
-		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V13(self))
+		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V14(self))
 	}
 }
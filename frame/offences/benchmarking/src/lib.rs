@@ -42,7 +42,7 @@ use pallet_offences::{Config as OffencesConfig, Pallet as Offences};
 use pallet_session::historical::{Config as HistoricalConfig, IdentificationTuple};
 use pallet_session::{Config as SessionConfig, SessionManager};
 use pallet_staking::{
-	Module as Staking, Config as StakingConfig, RewardDestination, ValidatorPrefs, Exposure,
+	Pallet as Staking, Config as StakingConfig, RewardDestination, ValidatorPrefs, Exposure,
 	IndividualExposure, Event as StakingEvent,
 };
 
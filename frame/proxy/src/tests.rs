@@ -25,6 +25,7 @@ use frame_support::{
 	assert_ok, assert_noop, parameter_types, RuntimeDebug, dispatch::DispatchError, traits::Filter,
 };
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_core::H256;
 use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
 use crate as proxy;
@@ -104,8 +105,7 @@ parameter_types! {
 }
 #[derive(
 	Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug,
-	max_encoded_len::MaxEncodedLen,
-)]
+	max_encoded_len::MaxEncodedLen, TypeInfo)]
 pub enum ProxyType {
 	Any,
 	JustTransfer,
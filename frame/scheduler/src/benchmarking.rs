@@ -33,7 +33,7 @@ const BLOCK_NUMBER: u32 = 2;
 // Add `n` named items to the schedule
 fn fill_schedule<T: Config> (when: T::BlockNumber, n: u32) -> Result<(), &'static str> {
 	// Essentially a no-op call.
-	let call = frame_system::Call::set_storage(vec![]);
+	let call: <T as Config>::Call = frame_system::Call::set_storage(vec![]).into();
 	for i in 0..n {
 		// Named schedule is strictly heavier than anonymous
 		Scheduler::<T>::do_schedule_named(
@@ -44,7 +44,7 @@ fn fill_schedule<T: Config> (when: T::BlockNumber, n: u32) -> Result<(), &'stati
 			// HARD_DEADLINE priority means it gets executed no matter what
 			0,
 			frame_system::RawOrigin::Root.into(),
-			call.clone().into(),
+			MaybeHashed::Value(call.clone()),
 		)?;
 	}
 	ensure!(Agenda::<T>::get(when).len() == n as usize, "didn't fill schedule");
@@ -58,7 +58,7 @@ benchmarks! {
 		let periodic = Some((T::BlockNumber::one(), 100));
 		let priority = 0;
 		// Essentially a no-op call.
-		let call = Box::new(frame_system::Call::set_storage(vec![]).into());
+		let call = Box::new(MaybeHashed::Value(frame_system::Call::set_storage(vec![]).into()));
 
 		fill_schedule::<T>(when, s)?;
 	}: _(RawOrigin::Root, when, periodic, priority, call)
@@ -95,7 +95,7 @@ benchmarks! {
 		let periodic = Some((T::BlockNumber::one(), 100));
 		let priority = 0;
 		// Essentially a no-op call.
-		let call = Box::new(frame_system::Call::set_storage(vec![]).into());
+		let call = Box::new(MaybeHashed::Value(frame_system::Call::set_storage(vec![]).into()));
 
 		fill_schedule::<T>(when, s)?;
 	}: _(RawOrigin::Root, id, when, periodic, priority, call)
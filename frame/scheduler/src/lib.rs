@@ -36,6 +36,11 @@
 //! If a call is scheduled using proxy or whatever mecanism which adds filter,
 //! then those filter will not be used when dispatching the schedule call.
 //!
+//! A call may be scheduled either by value or by the hash of its encoding. Calls given by hash
+//! are looked up from the configured `PreimageProvider` when they are due; if the preimage is
+//! not available at that point, `CallLookupFailed` is emitted and the task is postponed by
+//! `NoPreimagePostponement` blocks (or dropped, if that is `None`).
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -56,10 +61,15 @@ pub mod weights;
 
 use sp_std::{prelude::*, marker::PhantomData, borrow::Borrow};
 use codec::{Encode, Decode, Codec};
+use scale_info::TypeInfo;
 use sp_runtime::{RuntimeDebug, traits::{Zero, One, BadOrigin, Saturating}};
 use frame_support::{
 	dispatch::{Dispatchable, DispatchError, DispatchResult, Parameter},
-	traits::{Get, schedule::{self, DispatchTime}, OriginTrait, EnsureOrigin, IsType},
+	storage::{migration, StoragePrefixedMap},
+	traits::{
+		Get, schedule::{self, DispatchTime, MaybeHashed, LookupError}, OriginTrait, EnsureOrigin,
+		IsType, PreimageProvider,
+	},
 	weights::{GetDispatchInfo, Weight},
 };
 use frame_system::{self as system, ensure_signed};
@@ -71,8 +81,11 @@ pub type PeriodicIndex = u32;
 /// The location of a scheduled task that can be used to remove it.
 pub type TaskAddress<BlockNumber> = (BlockNumber, u32);
 
+/// A call, either given by value or by the hash of its encoding.
+pub type CallOrHashOf<T> = MaybeHashed<<T as Config>::Call, <T as frame_system::Config>::Hash>;
+
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
-#[derive(Clone, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
 struct ScheduledV1<Call, BlockNumber> {
 	maybe_id: Option<Vec<u8>>,
 	priority: schedule::Priority,
@@ -82,7 +95,7 @@ struct ScheduledV1<Call, BlockNumber> {
 
 /// Information regarding an item to be executed in the future.
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
-#[derive(Clone, RuntimeDebug, Encode, Decode)]
+#[derive(Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	/// The unique identity for this task, if there is one.
 	maybe_id: Option<Vec<u8>>,
@@ -97,17 +110,37 @@ pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	_phantom: PhantomData<AccountId>,
 }
 
+/// Information regarding an item to be executed in the future, where the call may be given by hash.
+pub type ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId> =
+	ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId>;
+
 /// The current version of Scheduled struct.
 pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> =
-	ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId>;
+	ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId>;
+
+type ScheduledV2Of<T> = ScheduledV2<
+	<T as Config>::Call,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::PalletsOrigin,
+	<T as frame_system::Config>::AccountId,
+>;
+
+/// The current version of Scheduled struct for a given runtime.
+pub type ScheduledOf<T> = Scheduled<
+	CallOrHashOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::PalletsOrigin,
+	<T as frame_system::Config>::AccountId,
+>;
 
 // A value placed in storage that represents the current version of the Scheduler storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
 // storage migration logic.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	V1,
 	V2,
+	V3,
 }
 
 impl Default for Releases {
@@ -138,7 +171,8 @@ pub mod pallet {
 			+ IsType<<Self as system::Config>::Origin>;
 
 		/// The caller origin, overarching type of all pallets origins.
-		type PalletsOrigin: From<system::RawOrigin<Self::AccountId>> + Codec + Clone + Eq;
+		type PalletsOrigin: From<system::RawOrigin<Self::AccountId>> + Codec + Clone + Eq
+			+ TypeInfo;
 
 		/// The aggregated call type.
 		type Call: Parameter
@@ -157,6 +191,13 @@ pub mod pallet {
 		/// Not strictly enforced, but used for weight estimation.
 		type MaxScheduledPerBlock: Get<u32>;
 
+		/// The preimage provider with which we look up call hashes to get the call.
+		type PreimageProvider: PreimageProvider<Self::Hash>;
+
+		/// If `Some` then the number of blocks to postpone execution for when the item is delayed
+		/// because its preimage is not available.
+		type NoPreimagePostponement: Get<Option<Self::BlockNumber>>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -167,7 +208,7 @@ pub mod pallet {
 		_,
 		Twox64Concat,
 		T::BlockNumber,
-		Vec<Option<Scheduled<<T as Config>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>>>,
+		Vec<Option<ScheduledOf<T>>>,
 		ValueQuery,
 	>;
 
@@ -193,6 +234,9 @@ pub mod pallet {
 		Canceled(T::BlockNumber, u32),
 		/// Dispatched some task. \[task, id, result\]
 		Dispatched(TaskAddress<T::BlockNumber>, Option<Vec<u8>>, DispatchResult),
+		/// The call for the provided hash was not found so the task has been aborted or
+		/// postponed. \[task, id, error\]
+		CallLookupFailed(TaskAddress<T::BlockNumber>, Option<Vec<u8>>, LookupError),
 	}
 
 	#[pallet::error]
@@ -220,26 +264,38 @@ pub mod pallet {
  	#[pallet::genesis_build]
  	impl<T: Config> GenesisBuild<T> for GenesisConfig {
  		fn build(&self) {
-			StorageVersion::<T>::put(Releases::V2);
+			StorageVersion::<T>::put(Releases::V3);
  		}
  	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Migrate the agenda from any previous storage version to the current one.
+		fn on_runtime_upgrade() -> Weight {
+			let mut migrated = Self::migrate_v1_to_t2();
+			migrated |= Self::migrate_v2_to_v3();
+			if migrated {
+				T::BlockWeights::get().max_block
+			} else {
+				T::DbWeight::get().reads(2)
+			}
+		}
+
 		/// Execute the scheduled calls
 		///
 		/// # <weight>
 		/// - S = Number of already scheduled calls
 		/// - N = Named scheduled calls
 		/// - P = Periodic Calls
+		/// - H = Calls scheduled by hash
 		/// - Base Weight: 9.243 + 23.45 * S µs
 		/// - DB Weight:
-		///     - Read: Agenda + Lookup * N + Agenda(Future) * P
-		///     - Write: Agenda + Lookup * N  + Agenda(future) * P
+		///     - Read: Agenda + Lookup * N + Agenda(Future) * P + Preimage * H
+		///     - Write: Agenda + Lookup * N  + Agenda(future) * P + Preimage request * H
 		/// # </weight>
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			let limit = T::MaximumWeight::get();
-			let mut queued = Agenda::<T>::take(now)
+			let queued = Agenda::<T>::take(now)
 				.into_iter()
 				.enumerate()
 				.filter_map(|(index, s)| s.map(|inner| (index as u32, inner)))
@@ -251,15 +307,25 @@ pub mod pallet {
 					expected from the runtime configuration. An update might be needed."
 				);
 			}
-			queued.sort_by_key(|(_, s)| s.priority);
+			// Weight spent on looking up calls given by hash, whether or not they get dispatched.
+			let mut lookup_weight: Weight = 0;
+			let mut queued = queued
+				.into_iter()
+				.filter_map(|(index, s)| {
+					let (maybe_resolved, weight) = Self::resolve_call(now, index, s);
+					lookup_weight = lookup_weight.saturating_add(weight);
+					maybe_resolved
+				})
+				.collect::<Vec<_>>();
+			queued.sort_by_key(|(_, _, s)| s.priority);
 			let base_weight: Weight = T::DbWeight::get().reads_writes(1, 2); // Agenda + Agenda(next)
 			let mut total_weight: Weight = 0;
 			queued
 				.into_iter()
 				.enumerate()
-				.scan(base_weight, |cumulative_weight, (order, (index, s))| {
+				.scan(base_weight, |cumulative_weight, (order, (index, call, s))| {
 					*cumulative_weight =
-						cumulative_weight.saturating_add(s.call.get_dispatch_info().weight);
+						cumulative_weight.saturating_add(call.get_dispatch_info().weight);
 
 					let origin =
 						<<T as Config>::Origin as From<T::PalletsOrigin>>::from(s.origin.clone())
@@ -282,9 +348,9 @@ pub mod pallet {
 							cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 					}
 
-					Some((order, index, *cumulative_weight, s))
+					Some((order, index, *cumulative_weight, call, s))
 				})
-				.filter_map(|(order, index, cumulative_weight, mut s)| {
+				.filter_map(|(order, index, cumulative_weight, call, mut s)| {
 					// We allow a scheduled call if any is true:
 					// - It's priority is `HARD_DEADLINE`
 					// - It does not push the weight past the limit.
@@ -293,7 +359,7 @@ pub mod pallet {
 						|| cumulative_weight <= limit
 						|| order == 0
 					{
-						let r = s.call.clone().dispatch(s.origin.clone().into());
+						let r = call.dispatch(s.origin.clone().into());
 						let maybe_id = s.maybe_id.clone();
 						if let &Some((period, count)) = &s.maybe_periodic {
 							if count > 1 {
@@ -329,7 +395,7 @@ pub mod pallet {
 					Agenda::<T>::append(next, unused);
 				});
 
-			total_weight
+			total_weight.saturating_add(lookup_weight)
 		}
 	}
 
@@ -351,7 +417,7 @@ pub mod pallet {
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -400,7 +466,7 @@ pub mod pallet {
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -444,7 +510,7 @@ pub mod pallet {
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -470,7 +536,7 @@ pub mod pallet {
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -494,10 +560,14 @@ impl<T: Config> Pallet<T> {
 		if StorageVersion::<T>::get() == Releases::V1 {
 			StorageVersion::<T>::put(Releases::V2);
 
-			Agenda::<T>::translate::<
-				Vec<Option<ScheduledV1<<T as Config>::Call, T::BlockNumber>>>, _
-			>(|_, agenda| Some(
-				agenda
+			// The agenda is typed with the current format, the V2 format is written untyped.
+			let module = Agenda::<T>::module_prefix();
+			let item = Agenda::<T>::storage_prefix();
+			let agendas = migration::storage_iter::<
+				Vec<Option<ScheduledV1<<T as Config>::Call, T::BlockNumber>>>
+			>(module, item).collect::<Vec<_>>();
+			for (key, agenda) in agendas {
+				let agenda: Vec<Option<ScheduledV2Of<T>>> = agenda
 					.into_iter()
 					.map(|schedule| schedule.map(|schedule| ScheduledV2 {
 						maybe_id: schedule.maybe_id,
@@ -507,6 +577,33 @@ impl<T: Config> Pallet<T> {
 						origin: system::RawOrigin::Root.into(),
 						_phantom: Default::default(),
 					}))
+					.collect();
+				migration::put_storage_value(module, item, &key, agenda);
+			}
+
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Migrate storage format from V2 to V3, wrapping all stored calls as values.
+	/// Return true if migration is performed.
+	pub fn migrate_v2_to_v3() -> bool {
+		if StorageVersion::<T>::get() == Releases::V2 {
+			StorageVersion::<T>::put(Releases::V3);
+
+			Agenda::<T>::translate::<Vec<Option<ScheduledV2Of<T>>>, _>(|_, agenda| Some(
+				agenda
+					.into_iter()
+					.map(|schedule| schedule.map(|schedule| ScheduledV3 {
+						maybe_id: schedule.maybe_id,
+						priority: schedule.priority,
+						call: schedule.call.into(),
+						maybe_periodic: schedule.maybe_periodic,
+						origin: schedule.origin,
+						_phantom: Default::default(),
+					}))
 					.collect::<Vec<_>>()
 			));
 
@@ -519,7 +616,7 @@ impl<T: Config> Pallet<T> {
 	/// Helper to migrate scheduler when the pallet origin type has changed.
 	pub fn migrate_origin<OldOrigin: Into<T::PalletsOrigin> + codec::Decode>() {
 		Agenda::<T>::translate::<
-			Vec<Option<Scheduled<<T as Config>::Call, T::BlockNumber, OldOrigin, T::AccountId>>>, _
+			Vec<Option<Scheduled<CallOrHashOf<T>, T::BlockNumber, OldOrigin, T::AccountId>>>, _
 		>(|_, agenda| Some(
 			agenda
 				.into_iter()
@@ -535,6 +632,61 @@ impl<T: Config> Pallet<T> {
 		));
 	}
 
+	/// Resolve the call of a task that is due, looking up its preimage if it was scheduled by hash.
+	///
+	/// Returns `None` in place of the task if its call could not be looked up, in which case the
+	/// task has been postponed or dropped. The returned weight only accounts for the lookup.
+	fn resolve_call(
+		now: T::BlockNumber,
+		index: u32,
+		mut s: ScheduledOf<T>,
+	) -> (Option<(u32, <T as Config>::Call, ScheduledOf<T>)>, Weight) {
+		let hash = match s.call {
+			MaybeHashed::Value(ref call) => {
+				let call = call.clone();
+				return (Some((index, call, s)), 0)
+			},
+			MaybeHashed::Hash(hash) => hash,
+		};
+
+		// Read the preimage.
+		let mut weight = T::DbWeight::get().reads(1);
+		match s.call.lookup::<T::PreimageProvider>() {
+			Ok(call) => {
+				// We no longer need the preimage to be kept around for us.
+				T::PreimageProvider::unrequest_preimage(&hash);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+				s.call = MaybeHashed::Value(call.clone());
+				(Some((index, call, s)), weight)
+			},
+			Err(error) => {
+				Self::deposit_event(Event::CallLookupFailed((now, index), s.maybe_id.clone(), error));
+				match (error, T::NoPreimagePostponement::get()) {
+					// The preimage may still be noted, so try again later.
+					(LookupError::Unknown, Some(delay)) => {
+						let until = now.saturating_add(delay.max(One::one()));
+						if let Some(ref id) = s.maybe_id {
+							let next_index = Agenda::<T>::decode_len(until).unwrap_or(0);
+							Lookup::<T>::insert(id, (until, next_index as u32));
+							weight = weight.saturating_add(T::DbWeight::get().writes(1));
+						}
+						Agenda::<T>::append(until, Some(s));
+						weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+					},
+					_ => {
+						T::PreimageProvider::unrequest_preimage(&hash);
+						if let Some(ref id) = s.maybe_id {
+							Lookup::<T>::remove(id);
+							weight = weight.saturating_add(T::DbWeight::get().writes(1));
+						}
+						weight = weight.saturating_add(T::DbWeight::get().writes(1));
+					},
+				}
+				(None, weight)
+			},
+		}
+	}
+
 	fn resolve_time(when: DispatchTime<T::BlockNumber>) -> Result<T::BlockNumber, DispatchError> {
 		let now = frame_system::Pallet::<T>::block_number();
 
@@ -557,9 +709,10 @@ impl<T: Config> Pallet<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: CallOrHashOf<T>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		let when = Self::resolve_time(when)?;
+		call.ensure_requested::<T::PreimageProvider>();
 
 		// sanitize maybe_periodic
 		let maybe_periodic = maybe_periodic
@@ -606,6 +759,7 @@ impl<T: Config> Pallet<T> {
 			)
 		})?;
 		if let Some(s) = scheduled {
+			s.call.ensure_unrequested::<T::PreimageProvider>();
 			if let Some(id) = s.maybe_id {
 				Lookup::<T>::remove(id);
 			}
@@ -646,7 +800,7 @@ impl<T: Config> Pallet<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: CallOrHashOf<T>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		// ensure id it is unique
 		if Lookup::<T>::contains_key(&id) {
@@ -654,6 +808,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let when = Self::resolve_time(when)?;
+		call.ensure_requested::<T::PreimageProvider>();
 
 		// sanitize maybe_periodic
 		let maybe_periodic = maybe_periodic
@@ -696,7 +851,9 @@ impl<T: Config> Pallet<T> {
 								return Err(BadOrigin.into());
 							}
 						}
-						*s = None;
+						if let Some(s) = s.take() {
+							s.call.ensure_unrequested::<T::PreimageProvider>();
+						}
 					}
 					Ok(())
 				})?;
@@ -743,7 +900,7 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
-impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+impl<T: Config> schedule::v1::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
 	for Pallet<T>
 {
 	type Address = TaskAddress<T::BlockNumber>;
@@ -755,7 +912,7 @@ impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOr
 		origin: T::PalletsOrigin,
 		call: <T as Config>::Call,
 	) -> Result<Self::Address, DispatchError> {
-		Self::do_schedule(when, maybe_periodic, priority, origin, call)
+		Self::do_schedule(when, maybe_periodic, priority, origin, call.into())
 	}
 
 	fn cancel((when, index): Self::Address) -> Result<(), ()> {
@@ -777,7 +934,7 @@ impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOr
 	}
 }
 
-impl<T: Config> schedule::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+impl<T: Config> schedule::v1::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
 	for Pallet<T>
 {
 	type Address = TaskAddress<T::BlockNumber>;
@@ -789,6 +946,76 @@ impl<T: Config> schedule::Named<T::BlockNumber, <T as Config>::Call, T::PalletsO
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
 		call: <T as Config>::Call,
+	) -> Result<Self::Address, ()> {
+		Self::do_schedule_named(id, when, maybe_periodic, priority, origin, call.into()).map_err(|_| ())
+	}
+
+	fn cancel_named(id: Vec<u8>) -> Result<(), ()> {
+		Self::do_cancel_named(None, id).map_err(|_| ())
+	}
+
+	fn reschedule_named(
+		id: Vec<u8>,
+		when: DispatchTime<T::BlockNumber>,
+	) -> Result<Self::Address, DispatchError> {
+		Self::do_reschedule_named(id, when)
+	}
+
+	fn next_dispatch_time(id: Vec<u8>) -> Result<T::BlockNumber, ()> {
+		Lookup::<T>::get(id)
+			.and_then(|(when, index)| Agenda::<T>::get(when).get(index as usize).map(|_| when))
+			.ok_or(())
+	}
+}
+
+impl<T: Config> schedule::v2::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+	for Pallet<T>
+{
+	type Address = TaskAddress<T::BlockNumber>;
+	type Hash = T::Hash;
+
+	fn schedule(
+		when: DispatchTime<T::BlockNumber>,
+		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
+		priority: schedule::Priority,
+		origin: T::PalletsOrigin,
+		call: CallOrHashOf<T>,
+	) -> Result<Self::Address, DispatchError> {
+		Self::do_schedule(when, maybe_periodic, priority, origin, call)
+	}
+
+	fn cancel((when, index): Self::Address) -> Result<(), ()> {
+		Self::do_cancel(None, (when, index)).map_err(|_| ())
+	}
+
+	fn reschedule(
+		address: Self::Address,
+		when: DispatchTime<T::BlockNumber>,
+	) -> Result<Self::Address, DispatchError> {
+		Self::do_reschedule(address, when)
+	}
+
+	fn next_dispatch_time((when, index): Self::Address) -> Result<T::BlockNumber, ()> {
+		Agenda::<T>::get(when)
+			.get(index as usize)
+			.ok_or(())
+			.map(|_| when)
+	}
+}
+
+impl<T: Config> schedule::v2::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+	for Pallet<T>
+{
+	type Address = TaskAddress<T::BlockNumber>;
+	type Hash = T::Hash;
+
+	fn schedule_named(
+		id: Vec<u8>,
+		when: DispatchTime<T::BlockNumber>,
+		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
+		priority: schedule::Priority,
+		origin: T::PalletsOrigin,
+		call: CallOrHashOf<T>,
 	) -> Result<Self::Address, ()> {
 		Self::do_schedule_named(id, when, maybe_periodic, priority, origin, call).map_err(|_| ())
 	}
@@ -818,7 +1045,7 @@ mod tests {
 	use crate as scheduler;
 	use frame_support::{
 		assert_err, assert_noop, assert_ok, ord_parameter_types, parameter_types,
-		traits::{Filter, OnFinalize, OnInitialize},
+		traits::{Filter, OnFinalize, OnInitialize, OnRuntimeUpgrade},
 		weights::constants::RocksDbWeight,
 		Hashable,
 	};
@@ -826,10 +1053,11 @@ mod tests {
 	use sp_core::H256;
 	use sp_runtime::{
 		testing::Header,
-		traits::{BlakeTwo256, IdentityLookup},
+		traits::{BlakeTwo256, Hash, IdentityLookup},
 		Perbill,
 	};
 	use substrate_test_utils::assert_eq_uvec;
+	use std::{cell::RefCell, collections::BTreeMap};
 
 	// Logger module to track execution.
 	#[frame_support::pallet]
@@ -949,6 +1177,60 @@ mod tests {
 	ord_parameter_types! {
 		pub const One: u64 = 1;
 	}
+	parameter_types! {
+		pub const NoPreimagePostponement: Option<u64> = Some(2);
+	}
+
+	thread_local! {
+		static PREIMAGES: RefCell<BTreeMap<H256, Vec<u8>>> = RefCell::new(BTreeMap::new());
+		static REQUESTED: RefCell<BTreeMap<H256, u32>> = RefCell::new(BTreeMap::new());
+	}
+
+	/// Simple preimage store keeping track of how many times each hash is requested.
+	pub struct Preimages;
+	impl Preimages {
+		fn note(call: &Call) -> H256 {
+			let data = call.encode();
+			let hash = BlakeTwo256::hash(&data[..]);
+			PREIMAGES.with(|p| p.borrow_mut().insert(hash, data));
+			hash
+		}
+
+		fn note_bytes(data: Vec<u8>) -> H256 {
+			let hash = BlakeTwo256::hash(&data[..]);
+			PREIMAGES.with(|p| p.borrow_mut().insert(hash, data));
+			hash
+		}
+	}
+	impl PreimageProvider<H256> for Preimages {
+		fn have_preimage(hash: &H256) -> bool {
+			PREIMAGES.with(|p| p.borrow().contains_key(hash))
+		}
+
+		fn get_preimage(hash: &H256) -> Option<Vec<u8>> {
+			PREIMAGES.with(|p| p.borrow().get(hash).cloned())
+		}
+
+		fn preimage_requested(hash: &H256) -> bool {
+			REQUESTED.with(|r| r.borrow().contains_key(hash))
+		}
+
+		fn request_preimage(hash: &H256) {
+			REQUESTED.with(|r| *r.borrow_mut().entry(*hash).or_default() += 1);
+		}
+
+		fn unrequest_preimage(hash: &H256) {
+			REQUESTED.with(|r| {
+				let mut r = r.borrow_mut();
+				if let Some(count) = r.get_mut(hash) {
+					*count -= 1;
+					if *count == 0 {
+						r.remove(hash);
+					}
+				}
+			});
+		}
+	}
 
 	impl Config for Test {
 		type Event = Event;
@@ -959,6 +1241,8 @@ mod tests {
 		type ScheduleOrigin = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
 		type MaxScheduledPerBlock = MaxScheduledPerBlock;
 		type WeightInfo = ();
+		type PreimageProvider = Preimages;
+		type NoPreimagePostponement = NoPreimagePostponement;
 	}
 
 	pub type LoggerCall = logger::Call<Test>;
@@ -987,7 +1271,7 @@ mod tests {
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(
 				&call
 			));
-			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call.into()));
 			run_to_block(3);
 			assert!(logger::log().is_empty());
 			run_to_block(4);
@@ -1006,7 +1290,7 @@ mod tests {
 				&call
 			));
 			// This will schedule the call 3 blocks after the next block... so block 3 + 3 = 6
-			assert_ok!(Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), call.into()));
 			run_to_block(5);
 			assert!(logger::log().is_empty());
 			run_to_block(6);
@@ -1022,7 +1306,7 @@ mod tests {
 			run_to_block(2);
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
-			assert_ok!(Scheduler::do_schedule(DispatchTime::After(0), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(DispatchTime::After(0), None, 127, root(), call.into()));
 			// Will trigger on the next block.
 			run_to_block(3);
 			assert_eq!(logger::log(), vec![(root(), 42u32)]);
@@ -1031,12 +1315,100 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn scheduling_with_preimages_works() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let hash = Preimages::note(&call);
+			assert!(!Preimages::preimage_requested(&hash));
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			assert!(Preimages::preimage_requested(&hash));
+			run_to_block(3);
+			assert!(logger::log().is_empty());
+			run_to_block(4);
+			assert!(!Preimages::preimage_requested(&hash));
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn scheduling_with_missing_preimage_postpones() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let hash = BlakeTwo256::hash_of(&call);
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			run_to_block(4);
+			assert!(logger::log().is_empty());
+			assert!(System::events().iter().any(|r| r.event == Event::scheduler(
+				crate::Event::CallLookupFailed((4, 0), Some(1u32.encode()), LookupError::Unknown)
+			)));
+			// The task is postponed by `NoPreimagePostponement` blocks and still requested.
+			assert!(Agenda::<Test>::get(4).is_empty());
+			assert_eq!(Agenda::<Test>::get(6).len(), 1);
+			assert_eq!(Lookup::<Test>::get(1u32.encode()), Some((6, 0)));
+			assert!(Preimages::preimage_requested(&hash));
+
+			Preimages::note(&call);
+			run_to_block(6);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert!(!Preimages::preimage_requested(&hash));
+			assert!(Lookup::<Test>::get(1u32.encode()).is_none());
+		});
+	}
+
+	#[test]
+	fn scheduling_with_bad_preimage_drops_task() {
+		new_test_ext().execute_with(|| {
+			let hash = Preimages::note_bytes(vec![0xff; 3]);
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			run_to_block(4);
+			assert!(System::events().iter().any(|r| r.event == Event::scheduler(
+				crate::Event::CallLookupFailed((4, 0), Some(1u32.encode()), LookupError::BadFormat)
+			)));
+			// The task is gone for good.
+			assert!(Agenda::<Test>::iter().all(|(_, agenda)| agenda.iter().all(Option::is_none)));
+			assert!(Lookup::<Test>::get(1u32.encode()).is_none());
+			assert!(!Preimages::preimage_requested(&hash));
+			run_to_block(100);
+			assert!(logger::log().is_empty());
+		});
+	}
+
+	#[test]
+	fn cancelling_task_with_preimage_unrequests_it() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log(42, 1000));
+			let hash = Preimages::note(&call);
+			let address = Scheduler::do_schedule(
+				DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			).unwrap();
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), MaybeHashed::Hash(hash)
+			));
+			assert!(Preimages::preimage_requested(&hash));
+			assert_ok!(Scheduler::do_cancel(None, address));
+			assert!(Preimages::preimage_requested(&hash));
+			assert_ok!(Scheduler::do_cancel_named(None, 1u32.encode()));
+			assert!(!Preimages::preimage_requested(&hash));
+			run_to_block(100);
+			assert!(logger::log().is_empty());
+		});
+	}
+
 	#[test]
 	fn periodic_scheduling_works() {
 		new_test_ext().execute_with(|| {
 			// at #4, every 3 blocks, 3 times.
 			assert_ok!(Scheduler::do_schedule(
-				DispatchTime::At(4), Some((3, 3)), 127, root(), Call::Logger(logger::Call::log(42, 1000))
+				DispatchTime::At(4), Some((3, 3)), 127, root(), Call::Logger(logger::Call::log(42, 1000)).into()
 			));
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1066,7 +1438,7 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
-			assert_eq!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call).unwrap(), (4, 0));
+			assert_eq!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call.into()).unwrap(), (4, 0));
 
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1092,7 +1464,7 @@ mod tests {
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
 			assert_eq!(Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), None, 127, root(), call
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), call.into()
 			).unwrap(), (4, 0));
 
 			run_to_block(3);
@@ -1119,7 +1491,7 @@ mod tests {
 			let call = Call::Logger(LoggerCall::log(42, 1000));
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::filter(&call));
 			assert_eq!(Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), Some((3, 3)), 127, root(), call
+				1u32.encode(), DispatchTime::At(4), Some((3, 3)), 127, root(), call.into()
 			).unwrap(), (4, 0));
 
 			run_to_block(3);
@@ -1155,10 +1527,10 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			// at #4.
 			Scheduler::do_schedule_named(
-				1u32.encode(), DispatchTime::At(4), None, 127, root(), Call::Logger(LoggerCall::log(69, 1000))
+				1u32.encode(), DispatchTime::At(4), None, 127, root(), Call::Logger(LoggerCall::log(69, 1000)).into()
 			).unwrap();
 			let i = Scheduler::do_schedule(
-				DispatchTime::At(4), None, 127, root(), Call::Logger(LoggerCall::log(42, 1000))
+				DispatchTime::At(4), None, 127, root(), Call::Logger(LoggerCall::log(42, 1000)).into()
 			).unwrap();
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1179,7 +1551,7 @@ mod tests {
 				Some((3, 3)),
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(42, 1000)),
+				Call::Logger(LoggerCall::log(42, 1000)).into(),
 			)
 			.unwrap();
 			// same id results in error.
@@ -1189,7 +1561,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, 1000))
+				Call::Logger(LoggerCall::log(69, 1000)).into()
 			)
 			.is_err());
 			// different id is ok.
@@ -1199,7 +1571,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, 1000)),
+				Call::Logger(LoggerCall::log(69, 1000)).into(),
 			)
 			.unwrap();
 			run_to_block(3);
@@ -1221,14 +1593,14 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			// 69 and 42 do not fit together
 			run_to_block(4);
@@ -1246,14 +1618,14 @@ mod tests {
 				None,
 				0,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			// With base weights, 69 and 42 should not fit together, but do because of hard deadlines
 			run_to_block(4);
@@ -1269,14 +1641,14 @@ mod tests {
 				None,
 				1,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				0,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			run_to_block(4);
 			assert_eq!(logger::log(), vec![(root(), 69u32), (root(), 42u32)]);
@@ -1291,21 +1663,21 @@ mod tests {
 				None,
 				255,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3))
+				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3)).into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
 				None,
 				126,
 				root(),
-				Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2)).into()
 			));
 
 			// 2600 does not fit with 69 or 42, but has higher priority, so will go through
@@ -1337,7 +1709,7 @@ mod tests {
 				None,
 				255,
 				root(),
-				Call::Logger(LoggerCall::log(3, MaximumSchedulerWeight::get() / 3))
+				Call::Logger(LoggerCall::log(3, MaximumSchedulerWeight::get() / 3)).into()
 			));
 			// Anon Periodic
 			assert_ok!(Scheduler::do_schedule(
@@ -1345,7 +1717,7 @@ mod tests {
 				Some((1000, 3)),
 				128,
 				root(),
-				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3))
+				Call::Logger(LoggerCall::log(42, MaximumSchedulerWeight::get() / 3)).into()
 			));
 			// Anon
 			assert_ok!(Scheduler::do_schedule(
@@ -1353,7 +1725,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(69, MaximumSchedulerWeight::get() / 2)).into()
 			));
 			// Named Periodic
 			assert_ok!(Scheduler::do_schedule_named(
@@ -1362,7 +1734,7 @@ mod tests {
 				Some((1000, 3)),
 				126,
 				root(),
-				Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2))
+				Call::Logger(LoggerCall::log(2600, MaximumSchedulerWeight::get() / 2)).into()
 			));
 
 			// Will include the named periodic only
@@ -1414,8 +1786,8 @@ mod tests {
 	#[test]
 	fn root_calls_works() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)));
+			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)).into());
 			assert_ok!(Scheduler::schedule_named(
 				Origin::root(),
 				1u32.encode(),
@@ -1442,8 +1814,8 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			run_to_block(3);
 
-			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)));
+			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)).into());
+			let call2: Box<CallOrHashOf<Test>> = Box::new(Call::Logger(LoggerCall::log(42, 1000)).into());
 
 			assert_err!(
 				Scheduler::schedule_named(Origin::root(), 1u32.encode(), 2, None, 127, call),
@@ -1465,8 +1837,8 @@ mod tests {
 	#[test]
 	fn should_use_orign() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)));
+			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)).into());
 			assert_ok!(Scheduler::schedule_named(
 				system::RawOrigin::Signed(1).into(),
 				1u32.encode(),
@@ -1500,8 +1872,8 @@ mod tests {
 	#[test]
 	fn should_check_orign() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)));
-			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)));
+			let call = Box::new(Call::Logger(LoggerCall::log(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(LoggerCall::log(42, 1000)).into());
 			assert_noop!(
 				Scheduler::schedule_named(
 					system::RawOrigin::Signed(2).into(),
@@ -1523,8 +1895,8 @@ mod tests {
 	#[test]
 	fn should_check_orign_for_cancel() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log_without_filter(69, 1000)));
-			let call2 = Box::new(Call::Logger(LoggerCall::log_without_filter(42, 1000)));
+			let call = Box::new(Call::Logger(LoggerCall::log_without_filter(69, 1000)).into());
+			let call2 = Box::new(Call::Logger(LoggerCall::log_without_filter(42, 1000)).into());
 			assert_ok!(Scheduler::schedule_named(
 				system::RawOrigin::Signed(1).into(),
 				1u32.encode(),
@@ -1572,7 +1944,7 @@ mod tests {
 	}
 
 	#[test]
-	fn migration_to_v2_works() {
+	fn migration_to_v3_works() {
 		new_test_ext().execute_with(|| {
 			for i in 0..3u64 {
 				let k = i.twox_64_concat();
@@ -1597,6 +1969,8 @@ mod tests {
 			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
 
 			assert!(Scheduler::migrate_v1_to_t2());
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+			assert!(Scheduler::migrate_v2_to_v3());
 
 			assert_eq_uvec!(
 				Agenda::<Test>::iter().collect::<Vec<_>>(),
@@ -1604,19 +1978,19 @@ mod tests {
 					(
 						0,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3::<CallOrHashOf<Test>, u64, OriginCaller, u64> {
 								maybe_id: None,
 								priority: 10,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: Call::Logger(LoggerCall::log(96, 100)).into(),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1626,19 +2000,19 @@ mod tests {
 					(
 						1,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 11,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: Call::Logger(LoggerCall::log(96, 100)).into(),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1648,19 +2022,19 @@ mod tests {
 					(
 						2,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 12,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: Call::Logger(LoggerCall::log(96, 100)).into(),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1670,7 +2044,90 @@ mod tests {
 				]
 			);
 
-			assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
+		});
+	}
+
+	#[test]
+	fn migration_v2_to_v3_works() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::<Test>::put(Releases::V2);
+			let old = vec![
+				Some(ScheduledV2::<Call, u64, OriginCaller, u64> {
+					maybe_id: None,
+					priority: 10,
+					call: Call::Logger(LoggerCall::log(96, 100)),
+					maybe_periodic: None,
+					origin: root(),
+					_phantom: PhantomData::<u64>::default(),
+				}),
+				None,
+			];
+			frame_support::migration::put_storage_value(
+				b"Scheduler", b"Agenda", &1u64.twox_64_concat(), old,
+			);
+
+			assert!(Scheduler::migrate_v2_to_v3());
+			assert!(!Scheduler::migrate_v2_to_v3());
+
+			assert_eq!(
+				Agenda::<Test>::get(1),
+				vec![
+					Some(ScheduledV3 {
+						maybe_id: None,
+						priority: 10,
+						call: Call::Logger(LoggerCall::log(96, 100)).into(),
+						maybe_periodic: None,
+						origin: root(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+					None,
+				]
+			);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
+		});
+	}
+
+	#[test]
+	fn runtime_upgrade_migrates_to_v3() {
+		new_test_ext().execute_with(|| {
+			let old = vec![
+				Some(ScheduledV1::<Call, u64> {
+					maybe_id: None,
+					priority: 10,
+					call: Call::Logger(LoggerCall::log(96, 100)),
+					maybe_periodic: None,
+				}),
+				None,
+			];
+			frame_support::migration::put_storage_value(
+				b"Scheduler", b"Agenda", &1u64.twox_64_concat(), old,
+			);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+
+			let max_block = <<Test as frame_system::Config>::BlockWeights as
+				Get<frame_system::limits::BlockWeights>>::get().max_block;
+			assert!(Scheduler::on_runtime_upgrade() >= max_block);
+
+			assert_eq!(
+				Agenda::<Test>::get(1),
+				vec![
+					Some(ScheduledV3 {
+						maybe_id: None,
+						priority: 10,
+						call: Call::Logger(LoggerCall::log(96, 100)).into(),
+						maybe_periodic: None,
+						origin: root(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+					None,
+				]
+			);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
+
+			// Nothing is migrated once the storage is up to date.
+			assert!(Scheduler::on_runtime_upgrade() < max_block);
+			assert_eq!(Agenda::<Test>::get(1).len(), 2);
 		});
 	}
 
@@ -1679,11 +2136,11 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			for i in 0..3u64 {
 				let k = i.twox_64_concat();
-				let old: Vec<Option<Scheduled<_, _, u32, u64>>> = vec![
+				let old: Vec<Option<Scheduled<CallOrHashOf<Test>, u64, u32, u64>>> = vec![
 					Some(Scheduled {
 						maybe_id: None,
 						priority: i as u8 + 10,
-						call: Call::Logger(LoggerCall::log(96, 100)),
+						call: Call::Logger(LoggerCall::log(96, 100)).into(),
 						origin: 3u32,
 						maybe_periodic: None,
 						_phantom: Default::default(),
@@ -1693,7 +2150,7 @@ mod tests {
 						maybe_id: Some(b"test".to_vec()),
 						priority: 123,
 						origin: 2u32,
-						call: Call::Logger(LoggerCall::log(69, 1000)),
+						call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 						maybe_periodic: Some((456u64, 10)),
 						_phantom: Default::default(),
 					}),
@@ -1719,19 +2176,19 @@ mod tests {
 					(
 						0,
 						vec![
-							Some(ScheduledV2::<_, _, OriginCaller, u64> {
+							Some(ScheduledV3::<CallOrHashOf<Test>, u64, OriginCaller, u64> {
 								maybe_id: None,
 								priority: 10,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: Call::Logger(LoggerCall::log(96, 100)).into(),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1741,19 +2198,19 @@ mod tests {
 					(
 						1,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 11,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: Call::Logger(LoggerCall::log(96, 100)).into(),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1763,19 +2220,19 @@ mod tests {
 					(
 						2,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 12,
-								call: Call::Logger(LoggerCall::log(96, 100)),
+								call: Call::Logger(LoggerCall::log(96, 100)).into(),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log(69, 1000)),
+								call: Call::Logger(LoggerCall::log(69, 1000)).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
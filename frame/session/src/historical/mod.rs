@@ -37,7 +37,7 @@ use frame_support::{
 };
 use sp_trie::{MemoryDB, Trie, TrieMut, Recorder, EMPTY_PREFIX};
 use sp_trie::trie_types::{TrieDBMut, TrieDB};
-use super::{SessionIndex, Module as SessionModule};
+use super::{SessionIndex, Pallet as SessionModule};
 
 mod shared;
 pub mod offchain;
@@ -109,11 +109,11 @@ impl<T: Config> ValidatorSet<T::AccountId> for Module<T> {
 	type ValidatorIdOf = T::ValidatorIdOf;
 
 	fn session_index() -> sp_staking::SessionIndex {
-		super::Module::<T>::current_index()
+		super::Pallet::<T>::current_index()
 	}
 
 	fn validators() -> Vec<Self::ValidatorId> {
-		super::Module::<T>::validators()
+		super::Pallet::<T>::validators()
 	}
 }
 
@@ -346,7 +346,7 @@ pub(crate) mod tests {
 		NEXT_VALIDATORS, force_new_session,
 		set_next_validators, Test, System, Session,
 	};
-	use frame_support::traits::{KeyOwnerProofSystem, OnInitialize};
+	use frame_support::traits::{GenesisBuild, KeyOwnerProofSystem, OnInitialize};
 	use frame_support::BasicExternalities;
 
 	type Historical = Module<Test>;
@@ -19,7 +19,7 @@
 
 use super::*;
 use std::cell::RefCell;
-use frame_support::{parameter_types, BasicExternalities};
+use frame_support::{parameter_types, traits::GenesisBuild, BasicExternalities};
 use sp_core::{crypto::key_types::DUMMY, H256};
 use sp_runtime::{
 	Perbill, impl_opaque_keys,
@@ -46,7 +46,7 @@ impl From<UintAuthorityId> for MockSessionKeys {
 pub const KEY_ID_A: KeyTypeId = KeyTypeId([4; 4]);
 pub const KEY_ID_B: KeyTypeId = KeyTypeId([9; 4]);
 
-#[derive(Debug, Clone, codec::Encode, codec::Decode, PartialEq, Eq)]
+#[derive(Debug, Clone, codec::Encode, codec::Decode, PartialEq, Eq, scale_info::TypeInfo)]
 pub struct PreUpgradeMockSessionKeys {
 	pub a: [u8; 32],
 	pub b: [u8; 64],
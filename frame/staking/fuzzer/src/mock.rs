@@ -165,7 +165,7 @@ impl frame_election_provider_support::ElectionProvider<AccountId, BlockNumber>
 	for MockElectionProvider
 {
 	type Error = ();
-	type DataProvider = pallet_staking::Module<Test>;
+	type DataProvider = pallet_staking::Pallet<Test>;
 
 	fn elect() -> Result<
 		(sp_npos_elections::Supports<AccountId>, frame_support::weights::Weight),
@@ -199,4 +199,5 @@ impl pallet_staking::Config for Test {
 	type OffchainSolutionWeightLimit = ();
 	type WeightInfo = ();
 	type ElectionProvider = MockElectionProvider;
+	type SortedListProvider = pallet_staking::UseNominatorsMap<Self>;
 }
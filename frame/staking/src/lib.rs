@@ -21,7 +21,7 @@
 //!
 //! - [`Config`]
 //! - [`Call`]
-//! - [`Module`]
+//! - [`Pallet`]
 //!
 //! ## Overview
 //!
@@ -162,7 +162,7 @@
 //!         #[weight = 0]
 //!         pub fn reward_myself(origin) -> dispatch::DispatchResult {
 //!             let reported = ensure_signed(origin)?;
-//!             <staking::Module<T>>::reward_by_ids(vec![(reported, 10)]);
+//!             <staking::Pallet<T>>::reward_by_ids(vec![(reported, 10)]);
 //!             Ok(())
 //!         }
 //!     }
@@ -198,9 +198,9 @@
 //!
 //! Total reward is split among validators and their nominators depending on the number of points
 //! they received during the era. Points are added to a validator using
-//! [`reward_by_ids`](Module::reward_by_ids).
+//! [`reward_by_ids`](Pallet::reward_by_ids).
 //!
-//! [`Module`] implements
+//! [`Pallet`] implements
 //! [`pallet_authorship::EventHandler`] to add reward
 //! points to block producer and block producer of referenced uncles.
 //!
@@ -280,6 +280,8 @@ pub mod slashing;
 pub mod inflation;
 pub mod weights;
 
+pub use pallet::*;
+
 use sp_std::{
 	result,
 	prelude::*,
@@ -287,13 +289,13 @@ use sp_std::{
 	convert::From,
 };
 use codec::{HasCompact, Encode, Decode};
+use scale_info::TypeInfo;
 use frame_support::{
-	decl_module, decl_event, decl_storage, ensure, decl_error,
+	ensure,
 	weights::{
 		Weight, WithPostDispatchInfo,
 		constants::{WEIGHT_PER_MICROS, WEIGHT_PER_NANOS},
 	},
-	storage::IterableStorageMap,
 	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	traits::{
 		Currency, LockIdentifier, LockableCurrency, WithdrawReasons, OnUnbalanced, Imbalance, Get,
@@ -317,7 +319,9 @@ use frame_system::{
 	self as system, ensure_signed, ensure_root,
 	offchain::SendTransactionTypes,
 };
-use frame_election_provider_support::{ElectionProvider, VoteWeight, Supports, data_provider};
+use frame_election_provider_support::{
+	ElectionProvider, VoteWeight, Supports, data_provider, SortedListProvider, VoteWeightProvider,
+};
 pub use weights::WeightInfo;
 
 const STAKING_ID: LockIdentifier = *b"staking ";
@@ -354,7 +358,7 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 >>::NegativeImbalance;
 
 /// Information regarding the active era (era in used in session).
-#[derive(Encode, Decode, RuntimeDebug)]
+#[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct ActiveEraInfo {
 	/// Index of era.
 	pub index: EraIndex,
@@ -368,7 +372,7 @@ pub struct ActiveEraInfo {
 /// Reward points of an era. Used to split era total payout between validators.
 ///
 /// This points will be used to reward validators and their respective nominators.
-#[derive(PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[derive(PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct EraRewardPoints<AccountId: Ord> {
 	/// Total number of points. Equals the sum of reward points for each validator.
 	total: RewardPoint,
@@ -389,7 +393,7 @@ pub enum StakerStatus<AccountId> {
 }
 
 /// A destination account for payment.
-#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum RewardDestination<AccountId> {
 	/// Pay into the stash account, increasing the amount at stake accordingly.
 	Staked,
@@ -410,7 +414,7 @@ impl<AccountId> Default for RewardDestination<AccountId> {
 }
 
 /// Preference of what happens regarding validation.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct ValidatorPrefs {
 	/// Reward that validator takes up-front; only the rest is split between themselves and
 	/// nominators.
@@ -432,7 +436,7 @@ impl Default for ValidatorPrefs {
 }
 
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct UnlockChunk<Balance: HasCompact> {
 	/// Amount of funds to be unlocked.
 	#[codec(compact)]
@@ -443,7 +447,7 @@ pub struct UnlockChunk<Balance: HasCompact> {
 }
 
 /// The ledger of a (bonded) stash.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct StakingLedger<AccountId, Balance: HasCompact> {
 	/// The stash account whose balance is actually locked and at stake.
 	pub stash: AccountId,
@@ -572,7 +576,7 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 }
 
 /// A record of the nominations made by a specific account.
-#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct Nominations<AccountId> {
 	/// The targets of nomination.
 	pub targets: Vec<AccountId>,
@@ -588,7 +592,7 @@ pub struct Nominations<AccountId> {
 }
 
 /// The amount of exposure (to slashing) than an individual nominator has.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub struct IndividualExposure<AccountId, Balance: HasCompact> {
 	/// The stash account of the nominator in question.
 	pub who: AccountId,
@@ -598,7 +602,7 @@ pub struct IndividualExposure<AccountId, Balance: HasCompact> {
 }
 
 /// A snapshot of the stake backing a single validator in the system.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct Exposure<AccountId, Balance: HasCompact> {
 	/// The total balance backing this validator.
 	#[codec(compact)]
@@ -612,7 +616,7 @@ pub struct Exposure<AccountId, Balance: HasCompact> {
 
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
-#[derive(Encode, Decode, Default, RuntimeDebug)]
+#[derive(Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 pub struct UnappliedSlash<AccountId, Balance: HasCompact> {
 	/// The stash ID of the offending validator.
 	validator: AccountId,
@@ -654,11 +658,11 @@ impl<T: Config> SessionInterface<<T as frame_system::Config>::AccountId> for T w
 		Convert<<T as frame_system::Config>::AccountId, Option<<T as frame_system::Config>::AccountId>>,
 {
 	fn disable_validator(validator: &<T as frame_system::Config>::AccountId) -> Result<bool, ()> {
-		<pallet_session::Module<T>>::disable(validator)
+		<pallet_session::Pallet<T>>::disable(validator)
 	}
 
 	fn validators() -> Vec<<T as frame_system::Config>::AccountId> {
-		<pallet_session::Module<T>>::validators()
+		<pallet_session::Pallet<T>>::validators()
 	}
 
 	fn prune_historical_up_to(up_to: SessionIndex) {
@@ -713,84 +717,8 @@ impl<
 	}
 }
 
-pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
-	/// The staking balance.
-	type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
-
-	/// Time used for computing era duration.
-	///
-	/// It is guaranteed to start being called from the first `on_finalize`. Thus value at genesis
-	/// is not used.
-	type UnixTime: UnixTime;
-
-	/// Convert a balance into a number used for election calculation. This must fit into a `u64`
-	/// but is allowed to be sensibly lossy. The `u64` is used to communicate with the
-	/// [`sp_npos_elections`] crate which accepts u64 numbers and does operations in 128.
-	/// Consequently, the backward convert is used convert the u128s from sp-elections back to a
-	/// [`BalanceOf`].
-	type CurrencyToVote: CurrencyToVote<BalanceOf<Self>>;
-
-	/// Something that provides the election functionality.
-	type ElectionProvider: frame_election_provider_support::ElectionProvider<
-		Self::AccountId,
-		Self::BlockNumber,
-		// we only accept an election provider that has staking as data provider.
-		DataProvider = Module<Self>,
-	>;
-
-	/// Maximum number of nominations per nominator.
-	const MAX_NOMINATIONS: u32;
-
-	/// Tokens have been minted and are unused for validator-reward.
-	/// See [Era payout](./index.html#era-payout).
-	type RewardRemainder: OnUnbalanced<NegativeImbalanceOf<Self>>;
-
-	/// The overarching event type.
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
-
-	/// Handler for the unbalanced reduction when slashing a staker.
-	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
-
-	/// Handler for the unbalanced increment when rewarding a staker.
-	type Reward: OnUnbalanced<PositiveImbalanceOf<Self>>;
-
-	/// Number of sessions per era.
-	type SessionsPerEra: Get<SessionIndex>;
-
-	/// Number of eras that staked funds must remain bonded for.
-	type BondingDuration: Get<EraIndex>;
-
-	/// Number of eras that slashes are deferred by, after computation.
-	///
-	/// This should be less than the bonding duration. Set to 0 if slashes
-	/// should be applied immediately, without opportunity for intervention.
-	type SlashDeferDuration: Get<EraIndex>;
-
-	/// The origin which can cancel a deferred slash. Root can always do this.
-	type SlashCancelOrigin: EnsureOrigin<Self::Origin>;
-
-	/// Interface for interacting with a session module.
-	type SessionInterface: self::SessionInterface<Self::AccountId>;
-
-	/// The payout for validators and the system for the current era.
-	/// See [Era payout](./index.html#era-payout).
-	type EraPayout: EraPayout<BalanceOf<Self>>;
-
-	/// Something that can estimate the next session change, accurately or as a best effort guess.
-	type NextNewSession: EstimateNextNewSession<Self::BlockNumber>;
-
-	/// The maximum number of nominators rewarded for each validator.
-	///
-	/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can claim
-	/// their reward. This used to limit the i/o cost for the nominator payout.
-	type MaxNominatorRewardedPerValidator: Get<u32>;
-
-	/// Weight information for extrinsics in this pallet.
-	type WeightInfo: WeightInfo;
-}
-
 /// Mode of era-forcing.
-#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum Forcing {
 	/// Not forcing anything - just let whatever happen.
@@ -812,7 +740,7 @@ impl Default for Forcing {
 // A value placed in storage that represents the current version of the Staking storage. This value
 // is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
 // This should match directly with the semantic versions of the Rust crate.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	V1_0_0Ancient,
 	V2_0_0,
@@ -820,191 +748,474 @@ enum Releases {
 	V4_0_0,
 	V5_0_0, // blockable validators.
 	V6_0_0, // removal of all storage associated with offchain phragmen.
+	V7_0_0, // keep track of nominators in the `SortedListProvider`.
+	V8_0_0, // keep track of number of nominators / validators in map
 }
 
 impl Default for Releases {
 	fn default() -> Self {
-		Releases::V6_0_0
+		Releases::V8_0_0
 	}
 }
 
-decl_storage! {
-	trait Store for Module<T: Config> as Staking {
-		/// Number of eras to keep in history.
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+		/// The staking balance.
+		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// Time used for computing era duration.
+		///
+		/// It is guaranteed to start being called from the first `on_finalize`. Thus value at genesis
+		/// is not used.
+		type UnixTime: UnixTime;
+
+		/// Convert a balance into a number used for election calculation. This must fit into a `u64`
+		/// but is allowed to be sensibly lossy. The `u64` is used to communicate with the
+		/// [`sp_npos_elections`] crate which accepts u64 numbers and does operations in 128.
+		/// Consequently, the backward convert is used convert the u128s from sp-elections back to a
+		/// [`BalanceOf`].
+		type CurrencyToVote: CurrencyToVote<BalanceOf<Self>>;
+
+		/// Something that provides the election functionality.
+		type ElectionProvider: frame_election_provider_support::ElectionProvider<
+			Self::AccountId,
+			Self::BlockNumber,
+			// we only accept an election provider that has staking as data provider.
+			DataProvider = Pallet<Self>,
+		>;
+
+		/// Maximum number of nominations per nominator.
+		const MAX_NOMINATIONS: u32;
+
+		/// Tokens have been minted and are unused for validator-reward.
+		/// See [Era payout](./index.html#era-payout).
+		type RewardRemainder: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Handler for the unbalanced reduction when slashing a staker.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Handler for the unbalanced increment when rewarding a staker.
+		type Reward: OnUnbalanced<PositiveImbalanceOf<Self>>;
+
+		/// Number of sessions per era.
+		#[pallet::constant]
+		type SessionsPerEra: Get<SessionIndex>;
+
+		/// Number of eras that staked funds must remain bonded for.
+		#[pallet::constant]
+		type BondingDuration: Get<EraIndex>;
+
+		/// Number of eras that slashes are deferred by, after computation.
 		///
-		/// Information is kept for eras in `[current_era - history_depth; current_era]`.
+		/// This should be less than the bonding duration. Set to 0 if slashes
+		/// should be applied immediately, without opportunity for intervention.
+		#[pallet::constant]
+		type SlashDeferDuration: Get<EraIndex>;
+
+		/// The origin which can cancel a deferred slash. Root can always do this.
+		type SlashCancelOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Interface for interacting with a session module.
+		type SessionInterface: self::SessionInterface<Self::AccountId>;
+
+		/// The payout for validators and the system for the current era.
+		/// See [Era payout](./index.html#era-payout).
+		type EraPayout: EraPayout<BalanceOf<Self>>;
+
+		/// Something that can estimate the next session change, accurately or as a best effort guess.
+		type NextNewSession: EstimateNextNewSession<Self::BlockNumber>;
+
+		/// The maximum number of nominators rewarded for each validator.
 		///
-		/// Must be more than the number of eras delayed by session otherwise. I.e. active era must
-		/// always be in history. I.e. `active_era > current_era - history_depth` must be
-		/// guaranteed.
-		HistoryDepth get(fn history_depth) config(): u32 = 84;
+		/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can claim
+		/// their reward. This used to limit the i/o cost for the nominator payout.
+		#[pallet::constant]
+		type MaxNominatorRewardedPerValidator: Get<u32>;
 
-		/// The ideal number of staking participants.
-		pub ValidatorCount get(fn validator_count) config(): u32;
+		/// Something that can provide a sorted list of voters in a somewhat sorted way. The original
+		/// use case for this was designed with [`pallet_bags_list::Pallet`] in mind. If the bags-list
+		/// is not desired, [`UseNominatorsMap`] is likely the desired option.
+		type SortedListProvider: SortedListProvider<Self::AccountId>;
 
-		/// Minimum number of staking participants before emergency conditions are imposed.
-		pub MinimumValidatorCount get(fn minimum_validator_count) config(): u32;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
 
-		/// Any validators that may never be slashed or forcibly kicked. It's a Vec since they're
-		/// easy to initialize and the performance hit is minimal (we expect no more than four
-		/// invulnerables) and restricted to testnets.
-		pub Invulnerables get(fn invulnerables) config(): Vec<T::AccountId>;
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// Maximum number of nominations per nominator.
+		#[allow(non_snake_case)]
+		fn MaxNominations() -> u32 {
+			T::MAX_NOMINATIONS
+		}
+	}
 
-		/// Map from all locked "stash" accounts to the controller account.
-		pub Bonded get(fn bonded): map hasher(twox_64_concat) T::AccountId => Option<T::AccountId>;
+	#[pallet::type_value]
+	pub(crate) fn HistoryDepthOnEmpty() -> u32 { 84u32 }
 
-		/// Map from all (unlocked) "controller" accounts to the info regarding the staking.
-		pub Ledger get(fn ledger):
-			map hasher(blake2_128_concat) T::AccountId
-			=> Option<StakingLedger<T::AccountId, BalanceOf<T>>>;
+	/// Number of eras to keep in history.
+	///
+	/// Information is kept for eras in `[current_era - history_depth; current_era]`.
+	///
+	/// Must be more than the number of eras delayed by session otherwise. I.e. active era must
+	/// always be in history. I.e. `active_era > current_era - history_depth` must be
+	/// guaranteed.
+	#[pallet::storage]
+	#[pallet::getter(fn history_depth)]
+	pub(crate) type HistoryDepth<T> = StorageValue<_, u32, ValueQuery, HistoryDepthOnEmpty>;
+
+	/// The ideal number of staking participants.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_count)]
+	pub type ValidatorCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Minimum number of staking participants before emergency conditions are imposed.
+	#[pallet::storage]
+	#[pallet::getter(fn minimum_validator_count)]
+	pub type MinimumValidatorCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Any validators that may never be slashed or forcibly kicked. It's a Vec since they're
+	/// easy to initialize and the performance hit is minimal (we expect no more than four
+	/// invulnerables) and restricted to testnets.
+	#[pallet::storage]
+	#[pallet::getter(fn invulnerables)]
+	pub type Invulnerables<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// Map from all locked "stash" accounts to the controller account.
+	#[pallet::storage]
+	#[pallet::getter(fn bonded)]
+	pub type Bonded<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+	/// The minimum active bond to become and maintain the role of a nominator.
+	#[pallet::storage]
+	#[pallet::getter(fn min_nominator_bond)]
+	pub type MinNominatorBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The minimum active bond to become and maintain the role of a validator.
+	#[pallet::storage]
+	#[pallet::getter(fn min_validator_bond)]
+	pub type MinValidatorBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Map from all (unlocked) "controller" accounts to the info regarding the staking.
+	#[pallet::storage]
+	#[pallet::getter(fn ledger)]
+	pub type Ledger<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat, T::AccountId,
+		StakingLedger<T::AccountId, BalanceOf<T>>,
+	>;
 
-		/// Where the reward payment should be made. Keyed by stash.
-		pub Payee get(fn payee): map hasher(twox_64_concat) T::AccountId => RewardDestination<T::AccountId>;
+	/// Where the reward payment should be made. Keyed by stash.
+	#[pallet::storage]
+	#[pallet::getter(fn payee)]
+	pub type Payee<T: Config> = StorageMap<
+		_,
+		Twox64Concat, T::AccountId,
+		RewardDestination<T::AccountId>,
+		ValueQuery,
+	>;
 
-		/// The map from (wannabe) validator stash key to the preferences of that validator.
-		pub Validators get(fn validators):
-			map hasher(twox_64_concat) T::AccountId => ValidatorPrefs;
+	/// The map from (wannabe) validator stash key to the preferences of that validator.
+	///
+	/// When updating this storage item, you must also update the `CounterForValidators`.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, ValidatorPrefs, ValueQuery>;
 
-		/// The map from nominator stash key to the set of stash keys of all validators to nominate.
-		pub Nominators get(fn nominators):
-			map hasher(twox_64_concat) T::AccountId => Option<Nominations<T::AccountId>>;
+	/// A tracker to keep count of the number of items in the `Validators` map.
+	#[pallet::storage]
+	#[pallet::getter(fn counter_for_validators)]
+	pub type CounterForValidators<T> = StorageValue<_, u32, ValueQuery>;
 
-		/// The current era index.
-		///
-		/// This is the latest planned era, depending on how the Session pallet queues the validator
-		/// set, it might be active or not.
-		pub CurrentEra get(fn current_era): Option<EraIndex>;
+	/// The maximum validator count before we stop allowing new validators to join.
+	///
+	/// When this value is not set, no limits are enforced.
+	#[pallet::storage]
+	#[pallet::getter(fn max_validators_count)]
+	pub type MaxValidatorsCount<T> = StorageValue<_, u32, OptionQuery>;
 
-		/// The active era information, it holds index and start.
-		///
-		/// The active era is the era being currently rewarded. Validator set of this era must be
-		/// equal to [`SessionInterface::validators`].
-		pub ActiveEra get(fn active_era): Option<ActiveEraInfo>;
+	/// The map from nominator stash key to the set of stash keys of all validators to nominate.
+	///
+	/// When updating this storage item, you must also update the `CounterForNominators`.
+	#[pallet::storage]
+	#[pallet::getter(fn nominators)]
+	pub type Nominators<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Nominations<T::AccountId>>;
 
-		/// The session index at which the era start for the last `HISTORY_DEPTH` eras.
-		///
-		/// Note: This tracks the starting session (i.e. session index when era start being active)
-		/// for the eras in `[CurrentEra - HISTORY_DEPTH, CurrentEra]`.
-		pub ErasStartSessionIndex get(fn eras_start_session_index):
-			map hasher(twox_64_concat) EraIndex => Option<SessionIndex>;
+	/// A tracker to keep count of the number of items in the `Nominators` map.
+	#[pallet::storage]
+	#[pallet::getter(fn counter_for_nominators)]
+	pub type CounterForNominators<T> = StorageValue<_, u32, ValueQuery>;
 
-		/// Exposure of validator at era.
-		///
-		/// This is keyed first by the era index to allow bulk deletion and then the stash account.
-		///
-		/// Is it removed after `HISTORY_DEPTH` eras.
-		/// If stakers hasn't been set or has been removed then empty exposure is returned.
-		pub ErasStakers get(fn eras_stakers):
-			double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
-			=> Exposure<T::AccountId, BalanceOf<T>>;
+	/// The maximum nominator count before we stop allowing new validators to join.
+	///
+	/// When this value is not set, no limits are enforced.
+	#[pallet::storage]
+	#[pallet::getter(fn max_nominators_count)]
+	pub type MaxNominatorsCount<T> = StorageValue<_, u32, OptionQuery>;
+
+	/// The threshold for when users can start calling `chill_other` for other validators /
+	/// nominators. The threshold is compared to the actual number of validators / nominators
+	/// (`CounterFor*`) in the system compared to the configured max (`Max*Count`).
+	#[pallet::storage]
+	#[pallet::getter(fn chill_threshold)]
+	pub type ChillThreshold<T> = StorageValue<_, Percent, OptionQuery>;
+
+	/// The current era index.
+	///
+	/// This is the latest planned era, depending on how the Session pallet queues the validator
+	/// set, it might be active or not.
+	#[pallet::storage]
+	#[pallet::getter(fn current_era)]
+	pub type CurrentEra<T> = StorageValue<_, EraIndex>;
 
-		/// Clipped Exposure of validator at era.
-		///
-		/// This is similar to [`ErasStakers`] but number of nominators exposed is reduced to the
-		/// `T::MaxNominatorRewardedPerValidator` biggest stakers.
-		/// (Note: the field `total` and `own` of the exposure remains unchanged).
-		/// This is used to limit the i/o cost for the nominator payout.
-		///
-		/// This is keyed fist by the era index to allow bulk deletion and then the stash account.
-		///
-		/// Is it removed after `HISTORY_DEPTH` eras.
-		/// If stakers hasn't been set or has been removed then empty exposure is returned.
-		pub ErasStakersClipped get(fn eras_stakers_clipped):
-			double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
-			=> Exposure<T::AccountId, BalanceOf<T>>;
+	/// The active era information, it holds index and start.
+	///
+	/// The active era is the era being currently rewarded. Validator set of this era must be
+	/// equal to [`SessionInterface::validators`].
+	#[pallet::storage]
+	#[pallet::getter(fn active_era)]
+	pub type ActiveEra<T> = StorageValue<_, ActiveEraInfo>;
 
-		/// Similar to `ErasStakers`, this holds the preferences of validators.
-		///
-		/// This is keyed first by the era index to allow bulk deletion and then the stash account.
-		///
-		/// Is it removed after `HISTORY_DEPTH` eras.
-		// If prefs hasn't been set or has been removed then 0 commission is returned.
-		pub ErasValidatorPrefs get(fn eras_validator_prefs):
-			double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
-			=> ValidatorPrefs;
+	/// The session index at which the era start for the last `HISTORY_DEPTH` eras.
+	///
+	/// Note: This tracks the starting session (i.e. session index when era start being active)
+	/// for the eras in `[CurrentEra - HISTORY_DEPTH, CurrentEra]`.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_start_session_index)]
+	pub type ErasStartSessionIndex<T> = StorageMap<_, Twox64Concat, EraIndex, SessionIndex>;
 
-		/// The total validator era payout for the last `HISTORY_DEPTH` eras.
-		///
-		/// Eras that haven't finished yet or has been removed doesn't have reward.
-		pub ErasValidatorReward get(fn eras_validator_reward):
-			map hasher(twox_64_concat) EraIndex => Option<BalanceOf<T>>;
+	/// Exposure of validator at era.
+	///
+	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	/// If stakers hasn't been set or has been removed then empty exposure is returned.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_stakers)]
+	pub type ErasStakers<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat, EraIndex,
+		Twox64Concat, T::AccountId,
+		Exposure<T::AccountId, BalanceOf<T>>,
+		ValueQuery,
+	>;
 
-		/// Rewards for the last `HISTORY_DEPTH` eras.
-		/// If reward hasn't been set or has been removed then 0 reward is returned.
-		pub ErasRewardPoints get(fn eras_reward_points):
-			map hasher(twox_64_concat) EraIndex => EraRewardPoints<T::AccountId>;
+	/// Clipped Exposure of validator at era.
+	///
+	/// This is similar to [`ErasStakers`] but number of nominators exposed is reduced to the
+	/// `T::MaxNominatorRewardedPerValidator` biggest stakers.
+	/// (Note: the field `total` and `own` of the exposure remains unchanged).
+	/// This is used to limit the i/o cost for the nominator payout.
+	///
+	/// This is keyed fist by the era index to allow bulk deletion and then the stash account.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	/// If stakers hasn't been set or has been removed then empty exposure is returned.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_stakers_clipped)]
+	pub type ErasStakersClipped<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat, EraIndex,
+		Twox64Concat, T::AccountId,
+		Exposure<T::AccountId, BalanceOf<T>>,
+		ValueQuery,
+	>;
 
-		/// The total amount staked for the last `HISTORY_DEPTH` eras.
-		/// If total hasn't been set or has been removed then 0 stake is returned.
-		pub ErasTotalStake get(fn eras_total_stake):
-			map hasher(twox_64_concat) EraIndex => BalanceOf<T>;
+	/// Similar to `ErasStakers`, this holds the preferences of validators.
+	///
+	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	// If prefs hasn't been set or has been removed then 0 commission is returned.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_validator_prefs)]
+	pub type ErasValidatorPrefs<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat, EraIndex,
+		Twox64Concat, T::AccountId,
+		ValidatorPrefs,
+		ValueQuery,
+	>;
 
-		/// Mode of era forcing.
-		pub ForceEra get(fn force_era) config(): Forcing;
+	/// The total validator era payout for the last `HISTORY_DEPTH` eras.
+	///
+	/// Eras that haven't finished yet or has been removed doesn't have reward.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_validator_reward)]
+	pub type ErasValidatorReward<T: Config> = StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>>;
+
+	/// Rewards for the last `HISTORY_DEPTH` eras.
+	/// If reward hasn't been set or has been removed then 0 reward is returned.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_reward_points)]
+	pub type ErasRewardPoints<T: Config> = StorageMap<
+		_,
+		Twox64Concat, EraIndex,
+		EraRewardPoints<T::AccountId>,
+		ValueQuery,
+	>;
 
-		/// The percentage of the slash that is distributed to reporters.
-		///
-		/// The rest of the slashed value is handled by the `Slash`.
-		pub SlashRewardFraction get(fn slash_reward_fraction) config(): Perbill;
+	/// The total amount staked for the last `HISTORY_DEPTH` eras.
+	/// If total hasn't been set or has been removed then 0 stake is returned.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_total_stake)]
+	pub type ErasTotalStake<T: Config> = StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, ValueQuery>;
 
-		/// The amount of currency given to reporters of a slash event which was
-		/// canceled by extraordinary circumstances (e.g. governance).
-		pub CanceledSlashPayout get(fn canceled_payout) config(): BalanceOf<T>;
+	/// Mode of era forcing.
+	#[pallet::storage]
+	#[pallet::getter(fn force_era)]
+	pub type ForceEra<T> = StorageValue<_, Forcing, ValueQuery>;
 
-		/// All unapplied slashes that are queued for later.
-		pub UnappliedSlashes:
-			map hasher(twox_64_concat) EraIndex => Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>;
+	/// The percentage of the slash that is distributed to reporters.
+	///
+	/// The rest of the slashed value is handled by the `Slash`.
+	#[pallet::storage]
+	#[pallet::getter(fn slash_reward_fraction)]
+	pub type SlashRewardFraction<T> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// The amount of currency given to reporters of a slash event which was
+	/// canceled by extraordinary circumstances (e.g. governance).
+	#[pallet::storage]
+	#[pallet::getter(fn canceled_payout)]
+	pub type CanceledSlashPayout<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// All unapplied slashes that are queued for later.
+	#[pallet::storage]
+	pub type UnappliedSlashes<T: Config> = StorageMap<
+		_,
+		Twox64Concat, EraIndex,
+		Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
 
-		/// A mapping from still-bonded eras to the first session index of that era.
-		///
-		/// Must contains information for eras for the range:
-		/// `[active_era - bounding_duration; active_era]`
-		BondedEras: Vec<(EraIndex, SessionIndex)>;
+	/// A mapping from still-bonded eras to the first session index of that era.
+	///
+	/// Must contains information for eras for the range:
+	/// `[active_era - bounding_duration; active_era]`
+	#[pallet::storage]
+	pub(crate) type BondedEras<T: Config> = StorageValue<_, Vec<(EraIndex, SessionIndex)>, ValueQuery>;
+
+	/// All slashing events on validators, mapped by era to the highest slash proportion
+	/// and slash value of the era.
+	#[pallet::storage]
+	pub(crate) type ValidatorSlashInEra<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat, EraIndex,
+		Twox64Concat, T::AccountId,
+		(Perbill, BalanceOf<T>),
+	>;
 
-		/// All slashing events on validators, mapped by era to the highest slash proportion
-		/// and slash value of the era.
-		ValidatorSlashInEra:
-			double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
-			=> Option<(Perbill, BalanceOf<T>)>;
+	/// All slashing events on nominators, mapped by era to the highest slash value of the era.
+	#[pallet::storage]
+	pub(crate) type NominatorSlashInEra<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat, EraIndex,
+		Twox64Concat, T::AccountId,
+		BalanceOf<T>,
+	>;
 
-		/// All slashing events on nominators, mapped by era to the highest slash value of the era.
-		NominatorSlashInEra:
-			double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
-			=> Option<BalanceOf<T>>;
+	/// Slashing spans for stash accounts.
+	#[pallet::storage]
+	#[pallet::getter(fn slashing_spans)]
+	pub(crate) type SlashingSpans<T: Config> = StorageMap<
+		_,
+		Twox64Concat, T::AccountId,
+		slashing::SlashingSpans,
+	>;
 
-		/// Slashing spans for stash accounts.
-		SlashingSpans get(fn slashing_spans): map hasher(twox_64_concat) T::AccountId => Option<slashing::SlashingSpans>;
+	/// Records information about the maximum slash of a stash within a slashing span,
+	/// as well as how much reward has been paid out.
+	#[pallet::storage]
+	pub(crate) type SpanSlash<T: Config> = StorageMap<
+		_,
+		Twox64Concat, (T::AccountId, slashing::SpanIndex),
+		slashing::SpanRecord<BalanceOf<T>>,
+		ValueQuery,
+	>;
 
-		/// Records information about the maximum slash of a stash within a slashing span,
-		/// as well as how much reward has been paid out.
-		SpanSlash:
-			map hasher(twox_64_concat) (T::AccountId, slashing::SpanIndex)
-			=> slashing::SpanRecord<BalanceOf<T>>;
+	/// The earliest era for which we have a pending, unapplied slash.
+	#[pallet::storage]
+	pub(crate) type EarliestUnappliedSlash<T> = StorageValue<_, EraIndex>;
 
-		/// The earliest era for which we have a pending, unapplied slash.
-		EarliestUnappliedSlash: Option<EraIndex>;
+	/// The last planned session scheduled by the session pallet.
+	///
+	/// This is basically in sync with the call to [`SessionManager::new_session`].
+	#[pallet::storage]
+	#[pallet::getter(fn current_planned_session)]
+	pub type CurrentPlannedSession<T> = StorageValue<_, SessionIndex, ValueQuery>;
 
-		/// The last planned session scheduled by the session pallet.
-		///
-		/// This is basically in sync with the call to [`SessionManager::new_session`].
-		pub CurrentPlannedSession get(fn current_planned_session): SessionIndex;
+	/// True if network has been upgraded to this version.
+	/// Storage version of the pallet.
+	///
+	/// This is set to v8.0.0 for new networks.
+	#[pallet::storage]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub history_depth: u32,
+		pub validator_count: u32,
+		pub minimum_validator_count: u32,
+		pub invulnerables: Vec<T::AccountId>,
+		pub force_era: Forcing,
+		pub slash_reward_fraction: Perbill,
+		pub canceled_payout: BalanceOf<T>,
+		pub stakers: Vec<(T::AccountId, T::AccountId, BalanceOf<T>, StakerStatus<T::AccountId>)>,
+		pub min_nominator_bond: BalanceOf<T>,
+		pub min_validator_bond: BalanceOf<T>,
+	}
 
-		/// True if network has been upgraded to this version.
-		/// Storage version of the pallet.
-		///
-		/// This is set to v6.0.0 for new networks.
-		StorageVersion build(|_: &GenesisConfig<T>| Releases::V6_0_0): Releases;
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				history_depth: 84u32,
+				validator_count: Default::default(),
+				minimum_validator_count: Default::default(),
+				invulnerables: Default::default(),
+				force_era: Default::default(),
+				slash_reward_fraction: Default::default(),
+				canceled_payout: Default::default(),
+				stakers: Default::default(),
+				min_nominator_bond: Default::default(),
+				min_validator_bond: Default::default(),
+			}
+		}
 	}
-	add_extra_genesis {
-		config(stakers):
-			Vec<(T::AccountId, T::AccountId, BalanceOf<T>, StakerStatus<T::AccountId>)>;
-		build(|config: &GenesisConfig<T>| {
-			for &(ref stash, ref controller, balance, ref status) in &config.stakers {
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			HistoryDepth::<T>::put(self.history_depth);
+			ValidatorCount::<T>::put(self.validator_count);
+			MinimumValidatorCount::<T>::put(self.minimum_validator_count);
+			Invulnerables::<T>::put(&self.invulnerables);
+			ForceEra::<T>::put(self.force_era);
+			CanceledSlashPayout::<T>::put(self.canceled_payout);
+			SlashRewardFraction::<T>::put(self.slash_reward_fraction);
+			StorageVersion::<T>::put(Releases::V8_0_0);
+			MinNominatorBond::<T>::put(self.min_nominator_bond);
+			MinValidatorBond::<T>::put(self.min_validator_bond);
+
+			for &(ref stash, ref controller, balance, ref status) in &self.stakers {
 				assert!(
 					T::Currency::free_balance(&stash) >= balance,
 					"Stash does not have enough balance to bond."
 				);
-				let _ = <Module<T>>::bond(
+				let _ = <Pallet<T>>::bond(
 					T::Origin::from(Some(stash.clone()).into()),
 					T::Lookup::unlookup(controller.clone()),
 					balance,
@@ -1012,80 +1223,42 @@ decl_storage! {
 				);
 				let _ = match status {
 					StakerStatus::Validator => {
-						<Module<T>>::validate(
+						<Pallet<T>>::validate(
 							T::Origin::from(Some(controller.clone()).into()),
 							Default::default(),
 						)
 					},
 					StakerStatus::Nominator(votes) => {
-						<Module<T>>::nominate(
+						<Pallet<T>>::nominate(
 							T::Origin::from(Some(controller.clone()).into()),
 							votes.iter().map(|l| T::Lookup::unlookup(l.clone())).collect(),
 						)
 					}, _ => Ok(())
 				};
 			}
-		});
-	}
-}
-
-pub mod migrations {
-	use super::*;
-
-	pub mod v6 {
-		use super::*;
-		use frame_support::{traits::Get, weights::Weight, generate_storage_alias};
-
-		// NOTE: value type doesn't matter, we just set it to () here.
-		generate_storage_alias!(Staking, SnapshotValidators => Value<()>);
-		generate_storage_alias!(Staking, SnapshotNominators => Value<()>);
-		generate_storage_alias!(Staking, QueuedElected => Value<()>);
-		generate_storage_alias!(Staking, QueuedScore => Value<()>);
-		generate_storage_alias!(Staking, EraElectionStatus => Value<()>);
-		generate_storage_alias!(Staking, IsCurrentSessionFinal => Value<()>);
-
-		/// check to execute prior to migration.
-		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
-			// these may or may not exist.
-			log!(info, "SnapshotValidators.exits()? {:?}", SnapshotValidators::exists());
-			log!(info, "SnapshotNominators.exits()? {:?}", SnapshotNominators::exists());
-			log!(info, "QueuedElected.exits()? {:?}", QueuedElected::exists());
-			log!(info, "QueuedScore.exits()? {:?}", QueuedScore::exists());
-			// these must exist.
-			assert!(IsCurrentSessionFinal::exists(), "IsCurrentSessionFinal storage item not found!");
-			assert!(EraElectionStatus::exists(), "EraElectionStatus storage item not found!");
-			Ok(())
-		}
-
-		/// Migrate storage to v6.
-		pub fn migrate<T: Config>() -> Weight {
-			log!(info, "Migrating staking to Releases::V6_0_0");
-
-			SnapshotValidators::kill();
-			SnapshotNominators::kill();
-			QueuedElected::kill();
-			QueuedScore::kill();
-			EraElectionStatus::kill();
-			IsCurrentSessionFinal::kill();
 
-			StorageVersion::put(Releases::V6_0_0);
-			log!(info, "Done.");
-			T::DbWeight::get().writes(6 + 1)
+			// all voters are inserted sequentially.
+			assert_eq!(
+				T::SortedListProvider::count(),
+				CounterForNominators::<T>::get(),
+				"not all genesis stakers were inserted into sorted list provider, something is wrong."
+			);
 		}
 	}
-}
 
-decl_event!(
-	pub enum Event<T> where Balance = BalanceOf<T>, <T as frame_system::Config>::AccountId {
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
 		/// The era payout has been set; the first balance is the validator-payout; the second is
 		/// the remainder from the maximum amount of reward.
 		/// \[era_index, validator_payout, remainder\]
-		EraPayout(EraIndex, Balance, Balance),
+		EraPayout(EraIndex, BalanceOf<T>, BalanceOf<T>),
 		/// The staker has been rewarded by this amount. \[stash, amount\]
-		Reward(AccountId, Balance),
+		Reward(T::AccountId, BalanceOf<T>),
 		/// One validator (and its nominators) has been slashed by the given amount.
 		/// \[validator, amount\]
-		Slash(AccountId, Balance),
+		Slash(T::AccountId, BalanceOf<T>),
 		/// An old slashing report from a prior era was discarded because it could
 		/// not be processed. \[session_index\]
 		OldSlashingReportDiscarded(SessionIndex),
@@ -1095,20 +1268,19 @@ decl_event!(
 		///
 		/// NOTE: This event is only emitted when funds are bonded via a dispatchable. Notably,
 		/// it will not be emitted for staking rewards when they are added to stake.
-		Bonded(AccountId, Balance),
+		Bonded(T::AccountId, BalanceOf<T>),
 		/// An account has unbonded this amount. \[stash, amount\]
-		Unbonded(AccountId, Balance),
+		Unbonded(T::AccountId, BalanceOf<T>),
 		/// An account has called `withdraw_unbonded` and removed unbonding chunks worth `Balance`
 		/// from the unlocking queue. \[stash, amount\]
-		Withdrawn(AccountId, Balance),
+		Withdrawn(T::AccountId, BalanceOf<T>),
 		/// A nominator has been kicked from a validator. \[nominator, stash\]
-		Kicked(AccountId, AccountId),
+		Kicked(T::AccountId, T::AccountId),
 	}
-);
 
-decl_error! {
 	/// Error for the staking module.
-	pub enum Error for Module<T: Config> {
+	#[pallet::error]
+	pub enum Error<T> {
 		/// Not a controller account.
 		NotController,
 		/// Not a stash account.
@@ -1149,58 +1321,53 @@ decl_error! {
 		TooManyTargets,
 		/// A nomination target was supplied that was blocked or otherwise not a validator.
 		BadTarget,
+		/// The user has enough bond and thus cannot be chilled forcefully by an external person.
+		CannotChillOther,
+		/// There are too many nominators in the system. Governance needs to adjust the staking
+		/// settings to keep things safe for the runtime.
+		TooManyNominators,
+		/// There are too many validators in the system. Governance needs to adjust the staking
+		/// settings to keep things safe for the runtime.
+		TooManyValidators,
+		/// Cannot have a validator or nominator role, with value less than the minimum defined by
+		/// governance (see `MinValidatorBond` and `MinNominatorBond`). If unbonding is the
+		/// intention, `chill` first to remove one's role as validator/nominator.
+		InsufficientBond,
 	}
-}
-
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		/// Number of sessions per era.
-		const SessionsPerEra: SessionIndex = T::SessionsPerEra::get();
-
-		/// Number of eras that staked funds must remain bonded for.
-		const BondingDuration: EraIndex = T::BondingDuration::get();
-
-		/// Number of eras that slashes are deferred by, after computation.
-		///
-		/// This should be less than the bonding duration.
-		/// Set to 0 if slashes should be applied immediately, without opportunity for
-		/// intervention.
-		const SlashDeferDuration: EraIndex = T::SlashDeferDuration::get();
-
-		/// The maximum number of nominators rewarded for each validator.
-		///
-		/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can claim
-		/// their reward. This used to limit the i/o cost for the nominator payout.
-		const MaxNominatorRewardedPerValidator: u32 = T::MaxNominatorRewardedPerValidator::get();
-
-		/// Maximum number of nominations per nominator.
-		const MaxNominations: u32 = T::MAX_NOMINATIONS;
-
-		type Error = Error<T>;
-
-		fn deposit_event() = default;
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_runtime_upgrade() -> Weight {
-			if StorageVersion::get() == Releases::V5_0_0 {
-				migrations::v6::migrate::<T>()
-			} else {
-				T::DbWeight::get().reads(1)
+			// Each step reads the version the previous one left behind, so a single upgrade
+			// brings the storage all the way to the latest release.
+			let mut weight = T::DbWeight::get().reads(1);
+			if StorageVersion::<T>::get() == Releases::V5_0_0 {
+				weight = weight.saturating_add(migrations::v6::migrate::<T>());
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			if StorageVersion::<T>::get() == Releases::V6_0_0 {
+				weight = weight.saturating_add(migrations::v7::migrate::<T>());
 			}
+			weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			if StorageVersion::<T>::get() == Releases::V7_0_0 {
+				weight = weight.saturating_add(migrations::v8::migrate::<T>());
+			}
+			weight
 		}
 
-		fn on_initialize(_now: T::BlockNumber) -> Weight {
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
 			// just return the weight of the on_finalize.
 			T::DbWeight::get().reads(1)
 		}
 
-		fn on_finalize() {
+		fn on_finalize(_n: BlockNumberFor<T>) {
 			// Set the start of the first era.
 			if let Some(mut active_era) = Self::active_era() {
 				if active_era.start.is_none() {
 					let now_as_millis_u64 = T::UnixTime::now().as_millis().saturated_into::<u64>();
 					active_era.start = Some(now_as_millis_u64);
 					// This write only ever happens once, we don't include it in the weight in general
-					ActiveEra::put(active_era);
+					ActiveEra::<T>::put(active_era);
 				}
 			}
 			// `on_finalize` weight is tracked in `on_initialize`
@@ -1216,7 +1383,10 @@ decl_module! {
 				)
 			);
 		}
+	}
 
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
 		/// Take the origin account as a stash and lock up `value` of its balance. `controller` will
 		/// be the account that controls it.
 		///
@@ -1239,12 +1409,13 @@ decl_module! {
 		/// - Read: Bonded, Ledger, [Origin Account], Current Era, History Depth, Locks
 		/// - Write: Bonded, Payee, [Origin Account], Locks, Ledger
 		/// # </weight>
-		#[weight = T::WeightInfo::bond()]
-		pub fn bond(origin,
+		#[pallet::weight(T::WeightInfo::bond())]
+		pub fn bond(
+			origin: OriginFor<T>,
 			controller: <T::Lookup as StaticLookup>::Source,
-			#[compact] value: BalanceOf<T>,
+			#[pallet::compact] value: BalanceOf<T>,
 			payee: RewardDestination<T::AccountId>,
-		) {
+		) -> DispatchResult {
 			let stash = ensure_signed(origin)?;
 
 			if <Bonded<T>>::contains_key(&stash) {
@@ -1269,13 +1440,13 @@ decl_module! {
 			<Bonded<T>>::insert(&stash, &controller);
 			<Payee<T>>::insert(&stash, payee);
 
-			let current_era = CurrentEra::get().unwrap_or(0);
+			let current_era = CurrentEra::<T>::get().unwrap_or(0);
 			let history_depth = Self::history_depth();
 			let last_reward_era = current_era.saturating_sub(history_depth);
 
 			let stash_balance = T::Currency::free_balance(&stash);
 			let value = value.min(stash_balance);
-			Self::deposit_event(RawEvent::Bonded(stash.clone(), value));
+			Self::deposit_event(Event::<T>::Bonded(stash.clone(), value));
 			let item = StakingLedger {
 				stash,
 				total: value,
@@ -1284,6 +1455,7 @@ decl_module! {
 				claimed_rewards: (last_reward_era..current_era).collect(),
 			};
 			Self::update_ledger(&controller, &item);
+			Ok(())
 		}
 
 		/// Add some extra amount that have appeared in the stash `free_balance` into the balance up
@@ -1307,8 +1479,8 @@ decl_module! {
 		/// - Read: Era Election Status, Bonded, Ledger, [Origin Account], Locks
 		/// - Write: [Origin Account], Locks, Ledger
 		/// # </weight>
-		#[weight = T::WeightInfo::bond_extra()]
-		fn bond_extra(origin, #[compact] max_additional: BalanceOf<T>) {
+		#[pallet::weight(T::WeightInfo::bond_extra())]
+		pub fn bond_extra(origin: OriginFor<T>, #[pallet::compact] max_additional: BalanceOf<T>) -> DispatchResult {
 			let stash = ensure_signed(origin)?;
 
 			let controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
@@ -1322,9 +1494,15 @@ decl_module! {
 				// last check: the new active amount of ledger must be more than ED.
 				ensure!(ledger.active >= T::Currency::minimum_balance(), Error::<T>::InsufficientValue);
 
-				Self::deposit_event(RawEvent::Bonded(stash, extra));
+				Self::deposit_event(Event::<T>::Bonded(stash.clone(), extra));
 				Self::update_ledger(&controller, &ledger);
+				// update this staker in the sorted list, if they exist in it.
+				if T::SortedListProvider::contains(&stash) {
+					T::SortedListProvider::on_update(&stash, Self::weight_of(&stash));
+					debug_assert_eq!(T::SortedListProvider::sanity_check(), Ok(()));
+				}
 			}
+			Ok(())
 		}
 
 		/// Schedule a portion of the stash to be unlocked ready for transfer out after the bond
@@ -1356,11 +1534,12 @@ decl_module! {
 		/// ----------
 		/// Weight: O(1)
 		/// DB Weight:
-		/// - Read: EraElectionStatus, Ledger, CurrentEra, Locks, BalanceOf Stash,
+		/// - Read: EraElectionStatus, Ledger, Nominators, Validators, MinNominatorBond /
+		///   MinValidatorBond, CurrentEra, Locks, BalanceOf Stash,
 		/// - Write: Locks, Ledger, BalanceOf Stash,
 		/// </weight>
-		#[weight = T::WeightInfo::unbond()]
-		fn unbond(origin, #[compact] value: BalanceOf<T>) {
+		#[pallet::weight(T::WeightInfo::unbond())]
+		pub fn unbond(origin: OriginFor<T>, #[pallet::compact] value: BalanceOf<T>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			ensure!(
@@ -1379,12 +1558,31 @@ decl_module! {
 					ledger.active = Zero::zero();
 				}
 
+				let min_active_bond = if Nominators::<T>::contains_key(&ledger.stash) {
+					MinNominatorBond::<T>::get()
+				} else if Validators::<T>::contains_key(&ledger.stash) {
+					MinValidatorBond::<T>::get()
+				} else {
+					Zero::zero()
+				};
+
+				// Make sure that the user maintains enough active bond for their role.
+				// If a user runs into this error, they should chill first.
+				ensure!(ledger.active >= min_active_bond, Error::<T>::InsufficientBond);
+
 				// Note: in case there is no current era it is fine to bond one era more.
 				let era = Self::current_era().unwrap_or(0) + T::BondingDuration::get();
 				ledger.unlocking.push(UnlockChunk { value, era });
 				Self::update_ledger(&controller, &ledger);
-				Self::deposit_event(RawEvent::Unbonded(ledger.stash, value));
+
+				// update this staker in the sorted list, if they exist in it.
+				if T::SortedListProvider::contains(&ledger.stash) {
+					T::SortedListProvider::on_update(&ledger.stash, Self::weight_of(&ledger.stash));
+				}
+
+				Self::deposit_event(Event::<T>::Unbonded(ledger.stash, value));
 			}
+			Ok(())
 		}
 
 		/// Remove any unlocked chunks from the `unlocking` queue from our management.
@@ -1418,8 +1616,8 @@ decl_module! {
 		/// - Writes Each: SpanSlash * S
 		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
 		/// # </weight>
-		#[weight = T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans)]
-		fn withdraw_unbonded(origin, num_slashing_spans: u32) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans))]
+		pub fn withdraw_unbonded(origin: OriginFor<T>, num_slashing_spans: u32) -> DispatchResultWithPostInfo {
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let (stash, old_total) = (ledger.stash.clone(), ledger.total);
@@ -1449,7 +1647,7 @@ decl_module! {
 			if ledger.total < old_total {
 				// Already checked that this won't overflow by entry condition.
 				let value = old_total - ledger.total;
-				Self::deposit_event(RawEvent::Withdrawn(stash, value));
+				Self::deposit_event(Event::<T>::Withdrawn(stash, value));
 			}
 
 			Ok(post_info_weight.into())
@@ -1472,13 +1670,29 @@ decl_module! {
 		/// - Read: Era Election Status, Ledger
 		/// - Write: Nominators, Validators
 		/// # </weight>
-		#[weight = T::WeightInfo::validate()]
-		pub fn validate(origin, prefs: ValidatorPrefs) {
+		#[pallet::weight(T::WeightInfo::validate())]
+		pub fn validate(origin: OriginFor<T>, prefs: ValidatorPrefs) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			ensure!(ledger.active >= MinValidatorBond::<T>::get(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
-			<Nominators<T>>::remove(stash);
-			<Validators<T>>::insert(stash, prefs);
+
+			// Only check limits if they are not already a validator.
+			if !<Validators<T>>::contains_key(stash) {
+				// If this error is reached, we need to adjust the `MinValidatorBond` and start
+				// calling `chill_other`. Until then, we explicitly block new validators to protect
+				// the runtime.
+				if let Some(max_validators) = MaxValidatorsCount::<T>::get() {
+					ensure!(
+						CounterForValidators::<T>::get() < max_validators,
+						Error::<T>::TooManyValidators,
+					);
+				}
+			}
+
+			Self::do_remove_nominator(stash);
+			Self::do_add_validator(stash, prefs);
+			Ok(())
 		}
 
 		/// Declare the desire to nominate `targets` for the origin controller.
@@ -1500,11 +1714,26 @@ decl_module! {
 		/// - Reads: Era Election Status, Ledger, Current Era
 		/// - Writes: Validators, Nominators
 		/// # </weight>
-		#[weight = T::WeightInfo::nominate(targets.len() as u32)]
-		pub fn nominate(origin, targets: Vec<<T::Lookup as StaticLookup>::Source>) {
+		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32))]
+		pub fn nominate(origin: OriginFor<T>, targets: Vec<<T::Lookup as StaticLookup>::Source>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			ensure!(ledger.active >= MinNominatorBond::<T>::get(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
+
+			// Only check limits if they are not already a nominator.
+			if !<Nominators<T>>::contains_key(stash) {
+				// If this error is reached, we need to adjust the `MinNominatorBond` and start
+				// calling `chill_other`. Until then, we explicitly block new nominators to protect
+				// the runtime.
+				if let Some(max_nominators) = MaxNominatorsCount::<T>::get() {
+					ensure!(
+						CounterForNominators::<T>::get() < max_nominators,
+						Error::<T>::TooManyNominators,
+					);
+				}
+			}
+
 			ensure!(!targets.is_empty(), Error::<T>::EmptyTargets);
 			ensure!(targets.len() <= T::MAX_NOMINATIONS as usize, Error::<T>::TooManyTargets);
 
@@ -1526,8 +1755,9 @@ decl_module! {
 				suppressed: false,
 			};
 
-			<Validators<T>>::remove(stash);
-			<Nominators<T>>::insert(stash, &nominations);
+			Self::do_remove_validator(stash);
+			Self::do_add_nominator(stash, nominations);
+			Ok(())
 		}
 
 		/// Declare no desire to either validate or nominate.
@@ -1547,11 +1777,12 @@ decl_module! {
 		/// - Read: EraElectionStatus, Ledger
 		/// - Write: Validators, Nominators
 		/// # </weight>
-		#[weight = T::WeightInfo::chill()]
-		fn chill(origin) {
+		#[pallet::weight(T::WeightInfo::chill())]
+		pub fn chill(origin: OriginFor<T>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			Self::chill_stash(&ledger.stash);
+			Ok(())
 		}
 
 		/// (Re-)set the payment target for a controller.
@@ -1570,12 +1801,13 @@ decl_module! {
 		///     - Read: Ledger
 		///     - Write: Payee
 		/// # </weight>
-		#[weight = T::WeightInfo::set_payee()]
-		fn set_payee(origin, payee: RewardDestination<T::AccountId>) {
+		#[pallet::weight(T::WeightInfo::set_payee())]
+		pub fn set_payee(origin: OriginFor<T>, payee: RewardDestination<T::AccountId>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let stash = &ledger.stash;
 			<Payee<T>>::insert(stash, payee);
+			Ok(())
 		}
 
 		/// (Re-)set the controller of a stash.
@@ -1594,8 +1826,8 @@ decl_module! {
 		/// - Read: Bonded, Ledger New Controller, Ledger Old Controller
 		/// - Write: Bonded, Ledger New Controller, Ledger Old Controller
 		/// # </weight>
-		#[weight = T::WeightInfo::set_controller()]
-		fn set_controller(origin, controller: <T::Lookup as StaticLookup>::Source) {
+		#[pallet::weight(T::WeightInfo::set_controller())]
+		pub fn set_controller(origin: OriginFor<T>, controller: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			let stash = ensure_signed(origin)?;
 			let old_controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
 			let controller = T::Lookup::lookup(controller)?;
@@ -1608,6 +1840,7 @@ decl_module! {
 					<Ledger<T>>::insert(&controller, l);
 				}
 			}
+			Ok(())
 		}
 
 		/// Sets the ideal number of validators.
@@ -1618,10 +1851,11 @@ decl_module! {
 		/// Weight: O(1)
 		/// Write: Validator Count
 		/// # </weight>
-		#[weight = T::WeightInfo::set_validator_count()]
-		fn set_validator_count(origin, #[compact] new: u32) {
+		#[pallet::weight(T::WeightInfo::set_validator_count())]
+		pub fn set_validator_count(origin: OriginFor<T>, #[pallet::compact] new: u32) -> DispatchResult {
 			ensure_root(origin)?;
-			ValidatorCount::put(new);
+			ValidatorCount::<T>::put(new);
+			Ok(())
 		}
 
 		/// Increments the ideal number of validators.
@@ -1631,10 +1865,11 @@ decl_module! {
 		/// # <weight>
 		/// Same as [`set_validator_count`].
 		/// # </weight>
-		#[weight = T::WeightInfo::set_validator_count()]
-		fn increase_validator_count(origin, #[compact] additional: u32) {
+		#[pallet::weight(T::WeightInfo::set_validator_count())]
+		pub fn increase_validator_count(origin: OriginFor<T>, #[pallet::compact] additional: u32) -> DispatchResult {
 			ensure_root(origin)?;
-			ValidatorCount::mutate(|n| *n += additional);
+			ValidatorCount::<T>::mutate(|n| *n += additional);
+			Ok(())
 		}
 
 		/// Scale up the ideal number of validators by a factor.
@@ -1644,10 +1879,11 @@ decl_module! {
 		/// # <weight>
 		/// Same as [`set_validator_count`].
 		/// # </weight>
-		#[weight = T::WeightInfo::set_validator_count()]
-		fn scale_validator_count(origin, factor: Percent) {
+		#[pallet::weight(T::WeightInfo::set_validator_count())]
+		pub fn scale_validator_count(origin: OriginFor<T>, factor: Percent) -> DispatchResult {
 			ensure_root(origin)?;
-			ValidatorCount::mutate(|n| *n += factor * *n);
+			ValidatorCount::<T>::mutate(|n| *n += factor * *n);
+			Ok(())
 		}
 
 		/// Force there to be no new eras indefinitely.
@@ -1659,10 +1895,11 @@ decl_module! {
 		/// - Weight: O(1)
 		/// - Write: ForceEra
 		/// # </weight>
-		#[weight = T::WeightInfo::force_no_eras()]
-		fn force_no_eras(origin) {
+		#[pallet::weight(T::WeightInfo::force_no_eras())]
+		pub fn force_no_eras(origin: OriginFor<T>) -> DispatchResult {
 			ensure_root(origin)?;
-			ForceEra::put(Forcing::ForceNone);
+			ForceEra::<T>::put(Forcing::ForceNone);
+			Ok(())
 		}
 
 		/// Force there to be a new era at the end of the next session. After this, it will be
@@ -1675,10 +1912,11 @@ decl_module! {
 		/// - Weight: O(1)
 		/// - Write ForceEra
 		/// # </weight>
-		#[weight = T::WeightInfo::force_new_era()]
-		fn force_new_era(origin) {
+		#[pallet::weight(T::WeightInfo::force_new_era())]
+		pub fn force_new_era(origin: OriginFor<T>) -> DispatchResult {
 			ensure_root(origin)?;
-			ForceEra::put(Forcing::ForceNew);
+			ForceEra::<T>::put(Forcing::ForceNew);
+			Ok(())
 		}
 
 		/// Set the validators who cannot be slashed (if any).
@@ -1689,10 +1927,11 @@ decl_module! {
 		/// - O(V)
 		/// - Write: Invulnerables
 		/// # </weight>
-		#[weight = T::WeightInfo::set_invulnerables(invulnerables.len() as u32)]
-		fn set_invulnerables(origin, invulnerables: Vec<T::AccountId>) {
+		#[pallet::weight(T::WeightInfo::set_invulnerables(invulnerables.len() as u32))]
+		pub fn set_invulnerables(origin: OriginFor<T>, invulnerables: Vec<T::AccountId>) -> DispatchResult {
 			ensure_root(origin)?;
 			<Invulnerables<T>>::put(invulnerables);
+			Ok(())
 		}
 
 		/// Force a current staker to become completely unstaked, immediately.
@@ -1705,8 +1944,8 @@ decl_module! {
 		/// Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, Account, Locks
 		/// Writes Each: SpanSlash * S
 		/// # </weight>
-		#[weight = T::WeightInfo::force_unstake(*num_slashing_spans)]
-		fn force_unstake(origin, stash: T::AccountId, num_slashing_spans: u32) {
+		#[pallet::weight(T::WeightInfo::force_unstake(*num_slashing_spans))]
+		pub fn force_unstake(origin: OriginFor<T>, stash: T::AccountId, num_slashing_spans: u32) -> DispatchResult {
 			ensure_root(origin)?;
 
 			// remove all staking-related information.
@@ -1714,6 +1953,7 @@ decl_module! {
 
 			// remove the lock.
 			T::Currency::remove_lock(STAKING_ID, &stash);
+			Ok(())
 		}
 
 		/// Force there to be a new era at the end of sessions indefinitely.
@@ -1724,10 +1964,11 @@ decl_module! {
 		/// - Weight: O(1)
 		/// - Write: ForceEra
 		/// # </weight>
-		#[weight = T::WeightInfo::force_new_era_always()]
-		fn force_new_era_always(origin) {
+		#[pallet::weight(T::WeightInfo::force_new_era_always())]
+		pub fn force_new_era_always(origin: OriginFor<T>) -> DispatchResult {
 			ensure_root(origin)?;
-			ForceEra::put(Forcing::ForceAlways);
+			ForceEra::<T>::put(Forcing::ForceAlways);
+			Ok(())
 		}
 
 		/// Cancel enactment of a deferred slash.
@@ -1743,14 +1984,14 @@ decl_module! {
 		/// - Read: Unapplied Slashes
 		/// - Write: Unapplied Slashes
 		/// # </weight>
-		#[weight = T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32)]
-		fn cancel_deferred_slash(origin, era: EraIndex, slash_indices: Vec<u32>) {
+		#[pallet::weight(T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32))]
+		pub fn cancel_deferred_slash(origin: OriginFor<T>, era: EraIndex, slash_indices: Vec<u32>) -> DispatchResult {
 			T::SlashCancelOrigin::ensure_origin(origin)?;
 
 			ensure!(!slash_indices.is_empty(), Error::<T>::EmptyTargets);
 			ensure!(is_sorted_and_unique(&slash_indices), Error::<T>::NotSortedAndUnique);
 
-			let mut unapplied = <Self as Store>::UnappliedSlashes::get(&era);
+			let mut unapplied = UnappliedSlashes::<T>::get(&era);
 			let last_item = slash_indices[slash_indices.len() - 1];
 			ensure!((last_item as usize) < unapplied.len(), Error::<T>::InvalidSlashIndex);
 
@@ -1759,7 +2000,8 @@ decl_module! {
 				unapplied.remove(index);
 			}
 
-			<Self as Store>::UnappliedSlashes::insert(&era, &unapplied);
+			UnappliedSlashes::<T>::insert(&era, &unapplied);
+			Ok(())
 		}
 
 		/// Pay out all the stakers behind a single validator for a single era.
@@ -1790,8 +2032,9 @@ decl_module! {
 		///   NOTE: weights are assuming that payouts are made to alive stash account (Staked).
 		///   Paying even a dead controller is cheaper weight-wise. We don't do any refunds here.
 		/// # </weight>
-		#[weight = T::WeightInfo::payout_stakers_alive_staked(T::MaxNominatorRewardedPerValidator::get())]
-		fn payout_stakers(origin, validator_stash: T::AccountId, era: EraIndex) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxNominatorRewardedPerValidator::get()))]
+		pub fn payout_stakers(
+			origin: OriginFor<T>, validator_stash: T::AccountId, era: EraIndex) -> DispatchResultWithPostInfo {
 			ensure_signed(origin)?;
 			Self::do_payout_stakers(validator_stash, era)
 		}
@@ -1810,8 +2053,8 @@ decl_module! {
 		///     - Reads: EraElectionStatus, Ledger, Locks, [Origin Account]
 		///     - Writes: [Origin Account], Locks, Ledger
 		/// # </weight>
-		#[weight = T::WeightInfo::rebond(MAX_UNLOCKING_CHUNKS as u32)]
-		fn rebond(origin, #[compact] value: BalanceOf<T>) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::rebond(MAX_UNLOCKING_CHUNKS as u32))]
+		pub fn rebond(origin: OriginFor<T>, #[pallet::compact] value: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			ensure!(!ledger.unlocking.is_empty(), Error::<T>::NoUnlockChunk);
@@ -1821,6 +2064,10 @@ decl_module! {
 			ensure!(ledger.active >= T::Currency::minimum_balance(), Error::<T>::InsufficientValue);
 
 			Self::update_ledger(&controller, &ledger);
+			if T::SortedListProvider::contains(&ledger.stash) {
+				T::SortedListProvider::on_update(&ledger.stash, Self::weight_of(&ledger.stash));
+			}
+
 			Ok(Some(
 				35 * WEIGHT_PER_MICROS
 				+ 50 * WEIGHT_PER_NANOS * (ledger.unlocking.len() as Weight)
@@ -1849,14 +2096,15 @@ decl_module! {
 		///     - Clear Prefix Each: Era Stakers, EraStakersClipped, ErasValidatorPrefs
 		///     - Writes Each: ErasValidatorReward, ErasRewardPoints, ErasTotalStake, ErasStartSessionIndex
 		/// # </weight>
-		#[weight = T::WeightInfo::set_history_depth(*_era_items_deleted)]
-		fn set_history_depth(origin,
-			#[compact] new_history_depth: EraIndex,
-			#[compact] _era_items_deleted: u32,
-		) {
+		#[pallet::weight(T::WeightInfo::set_history_depth(*_era_items_deleted))]
+		pub fn set_history_depth(
+			origin: OriginFor<T>,
+			#[pallet::compact] new_history_depth: EraIndex,
+			#[pallet::compact] _era_items_deleted: u32,
+		) -> DispatchResult {
 			ensure_root(origin)?;
 			if let Some(current_era) = Self::current_era() {
-				HistoryDepth::mutate(|history_depth| {
+				HistoryDepth::<T>::mutate(|history_depth| {
 					let last_kept = current_era.checked_sub(*history_depth).unwrap_or(0);
 					let new_last_kept = current_era.checked_sub(new_history_depth).unwrap_or(0);
 					for era_index in last_kept..new_last_kept {
@@ -1865,6 +2113,7 @@ decl_module! {
 					*history_depth = new_history_depth
 				})
 			}
+			Ok(())
 		}
 
 		/// Remove all data structure concerning a staker/stash once its balance is at the minimum.
@@ -1882,12 +2131,13 @@ decl_module! {
 		/// - Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, Stash Account, Locks
 		/// - Writes Each: SpanSlash * S
 		/// # </weight>
-		#[weight = T::WeightInfo::reap_stash(*num_slashing_spans)]
-		fn reap_stash(_origin, stash: T::AccountId, num_slashing_spans: u32) {
+		#[pallet::weight(T::WeightInfo::reap_stash(*num_slashing_spans))]
+		pub fn reap_stash(_origin: OriginFor<T>, stash: T::AccountId, num_slashing_spans: u32) -> DispatchResult {
 			let at_minimum = T::Currency::total_balance(&stash) == T::Currency::minimum_balance();
 			ensure!(at_minimum, Error::<T>::FundedTarget);
 			Self::kill_stash(&stash, num_slashing_spans)?;
 			T::Currency::remove_lock(STAKING_ID, &stash);
+			Ok(())
 		}
 
 		/// Remove the given nominations from the calling validator.
@@ -1903,8 +2153,8 @@ decl_module! {
 		///
 		/// Note: Making this call only makes sense if you first set the validator preferences to
 		/// block any further nominations.
-		#[weight = T::WeightInfo::kick(who.len() as u32)]
-		pub fn kick(origin, who: Vec<<T::Lookup as StaticLookup>::Source>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::kick(who.len() as u32))]
+		pub fn kick(origin: OriginFor<T>, who: Vec<<T::Lookup as StaticLookup>::Source>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
 			let stash = &ledger.stash;
@@ -1917,17 +2167,229 @@ decl_module! {
 				Nominators::<T>::mutate(&nom_stash, |maybe_nom| if let Some(ref mut nom) = maybe_nom {
 					if let Some(pos) = nom.targets.iter().position(|v| v == stash) {
 						nom.targets.swap_remove(pos);
-						Self::deposit_event(RawEvent::Kicked(nom_stash.clone(), stash.clone()));
+						Self::deposit_event(Event::<T>::Kicked(nom_stash.clone(), stash.clone()));
 					}
 				});
 			}
 
 			Ok(())
 		}
+
+		/// Update the various staking limits this pallet.
+		///
+		/// * `min_nominator_bond`: The minimum active bond needed to be a nominator.
+		/// * `min_validator_bond`: The minimum active bond needed to be a validator.
+		/// * `max_nominator_count`: The max number of users who can be a nominator at once. When
+		///   set to `None`, no limit is enforced.
+		/// * `max_validator_count`: The max number of users who can be a validator at once. When
+		///   set to `None`, no limit is enforced.
+		/// * `threshold`: The ratio of `Counter*` to `Max*Count` at which `chill_other` becomes
+		///   callable. When set to `None`, `chill_other` can never be used on others.
+		///
+		/// Origin must be Root to call this function.
+		///
+		/// NOTE: Existing nominators and validators will not be affected by this update. To kick
+		/// people under the new limits, `chill_other` should be called.
+		#[pallet::weight(T::WeightInfo::set_staking_limits())]
+		pub fn set_staking_limits(
+			origin: OriginFor<T>,
+			min_nominator_bond: BalanceOf<T>,
+			min_validator_bond: BalanceOf<T>,
+			max_nominator_count: Option<u32>,
+			max_validator_count: Option<u32>,
+			threshold: Option<Percent>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			MinNominatorBond::<T>::set(min_nominator_bond);
+			MinValidatorBond::<T>::set(min_validator_bond);
+			MaxNominatorsCount::<T>::set(max_nominator_count);
+			MaxValidatorsCount::<T>::set(max_validator_count);
+			ChillThreshold::<T>::set(threshold);
+			Ok(())
+		}
+
+		/// Declare a `controller` to stop participating as either a validator or nominator.
+		///
+		/// Effects will be felt at the beginning of the next era.
+		///
+		/// The dispatch origin for this call must be _Signed_, but can be called by anyone.
+		///
+		/// If the caller is the same as the controller being targeted, then no further checks are
+		/// enforced, and this function behaves just like `chill`.
+		///
+		/// If the caller is different than the controller being targeted, the following conditions
+		/// must be met:
+		/// * A `ChillThreshold` must be set and checked which defines how close to the max
+		///   nominators or validators we must reach before users can start chilling one-another.
+		/// * A `MaxNominatorsCount` and `MaxValidatorsCount` must be set which is used to determine
+		///   how close we are to the threshold.
+		/// * A `MinNominatorBond` and `MinValidatorBond` must be set and checked, which determines
+		///   if this is a person that should be chilled because they have not met the threshold
+		///   bond required.
+		///
+		/// This can be helpful if bond requirements are updated, and we need to remove old users
+		/// who do not satisfy these requirements.
+		#[pallet::weight(T::WeightInfo::chill_other())]
+		pub fn chill_other(origin: OriginFor<T>, controller: T::AccountId) -> DispatchResult {
+			// Anyone can call this function.
+			let caller = ensure_signed(origin)?;
+			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let stash = ledger.stash;
+
+			// In order for one user to chill another user, the following conditions must be met:
+			// * A `ChillThreshold` is set which defines how close to the max nominators or
+			//   validators we must reach before users can start chilling one-another.
+			// * A `MaxNominatorsCount` and `MaxValidatorsCount` which is used to determine how
+			//   close we are to the threshold.
+			// * A `MinNominatorBond` and `MinValidatorBond` which is the final condition checked
+			//   to determine this is a person that should be chilled because they have not met
+			//   the threshold bond required.
+			//
+			// Otherwise, if caller is the same as the controller, this is just like `chill`.
+			if caller != controller {
+				let threshold = ChillThreshold::<T>::get().ok_or(Error::<T>::CannotChillOther)?;
+				let min_active_bond = if <Nominators<T>>::contains_key(&stash) {
+					let max_nominator_count =
+						MaxNominatorsCount::<T>::get().ok_or(Error::<T>::CannotChillOther)?;
+					let current_nominator_count = CounterForNominators::<T>::get();
+					ensure!(
+						threshold * max_nominator_count < current_nominator_count,
+						Error::<T>::CannotChillOther,
+					);
+					MinNominatorBond::<T>::get()
+				} else if <Validators<T>>::contains_key(&stash) {
+					let max_validator_count =
+						MaxValidatorsCount::<T>::get().ok_or(Error::<T>::CannotChillOther)?;
+					let current_validator_count = CounterForValidators::<T>::get();
+					ensure!(
+						threshold * max_validator_count < current_validator_count,
+						Error::<T>::CannotChillOther,
+					);
+					MinValidatorBond::<T>::get()
+				} else {
+					Zero::zero()
+				};
+
+				ensure!(ledger.active < min_active_bond, Error::<T>::CannotChillOther);
+			}
+
+			Self::chill_stash(&stash);
+			Ok(())
+		}
+	}
+}
+
+pub mod migrations {
+	use super::*;
+
+	pub mod v8 {
+		use super::*;
+
+		/// check to execute prior to migration.
+		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+			assert!(CounterForValidators::<T>::get().is_zero(), "CounterForValidators already set.");
+			assert!(CounterForNominators::<T>::get().is_zero(), "CounterForNominators already set.");
+			assert!(StorageVersion::<T>::get() == Releases::V7_0_0, "must upgrade linearly");
+			Ok(())
+		}
+
+		/// Migrate storage to v8: initialize the validator and nominator counters.
+		pub fn migrate<T: Config>() -> Weight {
+			log!(info, "Migrating staking to Releases::V8_0_0");
+
+			let validator_count = Validators::<T>::iter().count() as u32;
+			let nominator_count = Nominators::<T>::iter().count() as u32;
+
+			CounterForValidators::<T>::put(validator_count);
+			CounterForNominators::<T>::put(nominator_count);
+
+			StorageVersion::<T>::put(Releases::V8_0_0);
+			log!(info, "Completed staking migration to Releases::V8_0_0");
+
+			T::DbWeight::get().reads_writes(
+				validator_count.saturating_add(nominator_count).into(),
+				3,
+			)
+		}
+	}
+
+	pub mod v7 {
+		use super::*;
+
+		/// check to execute prior to migration.
+		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+			assert!(
+				StorageVersion::<T>::get() == Releases::V6_0_0,
+				"must upgrade linearly",
+			);
+			assert!(
+				T::SortedListProvider::iter().count() == 0,
+				"voter list already exists",
+			);
+			Ok(())
+		}
+
+		/// Migrate storage to v7: populate the `SortedListProvider` with all current nominators.
+		pub fn migrate<T: Config>() -> Weight {
+			log!(info, "Migrating staking to Releases::V7_0_0");
+
+			let migrated = T::SortedListProvider::regenerate(
+				Nominators::<T>::iter().map(|(id, _)| id),
+				Pallet::<T>::slashable_balance_of_fn(),
+			);
+			debug_assert_eq!(T::SortedListProvider::sanity_check(), Ok(()));
+
+			StorageVersion::<T>::put(Releases::V7_0_0);
+			log!(info, "Done. Inserted {} nominators into the sorted list.", migrated);
+			// regenerating the list touches every nominator, so conservatively consume a full block.
+			T::BlockWeights::get().max_block
+		}
+	}
+
+	pub mod v6 {
+		use super::*;
+		use frame_support::{traits::Get, weights::Weight, generate_storage_alias};
+
+		// NOTE: value type doesn't matter, we just set it to () here.
+		generate_storage_alias!(Staking, SnapshotValidators => Value<()>);
+		generate_storage_alias!(Staking, SnapshotNominators => Value<()>);
+		generate_storage_alias!(Staking, QueuedElected => Value<()>);
+		generate_storage_alias!(Staking, QueuedScore => Value<()>);
+		generate_storage_alias!(Staking, EraElectionStatus => Value<()>);
+		generate_storage_alias!(Staking, IsCurrentSessionFinal => Value<()>);
+
+		/// check to execute prior to migration.
+		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+			// these may or may not exist.
+			log!(info, "SnapshotValidators.exits()? {:?}", SnapshotValidators::exists());
+			log!(info, "SnapshotNominators.exits()? {:?}", SnapshotNominators::exists());
+			log!(info, "QueuedElected.exits()? {:?}", QueuedElected::exists());
+			log!(info, "QueuedScore.exits()? {:?}", QueuedScore::exists());
+			// these must exist.
+			assert!(IsCurrentSessionFinal::exists(), "IsCurrentSessionFinal storage item not found!");
+			assert!(EraElectionStatus::exists(), "EraElectionStatus storage item not found!");
+			Ok(())
+		}
+
+		/// Migrate storage to v6.
+		pub fn migrate<T: Config>() -> Weight {
+			log!(info, "Migrating staking to Releases::V6_0_0");
+
+			SnapshotValidators::kill();
+			SnapshotNominators::kill();
+			QueuedElected::kill();
+			QueuedScore::kill();
+			EraElectionStatus::kill();
+			IsCurrentSessionFinal::kill();
+
+			StorageVersion::<T>::put(Releases::V6_0_0);
+			log!(info, "Done.");
+			T::DbWeight::get().writes(6 + 1)
+		}
 	}
 }
 
-impl<T: Config> Module<T> {
+impl<T: Config> Pallet<T> {
 	/// The total balance that can be slashed from a stash account as of right now.
 	pub fn slashable_balance_of(stash: &T::AccountId) -> BalanceOf<T> {
 		// Weight note: consider making the stake accessible through stash.
@@ -1955,9 +2417,75 @@ impl<T: Config> Module<T> {
 		})
 	}
 
+	/// The vote weight of `who`, as used by the [`Config::SortedListProvider`].
+	pub fn weight_of(who: &T::AccountId) -> VoteWeight {
+		Self::slashable_balance_of_vote_weight(who, T::Currency::total_issuance())
+	}
+
+	/// Add a nominator to the `Nominators` storage map, and keep the
+	/// [`Config::SortedListProvider`] in sync.
+	///
+	/// If the nominator already exists, their nominations will be updated.
+	pub fn do_add_nominator(who: &T::AccountId, nominations: Nominations<T::AccountId>) {
+		if !<Nominators<T>>::contains_key(who) {
+			CounterForNominators::<T>::mutate(|x| x.saturating_inc());
+
+			// maybe update the sorted list. Error checking is defensive-only, this should never
+			// fail.
+			if T::SortedListProvider::on_insert(who.clone(), Self::weight_of(who)).is_err() {
+				log!(warn, "attempt to insert duplicate nominator ({:?})", who);
+				debug_assert!(false, "attempt to insert duplicate nominator");
+			}
+			debug_assert_eq!(T::SortedListProvider::sanity_check(), Ok(()));
+		}
+
+		<Nominators<T>>::insert(who, nominations);
+	}
+
+	/// Remove a nominator from the `Nominators` storage map, and keep the
+	/// [`Config::SortedListProvider`] in sync.
+	///
+	/// Returns true if `who` was removed from `Nominators`, otherwise false.
+	pub fn do_remove_nominator(who: &T::AccountId) -> bool {
+		if <Nominators<T>>::contains_key(who) {
+			<Nominators<T>>::remove(who);
+			CounterForNominators::<T>::mutate(|x| x.saturating_dec());
+			T::SortedListProvider::on_remove(who);
+			debug_assert_eq!(T::SortedListProvider::sanity_check(), Ok(()));
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Add a validator to the `Validators` storage map, and keep track of the
+	/// `CounterForValidators`.
+	///
+	/// If the validator already exists, their preferences will be updated.
+	pub fn do_add_validator(who: &T::AccountId, prefs: ValidatorPrefs) {
+		if !<Validators<T>>::contains_key(who) {
+			CounterForValidators::<T>::mutate(|x| x.saturating_inc())
+		}
+		<Validators<T>>::insert(who, prefs);
+	}
+
+	/// Remove a validator from the `Validators` storage map, and keep track of the
+	/// `CounterForValidators`.
+	///
+	/// Returns true if `who` was removed from `Validators`, otherwise false.
+	pub fn do_remove_validator(who: &T::AccountId) -> bool {
+		if <Validators<T>>::contains_key(who) {
+			<Validators<T>>::remove(who);
+			CounterForValidators::<T>::mutate(|x| x.saturating_dec());
+			true
+		} else {
+			false
+		}
+	}
+
 	fn do_payout_stakers(validator_stash: T::AccountId, era: EraIndex) -> DispatchResultWithPostInfo {
 		// Validate input data
-		let current_era = CurrentEra::get().ok_or(
+		let current_era = CurrentEra::<T>::get().ok_or(
 			Error::<T>::InvalidEraToReward.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
 		)?;
 		let history_depth = Self::history_depth();
@@ -2039,7 +2567,7 @@ impl<T: Config> Module<T> {
 			&ledger.stash,
 			validator_staking_payout + validator_commission_payout
 		) {
-			Self::deposit_event(RawEvent::Reward(ledger.stash, imbalance.peek()));
+			Self::deposit_event(Event::<T>::Reward(ledger.stash, imbalance.peek()));
 		}
 
 		// Track the number of payout ops to nominators. Note: `WeightInfo::payout_stakers_alive_staked`
@@ -2059,7 +2587,7 @@ impl<T: Config> Module<T> {
 			if let Some(imbalance) = Self::make_payout(&nominator.who, nominator_reward) {
 				// Note: this logic does not count payouts for `RewardDestination::None`.
 				nominator_payout_count += 1;
-				Self::deposit_event(RawEvent::Reward(nominator.who.clone(), imbalance.peek()));
+				Self::deposit_event(Event::<T>::Reward(nominator.who.clone(), imbalance.peek()));
 			}
 		}
 
@@ -2085,8 +2613,8 @@ impl<T: Config> Module<T> {
 
 	/// Chill a stash account.
 	fn chill_stash(stash: &T::AccountId) {
-		<Validators<T>>::remove(stash);
-		<Nominators<T>>::remove(stash);
+		Self::do_remove_validator(stash);
+		Self::do_remove_nominator(stash);
 	}
 
 	/// Actually make a payment to a staker. This uses the currency's reward function
@@ -2130,9 +2658,9 @@ impl<T: Config> Module<T> {
 			let era_length = session_index.checked_sub(current_era_start_session_index)
 				.unwrap_or(0); // Must never happen.
 
-			match ForceEra::get() {
+			match ForceEra::<T>::get() {
 				// Will set to default again, which is `NotForcing`.
-				Forcing::ForceNew => ForceEra::kill(),
+				Forcing::ForceNew => ForceEra::<T>::kill(),
 				// Short circuit to `new_era`.
 				Forcing::ForceAlways => (),
 				// Only go to `new_era` if deadline reached.
@@ -2190,7 +2718,7 @@ impl<T: Config> Module<T> {
 	/// * reset `active_era.start`,
 	/// * update `BondedEras` and apply slashes.
 	fn start_era(start_session: SessionIndex) {
-		let active_era = ActiveEra::mutate(|active_era| {
+		let active_era = ActiveEra::<T>::mutate(|active_era| {
 			let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
 			*active_era = Some(ActiveEraInfo {
 				index: new_index,
@@ -2202,7 +2730,7 @@ impl<T: Config> Module<T> {
 
 		let bonding_duration = T::BondingDuration::get();
 
-		BondedEras::mutate(|bonded| {
+		BondedEras::<T>::mutate(|bonded| {
 			bonded.push((active_era, start_session));
 
 			if active_era > bonding_duration {
@@ -2238,7 +2766,7 @@ impl<T: Config> Module<T> {
 			let issuance = T::Currency::total_issuance();
 			let (validator_payout, rest) = T::EraPayout::era_payout(staked, issuance, era_duration);
 
-			Self::deposit_event(RawEvent::EraPayout(active_era.index, validator_payout, rest));
+			Self::deposit_event(Event::<T>::EraPayout(active_era.index, validator_payout, rest));
 
 			// Set ending era reward.
 			<ErasValidatorReward<T>>::insert(&active_era.index, validator_payout);
@@ -2249,11 +2777,11 @@ impl<T: Config> Module<T> {
 	/// Plan a new era. Return the potential new staking set.
 	fn new_era(start_session_index: SessionIndex) -> Option<Vec<T::AccountId>> {
 		// Increment or set current era.
-		let current_era = CurrentEra::mutate(|s| {
+		let current_era = CurrentEra::<T>::mutate(|s| {
 			*s = Some(s.map(|s| s + 1).unwrap_or(0));
 			s.unwrap()
 		});
-		ErasStartSessionIndex::insert(&current_era, &start_session_index);
+		ErasStartSessionIndex::<T>::insert(&current_era, &start_session_index);
 
 		// Clean old era information.
 		if let Some(old_era) = current_era.checked_sub(Self::history_depth() + 1) {
@@ -2337,7 +2865,7 @@ impl<T: Config> Module<T> {
 		}
 
 		// emit event
-		Self::deposit_event(RawEvent::StakingElection);
+		Self::deposit_event(Event::<T>::StakingElection);
 
 		if current_era > 0 {
 			log!(
@@ -2403,8 +2931,8 @@ impl<T: Config> Module<T> {
 		<Ledger<T>>::remove(&controller);
 
 		<Payee<T>>::remove(stash);
-		<Validators<T>>::remove(stash);
-		<Nominators<T>>::remove(stash);
+		Self::do_remove_validator(stash);
+		Self::do_remove_nominator(stash);
 
 		system::Pallet::<T>::dec_consumers(stash);
 
@@ -2419,16 +2947,16 @@ impl<T: Config> Module<T> {
 		<ErasValidatorReward<T>>::remove(era_index);
 		<ErasRewardPoints<T>>::remove(era_index);
 		<ErasTotalStake<T>>::remove(era_index);
-		ErasStartSessionIndex::remove(era_index);
+		ErasStartSessionIndex::<T>::remove(era_index);
 	}
 
 	/// Apply previously-unapplied slashes on the beginning of a new era, after a delay.
 	fn apply_unapplied_slashes(active_era: EraIndex) {
 		let slash_defer_duration = T::SlashDeferDuration::get();
-		<Self as Store>::EarliestUnappliedSlash::mutate(|earliest| if let Some(ref mut earliest) = earliest {
+		EarliestUnappliedSlash::<T>::mutate(|earliest| if let Some(ref mut earliest) = earliest {
 			let keep_from = active_era.saturating_sub(slash_defer_duration);
 			for era in (*earliest)..keep_from {
-				let era_slashes = <Self as Store>::UnappliedSlashes::take(&era);
+				let era_slashes = UnappliedSlashes::<T>::take(&era);
 				for slash in era_slashes {
 					slashing::apply_slash::<T>(slash);
 				}
@@ -2464,9 +2992,9 @@ impl<T: Config> Module<T> {
 
 	/// Ensures that at the end of the current session there will be a new era.
 	fn ensure_new_era() {
-		match ForceEra::get() {
+		match ForceEra::<T>::get() {
 			Forcing::ForceAlways | Forcing::ForceNew => (),
-			_ => ForceEra::put(Forcing::ForceNew),
+			_ => ForceEra::<T>::put(Forcing::ForceNew),
 		}
 	}
 
@@ -2481,24 +3009,35 @@ impl<T: Config> Module<T> {
 
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn set_slash_reward_fraction(fraction: Perbill) {
-		SlashRewardFraction::put(fraction);
+		SlashRewardFraction::<T>::put(fraction);
 	}
 
 	/// Get all of the voters that are eligible for the npos election.
 	///
-	/// This will use all on-chain nominators, and all the validators will inject a self vote.
+	/// `maybe_max_len` can imposes a cap on the number of voters returned. First all the validators
+	/// are included in no particular order, then remainder is taken from the nominators, as
+	/// returned by [`Config::SortedListProvider`].
+	///
+	/// This will use nominators, and all the validators will inject a self vote.
 	///
 	/// ### Slashing
 	///
 	/// All nominations that have been submitted before the last non-zero slash of the validator are
 	/// auto-chilled.
 	///
-	/// Note that this is VERY expensive. Use with care.
-	pub fn get_npos_voters() -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+	/// Note that this is fairly expensive. Use with care.
+	pub fn get_npos_voters(
+		maybe_max_len: Option<usize>,
+	) -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+		let nominator_count = T::SortedListProvider::count() as usize;
+		let validator_count = <Validators<T>>::iter().count();
+		let all_voter_count = validator_count.saturating_add(nominator_count);
+		let max_allowed_len = maybe_max_len.unwrap_or(all_voter_count).min(all_voter_count);
+
 		let weight_of = Self::slashable_balance_of_fn();
-		let mut all_voters = Vec::new();
+		let mut all_voters = Vec::with_capacity(max_allowed_len);
 
-		for (validator, _) in <Validators<T>>::iter() {
+		for (validator, _) in <Validators<T>>::iter().take(max_allowed_len) {
 			// append self vote
 			let self_vote = (validator.clone(), weight_of(&validator), vec![validator.clone()]);
 			all_voters.push(self_vote);
@@ -2507,20 +3046,26 @@ impl<T: Config> Module<T> {
 		// collect all slashing spans into a BTreeMap for further queries.
 		let slashing_spans = <SlashingSpans<T>>::iter().collect::<BTreeMap<_, _>>();
 
-		for (nominator, nominations) in <Nominators<T>>::iter() {
-			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
-
-			// Filter out nomination targets which were nominated before the most recent
-			// slashing span.
-			targets.retain(|stash| {
-				slashing_spans
-					.get(stash)
-					.map_or(true, |spans| submitted_in >= spans.last_nonzero_slash())
-			});
+		// the remainder of the quota is filled with the best nominators, as per the sorted list.
+		let nominators_quota = max_allowed_len.saturating_sub(all_voters.len());
+		for nominator in T::SortedListProvider::iter().take(nominators_quota) {
+			if let Some(Nominations { submitted_in, mut targets, suppressed: _ }) =
+				<Nominators<T>>::get(&nominator)
+			{
+				// Filter out nomination targets which were nominated before the most recent
+				// slashing span.
+				targets.retain(|stash| {
+					slashing_spans
+						.get(stash)
+						.map_or(true, |spans| submitted_in >= spans.last_nonzero_slash())
+				});
 
-			if !targets.is_empty() {
-				let vote_weight = weight_of(&nominator);
-				all_voters.push((nominator, vote_weight, targets))
+				if !targets.is_empty() {
+					let vote_weight = weight_of(&nominator);
+					all_voters.push((nominator, vote_weight, targets))
+				}
+			} else {
+				log!(error, "DEFENSIVE: invalid item in `SortedListProvider`: {:?}", nominator)
 			}
 		}
 
@@ -2533,7 +3078,7 @@ impl<T: Config> Module<T> {
 }
 
 impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::AccountId, T::BlockNumber>
-	for Module<T>
+	for Pallet<T>
 {
 	const MAXIMUM_VOTES_PER_VOTER: u32 = T::MAX_NOMINATIONS;
 	fn desired_targets() -> data_provider::Result<(u32, Weight)> {
@@ -2544,23 +3089,22 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 		maybe_max_len: Option<usize>,
 	) -> data_provider::Result<(Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)>, Weight)> {
 		// NOTE: reading these counts already needs to iterate a lot of storage keys, but they get
-		// cached. This is okay for the case of `Ok(_)`, but bad for `Err(_)`, as the trait does not
-		// report weight in failures.
-		let nominator_count = <Nominators<T>>::iter().count();
+		// cached.
+		let nominator_count = T::SortedListProvider::count() as usize;
 		let validator_count = <Validators<T>>::iter().count();
-		let voter_count = nominator_count.saturating_add(validator_count);
+		let slashing_span_count = <SlashingSpans<T>>::iter().count();
 
-		if maybe_max_len.map_or(false, |max_len| voter_count > max_len) {
-			return Err("Voter snapshot too big");
-		}
+		// the number of validators and nominators that `get_npos_voters` will at most look at.
+		let max_len = maybe_max_len.unwrap_or(usize::max_value());
+		let validators_taken = validator_count.min(max_len);
+		let nominators_taken = nominator_count.min(max_len.saturating_sub(validators_taken));
 
-		let slashing_span_count = <SlashingSpans<T>>::iter().count();
 		let weight = T::WeightInfo::get_npos_voters(
-			nominator_count as u32,
-			validator_count as u32,
+			validators_taken as u32,
+			nominators_taken as u32,
 			slashing_span_count as u32,
 		);
-		Ok((Self::get_npos_voters(), weight))
+		Ok((Self::get_npos_voters(maybe_max_len), weight))
 	}
 
 	fn targets(maybe_max_len: Option<usize>) -> data_provider::Result<(Vec<T::AccountId>, Weight)> {
@@ -2623,8 +3167,8 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 					claimed_rewards: vec![],
 				},
 			);
-			<Validators<T>>::insert(
-				v,
+			Self::do_add_validator(
+				&v,
 				ValidatorPrefs { commission: Perbill::zero(), blocked: false },
 			);
 		});
@@ -2644,23 +3188,105 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 					claimed_rewards: vec![],
 				},
 			);
-			<Nominators<T>>::insert(
-				v,
+			Self::do_add_nominator(
+				&v,
 				Nominations { targets: t, submitted_in: 0, suppressed: false },
 			);
 		});
 	}
 }
 
+impl<T: Config> VoteWeightProvider<T::AccountId> for Pallet<T> {
+	fn vote_weight(who: &T::AccountId) -> VoteWeight {
+		Self::weight_of(who)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_vote_weight_of(who: &T::AccountId, weight: VoteWeight) {
+		// this will clearly results in an inconsistent state, but it should not matter for a
+		// benchmark.
+		use sp_std::convert::TryInto;
+		let active: BalanceOf<T> = weight.try_into().map_err(|_| ()).unwrap();
+		let ledger = match Self::ledger(who) {
+			Some(mut ledger) => {
+				ledger.active = active;
+				ledger
+			},
+			None => StakingLedger {
+				stash: who.clone(),
+				active,
+				total: active,
+				unlocking: vec![],
+				claimed_rewards: vec![],
+			},
+		};
+		<Ledger<T>>::insert(who, ledger);
+		<Bonded<T>>::insert(who, who);
+
+		// also, we play a trick to make sure that a issuance based-`CurrencyToVote` behaves well:
+		// This will make sure that total issuance is zero, thus the currency to vote will be a 1-1
+		// conversion.
+		let imbalance = T::Currency::burn(T::Currency::total_issuance());
+		// kinda ugly, but gets the job done. The fact that this works here is a HUGE exception.
+		// Don't try this pattern in other places.
+		sp_std::mem::forget(imbalance);
+	}
+}
+
+/// A simple voter list implementation that does not require any additional pallets. Note, this
+/// does not provide nominators in sorted order. If you desire nominators in a sorted order take
+/// a look at `pallet-bags-list`.
+pub struct UseNominatorsMap<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> SortedListProvider<T::AccountId> for UseNominatorsMap<T> {
+	type Error = ();
+
+	/// Returns iterator over voter list, which can have `take` called on it.
+	fn iter() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(<Nominators<T>>::iter().map(|(n, _)| n))
+	}
+	fn count() -> u32 {
+		CounterForNominators::<T>::get()
+	}
+	fn contains(id: &T::AccountId) -> bool {
+		<Nominators<T>>::contains_key(id)
+	}
+	fn on_insert(_: T::AccountId, _weight: VoteWeight) -> Result<(), Self::Error> {
+		// nothing to do on insert.
+		Ok(())
+	}
+	fn on_update(_: &T::AccountId, _weight: VoteWeight) {
+		// nothing to do on update.
+	}
+	fn on_remove(_: &T::AccountId) {
+		// nothing to do on remove.
+	}
+	fn regenerate(
+		_: impl IntoIterator<Item = T::AccountId>,
+		_: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		// nothing to do upon regenerate.
+		0
+	}
+	fn sanity_check() -> Result<(), &'static str> {
+		Ok(())
+	}
+	fn clear() {
+		// NOTE: Caller must ensure this doesn't lead to too many storage accesses. This is a
+		// condition of SortedListProvider::clear.
+		<Nominators<T>>::remove_all();
+		CounterForNominators::<T>::kill();
+	}
+}
+
 /// In this implementation `new_session(session)` must be called before `end_session(session-1)`
 /// i.e. the new session must be planned before the ending of the previous session.
 ///
 /// Once the first new_session is planned, all session must start and then end in order, though
 /// some session can lag in between the newest session planned and the latest session started.
-impl<T: Config> pallet_session::SessionManager<T::AccountId> for Module<T> {
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
 	fn new_session(new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
 		log!(trace, "planning new_session({})", new_index);
-		CurrentPlannedSession::put(new_index);
+		CurrentPlannedSession::<T>::put(new_index);
 		Self::new_session(new_index)
 	}
 	fn start_session(start_index: SessionIndex) {
@@ -2674,7 +3300,7 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Module<T> {
 }
 
 impl<T: Config> historical::SessionManager<T::AccountId, Exposure<T::AccountId, BalanceOf<T>>>
-	for Module<T>
+	for Pallet<T>
 {
 	fn new_session(
 		new_index: SessionIndex,
@@ -2702,7 +3328,7 @@ impl<T: Config> historical::SessionManager<T::AccountId, Exposure<T::AccountId,
 /// * 20 points to the block producer for producing a (non-uncle) block in the relay chain,
 /// * 2 points to the block producer for each reference to a previously unreferenced uncle, and
 /// * 1 point to the producer of each referenced uncle block.
-impl<T> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Module<T>
+impl<T> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T>
 where
 	T: Config + pallet_authorship::Config + pallet_session::Config,
 {
@@ -2723,7 +3349,7 @@ pub struct StashOf<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> Convert<T::AccountId, Option<T::AccountId>> for StashOf<T> {
 	fn convert(controller: T::AccountId) -> Option<T::AccountId> {
-		<Module<T>>::ledger(&controller).map(|l| l.stash)
+		<Pallet<T>>::ledger(&controller).map(|l| l.stash)
 	}
 }
 
@@ -2738,15 +3364,15 @@ impl<T: Config> Convert<T::AccountId, Option<Exposure<T::AccountId, BalanceOf<T>
 	for ExposureOf<T>
 {
 	fn convert(validator: T::AccountId) -> Option<Exposure<T::AccountId, BalanceOf<T>>> {
-		<Module<T>>::active_era()
-			.map(|active_era| <Module<T>>::eras_stakers(active_era.index, &validator))
+		<Pallet<T>>::active_era()
+			.map(|active_era| <Pallet<T>>::eras_stakers(active_era.index, &validator))
 	}
 }
 
 /// This is intended to be used with `FilterHistoricalOffences`.
 impl<T: Config>
 	OnOffenceHandler<T::AccountId, pallet_session::historical::IdentificationTuple<T>, Weight>
-	for Module<T>
+	for Pallet<T>
 where
 	T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
 	T: pallet_session::historical::Config<
@@ -2768,7 +3394,7 @@ where
 		slash_fraction: &[Perbill],
 		slash_session: SessionIndex,
 	) -> Weight {
-		let reward_proportion = SlashRewardFraction::get();
+		let reward_proportion = SlashRewardFraction::<T>::get();
 		let mut consumed_weight: Weight = 0;
 		let mut add_db_reads_writes = |reads, writes| {
 			consumed_weight += T::DbWeight::get().reads_writes(reads, writes);
@@ -2797,7 +3423,7 @@ where
 		let slash_era = if slash_session >= active_era_start_session_index {
 			active_era
 		} else {
-			let eras = BondedEras::get();
+			let eras = BondedEras::<T>::get();
 			add_db_reads_writes(1, 0);
 
 			// reverse because it's more likely to find reports from recent eras.
@@ -2808,7 +3434,7 @@ where
 			}
 		};
 
-		<Self as Store>::EarliestUnappliedSlash::mutate(|earliest| {
+		EarliestUnappliedSlash::<T>::mutate(|earliest| {
 			if earliest.is_none() {
 				*earliest = Some(active_era)
 			}
@@ -2861,7 +3487,7 @@ where
 					}
 				} else {
 					// defer to end of some `slash_defer_duration` from now.
-					<Self as Store>::UnappliedSlashes::mutate(
+					UnappliedSlashes::<T>::mutate(
 						active_era,
 						move |for_later| for_later.push(unapplied),
 					);
@@ -2882,7 +3508,7 @@ pub struct FilterHistoricalOffences<T, R> {
 }
 
 impl<T, Reporter, Offender, R, O> ReportOffence<Reporter, Offender, O>
-	for FilterHistoricalOffences<Module<T>, R>
+	for FilterHistoricalOffences<Pallet<T>, R>
 where
 	T: Config,
 	R: ReportOffence<Reporter, Offender, O>,
@@ -2891,13 +3517,13 @@ where
 	fn report_offence(reporters: Vec<Reporter>, offence: O) -> Result<(), OffenceError> {
 		// disallow any slashing from before the current bonding period.
 		let offence_session = offence.session_index();
-		let bonded_eras = BondedEras::get();
+		let bonded_eras = BondedEras::<T>::get();
 
 		if bonded_eras.first().filter(|(_, start)| offence_session >= *start).is_some() {
 			R::report_offence(reporters, offence)
 		} else {
-			<Module<T>>::deposit_event(
-				RawEvent::OldSlashingReportDiscarded(offence_session)
+			<Pallet<T>>::deposit_event(
+				Event::<T>::OldSlashingReportDiscarded(offence_session)
 			);
 			Ok(())
 		}
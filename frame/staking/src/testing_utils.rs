@@ -19,7 +19,7 @@
 //! bonding validators, nominators, and generating different types of solutions.
 
 use crate::*;
-use crate::Module as Staking;
+use crate::Pallet as Staking;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use sp_io::hashing::blake2_256;
@@ -30,7 +30,12 @@ const SEED: u32 = 0;
 /// This function removes all validators and nominators from storage.
 pub fn clear_validators_and_nominators<T: Config>() {
 	Validators::<T>::remove_all();
+	CounterForValidators::<T>::kill();
 	Nominators::<T>::remove_all();
+	CounterForNominators::<T>::kill();
+
+	// NOTE: safe to call outside block production
+	T::SortedListProvider::clear();
 }
 
 /// Grab a funded user.
@@ -166,12 +171,12 @@ pub fn create_validators_with_nominators_for_era<T: Config>(
 		Staking::<T>::nominate(RawOrigin::Signed(n_controller.clone()).into(), selected_validators)?;
 	}
 
-	ValidatorCount::put(validators);
+	ValidatorCount::<T>::put(validators);
 
 	Ok(validator_chosen)
 }
 
 /// get the current era.
 pub fn current_era<T: Config>() -> EraIndex {
-	<Module<T>>::current_era().unwrap_or(0)
+	<Pallet<T>>::current_era().unwrap_or(0)
 }
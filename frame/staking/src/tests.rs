@@ -17,7 +17,7 @@
 
 //! Tests for the module.
 
-use super::*;
+use super::{*, Event};
 use mock::*;
 use sp_runtime::{
 	assert_eq_error_rate,
@@ -25,8 +25,8 @@ use sp_runtime::{
 };
 use sp_staking::offence::OffenceDetails;
 use frame_support::{
-	assert_ok, assert_noop, StorageMap,
-	traits::{Currency, ReservableCurrency, OnInitialize},
+	assert_ok, assert_noop,
+	traits::{Currency, ReservableCurrency, OnInitialize, OnRuntimeUpgrade},
 	weights::{extract_actual_weight, GetDispatchInfo},
 };
 use pallet_balances::Error as BalancesError;
@@ -187,10 +187,10 @@ fn rewards_should_work() {
 		Payee::<Test>::insert(21, RewardDestination::Controller);
 		Payee::<Test>::insert(101, RewardDestination::Controller);
 
-		<Module<Test>>::reward_by_ids(vec![(11, 50)]);
-		<Module<Test>>::reward_by_ids(vec![(11, 50)]);
+		Staking::reward_by_ids(vec![(11, 50)]);
+		Staking::reward_by_ids(vec![(11, 50)]);
 		// This is the second validator of the current elected set.
-		<Module<Test>>::reward_by_ids(vec![(21, 50)]);
+		Staking::reward_by_ids(vec![(21, 50)]);
 
 		// Compute total payout now for whole duration of the session.
 		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
@@ -227,7 +227,7 @@ fn rewards_should_work() {
 		);
 		assert_eq!(
 			*mock::staking_events().last().unwrap(),
-			RawEvent::EraPayout(0, total_payout_0, maximum_payout - total_payout_0)
+			Event::EraPayout(0, total_payout_0, maximum_payout - total_payout_0)
 		);
 		mock::make_all_reward_payment(0);
 
@@ -253,7 +253,7 @@ fn rewards_should_work() {
 		assert_eq_error_rate!(Balances::total_balance(&101), init_balance_101, 2);
 
 		assert_eq_uvec!(Session::validators(), vec![11, 21]);
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
@@ -265,7 +265,7 @@ fn rewards_should_work() {
 		);
 		assert_eq!(
 			*mock::staking_events().last().unwrap(),
-			RawEvent::EraPayout(1, total_payout_1, maximum_payout - total_payout_1)
+			Event::EraPayout(1, total_payout_1, maximum_payout - total_payout_1)
 		);
 		mock::make_all_reward_payment(1);
 
@@ -482,8 +482,8 @@ fn nominating_and_rewards_should_work() {
 
 			// the total reward for era 0
 			let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
-			<Module<Test>>::reward_by_ids(vec![(41, 1)]);
-			<Module<Test>>::reward_by_ids(vec![(31, 1)]);
+			Staking::reward_by_ids(vec![(41, 1)]);
+			Staking::reward_by_ids(vec![(31, 1)]);
 
 			mock::start_active_era(1);
 
@@ -505,8 +505,8 @@ fn nominating_and_rewards_should_work() {
 					total: 1000 + 800,
 					own: 1000,
 					others: vec![
-						IndividualExposure { who: 3, value: 400 },
 						IndividualExposure { who: 1, value: 400 },
+						IndividualExposure { who: 3, value: 400 },
 					]
 				},
 			);
@@ -516,16 +516,16 @@ fn nominating_and_rewards_should_work() {
 					total: 1000 + 1200,
 					own: 1000,
 					others: vec![
-						IndividualExposure { who: 3, value: 600 },
 						IndividualExposure { who: 1, value: 600 },
+						IndividualExposure { who: 3, value: 600 },
 					]
 				},
 			);
 
 			// the total reward for era 1
 			let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
-			<Module<Test>>::reward_by_ids(vec![(21, 2)]);
-			<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+			Staking::reward_by_ids(vec![(21, 2)]);
+			Staking::reward_by_ids(vec![(11, 1)]);
 
 			mock::start_active_era(2);
 
@@ -779,7 +779,7 @@ fn forcing_new_era_works() {
 		assert_eq!(active_era(), 1);
 
 		// no era change.
-		ForceEra::put(Forcing::ForceNone);
+		ForceEra::<Test>::put(Forcing::ForceNone);
 
 		start_session(4);
 		assert_eq!(active_era(), 1);
@@ -795,7 +795,7 @@ fn forcing_new_era_works() {
 
 		// back to normal.
 		// this immediately starts a new session.
-		ForceEra::put(Forcing::NotForcing);
+		ForceEra::<Test>::put(Forcing::NotForcing);
 
 		start_session(8);
 		assert_eq!(active_era(), 1);
@@ -803,7 +803,7 @@ fn forcing_new_era_works() {
 		start_session(9);
 		assert_eq!(active_era(), 2);
 		// forceful change
-		ForceEra::put(Forcing::ForceAlways);
+		ForceEra::<Test>::put(Forcing::ForceAlways);
 
 		start_session(10);
 		assert_eq!(active_era(), 2);
@@ -815,10 +815,10 @@ fn forcing_new_era_works() {
 		assert_eq!(active_era(), 4);
 
 		// just one forceful change
-		ForceEra::put(Forcing::ForceNew);
+		ForceEra::<Test>::put(Forcing::ForceNew);
 		start_session(13);
 		assert_eq!(active_era(), 5);
-		assert_eq!(ForceEra::get(), Forcing::NotForcing);
+		assert_eq!(ForceEra::<Test>::get(), Forcing::NotForcing);
 
 		start_session(14);
 		assert_eq!(active_era(), 6);
@@ -917,7 +917,7 @@ fn reward_destination_works() {
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 
 		mock::start_active_era(1);
 		mock::make_all_reward_payment(0);
@@ -940,7 +940,7 @@ fn reward_destination_works() {
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 
 		mock::start_active_era(2);
 		mock::make_all_reward_payment(1);
@@ -968,7 +968,7 @@ fn reward_destination_works() {
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_2 = current_total_payout_for_duration(reward_time_per_era());
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 
 		mock::start_active_era(3);
 		mock::make_all_reward_payment(2);
@@ -1015,7 +1015,7 @@ fn validator_payment_prefs_work() {
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
 		let exposure_1 = Staking::eras_stakers(Staking::active_era().unwrap().index, 11);
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 
 		mock::start_active_era(2);
 		mock::make_all_reward_payment(1);
@@ -1508,8 +1508,8 @@ fn reward_to_stake_works() {
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
-		<Module<Test>>::reward_by_ids(vec![(21, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(21, 1)]);
 
 		// New era --> rewards are paid --> stakes are changed
 		mock::start_active_era(1);
@@ -1849,8 +1849,8 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider() {
 			assert_eq!(
 				supports,
 				vec![
-					(21, Support { total: 1800, voters: vec![(21, 1000), (3, 400), (1, 400)] }),
-					(31, Support { total: 2200, voters: vec![(31, 1000), (3, 600), (1, 600)] })
+					(21, Support { total: 1800, voters: vec![(21, 1000), (1, 400), (3, 400)] }),
+					(31, Support { total: 2200, voters: vec![(31, 1000), (1, 600), (3, 600)] })
 				],
 			);
 		});
@@ -1897,7 +1897,7 @@ fn bond_with_duplicate_vote_should_be_ignored_by_election_provider_elected() {
 				supports,
 				vec![
 					(11, Support { total: 1500, voters: vec![(11, 1000), (1, 500)] }),
-					(21, Support { total: 2500, voters: vec![(21, 1000), (3, 1000), (1, 500)] })
+					(21, Support { total: 2500, voters: vec![(21, 1000), (1, 500), (3, 1000)] })
 				],
 			);
 		});
@@ -2009,10 +2009,10 @@ fn reward_from_authorship_event_handler_works() {
 
 		assert_eq!(<pallet_authorship::Pallet<Test>>::author(), 11);
 
-		<Module<Test>>::note_author(11);
-		<Module<Test>>::note_uncle(21, 1);
+		Staking::note_author(11);
+		Staking::note_uncle(21, 1);
 		// Rewarding the same two times works.
-		<Module<Test>>::note_uncle(11, 1);
+		Staking::note_uncle(11, 1);
 
 		// Not mandatory but must be coherent with rewards
 		assert_eq_uvec!(Session::validators(), vec![11, 21]);
@@ -2035,13 +2035,13 @@ fn add_reward_points_fns_works() {
 		// Not mandatory but must be coherent with rewards
 		assert_eq_uvec!(Session::validators(), vec![21, 11]);
 
-		<Module<Test>>::reward_by_ids(vec![
+		Staking::reward_by_ids(vec![
 			(21, 1),
 			(11, 1),
 			(11, 1),
 		]);
 
-		<Module<Test>>::reward_by_ids(vec![
+		Staking::reward_by_ids(vec![
 			(21, 1),
 			(11, 1),
 			(11, 1),
@@ -2084,7 +2084,7 @@ fn era_is_always_same_length() {
 		assert_eq!(Staking::eras_start_session_index(current_era()).unwrap(), session_per_era * 2u32);
 
 		let session = Session::current_index();
-		ForceEra::put(Forcing::ForceNew);
+		ForceEra::<Test>::put(Forcing::ForceNew);
 		advance_session();
 		advance_session();
 		assert_eq!(current_era(), 3);
@@ -2992,13 +2992,13 @@ fn claim_reward_at_the_last_era_and_no_double_claim_and_invalid_claim() {
 		Payee::<Test>::insert(11, RewardDestination::Controller);
 		Payee::<Test>::insert(101, RewardDestination::Controller);
 
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_0 = current_total_payout_for_duration(reward_time_per_era());
 
 		mock::start_active_era(1);
 
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 		// Change total issuance in order to modify total payout
 		let _ = Balances::deposit_creating(&999, 1_000_000_000);
 		// Compute total payout now for whole duration as other parameter won't change
@@ -3007,7 +3007,7 @@ fn claim_reward_at_the_last_era_and_no_double_claim_and_invalid_claim() {
 
 		mock::start_active_era(2);
 
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 		// Change total issuance in order to modify total payout
 		let _ = Balances::deposit_creating(&999, 1_000_000_000);
 		// Compute total payout now for whole duration as other parameter won't change
@@ -3168,7 +3168,7 @@ fn test_max_nominator_rewarded_per_validator_and_cant_steal_someone_else_reward(
 		}
 		mock::start_active_era(1);
 
-		<Module<Test>>::reward_by_ids(vec![(11, 1)]);
+		Staking::reward_by_ids(vec![(11, 1)]);
 		// compute and ensure the reward amount is greater than zero.
 		let _ = current_total_payout_for_duration(reward_time_per_era());
 
@@ -3934,11 +3934,61 @@ mod election_data_provider {
 	#[test]
 	fn respects_len_limits() {
 		ExtBuilder::default().build().execute_with(|| {
-			assert_eq!(Staking::voters(Some(1)).unwrap_err(), "Voter snapshot too big");
+			assert_eq!(Staking::voters(Some(1)).unwrap().0.len(), 1);
 			assert_eq!(Staking::targets(Some(1)).unwrap_err(), "Target snapshot too big");
 		});
 	}
 
+	#[test]
+	fn voters_take_validators_first_then_best_nominators() {
+		ExtBuilder::default().nominate(true).build_and_execute(|| {
+			// given 3 validators, and a nominator with a smaller stake than 101.
+			assert_eq!(<Validators<Test>>::iter().count(), 3);
+			bond_nominator(61, 60, 20, vec![11]);
+			assert_eq!(
+				<Test as Config>::SortedListProvider::iter().collect::<Vec<_>>(),
+				vec![101, 61],
+			);
+
+			// when only one nominator fits in the snapshot
+			let voters = Staking::voters(Some(4)).unwrap().0;
+
+			// then all validators are taken, followed by the best nominator.
+			assert_eq!(voters.len(), 4);
+			assert!(voters.iter().any(|(who, _, _)| *who == 101));
+			assert!(!voters.iter().any(|(who, _, _)| *who == 61));
+
+			// when the snapshot has room for everyone
+			let voters = Staking::voters(None).unwrap().0;
+
+			// then
+			assert_eq!(voters.len(), 5);
+		});
+	}
+
+	#[test]
+	fn sorted_list_provider_tracks_nominators() {
+		ExtBuilder::default().nominate(true).build_and_execute(|| {
+			// genesis nominators are in the list.
+			assert_eq!(<Test as Config>::SortedListProvider::count(), 1);
+			assert!(<Test as Config>::SortedListProvider::contains(&101));
+
+			// a validator that starts nominating is added.
+			assert_ok!(Staking::nominate(Origin::signed(10), vec![21]));
+			assert_eq!(<Test as Config>::SortedListProvider::count(), 2);
+			assert!(<Test as Config>::SortedListProvider::contains(&11));
+
+			// a nominator that starts validating is removed.
+			assert_ok!(Staking::validate(Origin::signed(100), Default::default()));
+			assert_eq!(<Test as Config>::SortedListProvider::count(), 1);
+			assert!(!<Test as Config>::SortedListProvider::contains(&101));
+
+			// chilling removes as well.
+			assert_ok!(Staking::chill(Origin::signed(10)));
+			assert_eq!(<Test as Config>::SortedListProvider::count(), 0);
+		});
+	}
+
 	#[test]
 	fn estimate_next_election_works() {
 		ExtBuilder::default().session_per_era(5).period(5).build().execute_with(|| {
@@ -3954,7 +4004,7 @@ mod election_data_provider {
 			assert_eq!(staking_events().len(), 1);
 			assert_eq!(
 				*staking_events().last().unwrap(),
-				RawEvent::StakingElection
+				Event::StakingElection
 			);
 
 			for b in 21..45 {
@@ -3968,8 +4018,292 @@ mod election_data_provider {
 			assert_eq!(staking_events().len(), 3);
 			assert_eq!(
 				*staking_events().last().unwrap(),
-				RawEvent::StakingElection
+				Event::StakingElection
 			);
 		})
 	}
 }
+
+#[test]
+fn min_bond_checks_work() {
+	ExtBuilder::default()
+		.build_and_execute(|| {
+			assert_ok!(Staking::set_staking_limits(Origin::root(), 1_000, 1_500, None, None, None));
+			Balances::make_free_balance_be(&3, 2_000);
+
+			// 500 is not enough for any role
+			assert_ok!(Staking::bond(Origin::signed(3), 4, 500, RewardDestination::Controller));
+			assert_noop!(Staking::nominate(Origin::signed(4), vec![1]), Error::<Test>::InsufficientBond);
+			assert_noop!(
+				Staking::validate(Origin::signed(4), ValidatorPrefs::default()),
+				Error::<Test>::InsufficientBond,
+			);
+
+			// 1000 is enough for nominator
+			assert_ok!(Staking::bond_extra(Origin::signed(3), 500));
+			assert_ok!(Staking::nominate(Origin::signed(4), vec![1]));
+			assert_noop!(
+				Staking::validate(Origin::signed(4), ValidatorPrefs::default()),
+				Error::<Test>::InsufficientBond,
+			);
+
+			// 1500 is enough for validator
+			assert_ok!(Staking::bond_extra(Origin::signed(3), 500));
+			assert_ok!(Staking::nominate(Origin::signed(4), vec![1]));
+			assert_ok!(Staking::validate(Origin::signed(4), ValidatorPrefs::default()));
+
+			// Can't unbond anything as validator
+			assert_noop!(Staking::unbond(Origin::signed(4), 500), Error::<Test>::InsufficientBond);
+
+			// Once they are a nominator, they can unbond 500
+			assert_ok!(Staking::nominate(Origin::signed(4), vec![1]));
+			assert_ok!(Staking::unbond(Origin::signed(4), 500));
+			assert_noop!(Staking::unbond(Origin::signed(4), 500), Error::<Test>::InsufficientBond);
+
+			// Once they are chilled they can unbond everything
+			assert_ok!(Staking::chill(Origin::signed(4)));
+			assert_ok!(Staking::unbond(Origin::signed(4), 1000));
+		})
+}
+
+#[test]
+fn runtime_upgrade_from_v6_migrates_to_v8() {
+	ExtBuilder::default().nominate(true).build_and_execute(|| {
+		// given a chain at V6: no sorted list and no counters yet.
+		StorageVersion::<Test>::put(Releases::V6_0_0);
+		<Test as Config>::SortedListProvider::clear();
+		CounterForValidators::<Test>::kill();
+		CounterForNominators::<Test>::kill();
+
+		// when
+		<Staking as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		// then both migrations ran in the same upgrade.
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V8_0_0);
+		assert_eq!(CounterForValidators::<Test>::get(), 3);
+		assert_eq!(CounterForNominators::<Test>::get(), 1);
+		assert_eq!(<Test as Config>::SortedListProvider::count(), 1);
+		assert!(<Test as Config>::SortedListProvider::contains(&101));
+	});
+}
+
+#[test]
+fn chill_other_works() {
+	ExtBuilder::default()
+		.build_and_execute(|| {
+			let initial_validators = CounterForValidators::<Test>::get();
+			let initial_nominators = CounterForNominators::<Test>::get();
+			for i in 0..15 {
+				let a = 4 * i + 1_000;
+				let b = 4 * i + 1_001;
+				let c = 4 * i + 1_002;
+				let d = 4 * i + 1_003;
+				Balances::make_free_balance_be(&a, 100_000);
+				Balances::make_free_balance_be(&b, 100_000);
+				Balances::make_free_balance_be(&c, 100_000);
+				Balances::make_free_balance_be(&d, 100_000);
+
+				// Nominator
+				assert_ok!(Staking::bond(Origin::signed(a), b, 1000, RewardDestination::Controller));
+				assert_ok!(Staking::nominate(Origin::signed(b), vec![1]));
+
+				// Validator
+				assert_ok!(Staking::bond(Origin::signed(c), d, 1500, RewardDestination::Controller));
+				assert_ok!(Staking::validate(Origin::signed(d), ValidatorPrefs::default()));
+			}
+
+			// To chill other users, we need to:
+			// * Set a minimum bond amount
+			// * Set a limit
+			// * Set a threshold
+			//
+			// If any of these are missing, we do not have enough information to allow the
+			// `chill_other` to succeed from one user to another.
+
+			// Can't chill these users
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_001),
+				Error::<Test>::CannotChillOther,
+			);
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_003),
+				Error::<Test>::CannotChillOther,
+			);
+
+			// Change the minimum bond... but no limits.
+			assert_ok!(Staking::set_staking_limits(Origin::root(), 1_500, 2_000, None, None, None));
+
+			// Still can't chill these users
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_001),
+				Error::<Test>::CannotChillOther,
+			);
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_003),
+				Error::<Test>::CannotChillOther,
+			);
+
+			// Add limits, but no threshold
+			assert_ok!(Staking::set_staking_limits(
+				Origin::root(),
+				1_500,
+				2_000,
+				Some(10),
+				Some(10),
+				None,
+			));
+
+			// Still can't chill these users
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_001),
+				Error::<Test>::CannotChillOther,
+			);
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_003),
+				Error::<Test>::CannotChillOther,
+			);
+
+			// Add threshold, but no limits
+			assert_ok!(Staking::set_staking_limits(
+				Origin::root(),
+				1_500,
+				2_000,
+				None,
+				None,
+				Some(Percent::from_percent(0)),
+			));
+
+			// Still can't chill these users
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_001),
+				Error::<Test>::CannotChillOther,
+			);
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_003),
+				Error::<Test>::CannotChillOther,
+			);
+
+			// Add threshold and limits
+			assert_ok!(Staking::set_staking_limits(
+				Origin::root(),
+				1_500,
+				2_000,
+				Some(10),
+				Some(10),
+				Some(Percent::from_percent(70)),
+			));
+
+			assert_eq!(CounterForNominators::<Test>::get(), 15 + initial_nominators);
+			assert_eq!(CounterForValidators::<Test>::get(), 15 + initial_validators);
+
+			// Users can now be chilled down to 7 people, so we try to remove 9 of them.
+			for i in 6..15 {
+				let b = 4 * i + 1_001;
+				let d = 4 * i + 1_003;
+				assert_ok!(Staking::chill_other(Origin::signed(1337), b));
+				assert_ok!(Staking::chill_other(Origin::signed(1337), d));
+			}
+
+			// chill a nominator. Limit is not reached, not chill-able
+			assert_eq!(CounterForNominators::<Test>::get(), 7);
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_001),
+				Error::<Test>::CannotChillOther,
+			);
+
+			// the validators started with more members, so two more can be chilled.
+			assert_eq!(CounterForValidators::<Test>::get(), 9);
+			assert_ok!(Staking::chill_other(Origin::signed(1337), 1_003));
+			assert_ok!(Staking::chill_other(Origin::signed(1337), 1_007));
+			assert_eq!(CounterForValidators::<Test>::get(), 7);
+			assert_noop!(
+				Staking::chill_other(Origin::signed(1337), 1_011),
+				Error::<Test>::CannotChillOther,
+			);
+
+			// users can always chill themselves.
+			assert_ok!(Staking::chill_other(Origin::signed(1_011), 1_011));
+			assert_eq!(CounterForValidators::<Test>::get(), 6);
+		})
+}
+
+#[test]
+fn capped_stakers_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let validator_count = CounterForValidators::<Test>::get();
+		assert_eq!(validator_count, 3);
+		let nominator_count = CounterForNominators::<Test>::get();
+		assert_eq!(nominator_count, 1);
+
+		// Change the maximums
+		let max = 10;
+		assert_ok!(Staking::set_staking_limits(
+			Origin::root(),
+			10,
+			10,
+			Some(max),
+			Some(max),
+			Some(Percent::from_percent(0)),
+		));
+
+		// can create `max - validator_count` validators
+		let mut some_existing_validator = AccountId::default();
+		for i in 0 .. max - validator_count {
+			let (_, controller) = testing_utils::create_stash_controller::<Test>(
+				i + 10_000_000,
+				100,
+				RewardDestination::Controller,
+			).unwrap();
+			assert_ok!(Staking::validate(Origin::signed(controller), ValidatorPrefs::default()));
+			some_existing_validator = controller;
+		}
+
+		// but no more
+		let (_, last_validator) = testing_utils::create_stash_controller::<Test>(
+			1337,
+			100,
+			RewardDestination::Controller,
+		).unwrap();
+
+		assert_noop!(
+			Staking::validate(Origin::signed(last_validator), ValidatorPrefs::default()),
+			Error::<Test>::TooManyValidators,
+		);
+
+		// same with nominators
+		let mut some_existing_nominator = AccountId::default();
+		for i in 0 .. max - nominator_count {
+			let (_, controller) = testing_utils::create_stash_controller::<Test>(
+				i + 20_000_000,
+				100,
+				RewardDestination::Controller,
+			).unwrap();
+			assert_ok!(Staking::nominate(Origin::signed(controller), vec![1]));
+			some_existing_nominator = controller;
+		}
+
+		// one more is too many
+		let (_, last_nominator) = testing_utils::create_stash_controller::<Test>(
+			30_000_000,
+			100,
+			RewardDestination::Controller,
+		).unwrap();
+		assert_noop!(
+			Staking::nominate(Origin::signed(last_nominator), vec![1]),
+			Error::<Test>::TooManyNominators,
+		);
+
+		// Re-nominate works fine
+		assert_ok!(Staking::nominate(Origin::signed(some_existing_nominator), vec![1]));
+		// Re-validate works fine
+		assert_ok!(Staking::validate(
+			Origin::signed(some_existing_validator),
+			ValidatorPrefs::default(),
+		));
+
+		// No problem when we set to `None` again
+		assert_ok!(Staking::set_staking_limits(Origin::root(), 10, 10, None, None, None));
+		assert_ok!(Staking::nominate(Origin::signed(last_nominator), vec![1]));
+		assert_ok!(Staking::validate(Origin::signed(last_validator), ValidatorPrefs::default()));
+	})
+}
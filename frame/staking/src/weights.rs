@@ -70,6 +70,8 @@ pub trait WeightInfo {
 	fn new_era(v: u32, n: u32, ) -> Weight;
 	fn get_npos_voters(v: u32, n: u32, s: u32, ) -> Weight;
 	fn get_npos_targets(v: u32, ) -> Weight;
+	fn set_staking_limits() -> Weight;
+	fn chill_other() -> Weight;
 }
 
 /// Weights for pallet_staking using the Substrate node and recommended hardware.
@@ -251,6 +253,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
 	}
+	fn set_staking_limits() -> Weight {
+		(5_028_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn chill_other() -> Weight {
+		(35_758_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -431,4 +442,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
 	}
+	fn set_staking_limits() -> Weight {
+		(5_028_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn chill_other() -> Weight {
+		(35_758_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
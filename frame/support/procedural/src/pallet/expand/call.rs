@@ -67,19 +67,39 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			.collect::<Vec<_>>()
 	});
 
-	let args_metadata_type = methods.iter().map(|method| {
+	let args_metadata_field = methods.iter().map(|method| {
 		method.args.iter()
-			.map(|(is_compact, _, type_)| {
-				let final_type = if *is_compact {
-					quote::quote_spanned!(type_.span() => Compact<#type_>)
+			.map(|(is_compact, name, type_)| {
+				let type_name = clean_type_string(&quote::quote!(#type_).to_string());
+				let ty = if *is_compact {
+					quote::quote_spanned!(type_.span() => compact::<#type_>)
 				} else {
-					quote::quote!(#type_)
+					quote::quote_spanned!(type_.span() => ty::<#type_>)
 				};
-				clean_type_string(&final_type.to_string())
+				quote::quote_spanned!(type_.span() =>
+					|f| f.name(stringify!(#name)).#ty().type_name(#type_name)
+				)
 			})
 			.collect::<Vec<_>>()
 	});
 
+	let fn_doc_metadata = fn_doc.iter()
+		.map(|docs| docs.iter().map(doc_metadata).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	let fn_index = (0..methods.len())
+		.map(|i| syn::Index::from(i))
+		.collect::<Vec<_>>();
+
+	let type_params = if def.config.has_instance {
+		quote::quote!(
+			#frame_support::scale_info::TypeParameter::new("T", None),
+			#frame_support::scale_info::TypeParameter::new("I", None),
+		)
+	} else {
+		quote::quote!(#frame_support::scale_info::TypeParameter::new("T", None))
+	};
+
 	let default_docs = [syn::parse_quote!(
 		r"Contains one variant per dispatchable that can be called by an extrinsic."
 	)];
@@ -89,6 +109,8 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 		&docs[..]
 	};
 
+	let docs_metadata = docs.iter().map(doc_metadata);
+
 	quote::quote_spanned!(span =>
 		#( #[doc = #docs] )*
 		#[derive(
@@ -196,33 +218,50 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 			type Call = #call_ident<#type_use_gen>;
 		}
 
+		impl<#type_impl_gen> #frame_support::scale_info::TypeInfo for #call_ident<#type_use_gen>
+			#where_clause
+		{
+			type Identity = Self;
+
+			fn type_info() -> #frame_support::scale_info::Type {
+				#frame_support::scale_info::Type::builder()
+					.path(#frame_support::scale_info::Path::new("Call", module_path!()))
+					.type_params(#frame_support::sp_std::vec![ #type_params ])
+					.docs_always(&[ #( #docs_metadata ),* ])
+					.variant(
+						#frame_support::scale_info::build::Variants::new()
+						#(
+							.variant(stringify!(#fn_name), |v| v
+								.index(#fn_index)
+								.fields(
+									#frame_support::scale_info::build::Fields::named()
+									#( .field(#args_metadata_field) )*
+								)
+								.docs_always(&[ #( #fn_doc_metadata ),* ])
+							)
+						)*
+					)
+			}
+		}
+
 		impl<#type_impl_gen> #pallet_ident<#type_use_gen> #where_clause {
 			#[doc(hidden)]
 			#[allow(dead_code)]
-			pub fn call_functions() -> &'static [#frame_support::dispatch::FunctionMetadata] {
-				&[ #(
-					#frame_support::dispatch::FunctionMetadata {
-						name: #frame_support::dispatch::DecodeDifferent::Encode(
-							stringify!(#fn_name)
-						),
-						arguments: #frame_support::dispatch::DecodeDifferent::Encode(
-							&[ #(
-								#frame_support::dispatch::FunctionArgumentMetadata {
-									name: #frame_support::dispatch::DecodeDifferent::Encode(
-										stringify!(#args_name)
-									),
-									ty: #frame_support::dispatch::DecodeDifferent::Encode(
-										#args_metadata_type
-									),
-								},
-							)* ]
-						),
-						documentation: #frame_support::dispatch::DecodeDifferent::Encode(
-							&[ #( #fn_doc ),* ]
-						),
-					},
-				)* ]
+			pub fn call_functions() -> #frame_support::metadata::PalletCallMetadata {
+				#frame_support::scale_info::meta_type::<#call_ident<#type_use_gen>>().into()
 			}
 		}
 	)
 }
+
+/// Strip the leading space of a doc comment, as the `TypeInfo` derive does for event and error.
+fn doc_metadata(doc: &syn::Lit) -> syn::Lit {
+	match doc {
+		syn::Lit::Str(lit) => {
+			let value = lit.value();
+			let value = value.strip_prefix(' ').unwrap_or(&value);
+			syn::Lit::Str(syn::LitStr::new(value, lit.span()))
+		},
+		lit => lit.clone(),
+	}
+}
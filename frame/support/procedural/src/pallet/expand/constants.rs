@@ -16,8 +16,6 @@
 // limitations under the License.
 
 use crate::pallet::Def;
-use frame_support_procedural_tools::clean_type_string;
-use quote::ToTokens;
 
 struct ConstDef {
 	/// Name of the associated type.
@@ -26,15 +24,14 @@ struct ConstDef {
 	pub type_: syn::Type,
 	/// The doc associated
 	pub doc: Vec<syn::Lit>,
-	/// default_byte implementation
-	pub default_byte_impl: proc_macro2::TokenStream,
+	/// value encoding implementation
+	pub value_impl: proc_macro2::TokenStream,
 }
 
 /// * Impl fn module_constant_metadata for pallet.
 pub fn expand_constants(def: &mut Def) -> proc_macro2::TokenStream {
 	let frame_support = &def.frame_support;
 	let type_impl_gen = &def.type_impl_generics(proc_macro2::Span::call_site());
-	let type_decl_gen = &def.type_decl_generics(proc_macro2::Span::call_site());
 	let type_use_gen = &def.type_use_generics(proc_macro2::Span::call_site());
 	let pallet_ident = &def.pallet_struct.pallet;
 
@@ -50,7 +47,7 @@ pub fn expand_constants(def: &mut Def) -> proc_macro2::TokenStream {
 			ident: const_.ident.clone(),
 			type_: const_.type_.clone(),
 			doc: const_.doc.clone(),
-			default_byte_impl: quote::quote!(
+			value_impl: quote::quote!(
 				let value = <T::#ident as #frame_support::traits::Get<#const_type>>::get();
 				#frame_support::codec::Encode::encode(&value)
 			),
@@ -64,7 +61,7 @@ pub fn expand_constants(def: &mut Def) -> proc_macro2::TokenStream {
 			ident: const_.ident.clone(),
 			type_: const_.type_.clone(),
 			doc: const_.doc.clone(),
-			default_byte_impl: quote::quote!(
+			value_impl: quote::quote!(
 				let value = <Pallet<#type_use_gen>>::#ident();
 				#frame_support::codec::Encode::encode(&value)
 			),
@@ -74,52 +71,16 @@ pub fn expand_constants(def: &mut Def) -> proc_macro2::TokenStream {
 	let consts = config_consts.chain(extra_consts)
 		.map(|const_| {
 			let const_type = &const_.type_;
-			let const_type_str = clean_type_string(&const_type.to_token_stream().to_string());
-			let ident = &const_.ident;
-			let ident_str = format!("{}", ident);
+			let ident_str = format!("{}", const_.ident);
 			let doc = const_.doc.clone().into_iter();
-			let default_byte_impl = &const_.default_byte_impl;
-			let default_byte_getter = syn::Ident::new(
-				&format!("{}DefaultByteGetter", ident),
-				ident.span()
-			);
+			let value_impl = &const_.value_impl;
 
 			quote::quote!({
-				#[allow(non_upper_case_types)]
-				#[allow(non_camel_case_types)]
-				struct #default_byte_getter<#type_decl_gen>(
-					#frame_support::sp_std::marker::PhantomData<(#type_use_gen)>
-				);
-
-				impl<#type_impl_gen> #frame_support::dispatch::DefaultByte for
-					#default_byte_getter<#type_use_gen>
-					#completed_where_clause
-				{
-					fn default_byte(&self) -> #frame_support::sp_std::vec::Vec<u8> {
-						#default_byte_impl
-					}
-				}
-
-				unsafe impl<#type_impl_gen> Send for #default_byte_getter<#type_use_gen>
-					#completed_where_clause
-				{}
-				unsafe impl<#type_impl_gen> Sync for #default_byte_getter<#type_use_gen>
-					#completed_where_clause
-				{}
-
-				#frame_support::dispatch::ModuleConstantMetadata {
-					name: #frame_support::dispatch::DecodeDifferent::Encode(#ident_str),
-					ty: #frame_support::dispatch::DecodeDifferent::Encode(#const_type_str),
-					value: #frame_support::dispatch::DecodeDifferent::Encode(
-						#frame_support::dispatch::DefaultByteGetter(
-							&#default_byte_getter::<#type_use_gen>(
-								#frame_support::sp_std::marker::PhantomData
-							)
-						)
-					),
-					documentation: #frame_support::dispatch::DecodeDifferent::Encode(
-						&[ #( #doc ),* ]
-					),
+				#frame_support::metadata::PalletConstantMetadata {
+					name: #ident_str,
+					ty: #frame_support::scale_info::meta_type::<#const_type>(),
+					value: { #value_impl },
+					docs: #frame_support::sp_std::vec![ #( #doc ),* ],
 				}
 			})
 		});
@@ -129,9 +90,9 @@ pub fn expand_constants(def: &mut Def) -> proc_macro2::TokenStream {
 
 			#[doc(hidden)]
 			pub fn module_constants_metadata()
-				-> &'static [#frame_support::dispatch::ModuleConstantMetadata]
+				-> #frame_support::sp_std::vec::Vec<#frame_support::metadata::PalletConstantMetadata>
 			{
-				&[ #( #consts ),* ]
+				#frame_support::sp_std::vec![ #( #consts ),* ]
 			}
 		}
 	)
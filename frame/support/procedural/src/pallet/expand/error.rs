@@ -18,7 +18,7 @@
 use crate::pallet::{Def, parse::helper::get_doc_literals};
 
 /// * impl various trait on Error
-/// * impl ModuleErrorMetadata for Error
+/// * derive TypeInfo for Error
 pub fn expand_error(def: &mut Def) -> proc_macro2::TokenStream {
 	let error = if let Some(error) = &def.error {
 		error
@@ -35,6 +35,7 @@ pub fn expand_error(def: &mut Def) -> proc_macro2::TokenStream {
 
 	let phantom_variant: syn::Variant = syn::parse_quote!(
 		#[doc(hidden)]
+		#[codec(skip)]
 		__Ignore(
 			#frame_support::sp_std::marker::PhantomData<(#type_use_gen)>,
 			#frame_support::Never,
@@ -52,17 +53,6 @@ pub fn expand_error(def: &mut Def) -> proc_macro2::TokenStream {
 			quote::quote_spanned!(error.attr_span => Self::#variant => #variant_str,)
 		});
 
-	let metadata = error.variants.iter()
-		.map(|(variant, doc)| {
-			let variant_str = format!("{}", variant);
-			quote::quote_spanned!(error.attr_span =>
-				#frame_support::error::ErrorMetadata {
-					name: #frame_support::error::DecodeDifferent::Encode(#variant_str),
-					documentation: #frame_support::error::DecodeDifferent::Encode(&[ #( #doc, )* ]),
-				},
-			)
-		});
-
 	let error_item = {
 		let item = &mut def.item.content.as_mut().expect("Checked by def parser").1[error.index];
 		if let syn::Item::Enum(item) = item {
@@ -74,6 +64,18 @@ pub fn expand_error(def: &mut Def) -> proc_macro2::TokenStream {
 
 	error_item.variants.insert(0, phantom_variant);
 
+	let type_params = if def.config.has_instance {
+		quote::quote!(T, I)
+	} else {
+		quote::quote!(T)
+	};
+	error_item.attrs.push(syn::parse_quote!(
+		#[derive(#frame_support::scale_info::TypeInfo)]
+	));
+	error_item.attrs.push(syn::parse_quote!(
+		#[scale_info(skip_type_params(#type_params), capture_docs = "always")]
+	));
+
 	if get_doc_literals(&error_item.attrs).is_empty() {
 		error_item.attrs.push(syn::parse_quote!(
 			#[doc = r"
@@ -136,14 +138,5 @@ pub fn expand_error(def: &mut Def) -> proc_macro2::TokenStream {
 				}
 			}
 		}
-
-		impl<#type_impl_gen> #frame_support::error::ModuleErrorMetadata
-			for #error_ident<#type_use_gen>
-			#config_where_clause
-		{
-			fn metadata() -> &'static [#frame_support::error::ErrorMetadata] {
-				&[ #( #metadata )* ]
-			}
-		}
 	)
 }
@@ -18,7 +18,6 @@
 use super::helper;
 use syn::spanned::Spanned;
 use quote::ToTokens;
-use frame_support_procedural_tools::clean_type_string;
 
 /// List of additional token to be used for parsing.
 mod keyword {
@@ -35,8 +34,6 @@ pub struct EventDef {
 	pub index: usize,
 	/// The keyword Event used (contains span).
 	pub event: keyword::Event,
-	/// Event metadatas: `(name, args, docs)`.
-	pub metadata: Vec<(syn::Ident, Vec<String>, Vec<syn::Lit>)>,
 	/// A set of usage of instance, must be check for consistency with trait.
 	pub instances: Vec<helper::InstanceUsage>,
 	/// The kind of generic the type `Event` has.
@@ -165,7 +162,9 @@ impl EventDef {
 
 		let event_attrs: Vec<PalletEventAttr> = helper::take_item_pallet_attrs(&mut item.attrs)?;
 		let attr_info = PalletEventAttrInfo::from_attrs(event_attrs)?;
-		let metadata = attr_info.metadata.unwrap_or_else(Vec::new);
+		// The metadata names are kept for backward compatibility only, the event type information
+		// is now provided by `scale_info::TypeInfo`.
+		let _ = attr_info.metadata;
 		let deposit_event = attr_info.deposit_event;
 
 		if !matches!(item.vis, syn::Visibility::Public(_)) {
@@ -195,28 +194,9 @@ impl EventDef {
 
 		let event = syn::parse2::<keyword::Event>(item.ident.to_token_stream())?;
 
-		let metadata = item.variants.iter()
-			.map(|variant| {
-				let name = variant.ident.clone();
-				let docs = helper::get_doc_literals(&variant.attrs);
-				let args = variant.fields.iter()
-					.map(|field| {
-						metadata.iter().find(|m| m.0 == field.ty)
-							.map(|m| m.1.clone())
-							.unwrap_or_else(|| {
-								clean_type_string(&field.ty.to_token_stream().to_string())
-							})
-					})
-					.collect();
-
-				(name, args, docs)
-			})
-			.collect();
-
 		Ok(EventDef {
 			attr_span,
 			index,
-			metadata,
 			instances,
 			deposit_event,
 			event,
@@ -20,10 +20,7 @@
 
 pub use crate::sp_std::{result, fmt, prelude::{Vec, Clone, Eq, PartialEq}, marker};
 pub use crate::codec::{Codec, EncodeLike, Decode, Encode, Input, Output, HasCompact, EncodeAsRef};
-pub use frame_metadata::{
-	FunctionMetadata, DecodeDifferent, DecodeDifferentArray, FunctionArgumentMetadata,
-	ModuleConstantMetadata, DefaultByte, DefaultByteGetter, ModuleErrorMetadata, ErrorMetadata
-};
+pub use crate::metadata::{PalletCallMetadata, PalletConstantMetadata, PalletErrorMetadata};
 pub use crate::weights::{
 	GetDispatchInfo, DispatchInfo, WeighData, ClassifyDispatch, TransactionPriority, Weight,
 	PaysFee, PostDispatchInfo, WithPostDispatchInfo,
@@ -61,8 +58,8 @@ pub type CallableCallFor<A, R> = <A as Callable<R>>::Call;
 /// A type that can be used as a parameter in a dispatchable function.
 ///
 /// When using `decl_module` all arguments for call functions must implement this trait.
-pub trait Parameter: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
-impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug {}
+pub trait Parameter: Codec + EncodeLike + Clone + Eq + fmt::Debug + scale_info::TypeInfo {}
+impl<T> Parameter for T where T: Codec + EncodeLike + Clone + Eq + fmt::Debug + scale_info::TypeInfo {}
 
 /// Declares a `Module` struct and a `Call` enum, which implements the dispatch logic.
 ///
@@ -1807,7 +1804,8 @@ macro_rules! decl_module {
 		/// Dispatchable calls.
 		///
 		/// Each variant of this enum maps to a dispatchable function from the associated module.
-		#[derive($crate::codec::Encode, $crate::codec::Decode)]
+		#[derive($crate::codec::Encode, $crate::codec::Decode, $crate::scale_info::TypeInfo)]
+		#[scale_info(skip_type_params($trait_instance $(, $instance)?), capture_docs = "always")]
 		pub enum $call_type<$trait_instance: $trait_name$(<I>, $instance: $instantiable $( = $module_default_instance)?)?>
 			where $( $other_where_bounds )*
 		{
@@ -2138,26 +2136,17 @@ macro_rules! decl_module {
 		$crate::__dispatch_impl_metadata! {
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>
 			{ $( $other_where_bounds )* }
-			$call_type $origin_type
-			{
-				$(
-					$(#[doc = $doc_attr])*
-					fn $fn_name($from $(, $(#[$codec_attr])* $param_name : $param )*);
-				)*
-			}
+			$call_type
 		}
 		$crate::__impl_module_constants_metadata ! {
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>
 			{ $( $other_where_bounds )* }
 			$( $constants )*
 		}
-
-		impl<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?> $crate::dispatch::ModuleErrorMetadata
-			for $mod_type<$trait_instance $(, $instance)?> where $( $other_where_bounds )*
-		{
-			fn metadata() -> &'static [$crate::dispatch::ErrorMetadata] {
-				<$error_type as $crate::dispatch::ModuleErrorMetadata>::metadata()
-			}
+		$crate::__impl_error_metadata! {
+			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>
+			{ $( $other_where_bounds )* }
+			$error_type
 		}
 	}
 }
@@ -2178,6 +2167,7 @@ macro_rules! impl_outer_dispatch {
 			Clone, PartialEq, Eq,
 			$crate::codec::Encode,
 			$crate::codec::Decode,
+			$crate::scale_info::TypeInfo,
 			$crate::RuntimeDebug,
 		)]
 		pub enum $call_type {
@@ -2320,15 +2310,15 @@ macro_rules! __dispatch_impl_metadata {
 	(
 		$mod_type:ident<$trait_instance:ident: $trait_name:ident$(<I>, $instance:ident: $instantiable:path)?>
 		{ $( $other_where_bounds:tt )* }
-		$($rest:tt)*
+		$call_type:ident
 	) => {
-		impl<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?> $mod_type<$trait_instance $(, $instance)?>
-			where $( $other_where_bounds )*
+		impl<$trait_instance: 'static + $trait_name $(<I>, $instance: $instantiable)?>
+			$mod_type<$trait_instance $(, $instance)?> where $( $other_where_bounds )*
 		{
 			#[doc(hidden)]
 			#[allow(dead_code)]
-			pub fn call_functions() -> &'static [$crate::dispatch::FunctionMetadata] {
-				$crate::__call_to_functions!($($rest)*)
+			pub fn call_functions() -> $crate::dispatch::PalletCallMetadata {
+				$crate::scale_info::meta_type::<$call_type<$trait_instance $(, $instance)?>>().into()
 			}
 		}
 	}
@@ -2338,62 +2328,12 @@ macro_rules! __dispatch_impl_metadata {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __impl_module_constants_metadata {
-	// Without instance
-	(
-		$mod_type:ident<$trait_instance:ident: $trait_name:ident>
-		{ $( $other_where_bounds:tt )* }
-		$(
-			$( #[doc = $doc_attr:tt] )*
-			$name:ident: $type:ty = $value:expr;
-		)*
-	) => {
-		$crate::paste::item! {
-			$crate::__impl_module_constants_metadata! {
-				GENERATE_CODE
-				$mod_type<$trait_instance: $trait_name>
-				{ $( $other_where_bounds )* }
-				$(
-					$( #[doc = $doc_attr] )*
-					[< $name DefaultByteGetter >]
-					$name<$trait_instance: $trait_name>: $type = $value;
-				)*
-			}
-		}
-	};
-	// With instance
 	(
-		$mod_type:ident<$trait_instance:ident: $trait_name:ident<I>, $instance:ident: $instantiable:path>
-		{ $( $other_where_bounds:tt )* }
-		$(
-			$( #[doc = $doc_attr:tt] )*
-			$name:ident: $type:ty = $value:expr;
-		)*
-	) => {
-		$crate::paste::item! {
-			$crate::__impl_module_constants_metadata! {
-				GENERATE_CODE
-				$mod_type<$trait_instance: $trait_name<I>, $instance: $instantiable>
-				{ $( $other_where_bounds )* }
-				$(
-					$( #[doc = $doc_attr] )*
-					[< $name DefaultByteGetter >]
-					$name<$trait_instance: $trait_name<I>, $instance: $instantiable>: $type = $value;
-				)*
-			}
-		}
-	};
-	// Do the code generation
-	(GENERATE_CODE
 		$mod_type:ident<$trait_instance:ident: $trait_name:ident $(<I>, $instance:ident: $instantiable:path)?>
 		{ $( $other_where_bounds:tt )* }
 		$(
 			$( #[doc = $doc_attr:tt] )*
-			$default_byte_name:ident
-			$name:ident<
-				$const_trait_instance:ident: $const_trait_name:ident $(
-					<I>, $const_instance:ident: $const_instantiable:path
-				)*
-			>: $type:ty = $value:expr;
+			$name:ident: $type:ty = $value:expr;
 		)*
 	) => {
 		impl<$trait_instance: 'static + $trait_name $(<I>, $instance: $instantiable)?>
@@ -2401,54 +2341,18 @@ macro_rules! __impl_module_constants_metadata {
 		{
 			#[doc(hidden)]
 			#[allow(dead_code)]
-			pub fn module_constants_metadata() -> &'static [$crate::dispatch::ModuleConstantMetadata] {
-				// Create the `ByteGetter`s
-				$(
-					#[allow(non_upper_case_types)]
-					#[allow(non_camel_case_types)]
-					struct $default_byte_name<
-						$const_trait_instance: $const_trait_name $(
-							<I>, $const_instance: $const_instantiable
-						)?
-					>($crate::dispatch::marker::PhantomData<
-						($const_trait_instance, $( $const_instance)?)
-					>);
-					impl<$const_trait_instance: 'static + $const_trait_name $(
-						<I>, $const_instance: $const_instantiable)?
-					> $crate::dispatch::DefaultByte
-						for $default_byte_name <$const_trait_instance $(, $const_instance)?>
-					{
-						fn default_byte(&self) -> $crate::dispatch::Vec<u8> {
-							let value: $type = $value;
-							$crate::dispatch::Encode::encode(&value)
-						}
-					}
-
-					unsafe impl<$const_trait_instance: 'static + $const_trait_name $(
-						<I>, $const_instance: $const_instantiable)?
-					> Send for $default_byte_name <$const_trait_instance $(, $const_instance)?> {}
-
-					unsafe impl<$const_trait_instance: 'static + $const_trait_name $(
-						<I>, $const_instance: $const_instantiable)?
-					> Sync for $default_byte_name <$const_trait_instance $(, $const_instance)?> {}
-				)*
-				&[
+			pub fn module_constants_metadata(
+			) -> $crate::dispatch::Vec<$crate::dispatch::PalletConstantMetadata> {
+				$crate::sp_std::vec![
 					$(
-						$crate::dispatch::ModuleConstantMetadata {
-							name: $crate::dispatch::DecodeDifferent::Encode(stringify!($name)),
-							ty: $crate::dispatch::DecodeDifferent::Encode(stringify!($type)),
-							value: $crate::dispatch::DecodeDifferent::Encode(
-								$crate::dispatch::DefaultByteGetter(
-									&$default_byte_name::<
-										$const_trait_instance $(, $const_instance)?
-									>(
-										$crate::dispatch::marker::PhantomData
-									)
-								)
-							),
-							documentation: $crate::dispatch::DecodeDifferent::Encode(
-								&[ $( $doc_attr ),* ]
-							),
+						$crate::dispatch::PalletConstantMetadata {
+							name: stringify!($name),
+							ty: $crate::scale_info::meta_type::<$type>(),
+							value: {
+								let value: $type = $value;
+								$crate::dispatch::Encode::encode(&value)
+							},
+							docs: $crate::sp_std::vec![ $( $doc_attr ),* ],
 						}
 					),*
 				]
@@ -2457,104 +2361,24 @@ macro_rules! __impl_module_constants_metadata {
 	}
 }
 
-/// Convert the list of calls into their JSON representation, joined by ",".
+/// Implement metadata for module errors.
 #[macro_export]
 #[doc(hidden)]
-macro_rules! __call_to_functions {
+macro_rules! __impl_error_metadata {
 	(
-		$call_type:ident $origin_type:ty
-		{
-			$(
-				$(#[doc = $doc_attr:tt])*
-				fn $fn_name:ident($from:ident
-					$(
-						, $(#[$codec_attr:ident])* $param_name:ident : $param:ty
-					)*
-				);
-			)*
-		}
-	) => {
-		$crate::__functions_to_metadata!(0; $origin_type;; $(
-			fn $fn_name( $($(#[$codec_attr])* $param_name: $param ),* );
-			$( $doc_attr ),*;
-		)*)
-	};
-}
-
-
-/// Convert a list of functions into a list of `FunctionMetadata` items.
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __functions_to_metadata{
-	(
-		$fn_id:expr;
-		$origin_type:ty;
-		$( $function_metadata:expr ),*;
-		fn $fn_name:ident(
-			$(
-				$(#[$codec_attr:ident])* $param_name:ident : $param:ty
-			),*
-		);
-		$( $fn_doc:expr ),*;
-		$( $rest:tt )*
-	) => {
-		$crate::__functions_to_metadata!(
-			$fn_id + 1; $origin_type;
-			$( $function_metadata, )* $crate::__function_to_metadata!(
-				fn $fn_name($( $(#[$codec_attr])* $param_name : $param ),*); $( $fn_doc ),*; $fn_id;
-			);
-			$($rest)*
-		)
-	};
-	(
-		$fn_id:expr;
-		$origin_type:ty;
-		$( $function_metadata:expr ),*;
-	) => {
-		&[ $( $function_metadata ),* ]
-	}
-}
-
-/// Convert a function into its metadata representation.
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __function_to_metadata {
-	(
-		fn $fn_name:ident(
-			$( $(#[$codec_attr:ident])* $param_name:ident : $param:ty),*
-		);
-		$( $fn_doc:expr ),*;
-		$fn_id:expr;
+		$mod_type:ident<$trait_instance:ident: $trait_name:ident $(<I>, $instance:ident: $instantiable:path)?>
+		{ $( $other_where_bounds:tt )* }
+		$error_type:ty
 	) => {
-		$crate::dispatch::FunctionMetadata {
-			name: $crate::dispatch::DecodeDifferent::Encode(stringify!($fn_name)),
-			arguments: $crate::dispatch::DecodeDifferent::Encode(&[
-				$(
-					$crate::dispatch::FunctionArgumentMetadata {
-						name: $crate::dispatch::DecodeDifferent::Encode(stringify!($param_name)),
-						ty: $crate::dispatch::DecodeDifferent::Encode(
-							$crate::__function_to_metadata!(@stringify_expand_attr
-								$(#[$codec_attr])* $param_name: $param
-							)
-						),
-					}
-				),*
-			]),
-			documentation: $crate::dispatch::DecodeDifferent::Encode(&[ $( $fn_doc ),* ]),
+		impl<$trait_instance: 'static + $trait_name $(<I>, $instance: $instantiable)?>
+			$mod_type<$trait_instance $(, $instance)?> where $( $other_where_bounds )*
+		{
+			#[doc(hidden)]
+			#[allow(dead_code)]
+			pub fn error_metadata() -> Option<$crate::dispatch::PalletErrorMetadata> {
+				Some($crate::scale_info::meta_type::<$error_type>().into())
+			}
 		}
-	};
-
-	(@stringify_expand_attr #[compact] $param_name:ident : $param:ty) => {
-		concat!("Compact<", stringify!($param), ">")
-	};
-
-	(@stringify_expand_attr $param_name:ident : $param:ty) => { stringify!($param) };
-
-	(@stringify_expand_attr $(#[codec_attr:ident])* $param_name:ident : $param:ty) => {
-		compile_error!(concat!(
-			"Invalid attribute for parameter `", stringify!($param_name),
-			"`, the following attributes are supported: `#[compact]`"
-		));
 	}
 }
 
@@ -2636,7 +2460,7 @@ mod tests {
 			type DbWeight: Get<RuntimeDbWeight>;
 		}
 
-		#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode)]
+		#[derive(Clone, PartialEq, Eq, Debug, Encode, Decode, scale_info::TypeInfo)]
 		pub enum RawOrigin<AccountId> {
 			Root,
 			Signed(AccountId),
@@ -2692,73 +2516,50 @@ mod tests {
 		}
 	}
 
-	const EXPECTED_METADATA: &'static [FunctionMetadata] = &[
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("aux_0"),
-			arguments: DecodeDifferent::Encode(&[]),
-			documentation: DecodeDifferent::Encode(&[
-				" Hi, this is a comment."
+	fn expected_metadata() -> scale_info::Type {
+		use scale_info::{build::{Fields, Variants}, Path, Type, TypeParameter};
+
+		Type::builder()
+			.path(Path::new("Call", module_path!()))
+			.type_params(vec![TypeParameter::new("T", None)])
+			.docs_always(&[
+				"Dispatchable calls.",
+				"",
+				"Each variant of this enum maps to a dispatchable function from the associated module.",
 			])
-		},
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("aux_1"),
-			arguments: DecodeDifferent::Encode(&[
-				FunctionArgumentMetadata {
-					name: DecodeDifferent::Encode("_data"),
-					ty: DecodeDifferent::Encode("Compact<u32>")
-				}
-			]),
-			documentation: DecodeDifferent::Encode(&[]),
-		},
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("aux_2"),
-			arguments: DecodeDifferent::Encode(&[
-				FunctionArgumentMetadata {
-					name: DecodeDifferent::Encode("_data"),
-					ty: DecodeDifferent::Encode("i32"),
-				},
-				FunctionArgumentMetadata {
-					name: DecodeDifferent::Encode("_data2"),
-					ty: DecodeDifferent::Encode("String"),
-				}
-			]),
-			documentation: DecodeDifferent::Encode(&[]),
-		},
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("aux_3"),
-			arguments: DecodeDifferent::Encode(&[]),
-			documentation: DecodeDifferent::Encode(&[]),
-		},
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("aux_4"),
-			arguments: DecodeDifferent::Encode(&[
-				FunctionArgumentMetadata {
-					name: DecodeDifferent::Encode("_data"),
-					ty: DecodeDifferent::Encode("i32"),
-				}
-			]),
-			documentation: DecodeDifferent::Encode(&[]),
-		},
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("aux_5"),
-			arguments: DecodeDifferent::Encode(&[
-				FunctionArgumentMetadata {
-					name: DecodeDifferent::Encode("_data"),
-					ty: DecodeDifferent::Encode("i32"),
-				},
-				FunctionArgumentMetadata {
-					name: DecodeDifferent::Encode("_data2"),
-					ty: DecodeDifferent::Encode("Compact<u32>")
-				}
-			]),
-			documentation: DecodeDifferent::Encode(&[]),
-		},
-		FunctionMetadata {
-			name: DecodeDifferent::Encode("operational"),
-			arguments: DecodeDifferent::Encode(&[]),
-			documentation: DecodeDifferent::Encode(&[]),
-		},
-	];
+			.variant(
+				Variants::new()
+					.variant("aux_0", |v| v
+						.index(0)
+						.fields(Fields::unnamed())
+						.docs_always(&["Hi, this is a comment."])
+					)
+					.variant("aux_1", |v| v
+						.index(1)
+						.fields(Fields::unnamed().field(|f| f.compact::<u32>().type_name("u32")))
+					)
+					.variant("aux_2", |v| v
+						.index(2)
+						.fields(Fields::unnamed()
+							.field(|f| f.ty::<i32>().type_name("i32"))
+							.field(|f| f.ty::<String>().type_name("String"))
+						)
+					)
+					.variant("aux_3", |v| v.index(3).fields(Fields::unnamed()))
+					.variant("aux_4", |v| v
+						.index(4)
+						.fields(Fields::unnamed().field(|f| f.ty::<i32>().type_name("i32")))
+					)
+					.variant("aux_5", |v| v
+						.index(5)
+						.fields(Fields::unnamed()
+							.field(|f| f.ty::<i32>().type_name("i32"))
+							.field(|f| f.compact::<u32>().type_name("u32"))
+						)
+					)
+					.variant("operational", |v| v.index(6).fields(Fields::unnamed()))
+			)
+	}
 
 	pub struct TraitImpl {}
 	impl Config for TraitImpl { }
@@ -2807,7 +2608,11 @@ mod tests {
 	#[test]
 	fn module_json_metadata() {
 		let metadata = Module::<TraitImpl>::call_functions();
-		assert_eq!(EXPECTED_METADATA, metadata);
+		assert_eq!(metadata.ty, scale_info::meta_type::<Call<TraitImpl>>());
+		pretty_assertions::assert_eq!(
+			expected_metadata(),
+			<Call<TraitImpl> as scale_info::TypeInfo>::type_info(),
+		);
 	}
 
 	#[test]
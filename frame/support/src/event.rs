@@ -21,8 +21,6 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
-pub use frame_metadata::{EventMetadata, DecodeDifferent, OuterEventMetadata, FnEncode};
-
 /// Implement the `Event` for a module.
 ///
 /// # Simple Event Example:
@@ -129,8 +127,10 @@ macro_rules! decl_event {
 			Clone, PartialEq, Eq,
 			$crate::codec::Encode,
 			$crate::codec::Decode,
+			$crate::scale_info::TypeInfo,
 			$crate::RuntimeDebug,
 		)]
+		#[scale_info(capture_docs = "always")]
 		/// Events for this module.
 		///
 		$(#[$attr])*
@@ -142,13 +142,6 @@ macro_rules! decl_event {
 		impl From<Event> for () {
 			fn from(_: Event) -> () { () }
 		}
-		impl Event {
-			#[allow(dead_code)]
-			#[doc(hidden)]
-			pub fn metadata() -> &'static [ $crate::event::EventMetadata ] {
-				$crate::__events_to_metadata!(; $( $events )* )
-			}
-		}
 	}
 }
 
@@ -272,8 +265,11 @@ macro_rules! __decl_generic_event {
 			Clone, PartialEq, Eq,
 			$crate::codec::Encode,
 			$crate::codec::Decode,
+			$crate::scale_info::TypeInfo,
 			$crate::RuntimeDebug,
 		)]
+		#[scale_info(capture_docs = "always")]
+		$( #[scale_info(skip_type_params($instance))] )?
 		/// Events for this module.
 		///
 		$(#[$attr])*
@@ -290,49 +286,12 @@ macro_rules! __decl_generic_event {
 		impl<$( $generic_param ),* $(, $instance)? > From<RawEvent<$( $generic_param ),* $(, $instance)?>> for () {
 			fn from(_: RawEvent<$( $generic_param ),* $(, $instance)?>) -> () { () }
 		}
-		impl<$( $generic_param ),* $(, $instance)?> RawEvent<$( $generic_param ),* $(, $instance)?> {
-			#[allow(dead_code)]
-			#[doc(hidden)]
-			pub fn metadata() -> &'static [$crate::event::EventMetadata] {
-				$crate::__events_to_metadata!(; $( $events )* )
-			}
-		}
 	};
 	(@cannot_parse $ty:ty) => {
 		compile_error!(concat!("The type `", stringify!($ty), "` can't be parsed as an unnamed one, please name it `Name = ", stringify!($ty), "`"));
 	}
 }
 
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __events_to_metadata {
-	(
-		$( $metadata:expr ),*;
-		$( #[doc = $doc_attr:tt] )*
-		$event:ident $( ( $( $param:path ),* $(,)? ) )*,
-		$( $rest:tt )*
-	) => {
-		$crate::__events_to_metadata!(
-			$( $metadata, )*
-			$crate::event::EventMetadata {
-				name: $crate::event::DecodeDifferent::Encode(stringify!($event)),
-				arguments: $crate::event::DecodeDifferent::Encode(&[
-					$( $( stringify!($param) ),* )*
-				]),
-				documentation: $crate::event::DecodeDifferent::Encode(&[
-					$( $doc_attr ),*
-				]),
-			};
-			$( $rest )*
-		)
-	};
-	(
-		$( $metadata:expr ),*;
-	) => {
-		&[ $( $metadata ),* ]
-	}
-}
-
 /// Constructs an Event type for a runtime. This is usually called automatically by the
 /// construct_runtime macro.
 #[macro_export]
@@ -450,6 +409,7 @@ macro_rules! impl_outer_event {
 				Clone, PartialEq, Eq,
 				$crate::codec::Encode,
 				$crate::codec::Decode,
+				$crate::scale_info::TypeInfo,
 				$crate::RuntimeDebug,
 			)]
 			$(#[$attr])*
@@ -484,73 +444,12 @@ macro_rules! impl_outer_event {
 				}
 			)*
 		}
-		$crate::__impl_outer_event_json_metadata!(
-			$runtime;
-			$name;
-			$(
-				$module_name::Event
-				< $( $generic_param )? $(, $module_name::$generic_instance )? >
-				$( $generic_instance )?,
-			)*;
-		);
-	}
-}
-
-#[macro_export]
-#[doc(hidden)]
-macro_rules! __impl_outer_event_json_metadata {
-	(
-		$runtime:ident;
-		$event_name:ident;
-		$( $module_name:ident::Event < $( $generic_params:path ),* > $( $instance:ident )?, )*;
-	) => {
-		impl $runtime {
-			#[allow(dead_code)]
-			pub fn outer_event_metadata() -> $crate::event::OuterEventMetadata {
-				$crate::event::OuterEventMetadata {
-					name: $crate::event::DecodeDifferent::Encode(stringify!($event_name)),
-					events: $crate::event::DecodeDifferent::Encode(&[
-						$(
-							(
-								stringify!($module_name),
-								$crate::event::FnEncode(
-									$module_name::Event ::< $( $generic_params ),* > ::metadata
-								)
-							)
-						),*
-					])
-				}
-			}
-
-			$crate::__impl_outer_event_json_metadata! {
-				@DECL_MODULE_EVENT_FNS
-				$( $module_name < $( $generic_params ),* > $( $instance )? ; )*
-			}
-		}
-	};
-
-	(@DECL_MODULE_EVENT_FNS
-		$(
-			$module_name:ident < $( $generic_params:path ),* > $( $instance:ident )? ;
-		)*
-	) => {
-		$crate::paste::item! {
-			$(
-				#[allow(dead_code)]
-				pub fn [< __module_events_ $module_name $( _ $instance )? >] () ->
-					&'static [$crate::event::EventMetadata]
-				{
-					$module_name::Event ::< $( $generic_params ),* > ::metadata()
-				}
-			)*
-		}
 	}
 }
 
 #[cfg(test)]
 #[allow(dead_code)]
 mod tests {
-	use super::*;
 	use serde::Serialize;
 	use codec::{Encode, Decode};
 
@@ -755,65 +654,47 @@ mod tests {
 		type DbWeight = ();
 	}
 
-	const EXPECTED_METADATA: OuterEventMetadata = OuterEventMetadata {
-		name: DecodeDifferent::Encode("TestEvent"),
-		events: DecodeDifferent::Encode(&[
-			(
-				"system",
-				FnEncode(|| &[
-					EventMetadata {
-						name: DecodeDifferent::Encode("SystemEvent"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[]),
-					}
-				])
-			),
-			(
-				"event_module",
-				FnEncode(|| &[
-					EventMetadata {
-						name: DecodeDifferent::Encode("TestEvent"),
-						arguments: DecodeDifferent::Encode(&[ "Balance", "Origin" ]),
-						documentation: DecodeDifferent::Encode(&[ " Hi, I am a comment." ])
-					},
-					EventMetadata {
-						name: DecodeDifferent::Encode("EventWithoutParams"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[ " Dog" ]),
-					},
-				])
-			),
-			(
-				"event_module2",
-				FnEncode(|| &[
-					EventMetadata {
-						name: DecodeDifferent::Encode("TestEvent"),
-						arguments: DecodeDifferent::Encode(&[ "BalanceRenamed" ]),
-						documentation: DecodeDifferent::Encode(&[])
-					},
-					EventMetadata {
-						name: DecodeDifferent::Encode("TestOrigin"),
-						arguments: DecodeDifferent::Encode(&[ "OriginRenamed" ]),
-						documentation: DecodeDifferent::Encode(&[]),
-					},
-				])
-			),
-			(
-				"event_module3",
-				FnEncode(|| &[
-					EventMetadata {
-						name: DecodeDifferent::Encode("HiEvent"),
-						arguments: DecodeDifferent::Encode(&[]),
-						documentation: DecodeDifferent::Encode(&[])
-					}
-				])
-			)
-		])
-	};
+	fn variants<T: scale_info::TypeInfo>() -> Vec<scale_info::Variant> {
+		match T::type_info().type_def() {
+			scale_info::TypeDef::Variant(variant) => variant.variants().to_vec(),
+			_ => panic!("Events are described as enums; qed"),
+		}
+	}
 
 	#[test]
 	fn outer_event_metadata() {
-		assert_eq!(EXPECTED_METADATA, TestRuntime::outer_event_metadata());
+		let outer = variants::<TestEvent>();
+		assert_eq!(
+			outer.iter().map(|v| (*v.name(), v.index())).collect::<Vec<_>>(),
+			vec![("system", 0), ("event_module", 1), ("event_module2", 2), ("event_module3", 3)],
+		);
+		assert_eq!(
+			outer[1].fields()[0].ty(),
+			&scale_info::meta_type::<event_module::Event<TestRuntime>>(),
+		);
+
+		let renamed = variants::<TestEventSystemRenamed>();
+		assert_eq!(
+			renamed.iter().map(|v| (*v.name(), v.index())).collect::<Vec<_>>(),
+			vec![
+				("system_renamed", 0),
+				("event_module", 1),
+				("event_module2", 5),
+				("event_module3", 3),
+			],
+		);
+
+		let events = variants::<event_module::Event<TestRuntime>>();
+		assert_eq!(events.len(), 2);
+		assert_eq!(*events[0].name(), "TestEvent");
+		assert_eq!(events[0].docs(), &["Hi, I am a comment."]);
+		assert_eq!(
+			events[0].fields().iter().map(|f| f.ty().clone()).collect::<Vec<_>>(),
+			vec![scale_info::meta_type::<u32>(), scale_info::meta_type::<u32>()],
+		);
+		assert_eq!(*events[1].name(), "EventWithoutParams");
+		assert_eq!(events[1].docs(), &["Dog"]);
+		assert!(events[1].fields().is_empty());
 	}
 
 	#[test]
@@ -26,6 +26,7 @@ use crate::{
 	traits::{Get, MaxEncodedLen},
 };
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 
 /// A bounded set based on a B-Tree.
 ///
@@ -34,7 +35,8 @@ use codec::{Encode, Decode};
 ///
 /// Unlike a standard `BTreeSet`, there is an enforced upper limit to the number of items in the
 /// set. All internal operations ensure this bound is respected.
-#[derive(Encode)]
+#[derive(Encode, TypeInfo)]
+#[scale_info(skip_type_params(S))]
 pub struct BoundedBTreeSet<T, S>(BTreeSet<T>, PhantomData<S>);
 
 impl<T, S> Decode for BoundedBTreeSet<T, S>
@@ -21,6 +21,7 @@
 use sp_std::prelude::*;
 use sp_std::{convert::TryFrom, fmt, marker::PhantomData};
 use codec::{Encode, Decode, EncodeLike};
+use scale_info::TypeInfo;
 use core::{
 	ops::{Deref, Index, IndexMut},
 	slice::SliceIndex,
@@ -37,7 +38,8 @@ use crate::{
 ///
 /// As the name suggests, the length of the queue is always bounded. All internal operations ensure
 /// this bound is respected.
-#[derive(Encode)]
+#[derive(Encode, TypeInfo)]
+#[scale_info(skip_type_params(S))]
 pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
 
 /// A bounded slice.
@@ -86,6 +86,16 @@ pub fn exists(key: &[u8]) -> bool {
 	sp_io::storage::exists(key)
 }
 
+/// Check to see if any key starting with `prefix` has an explicit entry in storage.
+///
+/// An entry under `prefix` itself is not taken into account.
+pub fn contains_prefixed_key(prefix: &[u8]) -> bool {
+	match sp_io::storage::next_key(prefix) {
+		Some(key) => key.starts_with(prefix),
+		None => false,
+	}
+}
+
 /// Ensure `key` has no explicit entry in storage.
 pub fn kill(key: &[u8]) {
 	sp_io::storage::clear(key);
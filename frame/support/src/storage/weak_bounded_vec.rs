@@ -21,6 +21,7 @@
 use sp_std::prelude::*;
 use sp_std::{convert::TryFrom, fmt, marker::PhantomData};
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use core::{
 	ops::{Deref, Index, IndexMut},
 	slice::SliceIndex,
@@ -37,7 +38,8 @@ use crate::{
 ///
 /// The length of the vec is not strictly bounded. Decoding a vec with more element that the bound
 /// is accepted, and some method allow to bypass the restriction with warnings.
-#[derive(Encode)]
+#[derive(Encode, TypeInfo)]
+#[scale_info(skip_type_params(S))]
 pub struct WeakBoundedVec<T, S>(Vec<T>, PhantomData<S>);
 
 impl<T: Decode, S: Get<u32>> Decode for WeakBoundedVec<T, S> {
@@ -19,7 +19,9 @@
 
 use sp_std::{prelude::*, fmt::Debug};
 use codec::{Encode, Decode, Codec, EncodeLike};
+use scale_info::TypeInfo;
 use sp_runtime::{RuntimeDebug, DispatchError};
+use super::PreimageProvider;
 
 /// Information relating to the period of a scheduled task. First item is the length of the
 /// period and the second is the number of times it should be executed in total before the task
@@ -31,7 +33,7 @@ pub type Period<BlockNumber> = (BlockNumber, u32);
 pub type Priority = u8;
 
 /// The dispatch time of a scheduled task.
-#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub enum DispatchTime<BlockNumber> {
 	/// At specified block.
 	At(BlockNumber),
@@ -48,86 +50,257 @@ pub const HARD_DEADLINE: Priority = 63;
 /// The lowest priority. Most stuff should be around here.
 pub const LOWEST_PRIORITY: Priority = 255;
 
-/// A type that can be used as a scheduler.
-pub trait Anon<BlockNumber, Call, Origin> {
-	/// An address which can be used for removing a scheduled task.
-	type Address: Codec + Clone + Eq + EncodeLike + Debug;
-
-	/// Schedule a dispatch to happen at the beginning of some block in the future.
-	///
-	/// This is not named.
-	fn schedule(
-		when: DispatchTime<BlockNumber>,
-		maybe_periodic: Option<Period<BlockNumber>>,
-		priority: Priority,
-		origin: Origin,
-		call: Call
-	) -> Result<Self::Address, DispatchError>;
-
-	/// Cancel a scheduled task. If periodic, then it will cancel all further instances of that,
-	/// also.
-	///
-	/// Will return an error if the `address` is invalid.
-	///
-	/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
-	/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
-	///
-	/// NOTE2: This will not work to cancel periodic tasks after their initial execution. For
-	/// that, you must name the task explicitly using the `Named` trait.
-	fn cancel(address: Self::Address) -> Result<(), ()>;
-
-	/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
-	/// only if it is executed *before* the currently scheduled block. For periodic tasks,
-	/// this dispatch is guaranteed to succeed only before the *initial* execution; for
-	/// others, use `reschedule_named`.
-	///
-	/// Will return an error if the `address` is invalid.
-	fn reschedule(
-		address: Self::Address,
-		when: DispatchTime<BlockNumber>,
-	) -> Result<Self::Address, DispatchError>;
-
-	/// Return the next dispatch time for a given task.
-	///
-	/// Will return an error if the `address` is invalid.
-	fn next_dispatch_time(address: Self::Address) -> Result<BlockNumber, ()>;
+/// Type that represents either the encoded value of some `T` or the hash of it.
+///
+/// The hash form is resolved against a `PreimageProvider` when the value is needed.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum MaybeHashed<T, Hash> {
+	/// The value itself.
+	Value(T),
+	/// The hash of the encoded value which this value represents.
+	Hash(Hash),
 }
 
-/// A type that can be used as a scheduler.
-pub trait Named<BlockNumber, Call, Origin> {
-	/// An address which can be used for removing a scheduled task.
-	type Address: Codec + Clone + Eq + EncodeLike + sp_std::fmt::Debug;
-
-	/// Schedule a dispatch to happen at the beginning of some block in the future.
-	///
-	/// - `id`: The identity of the task. This must be unique and will return an error if not.
-	fn schedule_named(
-		id: Vec<u8>,
-		when: DispatchTime<BlockNumber>,
-		maybe_periodic: Option<Period<BlockNumber>>,
-		priority: Priority,
-		origin: Origin,
-		call: Call
-	) -> Result<Self::Address, ()>;
-
-	/// Cancel a scheduled, named task. If periodic, then it will cancel all further instances
-	/// of that, also.
-	///
-	/// Will return an error if the `id` is invalid.
-	///
-	/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
-	/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
-	fn cancel_named(id: Vec<u8>) -> Result<(), ()>;
-
-	/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
-	/// only if it is executed *before* the currently scheduled block.
-	fn reschedule_named(
-		id: Vec<u8>,
-		when: DispatchTime<BlockNumber>,
-	) -> Result<Self::Address, DispatchError>;
-
-	/// Return the next dispatch time for a given task.
-	///
-	/// Will return an error if the `id` is invalid.
-	fn next_dispatch_time(id: Vec<u8>) -> Result<BlockNumber, ()>;
+impl<T, H> From<T> for MaybeHashed<T, H> {
+	fn from(t: T) -> Self {
+		MaybeHashed::Value(t)
+	}
 }
+
+/// Error type for `MaybeHashed::lookup`.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum LookupError {
+	/// A call of this hash was not known.
+	Unknown,
+	/// The preimage for this hash was known but could not be decoded into a `Call`.
+	BadFormat,
+}
+
+impl<T: Decode, H> MaybeHashed<T, H> {
+	/// Returns the value if it is not hashed.
+	pub fn as_value(&self) -> Option<&T> {
+		match &self {
+			Self::Value(c) => Some(c),
+			Self::Hash(_) => None,
+		}
+	}
+
+	/// Returns the hash if this is the hashed form.
+	pub fn as_hash(&self) -> Option<&H> {
+		match &self {
+			Self::Value(_) => None,
+			Self::Hash(h) => Some(h),
+		}
+	}
+
+	/// Request the preimage of the hash from `P`, if this is the hashed form.
+	pub fn ensure_requested<P: PreimageProvider<H>>(&self) {
+		if let Self::Hash(hash) = self {
+			P::request_preimage(hash)
+		}
+	}
+
+	/// Cancel the preimage request of the hash from `P`, if this is the hashed form.
+	pub fn ensure_unrequested<P: PreimageProvider<H>>(&self) {
+		if let Self::Hash(hash) = self {
+			P::unrequest_preimage(hash)
+		}
+	}
+
+	/// Look up the value, resolving the hash against the preimages known to `P` if needed.
+	pub fn lookup<P: PreimageProvider<H>>(&self) -> Result<T, LookupError> where T: Clone {
+		match self {
+			Self::Value(c) => Ok(c.clone()),
+			Self::Hash(hash) => {
+				let data = P::get_preimage(hash).ok_or(LookupError::Unknown)?;
+				T::decode(&mut &data[..]).map_err(|_| LookupError::BadFormat)
+			},
+		}
+	}
+}
+
+/// Scheduler traits taking the call to be dispatched by value.
+pub mod v1 {
+	use super::*;
+
+	/// A type that can be used as a scheduler.
+	pub trait Anon<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + Debug;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// This is not named.
+		fn schedule(
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: Call
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Cancel a scheduled task. If periodic, then it will cancel all further instances of that,
+		/// also.
+		///
+		/// Will return an error if the `address` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		///
+		/// NOTE2: This will not work to cancel periodic tasks after their initial execution. For
+		/// that, you must name the task explicitly using the `Named` trait.
+		fn cancel(address: Self::Address) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block. For periodic tasks,
+		/// this dispatch is guaranteed to succeed only before the *initial* execution; for
+		/// others, use `reschedule_named`.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn reschedule(
+			address: Self::Address,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn next_dispatch_time(address: Self::Address) -> Result<BlockNumber, ()>;
+	}
+
+	/// A type that can be used as a scheduler.
+	pub trait Named<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + Debug;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// - `id`: The identity of the task. This must be unique and will return an error if not.
+		fn schedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: Call
+		) -> Result<Self::Address, ()>;
+
+		/// Cancel a scheduled, named task. If periodic, then it will cancel all further instances
+		/// of that, also.
+		///
+		/// Will return an error if the `id` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		fn cancel_named(id: Vec<u8>) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block.
+		fn reschedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `id` is invalid.
+		fn next_dispatch_time(id: Vec<u8>) -> Result<BlockNumber, ()>;
+	}
+}
+
+/// Scheduler traits taking the call to be dispatched by value or by hash.
+pub mod v2 {
+	use super::*;
+
+	/// A type that can be used as a scheduler, allowing calls to be given by the hash of their
+	/// encoding.
+	pub trait Anon<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + Debug;
+		/// A means of expressing a call by the hash of its encoded data.
+		type Hash;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// This is not named. If `call` is given by hash, its preimage is looked up when it is due.
+		fn schedule(
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: MaybeHashed<Call, Self::Hash>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Cancel a scheduled task. If periodic, then it will cancel all further instances of that,
+		/// also.
+		///
+		/// Will return an error if the `address` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		///
+		/// NOTE2: This will not work to cancel periodic tasks after their initial execution. For
+		/// that, you must name the task explicitly using the `Named` trait.
+		fn cancel(address: Self::Address) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block. For periodic tasks,
+		/// this dispatch is guaranteed to succeed only before the *initial* execution; for
+		/// others, use `reschedule_named`.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn reschedule(
+			address: Self::Address,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn next_dispatch_time(address: Self::Address) -> Result<BlockNumber, ()>;
+	}
+
+	/// A type that can be used as a scheduler, allowing calls to be given by the hash of their
+	/// encoding.
+	pub trait Named<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + Debug;
+		/// A means of expressing a call by the hash of its encoded data.
+		type Hash;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// - `id`: The identity of the task. This must be unique and will return an error if not.
+		fn schedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: MaybeHashed<Call, Self::Hash>,
+		) -> Result<Self::Address, ()>;
+
+		/// Cancel a scheduled, named task. If periodic, then it will cancel all further instances
+		/// of that, also.
+		///
+		/// Will return an error if the `id` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		fn cancel_named(id: Vec<u8>) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block.
+		fn reschedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `id` is invalid.
+		fn next_dispatch_time(id: Vec<u8>) -> Result<BlockNumber, ()>;
+	}
+}
+
+pub use v1::*;
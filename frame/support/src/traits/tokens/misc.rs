@@ -19,6 +19,7 @@
 
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode, FullCodec};
+use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 use sp_arithmetic::traits::{Zero, AtLeast32BitUnsigned};
 use sp_runtime::{DispatchError, ArithmeticError, TokenError};
@@ -116,7 +117,7 @@ pub enum ExistenceRequirement {
 }
 
 /// Status of funds.
-#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum BalanceStatus {
 	/// Funds are free, as corresponding to `free` item in Balances.
 	Free,
@@ -161,9 +162,9 @@ impl WithdrawReasons {
 }
 
 /// Simple amalgamation trait to collect together properties for an AssetId under one roof.
-pub trait AssetId: FullCodec + Copy + Default + Eq + PartialEq + Debug {}
-impl<T: FullCodec + Copy + Default + Eq + PartialEq + Debug> AssetId for T {}
+pub trait AssetId: FullCodec + Copy + Default + Eq + PartialEq + Debug + TypeInfo {}
+impl<T: FullCodec + Copy + Default + Eq + PartialEq + Debug + TypeInfo> AssetId for T {}
 
 /// Simple amalgamation trait to collect together properties for a Balance under one roof.
-pub trait Balance: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug {}
-impl<T: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug> Balance for T {}
+pub trait Balance: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug + TypeInfo {}
+impl<T: AtLeast32BitUnsigned + FullCodec + Copy + Default + Debug + TypeInfo> Balance for T {}
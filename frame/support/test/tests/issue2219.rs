@@ -18,6 +18,7 @@
 use frame_support::sp_runtime::generic;
 use frame_support::sp_runtime::traits::{BlakeTwo256, Verify};
 use frame_support::codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_core::{H256, sr25519};
 use serde::{Serialize, Deserialize};
 
@@ -33,12 +34,13 @@ mod module {
 	);
 	pub type Requests<T> = Vec<Request<T>>;
 
-	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug)]
+	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug, TypeInfo)]
 	pub enum Role {
 		Storage,
 	}
 
-	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug)]
+	#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Debug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
 	pub struct RoleParameters<T: Config> {
 		// minimum actors to maintain - if role is unstaking
 		// and remaining actors would be less that this value - prevent or punish for unstaking
@@ -87,7 +89,8 @@ mod module {
 		pub struct Module<T: Config> for enum Call where origin: T::Origin, system=system {}
 	}
 
-	#[derive(Encode, Decode, Copy, Clone, Serialize, Deserialize)]
+	#[derive(Encode, Decode, Copy, Clone, Serialize, Deserialize, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
 	pub struct Data<T: Config> {
 		pub	data: T::BlockNumber,
 	}
@@ -265,18 +265,44 @@ mod test {
 	#[test]
 	fn metadata() {
 		let metadata = Runtime::metadata();
-		let modules = match metadata.1 {
-			frame_metadata::RuntimeMetadata::V13(frame_metadata::RuntimeMetadataV13 {
-				modules: frame_metadata::DecodeDifferent::Encode(m),
-				..
-			}) => m,
+		let (pallets, types) = match metadata.1 {
+			frame_support::metadata::RuntimeMetadata::V14(metadata) =>
+				(metadata.pallets, metadata.types),
 			_ => unreachable!(),
 		};
-		pretty_assertions::assert_eq!(modules[1].storage, modules[2].storage);
-		pretty_assertions::assert_eq!(modules[1].calls, modules[2].calls);
-		pretty_assertions::assert_eq!(modules[1].event, modules[2].event);
-		pretty_assertions::assert_eq!(modules[1].constants, modules[2].constants);
-		pretty_assertions::assert_eq!(modules[1].errors, modules[2].errors);
+
+		let variants = |id| match types.resolve(id).map(|ty| ty.type_def()) {
+			Some(scale_info::TypeDef::Variant(variant)) => variant.variants(),
+			_ => panic!("Expected variant type"),
+		};
+
+		// Compare variants and field types, the type paths and the call argument names differ.
+		let assert_variants_eq = |id1, id2| {
+			let (variants1, variants2) = (variants(id1), variants(id2));
+			assert_eq!(variants1.len(), variants2.len());
+			for (v1, v2) in variants1.iter().zip(variants2) {
+				assert_eq!(v1.name(), v2.name());
+				assert_eq!(v1.index(), v2.index());
+				let tys1 = v1.fields().iter().map(|f| f.ty()).collect::<Vec<_>>();
+				let tys2 = v2.fields().iter().map(|f| f.ty()).collect::<Vec<_>>();
+				assert_eq!(tys1, tys2);
+			}
+		};
+
+		pretty_assertions::assert_eq!(pallets[1].storage, pallets[2].storage);
+		assert_variants_eq(
+			pallets[1].calls.as_ref().unwrap().ty.id(),
+			pallets[2].calls.as_ref().unwrap().ty.id(),
+		);
+		assert_variants_eq(
+			pallets[1].event.as_ref().unwrap().ty.id(),
+			pallets[2].event.as_ref().unwrap().ty.id(),
+		);
+		pretty_assertions::assert_eq!(pallets[1].constants, pallets[2].constants);
+		assert_variants_eq(
+			pallets[1].error.as_ref().unwrap().ty.id(),
+			pallets[2].error.as_ref().unwrap().ty.id(),
+		);
 	}
 
 	#[test]
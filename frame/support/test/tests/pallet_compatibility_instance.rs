@@ -280,19 +280,45 @@ mod test {
 	#[test]
 	fn metadata() {
 		let metadata = Runtime::metadata();
-		let modules = match metadata.1 {
-			frame_metadata::RuntimeMetadata::V13(frame_metadata::RuntimeMetadataV13 {
-				modules: frame_metadata::DecodeDifferent::Encode(m),
-				..
-			}) => m,
+		let (pallets, types) = match metadata.1 {
+			frame_support::metadata::RuntimeMetadata::V14(metadata) =>
+				(metadata.pallets, metadata.types),
 			_ => unreachable!(),
 		};
+
+		let variants = |id| match types.resolve(id).map(|ty| ty.type_def()) {
+			Some(scale_info::TypeDef::Variant(variant)) => variant.variants(),
+			_ => panic!("Expected variant type"),
+		};
+
+		// Compare variants and field types, the type paths and the call argument names differ.
+		let assert_variants_eq = |id1, id2| {
+			let (variants1, variants2) = (variants(id1), variants(id2));
+			assert_eq!(variants1.len(), variants2.len());
+			for (v1, v2) in variants1.iter().zip(variants2) {
+				assert_eq!(v1.name(), v2.name());
+				assert_eq!(v1.index(), v2.index());
+				let tys1 = v1.fields().iter().map(|f| f.ty()).collect::<Vec<_>>();
+				let tys2 = v2.fields().iter().map(|f| f.ty()).collect::<Vec<_>>();
+				assert_eq!(tys1, tys2);
+			}
+		};
+
 		for i in vec![1, 3, 5].into_iter() {
-			pretty_assertions::assert_eq!(modules[i].storage, modules[i+1].storage);
-			pretty_assertions::assert_eq!(modules[i].calls, modules[i+1].calls);
-			pretty_assertions::assert_eq!(modules[i].event, modules[i+1].event);
-			pretty_assertions::assert_eq!(modules[i].constants, modules[i+1].constants);
-			pretty_assertions::assert_eq!(modules[i].errors, modules[i+1].errors);
+			pretty_assertions::assert_eq!(pallets[i].storage, pallets[i+1].storage);
+			assert_variants_eq(
+				pallets[i].calls.as_ref().unwrap().ty.id(),
+				pallets[i+1].calls.as_ref().unwrap().ty.id(),
+			);
+			assert_variants_eq(
+				pallets[i].event.as_ref().unwrap().ty.id(),
+				pallets[i+1].event.as_ref().unwrap().ty.id(),
+			);
+			pretty_assertions::assert_eq!(pallets[i].constants, pallets[i+1].constants);
+			assert_variants_eq(
+				pallets[i].error.as_ref().unwrap().ty.id(),
+				pallets[i+1].error.as_ref().unwrap().ty.id(),
+			);
 		}
 	}
 
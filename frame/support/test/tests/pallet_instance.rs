@@ -161,7 +161,8 @@ pub mod pallet {
 	}
 
 	#[pallet::origin]
-	#[derive(EqNoBound, RuntimeDebugNoBound, CloneNoBound, PartialEqNoBound, Encode, Decode)]
+	#[derive(EqNoBound, RuntimeDebugNoBound, CloneNoBound, PartialEqNoBound, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(T, I))]
 	pub struct Origin<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::validate_unsigned]
@@ -585,196 +586,184 @@ fn pallet_on_genesis() {
 
 #[test]
 fn metadata() {
-	use frame_metadata::*;
-	use codec::{Decode, Encode};
+	use frame_support::metadata::*;
+	use scale_info::meta_type;
+
+	let system_pallet_metadata = PalletMetadata {
+		index: 0,
+		name: "System",
+		storage: None,
+		calls: Some(frame_system::Pallet::<Runtime>::call_functions()),
+		event: Some(PalletEventMetadata { ty: meta_type::<frame_system::Event<Runtime>>() }),
+		constants: frame_system::Pallet::<Runtime>::module_constants_metadata(),
+		error: frame_system::Pallet::<Runtime>::error_metadata(),
+	};
 
-	let expected_pallet_metadata = ModuleMetadata {
+	let example_pallet_metadata = PalletMetadata {
 		index: 1,
-		name: DecodeDifferent::Decoded("Example".to_string()),
-		storage: Some(DecodeDifferent::Decoded(StorageMetadata {
-			prefix: DecodeDifferent::Decoded("Example".to_string()),
-			entries: DecodeDifferent::Decoded(vec![
+		name: "Example",
+		storage: Some(PalletStorageMetadata {
+			prefix: "Example",
+			entries: vec![
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("Value".to_string()),
+					name: "Value",
 					modifier: StorageEntryModifier::Optional,
-					ty: StorageEntryType::Plain(DecodeDifferent::Decoded("u32".to_string())),
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					ty: StorageEntryType::Plain(meta_type::<u32>()),
+					default: vec![0],
+					docs: vec![],
 				},
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("Map".to_string()),
+					name: "Map",
 					modifier: StorageEntryModifier::Optional,
 					ty: StorageEntryType::Map {
-						key: DecodeDifferent::Decoded("u8".to_string()),
-						value: DecodeDifferent::Decoded("u16".to_string()),
-						hasher: StorageHasher::Blake2_128Concat,
-						unused: false,
+						key: meta_type::<u8>(),
+						value: meta_type::<u16>(),
+						hashers: vec![StorageHasher::Blake2_128Concat],
 					},
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					default: vec![0],
+					docs: vec![],
 				},
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("Map2".to_string()),
+					name: "Map2",
 					modifier: StorageEntryModifier::Optional,
 					ty: StorageEntryType::Map {
-						key: DecodeDifferent::Decoded("u16".to_string()),
-						value: DecodeDifferent::Decoded("u32".to_string()),
-						hasher: StorageHasher::Twox64Concat,
-						unused: false,
+						key: meta_type::<u16>(),
+						value: meta_type::<u32>(),
+						hashers: vec![StorageHasher::Twox64Concat],
 					},
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					default: vec![0],
+					docs: vec![],
 				},
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("DoubleMap".to_string()),
+					name: "DoubleMap",
 					modifier: StorageEntryModifier::Optional,
-					ty: StorageEntryType::DoubleMap {
-						value: DecodeDifferent::Decoded("u32".to_string()),
-						key1: DecodeDifferent::Decoded("u8".to_string()),
-						key2: DecodeDifferent::Decoded("u16".to_string()),
-						hasher: StorageHasher::Blake2_128Concat,
-						key2_hasher: StorageHasher::Twox64Concat,
+					ty: StorageEntryType::Map {
+						value: meta_type::<u32>(),
+						key: meta_type::<(u8, u16)>(),
+						hashers: vec![StorageHasher::Blake2_128Concat, StorageHasher::Twox64Concat],
 					},
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					default: vec![0],
+					docs: vec![],
 				},
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("DoubleMap2".to_string()),
+					name: "DoubleMap2",
 					modifier: StorageEntryModifier::Optional,
-					ty: StorageEntryType::DoubleMap {
-						value: DecodeDifferent::Decoded("u64".to_string()),
-						key1: DecodeDifferent::Decoded("u16".to_string()),
-						key2: DecodeDifferent::Decoded("u32".to_string()),
-						hasher: StorageHasher::Twox64Concat,
-						key2_hasher: StorageHasher::Blake2_128Concat,
+					ty: StorageEntryType::Map {
+						value: meta_type::<u64>(),
+						key: meta_type::<(u16, u32)>(),
+						hashers: vec![StorageHasher::Twox64Concat, StorageHasher::Blake2_128Concat],
 					},
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					default: vec![0],
+					docs: vec![],
 				},
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("NMap".to_string()),
+					name: "NMap",
 					modifier: StorageEntryModifier::Optional,
-					ty: StorageEntryType::NMap {
-						keys: DecodeDifferent::Decoded(vec!["u8".to_string()]),
-						hashers: DecodeDifferent::Decoded(vec![
-							StorageHasher::Blake2_128Concat,
-						]),
-						value: DecodeDifferent::Decoded("u32".to_string()),
+					ty: StorageEntryType::Map {
+						key: meta_type::<u8>(),
+						hashers: vec![StorageHasher::Blake2_128Concat],
+						value: meta_type::<u32>(),
 					},
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					default: vec![0],
+					docs: vec![],
 				},
 				StorageEntryMetadata {
-					name: DecodeDifferent::Decoded("NMap2".to_string()),
+					name: "NMap2",
 					modifier: StorageEntryModifier::Optional,
-					ty: StorageEntryType::NMap {
-						keys: DecodeDifferent::Decoded(vec![
-							"u16".to_string(),
-							"u32".to_string(),
-						]),
-						hashers: DecodeDifferent::Decoded(vec![
-							StorageHasher::Twox64Concat,
-							StorageHasher::Blake2_128Concat,
-						]),
-						value: DecodeDifferent::Decoded("u64".to_string()),
+					ty: StorageEntryType::Map {
+						key: meta_type::<(u16, u32)>(),
+						hashers: vec![StorageHasher::Twox64Concat, StorageHasher::Blake2_128Concat],
+						value: meta_type::<u64>(),
 					},
-					default: DecodeDifferent::Decoded(vec![0]),
-					documentation: DecodeDifferent::Decoded(vec![]),
+					default: vec![0],
+					docs: vec![],
 				},
-			]),
-		})),
-		calls: Some(DecodeDifferent::Decoded(vec![
-			FunctionMetadata {
-				name: DecodeDifferent::Decoded("foo".to_string()),
-				arguments: DecodeDifferent::Decoded(vec![
-					FunctionArgumentMetadata {
-						name: DecodeDifferent::Decoded("_foo".to_string()),
-						ty: DecodeDifferent::Decoded("Compact<u32>".to_string()),
-					}
-				]),
-				documentation: DecodeDifferent::Decoded(vec![
-					" Doc comment put in metadata".to_string(),
-				]),
-			},
-			FunctionMetadata {
-				name: DecodeDifferent::Decoded("foo_transactional".to_string()),
-				arguments: DecodeDifferent::Decoded(vec![
-					FunctionArgumentMetadata {
-						name: DecodeDifferent::Decoded("_foo".to_string()),
-						ty: DecodeDifferent::Decoded("Compact<u32>".to_string()),
-					}
-				]),
-				documentation: DecodeDifferent::Decoded(vec![
-					" Doc comment put in metadata".to_string(),
-				]),
-			},
-		])),
-		event: Some(DecodeDifferent::Decoded(vec![
-			EventMetadata {
-				name: DecodeDifferent::Decoded("Proposed".to_string()),
-				arguments: DecodeDifferent::Decoded(vec!["<T as frame_system::Config>::AccountId".to_string()]),
-				documentation: DecodeDifferent::Decoded(vec![
-					" doc comment put in metadata".to_string()
-				]),
-			},
-			EventMetadata {
-				name: DecodeDifferent::Decoded("Spending".to_string()),
-				arguments: DecodeDifferent::Decoded(vec!["Balance".to_string()]),
-				documentation: DecodeDifferent::Decoded(vec![
-					" doc".to_string()
-				]),
-			},
-			EventMetadata {
-				name: DecodeDifferent::Decoded("Something".to_string()),
-				arguments: DecodeDifferent::Decoded(vec!["Other".to_string()]),
-				documentation: DecodeDifferent::Decoded(vec![]),
-			},
-		])),
-		constants: DecodeDifferent::Decoded(vec![
-			ModuleConstantMetadata {
-				name: DecodeDifferent::Decoded("MyGetParam".to_string()),
-				ty: DecodeDifferent::Decoded("u32".to_string()),
-				value: DecodeDifferent::Decoded(vec![10, 0, 0, 0]),
-				documentation: DecodeDifferent::Decoded(vec![]),
-			},
-		]),
-		errors: DecodeDifferent::Decoded(vec![
-			ErrorMetadata {
-				name: DecodeDifferent::Decoded("InsufficientProposersBalance".to_string()),
-				documentation: DecodeDifferent::Decoded(vec![
-					" doc comment put into metadata".to_string(),
-				]),
+			],
+		}),
+		calls: Some(meta_type::<pallet::Call<Runtime>>().into()),
+		event: Some(PalletEventMetadata { ty: meta_type::<pallet::Event<Runtime>>() }),
+		constants: vec![
+			PalletConstantMetadata {
+				name: "MyGetParam",
+				ty: meta_type::<u32>(),
+				value: vec![10, 0, 0, 0],
+				docs: vec![],
 			},
-		]),
+		],
+		error: Some(PalletErrorMetadata { ty: meta_type::<pallet::Error<Runtime>>() }),
 	};
 
-	let mut expected_pallet_instance1_metadata = expected_pallet_metadata.clone();
-	expected_pallet_instance1_metadata.name = DecodeDifferent::Decoded("Instance1Example".to_string());
-	expected_pallet_instance1_metadata.index = 2;
-	match expected_pallet_instance1_metadata.storage {
-		Some(DecodeDifferent::Decoded(ref mut storage_meta)) => {
-			storage_meta.prefix = DecodeDifferent::Decoded("Instance1Example".to_string());
-		},
-		_ => unreachable!(),
-	}
-
-
-	let metadata = match Runtime::metadata().1 {
-		RuntimeMetadata::V13(metadata) => metadata,
-		_ => panic!("metadata has been bump, test needs to be updated"),
+	let mut example_pallet_instance1_metadata = example_pallet_metadata.clone();
+	example_pallet_instance1_metadata.name = "Instance1Example";
+	example_pallet_instance1_metadata.index = 2;
+	example_pallet_instance1_metadata.storage.as_mut().unwrap().prefix = "Instance1Example";
+	example_pallet_instance1_metadata.calls =
+		Some(meta_type::<pallet::Call<Runtime, pallet::Instance1>>().into());
+	example_pallet_instance1_metadata.event =
+		Some(PalletEventMetadata { ty: meta_type::<pallet::Event<Runtime, pallet::Instance1>>() });
+	example_pallet_instance1_metadata.error =
+		Some(PalletErrorMetadata { ty: meta_type::<pallet::Error<Runtime, pallet::Instance1>>() });
+
+	// Type ids are assigned in registration order, so the expected pallets must be registered in
+	// the same order as in the runtime for the portable forms to be comparable.
+	let pallets = vec![
+		system_pallet_metadata,
+		example_pallet_metadata,
+		example_pallet_instance1_metadata,
+	];
+	let extrinsic = ExtrinsicMetadata {
+		ty: meta_type::<UncheckedExtrinsic>(),
+		version: 4,
+		signed_extensions: vec![],
 	};
-
-	let modules_metadata = match metadata.modules {
-		DecodeDifferent::Encode(modules_metadata) => modules_metadata,
-		_ => unreachable!(),
+	let expected_metadata: RuntimeMetadataPrefixed =
+		RuntimeMetadataLastVersion::new(pallets, extrinsic, meta_type::<Runtime>()).into();
+	let expected_metadata = match expected_metadata.1 {
+		RuntimeMetadata::V14(metadata) => metadata,
+		_ => panic!("metadata has been bumped, test needs to be updated"),
 	};
 
-	let pallet_metadata = ModuleMetadata::decode(&mut &modules_metadata[1].encode()[..]).unwrap();
-	let pallet_instance1_metadata =
-		ModuleMetadata::decode(&mut &modules_metadata[2].encode()[..]).unwrap();
+	let actual_metadata = match Runtime::metadata().1 {
+		RuntimeMetadata::V14(metadata) => metadata,
+		_ => panic!("metadata has been bumped, test needs to be updated"),
+	};
 
-	pretty_assertions::assert_eq!(pallet_metadata, expected_pallet_metadata);
-	pretty_assertions::assert_eq!(pallet_instance1_metadata, expected_pallet_instance1_metadata);
+	pretty_assertions::assert_eq!(actual_metadata.pallets[1], expected_metadata.pallets[1]);
+	pretty_assertions::assert_eq!(actual_metadata.pallets[2], expected_metadata.pallets[2]);
+
+	// Check the call, event and error types carry their documentation and arguments.
+	let types = &actual_metadata.types;
+	let variants = |ty: &scale_info::interner::UntrackedSymbol<std::any::TypeId>| {
+		let ty = types.resolve(ty.id()).expect("type is registered");
+		match ty.type_def() {
+			scale_info::TypeDef::Variant(def) => def.variants().iter().map(|v| (
+				v.name().as_str(),
+				v.fields().iter().map(|f| f.name().map_or("", |n| n.as_str())).collect::<Vec<_>>(),
+				v.docs().iter().map(|d| d.as_str()).collect::<Vec<_>>(),
+			)).collect::<Vec<_>>(),
+			_ => panic!("expected a variant type"),
+		}
+	};
+	let pallet = &actual_metadata.pallets[1];
+	pretty_assertions::assert_eq!(
+		variants(&pallet.calls.as_ref().unwrap().ty),
+		vec![
+			("foo", vec!["_foo"], vec!["Doc comment put in metadata"]),
+			("foo_transactional", vec!["_foo"], vec!["Doc comment put in metadata"]),
+		],
+	);
+	pretty_assertions::assert_eq!(
+		variants(&pallet.event.as_ref().unwrap().ty),
+		vec![
+			("Proposed", vec![""], vec!["doc comment put in metadata"]),
+			("Spending", vec![""], vec!["doc"]),
+			("Something", vec![""], vec![]),
+		],
+	);
+	pretty_assertions::assert_eq!(
+		variants(&pallet.error.as_ref().unwrap().ty),
+		vec![("InsufficientProposersBalance", vec![], vec!["doc comment put into metadata"])],
+	);
 }
 
 #[test]
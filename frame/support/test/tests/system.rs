@@ -18,15 +18,16 @@
 use frame_support::{
 	codec::{Encode, Decode, EncodeLike}, traits::Get, weights::RuntimeDbWeight,
 };
+use scale_info::TypeInfo;
 
 pub trait Config: 'static + Eq + Clone {
 	type Origin: Into<Result<RawOrigin<Self::AccountId>, Self::Origin>>
 		+ From<RawOrigin<Self::AccountId>>;
 
 	type BaseCallFilter: frame_support::traits::Filter<Self::Call>;
-	type BlockNumber: Decode + Encode + EncodeLike + Clone + Default;
+	type BlockNumber: Decode + Encode + EncodeLike + Clone + Default + TypeInfo;
 	type Hash;
-	type AccountId: Encode + EncodeLike + Decode;
+	type AccountId: Encode + EncodeLike + Decode + TypeInfo;
 	type Call;
 	type Event: From<Event<Self>>;
 	type PalletInfo: frame_support::traits::PalletInfo;
@@ -63,7 +64,7 @@ frame_support::decl_error! {
 }
 
 /// Origin for the system module.
-#[derive(PartialEq, Eq, Clone, sp_runtime::RuntimeDebug, Encode, Decode)]
+#[derive(PartialEq, Eq, Clone, sp_runtime::RuntimeDebug, Encode, Decode, TypeInfo)]
 pub enum RawOrigin<AccountId> {
 	Root,
 	Signed(AccountId),
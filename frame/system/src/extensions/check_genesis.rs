@@ -16,6 +16,7 @@
 // limitations under the License.
 
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use crate::{Config, Pallet};
 use sp_runtime::{
 	traits::{SignedExtension, Zero},
@@ -23,7 +24,8 @@ use sp_runtime::{
 };
 
 /// Genesis hash check to provide replay protection between different networks.
-#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
 pub struct CheckGenesis<T: Config + Send + Sync>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckGenesis<T> {
@@ -456,13 +456,15 @@ pub trait SigningTypes: crate::Config {
 		+ IdentifyAccount<AccountId = Self::AccountId>
 		+ core::fmt::Debug
 		+ codec::Codec
-		+ Ord;
+		+ Ord
+		+ scale_info::TypeInfo;
 
 	/// A matching `Signature` type.
 	type Signature: Clone
 		+ PartialEq
 		+ core::fmt::Debug
-		+ codec::Codec;
+		+ codec::Codec
+		+ scale_info::TypeInfo;
 }
 
 /// A definition of types required to submit transactions from within the runtime.
@@ -652,7 +654,7 @@ mod tests {
 		type OverarchingCall = Call;
 	}
 
-	#[derive(codec::Encode, codec::Decode)]
+	#[derive(codec::Encode, codec::Decode, scale_info::TypeInfo)]
 	struct SimplePayload {
 		pub public: UintAuthorityId,
 		pub data: Vec<u8>,
@@ -25,7 +25,7 @@ use frame_system::RawOrigin;
 use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
 use sp_runtime::traits::Saturating;
 
-use crate::Module as TipsMod;
+use crate::Pallet as TipsMod;
 
 const SEED: u32 = 0;
 
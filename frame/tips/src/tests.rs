@@ -21,10 +21,11 @@
 
 use crate as tips;
 use super::*;
+use super::Event as TipEvent;
 use std::cell::RefCell;
 use frame_support::{
 	assert_noop, assert_ok, parameter_types,
-	weights::Weight, traits::SortedMembers,
+	weights::Weight, traits::{GenesisBuild, SortedMembers},
 	PalletId
 };
 use sp_runtime::Permill;
@@ -169,11 +170,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		// Total issuance will be 200 with treasury account initialized at ED.
 		balances: vec![(0, 100), (1, 98), (2, 1)],
 	}.assimilate_storage(&mut t).unwrap();
-	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test, _>(&mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
 	t.into()
 }
 
-fn last_event() -> RawEvent<u64, u128, H256> {
+fn last_event() -> TipEvent<Test> {
 	System::events().into_iter().map(|r| r.event)
 		.filter_map(|e| {
 			if let Event::tips(inner) = e { Some(inner) } else { None }
@@ -263,7 +264,7 @@ fn close_tip_works() {
 
 		let h = tip_hash();
 
-		assert_eq!(last_event(), RawEvent::NewTip(h));
+		assert_eq!(last_event(), TipEvent::NewTip(h));
 
 		assert_ok!(TipsModTestInst::tip(Origin::signed(11), h.clone(), 10));
 
@@ -271,7 +272,7 @@ fn close_tip_works() {
 
 		assert_ok!(TipsModTestInst::tip(Origin::signed(12), h.clone(), 10));
 
-		assert_eq!(last_event(), RawEvent::TipClosing(h));
+		assert_eq!(last_event(), TipEvent::TipClosing(h));
 
 		assert_noop!(TipsModTestInst::close_tip(Origin::signed(0), h.into()), Error::<Test>::Premature);
 
@@ -280,7 +281,7 @@ fn close_tip_works() {
 		assert_ok!(TipsModTestInst::close_tip(Origin::signed(0), h.into()));
 		assert_eq!(Balances::free_balance(3), 10);
 
-		assert_eq!(last_event(), RawEvent::TipClosed(h, 3, 10));
+		assert_eq!(last_event(), TipEvent::TipClosed(h, 3, 10));
 
 		assert_noop!(TipsModTestInst::close_tip(Origin::signed(100), h.into()), Error::<Test>::UnknownTip);
 	});
@@ -302,7 +303,7 @@ fn slash_tip_works() {
 		assert_eq!(Balances::free_balance(0), 88);
 
 		let h = tip_hash();
-		assert_eq!(last_event(), RawEvent::NewTip(h));
+		assert_eq!(last_event(), TipEvent::NewTip(h));
 
 		// can't remove from any origin
 		assert_noop!(
@@ -312,7 +313,7 @@ fn slash_tip_works() {
 
 		// can remove from root.
 		assert_ok!(TipsModTestInst::slash_tip(Origin::root(), h.clone()));
-		assert_eq!(last_event(), RawEvent::TipSlashed(h, 0, 12));
+		assert_eq!(last_event(), TipEvent::TipSlashed(h, 0, 12));
 
 		// tipper slashed
 		assert_eq!(Balances::reserved_balance(0), 0);
@@ -387,7 +388,7 @@ fn test_last_reward_migration() {
 
 	let mut s = Storage::default();
 
-	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+	#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
 	pub struct OldOpenTip<
 		AccountId: Parameter,
 		Balance: Parameter,
@@ -485,7 +486,7 @@ fn genesis_funding_works() {
 		// Total issuance will be 200 with treasury account initialized with 100.
 		balances: vec![(0, 100), (Treasury::account_id(), initial_funding)],
 	}.assimilate_storage(&mut t).unwrap();
-	pallet_treasury::GenesisConfig::default().assimilate_storage::<Test, _>(&mut t).unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
 	let mut t: sp_io::TestExternalities = t.into();
 
 	t.execute_with(|| {
@@ -493,3 +494,21 @@ fn genesis_funding_works() {
 		assert_eq!(Treasury::pot(), initial_funding - Balances::minimum_balance());
 	});
 }
+
+#[test]
+fn migrate_to_pallet_prefix_works() {
+	use frame_support::storage::migration::{get_storage_value, put_storage_value};
+
+	new_test_ext().execute_with(|| {
+		let hash = BlakeTwo256::hash(b"reason");
+		put_storage_value(b"Treasury", b"Reasons", &hash.encode(), b"reason".to_vec());
+		put_storage_value(b"Treasury", b"ProposalCount", &[], 2u32);
+
+		crate::migrations::migrate_to_pallet_prefix::<Test>();
+
+		assert_eq!(TipsModTestInst::reasons(hash), Some(b"reason".to_vec()));
+		assert_eq!(get_storage_value::<Vec<u8>>(b"Treasury", b"Reasons", &hash.encode()), None);
+		// treasury storage is left where it is.
+		assert_eq!(Treasury::proposal_count(), 2);
+	});
+}
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! # Transaction Payment Module
+//! # Transaction Payment Pallet
 //!
 //! This module provides the basic logic needed to pay the absolute minimum amount needed for a
 //! transaction to be included. This includes:
@@ -49,8 +49,8 @@
 
 use sp_std::prelude::*;
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use frame_support::{
-	decl_storage, decl_module,
 	traits::Get,
 	weights::{
 		Weight, DispatchInfo, PostDispatchInfo, GetDispatchInfo, Pays, WeightToFeePolynomial,
@@ -72,6 +72,7 @@ use sp_runtime::{
 mod payment;
 mod types;
 
+pub use pallet::*;
 pub use payment::*;
 pub use types::{InclusionFee, FeeDetails, RuntimeDispatchInfo};
 
@@ -215,7 +216,7 @@ impl<T, S, V, M> Convert<Multiplier, Multiplier> for TargetedFeeAdjustment<T, S,
 }
 
 /// Storage releases of the module.
-#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
 	/// Original version of the module.
 	V1Ancient,
@@ -229,44 +230,80 @@ impl Default for Releases {
 	}
 }
 
-pub trait Config: frame_system::Config {
-	/// Handler for withdrawing, refunding and depositing the transaction fee.
-	/// Transaction fees are withdrawn before the transaction is executed.
-	/// After the transaction was executed the transaction weight can be
-	/// adjusted, depending on the used resources by the transaction. If the
-	/// transaction weight is lower than expected, parts of the transaction fee
-	/// might be refunded. In the end the fees can be deposited.
-	type OnChargeTransaction: OnChargeTransaction<Self>;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Handler for withdrawing, refunding and depositing the transaction fee.
+		/// Transaction fees are withdrawn before the transaction is executed.
+		/// After the transaction was executed the transaction weight can be
+		/// adjusted, depending on the used resources by the transaction. If the
+		/// transaction weight is lower than expected, parts of the transaction fee
+		/// might be refunded. In the end the fees can be deposited.
+		type OnChargeTransaction: OnChargeTransaction<Self>;
 
-	/// The fee to be paid for making a transaction; the per-byte portion.
-	type TransactionByteFee: Get<BalanceOf<Self>>;
+		/// The fee to be paid for making a transaction; the per-byte portion.
+		#[pallet::constant]
+		type TransactionByteFee: Get<BalanceOf<Self>>;
 
-	/// Convert a weight value into a deductible fee based on the currency type.
-	type WeightToFee: WeightToFeePolynomial<Balance=BalanceOf<Self>>;
+		/// Convert a weight value into a deductible fee based on the currency type.
+		type WeightToFee: WeightToFeePolynomial<Balance=BalanceOf<Self>>;
 
-	/// Update the multiplier of the next block, based on the previous block's weight.
-	type FeeMultiplierUpdate: MultiplierUpdate;
-}
+		/// Update the multiplier of the next block, based on the previous block's weight.
+		type FeeMultiplierUpdate: MultiplierUpdate;
+	}
 
-decl_storage! {
-	trait Store for Module<T: Config> as TransactionPayment {
-		pub NextFeeMultiplier get(fn next_fee_multiplier): Multiplier = Multiplier::saturating_from_integer(1);
+	#[pallet::extra_constants]
+	impl<T: Config> Pallet<T> {
+		/// The polynomial that is applied in order to derive fee from weight.
+		#[allow(non_snake_case)]
+		fn WeightToFee() -> Vec<WeightToFeeCoefficient<BalanceOf<T>>> {
+			T::WeightToFee::polynomial().to_vec()
+		}
+	}
 
-		StorageVersion build(|_: &GenesisConfig| Releases::V2): Releases;
+	#[pallet::type_value]
+	pub fn NextFeeMultiplierOnEmpty() -> Multiplier {
+		Multiplier::saturating_from_integer(1)
 	}
-}
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		/// The fee to be paid for making a transaction; the per-byte portion.
-		const TransactionByteFee: BalanceOf<T> = T::TransactionByteFee::get();
+	#[pallet::storage]
+	#[pallet::getter(fn next_fee_multiplier)]
+	pub type NextFeeMultiplier<T: Config> =
+		StorageValue<_, Multiplier, ValueQuery, NextFeeMultiplierOnEmpty>;
 
-		/// The polynomial that is applied in order to derive fee from weight.
-		const WeightToFee: Vec<WeightToFeeCoefficient<BalanceOf<T>>> =
-			T::WeightToFee::polynomial().to_vec();
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
-		fn on_finalize() {
-			NextFeeMultiplier::mutate(|fm| {
+	#[pallet::genesis_config]
+	pub struct GenesisConfig;
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			StorageVersion::<T>::put(Releases::V2);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_finalize(_: T::BlockNumber) {
+			<NextFeeMultiplier<T>>::mutate(|fm| {
 				*fm = T::FeeMultiplierUpdate::convert(*fm);
 			});
 		}
@@ -312,9 +349,12 @@ decl_module! {
 			})
 		}
 	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
 }
 
-impl<T: Config> Module<T> where
+impl<T: Config> Pallet<T> where
 	BalanceOf<T>: FixedPointOperand
 {
 	/// Query the data that we know about the fee of a given `call`.
@@ -457,7 +497,7 @@ impl<T: Config> Module<T> where
 	}
 }
 
-impl<T> Convert<Weight, BalanceOf<T>> for Module<T> where
+impl<T> Convert<Weight, BalanceOf<T>> for Pallet<T> where
 	T: Config,
 	BalanceOf<T>: FixedPointOperand,
 {
@@ -467,13 +507,14 @@ impl<T> Convert<Weight, BalanceOf<T>> for Module<T> where
 	/// share that the weight contributes to the overall fee of a transaction. It is mainly
 	/// for informational purposes and not used in the actual fee calculation.
 	fn convert(weight: Weight) -> BalanceOf<T> {
-		NextFeeMultiplier::get().saturating_mul_int(Self::weight_to_fee(weight))
+		NextFeeMultiplier::<T>::get().saturating_mul_int(Self::weight_to_fee(weight))
 	}
 }
 
 /// Require the transactor pay for themselves and maybe include a tip to gain additional priority
 /// in the queue.
-#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
 pub struct ChargeTransactionPayment<T: Config>(#[codec(compact)] BalanceOf<T>);
 
 impl<T: Config> ChargeTransactionPayment<T> where
@@ -499,7 +540,7 @@ impl<T: Config> ChargeTransactionPayment<T> where
 		TransactionValidityError,
 	> {
 		let tip = self.0;
-		let fee = Module::<T>::compute_fee(len as u32, info, tip);
+		let fee = Pallet::<T>::compute_fee(len as u32, info, tip);
 
 		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(who, call, info, fee, tip)
 			.map(|i| (fee, i))
@@ -586,7 +627,7 @@ impl<T: Config> SignedExtension for ChargeTransactionPayment<T> where
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
 		let (tip, who, imbalance) = pre;
-		let actual_fee = Module::<T>::compute_actual_fee(
+		let actual_fee = Pallet::<T>::compute_actual_fee(
 			len as u32,
 			info,
 			post_info,
@@ -882,7 +923,7 @@ mod tests {
 			.execute_with(||
 		{
 			let len = 10;
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(3, 2));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
 
 			let pre = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
 				.pre_dispatch(&2, CALL, &info_from_weight(100), len)
@@ -967,7 +1008,7 @@ mod tests {
 			.execute_with(||
 		{
 			// all fees should be x1.5
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(3, 2));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
 			let len = 10;
 
 			assert_ok!(
@@ -1001,7 +1042,7 @@ mod tests {
 			.execute_with(||
 		{
 			// all fees should be x1.5
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(3, 2));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
 
 			assert_eq!(
 				TransactionPayment::query_info(xt, len),
@@ -1028,7 +1069,7 @@ mod tests {
 			.execute_with(||
 		{
 			// Next fee multiplier is zero
-			assert_eq!(NextFeeMultiplier::get(), Multiplier::one());
+			assert_eq!(NextFeeMultiplier::<Runtime>::get(), Multiplier::one());
 
 			// Tip only, no fees works
 			let dispatch_info = DispatchInfo {
@@ -1036,25 +1077,25 @@ mod tests {
 				class: DispatchClass::Operational,
 				pays_fee: Pays::No,
 			};
-			assert_eq!(Module::<Runtime>::compute_fee(0, &dispatch_info, 10), 10);
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 10), 10);
 			// No tip, only base fee works
 			let dispatch_info = DispatchInfo {
 				weight: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
-			assert_eq!(Module::<Runtime>::compute_fee(0, &dispatch_info, 0), 100);
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 0), 100);
 			// Tip + base fee works
-			assert_eq!(Module::<Runtime>::compute_fee(0, &dispatch_info, 69), 169);
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 69), 169);
 			// Len (byte fee) + base fee works
-			assert_eq!(Module::<Runtime>::compute_fee(42, &dispatch_info, 0), 520);
+			assert_eq!(Pallet::<Runtime>::compute_fee(42, &dispatch_info, 0), 520);
 			// Weight fee + base fee works
 			let dispatch_info = DispatchInfo {
 				weight: 1000,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
-			assert_eq!(Module::<Runtime>::compute_fee(0, &dispatch_info, 0), 1100);
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 0), 1100);
 		});
 	}
 
@@ -1068,14 +1109,14 @@ mod tests {
 			.execute_with(||
 		{
 			// Add a next fee multiplier. Fees will be x3/2.
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(3, 2));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
 			// Base fee is unaffected by multiplier
 			let dispatch_info = DispatchInfo {
 				weight: 0,
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
-			assert_eq!(Module::<Runtime>::compute_fee(0, &dispatch_info, 0), 100);
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 0), 100);
 
 			// Everything works together :)
 			let dispatch_info = DispatchInfo {
@@ -1085,7 +1126,7 @@ mod tests {
 			};
 			// 123 weight, 456 length, 100 base
 			assert_eq!(
-				Module::<Runtime>::compute_fee(456, &dispatch_info, 789),
+				Pallet::<Runtime>::compute_fee(456, &dispatch_info, 789),
 				100 + (3 * 123 / 2) + 4560 + 789,
 			);
 		});
@@ -1101,7 +1142,7 @@ mod tests {
 			.execute_with(||
 		{
 			// Add a next fee multiplier. All fees will be x1/2.
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(1, 2));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(1, 2));
 
 			// Base fee is unaffected by multiplier.
 			let dispatch_info = DispatchInfo {
@@ -1109,7 +1150,7 @@ mod tests {
 				class: DispatchClass::Operational,
 				pays_fee: Pays::Yes,
 			};
-			assert_eq!(Module::<Runtime>::compute_fee(0, &dispatch_info, 0), 100);
+			assert_eq!(Pallet::<Runtime>::compute_fee(0, &dispatch_info, 0), 100);
 
 			// Everything works together.
 			let dispatch_info = DispatchInfo {
@@ -1119,7 +1160,7 @@ mod tests {
 			};
 			// 123 weight, 456 length, 100 base
 			assert_eq!(
-				Module::<Runtime>::compute_fee(456, &dispatch_info, 789),
+				Pallet::<Runtime>::compute_fee(456, &dispatch_info, 789),
 				100 + (123 / 2) + 4560 + 789,
 			);
 		});
@@ -1141,7 +1182,7 @@ mod tests {
 				pays_fee: Pays::Yes,
 			};
 			assert_eq!(
-				Module::<Runtime>::compute_fee(
+				Pallet::<Runtime>::compute_fee(
 					<u32>::max_value(),
 					&dispatch_info,
 					<u64>::max_value()
@@ -1250,7 +1291,7 @@ mod tests {
 			let len = 10;
 			let tip = 5;
 
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(5, 4));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(5, 4));
 
 			let pre = ChargeTransactionPayment::<Runtime>::from(tip)
 				.pre_dispatch(&2, CALL, &info, len)
@@ -1261,7 +1302,7 @@ mod tests {
 				.unwrap();
 
 			let refund_based_fee = prev_balance - Balances::free_balance(2);
-			let actual_fee = Module::<Runtime>
+			let actual_fee = Pallet::<Runtime>
 				::compute_actual_fee(len as u32, &info, &post_info, tip);
 
 			// 33 weight, 10 length, 7 base, 5 tip
@@ -1284,7 +1325,7 @@ mod tests {
 			let len = 10;
 			let tip = 5;
 
-			NextFeeMultiplier::put(Multiplier::saturating_from_rational(5, 4));
+			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(5, 4));
 
 			let pre = ChargeTransactionPayment::<Runtime>::from(tip)
 				.pre_dispatch(&2, CALL, &info, len)
@@ -1295,7 +1336,7 @@ mod tests {
 				.unwrap();
 
 			let refund_based_fee = prev_balance - Balances::free_balance(2);
-			let actual_fee = Module::<Runtime>
+			let actual_fee = Pallet::<Runtime>
 				::compute_actual_fee(len as u32, &info, &post_info, tip);
 
 			// Only 5 tip is paid
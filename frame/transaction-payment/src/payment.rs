@@ -1,6 +1,7 @@
 ///! Traits and default implementation for paying transaction fees.
 use crate::Config;
 use codec::FullCodec;
+use scale_info::TypeInfo;
 use frame_support::{
 	traits::{Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, WithdrawReasons},
 	unsigned::TransactionValidityError,
@@ -17,7 +18,8 @@ type NegativeImbalanceOf<C, T> =
 /// Handle withdrawing, refunding and depositing of transaction fees.
 pub trait OnChargeTransaction<T: Config> {
 	/// The underlying integer type in which fees are calculated.
-	type Balance: AtLeast32BitUnsigned + FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default;
+	type Balance: AtLeast32BitUnsigned + FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default
+		+ TypeInfo;
 	type LiquidityInfo: Default;
 
 	/// Before the transaction is executed the payment of the transaction fees
@@ -35,6 +35,7 @@ use frame_support::{
 use sp_std::prelude::*;
 use sp_std::{result};
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_runtime::traits::{Saturating, BlakeTwo256, Hash, Zero, One};
 use sp_transaction_storage_proof::{
 	TransactionStorageProof, InherentError,
@@ -57,7 +58,7 @@ pub const DEFAULT_MAX_TRANSACTION_SIZE: u32 = 8 * 1024 * 1024;
 pub const DEFAULT_MAX_BLOCK_TRANSACTIONS: u32 = 512;
 
 /// State data for a stored transaction.
-#[derive(Encode, Decode, Clone, sp_runtime::RuntimeDebug, PartialEq, Eq)]
+#[derive(Encode, Decode, Clone, sp_runtime::RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct TransactionInfo {
 	/// Chunk trie root.
 	chunk_root: <BlakeTwo256 as Hash>::Output,
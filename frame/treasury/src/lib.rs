@@ -65,8 +65,7 @@ pub mod weights;
 
 use sp_std::prelude::*;
 use frame_support::{
-	decl_module, decl_storage, decl_event, ensure, print, decl_error,
-	PalletId, BoundedVec, storage::TryAppendValue,
+	ensure, print, PalletId, BoundedVec,
 };
 use frame_support::traits::{
 	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::KeepAlive,
@@ -81,61 +80,18 @@ use sp_runtime::{
 use frame_support::weights::{Weight, DispatchClass};
 use frame_support::traits::EnsureOrigin;
 use codec::{Encode, Decode};
-use frame_system::ensure_signed;
+use scale_info::TypeInfo;
 pub use weights::WeightInfo;
 
-pub type BalanceOf<T, I=DefaultInstance> =
+pub use pallet::*;
+
+pub type BalanceOf<T, I = ()> =
 	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-pub type PositiveImbalanceOf<T, I=DefaultInstance> =
+pub type PositiveImbalanceOf<T, I = ()> =
 	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::PositiveImbalance;
-pub type NegativeImbalanceOf<T, I=DefaultInstance> =
+pub type NegativeImbalanceOf<T, I = ()> =
 	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
-pub trait Config<I=DefaultInstance>: frame_system::Config {
-	/// The treasury's module id, used for deriving its sovereign account ID.
-	type PalletId: Get<PalletId>;
-
-	/// The staking balance.
-	type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
-
-	/// Origin from which approvals must come.
-	type ApproveOrigin: EnsureOrigin<Self::Origin>;
-
-	/// Origin from which rejections must come.
-	type RejectOrigin: EnsureOrigin<Self::Origin>;
-
-	/// The overarching event type.
-	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Config>::Event>;
-
-	/// Handler for the unbalanced decrease when slashing for a rejected proposal or bounty.
-	type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
-
-	/// Fraction of a proposal's value that should be bonded in order to place the proposal.
-	/// An accepted proposal gets these back. A rejected proposal does not.
-	type ProposalBond: Get<Permill>;
-
-	/// Minimum amount of funds that should be placed in a deposit for making a proposal.
-	type ProposalBondMinimum: Get<BalanceOf<Self, I>>;
-
-	/// Period between successive spends.
-	type SpendPeriod: Get<Self::BlockNumber>;
-
-	/// Percentage of spare funds (if any) that are burnt per spend period.
-	type Burn: Get<Permill>;
-
-	/// Handler for the unbalanced decrease when treasury funds are burned.
-	type BurnDestination: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
-
-	/// Weight information for extrinsics in this pallet.
-	type WeightInfo: WeightInfo;
-
-	/// Runtime hooks to external pallet using treasury to compute spend funds.
-	type SpendFunds: SpendFunds<Self, I>;
-
-	/// The maximum number of approvals that can wait in the spending queue.
-	type MaxApprovals: Get<u32>;
-}
-
 /// A trait to allow the Treasury Pallet to spend it's funds for other purposes.
 /// There is an expectation that the implementer of this trait will correctly manage
 /// the mutable variables passed to it:
@@ -149,7 +105,7 @@ pub trait Config<I=DefaultInstance>: frame_system::Config {
 ///    not enough funds, mark this value as `true`. This will prevent the treasury
 ///    from burning the excess funds.
 #[impl_trait_for_tuples::impl_for_tuples(30)]
-pub trait SpendFunds<T: Config<I>, I=DefaultInstance> {
+pub trait SpendFunds<T: Config<I>, I: 'static = ()> {
 	fn spend_funds(
 		budget_remaining: &mut BalanceOf<T, I>,
 		imbalance: &mut PositiveImbalanceOf<T, I>,
@@ -163,7 +119,7 @@ pub type ProposalIndex = u32;
 
 /// A spending proposal.
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct Proposal<AccountId, Balance> {
 	/// The account proposing it.
 	proposer: AccountId,
@@ -175,58 +131,135 @@ pub struct Proposal<AccountId, Balance> {
 	bond: Balance,
 }
 
-decl_storage! {
-	trait Store for Module<T: Config<I>, I: Instance=DefaultInstance> as Treasury {
-		/// Number of proposals that have been made.
-		ProposalCount get(fn proposal_count): ProposalIndex;
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The treasury's pallet id, used for deriving its sovereign account ID.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The staking balance.
+		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+
+		/// Origin from which approvals must come.
+		type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin from which rejections must come.
+		type RejectOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Proposals that have been made.
-		pub Proposals get(fn proposals):
-			map hasher(twox_64_concat) ProposalIndex
-			=> Option<Proposal<T::AccountId, BalanceOf<T, I>>>;
+		/// Handler for the unbalanced decrease when slashing for a rejected proposal or bounty.
+		type OnSlash: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
 
-		/// Proposal indices that have been approved but not yet awarded.
-		pub Approvals get(fn approvals): BoundedVec<ProposalIndex, T::MaxApprovals>;
+		/// Fraction of a proposal's value that should be bonded in order to place the proposal.
+		/// An accepted proposal gets these back. A rejected proposal does not.
+		#[pallet::constant]
+		type ProposalBond: Get<Permill>;
+
+		/// Minimum amount of funds that should be placed in a deposit for making a proposal.
+		#[pallet::constant]
+		type ProposalBondMinimum: Get<BalanceOf<Self, I>>;
+
+		/// Period between successive spends.
+		#[pallet::constant]
+		type SpendPeriod: Get<Self::BlockNumber>;
+
+		/// Percentage of spare funds (if any) that are burnt per spend period.
+		#[pallet::constant]
+		type Burn: Get<Permill>;
+
+		/// Handler for the unbalanced decrease when treasury funds are burned.
+		type BurnDestination: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// Runtime hooks to external pallet using treasury to compute spend funds.
+		type SpendFunds: SpendFunds<Self, I>;
+
+		/// The maximum number of approvals that can wait in the spending queue.
+		type MaxApprovals: Get<u32>;
 	}
-	add_extra_genesis {
-		build(|_config| {
+
+	/// Number of proposals that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_count)]
+	pub(crate) type ProposalCount<T, I = ()> = StorageValue<_, ProposalIndex, ValueQuery>;
+
+	/// Proposals that have been made.
+	#[pallet::storage]
+	#[pallet::getter(fn proposals)]
+	pub type Proposals<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		ProposalIndex,
+		Proposal<T::AccountId, BalanceOf<T, I>>,
+		OptionQuery,
+	>;
+
+	/// Proposal indices that have been approved but not yet awarded.
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	pub type Approvals<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<ProposalIndex, T::MaxApprovals>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig;
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig {
+		fn build(&self) {
 			// Create Treasury account
-			let account_id = <Module<T, I>>::account_id();
+			let account_id = <Pallet<T, I>>::account_id();
 			let min = T::Currency::minimum_balance();
 			if T::Currency::free_balance(&account_id) < min {
 				let _ = T::Currency::make_free_balance_be(&account_id, min);
 			}
-		});
+		}
 	}
-}
 
-decl_event!(
-	pub enum Event<T, I=DefaultInstance>
-	where
-		Balance = BalanceOf<T, I>,
-		<T as frame_system::Config>::AccountId,
-	{
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T, I> = "Balance")]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// New proposal. \[proposal_index\]
 		Proposed(ProposalIndex),
 		/// We have ended a spend period and will now allocate funds. \[budget_remaining\]
-		Spending(Balance),
+		Spending(BalanceOf<T, I>),
 		/// Some funds have been allocated. \[proposal_index, award, beneficiary\]
-		Awarded(ProposalIndex, Balance, AccountId),
+		Awarded(ProposalIndex, BalanceOf<T, I>, T::AccountId),
 		/// A proposal was rejected; funds were slashed. \[proposal_index, slashed\]
-		Rejected(ProposalIndex, Balance),
+		Rejected(ProposalIndex, BalanceOf<T, I>),
 		/// Some of our funds have been burnt. \[burn\]
-		Burnt(Balance),
+		Burnt(BalanceOf<T, I>),
 		/// Spending has finished; this is the amount that rolls over until next spend.
 		/// \[budget_remaining\]
-		Rollover(Balance),
+		Rollover(BalanceOf<T, I>),
 		/// Some funds have been deposited. \[deposit\]
-		Deposit(Balance),
+		Deposit(BalanceOf<T, I>),
 	}
-);
 
-decl_error! {
-	/// Error for the treasury module.
-	pub enum Error for Module<T: Config<I>, I: Instance> {
+	/// Error for the treasury pallet.
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
 		/// Proposer's balance is too low.
 		InsufficientProposersBalance,
 		/// No proposal or bounty at that index.
@@ -234,33 +267,28 @@ decl_error! {
 		/// Too many approvals in the queue.
 		TooManyApprovals,
 	}
-}
-
-decl_module! {
-	pub struct Module<T: Config<I>, I: Instance=DefaultInstance>
-		for enum Call
-		where origin: T::Origin
-	{
-		/// Fraction of a proposal's value that should be bonded in order to place the proposal.
-		/// An accepted proposal gets these back. A rejected proposal does not.
-		const ProposalBond: Permill = T::ProposalBond::get();
-
-		/// Minimum amount of funds that should be placed in a deposit for making a proposal.
-		const ProposalBondMinimum: BalanceOf<T, I> = T::ProposalBondMinimum::get();
-
-		/// Period between successive spends.
-		const SpendPeriod: T::BlockNumber = T::SpendPeriod::get();
 
-		/// Percentage of spare funds (if any) that are burnt per spend period.
-		const Burn: Permill = T::Burn::get();
-
-		/// The treasury's module id, used for deriving its sovereign account ID.
-		const PalletId: PalletId = T::PalletId::get();
-
-		type Error = Error<T, I>;
-
-		fn deposit_event() = default;
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// # <weight>
+		/// - Complexity: `O(A)` where `A` is the number of approvals
+		/// - Db reads and writes: `Approvals`, `pot account data`
+		/// - Db reads and writes per approval:
+		///   `Proposals`, `proposer account data`, `beneficiary account data`
+		/// - The weight is overestimated if some approvals got missed.
+		/// # </weight>
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// Check to see if we should spend some funds!
+			if (n % T::SpendPeriod::get()).is_zero() {
+				Self::spend_funds()
+			} else {
+				0
+			}
+		}
+	}
 
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Put forward a suggestion for spending. A deposit proportional to the value
 		/// is reserved and slashed if the proposal is rejected. It is returned once the
 		/// proposal is awarded.
@@ -270,12 +298,12 @@ decl_module! {
 		/// - DbReads: `ProposalCount`, `origin account`
 		/// - DbWrites: `ProposalCount`, `Proposals`, `origin account`
 		/// # </weight>
-		#[weight = T::WeightInfo::propose_spend()]
+		#[pallet::weight(T::WeightInfo::propose_spend())]
 		pub fn propose_spend(
-			origin,
-			#[compact] value: BalanceOf<T, I>,
-			beneficiary: <T::Lookup as StaticLookup>::Source
-		) {
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T, I>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
 			let proposer = ensure_signed(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
 
@@ -284,10 +312,11 @@ decl_module! {
 				.map_err(|_| Error::<T, I>::InsufficientProposersBalance)?;
 
 			let c = Self::proposal_count();
-			<ProposalCount<I>>::put(c + 1);
+			<ProposalCount<T, I>>::put(c + 1);
 			<Proposals<T, I>>::insert(c, Proposal { proposer, value, beneficiary, bond });
 
-			Self::deposit_event(RawEvent::Proposed(c));
+			Self::deposit_event(Event::Proposed(c));
+			Ok(())
 		}
 
 		/// Reject a proposed spend. The original deposit will be slashed.
@@ -299,8 +328,11 @@ decl_module! {
 		/// - DbReads: `Proposals`, `rejected proposer account`
 		/// - DbWrites: `Proposals`, `rejected proposer account`
 		/// # </weight>
-		#[weight = (T::WeightInfo::reject_proposal(), DispatchClass::Operational)]
-		pub fn reject_proposal(origin, #[compact] proposal_id: ProposalIndex) {
+		#[pallet::weight((T::WeightInfo::reject_proposal(), DispatchClass::Operational))]
+		pub fn reject_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
 			T::RejectOrigin::ensure_origin(origin)?;
 
 			let proposal = <Proposals<T, I>>::take(&proposal_id).ok_or(Error::<T, I>::InvalidIndex)?;
@@ -309,6 +341,7 @@ decl_module! {
 			T::OnSlash::on_unbalanced(imbalance);
 
 			Self::deposit_event(Event::<T, I>::Rejected(proposal_id, value));
+			Ok(())
 		}
 
 		/// Approve a proposal. At a later time, the proposal will be allocated to the beneficiary
@@ -321,33 +354,21 @@ decl_module! {
 		/// - DbReads: `Proposals`, `Approvals`
 		/// - DbWrite: `Approvals`
 		/// # </weight>
-		#[weight = (T::WeightInfo::approve_proposal(T::MaxApprovals::get()), DispatchClass::Operational)]
-		pub fn approve_proposal(origin, #[compact] proposal_id: ProposalIndex) {
+		#[pallet::weight((T::WeightInfo::approve_proposal(T::MaxApprovals::get()), DispatchClass::Operational))]
+		pub fn approve_proposal(
+			origin: OriginFor<T>,
+			#[pallet::compact] proposal_id: ProposalIndex,
+		) -> DispatchResult {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
 			ensure!(<Proposals<T, I>>::contains_key(proposal_id), Error::<T, I>::InvalidIndex);
 			Approvals::<T, I>::try_append(proposal_id).map_err(|_| Error::<T, I>::TooManyApprovals)?;
-		}
-
-		/// # <weight>
-		/// - Complexity: `O(A)` where `A` is the number of approvals
-		/// - Db reads and writes: `Approvals`, `pot account data`
-		/// - Db reads and writes per approval:
-		///   `Proposals`, `proposer account data`, `beneficiary account data`
-		/// - The weight is overestimated if some approvals got missed.
-		/// # </weight>
-		fn on_initialize(n: T::BlockNumber) -> Weight {
-			// Check to see if we should spend some funds!
-			if (n % T::SpendPeriod::get()).is_zero() {
-				Self::spend_funds()
-			} else {
-				0
-			}
+			Ok(())
 		}
 	}
 }
 
-impl<T: Config<I>, I: Instance> Module<T, I> {
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	// Add public immutables and private mutables.
 
 	/// The account ID of the treasury pot.
@@ -368,7 +389,7 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		let mut total_weight: Weight = Zero::zero();
 
 		let mut budget_remaining = Self::pot();
-		Self::deposit_event(RawEvent::Spending(budget_remaining));
+		Self::deposit_event(Event::Spending(budget_remaining));
 		let account_id = Self::account_id();
 
 		let mut missed_any = false;
@@ -389,7 +410,7 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 						// provide the allocation.
 						imbalance.subsume(T::Currency::deposit_creating(&p.beneficiary, p.value));
 
-						Self::deposit_event(RawEvent::Awarded(index, p.value, p.beneficiary));
+						Self::deposit_event(Event::Awarded(index, p.value, p.beneficiary));
 						false
 					} else {
 						missed_any = true;
@@ -415,7 +436,7 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 			let (debit, credit) = T::Currency::pair(burn);
 			imbalance.subsume(debit);
 			T::BurnDestination::on_unbalanced(credit);
-			Self::deposit_event(RawEvent::Burnt(burn))
+			Self::deposit_event(Event::Burnt(burn))
 		}
 
 		// Must never be an error, but better to be safe.
@@ -433,7 +454,7 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 			drop(problem);
 		}
 
-		Self::deposit_event(RawEvent::Rollover(budget_remaining));
+		Self::deposit_event(Event::Rollover(budget_remaining));
 
 		total_weight
 	}
@@ -448,13 +469,13 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 
 }
 
-impl<T: Config<I>, I: Instance> OnUnbalanced<NegativeImbalanceOf<T, I>> for Module<T, I> {
+impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalanceOf<T, I>> for Pallet<T, I> {
 	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T, I>) {
 		let numeric_amount = amount.peek();
 
 		// Must resolve into existing but better to be safe.
 		let _ = T::Currency::resolve_creating(&Self::account_id(), amount);
 
-		Self::deposit_event(RawEvent::Deposit(numeric_amount));
+		Self::deposit_event(Event::Deposit(numeric_amount));
 	}
 }
@@ -23,7 +23,8 @@ use super::*;
 
 use frame_system::{RawOrigin, Pallet as System};
 use frame_benchmarking::{benchmarks, account, whitelisted_caller, impl_benchmark_test_suite};
-use sp_runtime::traits::Bounded;
+use frame_support::assert_ok;
+use sp_runtime::traits::{Bounded, CheckedMul, CheckedDiv};
 
 use crate::Pallet as Vesting;
 
@@ -40,36 +41,53 @@ fn add_locks<T: Config>(who: &T::AccountId, n: u8) {
 	}
 }
 
-fn add_vesting_schedule<T: Config>(who: &T::AccountId) -> Result<(), &'static str> {
-	let locked = 100u32;
-	let per_block = 10u32;
+fn add_vesting_schedules<T: Config>(
+	target: <T::Lookup as StaticLookup>::Source,
+	n: u32,
+) -> Result<BalanceOf<T>, &'static str> {
+	let min_transfer = T::MinVestedTransfer::get();
+	let locked = min_transfer.checked_mul(&20u32.into()).unwrap();
+	// Schedule has a duration of 20.
+	let per_block = min_transfer;
 	let starting_block = 1u32;
 
-	System::<T>::set_block_number(0u32.into());
+	let source: T::AccountId = account("source", 0, SEED);
+	let source_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(source.clone());
+	T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
 
-	// Add schedule to avoid `NotVesting` error.
-	Vesting::<T>::add_vesting_schedule(
-		&who,
-		locked.into(),
-		per_block.into(),
-		starting_block.into(),
-	)?;
-	Ok(())
+	System::<T>::set_block_number(T::BlockNumber::zero());
+
+	let mut total_locked: BalanceOf<T> = Zero::zero();
+	for _ in 0 .. n {
+		total_locked += locked;
+
+		let schedule = VestingInfo::new(locked, per_block, starting_block.into());
+		assert_ok!(Vesting::<T>::do_vested_transfer(source_lookup.clone(), target.clone(), schedule));
+
+		// Top up to guarantee we can always transfer another schedule.
+		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+	}
+
+	Ok(total_locked)
 }
 
 benchmarks! {
 	vest_locked {
-		let l in 0 .. MaxLocksOf::<T>::get();
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 1 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
 
-		let caller = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		add_locks::<T>(&caller, l as u8);
-		add_vesting_schedule::<T>(&caller)?;
+		let expected_balance = add_vesting_schedules::<T>(caller_lookup, s)?;
+
 		// At block zero, everything is vested.
-		System::<T>::set_block_number(T::BlockNumber::zero());
+		assert_eq!(System::<T>::block_number(), T::BlockNumber::zero());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
-			Some(100u32.into()),
+			Some(expected_balance.into()),
 			"Vesting schedule not added",
 		);
 	}: vest(RawOrigin::Signed(caller.clone()))
@@ -77,20 +95,24 @@ benchmarks! {
 		// Nothing happened since everything is still vested.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
-			Some(100u32.into()),
+			Some(expected_balance.into()),
 			"Vesting schedule was removed",
 		);
 	}
 
 	vest_unlocked {
-		let l in 0 .. MaxLocksOf::<T>::get();
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 1 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
 
-		let caller = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 		add_locks::<T>(&caller, l as u8);
-		add_vesting_schedule::<T>(&caller)?;
-		// At block 20, everything is unvested.
-		System::<T>::set_block_number(20u32.into());
+		add_vesting_schedules::<T>(caller_lookup, s)?;
+
+		// At block 21, everything is unlocked.
+		System::<T>::set_block_number(21u32.into());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&caller),
 			Some(BalanceOf::<T>::zero()),
@@ -107,18 +129,20 @@ benchmarks! {
 	}
 
 	vest_other_locked {
-		let l in 0 .. MaxLocksOf::<T>::get();
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let other: T::AccountId = account("other", 0, SEED);
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
-		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
+
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
+		let expected_balance = add_vesting_schedules::<T>(other_lookup.clone(), s)?;
+
 		// At block zero, everything is vested.
-		System::<T>::set_block_number(T::BlockNumber::zero());
+		assert_eq!(System::<T>::block_number(), T::BlockNumber::zero());
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
-			Some(100u32.into()),
+			Some(expected_balance),
 			"Vesting schedule not added",
 		);
 
@@ -128,21 +152,23 @@ benchmarks! {
 		// Nothing happened since everything is still vested.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
-			Some(100u32.into()),
+			Some(expected_balance.into()),
 			"Vesting schedule was removed",
 		);
 	}
 
 	vest_other_unlocked {
-		let l in 0 .. MaxLocksOf::<T>::get();
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 1 .. T::MaxVestingSchedules::get();
 
 		let other: T::AccountId = account("other", 0, SEED);
 		let other_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(other.clone());
-		T::Currency::make_free_balance_be(&other, BalanceOf::<T>::max_value());
+
 		add_locks::<T>(&other, l as u8);
-		add_vesting_schedule::<T>(&other)?;
-		// At block 20, everything is unvested.
-		System::<T>::set_block_number(20u32.into());
+		add_vesting_schedules::<T>(other_lookup.clone(), s)?;
+		// At block 21 everything is unlocked.
+		System::<T>::set_block_number(21u32.into());
+
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
 			Some(BalanceOf::<T>::zero()),
@@ -152,7 +178,7 @@ benchmarks! {
 		let caller: T::AccountId = whitelisted_caller();
 	}: vest_other(RawOrigin::Signed(caller.clone()), other_lookup)
 	verify {
-		// Vesting schedule is removed!
+		// Vesting schedule is removed.
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&other),
 			None,
@@ -161,71 +187,189 @@ benchmarks! {
 	}
 
 	vested_transfer {
-		let l in 0 .. MaxLocksOf::<T>::get();
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
 
 		let caller: T::AccountId = whitelisted_caller();
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
 		// Give target existing locks
 		add_locks::<T>(&target, l as u8);
+		// Add `s` vesting schedules.
+		let mut expected_balance = add_vesting_schedules::<T>(target_lookup.clone(), s)?;
 
 		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20u32.into()).unwrap();
+		expected_balance += transfer_amount;
 
-		let vesting_schedule = VestingInfo {
-			locked: transfer_amount,
-			per_block: 10u32.into(),
-			starting_block: 1u32.into(),
-		};
+		let vesting_schedule = VestingInfo::new(
+			transfer_amount,
+			per_block,
+			1u32.into(),
+		);
 	}: _(RawOrigin::Signed(caller), target_lookup, vesting_schedule)
 	verify {
 		assert_eq!(
-			T::MinVestedTransfer::get(),
+			expected_balance,
 			T::Currency::free_balance(&target),
 			"Transfer didn't happen",
 		);
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&target),
-			Some(T::MinVestedTransfer::get()),
-			"Lock not created",
+			Some(expected_balance),
+			"Lock not correctly updated",
 		);
 	}
 
 	force_vested_transfer {
-		let l in 0 .. MaxLocksOf::<T>::get();
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 0 .. T::MaxVestingSchedules::get() - 1;
 
 		let source: T::AccountId = account("source", 0, SEED);
 		let source_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(source.clone());
 		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
 		// Give target existing locks
 		add_locks::<T>(&target, l as u8);
+		// Add `s` vesting schedules.
+		let mut expected_balance = add_vesting_schedules::<T>(target_lookup.clone(), s)?;
 
 		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20u32.into()).unwrap();
+		expected_balance += transfer_amount;
 
-		let vesting_schedule = VestingInfo {
-			locked: transfer_amount,
-			per_block: 10u32.into(),
-			starting_block: 1u32.into(),
-		};
+		let vesting_schedule = VestingInfo::new(
+			transfer_amount,
+			per_block,
+			1u32.into(),
+		);
 	}: _(RawOrigin::Root, source_lookup, target_lookup, vesting_schedule)
 	verify {
 		assert_eq!(
-			T::MinVestedTransfer::get(),
+			expected_balance,
 			T::Currency::free_balance(&target),
 			"Transfer didn't happen",
 		);
 		assert_eq!(
 			Vesting::<T>::vesting_balance(&target),
-			Some(T::MinVestedTransfer::get()),
-			"Lock not created",
+			Some(expected_balance.into()),
+			"Lock not correctly updated",
+		);
+	}
+
+	not_unlocking_merge_schedules {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		// Give target existing locks.
+		add_locks::<T>(&caller, l as u8);
+		// Add `s` vesting schedules.
+		let expected_balance = add_vesting_schedules::<T>(caller_lookup.clone(), s)?;
+
+		// Schedules are not vesting at block 0.
+		assert_eq!(System::<T>::block_number(), T::BlockNumber::zero());
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(expected_balance),
+			"Vesting balance should equal sum locked of all schedules",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			s as usize,
+			"There should be exactly `s` vesting schedules"
+		);
+	}: merge_schedules(RawOrigin::Signed(caller.clone()), 0, s - 1)
+	verify {
+		let expected_schedule = VestingInfo::new(
+			T::MinVestedTransfer::get() * 20u32.into() * 2u32.into(),
+			T::MinVestedTransfer::get() * 2u32.into(),
+			1u32.into(),
+		);
+		let expected_index = (s - 2) as usize;
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap()[expected_index],
+			expected_schedule
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(expected_balance),
+			"Vesting balance should equal total locked of all schedules",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			(s - 1) as usize,
+			"Schedule count should reduce by 1"
+		);
+	}
+
+	unlocking_merge_schedules {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 2 .. T::MaxVestingSchedules::get();
+
+		// Destination used just for currency transfers in asserts.
+		let test_dest: T::AccountId = account("test_dest", 0, SEED);
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		// Give target other locks.
+		add_locks::<T>(&caller, l as u8);
+		// Add `s` vesting schedules.
+		let total_transferred = add_vesting_schedules::<T>(caller_lookup.clone(), s)?;
+
+		// Go half way through all the schedules duration. (They all start at 1 and have a duration of 20).
+		System::<T>::set_block_number(11u32.into());
+		// We expect half the original locked balance.
+		let expected_balance = total_transferred / 2u32.into();
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(expected_balance),
+			"Vesting balance should reflect that we are half way through all schedules duration",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			s as usize,
+			"There should be exactly `s` vesting schedules"
+		);
+		// The balance is not actually transferable because it has not been unlocked.
+		assert!(T::Currency::transfer(&caller, &test_dest, expected_balance, ExistenceRequirement::AllowDeath).is_err());
+	}: merge_schedules(RawOrigin::Signed(caller.clone()), 0, s - 1)
+	verify {
+		let expected_schedule = VestingInfo::new(
+			T::MinVestedTransfer::get() * 2u32.into() * 10u32.into(),
+			T::MinVestedTransfer::get() * 2u32.into(),
+			11u32.into(),
+		);
+		let expected_index = (s - 2) as usize;
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap()[expected_index],
+			expected_schedule,
+			"New schedule is properly created and placed"
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&caller),
+			Some(expected_balance),
+			"Vesting balance should equal half total locked of all schedules",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting(&caller).unwrap().len(),
+			(s - 1) as usize,
+			"Schedule count should reduce by 1"
+		);
+		// Since merge unlocks all schedules we can now transfer the balance.
+		assert_ok!(
+			T::Currency::transfer(&caller, &test_dest, expected_balance, ExistenceRequirement::AllowDeath)
 		);
 	}
 }
 
 impl_benchmark_test_suite!(
 	Vesting,
-	crate::tests::ExtBuilder::default().existential_deposit(256).build(),
-	crate::tests::Test,
+	crate::mock::ExtBuilder::default().existential_deposit(256).build(),
+	crate::mock::Test,
 );
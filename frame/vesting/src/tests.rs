@@ -0,0 +1,682 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the vesting pallet.
+
+use super::*;
+use crate::mock::{Balances, ExtBuilder, System, Test, Vesting};
+
+use frame_support::{assert_ok, assert_noop, assert_storage_noop, traits::OnRuntimeUpgrade};
+use sp_runtime::traits::{BadOrigin, Identity};
+use frame_system::RawOrigin;
+
+#[test]
+fn check_vesting_status() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user1_free_balance = Balances::free_balance(&1);
+			let user2_free_balance = Balances::free_balance(&2);
+			let user12_free_balance = Balances::free_balance(&12);
+			assert_eq!(user1_free_balance, 256 * 10); // Account 1 has free balance
+			assert_eq!(user2_free_balance, 256 * 20); // Account 2 has free balance
+			assert_eq!(user12_free_balance, 256 * 10); // Account 12 has free balance
+			let user1_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 128, // Vesting over 10 blocks
+				starting_block: 0,
+			};
+			let user2_vesting_schedule = VestingInfo {
+				locked: 256 * 20,
+				per_block: 256, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			let user12_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_eq!(Vesting::vesting(&1).unwrap(), vec![user1_vesting_schedule]); // Account 1 has a vesting schedule
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]); // Account 2 has a vesting schedule
+			assert_eq!(Vesting::vesting(&12).unwrap(), vec![user12_vesting_schedule]); // Account 12 has a vesting schedule
+
+			// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
+			assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
+			// Account 2 has their full balance locked
+			assert_eq!(Vesting::vesting_balance(&2), Some(user2_free_balance));
+			// Account 12 has only their illiquid funds locked
+			assert_eq!(Vesting::vesting_balance(&12), Some(user12_free_balance - 256 * 5));
+
+			System::set_block_number(10);
+			assert_eq!(System::block_number(), 10);
+
+			// Account 1 has fully vested by block 10
+			assert_eq!(Vesting::vesting_balance(&1), Some(0));
+			// Account 2 has started vesting by block 10
+			assert_eq!(Vesting::vesting_balance(&2), Some(user2_free_balance));
+			// Account 12 has started vesting by block 10
+			assert_eq!(Vesting::vesting_balance(&12), Some(user12_free_balance - 256 * 5));
+
+			System::set_block_number(30);
+			assert_eq!(System::block_number(), 30);
+
+			assert_eq!(Vesting::vesting_balance(&1), Some(0)); // Account 1 is still fully vested, and not negative
+			assert_eq!(Vesting::vesting_balance(&2), Some(0)); // Account 2 has fully vested by block 30
+			assert_eq!(Vesting::vesting_balance(&12), Some(0)); // Account 2 has fully vested by block 30
+
+		});
+}
+
+#[test]
+fn unvested_balance_should_not_transfer() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let user1_free_balance = Balances::free_balance(&1);
+			assert_eq!(user1_free_balance, 100); // Account 1 has free balance
+			// Account 1 has only 5 units vested at block 1 (plus 50 unvested)
+			assert_eq!(Vesting::vesting_balance(&1), Some(45));
+			assert_noop!(
+				Balances::transfer(Some(1).into(), 2, 56),
+				pallet_balances::Error::<Test, _>::LiquidityRestrictions,
+			); // Account 1 cannot send more than vested amount
+		});
+}
+
+#[test]
+fn vested_balance_should_transfer() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let user1_free_balance = Balances::free_balance(&1);
+			assert_eq!(user1_free_balance, 100); // Account 1 has free balance
+			// Account 1 has only 5 units vested at block 1 (plus 50 unvested)
+			assert_eq!(Vesting::vesting_balance(&1), Some(45));
+			assert_ok!(Vesting::vest(Some(1).into()));
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 55));
+		});
+}
+
+#[test]
+fn vested_balance_should_transfer_using_vest_other() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			let user1_free_balance = Balances::free_balance(&1);
+			assert_eq!(user1_free_balance, 100); // Account 1 has free balance
+			// Account 1 has only 5 units vested at block 1 (plus 50 unvested)
+			assert_eq!(Vesting::vesting_balance(&1), Some(45));
+			assert_ok!(Vesting::vest_other(Some(2).into(), 1));
+			assert_ok!(Balances::transfer(Some(1).into(), 2, 55));
+		});
+}
+
+#[test]
+fn extra_balance_should_transfer() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Balances::transfer(Some(3).into(), 1, 100));
+			assert_ok!(Balances::transfer(Some(3).into(), 2, 100));
+
+			let user1_free_balance = Balances::free_balance(&1);
+			assert_eq!(user1_free_balance, 200); // Account 1 has 100 more free balance than normal
+
+			let user2_free_balance = Balances::free_balance(&2);
+			assert_eq!(user2_free_balance, 300); // Account 2 has 100 more free balance than normal
+
+			// Account 1 has only 5 units vested at block 1 (plus 150 unvested)
+			assert_eq!(Vesting::vesting_balance(&1), Some(45));
+			assert_ok!(Vesting::vest(Some(1).into()));
+			assert_ok!(Balances::transfer(Some(1).into(), 3, 155)); // Account 1 can send extra units gained
+
+			// Account 2 has no units vested at block 1, but gained 100
+			assert_eq!(Vesting::vesting_balance(&2), Some(200));
+			assert_ok!(Vesting::vest(Some(2).into()));
+			assert_ok!(Balances::transfer(Some(2).into(), 3, 100)); // Account 2 can send extra units gained
+		});
+}
+
+#[test]
+fn liquid_funds_should_transfer_with_delayed_vesting() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user12_free_balance = Balances::free_balance(&12);
+
+			assert_eq!(user12_free_balance, 2560); // Account 12 has free balance
+			// Account 12 has liquid funds
+			assert_eq!(Vesting::vesting_balance(&12), Some(user12_free_balance - 256 * 5));
+
+			// Account 12 has delayed vesting
+			let user12_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_eq!(Vesting::vesting(&12).unwrap(), vec![user12_vesting_schedule]);
+
+			// Account 12 can still send liquid funds
+			assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
+		});
+}
+
+#[test]
+fn vested_transfer_works() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user3_free_balance = Balances::free_balance(&3);
+			let user4_free_balance = Balances::free_balance(&4);
+			assert_eq!(user3_free_balance, 256 * 30);
+			assert_eq!(user4_free_balance, 256 * 40);
+			// Account 4 should not have any vesting yet.
+			assert_eq!(Vesting::vesting(&4), None);
+			// Make the schedule for the new transfer.
+			let new_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
+			// Now account 4 should have vesting.
+			assert_eq!(Vesting::vesting(&4).unwrap(), vec![new_vesting_schedule]);
+			// Ensure the transfer happened correctly.
+			let user3_free_balance_updated = Balances::free_balance(&3);
+			assert_eq!(user3_free_balance_updated, 256 * 25);
+			let user4_free_balance_updated = Balances::free_balance(&4);
+			assert_eq!(user4_free_balance_updated, 256 * 45);
+			// Account 4 has 5 * 256 locked.
+			assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5));
+
+			System::set_block_number(20);
+			assert_eq!(System::block_number(), 20);
+
+			// Account 4 has 5 * 64 units vested by block 20.
+			assert_eq!(Vesting::vesting_balance(&4), Some(10 * 64));
+
+			System::set_block_number(30);
+			assert_eq!(System::block_number(), 30);
+
+			// Account 4 has fully vested.
+			assert_eq!(Vesting::vesting_balance(&4), Some(0));
+		});
+}
+
+#[test]
+fn vested_transfer_correctly_fails() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user2_free_balance = Balances::free_balance(&2);
+			let user4_free_balance = Balances::free_balance(&4);
+			assert_eq!(user2_free_balance, 256 * 20);
+			assert_eq!(user4_free_balance, 256 * 40);
+			// Account 2 should already have a vesting schedule.
+			let user2_vesting_schedule = VestingInfo {
+				locked: 256 * 20,
+				per_block: 256, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]);
+
+			// Fails due to a `per_block` of 0, which would never vest.
+			let new_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 0,
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule),
+				Error::<Test>::InvalidScheduleParams,
+			);
+
+			// Fails due to too low transfer amount.
+			let new_vesting_schedule_too_low = VestingInfo {
+				locked: 256 * 1,
+				per_block: 64,
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule_too_low),
+				Error::<Test>::AmountLow,
+			);
+
+			// Verify no currency transfer happened.
+			assert_eq!(user2_free_balance, 256 * 20);
+			assert_eq!(user4_free_balance, 256 * 40);
+		});
+}
+
+#[test]
+fn force_vested_transfer_works() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user3_free_balance = Balances::free_balance(&3);
+			let user4_free_balance = Balances::free_balance(&4);
+			assert_eq!(user3_free_balance, 256 * 30);
+			assert_eq!(user4_free_balance, 256 * 40);
+			// Account 4 should not have any vesting yet.
+			assert_eq!(Vesting::vesting(&4), None);
+			// Make the schedule for the new transfer.
+			let new_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_noop!(Vesting::force_vested_transfer(Some(4).into(), 3, 4, new_vesting_schedule), BadOrigin);
+			assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, new_vesting_schedule));
+			// Now account 4 should have vesting.
+			assert_eq!(Vesting::vesting(&4).unwrap(), vec![new_vesting_schedule]);
+			// Ensure the transfer happened correctly.
+			let user3_free_balance_updated = Balances::free_balance(&3);
+			assert_eq!(user3_free_balance_updated, 256 * 25);
+			let user4_free_balance_updated = Balances::free_balance(&4);
+			assert_eq!(user4_free_balance_updated, 256 * 45);
+			// Account 4 has 5 * 256 locked.
+			assert_eq!(Vesting::vesting_balance(&4), Some(256 * 5));
+
+			System::set_block_number(20);
+			assert_eq!(System::block_number(), 20);
+
+			// Account 4 has 5 * 64 units vested by block 20.
+			assert_eq!(Vesting::vesting_balance(&4), Some(10 * 64));
+
+			System::set_block_number(30);
+			assert_eq!(System::block_number(), 30);
+
+			// Account 4 has fully vested.
+			assert_eq!(Vesting::vesting_balance(&4), Some(0));
+		});
+}
+
+#[test]
+fn force_vested_transfer_correctly_fails() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user2_free_balance = Balances::free_balance(&2);
+			let user4_free_balance = Balances::free_balance(&4);
+			assert_eq!(user2_free_balance, 256 * 20);
+			assert_eq!(user4_free_balance, 256 * 40);
+			// Account 2 should already have a vesting schedule.
+			let user2_vesting_schedule = VestingInfo {
+				locked: 256 * 20,
+				per_block: 256, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]);
+
+			// Fails due to a `per_block` of 0, which would never vest.
+			let new_vesting_schedule = VestingInfo {
+				locked: 256 * 5,
+				per_block: 0,
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule),
+				Error::<Test>::InvalidScheduleParams,
+			);
+
+			// Fails due to too low transfer amount.
+			let new_vesting_schedule_too_low = VestingInfo {
+				locked: 256 * 1,
+				per_block: 64,
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, new_vesting_schedule_too_low),
+				Error::<Test>::AmountLow,
+			);
+
+			// Verify no currency transfer happened.
+			assert_eq!(user2_free_balance, 256 * 20);
+			assert_eq!(user4_free_balance, 256 * 40);
+		});
+}
+
+#[test]
+fn vested_transfer_allows_multiple_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user2_free_balance = Balances::free_balance(&2);
+			assert_eq!(user2_free_balance, 256 * 20);
+			let user2_vesting_schedule = VestingInfo::new(256 * 20, 256, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]);
+
+			// A second schedule is added on top of the existing one.
+			let new_vesting_schedule = VestingInfo::new(256 * 5, 64, 10);
+			assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, new_vesting_schedule));
+			assert_eq!(
+				Vesting::vesting(&2).unwrap(),
+				vec![user2_vesting_schedule, new_vesting_schedule],
+			);
+			assert_eq!(Balances::free_balance(&2), 256 * 25);
+			// Both schedules are locked in full.
+			assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+
+			System::set_block_number(20);
+			// Half of the first schedule and half of the second schedule have vested.
+			assert_eq!(Vesting::vesting_balance(&2), Some(256 * 10 + 64 * 10));
+			assert_ok!(Vesting::vest(Some(2).into()));
+			assert_eq!(Vesting::vesting(&2).unwrap().len(), 2);
+
+			System::set_block_number(30);
+			// Both schedules have fully vested, so they are removed together with the lock.
+			assert_eq!(Vesting::vesting_balance(&2), Some(0));
+			assert_ok!(Vesting::vest(Some(2).into()));
+			assert_eq!(Vesting::vesting(&2), None);
+			assert_ok!(Balances::transfer(Some(2).into(), 3, 256 * 25));
+		});
+}
+
+#[test]
+fn vested_transfer_fails_at_max_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let schedule = VestingInfo::new(256 * 5, 64, 10);
+			// Account 2 starts with a single schedule; fill it up to `MaxVestingSchedules`.
+			for _ in 1 .. <Test as Config>::MaxVestingSchedules::get() {
+				assert_ok!(Vesting::vested_transfer(Some(13).into(), 2, schedule));
+			}
+			assert_eq!(Vesting::vesting(&2).unwrap().len(), 3);
+
+			let user13_free_balance = Balances::free_balance(&13);
+			assert_noop!(
+				Vesting::vested_transfer(Some(13).into(), 2, schedule),
+				Error::<Test>::AtMaxVestingSchedules,
+			);
+			assert_noop!(
+				Vesting::force_vested_transfer(RawOrigin::Root.into(), 13, 2, schedule),
+				Error::<Test>::AtMaxVestingSchedules,
+			);
+			// No funds were moved.
+			assert_eq!(Balances::free_balance(&13), user13_free_balance);
+		});
+}
+
+#[test]
+fn merge_schedules_that_have_not_started() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			// Account 2 should already have a vesting schedule.
+			let sched0 = VestingInfo::new(256 * 20, 256, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0]);
+			assert_eq!(Balances::usable_balance(&2), 0);
+
+			// Add a schedule that is identical to the one that already exists.
+			assert_ok!(Vesting::vested_transfer(Some(13).into(), 2, sched0));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0, sched0]);
+			assert_eq!(Balances::usable_balance(&2), 0);
+
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+
+			// Since we merged identical schedules, the new schedule finishes at the same
+			// time as the original, just with double the amount.
+			let sched1 = VestingInfo::new(sched0.locked * 2, sched0.per_block * 2, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched1]);
+			assert_eq!(Balances::usable_balance(&2), 0);
+		});
+}
+
+#[test]
+fn merge_ongoing_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			// Account 2 should already have a vesting schedule, ending at block 30.
+			let sched0 = VestingInfo::new(256 * 20, 256, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0]);
+
+			// A second schedule ending at block 55.
+			let sched1 = VestingInfo::new(256 * 10, 64, 15);
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0, sched1]);
+
+			System::set_block_number(20);
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+
+			// The merged schedule starts at the current block, ends at the later of the two
+			// ending blocks and locks whatever was still locked across both schedules.
+			let locked = sched0.locked_at::<Identity>(20) + sched1.locked_at::<Identity>(20);
+			assert_eq!(locked, 256 * 10 + 256 * 10 - 64 * 5);
+			let sched2 = VestingInfo::new(locked, locked / (55 - 20), 20);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched2]);
+			assert_eq!(Vesting::vesting_balance(&2), Some(locked));
+			assert_eq!(Balances::usable_balance(&2), Balances::free_balance(&2) - locked);
+		});
+}
+
+#[test]
+fn merge_finished_and_ongoing_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			// Account 2 should already have a vesting schedule, ending at block 30.
+			let sched0 = VestingInfo::new(256 * 20, 256, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0]);
+
+			// A second schedule ending at block 50.
+			let sched1 = VestingInfo::new(256 * 10, 64, 10);
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+
+			System::set_block_number(30);
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+
+			// The first schedule has finished, so the ongoing one is kept untouched.
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched1]);
+			assert_eq!(Vesting::vesting_balance(&2), Some(sched1.locked_at::<Identity>(30)));
+
+			// Once the remaining schedule has finished, merging removes everything.
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+			System::set_block_number(50);
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+			assert_eq!(Vesting::vesting(&2), None);
+			assert_eq!(Balances::usable_balance(&2), Balances::free_balance(&2));
+		});
+}
+
+#[test]
+fn merge_schedules_throws_proper_errors() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			// Account 2 should already have a vesting schedule.
+			let sched0 = VestingInfo::new(256 * 20, 256, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0]);
+
+			// There is only 1 vesting schedule.
+			assert_noop!(
+				Vesting::merge_schedules(Some(2).into(), 0, 1),
+				Error::<Test>::ScheduleIndexOutOfBounds,
+			);
+
+			// There are 0 vesting schedules.
+			assert_eq!(Vesting::vesting(&4), None);
+			assert_noop!(
+				Vesting::merge_schedules(Some(4).into(), 0, 1),
+				Error::<Test>::NotVesting,
+			);
+
+			// Merging a schedule with itself is a no-op.
+			assert_storage_noop!(Vesting::merge_schedules(Some(2).into(), 0, 0).unwrap());
+		});
+}
+
+#[test]
+fn add_and_remove_vesting_schedule_works() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let sched0 = VestingInfo::new(256 * 20, 256, 10);
+			let sched1 = VestingInfo::new(256 * 5, 64, 10);
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0]);
+
+			assert_noop!(
+				<Vesting as VestingSchedule<u64>>::add_vesting_schedule(&2, 256 * 5, 0, 10),
+				Error::<Test>::InvalidScheduleParams,
+			);
+			assert_ok!(<Vesting as VestingSchedule<u64>>::add_vesting_schedule(
+				&2,
+				sched1.locked,
+				sched1.per_block,
+				sched1.starting_block,
+			));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched0, sched1]);
+
+			assert_noop!(
+				<Vesting as VestingSchedule<u64>>::remove_vesting_schedule(&2, 2),
+				Error::<Test>::ScheduleIndexOutOfBounds,
+			);
+			assert_ok!(<Vesting as VestingSchedule<u64>>::remove_vesting_schedule(&2, 0));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched1]);
+			assert_eq!(Balances::usable_balance(&2), Balances::free_balance(&2) - sched1.locked);
+
+			assert_ok!(<Vesting as VestingSchedule<u64>>::remove_vesting_schedule(&2, 0));
+			assert_eq!(Vesting::vesting(&2), None);
+			assert_eq!(Balances::usable_balance(&2), Balances::free_balance(&2));
+			assert_noop!(
+				<Vesting as VestingSchedule<u64>>::remove_vesting_schedule(&2, 0),
+				Error::<Test>::NotVesting,
+			);
+		});
+}
+
+#[test]
+fn can_add_vesting_schedule_respects_max_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Vesting as VestingSchedule<u64>>::can_add_vesting_schedule(&2, 256 * 5, 64, 10));
+			assert_noop!(
+				<Vesting as VestingSchedule<u64>>::can_add_vesting_schedule(&2, 256 * 5, 0, 10),
+				Error::<Test>::InvalidScheduleParams,
+			);
+
+			for _ in 1 .. <Test as Config>::MaxVestingSchedules::get() {
+				assert_ok!(<Vesting as VestingSchedule<u64>>::add_vesting_schedule(&2, 256 * 5, 64, 10));
+			}
+			assert_noop!(
+				<Vesting as VestingSchedule<u64>>::can_add_vesting_schedule(&2, 256 * 5, 64, 10),
+				Error::<Test>::AtMaxVestingSchedules,
+			);
+			assert_noop!(
+				<Vesting as VestingSchedule<u64>>::add_vesting_schedule(&2, 256 * 5, 64, 10),
+				Error::<Test>::AtMaxVestingSchedules,
+			);
+		});
+}
+
+#[test]
+fn multiple_schedules_from_genesis_config_work() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.vesting_genesis_config(vec![
+			(4, 0, 10, 256 * 20),
+			(4, 10, 20, 256 * 20),
+		])
+		.build()
+		.execute_with(|| {
+			let sched0 = VestingInfo::new(256 * 20, 512, 0);
+			let sched1 = VestingInfo::new(256 * 20, 256, 10);
+			assert_eq!(Vesting::vesting(&4).unwrap(), vec![sched0, sched1]);
+			// Both schedules are locked, less what vested from the first one at block 1.
+			assert_eq!(Vesting::vesting_balance(&4), Some(256 * 40 - 512));
+			assert_eq!(Balances::usable_balance(&4), 0);
+		});
+}
+
+#[test]
+#[should_panic(expected = "Too many vesting schedules at genesis.")]
+fn too_many_schedules_at_genesis_panics() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.vesting_genesis_config(vec![
+			(4, 0, 10, 256 * 30),
+			(4, 0, 10, 256 * 30),
+			(4, 0, 10, 256 * 30),
+			(4, 0, 10, 256 * 30),
+		])
+		.build();
+}
+
+#[test]
+#[should_panic(expected = "Invalid VestingInfo params at genesis")]
+fn invalid_schedule_at_genesis_panics() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.vesting_genesis_config(vec![(4, 0, 10, 256 * 40)])
+		.build();
+}
+
+#[test]
+fn migrate_single_schedule_to_bounded_vec() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.vesting_genesis_config(vec![])
+		.build()
+		.execute_with(|| {
+			let schedule = VestingInfo::new(256 * 5, 64, 10);
+			// Write a schedule in the single schedule layout.
+			sp_io::storage::set(&pallet::Vesting::<Test>::hashed_key_for(&4), &schedule.encode());
+			StorageVersion::<Test>::put(Releases::V0);
+
+			<Vesting as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
+			assert_eq!(Vesting::vesting(&4).unwrap(), vec![schedule]);
+		});
+}
+
+#[test]
+fn vesting_info_ending_block_as_balance_works() {
+	// Treats `per_block` 0 as 1.
+	let per_block_0 = VestingInfo::new(256u32, 0u32, 10u32);
+	assert_eq!(per_block_0.ending_block_as_balance::<Identity>(), 256 + 10);
+
+	// `per_block >= locked` always results in a schedule ending the block after it starts.
+	let per_block_gt_locked = VestingInfo::new(256u32, 256 * 2u32, 10u32);
+	assert_eq!(per_block_gt_locked.ending_block_as_balance::<Identity>(), 10 + 1);
+
+	// Correctly calcs end if `locked % per_block != 0`. (We need a block to unlock the remainder).
+	let imperfect_per_block = VestingInfo::new(256u32, 7u32, 10u32);
+	assert_eq!(imperfect_per_block.ending_block_as_balance::<Identity>(), 10 + 256 / 7 + 1);
+	assert!(imperfect_per_block.locked_at::<Identity>(10 + 256 / 7) > 0);
+	assert_eq!(imperfect_per_block.locked_at::<Identity>(10 + 256 / 7 + 1), 0);
+}
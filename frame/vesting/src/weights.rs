@@ -17,7 +17,7 @@
 
 //! Weights for pallet_vesting
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
-//! DATE: 2020-10-27, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! DATE: 2021-07-16, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
 
 // Executed Command:
@@ -43,106 +43,152 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_vesting.
 pub trait WeightInfo {
-	fn vest_locked(l: u32, ) -> Weight;
-	fn vest_unlocked(l: u32, ) -> Weight;
-	fn vest_other_locked(l: u32, ) -> Weight;
-	fn vest_other_unlocked(l: u32, ) -> Weight;
-	fn vested_transfer(l: u32, ) -> Weight;
-	fn force_vested_transfer(l: u32, ) -> Weight;
+	fn vest_locked(l: u32, s: u32, ) -> Weight;
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight;
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
+	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 
 }
 
 /// Weights for pallet_vesting using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	fn vest_locked(l: u32, ) -> Weight {
-		(57_472_000 as Weight)
-			.saturating_add((155_000 as Weight).saturating_mul(l as Weight))
+	fn vest_locked(l: u32, s: u32, ) -> Weight {
+		(50_642_000 as Weight)
+			.saturating_add((144_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((177_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 
 	}
-	fn vest_unlocked(l: u32, ) -> Weight {
-		(61_681_000 as Weight)
-			.saturating_add((138_000 as Weight).saturating_mul(l as Weight))
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight {
+		(50_830_000 as Weight)
+			.saturating_add((115_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((112_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 
 	}
-	fn vest_other_locked(l: u32, ) -> Weight {
-		(56_910_000 as Weight)
-			.saturating_add((160_000 as Weight).saturating_mul(l as Weight))
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight {
+		(52_151_000 as Weight)
+			.saturating_add((130_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((162_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 
 	}
-	fn vest_other_unlocked(l: u32, ) -> Weight {
-		(61_319_000 as Weight)
-			.saturating_add((144_000 as Weight).saturating_mul(l as Weight))
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight {
+		(51_009_000 as Weight)
+			.saturating_add((131_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((112_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 
 	}
-	fn vested_transfer(l: u32, ) -> Weight {
-		(124_996_000 as Weight)
-			.saturating_add((209_000 as Weight).saturating_mul(l as Weight))
+	fn vested_transfer(l: u32, s: u32, ) -> Weight {
+		(89_517_000 as Weight)
+			.saturating_add((114_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((23_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 
 	}
-	fn force_vested_transfer(l: u32, ) -> Weight {
-		(123_911_000 as Weight)
-			.saturating_add((213_000 as Weight).saturating_mul(l as Weight))
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight {
+		(87_903_000 as Weight)
+			.saturating_add((121_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((56_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 
 	}
+	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
+		(54_463_000 as Weight)
+			.saturating_add((123_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((149_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+
+	}
+	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
+		(53_674_000 as Weight)
+			.saturating_add((137_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((152_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+
+	}
 
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn vest_locked(l: u32, ) -> Weight {
-		(57_472_000 as Weight)
-			.saturating_add((155_000 as Weight).saturating_mul(l as Weight))
+	fn vest_locked(l: u32, s: u32, ) -> Weight {
+		(50_642_000 as Weight)
+			.saturating_add((144_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((177_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 
 	}
-	fn vest_unlocked(l: u32, ) -> Weight {
-		(61_681_000 as Weight)
-			.saturating_add((138_000 as Weight).saturating_mul(l as Weight))
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight {
+		(50_830_000 as Weight)
+			.saturating_add((115_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((112_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 
 	}
-	fn vest_other_locked(l: u32, ) -> Weight {
-		(56_910_000 as Weight)
-			.saturating_add((160_000 as Weight).saturating_mul(l as Weight))
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight {
+		(52_151_000 as Weight)
+			.saturating_add((130_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((162_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 
 	}
-	fn vest_other_unlocked(l: u32, ) -> Weight {
-		(61_319_000 as Weight)
-			.saturating_add((144_000 as Weight).saturating_mul(l as Weight))
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight {
+		(51_009_000 as Weight)
+			.saturating_add((131_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((112_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 
 	}
-	fn vested_transfer(l: u32, ) -> Weight {
-		(124_996_000 as Weight)
-			.saturating_add((209_000 as Weight).saturating_mul(l as Weight))
+	fn vested_transfer(l: u32, s: u32, ) -> Weight {
+		(89_517_000 as Weight)
+			.saturating_add((114_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((23_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 
 	}
-	fn force_vested_transfer(l: u32, ) -> Weight {
-		(123_911_000 as Weight)
-			.saturating_add((213_000 as Weight).saturating_mul(l as Weight))
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight {
+		(87_903_000 as Weight)
+			.saturating_add((121_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((56_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 
 	}
+	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
+		(54_463_000 as Weight)
+			.saturating_add((123_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((149_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+
+	}
+	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight {
+		(53_674_000 as Weight)
+			.saturating_add((137_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((152_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+
+	}
 
 }
@@ -31,6 +31,8 @@ pub use sp_core::crypto::{KeyTypeId, CryptoTypeId, key_types};
 #[doc(hidden)]
 pub use codec;
 #[doc(hidden)]
+pub use scale_info;
+#[doc(hidden)]
 #[cfg(feature = "std")]
 pub use serde;
 #[doc(hidden)]
@@ -201,6 +203,7 @@ macro_rules! app_crypto_public_full_crypto {
 				$crate::codec::Decode,
 				$crate::RuntimeDebug,
 				$crate::max_encoded_len::MaxEncodedLen,
+				$crate::scale_info::TypeInfo,
 			)]
 			#[max_encoded_len_crate($crate::max_encoded_len)]
 			pub struct Public($public);
@@ -236,6 +239,7 @@ macro_rules! app_crypto_public_not_full_crypto {
 				$crate::codec::Encode,
 				$crate::codec::Decode,
 				$crate::RuntimeDebug,
+				$crate::scale_info::TypeInfo,
 			)]
 			pub struct Public($public);
 		}
@@ -403,6 +407,7 @@ macro_rules! app_crypto_signature_full_crypto {
 				$crate::codec::Encode,
 				$crate::codec::Decode,
 				$crate::RuntimeDebug,
+				$crate::scale_info::TypeInfo,
 			)]
 			#[derive(Hash)]
 			pub struct Signature($sig);
@@ -437,6 +442,7 @@ macro_rules! app_crypto_signature_not_full_crypto {
 				$crate::codec::Encode,
 				$crate::codec::Decode,
 				$crate::RuntimeDebug,
+				$crate::scale_info::TypeInfo,
 			)]
 			pub struct Signature($sig);
 		}
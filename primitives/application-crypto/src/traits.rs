@@ -19,6 +19,7 @@
 use sp_core::crypto::Pair;
 
 use codec::Codec;
+use scale_info::TypeInfo;
 use sp_core::crypto::{KeyTypeId, CryptoType, CryptoTypeId, IsWrappedBy, Public};
 use sp_std::{fmt::Debug, vec::Vec};
 
@@ -122,7 +123,7 @@ pub trait RuntimeAppPublic: Sized {
 	const CRYPTO_ID: CryptoTypeId;
 
 	/// The signature that will be generated when signing with the corresponding private key.
-	type Signature: Codec + Debug + MaybeHash + Eq + PartialEq + Clone;
+	type Signature: Codec + Debug + MaybeHash + Eq + PartialEq + Clone + TypeInfo;
 
 	/// Returns all public keys for this application in the keystore.
 	fn all() -> crate::Vec<Self>;
@@ -34,7 +34,7 @@ use crate::{
 	error::Error as ConsensusError,
 	block_import::{
 		BlockImport, BlockOrigin, BlockImportParams, ImportedAux, JustificationImport, ImportResult,
-		BlockCheckParams,
+		BlockCheckParams, ImportedState, ForkChoiceStrategy,
 	},
 	metrics::Metrics,
 };
@@ -76,6 +76,10 @@ pub struct IncomingBlock<B: BlockT> {
 	pub allow_missing_state: bool,
 	/// Re-validate existing block.
 	pub import_existing: bool,
+	/// Do not compute new state, but rather set it to the given set.
+	pub state: Option<ImportedState<B>>,
+	/// Skip block execution and state verification.
+	pub skip_execution: bool,
 }
 
 /// Type of keys in the blockchain cache that consensus module could use for its needs.
@@ -233,28 +237,42 @@ pub(crate) async fn import_single_block_metered<B: BlockT, V: Verifier<B>, Trans
 		parent_hash,
 		allow_missing_state: block.allow_missing_state,
 		import_existing: block.import_existing,
+		allow_missing_parent: block.state.is_some(),
 	}).await)? {
 		BlockImportResult::ImportedUnknown { .. } => (),
 		r => return Ok(r), // Any other successful result means that the block is already imported.
 	}
 
 	let started = wasm_timer::Instant::now();
-	let (mut import_block, maybe_keys) = verifier.verify(
-		block_origin,
-		header,
-		justifications,
-		block.body
-	).await.map_err(|msg| {
-		if let Some(ref peer) = peer {
-			trace!(target: "sync", "Verifying {}({}) from {} failed: {}", number, hash, peer, msg);
-		} else {
-			trace!(target: "sync", "Verifying {}({}) failed: {}", number, hash, msg);
-		}
-		if let Some(metrics) = metrics.as_ref() {
-			metrics.report_verification(false, started.elapsed());
-		}
-		BlockImportError::VerificationFailed(peer.clone(), msg)
-	})?;
+	let (mut import_block, maybe_keys) = if block.state.is_some() {
+		// Blocks imported together with their state either had their header verified when it
+		// was first imported, or had their finality proven by the sync (e.g. the target block
+		// of a warp sync). The latter has no parent the verifier could check it against.
+		// Either way the block is final, and so is the state the node continues from.
+		let mut import_block = BlockImportParams::new(block_origin, header);
+		import_block.justifications = justifications;
+		import_block.finalized = true;
+		import_block.body = block.body;
+		import_block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+		(import_block, None)
+	} else {
+		verifier.verify(
+			block_origin,
+			header,
+			justifications,
+			block.body
+		).await.map_err(|msg| {
+			if let Some(ref peer) = peer {
+				trace!(target: "sync", "Verifying {}({}) from {} failed: {}", number, hash, peer, msg);
+			} else {
+				trace!(target: "sync", "Verifying {}({}) failed: {}", number, hash, msg);
+			}
+			if let Some(metrics) = metrics.as_ref() {
+				metrics.report_verification(false, started.elapsed());
+			}
+			BlockImportError::VerificationFailed(peer.clone(), msg)
+		})?
+	};
 
 	if let Some(metrics) = metrics.as_ref() {
 		metrics.report_verification(true, started.elapsed());
@@ -265,6 +283,9 @@ pub(crate) async fn import_single_block_metered<B: BlockT, V: Verifier<B>, Trans
 		cache.extend(keys.into_iter());
 	}
 	import_block.allow_missing_state = block.allow_missing_state;
+	import_block.import_existing = block.import_existing;
+	import_block.skip_execution = block.skip_execution;
+	import_block.state = block.state;
 
 	let imported = import_handle.import_block(import_block.convert_transaction(), cache).await;
 	if let Some(metrics) = metrics.as_ref() {
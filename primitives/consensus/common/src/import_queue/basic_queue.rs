@@ -548,6 +548,8 @@ mod tests {
 					origin: None,
 					allow_missing_state: false,
 					import_existing: false,
+					state: None,
+					skip_execution: false,
 				}],
 			)))
 			.unwrap();
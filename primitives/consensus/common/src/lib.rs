@@ -50,7 +50,7 @@ mod metrics;
 pub use self::error::Error;
 pub use block_import::{
 	BlockCheckParams, BlockImport, BlockImportParams, BlockOrigin, ForkChoiceStrategy,
-	ImportResult, ImportedAux, JustificationImport, JustificationSyncLink,
+	ImportResult, ImportedAux, ImportedState, JustificationImport, JustificationSyncLink,
 };
 pub use select_chain::SelectChain;
 pub use sp_state_machine::Backend as StateBackend;
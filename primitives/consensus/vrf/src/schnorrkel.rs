@@ -49,6 +49,14 @@ impl Encode for VRFOutput {
 
 impl EncodeLike for VRFOutput { }
 
+impl scale_info::TypeInfo for VRFOutput {
+	type Identity = [u8; VRF_OUTPUT_LENGTH];
+
+	fn type_info() -> scale_info::Type {
+		Self::Identity::type_info()
+	}
+}
+
 impl Decode for VRFOutput {
 	fn decode<R: codec::Input>(i: &mut R) -> Result<Self, codec::Error> {
 		let decoded = <[u8; VRF_OUTPUT_LENGTH]>::decode(i)?;
@@ -97,6 +105,14 @@ impl Encode for VRFProof {
 
 impl EncodeLike for VRFProof { }
 
+impl scale_info::TypeInfo for VRFProof {
+	type Identity = [u8; VRF_PROOF_LENGTH];
+
+	fn type_info() -> scale_info::Type {
+		Self::Identity::type_info()
+	}
+}
+
 impl Decode for VRFProof {
 	fn decode<R: codec::Input>(i: &mut R) -> Result<Self, codec::Error> {
 		let decoded = <[u8; VRF_PROOF_LENGTH]>::decode(i)?;
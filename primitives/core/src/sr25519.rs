@@ -43,6 +43,7 @@ use crate::crypto::Ss58Codec;
 use crate::crypto::{Public as TraitPublic, CryptoTypePublicPair, UncheckedFrom, CryptoType, Derive, CryptoTypeId};
 use crate::hash::{H256, H512};
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_std::ops::Deref;
 
 #[cfg(feature = "std")]
@@ -62,7 +63,7 @@ pub const CRYPTO_ID: CryptoTypeId = CryptoTypeId(*b"sr25");
 #[cfg_attr(feature = "full_crypto", derive(Hash))]
 #[derive(
 	PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Encode, Decode, Default, PassByInner,
-	max_encoded_len::MaxEncodedLen,
+	max_encoded_len::MaxEncodedLen, TypeInfo,
 )]
 pub struct Public(pub [u8; 32]);
 
@@ -192,7 +193,7 @@ impl<'de> Deserialize<'de> for Public {
 /// An Schnorrkel/Ristretto x25519 ("sr25519") signature.
 ///
 /// Instead of importing it for the local module, alias it to be available as a public type
-#[derive(Encode, Decode, PassByInner)]
+#[derive(Encode, Decode, PassByInner, TypeInfo)]
 pub struct Signature(pub [u8; 64]);
 
 impl sp_std::convert::TryFrom<&[u8]> for Signature {
@@ -201,3 +201,74 @@ fn encode_impl(ident: syn::Ident, count: usize) -> TokenStream2 {
 		}
 	)
 }
+
+pub(crate) fn scale_info_impl(
+	ident: syn::Ident,
+	voter_type: syn::Type,
+	target_type: syn::Type,
+	weight_type: syn::Type,
+	count: usize,
+	compact_encoding: bool,
+) -> TokenStream2 {
+	// wrap the inner types in `Compact` if that's how they end up being encoded.
+	let maybe_compact = |ty: &syn::Type| if compact_encoding {
+		quote!(_npos::codec::Compact<#ty>)
+	} else {
+		quote!(#ty)
+	};
+	let voter = maybe_compact(&voter_type);
+	let target = maybe_compact(&target_type);
+	let weight = maybe_compact(&weight_type);
+
+	let scale_info_impl_single = {
+		let name = format!("{}", field_name_for(1));
+		quote! {
+			.field(|f|
+				f.ty::<_npos::sp_std::prelude::Vec<(#voter, #target)>>()
+					.name(#name)
+			)
+		}
+	};
+
+	let scale_info_impl_double = {
+		let name = format!("{}", field_name_for(2));
+		quote! {
+			.field(|f|
+				f.ty::<_npos::sp_std::prelude::Vec<(#voter, (#target, #weight), #target)>>()
+					.name(#name)
+			)
+		}
+	};
+
+	let scale_info_impl_rest = (3..=count).map(|c| {
+		let name = format!("{}", field_name_for(c));
+		let array_len = c - 1;
+		quote! {
+			.field(|f|
+				f.ty::<_npos::sp_std::prelude::Vec<(
+					#voter,
+					[(#target, #weight); #array_len],
+					#target
+				)>>()
+					.name(#name)
+			)
+		}
+	}).collect::<TokenStream2>();
+
+	quote!(
+		impl _npos::scale_info::TypeInfo for #ident {
+			type Identity = Self;
+
+			fn type_info() -> _npos::scale_info::Type<_npos::scale_info::form::MetaForm> {
+				_npos::scale_info::Type::builder()
+					.path(_npos::scale_info::Path::new(stringify!(#ident), module_path!()))
+					.composite(
+						_npos::scale_info::build::Fields::named()
+							#scale_info_impl_single
+							#scale_info_impl_double
+							#scale_info_impl_rest
+					)
+			}
+		}
+	)
+}
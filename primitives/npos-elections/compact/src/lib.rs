@@ -176,6 +176,15 @@ fn struct_def(
 		quote!(#[derive(Default, PartialEq, Eq, Clone, Debug, _npos::codec::Encode, _npos::codec::Decode)])
 	};
 
+	let scale_info_impl = codec::scale_info_impl(
+		ident.clone(),
+		voter_type.clone(),
+		target_type.clone(),
+		weight_type.clone(),
+		count,
+		compact_encoding,
+	);
+
 	let from_impl = assignment::from_impl(count);
 	let into_impl = assignment::into_impl(count, weight_type.clone());
 	let from_index_impl = index_assignment::from_impl(count);
@@ -185,6 +194,8 @@ fn struct_def(
 		#derives_and_maybe_compact_encoding
 		#vis struct #ident { #singles #doubles #rest }
 
+		#scale_info_impl
+
 		use _npos::__OrInvalidIndex;
 		impl _npos::CompactSolution for #ident {
 			const LIMIT: usize = #count;
@@ -18,10 +18,11 @@
 //! MultiAddress type is a wrapper for multiple downstream account formats.
 
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_std::vec::Vec;
 
 /// A multi-format address wrapper for on-chain accounts.
-#[derive(Encode, Decode, PartialEq, Eq, Clone, crate::RuntimeDebug)]
+#[derive(Encode, Decode, PartialEq, Eq, Clone, crate::RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Hash))]
 pub enum MultiAddress<AccountId, AccountIndex> {
 	/// It's an account ID (pubkey).
@@ -177,7 +177,8 @@ mod execution {
 	use hash_db::Hasher;
 	use codec::{Decode, Encode, Codec};
 	use sp_core::{
-		storage::ChildInfo, NativeOrEncoded, NeverNativeValue, hexdisplay::HexDisplay,
+		storage::{ChildInfo, ChildType, PrefixedStorageKey, well_known_keys},
+		NativeOrEncoded, NeverNativeValue, hexdisplay::HexDisplay,
 		traits::{CodeExecutor, ReadRuntimeVersionExt, RuntimeCode, SpawnNamed},
 	};
 	use sp_externalities::Extensions;
@@ -787,6 +788,265 @@ mod execution {
 		Ok(proving_backend.extract_proof())
 	}
 
+	/// Key-value pairs of a single trie, read by a range read.
+	#[derive(PartialEq, Eq, Clone, Debug, Default)]
+	pub struct KeyValueStorageLevel {
+		/// Prefixed storage key of the child trie, empty for the top trie.
+		pub storage_key: Vec<u8>,
+		/// Key-value pairs in lexicographic key order.
+		pub key_values: Vec<(Vec<u8>, Vec<u8>)>,
+		/// Whether the read reached the last key of the trie.
+		pub complete: bool,
+	}
+
+	/// Key-value pairs read by a range read. The top trie always comes first,
+	/// followed by the default child tries in the order they were read.
+	#[derive(PartialEq, Eq, Clone, Debug, Default)]
+	pub struct KeyValueStates(pub Vec<KeyValueStorageLevel>);
+
+	impl KeyValueStates {
+		/// Returns `true` if the read reached the end of the state.
+		pub fn is_complete(&self) -> bool {
+			self.0.iter().all(|level| level.complete)
+		}
+	}
+
+	/// Generate range storage read proof, including the content of the default child tries.
+	///
+	/// Keys are read in lexicographic order right after `start_at`, which is either empty,
+	/// the last top trie key already read, or the prefixed key of a default child trie followed
+	/// by the last key already read from it. When a child trie root is read from the top trie,
+	/// the child trie content is read before proceeding with the top trie.
+	///
+	/// Reading stops once the proof grows bigger than `size_limit`, though at least one
+	/// key-value pair is always part of the proof.
+	///
+	/// Returns the proof and the number of key-value pairs it contains.
+	pub fn prove_range_read_with_child_with_size<B, H>(
+		mut backend: B,
+		size_limit: usize,
+		start_at: &[Vec<u8>],
+	) -> Result<(StorageProof, u32), Box<dyn Error>>
+	where
+		B: Backend<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let trie_backend = backend.as_trie_backend()
+			.ok_or_else(|| Box::new(ExecutionError::UnableToGenerateProof) as Box<dyn Error>)?;
+		prove_range_read_with_child_with_size_on_trie_backend(trie_backend, size_limit, start_at)
+	}
+
+	/// Generate range storage read proof on pre-created trie backend.
+	///
+	/// See `prove_range_read_with_child_with_size`.
+	pub fn prove_range_read_with_child_with_size_on_trie_backend<S, H>(
+		trie_backend: &TrieBackend<S, H>,
+		size_limit: usize,
+		start_at: &[Vec<u8>],
+	) -> Result<(StorageProof, u32), Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let proving_backend = proving_backend::ProvingBackend::<S, H>::new(trie_backend);
+		let mut count = 0;
+		read_range_with_child_on_trie_backend(
+			proving_backend.trie_backend(),
+			start_at,
+			|_, _| {
+				count += 1;
+				proving_backend.estimate_encoded_size() > size_limit
+			},
+			false,
+		)?;
+		Ok((proving_backend.extract_proof(), count))
+	}
+
+	/// Read state key-values in the same range and order as
+	/// `prove_range_read_with_child_with_size`, without generating a proof.
+	///
+	/// Reading stops once the size of the read keys and values exceeds `size_limit`.
+	pub fn read_range_with_child_with_size<B, H>(
+		mut backend: B,
+		size_limit: usize,
+		start_at: &[Vec<u8>],
+	) -> Result<KeyValueStates, Box<dyn Error>>
+	where
+		B: Backend<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let trie_backend = backend.as_trie_backend()
+			.ok_or_else(|| Box::new(ExecutionError::UnableToGenerateProof) as Box<dyn Error>)?;
+		let mut size = 0;
+		read_range_with_child_on_trie_backend(
+			trie_backend,
+			start_at,
+			|key, value| {
+				size += key.len() + value.len();
+				size > size_limit
+			},
+			false,
+		)
+	}
+
+	/// Check range storage read proof, generated by `prove_range_read_with_child_with_size`
+	/// call, and return the proven key-values.
+	///
+	/// The proven range ends where the proof content ends: a trie read up to its last key is
+	/// marked as complete.
+	pub fn read_range_proof_check_with_child<H>(
+		root: H::Out,
+		proof: StorageProof,
+		start_at: &[Vec<u8>],
+	) -> Result<KeyValueStates, Box<dyn Error>>
+	where
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		let proving_backend = create_proof_check_backend::<H>(root, proof)?;
+		read_range_proof_check_with_child_on_proving_backend(&proving_backend, start_at)
+	}
+
+	/// Check range storage read proof on pre-created proving backend.
+	pub fn read_range_proof_check_with_child_on_proving_backend<H>(
+		proving_backend: &TrieBackend<MemoryDB<H>, H>,
+		start_at: &[Vec<u8>],
+	) -> Result<KeyValueStates, Box<dyn Error>>
+	where
+		H: Hasher,
+		H::Out: Ord + Codec,
+	{
+		read_range_with_child_on_trie_backend(proving_backend, start_at, |_, _| false, true)
+	}
+
+	/// Read the key-values of the top trie and of the default child tries right after
+	/// `start_at`, until `stop` returns `true`.
+	fn read_range_with_child_on_trie_backend<S, H, F>(
+		trie_backend: &TrieBackend<S, H>,
+		start_at: &[Vec<u8>],
+		mut stop: F,
+		allow_missing_nodes: bool,
+	) -> Result<KeyValueStates, Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+		F: FnMut(&[u8], &[u8]) -> bool,
+	{
+		if start_at.len() > 2 {
+			return Err(Box::new(format!("Invalid range start, got {} keys", start_at.len())));
+		}
+
+		let essence = trie_backend.essence();
+		let mut levels = vec![KeyValueStorageLevel::default()];
+
+		if let [child_storage_key, child_start] = start_at {
+			if !child_storage_key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+				return Err(Box::new(format!(
+					"Invalid range start, {} is not a default child trie key",
+					HexDisplay::from(child_storage_key),
+				)));
+			}
+			let level = read_child_range_on_trie_backend(
+				essence,
+				child_storage_key,
+				Some(child_start),
+				&mut stop,
+				allow_missing_nodes,
+			)?;
+			let complete = level.complete;
+			levels.push(level);
+			if !complete {
+				return Ok(KeyValueStates(levels));
+			}
+		}
+
+		let mut key_values = Vec::new();
+		let mut child_error = None;
+		let complete = essence.apply_to_key_values_while(
+			None,
+			start_at.first().map(Vec::as_slice),
+			|key, value| {
+				let mut stop_read = stop(&key, &value);
+				let is_child = key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX);
+				if is_child {
+					// The child trie content is always read after its root, so that the
+					// next read can resume from the top trie key.
+					match read_child_range_on_trie_backend(
+						essence,
+						&key,
+						None,
+						&mut stop,
+						allow_missing_nodes,
+					) {
+						Ok(level) => {
+							stop_read = stop_read || !level.complete;
+							levels.push(level);
+						},
+						Err(e) => {
+							child_error = Some(e);
+							stop_read = true;
+						},
+					}
+				}
+				key_values.push((key, value));
+				!stop_read
+			},
+			allow_missing_nodes,
+		).map_err(|e| Box::new(e) as Box<dyn Error>)?;
+
+		if let Some(e) = child_error {
+			return Err(e);
+		}
+
+		levels[0].key_values = key_values;
+		levels[0].complete = complete;
+		Ok(KeyValueStates(levels))
+	}
+
+	fn read_child_range_on_trie_backend<S, H, F>(
+		essence: &trie_backend_essence::TrieBackendEssence<S, H>,
+		storage_key: &Vec<u8>,
+		start_at: Option<&[u8]>,
+		stop: &mut F,
+		allow_missing_nodes: bool,
+	) -> Result<KeyValueStorageLevel, Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+		F: FnMut(&[u8], &[u8]) -> bool,
+	{
+		let child_info = match ChildType::from_prefixed_key(PrefixedStorageKey::new_ref(storage_key)) {
+			Some((ChildType::ParentKeyId, storage_key)) => ChildInfo::new_default(storage_key),
+			None => return Err(Box::new(format!(
+				"Invalid child trie key {}",
+				HexDisplay::from(storage_key),
+			))),
+		};
+
+		let mut key_values = Vec::new();
+		let complete = essence.apply_to_key_values_while(
+			Some(&child_info),
+			start_at,
+			|key, value| {
+				let stop_read = stop(&key, &value);
+				key_values.push((key, value));
+				!stop_read
+			},
+			allow_missing_nodes,
+		).map_err(|e| Box::new(e) as Box<dyn Error>)?;
+
+		Ok(KeyValueStorageLevel {
+			storage_key: storage_key.clone(),
+			key_values,
+			complete,
+		})
+	}
+
 	/// Check storage read proof, generated by `prove_read` call.
 	pub fn read_proof_check<H, I>(
 		root: H::Out,
@@ -1510,6 +1770,98 @@ mod tests {
 		assert_eq!(local_result1.get(&b"key1"[..]), Some(&Some(b"val2".to_vec())));
 	}
 
+	#[test]
+	fn prove_range_with_child_works() {
+		let child_info1 = ChildInfo::new_default(b"sub1");
+		let child_info2 = ChildInfo::new_default(b"sub2");
+		let mut remote_backend = trie_backend::tests::test_trie();
+		let (remote_root, transaction) = remote_backend.full_storage_root(
+			std::iter::empty(),
+			vec![
+				(&child_info1, vec![
+					(&b"key1"[..], Some(&b"val2"[..])),
+					(&b"key2"[..], Some(&b"val3"[..])),
+				].into_iter()),
+				(&child_info2, vec![
+					(&b"key3"[..], Some(&b"val4"[..])),
+					(&b"key4"[..], Some(&b"val5"[..])),
+				].into_iter()),
+			].into_iter(),
+		);
+		remote_backend.backend_storage_mut().consolidate(transaction);
+		remote_backend.essence.set_root(remote_root.clone());
+
+		let mut start_at: Vec<Vec<u8>> = Vec::new();
+		let mut top = Vec::new();
+		let mut children: BTreeMap<Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>> = BTreeMap::new();
+		let mut num_requests = 0;
+		loop {
+			num_requests += 1;
+			let (proof, count) = prove_range_read_with_child_with_size_on_trie_backend(
+				&remote_backend,
+				300,
+				&start_at,
+			).unwrap();
+			assert!(count > 0);
+			let states = read_range_proof_check_with_child::<BlakeTwo256>(
+				remote_root,
+				proof,
+				&start_at,
+			).unwrap();
+			let complete = states.is_complete();
+			start_at.clear();
+			for level in states.0.into_iter() {
+				if let Some((last_key, _)) = level.key_values.last() {
+					if level.storage_key.is_empty() {
+						if start_at.is_empty() {
+							start_at.push(last_key.clone());
+						}
+					} else if !level.complete {
+						start_at = vec![level.storage_key.clone(), last_key.clone()];
+					}
+				}
+				if level.storage_key.is_empty() {
+					top.extend(level.key_values);
+				} else {
+					children.entry(level.storage_key).or_default().extend(level.key_values);
+				}
+			}
+			if complete {
+				break;
+			}
+		}
+
+		assert!(num_requests > 1);
+		assert_eq!(top, remote_backend.pairs());
+		assert_eq!(children.len(), 2);
+		assert_eq!(
+			children.get(child_info1.prefixed_storage_key().as_slice()),
+			Some(&vec![
+				(b"key1".to_vec(), b"val2".to_vec()),
+				(b"key2".to_vec(), b"val3".to_vec()),
+				(b"value3".to_vec(), vec![142]),
+				(b"value4".to_vec(), vec![124]),
+			]),
+		);
+		assert_eq!(
+			children.get(child_info2.prefixed_storage_key().as_slice()),
+			Some(&vec![
+				(b"key3".to_vec(), b"val4".to_vec()),
+				(b"key4".to_vec(), b"val5".to_vec()),
+			]),
+		);
+
+		// Reading without proof and without limit returns the whole state at once.
+		let states = read_range_with_child_with_size(
+			remote_backend,
+			usize::max_value(),
+			&[],
+		).unwrap();
+		assert!(states.is_complete());
+		assert_eq!(states.0.len(), 3);
+		assert_eq!(states.0[0].key_values, top);
+	}
+
 	#[test]
 	fn child_storage_uuid() {
 
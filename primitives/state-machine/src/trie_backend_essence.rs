@@ -214,6 +214,87 @@ impl<S: TrieBackendStorage<H>, H: Hasher> TrieBackendEssence<S, H> where H::Out:
 		}
 	}
 
+	/// Execute given closure for all key and values of the trie (or of the given child trie)
+	/// that are strictly superior to `start_at` in lexicographic order.
+	/// Aborts as soon as `f` returns false.
+	///
+	/// Returns `true` if all keys were visited. When `allow_missing_nodes` is set, reaching a
+	/// trie node that is not in the storage stops the iteration and returns `false` instead of
+	/// an error.
+	pub fn apply_to_key_values_while<F: FnMut(Vec<u8>, Vec<u8>) -> bool>(
+		&self,
+		child_info: Option<&ChildInfo>,
+		start_at: Option<&[u8]>,
+		f: F,
+		allow_missing_nodes: bool,
+	) -> Result<bool> {
+		let mut child_root;
+		let root = if let Some(child_info) = child_info {
+			let fetched_child_root = match self.child_root(child_info)? {
+				Some(fetched_child_root) => fetched_child_root,
+				None => return Ok(true),
+			};
+			child_root = H::Out::default();
+			if fetched_child_root.len() != child_root.as_ref().len() {
+				return Err(format!("Invalid child storage hash at {:?}", child_info.storage_key()));
+			}
+			// note: child_root and fetched_child_root must be same size, panics otherwise.
+			child_root.as_mut().copy_from_slice(&fetched_child_root[..]);
+			&child_root
+		} else {
+			&self.root
+		};
+
+		self.trie_iter_inner(root, start_at, f, child_info, allow_missing_nodes)
+	}
+
+	fn trie_iter_inner<F: FnMut(Vec<u8>, Vec<u8>) -> bool>(
+		&self,
+		root: &H::Out,
+		start_at: Option<&[u8]>,
+		mut f: F,
+		child_info: Option<&ChildInfo>,
+		allow_missing_nodes: bool,
+	) -> Result<bool> {
+		let mut iter = move |db| -> sp_std::result::Result<bool, Box<TrieError<H::Out>>> {
+			let trie = TrieDB::<H>::new(db, root)?;
+			let mut iter = trie.iter()?;
+			if let Some(start_at) = start_at {
+				iter.seek(start_at)?;
+			}
+
+			for x in iter {
+				let (key, value) = x?;
+
+				if Some(key.as_slice()) == start_at {
+					continue;
+				}
+
+				if !f(key, value) {
+					return Ok(false);
+				}
+			}
+
+			Ok(true)
+		};
+
+		let result = if let Some(child_info) = child_info {
+			let db = KeySpacedDB::new(self, child_info.keyspace());
+			iter(&db)
+		} else {
+			iter(self)
+		};
+
+		match result {
+			Ok(completed) => Ok(completed),
+			Err(e) => match *e {
+				TrieError::IncompleteDatabase(_) | TrieError::InvalidStateRoot(_)
+					if allow_missing_nodes => Ok(false),
+				e => Err(format!("TrieDB iteration error: {}", e)),
+			},
+		}
+	}
+
 	/// Execute given closure for all keys starting with prefix.
 	pub fn for_child_keys_with_prefix<F: FnMut(&[u8])>(
 		&self,
@@ -69,6 +69,9 @@ pub enum Error {
 
 	#[error("The pool is not accepting future transactions")]
 	RejectedFutureTransaction,
+
+	#[error("The pool does not support replacing transactions")]
+	ReplacementUnsupported,
 }
 
 /// Transaction pool error conversion.
@@ -204,6 +204,20 @@ pub trait TransactionPool: Send + Sync {
 		xt: TransactionFor<Self>,
 	) -> PoolFuture<TxHash<Self>, Self::Error>;
 
+	/// Returns a future that imports one unverified transaction to the pool and resolves to its
+	/// hash along with the hashes of the transactions it replaced.
+	///
+	/// Pools that can't replace transactions keep the default, which always fails with
+	/// [`ReplacementUnsupported`](crate::error::Error::ReplacementUnsupported).
+	fn submit_replacing(
+		&self,
+		_at: &BlockId<Self::Block>,
+		_source: TransactionSource,
+		_xt: TransactionFor<Self>,
+	) -> PoolFuture<(TxHash<Self>, Vec<TxHash<Self>>), Self::Error> {
+		Box::pin(futures::future::ready(Err(crate::error::Error::ReplacementUnsupported.into())))
+	}
+
 	/// Returns a future that import a single transaction and starts to watch their progress in the pool.
 	fn submit_and_watch(
 		&self,
@@ -23,6 +23,7 @@
 use sp_std::{result::Result, prelude::*};
 
 use codec::{Encode, Decode};
+use scale_info::TypeInfo;
 use sp_inherents::{InherentIdentifier, InherentData, IsFatalError};
 use sp_runtime::{traits::{Block as BlockT, NumberFor}};
 
@@ -49,7 +50,7 @@ impl IsFatalError for InherentError {
 	}
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, Debug)]
+#[derive(Encode, Decode, Clone, PartialEq, Debug, TypeInfo)]
 pub struct TransactionStorageProof {
 	/// Data chunk that is proved to exist.
 	pub chunk: Vec<u8>,
@@ -65,6 +65,10 @@ pub use sp_consensus_babe::{AuthorityId, Slot, AllowedSlots};
 
 pub type AuraId = sp_consensus_aura::sr25519::AuthorityId;
 
+/// Storage key of the GRANDPA set id returned by the `GrandpaApi`. The authorities are read
+/// from [`sp_finality_grandpa::GRANDPA_AUTHORITIES_KEY`].
+pub const GRANDPA_SET_ID_KEY: &[u8] = b":test_grandpa_set_id";
+
 // Include the WASM binary
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -117,7 +121,7 @@ pub fn native_version() -> NativeVersion {
 }
 
 /// Calls in transactions.
-#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
 pub struct Transfer {
 	pub from: AccountId,
 	pub to: AccountId,
@@ -154,7 +158,7 @@ impl Transfer {
 }
 
 /// Extrinsic for test-runtime.
-#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
 pub enum Extrinsic {
 	AuthoritiesChange(Vec<AuthorityId>),
 	Transfer {
@@ -440,7 +444,7 @@ impl_outer_origin!{
 	pub enum Origin for Runtime where system = frame_system {}
 }
 
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
 pub struct Event;
 
 impl From<frame_system::Event<Runtime>> for Event {
@@ -835,7 +839,19 @@ cfg_if! {
 
 			impl sp_finality_grandpa::GrandpaApi<Block> for Runtime {
 				fn grandpa_authorities() -> sp_finality_grandpa::AuthorityList {
-					Vec::new()
+					sp_io::storage::get(sp_finality_grandpa::GRANDPA_AUTHORITIES_KEY)
+						.and_then(|authorities| {
+							sp_finality_grandpa::VersionedAuthorityList::decode(&mut &authorities[..])
+								.ok()
+						})
+						.map(Into::into)
+						.unwrap_or_default()
+				}
+
+				fn current_set_id() -> sp_finality_grandpa::SetId {
+					sp_io::storage::get(GRANDPA_SET_ID_KEY)
+						.and_then(|set_id| Decode::decode(&mut &set_id[..]).ok())
+						.unwrap_or_default()
 				}
 
 				fn submit_report_equivocation_unsigned_extrinsic(
@@ -0,0 +1,133 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A key-value database persisted in the browser's IndexedDB.
+//!
+//! This is a port of `kvdb-web`, which has no release for `kvdb` 0.10. All reads are served
+//! from an in-memory copy that is loaded from IndexedDB on open, and every write is applied to
+//! both.
+
+mod indexed_db;
+
+use std::{fmt, io};
+use futures::prelude::*;
+use kvdb::{DBTransaction, DBValue, KeyValueDB};
+use kvdb_memorydb::InMemory;
+use send_wrapper::SendWrapper;
+use web_sys::IdbDatabase;
+
+/// An error when opening the database.
+#[derive(Debug)]
+pub enum Error {
+	/// Accessing a Window has failed.
+	WindowNotAvailable,
+	/// IndexedDB is not supported by the browser.
+	NotSupported(String),
+}
+
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::WindowNotAvailable => write!(f, "Accessing a Window has failed"),
+			Error::NotSupported(err) => write!(f, "IndexedDB is not supported by your browser: {}", err),
+		}
+	}
+}
+
+/// Database backed by both IndexedDB and an in-memory copy.
+pub struct Database {
+	columns: u32,
+	in_memory: InMemory,
+	indexed_db: SendWrapper<IdbDatabase>,
+}
+
+impl parity_util_mem::MallocSizeOf for Database {
+	fn size_of(&self, ops: &mut parity_util_mem::MallocSizeOfOps) -> usize {
+		parity_util_mem::MallocSizeOf::size_of(&self.in_memory, ops)
+	}
+}
+
+impl Database {
+	/// Opens the database with the given name and number of columns.
+	pub async fn open(name: &str, columns: u32) -> Result<Database, Error> {
+		// Open the latest version of the database first.
+		let db = indexed_db::open(name, None, columns).await?;
+
+		// Object stores can only be created while upgrading, so if the database has fewer
+		// columns than requested it has to be reopened with a higher version.
+		let db = if columns > db.columns {
+			let next_version = db.version + 1;
+			db.inner.close();
+			indexed_db::open(name, Some(next_version), columns).await?
+		} else {
+			db
+		};
+
+		let in_memory = kvdb_memorydb::create(columns);
+		for column in 0..columns {
+			let mut transaction = DBTransaction::new();
+			let mut cursor = indexed_db::idb_cursor(&db.inner, column);
+			while let Some((key, value)) = cursor.next().await {
+				transaction.put_vec(column, &key, value);
+			}
+			in_memory.write(transaction).expect("Writing to memory never fails; qed");
+		}
+
+		Ok(Database { columns, in_memory, indexed_db: db.inner })
+	}
+}
+
+impl Drop for Database {
+	fn drop(&mut self) {
+		self.indexed_db.close();
+	}
+}
+
+impl KeyValueDB for Database {
+	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<DBValue>> {
+		self.in_memory.get(col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>> {
+		self.in_memory.get_by_prefix(col, prefix)
+	}
+
+	fn write(&self, transaction: DBTransaction) -> io::Result<()> {
+		// The IndexedDB transaction completes asynchronously, reads are served from memory in
+		// the meantime.
+		let _ = indexed_db::idb_commit_transaction(&self.indexed_db, &transaction, self.columns);
+		self.in_memory.write(transaction)
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
+		self.in_memory.iter(col)
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
+		self.in_memory.iter_with_prefix(col, prefix)
+	}
+
+	fn restore(&self, _new_db: &str) -> io::Result<()> {
+		Err(io::Error::new(io::ErrorKind::Other, "Not supported yet"))
+	}
+}
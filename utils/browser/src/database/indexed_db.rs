@@ -0,0 +1,225 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Thin wrappers around the IndexedDB browser API.
+
+use futures::{channel::{mpsc, oneshot}, future, prelude::*};
+use js_sys::{Array, ArrayBuffer, Uint8Array};
+use kvdb::{DBOp, DBTransaction};
+use log::{debug, warn};
+use send_wrapper::SendWrapper;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+	Event, IdbCursorWithValue, IdbDatabase, IdbKeyRange, IdbOpenDbRequest, IdbRequest,
+	IdbTransactionMode,
+};
+
+use super::Error;
+
+/// An opened IndexedDB database.
+pub struct IndexedDb {
+	/// The version the database was opened with.
+	pub version: u32,
+	/// The number of object stores, one per column.
+	pub columns: u32,
+	/// The database handle.
+	pub inner: SendWrapper<IdbDatabase>,
+}
+
+/// Opens the IndexedDB with the given name, version and number of columns.
+///
+/// Without a version the latest one is opened. Missing object stores are only created when
+/// the version is raised.
+pub fn open(
+	name: &str,
+	version: Option<u32>,
+	columns: u32,
+) -> impl Future<Output = Result<IndexedDb, Error>> {
+	let window = match web_sys::window() {
+		Some(window) => window,
+		None => return future::Either::Right(future::err(Error::WindowNotAvailable)),
+	};
+	let idb_factory = match window.indexed_db() {
+		Ok(Some(idb_factory)) => idb_factory,
+		Ok(None) => return future::Either::Right(future::err(
+			Error::NotSupported("no IndexedDB factory".into())
+		)),
+		Err(err) => return future::Either::Right(future::err(
+			Error::NotSupported(format!("{:?}", err))
+		)),
+	};
+
+	let open_request = match version {
+		Some(version) => idb_factory.open_with_u32(name, version),
+		None => idb_factory.open(name),
+	}.expect("TypeError is not possible with Rust; qed");
+
+	try_create_missing_stores(&open_request, columns, version);
+
+	let (tx, rx) = oneshot::channel();
+	let on_success = Closure::once(move |event: &Event| {
+		let result = request_result(event);
+		let db = IdbDatabase::from(result);
+		// JS returns the version as f64.
+		let version = db.version().round() as u32;
+		let columns = db.object_store_names().length();
+
+		// The receiver may have been dropped, there is nothing to do about it.
+		let _ = tx.send(IndexedDb { version, columns, inner: SendWrapper::new(db) });
+	});
+	open_request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+	on_success.forget();
+
+	future::Either::Left(rx.map(|r| Ok(r.expect("The success callback is never dropped; qed"))))
+}
+
+/// Commits a transaction to the IndexedDB.
+///
+/// The returned future resolves once the browser reports the transaction as complete.
+pub fn idb_commit_transaction(
+	idb: &IdbDatabase,
+	transaction: &DBTransaction,
+	columns: u32,
+) -> impl Future<Output = ()> {
+	let idb_transaction = idb.transaction_with_str_sequence_and_mode(
+		&store_names_js(columns),
+		IdbTransactionMode::Readwrite,
+	).expect("The provided mode and store names are valid; qed");
+
+	let object_stores = (0..columns)
+		.map(|n| idb_transaction.object_store(&store_name(n))
+			.expect("Object stores are created when opening the database; qed"))
+		.collect::<Vec<_>>();
+
+	for op in &transaction.ops {
+		let col = op.col();
+		let store = &object_stores[col as usize];
+		let res = match op {
+			DBOp::Insert { key, value, .. } => store.put_with_key(
+				&Uint8Array::from(value.as_ref()),
+				&Uint8Array::from(key.as_ref()),
+			).map(|_| ()),
+			DBOp::Delete { key, .. } => store.delete(&Uint8Array::from(key.as_ref())).map(|_| ()),
+			DBOp::DeletePrefix { prefix, .. } => {
+				let lower = Uint8Array::from(prefix.as_ref());
+				let range = match kvdb::end_prefix(prefix) {
+					Some(end) => IdbKeyRange::bound_with_lower_open_and_upper_open(
+						&lower,
+						&Uint8Array::from(&end[..]),
+						false,
+						true,
+					),
+					None => IdbKeyRange::lower_bound(&lower),
+				};
+				range.and_then(|range| store.delete(&range)).map(|_| ())
+			},
+		};
+		if let Err(err) = res {
+			warn!("Error writing to IndexedDB column {}: {:?}", col, err);
+		}
+	}
+
+	let (tx, rx) = oneshot::channel();
+	let on_complete = Closure::once(move || {
+		let _ = tx.send(());
+	});
+	idb_transaction.set_oncomplete(Some(on_complete.as_ref().unchecked_ref()));
+	on_complete.forget();
+
+	let on_error = Closure::once(move || {
+		warn!("Failed to commit a transaction to IndexedDB");
+	});
+	idb_transaction.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+	on_error.forget();
+
+	rx.map(|_| ())
+}
+
+/// Returns a stream of all key/value pairs in the given column.
+pub fn idb_cursor(idb: &IdbDatabase, col: u32) -> impl Stream<Item = (Vec<u8>, Vec<u8>)> {
+	let store_name = store_name(col);
+	let idb_transaction = idb.transaction_with_str(&store_name)
+		.expect("Object stores are created when opening the database; qed");
+	let store = idb_transaction.object_store(&store_name)
+		.expect("Opening an existing store doesn't fail; qed");
+	let cursor = store.open_cursor().expect("Opening a cursor doesn't fail; qed");
+
+	let (tx, rx) = mpsc::unbounded();
+	let on_cursor = Closure::wrap(Box::new(move |event: Event| {
+		let result = request_result(&event);
+		// The result is `null` once the cursor has run past the last entry.
+		if result.is_null() {
+			tx.close_channel();
+			return
+		}
+
+		let cursor: &IdbCursorWithValue = result.unchecked_ref();
+		match (cursor.key(), cursor.value()) {
+			(Ok(key), Ok(value)) => {
+				let key = Uint8Array::new(key.unchecked_ref::<ArrayBuffer>()).to_vec();
+				let value = value.unchecked_ref::<Uint8Array>().to_vec();
+				if let Err(err) = tx.unbounded_send((key, value)) {
+					warn!("Error sending an IndexedDB entry: {:?}", err);
+				}
+				if let Err(err) = cursor.continue_() {
+					warn!("Error advancing an IndexedDB cursor: {:?}", err);
+					tx.close_channel();
+				}
+			},
+			_ => tx.close_channel(),
+		}
+	}) as Box<dyn FnMut(_)>);
+	cursor.set_onsuccess(Some(on_cursor.as_ref().unchecked_ref()));
+	on_cursor.forget();
+
+	rx
+}
+
+/// Creates the object stores the database is missing when its version is raised.
+fn try_create_missing_stores(req: &IdbOpenDbRequest, columns: u32, version: Option<u32>) {
+	let on_upgrade_needed = Closure::once(move |event: &Event| {
+		debug!("Upgrading or creating the database to version {:?}, columns {}", version, columns);
+		let result = request_result(event);
+		let db: &IdbDatabase = result.unchecked_ref();
+
+		let previous_columns = db.object_store_names().length();
+		for name in (previous_columns..columns).map(store_name) {
+			if let Err(err) = db.create_object_store(&name) {
+				debug!("Error creating object store {}: {:?}", name, err);
+			}
+		}
+	});
+	req.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+	on_upgrade_needed.forget();
+}
+
+/// Returns the result of the request that is the target of the given event.
+fn request_result(event: &Event) -> JsValue {
+	event.target()
+		.expect("IndexedDB events have a target; qed")
+		.unchecked_ref::<IdbRequest>()
+		.result()
+		.expect("IndexedDB requests have a result once they succeed; qed")
+}
+
+fn store_name(col: u32) -> String {
+	format!("col{}", col)
+}
+
+fn store_names_js(columns: u32) -> Array {
+	(0..columns).map(|col| JsValue::from(store_name(col))).collect()
+}
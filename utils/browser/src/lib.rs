@@ -33,6 +33,8 @@ use std::pin::Pin;
 use sc_chain_spec::Extension;
 use libp2p_wasm_ext::{ExtTransport, ffi};
 
+mod database;
+
 pub use console_error_panic_hook::set_once as set_console_error_panic_hook;
 
 /// Initialize the logger and return a `TelemetryWorker` and a wasm `ExtTransport`.
@@ -78,7 +80,7 @@ where
 		role: Role::Light,
 		database: {
 			info!("Opening Indexed DB database '{}'...", name);
-			let db = kvdb_web::Database::open(name, 10).await?;
+			let db = database::Database::open(&name, 10).await?;
 
 			DatabaseConfig::Custom(sp_database::as_database(db))
 		},